@@ -1,13 +1,29 @@
-use std::{fmt, fmt::Formatter};
+use std::{borrow::Cow, fmt, fmt::Formatter};
 
 /// An attribute in the class file, which can belong to a class, field, method, or code block.
-#[derive(Debug, Default, PartialEq)]
-pub struct Attribute {
-    pub name: String,
-    pub bytes: Vec<u8>,
+///
+/// The attributes that the reader understands are parsed in dedicated fields of the structures
+/// they belong to (e.g. [crate::class_file_method::ClassFileMethod::thrown_exceptions]);
+/// all the others, including those defined by newer versions of the JVM specs or by
+/// third-party tools, are preserved as-is in the `attributes` field of those structures, in the
+/// order in which they appear in the class file, so that they are not lost when transforming
+/// a class.
+#[derive(Debug, Default, PartialEq, Clone)]
+pub struct Attribute<'a> {
+    pub name: Cow<'a, str>,
+    pub bytes: Cow<'a, [u8]>,
 }
 
-impl fmt::Display for Attribute {
+impl<'a> Attribute<'a> {
+    pub fn into_owned(self) -> Attribute<'static> {
+        Attribute {
+            name: Cow::Owned(self.name.into_owned()),
+            bytes: Cow::Owned(self.bytes.into_owned()),
+        }
+    }
+}
+
+impl<'a> fmt::Display for Attribute<'a> {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         write!(f, "{} (data = {} bytes)", self.name, self.bytes.len())
     }
@@ -0,0 +1,10 @@
+/// An entry of the `BootstrapMethods` attribute of a class, referenced by the
+/// `invokedynamic` call sites. See
+/// https://docs.oracle.com/javase/specs/jvms/se8/html/jvms-4.html#jvms-4.7.23
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct BootstrapMethod {
+    /// Index of the `MethodHandle` constant of the bootstrap method
+    pub method_ref: u16,
+    /// Indexes of the constants passed as static arguments to the bootstrap method
+    pub arguments: Vec<u16>,
+}
@@ -1,3 +1,5 @@
+use std::borrow::Cow;
+
 use cesu8::from_java_cesu8;
 use thiserror::Error;
 
@@ -72,10 +74,11 @@ impl<'a> Buffer<'a> {
             .map(|bytes| f64::from_be_bytes(bytes.try_into().unwrap()))
     }
 
-    pub fn read_utf8(&mut self, len: usize) -> Result<String> {
+    /// Reads a string, borrowing it from the underlying data if it does not need any decoding,
+    /// i.e. if it does not contain any surrogate pairs or null characters
+    pub fn read_utf8(&mut self, len: usize) -> Result<Cow<'a, str>> {
         self.advance(len)
             .and_then(|bytes| from_java_cesu8(bytes).map_err(|_| BufferError::InvalidCesu8String))
-            .map(|cow_string| cow_string.into_owned())
     }
 
     pub fn read_bytes(&mut self, len: usize) -> Result<&'a [u8]> {
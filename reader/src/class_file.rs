@@ -1,27 +1,74 @@
-use std::fmt;
+use std::{borrow::Cow, fmt};
 
 use crate::{
-    class_access_flags::ClassAccessFlags, class_file_field::ClassFileField,
-    class_file_method::ClassFileMethod, class_file_version::ClassFileVersion,
-    constant_pool::ConstantPool,
+    attribute::Attribute, bootstrap_method::BootstrapMethod, class_access_flags::ClassAccessFlags,
+    class_file_field::ClassFileField, class_file_method::ClassFileMethod,
+    class_file_version::ClassFileVersion, constant_pool::ConstantPool,
 };
 
 /// Represents the content of a .class file.
+///
+/// Strings and byte arrays such as the methods' code are borrowed from the buffer the class
+/// was read from, whenever possible, to avoid copying them. Use [ClassFile::into_owned] to get
+/// a copy that does not depend on the buffer.
 #[derive(Debug, Default)]
-pub struct ClassFile {
+pub struct ClassFile<'a> {
     pub version: ClassFileVersion,
-    pub constants: ConstantPool,
+    pub constants: ConstantPool<'a>,
     pub flags: ClassAccessFlags,
-    pub name: String,
-    pub superclass: Option<String>,
-    pub interfaces: Vec<String>,
-    pub fields: Vec<ClassFileField>,
-    pub methods: Vec<ClassFileMethod>,
+    pub name: Cow<'a, str>,
+    pub superclass: Option<Cow<'a, str>>,
+    pub interfaces: Vec<Cow<'a, str>>,
+    pub fields: Vec<ClassFileField<'a>>,
+    pub methods: Vec<ClassFileMethod<'a>>,
     pub deprecated: bool,
-    pub source_file: Option<String>,
+    pub source_file: Option<Cow<'a, str>>,
+    /// The host of the nest the class belongs to, from the `NestHost` attribute. The members
+    /// of a nest, i.e. a class and its nested classes, can access each other's private members.
+    pub nest_host: Option<Cow<'a, str>>,
+    /// Bootstrap methods referenced by the `invokedynamic` instructions
+    pub bootstrap_methods: Vec<BootstrapMethod>,
+    /// Attributes of the class not interpreted by the reader
+    pub attributes: Vec<Attribute<'a>>,
 }
 
-impl fmt::Display for ClassFile {
+impl<'a> ClassFile<'a> {
+    pub fn into_owned(self) -> ClassFile<'static> {
+        ClassFile {
+            version: self.version,
+            constants: self.constants.into_owned(),
+            flags: self.flags,
+            name: Cow::Owned(self.name.into_owned()),
+            superclass: self.superclass.map(|name| Cow::Owned(name.into_owned())),
+            interfaces: self
+                .interfaces
+                .into_iter()
+                .map(|name| Cow::Owned(name.into_owned()))
+                .collect(),
+            fields: self
+                .fields
+                .into_iter()
+                .map(ClassFileField::into_owned)
+                .collect(),
+            methods: self
+                .methods
+                .into_iter()
+                .map(ClassFileMethod::into_owned)
+                .collect(),
+            deprecated: self.deprecated,
+            source_file: self.source_file.map(|name| Cow::Owned(name.into_owned())),
+            nest_host: self.nest_host.map(|name| Cow::Owned(name.into_owned())),
+            bootstrap_methods: self.bootstrap_methods,
+            attributes: self
+                .attributes
+                .into_iter()
+                .map(Attribute::into_owned)
+                .collect(),
+        }
+    }
+}
+
+impl<'a> fmt::Display for ClassFile<'a> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "Class {} ", self.name,)?;
         if let Some(superclass) = self.superclass.as_ref() {
@@ -35,6 +82,8 @@ impl fmt::Display for ClassFile {
             self.flags, self.deprecated
         )?;
         writeln!(f, "interfaces: {:?}", self.interfaces)?;
+        writeln!(f, "bootstrap_methods: {:?}", self.bootstrap_methods)?;
+        writeln!(f, "raw_attributes: {:?}", self.attributes)?;
         writeln!(f, "fields:")?;
         for field in self.fields.iter() {
             writeln!(f, "  - {field}")?;
@@ -1,19 +1,38 @@
-use std::{fmt, fmt::Formatter};
+use std::{borrow::Cow, fmt, fmt::Formatter};
 
-use crate::{field_flags::FieldFlags, field_type::FieldType};
+use crate::{attribute::Attribute, field_flags::FieldFlags, field_type::FieldType};
 
 /// Models a field in a class
 #[derive(Debug, PartialEq)]
-pub struct ClassFileField {
+pub struct ClassFileField<'a> {
     pub flags: FieldFlags,
-    pub name: String,
+    pub name: Cow<'a, str>,
     pub type_descriptor: FieldType,
     /// Fields which model a constant (final) will have an attribute specifying the value
-    pub constant_value: Option<FieldConstantValue>,
+    pub constant_value: Option<FieldConstantValue<'a>>,
     pub deprecated: bool,
+    /// Attributes of the field not interpreted by the reader
+    pub attributes: Vec<Attribute<'a>>,
 }
 
-impl fmt::Display for ClassFileField {
+impl<'a> ClassFileField<'a> {
+    pub fn into_owned(self) -> ClassFileField<'static> {
+        ClassFileField {
+            flags: self.flags,
+            name: Cow::Owned(self.name.into_owned()),
+            type_descriptor: self.type_descriptor,
+            constant_value: self.constant_value.map(FieldConstantValue::into_owned),
+            deprecated: self.deprecated,
+            attributes: self
+                .attributes
+                .into_iter()
+                .map(Attribute::into_owned)
+                .collect(),
+        }
+    }
+}
+
+impl<'a> fmt::Display for ClassFileField<'a> {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         write!(
             f,
@@ -29,10 +48,22 @@ impl fmt::Display for ClassFileField {
 
 /// Possible constant values of a field
 #[derive(Debug, PartialEq, strum_macros::Display)]
-pub enum FieldConstantValue {
+pub enum FieldConstantValue<'a> {
     Int(i32),
     Float(f32),
     Long(i64),
     Double(f64),
-    String(String),
+    String(Cow<'a, str>),
+}
+
+impl<'a> FieldConstantValue<'a> {
+    pub fn into_owned(self) -> FieldConstantValue<'static> {
+        match self {
+            FieldConstantValue::Int(v) => FieldConstantValue::Int(v),
+            FieldConstantValue::Float(v) => FieldConstantValue::Float(v),
+            FieldConstantValue::Long(v) => FieldConstantValue::Long(v),
+            FieldConstantValue::Double(v) => FieldConstantValue::Double(v),
+            FieldConstantValue::String(v) => FieldConstantValue::String(Cow::Owned(v.into_owned())),
+        }
+    }
 }
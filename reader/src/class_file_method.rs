@@ -1,4 +1,4 @@
-use std::{fmt, fmt::Formatter};
+use std::{borrow::Cow, fmt, fmt::Formatter};
 
 use crate::{
     attribute::Attribute,
@@ -8,27 +8,27 @@ use crate::{
     line_number_table::LineNumberTable,
     method_descriptor::MethodDescriptor,
     method_flags::MethodFlags,
+    stack_map_table::StackMapTable,
 };
 
 /// Models a method in a class
 #[derive(Debug, PartialEq)]
-pub struct ClassFileMethod {
+pub struct ClassFileMethod<'a> {
     pub flags: MethodFlags,
-    pub name: String,
+    pub name: Cow<'a, str>,
     /// The type descriptor in the internal JVM form, i.e. something like (L)I in the unparsed form
-    pub type_descriptor: String,
+    pub type_descriptor: Cow<'a, str>,
     /// Parsed form of the method descriptor
     pub parsed_type_descriptor: MethodDescriptor,
-    /// Generic attributes of the method
-    // TODO: replace with some proper struct
-    pub attributes: Vec<Attribute>,
-    pub code: Option<ClassFileMethodCode>,
+    /// Attributes of the method not interpreted by the reader
+    pub attributes: Vec<Attribute<'a>>,
+    pub code: Option<ClassFileMethodCode<'a>>,
     pub deprecated: bool,
     /// List of exceptions in the `throws` clause of the method
-    pub thrown_exceptions: Vec<String>,
+    pub thrown_exceptions: Vec<Cow<'a, str>>,
 }
 
-impl fmt::Display for ClassFileMethod {
+impl<'a> fmt::Display for ClassFileMethod<'a> {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         writeln!(
             f,
@@ -46,11 +46,36 @@ impl fmt::Display for ClassFileMethod {
     }
 }
 
-impl ClassFileMethod {
+impl<'a> ClassFileMethod<'a> {
+    pub fn into_owned(self) -> ClassFileMethod<'static> {
+        ClassFileMethod {
+            flags: self.flags,
+            name: Cow::Owned(self.name.into_owned()),
+            type_descriptor: Cow::Owned(self.type_descriptor.into_owned()),
+            parsed_type_descriptor: self.parsed_type_descriptor,
+            attributes: self
+                .attributes
+                .into_iter()
+                .map(Attribute::into_owned)
+                .collect(),
+            code: self.code.map(ClassFileMethodCode::into_owned),
+            deprecated: self.deprecated,
+            thrown_exceptions: self
+                .thrown_exceptions
+                .into_iter()
+                .map(|class_name| Cow::Owned(class_name.into_owned()))
+                .collect(),
+        }
+    }
+
     pub fn is_static(&self) -> bool {
         self.flags.contains(MethodFlags::STATIC)
     }
 
+    pub fn is_synchronized(&self) -> bool {
+        self.flags.contains(MethodFlags::SYNCHRONIZED)
+    }
+
     pub fn is_native(&self) -> bool {
         self.flags.contains(MethodFlags::NATIVE)
     }
@@ -75,27 +100,45 @@ impl ClassFileMethod {
 
 /// Code of a given method
 #[derive(Debug, Default, PartialEq)]
-pub struct ClassFileMethodCode {
+pub struct ClassFileMethodCode<'a> {
     /// Maximum depth of the stack at any time
     pub max_stack: u16,
     /// Number of local variables used by the method
     pub max_locals: u16,
     /// Raw bytecode
-    pub code: Vec<u8>,
-    pub exception_table: ExceptionTable,
+    pub code: Cow<'a, [u8]>,
+    pub exception_table: ExceptionTable<'a>,
     pub line_number_table: Option<LineNumberTable>,
+    pub stack_map_table: Option<StackMapTable<'a>>,
 
-    /// Generic unmapped attributes of the code
-    // TODO: replace with some proper struct
-    pub attributes: Vec<Attribute>,
+    /// Attributes of the code not interpreted by the reader
+    pub attributes: Vec<Attribute<'a>>,
+}
+
+impl<'a> ClassFileMethodCode<'a> {
+    pub fn into_owned(self) -> ClassFileMethodCode<'static> {
+        ClassFileMethodCode {
+            max_stack: self.max_stack,
+            max_locals: self.max_locals,
+            code: Cow::Owned(self.code.into_owned()),
+            exception_table: self.exception_table.into_owned(),
+            line_number_table: self.line_number_table,
+            stack_map_table: self.stack_map_table.map(StackMapTable::into_owned),
+            attributes: self
+                .attributes
+                .into_iter()
+                .map(Attribute::into_owned)
+                .collect(),
+        }
+    }
 }
 
-impl fmt::Display for ClassFileMethodCode {
+impl<'a> fmt::Display for ClassFileMethodCode<'a> {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         writeln!(
             f,
-            "max_stack = {}, max_locals = {}, exception_table = {:?}, line_number_table: {:?}, attributes = {:?}, instructions:",
-            self.max_stack, self.max_locals, self.exception_table, self.line_number_table, self.attributes,
+            "max_stack = {}, max_locals = {}, exception_table = {:?}, line_number_table: {:?}, stack_map_table: {:?}, attributes = {:?}, instructions:",
+            self.max_stack, self.max_locals, self.exception_table, self.line_number_table, self.stack_map_table, self.attributes,
         )?;
 
         let instructions = Instruction::parse_instructions(&self.code);
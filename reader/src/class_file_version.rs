@@ -1,7 +1,7 @@
 use crate::class_reader_error::{ClassReaderError, Result};
 
-/// Versions of the JVM class file format.
-#[derive(Debug, PartialEq, Default, strum_macros::Display)]
+/// Versions of the JVM class file format, in chronological order
+#[derive(Debug, PartialEq, PartialOrd, Default, strum_macros::Display)]
 #[allow(dead_code)]
 pub enum ClassFileVersion {
     Jdk1_1,
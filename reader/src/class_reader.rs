@@ -1,8 +1,11 @@
+use std::borrow::Cow;
+
 use log::warn;
 use result::prelude::*;
 
 use crate::{
     attribute::Attribute,
+    bootstrap_method::BootstrapMethod,
     class_access_flags::ClassAccessFlags,
     class_file::ClassFile,
     class_file_field::{ClassFileField, FieldConstantValue},
@@ -17,7 +20,9 @@ use crate::{
     line_number_table::{LineNumberTable, LineNumberTableEntry},
     method_descriptor::MethodDescriptor,
     method_flags::MethodFlags,
+    method_handle_kind::MethodHandleKind,
     program_counter::ProgramCounter,
+    stack_map_table::{StackMapFrame, StackMapFrameKind, StackMapTable, VerificationType},
 };
 use crate::{buffer::Buffer, type_conversion::ToUsizeSafe};
 
@@ -26,19 +31,46 @@ use crate::{buffer::Buffer, type_conversion::ToUsizeSafe};
 struct ClassFileReader<'a> {
     buffer: Buffer<'a>,
     /// The class being read, created empty and updated in place
-    class_file: ClassFile,
+    class_file: ClassFile<'a>,
+}
+
+/// An attribute as read from the buffer, before we decide whether to parse or to store it
+struct RawAttribute<'a> {
+    name: Cow<'a, str>,
+    bytes: &'a [u8],
+}
+
+impl<'a> From<RawAttribute<'a>> for Attribute<'a> {
+    fn from(raw_attribute: RawAttribute<'a>) -> Self {
+        Attribute {
+            name: raw_attribute.name,
+            bytes: Cow::Borrowed(raw_attribute.bytes),
+        }
+    }
+}
+
+/// Returns the attributes whose name is not among the interpreted ones, preserving their order
+fn uninterpreted_attributes<'a>(
+    raw_attributes: Vec<RawAttribute<'a>>,
+    interpreted: &[&str],
+) -> Vec<Attribute<'a>> {
+    raw_attributes
+        .into_iter()
+        .filter(|attr| !interpreted.contains(&attr.name.as_ref()))
+        .map(Attribute::from)
+        .collect()
 }
 
 /// Reference: https://docs.oracle.com/javase/specs/jvms/se7/html/jvms-4.html
 impl<'a> ClassFileReader<'a> {
-    fn new(data: &[u8]) -> ClassFileReader {
+    fn new(data: &'a [u8]) -> ClassFileReader<'a> {
         ClassFileReader {
             buffer: Buffer::new(data),
             class_file: Default::default(),
         }
     }
 
-    fn read(mut self) -> Result<ClassFile> {
+    fn read(mut self) -> Result<ClassFile<'a>> {
         self.check_magic_number()?;
         self.read_version()?;
         self.read_constants()?;
@@ -94,6 +126,9 @@ impl<'a> ClassFileReader<'a> {
                 10 => self.read_method_reference_constant()?,
                 11 => self.read_interface_method_reference_constant()?,
                 12 => self.read_name_and_type_constant()?,
+                15 => self.read_method_handle_constant()?,
+                16 => self.read_method_type_constant()?,
+                18 => self.read_invoke_dynamic_constant()?,
                 // For newer versions of java, there are more constant types
                 _ => {
                     warn!("invalid entry in constant pool at index {} tag {}", i, tag);
@@ -110,7 +145,7 @@ impl<'a> ClassFileReader<'a> {
         Ok(())
     }
 
-    fn read_utf8_constant(&mut self) -> Result<ConstantPoolEntry> {
+    fn read_utf8_constant(&mut self) -> Result<ConstantPoolEntry<'a>> {
         let len = self.buffer.read_u16()?;
         self.buffer
             .read_utf8(len as usize)
@@ -118,45 +153,45 @@ impl<'a> ClassFileReader<'a> {
             .map_err(|err| err.into())
     }
 
-    fn read_int_constant(&mut self) -> Result<ConstantPoolEntry> {
+    fn read_int_constant(&mut self) -> Result<ConstantPoolEntry<'a>> {
         self.buffer
             .read_i32()
             .map(ConstantPoolEntry::Integer)
             .map_err(|err| err.into())
     }
 
-    fn read_float_constant(&mut self) -> Result<ConstantPoolEntry> {
+    fn read_float_constant(&mut self) -> Result<ConstantPoolEntry<'a>> {
         self.buffer
             .read_f32()
             .map(ConstantPoolEntry::Float)
             .map_err(|err| err.into())
     }
 
-    fn read_long_constant(&mut self) -> Result<ConstantPoolEntry> {
+    fn read_long_constant(&mut self) -> Result<ConstantPoolEntry<'a>> {
         self.buffer
             .read_i64()
             .map(ConstantPoolEntry::Long)
             .map_err(|err| err.into())
     }
 
-    fn read_double_constant(&mut self) -> Result<ConstantPoolEntry> {
+    fn read_double_constant(&mut self) -> Result<ConstantPoolEntry<'a>> {
         self.buffer
             .read_f64()
             .map(ConstantPoolEntry::Double)
             .map_err(|err| err.into())
     }
 
-    fn read_class_reference_constant(&mut self) -> Result<ConstantPoolEntry> {
+    fn read_class_reference_constant(&mut self) -> Result<ConstantPoolEntry<'a>> {
         let fqn_string_index = self.buffer.read_u16()?;
         Ok(ConstantPoolEntry::ClassReference(fqn_string_index))
     }
 
-    fn read_string_reference_constant(&mut self) -> Result<ConstantPoolEntry> {
+    fn read_string_reference_constant(&mut self) -> Result<ConstantPoolEntry<'a>> {
         let string_index = self.buffer.read_u16()?;
         Ok(ConstantPoolEntry::StringReference(string_index))
     }
 
-    fn read_method_reference_constant(&mut self) -> Result<ConstantPoolEntry> {
+    fn read_method_reference_constant(&mut self) -> Result<ConstantPoolEntry<'a>> {
         let class_reference = self.buffer.read_u16()?;
         let name_and_type = self.buffer.read_u16()?;
         Ok(ConstantPoolEntry::MethodReference(
@@ -165,7 +200,7 @@ impl<'a> ClassFileReader<'a> {
         ))
     }
 
-    fn read_interface_method_reference_constant(&mut self) -> Result<ConstantPoolEntry> {
+    fn read_interface_method_reference_constant(&mut self) -> Result<ConstantPoolEntry<'a>> {
         let class_reference = self.buffer.read_u16()?;
         let name_and_type = self.buffer.read_u16()?;
         Ok(ConstantPoolEntry::InterfaceMethodReference(
@@ -174,7 +209,7 @@ impl<'a> ClassFileReader<'a> {
         ))
     }
 
-    fn read_field_reference_constant(&mut self) -> Result<ConstantPoolEntry> {
+    fn read_field_reference_constant(&mut self) -> Result<ConstantPoolEntry<'a>> {
         let class_reference = self.buffer.read_u16()?;
         let name_and_type = self.buffer.read_u16()?;
         Ok(ConstantPoolEntry::FieldReference(
@@ -183,7 +218,7 @@ impl<'a> ClassFileReader<'a> {
         ))
     }
 
-    fn read_name_and_type_constant(&mut self) -> Result<ConstantPoolEntry> {
+    fn read_name_and_type_constant(&mut self) -> Result<ConstantPoolEntry<'a>> {
         let name = self.buffer.read_u16()?;
         let type_descriptor = self.buffer.read_u16()?;
         Ok(ConstantPoolEntry::NameAndTypeDescriptor(
@@ -192,6 +227,31 @@ impl<'a> ClassFileReader<'a> {
         ))
     }
 
+    fn read_method_handle_constant(&mut self) -> Result<ConstantPoolEntry<'a>> {
+        let reference_kind = self.buffer.read_u8()?;
+        let reference_index = self.buffer.read_u16()?;
+        match MethodHandleKind::from_reference_kind(reference_kind) {
+            Some(kind) => Ok(ConstantPoolEntry::MethodHandle(kind, reference_index)),
+            None => Err(ClassReaderError::invalid_class_data(format!(
+                "invalid method handle kind: {reference_kind}"
+            ))),
+        }
+    }
+
+    fn read_method_type_constant(&mut self) -> Result<ConstantPoolEntry<'a>> {
+        let descriptor = self.buffer.read_u16()?;
+        Ok(ConstantPoolEntry::MethodType(descriptor))
+    }
+
+    fn read_invoke_dynamic_constant(&mut self) -> Result<ConstantPoolEntry<'a>> {
+        let bootstrap_method_index = self.buffer.read_u16()?;
+        let name_and_type = self.buffer.read_u16()?;
+        Ok(ConstantPoolEntry::InvokeDynamic(
+            bootstrap_method_index,
+            name_and_type,
+        ))
+    }
+
     fn read_access_flags(&mut self) -> Result<()> {
         let num = self.buffer.read_u16()?;
         match ClassAccessFlags::from_bits(num) {
@@ -205,12 +265,12 @@ impl<'a> ClassFileReader<'a> {
         }
     }
 
-    fn read_class_reference(&mut self) -> Result<String> {
+    fn read_class_reference(&mut self) -> Result<Cow<'a, str>> {
         let class_constant_idx = self.buffer.read_u16()?;
         self.read_string_reference(class_constant_idx)
     }
 
-    fn read_class_reference_optional(&mut self) -> Result<Option<String>> {
+    fn read_class_reference_optional(&mut self) -> Result<Option<Cow<'a, str>>> {
         let super_constant_idx = self.buffer.read_u16()?;
         if super_constant_idx == 0 {
             Ok(None)
@@ -219,19 +279,22 @@ impl<'a> ClassFileReader<'a> {
         }
     }
 
-    fn read_string_reference(&self, index: u16) -> Result<String> {
+    fn read_string_reference(&self, index: u16) -> Result<Cow<'a, str>> {
         Self::read_string_reference_from(&self.class_file.constants, index)
     }
 
-    fn read_string_reference_from(constants_pool: &ConstantPool, index: u16) -> Result<String> {
-        constants_pool.text_of(index).map_err(|err| err.into())
+    fn read_string_reference_from(
+        constants_pool: &ConstantPool<'a>,
+        index: u16,
+    ) -> Result<Cow<'a, str>> {
+        constants_pool.string_of(index).map_err(|err| err.into())
     }
 
     fn read_interfaces(&mut self) -> Result<()> {
         let interfaces_count = self.buffer.read_u16()?;
         self.class_file.interfaces = (0..interfaces_count)
             .map(|_| self.read_class_reference())
-            .collect::<Result<Vec<String>>>()?;
+            .collect::<Result<Vec<Cow<'a, str>>>>()?;
         Ok(())
     }
 
@@ -239,11 +302,11 @@ impl<'a> ClassFileReader<'a> {
         let fields_count = self.buffer.read_u16()?;
         self.class_file.fields = (0..fields_count)
             .map(|_| self.read_field())
-            .collect::<Result<Vec<ClassFileField>>>()?;
+            .collect::<Result<Vec<ClassFileField<'a>>>>()?;
         Ok(())
     }
 
-    fn read_field(&mut self) -> Result<ClassFileField> {
+    fn read_field(&mut self) -> Result<ClassFileField<'a>> {
         let flags = self.read_field_flags()?;
         let name_constant_index = self.buffer.read_u16()?;
         let name = self.read_string_reference(name_constant_index)?;
@@ -261,6 +324,7 @@ impl<'a> ClassFileReader<'a> {
             type_descriptor,
             constant_value,
             deprecated,
+            attributes: uninterpreted_attributes(raw_attributes, &["ConstantValue", "Deprecated"]),
         })
     }
 
@@ -276,8 +340,8 @@ impl<'a> ClassFileReader<'a> {
 
     fn extract_constant_value(
         &self,
-        raw_attributes: &[Attribute],
-    ) -> Result<Option<FieldConstantValue>> {
+        raw_attributes: &[RawAttribute<'a>],
+    ) -> Result<Option<FieldConstantValue<'a>>> {
         raw_attributes
             .iter()
             .filter(|attr| attr.name == "ConstantValue")
@@ -287,8 +351,7 @@ impl<'a> ClassFileReader<'a> {
                         "invalid attribute of type ConstantValue".to_string(),
                     ))
                 } else {
-                    let attribute_bytes: &[u8] = &attr.bytes;
-                    let constant_index = u16::from_be_bytes(attribute_bytes.try_into().unwrap());
+                    let constant_index = u16::from_be_bytes(attr.bytes.try_into().unwrap());
                     self.class_file
                         .constants
                         .get(constant_index)
@@ -312,7 +375,7 @@ impl<'a> ClassFileReader<'a> {
             .invert()
     }
 
-    fn search_deprecated_attribute(&self, raw_attributes: &[Attribute]) -> bool {
+    fn search_deprecated_attribute(&self, raw_attributes: &[RawAttribute<'a>]) -> bool {
         raw_attributes.iter().any(|attr| attr.name == "Deprecated")
     }
 
@@ -320,11 +383,11 @@ impl<'a> ClassFileReader<'a> {
         let methods_count = self.buffer.read_u16()?;
         self.class_file.methods = (0..methods_count)
             .map(|_| self.read_method())
-            .collect::<Result<Vec<ClassFileMethod>>>()?;
+            .collect::<Result<Vec<ClassFileMethod<'a>>>>()?;
         Ok(())
     }
 
-    fn read_method(&mut self) -> Result<ClassFileMethod> {
+    fn read_method(&mut self) -> Result<ClassFileMethod<'a>> {
         let flags = self.read_method_flags()?;
         let name_constant_index = self.buffer.read_u16()?;
         let name = self.read_string_reference(name_constant_index)?;
@@ -345,7 +408,10 @@ impl<'a> ClassFileReader<'a> {
             name,
             type_descriptor,
             parsed_type_descriptor,
-            attributes: raw_attributes,
+            attributes: uninterpreted_attributes(
+                raw_attributes,
+                &["Code", "Deprecated", "Exceptions"],
+            ),
             code,
             deprecated,
             thrown_exceptions,
@@ -364,22 +430,23 @@ impl<'a> ClassFileReader<'a> {
 
     fn extract_code(
         &self,
-        raw_attributes: &[Attribute],
+        raw_attributes: &[RawAttribute<'a>],
         name: &str,
-    ) -> Result<ClassFileMethodCode> {
+    ) -> Result<ClassFileMethodCode<'a>> {
         raw_attributes
             .iter()
             .filter(|attr| attr.name == "Code")
             .map(|attr| {
-                let mut buf = Buffer::new(&attr.bytes);
+                let mut buf = Buffer::new(attr.bytes);
                 let max_stack = buf.read_u16()?;
                 let max_locals = buf.read_u16()?;
                 let code_length = buf.read_u32()?.into_usize_safe();
-                let code = Vec::from(buf.read_bytes(code_length)?);
+                let code = Cow::Borrowed(buf.read_bytes(code_length)?);
                 let exception_table = self.read_exception_table(&mut buf)?;
                 let attributes =
                     Self::read_raw_attributes_from(&self.class_file.constants, &mut buf)?;
                 let line_number_table = self.extract_line_number_table(&attributes)?;
+                let stack_map_table = self.extract_stack_map_table(&attributes)?;
 
                 Result::<ClassFileMethodCode>::Ok(ClassFileMethodCode {
                     max_stack,
@@ -387,7 +454,11 @@ impl<'a> ClassFileReader<'a> {
                     code,
                     exception_table,
                     line_number_table,
-                    attributes,
+                    stack_map_table,
+                    attributes: uninterpreted_attributes(
+                        attributes,
+                        &["LineNumberTable", "StackMapTable"],
+                    ),
                 })
             })
             .next()
@@ -399,7 +470,7 @@ impl<'a> ClassFileReader<'a> {
             })
     }
 
-    fn read_exception_table(&self, buf: &mut Buffer) -> Result<ExceptionTable> {
+    fn read_exception_table(&self, buf: &mut Buffer<'a>) -> Result<ExceptionTable<'a>> {
         let exception_table_length = buf.read_u16()?.into_usize_safe();
         let mut entries: Vec<ExceptionTableEntry> = Vec::with_capacity(exception_table_length / 8);
         for _ in 0..exception_table_length {
@@ -413,8 +484,8 @@ impl<'a> ClassFileReader<'a> {
                 Some(self.read_string_reference(catch_class_constant)?)
             };
             entries.push(ExceptionTableEntry {
-                range: ProgramCounter(start_pc)..ProgramCounter(end_pc),
-                handler_pc: ProgramCounter(handler_pc),
+                range: ProgramCounter(u32::from(start_pc))..ProgramCounter(u32::from(end_pc)),
+                handler_pc: ProgramCounter(u32::from(handler_pc)),
                 catch_class,
             })
         }
@@ -423,20 +494,20 @@ impl<'a> ClassFileReader<'a> {
 
     fn extract_line_number_table(
         &self,
-        raw_attributes: &[Attribute],
+        raw_attributes: &[RawAttribute<'a>],
     ) -> Result<Option<LineNumberTable>> {
         raw_attributes
             .iter()
             .find(|attr| attr.name == "LineNumberTable")
             .map(|attr| {
-                let mut buf = Buffer::new(&attr.bytes);
+                let mut buf = Buffer::new(attr.bytes);
                 let num_entries = buf.read_u16()?.into_usize_safe();
                 let mut entries = Vec::with_capacity(num_entries);
                 for _ in 0..num_entries {
                     let program_counter = buf.read_u16()?;
                     let line_number = buf.read_u16()?;
                     entries.push(LineNumberTableEntry::new(
-                        ProgramCounter(program_counter),
+                        ProgramCounter(u32::from(program_counter)),
                         LineNumber(line_number),
                     ));
                 }
@@ -445,12 +516,110 @@ impl<'a> ClassFileReader<'a> {
             .invert()
     }
 
-    fn extract_thrown_exceptions(&self, raw_attributes: &[Attribute]) -> Result<Vec<String>> {
+    fn extract_stack_map_table(
+        &self,
+        raw_attributes: &[RawAttribute<'a>],
+    ) -> Result<Option<StackMapTable<'a>>> {
+        raw_attributes
+            .iter()
+            .find(|attr| attr.name == "StackMapTable")
+            .map(|attr| {
+                let mut buf = Buffer::new(attr.bytes);
+                let num_frames = buf.read_u16()?.into_usize_safe();
+                let mut frames = Vec::with_capacity(num_frames);
+                let mut previous_pc: Option<u32> = None;
+                for _ in 0..num_frames {
+                    let (offset_delta, kind) = self.read_stack_map_frame(&mut buf)?;
+                    // The offsets are relative to the previous frame, plus one, so that
+                    // two frames can never be at the same address
+                    let pc = match previous_pc {
+                        None => u32::from(offset_delta),
+                        Some(previous_pc) => previous_pc + u32::from(offset_delta) + 1,
+                    };
+                    previous_pc = Some(pc);
+                    frames.push(StackMapFrame {
+                        pc: ProgramCounter(pc),
+                        kind,
+                    });
+                }
+                Ok(StackMapTable::new(frames))
+            })
+            .invert()
+    }
+
+    fn read_stack_map_frame(&self, buf: &mut Buffer<'a>) -> Result<(u16, StackMapFrameKind<'a>)> {
+        let frame_type = buf.read_u8()?;
+        match frame_type {
+            0..=63 => Ok((u16::from(frame_type), StackMapFrameKind::Same)),
+            64..=127 => Ok((
+                u16::from(frame_type - 64),
+                StackMapFrameKind::SameLocalsOneStackItem(self.read_verification_type(buf)?),
+            )),
+            247 => Ok((
+                buf.read_u16()?,
+                StackMapFrameKind::SameLocalsOneStackItem(self.read_verification_type(buf)?),
+            )),
+            248..=250 => Ok((buf.read_u16()?, StackMapFrameKind::Chop(251 - frame_type))),
+            251 => Ok((buf.read_u16()?, StackMapFrameKind::Same)),
+            252..=254 => {
+                let offset_delta = buf.read_u16()?;
+                let locals = (0..frame_type - 251)
+                    .map(|_| self.read_verification_type(buf))
+                    .collect::<Result<Vec<_>>>()?;
+                Ok((offset_delta, StackMapFrameKind::Append(locals)))
+            }
+            255 => {
+                let offset_delta = buf.read_u16()?;
+                let num_locals = buf.read_u16()?;
+                let locals = (0..num_locals)
+                    .map(|_| self.read_verification_type(buf))
+                    .collect::<Result<Vec<_>>>()?;
+                let num_stack_entries = buf.read_u16()?;
+                let stack = (0..num_stack_entries)
+                    .map(|_| self.read_verification_type(buf))
+                    .collect::<Result<Vec<_>>>()?;
+                Ok((offset_delta, StackMapFrameKind::Full { locals, stack }))
+            }
+            _ => Err(ClassReaderError::invalid_class_data(format!(
+                "invalid stack map frame type: {frame_type}"
+            ))),
+        }
+    }
+
+    fn read_verification_type(&self, buf: &mut Buffer<'a>) -> Result<VerificationType<'a>> {
+        let tag = buf.read_u8()?;
+        match tag {
+            0 => Ok(VerificationType::Top),
+            1 => Ok(VerificationType::Integer),
+            2 => Ok(VerificationType::Float),
+            3 => Ok(VerificationType::Double),
+            4 => Ok(VerificationType::Long),
+            5 => Ok(VerificationType::Null),
+            6 => Ok(VerificationType::UninitializedThis),
+            7 => {
+                let class_constant = buf.read_u16()?;
+                Ok(VerificationType::Object(
+                    self.read_string_reference(class_constant)?,
+                ))
+            }
+            8 => Ok(VerificationType::Uninitialized(ProgramCounter(u32::from(
+                buf.read_u16()?,
+            )))),
+            _ => Err(ClassReaderError::invalid_class_data(format!(
+                "invalid verification type: {tag}"
+            ))),
+        }
+    }
+
+    fn extract_thrown_exceptions(
+        &self,
+        raw_attributes: &[RawAttribute<'a>],
+    ) -> Result<Vec<Cow<'a, str>>> {
         raw_attributes
             .iter()
             .find(|attr| attr.name == "Exceptions")
             .map(|attr| {
-                let mut buf = Buffer::new(&attr.bytes);
+                let mut buf = Buffer::new(attr.bytes);
                 let num_entries = buf.read_u16()?.into_usize_safe();
                 let mut exceptions = Vec::with_capacity(num_entries);
                 for _ in 0..num_entries {
@@ -467,16 +636,25 @@ impl<'a> ClassFileReader<'a> {
         let raw_attributes = self.read_raw_attributes()?;
         self.class_file.deprecated = self.search_deprecated_attribute(&raw_attributes);
         self.class_file.source_file = self.search_source_file_attribute(&raw_attributes)?;
+        self.class_file.nest_host = self.search_nest_host_attribute(&raw_attributes)?;
+        self.class_file.bootstrap_methods =
+            Self::search_bootstrap_methods_attribute(&raw_attributes)?;
+        self.class_file.attributes = uninterpreted_attributes(
+            raw_attributes,
+            &["BootstrapMethods", "Deprecated", "NestHost", "SourceFile"],
+        );
         Ok(())
     }
 
-    fn search_source_file_attribute(&self, raw_attributes: &[Attribute]) -> Result<Option<String>> {
+    fn search_source_file_attribute(
+        &self,
+        raw_attributes: &[RawAttribute<'a>],
+    ) -> Result<Option<Cow<'a, str>>> {
         raw_attributes
             .iter()
             .find(|attr| attr.name == "SourceFile")
             .map(|attr| {
-                let attribute_bytes: &[u8] = &attr.bytes;
-                let constant_index = u16::from_be_bytes(attribute_bytes.try_into().unwrap());
+                let constant_index = u16::from_be_bytes(attr.bytes.try_into().unwrap());
                 self.class_file
                     .constants
                     .get(constant_index)
@@ -491,34 +669,75 @@ impl<'a> ClassFileReader<'a> {
             .invert()
     }
 
-    fn read_raw_attributes(&mut self) -> Result<Vec<Attribute>> {
+    fn search_nest_host_attribute(
+        &self,
+        raw_attributes: &[RawAttribute<'a>],
+    ) -> Result<Option<Cow<'a, str>>> {
+        raw_attributes
+            .iter()
+            .find(|attr| attr.name == "NestHost")
+            .map(|attr| {
+                let constant_index = Buffer::new(attr.bytes).read_u16()?;
+                self.read_string_reference(constant_index)
+            })
+            .invert()
+    }
+
+    fn search_bootstrap_methods_attribute(
+        raw_attributes: &[RawAttribute<'a>],
+    ) -> Result<Vec<BootstrapMethod>> {
+        raw_attributes
+            .iter()
+            .find(|attr| attr.name == "BootstrapMethods")
+            .map(|attr| {
+                let mut buf = Buffer::new(attr.bytes);
+                let num_bootstrap_methods = buf.read_u16()?;
+                (0..num_bootstrap_methods)
+                    .map(|_| {
+                        let method_ref = buf.read_u16()?;
+                        let num_arguments = buf.read_u16()?;
+                        let arguments = (0..num_arguments)
+                            .map(|_| buf.read_u16())
+                            .collect::<std::result::Result<Vec<u16>, _>>()?;
+                        Ok(BootstrapMethod {
+                            method_ref,
+                            arguments,
+                        })
+                    })
+                    .collect::<Result<Vec<BootstrapMethod>>>()
+            })
+            .unwrap_or(Ok(Vec::new()))
+    }
+
+    fn read_raw_attributes(&mut self) -> Result<Vec<RawAttribute<'a>>> {
         Self::read_raw_attributes_from(&self.class_file.constants, &mut self.buffer)
     }
 
     fn read_raw_attributes_from(
-        constants_pool: &ConstantPool,
-        buffer: &mut Buffer,
-    ) -> Result<Vec<Attribute>> {
+        constants_pool: &ConstantPool<'a>,
+        buffer: &mut Buffer<'a>,
+    ) -> Result<Vec<RawAttribute<'a>>> {
         let attributes_count = buffer.read_u16()?;
         (0..attributes_count)
             .map(|_| Self::read_raw_attribute(constants_pool, buffer))
-            .collect::<Result<Vec<Attribute>>>()
+            .collect::<Result<Vec<RawAttribute<'a>>>>()
     }
 
-    fn read_raw_attribute(constants_pool: &ConstantPool, buffer: &mut Buffer) -> Result<Attribute> {
+    fn read_raw_attribute(
+        constants_pool: &ConstantPool<'a>,
+        buffer: &mut Buffer<'a>,
+    ) -> Result<RawAttribute<'a>> {
         let name_constant_index = buffer.read_u16()?;
         let name = Self::read_string_reference_from(constants_pool, name_constant_index)?;
         let len = buffer.read_u32()?;
         let bytes = buffer.read_bytes(len.into_usize_safe())?;
-        Ok(Attribute {
-            name,
-            bytes: Vec::from(bytes),
-        })
+        Ok(RawAttribute { name, bytes })
     }
 }
 
-/// Reads a class from a byte slice.
-pub fn read_buffer(buf: &[u8]) -> Result<ClassFile> {
+/// Reads a class from a byte slice. The returned class borrows strings and bytecode from
+/// the slice, whenever possible; use [ClassFile::into_owned] if the slice cannot be kept alive.
+pub fn read_buffer(buf: &[u8]) -> Result<ClassFile<'_>> {
     ClassFileReader::new(buf).read()
 }
 
@@ -3,7 +3,10 @@ use std::{
     fmt::{Display, Formatter},
 };
 
-use crate::{buffer::BufferError, constant_pool::InvalidConstantPoolIndexError};
+use crate::{
+    buffer::BufferError, constant_pool::InvalidConstantPoolIndexError,
+    field_type::TypeDescriptorError,
+};
 
 /// Models the possible errors returned when reading a .class file
 #[derive(Debug, PartialEq, Eq)]
@@ -12,7 +15,7 @@ pub enum ClassReaderError {
     InvalidClassData(String, Option<InvalidConstantPoolIndexError>),
     UnsupportedVersion(u16, u16),
     /// Error while parsing a given type descriptor in the file
-    InvalidTypeDescriptor(String),
+    InvalidTypeDescriptor(TypeDescriptorError),
 }
 
 impl ClassReaderError {
@@ -30,9 +33,7 @@ impl Display for ClassReaderError {
             ClassReaderError::UnsupportedVersion(major, minor) => {
                 write!(f, "unsupported class file version {major}.{minor}")
             }
-            ClassReaderError::InvalidTypeDescriptor(descriptor) => {
-                write!(f, "invalid type descriptor: {descriptor}")
-            }
+            ClassReaderError::InvalidTypeDescriptor(err) => write!(f, "{err}"),
         }
     }
 }
@@ -41,6 +42,7 @@ impl Error for ClassReaderError {
     fn source(&self) -> Option<&(dyn Error + 'static)> {
         match self {
             ClassReaderError::InvalidClassData(_, Some(source)) => Some(source),
+            ClassReaderError::InvalidTypeDescriptor(source) => Some(source),
             _ => None,
         }
     }
@@ -54,6 +56,12 @@ impl From<InvalidConstantPoolIndexError> for ClassReaderError {
     }
 }
 
+impl From<TypeDescriptorError> for ClassReaderError {
+    fn from(err: TypeDescriptorError) -> Self {
+        Self::InvalidTypeDescriptor(err)
+    }
+}
+
 impl From<BufferError> for ClassReaderError {
     fn from(err: BufferError) -> Self {
         match err {
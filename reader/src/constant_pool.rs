@@ -1,11 +1,13 @@
-use std::{fmt, vec::Vec};
+use std::{borrow::Cow, fmt, vec::Vec};
 use thiserror::Error;
 
+use crate::method_handle_kind::MethodHandleKind;
+
 /// Types of a constant in the constant pool of a class, following the JVM spec:
 /// https://docs.oracle.com/javase/specs/jvms/se7/html/jvms-4.html#jvms-4.4
 #[derive(Debug, PartialEq)]
-pub enum ConstantPoolEntry {
-    Utf8(String),
+pub enum ConstantPoolEntry<'a> {
+    Utf8(Cow<'a, str>),
     Integer(i32),
     Float(f32),
     Long(i64),
@@ -16,21 +18,28 @@ pub enum ConstantPoolEntry {
     MethodReference(u16, u16),
     InterfaceMethodReference(u16, u16),
     NameAndTypeDescriptor(u16, u16),
+    /// Kind and index of the referenced field or method
+    MethodHandle(MethodHandleKind, u16),
+    /// Index of the method descriptor
+    MethodType(u16),
+    /// Index in the class' bootstrap methods, and index of the name and type of the call site
+    InvokeDynamic(u16, u16),
 }
 
 /// Constants in the pool generally take one slot, but long and double take two. We do not use
 /// the second one, so we have a tombstone to ensure the indexes match.
 #[derive(Debug)]
-enum ConstantPoolPhysicalEntry {
-    Entry(ConstantPoolEntry),
+enum ConstantPoolPhysicalEntry<'a> {
+    Entry(ConstantPoolEntry<'a>),
     MultiByteEntryTombstone(),
 }
 
 /// Implementation of the constant pool of a java class.
 /// Note that constants are 1-based in java.
+/// Strings can be borrowed from the buffer the class was read from.
 #[derive(Debug, Default)]
-pub struct ConstantPool {
-    entries: Vec<ConstantPoolPhysicalEntry>,
+pub struct ConstantPool<'a> {
+    entries: Vec<ConstantPoolPhysicalEntry<'a>>,
 }
 
 /// Error used to signal that an attempt was made to access a non existing constant pool entry.
@@ -46,13 +55,39 @@ impl InvalidConstantPoolIndexError {
     }
 }
 
-impl ConstantPool {
-    pub fn new() -> ConstantPool {
+impl<'a> ConstantPoolEntry<'a> {
+    /// Converts the entry in one that does not borrow any data
+    pub fn into_owned(self) -> ConstantPoolEntry<'static> {
+        match self {
+            ConstantPoolEntry::Utf8(s) => ConstantPoolEntry::Utf8(Cow::Owned(s.into_owned())),
+            ConstantPoolEntry::Integer(n) => ConstantPoolEntry::Integer(n),
+            ConstantPoolEntry::Float(n) => ConstantPoolEntry::Float(n),
+            ConstantPoolEntry::Long(n) => ConstantPoolEntry::Long(n),
+            ConstantPoolEntry::Double(n) => ConstantPoolEntry::Double(n),
+            ConstantPoolEntry::ClassReference(n) => ConstantPoolEntry::ClassReference(n),
+            ConstantPoolEntry::StringReference(n) => ConstantPoolEntry::StringReference(n),
+            ConstantPoolEntry::FieldReference(i, j) => ConstantPoolEntry::FieldReference(i, j),
+            ConstantPoolEntry::MethodReference(i, j) => ConstantPoolEntry::MethodReference(i, j),
+            ConstantPoolEntry::InterfaceMethodReference(i, j) => {
+                ConstantPoolEntry::InterfaceMethodReference(i, j)
+            }
+            ConstantPoolEntry::NameAndTypeDescriptor(i, j) => {
+                ConstantPoolEntry::NameAndTypeDescriptor(i, j)
+            }
+            ConstantPoolEntry::MethodHandle(kind, n) => ConstantPoolEntry::MethodHandle(kind, n),
+            ConstantPoolEntry::MethodType(n) => ConstantPoolEntry::MethodType(n),
+            ConstantPoolEntry::InvokeDynamic(i, j) => ConstantPoolEntry::InvokeDynamic(i, j),
+        }
+    }
+}
+
+impl<'a> ConstantPool<'a> {
+    pub fn new() -> ConstantPool<'a> {
         Default::default()
     }
 
     /// Adds a new entry.
-    pub fn add(&mut self, entry: ConstantPoolEntry) {
+    pub fn add(&mut self, entry: ConstantPoolEntry<'a>) {
         let add_tombstone = matches!(
             &entry,
             ConstantPoolEntry::Long(_) | ConstantPoolEntry::Double(_)
@@ -69,7 +104,7 @@ impl ConstantPool {
     pub fn get(
         &self,
         input_index: u16,
-    ) -> Result<&ConstantPoolEntry, InvalidConstantPoolIndexError> {
+    ) -> Result<&ConstantPoolEntry<'a>, InvalidConstantPoolIndexError> {
         if input_index == 0 || input_index as usize > self.entries.len() {
             Err(InvalidConstantPoolIndexError::new(input_index))
         } else {
@@ -84,6 +119,18 @@ impl ConstantPool {
         }
     }
 
+    /// Iterates over the entries with their index, skipping the unused second slot of the
+    /// long and double constants
+    pub fn iter(&self) -> impl Iterator<Item = (u16, &ConstantPoolEntry<'a>)> {
+        self.entries
+            .iter()
+            .enumerate()
+            .filter_map(|(raw_idx, entry)| match entry {
+                ConstantPoolPhysicalEntry::Entry(entry) => Some(((raw_idx + 1) as u16, entry)),
+                ConstantPoolPhysicalEntry::MultiByteEntryTombstone() => None,
+            })
+    }
+
     fn fmt_entry(&self, idx: u16) -> Result<String, InvalidConstantPoolIndexError> {
         let entry = self.get(idx)?;
         let text = match entry {
@@ -134,14 +181,35 @@ impl ConstantPool {
                     self.fmt_entry(j)?
                 )
             }
+            ConstantPoolEntry::MethodHandle(kind, n) => {
+                format!("MethodHandle: {}, {} => ({})", kind, n, self.fmt_entry(*n)?)
+            }
+            ConstantPoolEntry::MethodType(n) => {
+                format!("MethodType: {} => ({})", n, self.fmt_entry(*n)?)
+            }
+            ConstantPoolEntry::InvokeDynamic(i, j) => {
+                format!("InvokeDynamic: {}, {} => ({})", i, j, self.fmt_entry(*j)?)
+            }
         };
         Ok(text)
     }
 
+    /// Like [ConstantPool::text_of], but avoids copying the strings that are borrowed from
+    /// the class' buffer when the entry is a string or a reference to one
+    pub fn string_of(&self, idx: u16) -> Result<Cow<'a, str>, InvalidConstantPoolIndexError> {
+        match self.get(idx)? {
+            ConstantPoolEntry::Utf8(s) => Ok(s.clone()),
+            ConstantPoolEntry::ClassReference(n) | ConstantPoolEntry::StringReference(n) => {
+                self.string_of(*n)
+            }
+            _ => self.text_of(idx).map(Cow::Owned),
+        }
+    }
+
     pub fn text_of(&self, idx: u16) -> Result<String, InvalidConstantPoolIndexError> {
         let entry = self.get(idx)?;
         let text = match entry {
-            ConstantPoolEntry::Utf8(ref s) => s.clone(),
+            ConstantPoolEntry::Utf8(ref s) => s.to_string(),
             ConstantPoolEntry::Integer(n) => n.to_string(),
             ConstantPoolEntry::Float(n) => n.to_string(),
             ConstantPoolEntry::Long(n) => n.to_string(),
@@ -160,17 +228,42 @@ impl ConstantPool {
             ConstantPoolEntry::NameAndTypeDescriptor(i, j) => {
                 format!("{}: {}", self.text_of(*i)?, self.text_of(*j)?)
             }
+            ConstantPoolEntry::MethodHandle(kind, n) => {
+                format!("{} {}", kind, self.text_of(*n)?)
+            }
+            ConstantPoolEntry::MethodType(n) => self.text_of(*n)?,
+            ConstantPoolEntry::InvokeDynamic(i, j) => {
+                format!("#{}:{}", i, self.text_of(*j)?)
+            }
         };
         Ok(text)
     }
+
+    /// Converts the pool in one that does not borrow any data
+    pub fn into_owned(self) -> ConstantPool<'static> {
+        ConstantPool {
+            entries: self
+                .entries
+                .into_iter()
+                .map(|entry| match entry {
+                    ConstantPoolPhysicalEntry::Entry(entry) => {
+                        ConstantPoolPhysicalEntry::Entry(entry.into_owned())
+                    }
+                    ConstantPoolPhysicalEntry::MultiByteEntryTombstone() => {
+                        ConstantPoolPhysicalEntry::MultiByteEntryTombstone()
+                    }
+                })
+                .collect(),
+        }
+    }
 }
 
-impl fmt::Display for ConstantPool {
+impl<'a> fmt::Display for ConstantPool<'a> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         writeln!(f, "Constant pool: (size: {})", self.entries.len())?;
         for (raw_idx, _) in self.entries.iter().enumerate() {
             let index = (raw_idx + 1) as u16;
-            let entry_text = self.fmt_entry(index).map_err(|_| fmt::Error::default())?;
+            let entry_text = self.fmt_entry(index).map_err(|_| fmt::Error)?;
             writeln!(f, "    {}, {}", index, entry_text)?;
         }
         Ok(())
@@ -179,28 +272,27 @@ impl fmt::Display for ConstantPool {
 
 #[cfg(test)]
 mod tests {
+    use std::borrow::Cow;
+
     use crate::constant_pool::{ConstantPool, ConstantPoolEntry, InvalidConstantPoolIndexError};
 
     #[test]
     fn constant_pool_works() {
         let mut cp = ConstantPool::new();
-        cp.add(ConstantPoolEntry::Utf8("hey".to_string()));
+        cp.add(ConstantPoolEntry::Utf8("hey".into()));
         cp.add(ConstantPoolEntry::Integer(1));
         cp.add(ConstantPoolEntry::Float(2.1));
         cp.add(ConstantPoolEntry::Long(123));
         cp.add(ConstantPoolEntry::Double(3.56));
         cp.add(ConstantPoolEntry::ClassReference(1));
         cp.add(ConstantPoolEntry::StringReference(1));
-        cp.add(ConstantPoolEntry::Utf8("joe".to_string()));
+        cp.add(ConstantPoolEntry::Utf8("joe".into()));
         cp.add(ConstantPoolEntry::FieldReference(1, 10));
         cp.add(ConstantPoolEntry::MethodReference(1, 10));
         cp.add(ConstantPoolEntry::InterfaceMethodReference(1, 10));
         cp.add(ConstantPoolEntry::NameAndTypeDescriptor(1, 10));
 
-        assert_eq!(
-            ConstantPoolEntry::Utf8("hey".to_string()),
-            *cp.get(1).unwrap()
-        );
+        assert_eq!(ConstantPoolEntry::Utf8("hey".into()), *cp.get(1).unwrap());
         assert_eq!(ConstantPoolEntry::Integer(1), *cp.get(2).unwrap());
         assert_eq!(ConstantPoolEntry::Float(2.1), *cp.get(3).unwrap());
         assert_eq!(ConstantPoolEntry::Long(123i64), *cp.get(4).unwrap());
@@ -209,10 +301,7 @@ mod tests {
         assert_eq!(Err(InvalidConstantPoolIndexError::new(7)), cp.get(7));
         assert_eq!(ConstantPoolEntry::ClassReference(1), *cp.get(8).unwrap());
         assert_eq!(ConstantPoolEntry::StringReference(1), *cp.get(9).unwrap());
-        assert_eq!(
-            ConstantPoolEntry::Utf8("joe".to_string()),
-            *cp.get(10).unwrap()
-        );
+        assert_eq!(ConstantPoolEntry::Utf8("joe".into()), *cp.get(10).unwrap());
         assert_eq!(
             ConstantPoolEntry::FieldReference(1, 10),
             *cp.get(11).unwrap()
@@ -244,5 +333,40 @@ mod tests {
         assert_eq!("hey.joe", cp.text_of(12).unwrap());
         assert_eq!("hey.joe", cp.text_of(13).unwrap());
         assert_eq!("hey: joe", cp.text_of(14).unwrap());
+
+        assert_eq!("hey", cp.string_of(1).unwrap());
+        assert_eq!("hey", cp.string_of(8).unwrap());
+        assert_eq!("123", cp.string_of(4).unwrap());
+        assert_eq!("hey.joe", cp.string_of(11).unwrap());
+    }
+
+    #[test]
+    fn iter_skips_the_second_slot_of_wide_constants() {
+        let mut cp = ConstantPool::new();
+        cp.add(ConstantPoolEntry::Long(123));
+        cp.add(ConstantPoolEntry::Integer(1));
+
+        assert_eq!(
+            vec![
+                (1, &ConstantPoolEntry::Long(123)),
+                (3, &ConstantPoolEntry::Integer(1))
+            ],
+            cp.iter().collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn strings_can_be_borrowed() {
+        let data = String::from("borrowed");
+        let mut cp = ConstantPool::new();
+        cp.add(ConstantPoolEntry::Utf8(Cow::Borrowed(&data)));
+        cp.add(ConstantPoolEntry::ClassReference(1));
+
+        assert!(matches!(
+            cp.string_of(2).unwrap(),
+            Cow::Borrowed("borrowed")
+        ));
+        let owned = cp.into_owned();
+        assert!(matches!(owned.string_of(2).unwrap(), Cow::Owned(s) if s == "borrowed"));
     }
 }
@@ -1,35 +1,51 @@
-use std::ops::Range;
+use std::{borrow::Cow, ops::Range};
 
 use crate::program_counter::ProgramCounter;
 
 /// Exception table of a method's code
 #[derive(Debug, Default, PartialEq)]
-pub struct ExceptionTable {
-    entries: Vec<ExceptionTableEntry>,
+pub struct ExceptionTable<'a> {
+    entries: Vec<ExceptionTableEntry<'a>>,
 }
 
-impl ExceptionTable {
-    pub fn new(entries: Vec<ExceptionTableEntry>) -> Self {
+impl<'a> ExceptionTable<'a> {
+    pub fn new(entries: Vec<ExceptionTableEntry<'a>>) -> Self {
         Self { entries }
     }
 
-    pub fn lookup(&self, pc: ProgramCounter) -> Vec<&ExceptionTableEntry> {
+    pub fn lookup(&self, pc: ProgramCounter) -> Vec<&ExceptionTableEntry<'a>> {
         self.entries
             .iter()
             .filter(|entry| entry.range.contains(&pc))
             .collect()
     }
+
+    pub fn into_owned(self) -> ExceptionTable<'static> {
+        ExceptionTable {
+            entries: self
+                .entries
+                .into_iter()
+                .map(|entry| ExceptionTableEntry {
+                    range: entry.range,
+                    handler_pc: entry.handler_pc,
+                    catch_class: entry
+                        .catch_class
+                        .map(|class_name| Cow::Owned(class_name.into_owned())),
+                })
+                .collect(),
+        }
+    }
 }
 
 /// Entries of the exception table
 #[derive(Debug, PartialEq, Eq, Clone)]
-pub struct ExceptionTableEntry {
+pub struct ExceptionTableEntry<'a> {
     /// The range of program counters that this entry covers
     pub range: Range<ProgramCounter>,
     /// The address of the handler of this entry
     pub handler_pc: ProgramCounter,
     /// The class or superclass that matches this entry
-    pub catch_class: Option<String>,
+    pub catch_class: Option<Cow<'a, str>>,
 }
 
 #[cfg(test)]
@@ -49,12 +65,12 @@ mod tests {
         let entry_2 = ExceptionTableEntry {
             range: ProgramCounter(8)..ProgramCounter(14),
             handler_pc: ProgramCounter(88),
-            catch_class: Some("java/lang/RuntimeException".to_string()),
+            catch_class: Some("java/lang/RuntimeException".into()),
         };
         let entry_3 = ExceptionTableEntry {
             range: ProgramCounter(13)..ProgramCounter(14),
             handler_pc: ProgramCounter(77),
-            catch_class: Some("java/lang/ClassCastException".to_string()),
+            catch_class: Some("java/lang/ClassCastException".into()),
         };
         let table = ExceptionTable::new(vec![entry_1.clone(), entry_2.clone(), entry_3.clone()]);
 
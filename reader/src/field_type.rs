@@ -1,10 +1,4 @@
-use std::{fmt, fmt::Formatter, str::Chars};
-
-use itertools::Itertools;
-
-use ClassReaderError::InvalidTypeDescriptor;
-
-use crate::class_reader_error::ClassReaderError;
+use std::{fmt, fmt::Formatter};
 
 /// Models the type of one field, or one parameter of a method
 #[derive(Debug, Clone, PartialEq)]
@@ -43,63 +37,269 @@ pub enum BaseType {
     Boolean,
 }
 
+impl BaseType {
+    /// The character used for the type in descriptors, i.e. `I` for `int`
+    pub fn descriptor_char(&self) -> char {
+        match self {
+            BaseType::Byte => 'B',
+            BaseType::Char => 'C',
+            BaseType::Double => 'D',
+            BaseType::Float => 'F',
+            BaseType::Int => 'I',
+            BaseType::Long => 'J',
+            BaseType::Short => 'S',
+            BaseType::Boolean => 'Z',
+        }
+    }
+
+    pub fn from_descriptor_char(c: char) -> Option<BaseType> {
+        match c {
+            'B' => Some(BaseType::Byte),
+            'C' => Some(BaseType::Char),
+            'D' => Some(BaseType::Double),
+            'F' => Some(BaseType::Float),
+            'I' => Some(BaseType::Int),
+            'J' => Some(BaseType::Long),
+            'S' => Some(BaseType::Short),
+            'Z' => Some(BaseType::Boolean),
+            _ => None,
+        }
+    }
+}
+
 impl FieldType {
     /// Parses a type descriptor as specified in the JVM specs:
     /// https://docs.oracle.com/javase/specs/jvms/se7/html/jvms-4.html#jvms-4.3.2
-    pub fn parse(type_descriptor: &str) -> Result<FieldType, ClassReaderError> {
-        let mut chars = type_descriptor.chars();
-        let descriptor = Self::parse_from(type_descriptor, &mut chars)?;
-        match chars.next() {
-            None => Ok(descriptor),
-            Some(_) => Err(InvalidTypeDescriptor(type_descriptor.to_string())),
-        }
-    }
-
-    pub(crate) fn parse_from(
-        type_descriptor: &str,
-        chars: &mut Chars,
-    ) -> Result<FieldType, ClassReaderError> {
-        let first_char = chars
-            .next()
-            .ok_or(InvalidTypeDescriptor(type_descriptor.to_string()))?;
-
-        Ok(match first_char {
-            'B' => FieldType::Base(BaseType::Byte),
-            'C' => FieldType::Base(BaseType::Char),
-            'D' => FieldType::Base(BaseType::Double),
-            'F' => FieldType::Base(BaseType::Float),
-            'I' => FieldType::Base(BaseType::Int),
-            'J' => FieldType::Base(BaseType::Long),
-            'S' => FieldType::Base(BaseType::Short),
-            'Z' => FieldType::Base(BaseType::Boolean),
-            'L' => {
-                let class_name: String = chars.take_while_ref(|c| *c != ';').collect();
-                match chars.next() {
-                    Some(';') => FieldType::Object(class_name),
-                    _ => return Err(InvalidTypeDescriptor(type_descriptor.to_string())),
+    pub fn parse(type_descriptor: &str) -> Result<FieldType, TypeDescriptorError> {
+        let mut parser = DescriptorParser::new(type_descriptor);
+        let field_type = parser.parse_field_type()?;
+        parser.expect_end()?;
+        Ok(field_type)
+    }
+
+    /// Formats the type as a descriptor; this is the inverse of [FieldType::parse]
+    pub fn to_descriptor(&self) -> String {
+        let mut descriptor = String::new();
+        self.write_descriptor(&mut descriptor);
+        descriptor
+    }
+
+    pub(crate) fn write_descriptor(&self, descriptor: &mut String) {
+        match self {
+            FieldType::Base(base_type) => descriptor.push(base_type.descriptor_char()),
+            FieldType::Object(class_name) => {
+                descriptor.push('L');
+                descriptor.push_str(class_name);
+                descriptor.push(';');
+            }
+            FieldType::Array(component_type) => {
+                descriptor.push('[');
+                component_type.write_descriptor(descriptor);
+            }
+        }
+    }
+}
+
+/// Error returned when parsing an invalid type or method descriptor
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TypeDescriptorError {
+    /// The whole descriptor that was being parsed
+    pub descriptor: String,
+    /// The offset, in bytes, of the part of the descriptor that could not be parsed
+    pub position: usize,
+    pub kind: TypeDescriptorErrorKind,
+}
+
+impl TypeDescriptorError {
+    /// The part of the descriptor that could not be parsed
+    pub fn remaining_input(&self) -> &str {
+        self.descriptor.get(self.position..).unwrap_or_default()
+    }
+}
+
+impl fmt::Display for TypeDescriptorError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "invalid type descriptor \"{}\": {} at position {} (remaining input: \"{}\")",
+            self.descriptor,
+            self.kind,
+            self.position,
+            self.remaining_input()
+        )
+    }
+}
+
+impl std::error::Error for TypeDescriptorError {}
+
+/// The reason why a descriptor is invalid
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TypeDescriptorErrorKind {
+    UnexpectedEnd,
+    UnexpectedCharacter(char),
+    /// A class name that is empty or contains empty or invalid segments, i.e. `Ljava//Foo;`
+    InvalidClassName(String),
+    /// A class name that is not followed by `;`
+    UnterminatedClassName,
+    /// The JVM spec limits arrays to 255 dimensions
+    TooManyDimensions,
+    TrailingCharacters,
+}
+
+impl fmt::Display for TypeDescriptorErrorKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            TypeDescriptorErrorKind::UnexpectedEnd => f.write_str("unexpected end of descriptor"),
+            TypeDescriptorErrorKind::UnexpectedCharacter(c) => {
+                write!(f, "unexpected character '{c}'")?;
+                let upper_case = c.to_ascii_uppercase();
+                if *c != upper_case
+                    && (BaseType::from_descriptor_char(upper_case).is_some()
+                        || matches!(upper_case, 'L' | 'V'))
+                {
+                    write!(
+                        f,
+                        " (descriptors are case sensitive, did you mean '{upper_case}'?)"
+                    )?;
                 }
+                Ok(())
+            }
+            TypeDescriptorErrorKind::InvalidClassName(class_name) => {
+                write!(f, "invalid class name \"{class_name}\"")
+            }
+            TypeDescriptorErrorKind::UnterminatedClassName => {
+                f.write_str("class name not terminated by ';'")
             }
-            '[' => {
-                let component_type = Self::parse_from(type_descriptor, chars)?;
-                FieldType::Array(Box::new(component_type))
+            TypeDescriptorErrorKind::TooManyDimensions => {
+                f.write_str("array type with more than 255 dimensions")
             }
-            _ => return Err(InvalidTypeDescriptor(type_descriptor.to_string())),
-        })
+            TypeDescriptorErrorKind::TrailingCharacters => {
+                f.write_str("unexpected characters after the end of the descriptor")
+            }
+        }
+    }
+}
+
+/// Parses type and method descriptors, keeping track of the position reached so that errors
+/// can report it
+pub(crate) struct DescriptorParser<'s> {
+    descriptor: &'s str,
+    position: usize,
+}
+
+impl<'s> DescriptorParser<'s> {
+    pub fn new(descriptor: &'s str) -> Self {
+        Self {
+            descriptor,
+            position: 0,
+        }
+    }
+
+    pub fn peek(&self) -> Option<char> {
+        self.descriptor[self.position..].chars().next()
+    }
+
+    pub fn next(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.position += c.len_utf8();
+        Some(c)
+    }
+
+    /// Consumes the given character, or fails if the next one is different
+    pub fn expect(&mut self, expected: char) -> Result<(), TypeDescriptorError> {
+        match self.peek() {
+            Some(c) if c == expected => {
+                self.next();
+                Ok(())
+            }
+            Some(c) => Err(self.error(TypeDescriptorErrorKind::UnexpectedCharacter(c))),
+            None => Err(self.error(TypeDescriptorErrorKind::UnexpectedEnd)),
+        }
+    }
+
+    pub fn expect_end(&self) -> Result<(), TypeDescriptorError> {
+        match self.peek() {
+            None => Ok(()),
+            Some(_) => Err(self.error(TypeDescriptorErrorKind::TrailingCharacters)),
+        }
+    }
+
+    pub fn parse_field_type(&mut self) -> Result<FieldType, TypeDescriptorError> {
+        let start = self.position;
+        let mut dimensions = 0;
+        while self.peek() == Some('[') {
+            self.next();
+            dimensions += 1;
+        }
+        if dimensions > 255 {
+            return Err(self.error_at(start, TypeDescriptorErrorKind::TooManyDimensions));
+        }
+
+        let type_start = self.position;
+        let mut field_type = match self.next() {
+            Some('L') => self.parse_class_name(type_start)?,
+            Some(c) => match BaseType::from_descriptor_char(c) {
+                Some(base_type) => FieldType::Base(base_type),
+                None => {
+                    return Err(
+                        self.error_at(type_start, TypeDescriptorErrorKind::UnexpectedCharacter(c))
+                    )
+                }
+            },
+            None => return Err(self.error(TypeDescriptorErrorKind::UnexpectedEnd)),
+        };
+        for _ in 0..dimensions {
+            field_type = FieldType::Array(Box::new(field_type));
+        }
+        Ok(field_type)
+    }
+
+    fn parse_class_name(&mut self, type_start: usize) -> Result<FieldType, TypeDescriptorError> {
+        let remaining = &self.descriptor[self.position..];
+        let length = remaining.find(';').ok_or_else(|| {
+            self.error_at(type_start, TypeDescriptorErrorKind::UnterminatedClassName)
+        })?;
+        let class_name = &remaining[..length];
+        if !is_valid_class_name(class_name) {
+            return Err(self.error_at(
+                type_start,
+                TypeDescriptorErrorKind::InvalidClassName(class_name.to_string()),
+            ));
+        }
+        self.position += length + 1;
+        Ok(FieldType::Object(class_name.to_string()))
     }
+
+    fn error(&self, kind: TypeDescriptorErrorKind) -> TypeDescriptorError {
+        self.error_at(self.position, kind)
+    }
+
+    fn error_at(&self, position: usize, kind: TypeDescriptorErrorKind) -> TypeDescriptorError {
+        TypeDescriptorError {
+            descriptor: self.descriptor.to_string(),
+            position,
+            kind,
+        }
+    }
+}
+
+/// Checks that a class name, in its internal form, is made of non-empty segments
+/// separated by `/`, none of which contains characters that the JVM spec forbids
+fn is_valid_class_name(class_name: &str) -> bool {
+    class_name
+        .split('/')
+        .all(|segment| !segment.is_empty() && !segment.contains(['.', ';', '[', '<', '>']))
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::{
-        class_reader_error::ClassReaderError,
-        field_type::{BaseType, FieldType},
-    };
+    use crate::field_type::{BaseType, FieldType, TypeDescriptorError, TypeDescriptorErrorKind};
 
     #[test]
     fn cannot_parse_empty_descriptor() {
         assert!(matches!(
             FieldType::parse(""),
-            Err(ClassReaderError::InvalidTypeDescriptor(s)) if s.is_empty()
+            Err(err) if err.descriptor.is_empty()
         ));
     }
 
@@ -107,7 +307,7 @@ mod tests {
     fn cannot_parse_invalid_primitive() {
         assert!(matches!(
             FieldType::parse("W"),
-            Err(ClassReaderError::InvalidTypeDescriptor(s)) if s == "W"
+            Err(err) if err.descriptor == "W"
         ));
     }
 
@@ -115,7 +315,7 @@ mod tests {
     fn cannot_parse_missing_semicolon() {
         assert!(matches!(
             FieldType::parse("Ljava/lang/String"),
-            Err(ClassReaderError::InvalidTypeDescriptor(s)) if s == "Ljava/lang/String"
+            Err(err) if err.descriptor == "Ljava/lang/String"
         ));
     }
 
@@ -123,7 +323,7 @@ mod tests {
     fn cannot_parse_invalid_array() {
         assert!(matches!(
             FieldType::parse("["),
-            Err(ClassReaderError::InvalidTypeDescriptor(s)) if s == "["
+            Err(err) if err.descriptor == "["
         ));
     }
 
@@ -188,4 +388,149 @@ mod tests {
     fn can_format_array() {
         assert_eq!("Int[]", format!("{}", FieldType::parse("[I").unwrap()));
     }
+
+    #[test]
+    fn parse_errors_report_position_and_remaining_input() {
+        assert_eq!(
+            Err(TypeDescriptorError {
+                descriptor: "[[Q".to_string(),
+                position: 2,
+                kind: TypeDescriptorErrorKind::UnexpectedCharacter('Q'),
+            }),
+            FieldType::parse("[[Q")
+        );
+        assert_eq!(
+            Err(TypeDescriptorError {
+                descriptor: "[Ljava/lang/String".to_string(),
+                position: 1,
+                kind: TypeDescriptorErrorKind::UnterminatedClassName,
+            }),
+            FieldType::parse("[Ljava/lang/String")
+        );
+        assert_eq!(
+            Err(TypeDescriptorError {
+                descriptor: "II".to_string(),
+                position: 1,
+                kind: TypeDescriptorErrorKind::TrailingCharacters,
+            }),
+            FieldType::parse("II")
+        );
+        assert_eq!(
+            "invalid type descriptor \"[[Q\": unexpected character 'Q' at position 2 \
+             (remaining input: \"Q\")",
+            FieldType::parse("[[Q").unwrap_err().to_string()
+        );
+    }
+
+    #[test]
+    fn descriptors_are_case_sensitive() {
+        let err = FieldType::parse("i").unwrap_err();
+        assert_eq!(TypeDescriptorErrorKind::UnexpectedCharacter('i'), err.kind);
+        assert_eq!(
+            "unexpected character 'i' (descriptors are case sensitive, did you mean 'I'?)",
+            err.kind.to_string()
+        );
+    }
+
+    #[test]
+    fn cannot_parse_invalid_class_names() {
+        for class_name in [
+            "",
+            "java//String",
+            "/String",
+            "java/lang/",
+            "java.lang.String",
+        ] {
+            let descriptor = format!("L{class_name};");
+            assert_eq!(
+                Err(TypeDescriptorError {
+                    descriptor: descriptor.clone(),
+                    position: 0,
+                    kind: TypeDescriptorErrorKind::InvalidClassName(class_name.to_string()),
+                }),
+                FieldType::parse(&descriptor)
+            );
+        }
+    }
+
+    #[test]
+    fn cannot_parse_arrays_with_too_many_dimensions() {
+        assert!(FieldType::parse(&format!("{}I", "[".repeat(255))).is_ok());
+        assert_eq!(
+            TypeDescriptorErrorKind::TooManyDimensions,
+            FieldType::parse(&format!("{}I", "[".repeat(256)))
+                .unwrap_err()
+                .kind
+        );
+    }
+
+    /// A tiny deterministic pseudo-random generator, good enough to generate test inputs
+    struct Lcg(u64);
+
+    impl Lcg {
+        fn next(&mut self, bound: usize) -> usize {
+            self.0 = self
+                .0
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            ((self.0 >> 33) as usize) % bound
+        }
+    }
+
+    const BASE_TYPES: [BaseType; 8] = [
+        BaseType::Byte,
+        BaseType::Char,
+        BaseType::Double,
+        BaseType::Float,
+        BaseType::Int,
+        BaseType::Long,
+        BaseType::Short,
+        BaseType::Boolean,
+    ];
+
+    fn random_field_type(rng: &mut Lcg) -> FieldType {
+        match rng.next(4) {
+            0 | 1 => FieldType::Base(BASE_TYPES[rng.next(BASE_TYPES.len())].clone()),
+            2 => {
+                let segments: Vec<String> = (0..1 + rng.next(3))
+                    .map(|_| {
+                        (0..1 + rng.next(5))
+                            .map(|_| ["a", "Z", "$", "_", "1", "é"][rng.next(6)])
+                            .collect()
+                    })
+                    .collect();
+                FieldType::Object(segments.join("/"))
+            }
+            _ => FieldType::Array(Box::new(random_field_type(rng))),
+        }
+    }
+
+    #[test]
+    fn field_types_round_trip_through_descriptors() {
+        let mut rng = Lcg(42);
+        for _ in 0..10_000 {
+            let field_type = random_field_type(&mut rng);
+            let descriptor = field_type.to_descriptor();
+            assert_eq!(Ok(field_type), FieldType::parse(&descriptor));
+        }
+    }
+
+    #[test]
+    fn parsed_descriptors_are_canonical() {
+        let alphabet = ['B', 'I', 'J', 'Z', 'L', ';', '/', '[', 'a', 'i', 'V', '.'];
+        let mut rng = Lcg(7);
+        for _ in 0..10_000 {
+            let input: String = (0..1 + rng.next(8))
+                .map(|_| alphabet[rng.next(alphabet.len())])
+                .collect();
+            match FieldType::parse(&input) {
+                Ok(field_type) => assert_eq!(input, field_type.to_descriptor()),
+                Err(err) => {
+                    assert_eq!(input, err.descriptor);
+                    assert!(err.position <= input.len());
+                    assert!(input.ends_with(err.remaining_input()));
+                }
+            }
+        }
+    }
 }
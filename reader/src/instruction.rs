@@ -8,7 +8,7 @@ pub enum Instruction {
     Aaload,
     Aastore,
     Aconst_null,
-    Aload(u8),
+    Aload(u16),
     Aload_0,
     Aload_1,
     Aload_2,
@@ -16,7 +16,7 @@ pub enum Instruction {
     Anewarray(u16),
     Areturn,
     Arraylength,
-    Astore(u8),
+    Astore(u16),
     Astore_0,
     Astore_1,
     Astore_2,
@@ -24,7 +24,7 @@ pub enum Instruction {
     Athrow,
     Baload,
     Bastore,
-    Bipush(u8),
+    Bipush(i8),
     Caload,
     Castore,
     Checkcast(u16),
@@ -39,7 +39,7 @@ pub enum Instruction {
     Dconst_0,
     Dconst_1,
     Ddiv,
-    Dload(u8),
+    Dload(u16),
     Dload_0,
     Dload_1,
     Dload_2,
@@ -48,7 +48,7 @@ pub enum Instruction {
     Dneg,
     Drem,
     Dreturn,
-    Dstore(u8),
+    Dstore(u16),
     Dstore_0,
     Dstore_1,
     Dstore_2,
@@ -72,7 +72,7 @@ pub enum Instruction {
     Fconst_1,
     Fconst_2,
     Fdiv,
-    Fload(u8),
+    Fload(u16),
     Fload_0,
     Fload_1,
     Fload_2,
@@ -81,7 +81,7 @@ pub enum Instruction {
     Fneg,
     Frem,
     Freturn,
-    Fstore(u8),
+    Fstore(u16),
     Fstore_0,
     Fstore_1,
     Fstore_2,
@@ -89,8 +89,8 @@ pub enum Instruction {
     Fsub,
     Getfield(u16),
     Getstatic(u16),
-    Goto(u16),
-    Goto_w,
+    Goto(u32),
+    Goto_w(u32),
     I2b,
     I2c,
     I2d,
@@ -109,24 +109,24 @@ pub enum Instruction {
     Iconst_4,
     Iconst_5,
     Idiv,
-    If_acmpeq(u16),
-    If_acmpne(u16),
-    If_icmpeq(u16),
-    If_icmpne(u16),
-    If_icmplt(u16),
-    If_icmpge(u16),
-    If_icmpgt(u16),
-    If_icmple(u16),
-    Ifeq(u16),
-    Ifne(u16),
-    Iflt(u16),
-    Ifge(u16),
-    Ifgt(u16),
-    Ifle(u16),
-    Ifnonnull(u16),
-    Ifnull(u16),
-    Iinc(u8, i8),
-    Iload(u8),
+    If_acmpeq(u32),
+    If_acmpne(u32),
+    If_icmpeq(u32),
+    If_icmpne(u32),
+    If_icmplt(u32),
+    If_icmpge(u32),
+    If_icmpgt(u32),
+    If_icmple(u32),
+    Ifeq(u32),
+    Ifne(u32),
+    Iflt(u32),
+    Ifge(u32),
+    Ifgt(u32),
+    Ifle(u32),
+    Ifnonnull(u32),
+    Ifnull(u32),
+    Iinc(u16, i16),
+    Iload(u16),
     Iload_0,
     Iload_1,
     Iload_2,
@@ -144,7 +144,7 @@ pub enum Instruction {
     Ireturn,
     Ishl,
     Ishr,
-    Istore(u8),
+    Istore(u16),
     Istore_0,
     Istore_1,
     Istore_2,
@@ -152,8 +152,8 @@ pub enum Instruction {
     Isub,
     Iushr,
     Ixor,
-    Jsr(u16),
-    Jsr_w,
+    Jsr(u32),
+    Jsr_w(u32),
     L2d,
     L2f,
     L2i,
@@ -168,20 +168,20 @@ pub enum Instruction {
     Ldc_w(u16),
     Ldc2_w(u16),
     Ldiv,
-    Lload(u8),
+    Lload(u16),
     Lload_0,
     Lload_1,
     Lload_2,
     Lload_3,
     Lmul,
     Lneg,
-    Lookupswitch,
+    Lookupswitch(SwitchTable),
     Lor,
     Lrem,
     Lreturn,
     Lshl,
     Lshr,
-    Lstore(u8),
+    Lstore(u16),
     Lstore_0,
     Lstore_1,
     Lstore_2,
@@ -199,14 +199,106 @@ pub enum Instruction {
     Pop2,
     Putfield(u16),
     Putstatic(u16),
-    Ret(u8),
+    Ret(u16),
     Return,
     Saload,
     Sastore,
     Sipush(i16),
     Swap,
-    Tableswitch,
-    Wide,
+    Tableswitch(SwitchTable),
+}
+
+/// The operands of a `tableswitch` or a `lookupswitch`. Their length depends on the number of
+/// cases, so only their position in the code is kept, to keep [Instruction] small and `Copy`;
+/// the jump targets are read from the code by [SwitchTable::jump_address].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct SwitchTable {
+    /// The address of the switch instruction, to which the offsets are relative
+    pub instruction_address: u32,
+    /// The address to jump to when no case matches
+    pub default_address: u32,
+    /// The address of the operands after the default offset: `low` for a `tableswitch`,
+    /// `npairs` for a `lookupswitch`
+    operands_address: u32,
+    is_lookup: bool,
+}
+
+impl SwitchTable {
+    /// Returns the address to jump to for the given key. Must be invoked with the same
+    /// code from which the instruction was parsed.
+    pub fn jump_address(&self, raw_code: &[u8], key: i32) -> Result<u32, ClassReaderError> {
+        let mut address = self.operands_address as usize;
+        let offset = if self.is_lookup {
+            let pairs_count = Instruction::read_i32(raw_code, &mut address)? as usize;
+            Self::lookup_offset(raw_code, address, pairs_count, key)?
+        } else {
+            let low = Instruction::read_i32(raw_code, &mut address)?;
+            let high = Instruction::read_i32(raw_code, &mut address)?;
+            if key < low || key > high {
+                None
+            } else {
+                let mut offset_address = address + 4 * (key.abs_diff(low) as usize);
+                Some(Instruction::read_i32(raw_code, &mut offset_address)?)
+            }
+        };
+        match offset {
+            Some(offset) => Instruction::jump_address(
+                self.instruction_address as usize,
+                offset,
+                self.instruction_address as usize,
+            ),
+            None => Ok(self.default_address),
+        }
+    }
+
+    /// Returns all the addresses to which the switch can jump, including the default one
+    pub fn jump_addresses(&self, raw_code: &[u8]) -> Result<Vec<u32>, ClassReaderError> {
+        let mut address = self.operands_address as usize;
+        let (count, stride) = if self.is_lookup {
+            let pairs_count = Instruction::read_i32(raw_code, &mut address)? as usize;
+            // Skips the key of the first pair
+            address += 4;
+            (pairs_count, 8)
+        } else {
+            let low = Instruction::read_i32(raw_code, &mut address)?;
+            let high = Instruction::read_i32(raw_code, &mut address)?;
+            (high.abs_diff(low) as usize + 1, 4)
+        };
+        let mut addresses = vec![self.default_address];
+        for index in 0..count {
+            let mut offset_address = address + stride * index;
+            let offset = Instruction::read_i32(raw_code, &mut offset_address)?;
+            addresses.push(Instruction::jump_address(
+                self.instruction_address as usize,
+                offset,
+                self.instruction_address as usize,
+            )?);
+        }
+        Ok(addresses)
+    }
+
+    /// Binary search of the key in the match-offset pairs, which are sorted by key
+    fn lookup_offset(
+        raw_code: &[u8],
+        pairs_address: usize,
+        pairs_count: usize,
+        key: i32,
+    ) -> Result<Option<i32>, ClassReaderError> {
+        let (mut low, mut high) = (0, pairs_count);
+        while low < high {
+            let middle = low + (high - low) / 2;
+            let mut pair_address = pairs_address + 8 * middle;
+            let pair_key = Instruction::read_i32(raw_code, &mut pair_address)?;
+            match pair_key.cmp(&key) {
+                std::cmp::Ordering::Less => low = middle + 1,
+                std::cmp::Ordering::Greater => high = middle,
+                std::cmp::Ordering::Equal => {
+                    return Ok(Some(Instruction::read_i32(raw_code, &mut pair_address)?))
+                }
+            }
+        }
+        Ok(None)
+    }
 }
 
 /// Possible arguments of instruction `newarray`
@@ -232,7 +324,7 @@ impl Instruction {
             0x32 => Instruction::Aaload,
             0x53 => Instruction::Aastore,
             0x01 => Instruction::Aconst_null,
-            0x19 => Instruction::Aload(Self::read_u8(raw_code, &mut address)?),
+            0x19 => Instruction::Aload(u16::from(Self::read_u8(raw_code, &mut address)?)),
             0x2a => Instruction::Aload_0,
             0x2b => Instruction::Aload_1,
             0x2c => Instruction::Aload_2,
@@ -240,7 +332,7 @@ impl Instruction {
             0xbd => Instruction::Anewarray(Self::read_u16(raw_code, &mut address)?),
             0xb0 => Instruction::Areturn,
             0xbe => Instruction::Arraylength,
-            0x3a => Instruction::Astore(Self::read_u8(raw_code, &mut address)?),
+            0x3a => Instruction::Astore(u16::from(Self::read_u8(raw_code, &mut address)?)),
             0x4b => Instruction::Astore_0,
             0x4c => Instruction::Astore_1,
             0x4d => Instruction::Astore_2,
@@ -248,7 +340,7 @@ impl Instruction {
             0xbf => Instruction::Athrow,
             0x33 => Instruction::Baload,
             0x54 => Instruction::Bastore,
-            0x10 => Instruction::Bipush(Self::read_u8(raw_code, &mut address)?),
+            0x10 => Instruction::Bipush(Self::read_i8(raw_code, &mut address)?),
             0x34 => Instruction::Caload,
             0x55 => Instruction::Castore,
             0xc0 => Instruction::Checkcast(Self::read_u16(raw_code, &mut address)?),
@@ -263,7 +355,7 @@ impl Instruction {
             0x0e => Instruction::Dconst_0,
             0x0f => Instruction::Dconst_1,
             0x6f => Instruction::Ddiv,
-            0x18 => Instruction::Dload(Self::read_u8(raw_code, &mut address)?),
+            0x18 => Instruction::Dload(u16::from(Self::read_u8(raw_code, &mut address)?)),
             0x26 => Instruction::Dload_0,
             0x27 => Instruction::Dload_1,
             0x28 => Instruction::Dload_2,
@@ -272,7 +364,7 @@ impl Instruction {
             0x77 => Instruction::Dneg,
             0x73 => Instruction::Drem,
             0xaf => Instruction::Dreturn,
-            0x39 => Instruction::Dstore(Self::read_u8(raw_code, &mut address)?),
+            0x39 => Instruction::Dstore(u16::from(Self::read_u8(raw_code, &mut address)?)),
             0x47 => Instruction::Dstore_0,
             0x48 => Instruction::Dstore_1,
             0x49 => Instruction::Dstore_2,
@@ -296,7 +388,7 @@ impl Instruction {
             0x0c => Instruction::Fconst_1,
             0x0d => Instruction::Fconst_2,
             0x6e => Instruction::Fdiv,
-            0x17 => Instruction::Fload(Self::read_u8(raw_code, &mut address)?),
+            0x17 => Instruction::Fload(u16::from(Self::read_u8(raw_code, &mut address)?)),
             0x22 => Instruction::Fload_0,
             0x23 => Instruction::Fload_1,
             0x24 => Instruction::Fload_2,
@@ -305,7 +397,7 @@ impl Instruction {
             0x76 => Instruction::Fneg,
             0x72 => Instruction::Frem,
             0xae => Instruction::Freturn,
-            0x38 => Instruction::Fstore(Self::read_u8(raw_code, &mut address)?),
+            0x38 => Instruction::Fstore(u16::from(Self::read_u8(raw_code, &mut address)?)),
             0x43 => Instruction::Fstore_0,
             0x44 => Instruction::Fstore_1,
             0x45 => Instruction::Fstore_2,
@@ -314,7 +406,7 @@ impl Instruction {
             0xb4 => Instruction::Getfield(Self::read_u16(raw_code, &mut address)?),
             0xb2 => Instruction::Getstatic(Self::read_u16(raw_code, &mut address)?),
             0xa7 => Instruction::Goto(Self::read_offset(raw_code, &mut address)?),
-            0xc8 => todo!("OpCode::Goto_w"),
+            0xc8 => Instruction::Goto_w(Self::read_wide_offset(raw_code, &mut address)?),
             0x91 => Instruction::I2b,
             0x92 => Instruction::I2c,
             0x87 => Instruction::I2d,
@@ -350,10 +442,10 @@ impl Instruction {
             0xc7 => Instruction::Ifnonnull(Self::read_offset(raw_code, &mut address)?),
             0xc6 => Instruction::Ifnull(Self::read_offset(raw_code, &mut address)?),
             0x84 => Instruction::Iinc(
-                Self::read_u8(raw_code, &mut address)?,
-                Self::read_i8(raw_code, &mut address)?,
+                u16::from(Self::read_u8(raw_code, &mut address)?),
+                i16::from(Self::read_i8(raw_code, &mut address)?),
             ),
-            0x15 => Instruction::Iload(Self::read_u8(raw_code, &mut address)?),
+            0x15 => Instruction::Iload(u16::from(Self::read_u8(raw_code, &mut address)?)),
             0x1a => Instruction::Iload_0,
             0x1b => Instruction::Iload_1,
             0x1c => Instruction::Iload_2,
@@ -388,7 +480,7 @@ impl Instruction {
             0xac => Instruction::Ireturn,
             0x78 => Instruction::Ishl,
             0x7a => Instruction::Ishr,
-            0x36 => Instruction::Istore(Self::read_u8(raw_code, &mut address)?),
+            0x36 => Instruction::Istore(u16::from(Self::read_u8(raw_code, &mut address)?)),
             0x3b => Instruction::Istore_0,
             0x3c => Instruction::Istore_1,
             0x3d => Instruction::Istore_2,
@@ -397,7 +489,7 @@ impl Instruction {
             0x7c => Instruction::Iushr,
             0x82 => Instruction::Ixor,
             0xa8 => Instruction::Jsr(Self::read_offset(raw_code, &mut address)?),
-            0xc9 => todo!("OpCode::Jsr_w"),
+            0xc9 => Instruction::Jsr_w(Self::read_wide_offset(raw_code, &mut address)?),
             0x8a => Instruction::L2d,
             0x89 => Instruction::L2f,
             0x88 => Instruction::L2i,
@@ -412,20 +504,20 @@ impl Instruction {
             0x13 => Instruction::Ldc_w(Self::read_u16(raw_code, &mut address)?),
             0x14 => Instruction::Ldc2_w(Self::read_u16(raw_code, &mut address)?),
             0x6d => Instruction::Ldiv,
-            0x16 => Instruction::Lload(Self::read_u8(raw_code, &mut address)?),
+            0x16 => Instruction::Lload(u16::from(Self::read_u8(raw_code, &mut address)?)),
             0x1e => Instruction::Lload_0,
             0x1f => Instruction::Lload_1,
             0x20 => Instruction::Lload_2,
             0x21 => Instruction::Lload_3,
             0x69 => Instruction::Lmul,
             0x75 => Instruction::Lneg,
-            0xab => todo!("OpCode::Lookupswitch"),
+            0xab => Instruction::Lookupswitch(Self::parse_switch(raw_code, &mut address, true)?),
             0x81 => Instruction::Lor,
             0x71 => Instruction::Lrem,
             0xad => Instruction::Lreturn,
             0x79 => Instruction::Lshl,
             0x7b => Instruction::Lshr,
-            0x37 => Instruction::Lstore(Self::read_u8(raw_code, &mut address)?),
+            0x37 => Instruction::Lstore(u16::from(Self::read_u8(raw_code, &mut address)?)),
             0x3f => Instruction::Lstore_0,
             0x40 => Instruction::Lstore_1,
             0x41 => Instruction::Lstore_2,
@@ -464,14 +556,14 @@ impl Instruction {
             0x58 => Instruction::Pop2,
             0xb5 => Instruction::Putfield(Self::read_u16(raw_code, &mut address)?),
             0xb3 => Instruction::Putstatic(Self::read_u16(raw_code, &mut address)?),
-            0xa9 => Instruction::Ret(Self::read_u8(raw_code, &mut address)?),
+            0xa9 => Instruction::Ret(u16::from(Self::read_u8(raw_code, &mut address)?)),
             0xb1 => Instruction::Return,
             0x35 => Instruction::Saload,
             0x56 => Instruction::Sastore,
             0x11 => Instruction::Sipush(Self::read_i16(raw_code, &mut address)?),
             0x5f => Instruction::Swap,
-            0xaa => todo!("OpCode::Tableswitch"),
-            0xc4 => todo!("OpCode::Wide"),
+            0xaa => Instruction::Tableswitch(Self::parse_switch(raw_code, &mut address, false)?),
+            0xc4 => Self::parse_wide(raw_code, &mut address)?,
             _ => {
                 return Err(ClassReaderError::invalid_class_data(format!(
                     "invalid op code: {op_byte:#04x} at address {address}"
@@ -482,8 +574,35 @@ impl Instruction {
         Ok((op_code, address))
     }
 
-    /// Parses all instructions in the given raw code.
-    pub(crate) fn parse_instructions(
+    /// Parses the instruction modified by a `wide` prefix, whose local variable index (and
+    /// constant, for `iinc`) takes two bytes rather than one. The result is the same instruction
+    /// that would be parsed without the prefix, since the operands are always stored as `u16`.
+    fn parse_wide(raw_code: &[u8], address: &mut usize) -> Result<Instruction, ClassReaderError> {
+        let op_byte = Self::read_u8(raw_code, address)?;
+        let index = Self::read_u16(raw_code, address)?;
+        Ok(match op_byte {
+            0x15 => Instruction::Iload(index),
+            0x16 => Instruction::Lload(index),
+            0x17 => Instruction::Fload(index),
+            0x18 => Instruction::Dload(index),
+            0x19 => Instruction::Aload(index),
+            0x36 => Instruction::Istore(index),
+            0x37 => Instruction::Lstore(index),
+            0x38 => Instruction::Fstore(index),
+            0x39 => Instruction::Dstore(index),
+            0x3a => Instruction::Astore(index),
+            0xa9 => Instruction::Ret(index),
+            0x84 => Instruction::Iinc(index, Self::read_i16(raw_code, address)?),
+            _ => {
+                return Err(ClassReaderError::invalid_class_data(format!(
+                    "invalid op code after wide: {op_byte:#04x} at address {address}"
+                )))
+            }
+        })
+    }
+
+    /// Parses all instructions in the given raw code, returning them with their address
+    pub fn parse_instructions(
         raw_code: &[u8],
     ) -> Result<Vec<(usize, Instruction)>, ClassReaderError> {
         let mut instructions: Vec<(usize, Self)> = Vec::new();
@@ -498,6 +617,46 @@ impl Instruction {
         Ok(instructions)
     }
 
+    /// Parses the operands of a `tableswitch` or `lookupswitch`, skipping over the table
+    fn parse_switch(
+        raw_code: &[u8],
+        address: &mut usize,
+        is_lookup: bool,
+    ) -> Result<SwitchTable, ClassReaderError> {
+        let instruction_address = *address - 1;
+        // The operands are aligned to a multiple of four bytes from the start of the code
+        *address = address.next_multiple_of(4);
+        let default_offset = Self::read_i32(raw_code, address)?;
+        let default_address =
+            Self::jump_address(instruction_address, default_offset, instruction_address)?;
+        let operands_address = *address;
+
+        let table_size = if is_lookup {
+            let pairs_count = Self::read_i32(raw_code, address)?;
+            usize::try_from(pairs_count).ok().map(|count| 8 * count)
+        } else {
+            let low = Self::read_i32(raw_code, address)?;
+            let high = Self::read_i32(raw_code, address)?;
+            (low <= high).then(|| 4 * (high.abs_diff(low) as usize + 1))
+        };
+        let end_address = table_size
+            .map(|table_size| *address + table_size)
+            .filter(|&end_address| end_address <= raw_code.len())
+            .ok_or_else(|| {
+                ClassReaderError::invalid_class_data(format!(
+                    "invalid switch table at address {instruction_address}"
+                ))
+            })?;
+        *address = end_address;
+
+        Ok(SwitchTable {
+            instruction_address: instruction_address as u32,
+            default_address,
+            operands_address: operands_address as u32,
+            is_lookup,
+        })
+    }
+
     fn byte_at(raw_code: &[u8], address: usize) -> Result<u8, ClassReaderError> {
         let op_byte = *raw_code
             .get(address)
@@ -519,7 +678,7 @@ impl Instruction {
 
     fn read_i8(raw_code: &[u8], address: &mut usize) -> Result<i8, ClassReaderError> {
         let value = Self::read_u8(raw_code, address)?;
-        Ok(unsafe { std::mem::transmute(value) })
+        Ok(value as i8)
     }
 
     fn read_u16(raw_code: &[u8], address: &mut usize) -> Result<u16, ClassReaderError> {
@@ -530,17 +689,43 @@ impl Instruction {
 
     fn read_i16(raw_code: &[u8], address: &mut usize) -> Result<i16, ClassReaderError> {
         let value = Self::read_u16(raw_code, address)?;
-        Ok(unsafe { std::mem::transmute(value) })
+        Ok(value as i16)
+    }
+
+    fn read_i32(raw_code: &[u8], address: &mut usize) -> Result<i32, ClassReaderError> {
+        let high = Self::read_u16(raw_code, address)? as u32;
+        let low = Self::read_u16(raw_code, address)? as u32;
+        Ok(((high << 16) | low) as i32)
     }
 
-    fn read_offset(raw_code: &[u8], address: &mut usize) -> Result<u16, ClassReaderError> {
+    /// Reads a 16 bit branch offset, returning the absolute address of the jump target
+    fn read_offset(raw_code: &[u8], address: &mut usize) -> Result<u32, ClassReaderError> {
         let instruction_address = *address - 1;
         let offset = Self::read_i16(raw_code, address)?;
-        let jump_address = (instruction_address as i32) + (offset as i32);
-        u16::try_from(jump_address).map_err(|_| {
-            ClassReaderError::invalid_class_data(format!(
-                "invalid jump offset at address {address}"
-            ))
-        })
+        Self::jump_address(instruction_address, i32::from(offset), *address)
+    }
+
+    /// Reads a 32 bit branch offset, as used by `goto_w` and `jsr_w`, returning the absolute
+    /// address of the jump target
+    fn read_wide_offset(raw_code: &[u8], address: &mut usize) -> Result<u32, ClassReaderError> {
+        let instruction_address = *address - 1;
+        let offset = Self::read_i32(raw_code, address)?;
+        Self::jump_address(instruction_address, offset, *address)
+    }
+
+    fn jump_address(
+        instruction_address: usize,
+        offset: i32,
+        address: usize,
+    ) -> Result<u32, ClassReaderError> {
+        i64::try_from(instruction_address)
+            .ok()
+            .map(|instruction_address| instruction_address + i64::from(offset))
+            .and_then(|jump_address| u32::try_from(jump_address).ok())
+            .ok_or_else(|| {
+                ClassReaderError::invalid_class_data(format!(
+                    "invalid jump offset at address {address}"
+                ))
+            })
     }
 }
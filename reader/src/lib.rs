@@ -1,7 +1,8 @@
 #[macro_use]
 extern crate bitflags;
 
-mod attribute;
+pub mod attribute;
+pub mod bootstrap_method;
 mod buffer;
 pub mod class_access_flags;
 pub mod class_file;
@@ -19,5 +20,7 @@ pub mod line_number;
 pub mod line_number_table;
 pub mod method_descriptor;
 pub mod method_flags;
+pub mod method_handle_kind;
 pub mod program_counter;
+pub mod stack_map_table;
 pub mod type_conversion;
@@ -1,11 +1,8 @@
-use std::{fmt, fmt::Formatter, str::Chars};
+use std::{fmt, fmt::Formatter};
 
 use itertools::Itertools;
 
-use crate::{
-    class_reader_error::{ClassReaderError, ClassReaderError::InvalidTypeDescriptor},
-    field_type::FieldType,
-};
+use crate::field_type::{DescriptorParser, FieldType, TypeDescriptorError};
 
 /// Models the signature of a method, i.e. the type of the parameters it takes and the type
 /// of the return value
@@ -29,58 +26,39 @@ impl fmt::Display for MethodDescriptor {
 impl MethodDescriptor {
     /// Parses a method descriptor as specified in the JVM specs:
     /// https://docs.oracle.com/javase/specs/jvms/se7/html/jvms-4.html#jvms-4.3.3
-    pub fn parse(descriptor: &str) -> Result<MethodDescriptor, ClassReaderError> {
-        let mut chars = descriptor.chars();
-        match chars.next() {
-            Some('(') => {
-                let parameters = Self::parse_parameters(descriptor, &mut chars)?;
-                if Some(')') == chars.next() {
-                    let return_type = Self::parse_return_type(descriptor, &mut chars)?;
-                    Ok(MethodDescriptor {
-                        parameters,
-                        return_type,
-                    })
-                } else {
-                    Err(InvalidTypeDescriptor(descriptor.to_string()))
-                }
-            }
-            _ => Err(InvalidTypeDescriptor(descriptor.to_string())),
-        }
-    }
-
-    fn parse_parameters(
-        descriptor: &str,
-        chars: &mut Chars,
-    ) -> Result<Vec<FieldType>, ClassReaderError> {
+    pub fn parse(descriptor: &str) -> Result<MethodDescriptor, TypeDescriptorError> {
+        let mut parser = DescriptorParser::new(descriptor);
+        parser.expect('(')?;
         let mut parameters = Vec::new();
-        loop {
-            match chars.clone().next() {
-                Some(')') => return Ok(parameters),
-                Some(_) => {
-                    let param = FieldType::parse_from(descriptor, chars)?;
-                    parameters.push(param);
-                }
-                None => return Err(InvalidTypeDescriptor(descriptor.to_string())),
-            }
+        while parser.peek() != Some(')') {
+            parameters.push(parser.parse_field_type()?);
         }
-    }
-
-    fn parse_return_type(
-        descriptor: &str,
-        chars: &mut Chars,
-    ) -> Result<Option<FieldType>, ClassReaderError> {
-        match chars.clone().next() {
-            Some('V') => Ok(None),
-            Some(_) => {
-                let return_type = Some(FieldType::parse_from(descriptor, chars)?);
-                if chars.next().is_none() {
-                    Ok(return_type)
-                } else {
-                    Err(InvalidTypeDescriptor(descriptor.to_string()))
-                }
-            }
-            _ => Err(InvalidTypeDescriptor(descriptor.to_string())),
+        parser.expect(')')?;
+        let return_type = if parser.peek() == Some('V') {
+            parser.next();
+            None
+        } else {
+            Some(parser.parse_field_type()?)
+        };
+        parser.expect_end()?;
+        Ok(MethodDescriptor {
+            parameters,
+            return_type,
+        })
+    }
+
+    /// Formats the descriptor; this is the inverse of [MethodDescriptor::parse]
+    pub fn to_descriptor(&self) -> String {
+        let mut descriptor = String::from("(");
+        for parameter in self.parameters.iter() {
+            parameter.write_descriptor(&mut descriptor);
         }
+        descriptor.push(')');
+        match &self.return_type {
+            Some(return_type) => return_type.write_descriptor(&mut descriptor),
+            None => descriptor.push('V'),
+        }
+        descriptor
     }
 
     pub fn num_arguments(&self) -> usize {
@@ -91,8 +69,7 @@ impl MethodDescriptor {
 #[cfg(test)]
 mod tests {
     use crate::{
-        class_reader_error::ClassReaderError,
-        field_type::{BaseType, FieldType},
+        field_type::{BaseType, FieldType, TypeDescriptorErrorKind},
         method_descriptor::MethodDescriptor,
     };
 
@@ -116,13 +93,45 @@ mod tests {
         assert_cannot_parse("()JJ")
     }
 
+    #[test]
+    fn cannot_parse_invalid_descriptor_trash_after_void() {
+        assert_cannot_parse("()VJ")
+    }
+
     fn assert_cannot_parse(descriptor: &str) {
         assert!(matches!(
             MethodDescriptor::parse(descriptor),
-            Err(ClassReaderError::InvalidTypeDescriptor(s)) if s == descriptor
+            Err(err) if err.descriptor == descriptor
         ));
     }
 
+    #[test]
+    fn parse_errors_report_the_position() {
+        let err = MethodDescriptor::parse("(IJx)V").unwrap_err();
+        assert_eq!(3, err.position);
+        assert_eq!(TypeDescriptorErrorKind::UnexpectedCharacter('x'), err.kind);
+        assert_eq!("x)V", err.remaining_input());
+
+        let err = MethodDescriptor::parse("(J)").unwrap_err();
+        assert_eq!(3, err.position);
+        assert_eq!(TypeDescriptorErrorKind::UnexpectedEnd, err.kind);
+    }
+
+    #[test]
+    fn can_format_as_descriptor() {
+        for descriptor in [
+            "()V",
+            "(JI)D",
+            "(Ljava/lang/String;[[I)[J",
+            "([Ljava/lang/Object;)V",
+        ] {
+            assert_eq!(
+                descriptor,
+                MethodDescriptor::parse(descriptor).unwrap().to_descriptor()
+            );
+        }
+    }
+
     #[test]
     fn can_parse_primitives() {
         assert_eq!(
@@ -0,0 +1,32 @@
+/// The kind of a method handle constant, which determines the bytecode behavior of the handle.
+/// See https://docs.oracle.com/javase/specs/jvms/se8/html/jvms-5.html#jvms-5.4.3.5
+#[derive(Debug, Clone, Copy, PartialEq, Eq, strum_macros::Display)]
+pub enum MethodHandleKind {
+    GetField,
+    GetStatic,
+    PutField,
+    PutStatic,
+    InvokeVirtual,
+    InvokeStatic,
+    InvokeSpecial,
+    NewInvokeSpecial,
+    InvokeInterface,
+}
+
+impl MethodHandleKind {
+    /// Creates a kind from the `reference_kind` byte stored in the class file
+    pub fn from_reference_kind(reference_kind: u8) -> Option<MethodHandleKind> {
+        match reference_kind {
+            1 => Some(MethodHandleKind::GetField),
+            2 => Some(MethodHandleKind::GetStatic),
+            3 => Some(MethodHandleKind::PutField),
+            4 => Some(MethodHandleKind::PutStatic),
+            5 => Some(MethodHandleKind::InvokeVirtual),
+            6 => Some(MethodHandleKind::InvokeStatic),
+            7 => Some(MethodHandleKind::InvokeSpecial),
+            8 => Some(MethodHandleKind::NewInvokeSpecial),
+            9 => Some(MethodHandleKind::InvokeInterface),
+            _ => None,
+        }
+    }
+}
@@ -5,7 +5,7 @@ use std::{
 
 /// Models the program counter, i.e. the address of an instruction in the bytecode of a method
 #[derive(Debug, PartialEq, Eq, Clone, Copy, PartialOrd, Ord)]
-pub struct ProgramCounter(pub u16);
+pub struct ProgramCounter(pub u32);
 
 impl Display for ProgramCounter {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
@@ -0,0 +1,133 @@
+use std::borrow::Cow;
+
+use crate::program_counter::ProgramCounter;
+
+/// The `StackMapTable` attribute of a method's code, which declares the types of the locals
+/// and of the operand stack at the targets of the jumps and at the exception handlers.
+/// Frames are sorted by program counter.
+#[derive(Debug, Default, PartialEq)]
+pub struct StackMapTable<'a> {
+    frames: Vec<StackMapFrame<'a>>,
+}
+
+impl<'a> StackMapTable<'a> {
+    pub fn new(frames: Vec<StackMapFrame<'a>>) -> Self {
+        Self { frames }
+    }
+
+    pub fn frames(&self) -> &[StackMapFrame<'a>] {
+        &self.frames
+    }
+
+    pub fn into_owned(self) -> StackMapTable<'static> {
+        StackMapTable {
+            frames: self
+                .frames
+                .into_iter()
+                .map(StackMapFrame::into_owned)
+                .collect(),
+        }
+    }
+}
+
+/// A frame of the [StackMapTable]. Except for the full frames, they are expressed as
+/// differences with respect to the previous frame; the first frame is relative to the
+/// implicit one derived from the method's descriptor.
+#[derive(Debug, PartialEq, Clone)]
+pub struct StackMapFrame<'a> {
+    /// The address of the instruction to which the frame applies
+    pub pc: ProgramCounter,
+    pub kind: StackMapFrameKind<'a>,
+}
+
+impl<'a> StackMapFrame<'a> {
+    pub fn into_owned(self) -> StackMapFrame<'static> {
+        let kind = match self.kind {
+            StackMapFrameKind::Same => StackMapFrameKind::Same,
+            StackMapFrameKind::SameLocalsOneStackItem(item) => {
+                StackMapFrameKind::SameLocalsOneStackItem(item.into_owned())
+            }
+            StackMapFrameKind::Chop(count) => StackMapFrameKind::Chop(count),
+            StackMapFrameKind::Append(locals) => StackMapFrameKind::Append(
+                locals
+                    .into_iter()
+                    .map(VerificationType::into_owned)
+                    .collect(),
+            ),
+            StackMapFrameKind::Full { locals, stack } => StackMapFrameKind::Full {
+                locals: locals
+                    .into_iter()
+                    .map(VerificationType::into_owned)
+                    .collect(),
+                stack: stack
+                    .into_iter()
+                    .map(VerificationType::into_owned)
+                    .collect(),
+            },
+        };
+        StackMapFrame { pc: self.pc, kind }
+    }
+}
+
+/// The possible contents of a [StackMapFrame]
+#[derive(Debug, PartialEq, Clone)]
+pub enum StackMapFrameKind<'a> {
+    /// The same locals as the previous frame, and an empty stack
+    Same,
+    /// The same locals as the previous frame, and a stack with only the given entry
+    SameLocalsOneStackItem(VerificationType<'a>),
+    /// The locals of the previous frame without the last given number of ones,
+    /// and an empty stack
+    Chop(u8),
+    /// The locals of the previous frame plus the given ones, and an empty stack
+    Append(Vec<VerificationType<'a>>),
+    /// All the locals and the stack entries
+    Full {
+        locals: Vec<VerificationType<'a>>,
+        stack: Vec<VerificationType<'a>>,
+    },
+}
+
+/// The type of a local or of an entry in the stack, as declared in a [StackMapFrame].
+/// Longs and doubles take two slots in the locals and in the stack, but they are listed once.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum VerificationType<'a> {
+    /// An unusable slot
+    Top,
+    /// Any of `int`, `short`, `char`, `byte` and `boolean`
+    Integer,
+    Float,
+    Long,
+    Double,
+    Null,
+    /// The `this` of a constructor, before the constructor of the superclass is invoked
+    UninitializedThis,
+    /// An instance of the class, or array type, with the given name
+    Object(Cow<'a, str>),
+    /// An object created by the `new` instruction at the given address, whose constructor
+    /// has not been invoked yet
+    Uninitialized(ProgramCounter),
+}
+
+impl<'a> VerificationType<'a> {
+    pub fn into_owned(self) -> VerificationType<'static> {
+        match self {
+            VerificationType::Top => VerificationType::Top,
+            VerificationType::Integer => VerificationType::Integer,
+            VerificationType::Float => VerificationType::Float,
+            VerificationType::Long => VerificationType::Long,
+            VerificationType::Double => VerificationType::Double,
+            VerificationType::Null => VerificationType::Null,
+            VerificationType::UninitializedThis => VerificationType::UninitializedThis,
+            VerificationType::Object(class_name) => {
+                VerificationType::Object(Cow::Owned(class_name.into_owned()))
+            }
+            VerificationType::Uninitialized(pc) => VerificationType::Uninitialized(pc),
+        }
+    }
+
+    /// Whether the type takes two slots, in the locals or in the stack
+    pub fn is_wide(&self) -> bool {
+        matches!(self, VerificationType::Long | VerificationType::Double)
+    }
+}
@@ -1,3 +1,7 @@
+use std::fmt::Display;
+
+use thiserror::Error;
+
 /// Simple utility trait that models the conversion of various integer types into usize.
 /// Assumes that these types are all smaller than usize, or panics.
 pub trait ToUsizeSafe {
@@ -6,13 +10,13 @@ pub trait ToUsizeSafe {
 
 impl ToUsizeSafe for u8 {
     fn into_usize_safe(self) -> usize {
-        usize::try_from(self).expect("usize should have at least 8 bits")
+        usize::from(self)
     }
 }
 
 impl ToUsizeSafe for u16 {
     fn into_usize_safe(self) -> usize {
-        usize::try_from(self).expect("usize should have at least 16 bits")
+        usize::from(self)
     }
 }
 
@@ -27,3 +31,64 @@ impl ToUsizeSafe for i32 {
         usize::try_from(self).expect("usize should have at least 64 bits")
     }
 }
+
+/// Error returned by [CheckedNarrowing] when a value does not fit in the target type
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[error("value {value} does not fit in type {target_type}")]
+pub struct NarrowingError {
+    pub value: String,
+    pub target_type: &'static str,
+}
+
+/// Utility trait that models the conversion of an integer into a narrower, or differently
+/// signed, integer type. Unlike an `as` cast, it never truncates silently: values that do not
+/// fit in the target type are reported as an error.
+pub trait CheckedNarrowing: Copy + Display {
+    fn narrow<T: TryFrom<Self>>(self) -> Result<T, NarrowingError> {
+        T::try_from(self).map_err(|_| NarrowingError {
+            value: self.to_string(),
+            target_type: std::any::type_name::<T>(),
+        })
+    }
+}
+
+impl CheckedNarrowing for u8 {}
+impl CheckedNarrowing for u16 {}
+impl CheckedNarrowing for u32 {}
+impl CheckedNarrowing for u64 {}
+impl CheckedNarrowing for usize {}
+impl CheckedNarrowing for i8 {}
+impl CheckedNarrowing for i16 {}
+impl CheckedNarrowing for i32 {}
+impl CheckedNarrowing for i64 {}
+
+#[cfg(test)]
+mod tests {
+    use crate::type_conversion::{CheckedNarrowing, NarrowingError};
+
+    #[test]
+    fn narrowing_values_that_fit() {
+        assert_eq!(Ok(65535u16), 65535usize.narrow());
+        assert_eq!(Ok(-1i8), (-1i32).narrow());
+        assert_eq!(Ok(7usize), 7i32.narrow());
+    }
+
+    #[test]
+    fn narrowing_values_that_do_not_fit() {
+        assert_eq!(
+            Err(NarrowingError {
+                value: "65536".to_string(),
+                target_type: "u16",
+            }),
+            65536usize.narrow::<u16>()
+        );
+        assert_eq!(
+            Err(NarrowingError {
+                value: "-1".to_string(),
+                target_type: "usize",
+            }),
+            (-1i32).narrow::<usize>()
+        );
+        assert!(u32::MAX.narrow::<i32>().is_err());
+    }
+}
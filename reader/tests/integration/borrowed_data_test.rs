@@ -0,0 +1,80 @@
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    borrow::Cow,
+    cell::Cell,
+};
+
+use rjvm_reader::class_reader;
+
+/// Counts the allocations made by each thread, so that tests running in parallel do not
+/// interfere with each other
+struct CountingAllocator;
+
+thread_local! {
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.with(|allocations| allocations.set(allocations.get() + 1));
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+fn count_allocations<T>(callback: impl FnOnce() -> T) -> (T, usize) {
+    let before = ALLOCATIONS.with(Cell::get);
+    let result = callback();
+    (result, ALLOCATIONS.with(Cell::get) - before)
+}
+
+#[test_log::test]
+fn read_class_borrows_from_buffer() {
+    let bytes = include_bytes!("../resources/rjvm/Complex.class").to_vec();
+    let class = class_reader::read_buffer(&bytes).unwrap();
+
+    assert!(matches!(class.name, Cow::Borrowed("rjvm/Complex")));
+    assert!(class
+        .fields
+        .iter()
+        .all(|field| matches!(field.name, Cow::Borrowed(_))));
+    let code = class.methods[0]
+        .code
+        .as_ref()
+        .expect("constructor should have code");
+    assert!(matches!(code.code, Cow::Borrowed(_)));
+}
+
+#[test_log::test]
+fn owned_class_does_not_depend_on_buffer() {
+    let bytes = include_bytes!("../resources/rjvm/Complex.class").to_vec();
+    let borrowed_class_display = class_reader::read_buffer(&bytes).unwrap().to_string();
+    let owned_class = class_reader::read_buffer(&bytes).unwrap().into_owned();
+    drop(bytes);
+
+    assert!(matches!(owned_class.name, Cow::Owned(_)));
+    assert_eq!("rjvm/Complex", owned_class.name);
+    assert_eq!(borrowed_class_display, owned_class.to_string());
+}
+
+#[test_log::test]
+fn borrowing_class_allocates_less_than_copying_it() {
+    let bytes = include_bytes!("../resources/rjvm/Complex.class").to_vec();
+    let (borrowed_class, borrowed_allocations) =
+        count_allocations(|| class_reader::read_buffer(&bytes).unwrap());
+    let (_, owned_allocations) =
+        count_allocations(|| class_reader::read_buffer(&bytes).unwrap().into_owned());
+
+    // Copying needs an allocation at least for each name and each code attribute
+    let copied_data = 1 + borrowed_class.fields.len() + 2 * borrowed_class.methods.len();
+    assert!(
+        owned_allocations >= borrowed_allocations + copied_data,
+        "borrowing allocated {borrowed_allocations} times, copying {owned_allocations} times"
+    );
+}
@@ -16,38 +16,43 @@ fn can_read_constants() {
         vec!(
             ClassFileField {
                 flags: FieldFlags::PUBLIC | FieldFlags::STATIC | FieldFlags::FINAL,
-                name: "AN_INT".to_string(),
+                name: "AN_INT".into(),
                 type_descriptor: FieldType::Base(BaseType::Int),
                 constant_value: Some(FieldConstantValue::Int(2023)),
                 deprecated: false,
+                attributes: vec![],
             },
             ClassFileField {
                 flags: FieldFlags::PROTECTED | FieldFlags::STATIC | FieldFlags::FINAL,
-                name: "A_FLOAT".to_string(),
+                name: "A_FLOAT".into(),
                 type_descriptor: FieldType::Base(BaseType::Float),
                 constant_value: Some(FieldConstantValue::Float(20.23)),
                 deprecated: false,
+                attributes: vec![],
             },
             ClassFileField {
                 flags: FieldFlags::PRIVATE | FieldFlags::STATIC | FieldFlags::FINAL,
-                name: "A_LONG".to_string(),
+                name: "A_LONG".into(),
                 type_descriptor: FieldType::Base(BaseType::Long),
                 constant_value: Some(FieldConstantValue::Long(2023)),
                 deprecated: false,
+                attributes: vec![],
             },
             ClassFileField {
                 flags: FieldFlags::PUBLIC | FieldFlags::STATIC | FieldFlags::FINAL,
-                name: "A_DOUBLE".to_string(),
+                name: "A_DOUBLE".into(),
                 type_descriptor: FieldType::Base(BaseType::Double),
                 constant_value: Some(FieldConstantValue::Double(20.23)),
                 deprecated: false,
+                attributes: vec![],
             },
             ClassFileField {
                 flags: FieldFlags::PUBLIC | FieldFlags::STATIC | FieldFlags::FINAL,
-                name: "A_STRING".to_string(),
+                name: "A_STRING".into(),
                 type_descriptor: FieldType::Object("java/lang/String".to_string()),
-                constant_value: Some(FieldConstantValue::String("2023".to_string())),
+                constant_value: Some(FieldConstantValue::String("2023".into())),
                 deprecated: false,
+                attributes: vec![],
             }
         ),
         class.fields
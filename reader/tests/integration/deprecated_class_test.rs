@@ -1,5 +1,7 @@
 extern crate rjvm_reader;
 
+use rjvm_reader::attribute::Attribute;
+
 use crate::utils;
 use utils::read_class_from_bytes;
 
@@ -8,7 +10,7 @@ fn can_read_deprecated_attribute() {
     let class = read_class_from_bytes(include_bytes!("../resources/rjvm/DeprecatedClass.class"));
     assert!(class.deprecated);
 
-    class.fields.get(0).unwrap();
+    class.fields.first().unwrap();
 
     let field = class
         .fields
@@ -24,3 +26,50 @@ fn can_read_deprecated_attribute() {
         .expect("should find method");
     assert!(method.deprecated);
 }
+
+#[test_log::test]
+fn uninterpreted_attributes_are_preserved() {
+    let class = read_class_from_bytes(include_bytes!("../resources/rjvm/DeprecatedClass.class"));
+    assert_eq!(
+        vec!["RuntimeVisibleAnnotations"],
+        attribute_names(&class.attributes)
+    );
+
+    let field = class
+        .fields
+        .iter()
+        .find(|f| f.name == "deprecatedField")
+        .expect("should find field");
+    assert_eq!(
+        vec!["RuntimeVisibleAnnotations"],
+        attribute_names(&field.attributes)
+    );
+
+    let method = class
+        .methods
+        .iter()
+        .find(|m| m.name == "deprecatedMethod")
+        .expect("should find method");
+    assert_eq!(
+        vec!["RuntimeVisibleAnnotations"],
+        attribute_names(&method.attributes)
+    );
+    // One annotation, of type Ljava/lang/Deprecated; without any element-value pairs
+    let annotation = &method.attributes[0].bytes;
+    assert_eq!(6, annotation.len());
+    assert_eq!([0, 1], annotation[0..2]);
+    assert_eq!(
+        "Ljava/lang/Deprecated;",
+        class
+            .constants
+            .text_of(u16::from_be_bytes([annotation[2], annotation[3]]))
+            .unwrap()
+    );
+
+    let code = method.code.as_ref().expect("method should have code");
+    assert!(code.attributes.is_empty());
+}
+
+fn attribute_names<'a>(attributes: &'a [Attribute]) -> Vec<&'a str> {
+    attributes.iter().map(|attr| attr.name.as_ref()).collect()
+}
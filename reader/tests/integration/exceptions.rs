@@ -5,6 +5,7 @@ use rjvm_reader::{
     exception_table::{ExceptionTable, ExceptionTableEntry},
     method_flags::MethodFlags,
     program_counter::ProgramCounter,
+    stack_map_table::{StackMapFrame, StackMapFrameKind, StackMapTable, VerificationType},
 };
 use utils::read_class_from_bytes;
 
@@ -44,9 +45,37 @@ fn check_methods(class: &ClassFile) {
             ExceptionTableEntry {
                 range: ProgramCounter(18)..ProgramCounter(22),
                 handler_pc: ProgramCounter(25),
-                catch_class: Some("java/lang/IllegalStateException".to_string())
+                catch_class: Some("java/lang/IllegalStateException".into())
             }
         ]),
         class.methods[3].code.as_ref().unwrap().exception_table
     );
+    let code = class.methods[3].code.as_ref().unwrap();
+    assert_eq!(
+        Some(StackMapTable::new(vec![
+            StackMapFrame {
+                pc: ProgramCounter(11),
+                kind: StackMapFrameKind::SameLocalsOneStackItem(VerificationType::Object(
+                    "java/lang/Throwable".into()
+                )),
+            },
+            StackMapFrame {
+                pc: ProgramCounter(18),
+                kind: StackMapFrameKind::Same,
+            },
+            StackMapFrame {
+                pc: ProgramCounter(25),
+                kind: StackMapFrameKind::SameLocalsOneStackItem(VerificationType::Object(
+                    "java/lang/IllegalStateException".into()
+                )),
+            },
+            StackMapFrame {
+                pc: ProgramCounter(30),
+                kind: StackMapFrameKind::Same,
+            },
+        ])),
+        code.stack_map_table
+    );
+    // StackMapTable is interpreted, so it is not preserved as a raw attribute
+    assert!(code.attributes.is_empty());
 }
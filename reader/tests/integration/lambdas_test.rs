@@ -0,0 +1,63 @@
+extern crate rjvm_reader;
+
+use rjvm_reader::{
+    bootstrap_method::BootstrapMethod, constant_pool::ConstantPoolEntry,
+    method_handle_kind::MethodHandleKind,
+};
+
+use crate::utils;
+use utils::read_class_from_bytes;
+
+#[test_log::test]
+fn can_read_invokedynamic_constants_and_bootstrap_methods() {
+    let class = read_class_from_bytes(include_bytes!("../resources/rjvm/Lambdas.class"));
+
+    assert_eq!(1, class.bootstrap_methods.len());
+    let bootstrap_method: &BootstrapMethod = &class.bootstrap_methods[0];
+    assert_eq!(3, bootstrap_method.arguments.len());
+    assert!(class
+        .attributes
+        .iter()
+        .all(|a| a.name != "BootstrapMethods"));
+
+    let bootstrap_method_handle = class.constants.get(bootstrap_method.method_ref).unwrap();
+    let ConstantPoolEntry::MethodHandle(kind, method_ref) = bootstrap_method_handle else {
+        panic!("expected a method handle but got {bootstrap_method_handle:?}");
+    };
+    assert_eq!(MethodHandleKind::InvokeStatic, *kind);
+    assert!(class
+        .constants
+        .text_of(*method_ref)
+        .unwrap()
+        .starts_with("java/lang/invoke/LambdaMetafactory.metafactory: "));
+
+    assert_eq!(
+        "(I)I",
+        class
+            .constants
+            .text_of(bootstrap_method.arguments[0])
+            .unwrap()
+    );
+    assert_eq!(
+        "InvokeStatic rjvm/Lambdas.lambda$applyTwice$0: (II)I",
+        class
+            .constants
+            .text_of(bootstrap_method.arguments[1])
+            .unwrap()
+    );
+
+    let call_site = (1..=u16::MAX)
+        .map(|index| class.constants.get(index))
+        .find_map(|entry| match entry {
+            Ok(ConstantPoolEntry::InvokeDynamic(bootstrap_method_index, name_and_type)) => {
+                Some((*bootstrap_method_index, *name_and_type))
+            }
+            _ => None,
+        })
+        .expect("should find an invokedynamic constant");
+    assert_eq!(0, call_site.0);
+    assert_eq!(
+        "apply: (I)Lrjvm/Lambdas$Operation;",
+        class.constants.text_of(call_site.1).unwrap()
+    );
+}
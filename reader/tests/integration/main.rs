@@ -1,6 +1,8 @@
 mod assertions;
+mod borrowed_data_test;
 mod constants_class_test;
 mod deprecated_class_test;
 mod exceptions;
+mod lambdas_test;
 mod pojo_class_test;
 mod utils;
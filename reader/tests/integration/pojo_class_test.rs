@@ -33,7 +33,7 @@ fn can_read_pojo_class_file() {
         vec!("java/lang/Cloneable", "java/io/Serializable"),
         class.interfaces
     );
-    assert_eq!(Some("Complex.java".to_string()), class.source_file);
+    assert_eq!(Some("Complex.java".into()), class.source_file);
 
     check_fields(&class);
     check_methods(&class);
@@ -44,17 +44,19 @@ fn check_fields(class: &ClassFile) {
         vec!(
             ClassFileField {
                 flags: FieldFlags::PRIVATE | FieldFlags::FINAL,
-                name: "real".to_string(),
+                name: "real".into(),
                 type_descriptor: FieldType::Base(BaseType::Double),
                 constant_value: None,
                 deprecated: false,
+                attributes: vec![],
             },
             ClassFileField {
                 flags: FieldFlags::PRIVATE | FieldFlags::FINAL,
-                name: "imag".to_string(),
+                name: "imag".into(),
                 type_descriptor: FieldType::Base(BaseType::Double),
                 constant_value: None,
                 deprecated: false,
+                attributes: vec![],
             }
         ),
         class.fields
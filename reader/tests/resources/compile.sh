@@ -1,2 +1,4 @@
 #!/usr/bin/env sh
-javac -source 6 -target 6 rjvm/*.java
+javac -source 6 -target 6 $(ls rjvm/*.java | grep -v Lambdas.java)
+# Lambdas require at least java 8
+javac -source 8 -target 8 rjvm/Lambdas.java
@@ -0,0 +1,12 @@
+package rjvm;
+
+public class Lambdas {
+    interface Operation {
+        int apply(int value);
+    }
+
+    public static int applyTwice(int base) {
+        Operation addBase = value -> value + base;
+        return addBase.apply(addBase.apply(0));
+    }
+}
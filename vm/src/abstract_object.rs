@@ -1,4 +1,5 @@
 use std::{
+    fmt,
     fmt::{Debug, Formatter},
     marker::PhantomData,
     mem::size_of,
@@ -8,7 +9,7 @@ use bitfield_struct::bitfield;
 
 use rjvm_reader::{
     field_type::{BaseType, FieldType},
-    type_conversion::ToUsizeSafe,
+    type_conversion::{CheckedNarrowing, ToUsizeSafe},
 };
 
 use crate::{
@@ -17,6 +18,7 @@ use crate::{
     array_entry_type::ArrayEntryType,
     class::{Class, ClassId, ClassRef},
     object::Object,
+    thread_scheduler::ThreadId,
     value::Value,
     vm_error::VmError,
 };
@@ -87,20 +89,58 @@ pub(crate) struct AllocHeader {
     #[bits(1)]
     pub(crate) state: GcState,
 
+    /// Zero until the object is assigned an identity hash code
     #[bits(30)]
-    identity_hash_code: i32,
+    identity_hash_code: u32,
 
     #[bits(32)]
     pub(crate) size: usize,
 }
 
-/// The second word of an allocated "classical" object
+/// A unique id assigned to every allocated object, in allocation order. Unlike the object's
+/// address, it does not change when the gc moves the object, so it can be used to correlate
+/// the same object across heap dumps, traces and debug output taken at different times.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(transparent)]
+pub struct ObjectId(u64);
+
+impl fmt::Display for ObjectId {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl ObjectId {
+    pub fn new(id: u64) -> Self {
+        Self(id)
+    }
+
+    pub fn as_u64(&self) -> u64 {
+        self.0
+    }
+}
+
+/// The second word of any allocated object
+#[repr(transparent)]
+struct ObjectIdHeader {
+    id: ObjectId,
+}
+
+/// The third word of any allocated object: the state of its monitor, used by `synchronized`.
+/// A monitor is either free or owned by a thread, which can enter it multiple times.
+struct MonitorHeader {
+    /// The id of the owner thread plus one, or zero if the monitor is free
+    owner: u32,
+    entry_count: u32,
+}
+
+/// The fourth word of an allocated "classical" object
 #[repr(transparent)]
 struct ObjectHeader {
     class_id: ClassId,
 }
 
-/// The second word of an allocated array
+/// The fourth word of an allocated array
 struct ArrayHeader {
     elements_type: ArrayEntryType,
     length: u32,
@@ -113,7 +153,10 @@ const fn align_to_8_bytes(required_size: usize) -> usize {
     }
 }
 
-pub(crate) const ALLOC_HEADER_SIZE: usize = align_to_8_bytes(size_of::<AllocHeader>());
+// Includes the AllocHeader, the ObjectIdHeader and the MonitorHeader, which are common to all objects
+pub(crate) const ALLOC_HEADER_SIZE: usize = align_to_8_bytes(size_of::<AllocHeader>())
+    + align_to_8_bytes(size_of::<ObjectIdHeader>())
+    + align_to_8_bytes(size_of::<MonitorHeader>());
 pub(crate) const OBJECT_HEADER_SIZE: usize = align_to_8_bytes(size_of::<ObjectHeader>());
 pub(crate) const ARRAY_HEADER_SIZE: usize = align_to_8_bytes(size_of::<ArrayHeader>());
 
@@ -133,17 +176,17 @@ impl<'a> AbstractObject<'a> {
         ALLOC_HEADER_SIZE + ARRAY_HEADER_SIZE + length * 8
     }
 
-    pub fn new_object(class: &Class<'a>, alloc_entry: AllocEntry) -> Self {
-        Self::write_object_header(class, &alloc_entry);
+    pub fn new_object(class: &Class<'a>, alloc_entry: AllocEntry, id: ObjectId) -> Self {
+        Self::write_object_header(class, &alloc_entry, id);
         Self {
             data: alloc_entry.ptr,
             marker: PhantomData,
         }
     }
 
-    fn write_object_header(class: &Class, alloc_entry: &AllocEntry) {
+    fn write_object_header(class: &Class, alloc_entry: &AllocEntry, id: ObjectId) {
         unsafe {
-            let next_ptr = Self::write_alloc_header(alloc_entry, ObjectKind::Object);
+            let next_ptr = Self::write_alloc_header(alloc_entry, ObjectKind::Object, id);
             std::ptr::write(
                 next_ptr as *mut ObjectHeader,
                 ObjectHeader { class_id: class.id },
@@ -155,8 +198,9 @@ impl<'a> AbstractObject<'a> {
         elements_type: ArrayEntryType,
         array_length: usize,
         alloc_entry: &AllocEntry,
+        id: ObjectId,
     ) -> Self {
-        Self::write_array_header(elements_type, array_length, alloc_entry);
+        Self::write_array_header(elements_type, array_length, alloc_entry, id);
         Self {
             data: alloc_entry.ptr,
             marker: PhantomData,
@@ -167,30 +211,47 @@ impl<'a> AbstractObject<'a> {
         elements_type: ArrayEntryType,
         array_length: usize,
         alloc_entry: &AllocEntry,
+        id: ObjectId,
     ) {
         unsafe {
-            let next_ptr = Self::write_alloc_header(alloc_entry, ObjectKind::Array);
+            let next_ptr = Self::write_alloc_header(alloc_entry, ObjectKind::Array, id);
             std::ptr::write(
                 next_ptr as *mut ArrayHeader,
                 ArrayHeader {
                     elements_type,
-                    length: array_length as u32,
+                    length: array_length
+                        .narrow()
+                        .expect("array length should fit in a u32"),
                 },
             );
         }
     }
 
-    unsafe fn write_alloc_header(alloc_entry: &AllocEntry, kind: ObjectKind) -> *mut u8 {
+    unsafe fn write_alloc_header(
+        alloc_entry: &AllocEntry,
+        kind: ObjectKind,
+        id: ObjectId,
+    ) -> *mut u8 {
         let next_ptr = alloc_entry.ptr as *mut AllocHeader;
         std::ptr::write(
             next_ptr,
             AllocHeader::new()
                 .with_kind(kind)
                 .with_state(GcState::Unmarked)
-                .with_identity_hash_code(identity_hash_code(alloc_entry.ptr))
+                .with_identity_hash_code(0)
                 .with_size(alloc_entry.alloc_size),
         );
-        next_ptr.add(1) as *mut u8
+        let id_ptr = next_ptr.add(1) as *mut ObjectIdHeader;
+        std::ptr::write(id_ptr, ObjectIdHeader { id });
+        let monitor_ptr = id_ptr.add(1) as *mut MonitorHeader;
+        std::ptr::write(
+            monitor_ptr,
+            MonitorHeader {
+                owner: 0,
+                entry_count: 0,
+            },
+        );
+        monitor_ptr.add(1) as *mut u8
     }
 
     pub(crate) fn from_raw_ptr(ptr: *mut u8) -> Self {
@@ -205,12 +266,122 @@ impl<'a> AbstractObject<'a> {
         self.data == other.data
     }
 
+    /// The current address of the object; note that it changes when the gc moves the object
+    pub(crate) fn address(&self) -> usize {
+        self.data as usize
+    }
+
     fn alloc_header(&self) -> &AllocHeader {
         unsafe { &*(self.data as *const AllocHeader) }
     }
 
-    pub fn identity_hash_code(&self) -> i32 {
-        self.alloc_header().identity_hash_code()
+    /// The unique id of the object, which is preserved by the gc
+    pub fn id(&self) -> ObjectId {
+        unsafe {
+            let ptr = self.data.add(align_to_8_bytes(size_of::<AllocHeader>()));
+            (*(ptr as *const ObjectIdHeader)).id
+        }
+    }
+
+    fn monitor_header(&self) -> *mut MonitorHeader {
+        unsafe {
+            self.data.add(
+                align_to_8_bytes(size_of::<AllocHeader>())
+                    + align_to_8_bytes(size_of::<ObjectIdHeader>()),
+            ) as *mut MonitorHeader
+        }
+    }
+
+    /// The thread that owns the monitor of the object, if any
+    pub fn monitor_owner(&self) -> Option<ThreadId> {
+        match unsafe { (*self.monitor_header()).owner } {
+            0 => None,
+            owner => Some(ThreadId::new(owner - 1)),
+        }
+    }
+
+    /// How many times the owner of the monitor has entered it and not yet exited it
+    pub fn monitor_entry_count(&self) -> u32 {
+        unsafe { (*self.monitor_header()).entry_count }
+    }
+
+    /// Acquires the monitor of the object for the given thread, as done by `monitorenter`,
+    /// unless it is owned by another thread. Monitors are reentrant.
+    pub(crate) fn try_monitor_enter(&self, thread: ThreadId) -> bool {
+        self.try_monitor_enter_times(thread, 1)
+    }
+
+    /// Like [try_monitor_enter](Self::try_monitor_enter), but enters the monitor the given
+    /// number of times at once, as needed to restore the state saved by
+    /// [monitor_exit_all](Self::monitor_exit_all)
+    pub(crate) fn try_monitor_enter_times(&self, thread: ThreadId, times: u32) -> bool {
+        let header = self.monitor_header();
+        unsafe {
+            let owner = thread.as_u32() + 1;
+            if (*header).owner != 0 && (*header).owner != owner {
+                return false;
+            }
+            (*header).owner = owner;
+            (*header).entry_count += times;
+        }
+        true
+    }
+
+    /// Releases the monitor of the object, as done by `monitorexit`, failing if it was not
+    /// owned by the given thread
+    pub(crate) fn monitor_exit(&self, thread: ThreadId) -> Result<(), VmError> {
+        self.check_monitor_owner(thread)?;
+        let header = self.monitor_header();
+        unsafe {
+            (*header).entry_count -= 1;
+            if (*header).entry_count == 0 {
+                (*header).owner = 0;
+            }
+        }
+        Ok(())
+    }
+
+    /// Releases the monitor completely, however many times the given thread has entered it,
+    /// and returns that number
+    pub(crate) fn monitor_exit_all(&self, thread: ThreadId) -> Result<u32, VmError> {
+        self.check_monitor_owner(thread)?;
+        let header = self.monitor_header();
+        unsafe {
+            let entry_count = (*header).entry_count;
+            (*header).owner = 0;
+            (*header).entry_count = 0;
+            Ok(entry_count)
+        }
+    }
+
+    /// Fails with `IllegalMonitorStateException` unless the given thread owns the monitor
+    pub(crate) fn check_monitor_owner(&self, thread: ThreadId) -> Result<(), VmError> {
+        if self.monitor_owner() == Some(thread) {
+            Ok(())
+        } else {
+            Err(VmError::IllegalMonitorStateException)
+        }
+    }
+
+    /// The identity hash code of the object, if one has been assigned; see
+    /// [Vm::identity_hash_code](crate::vm::Vm::identity_hash_code). It is stored in the
+    /// header, so that it is preserved when the gc moves the object.
+    pub fn identity_hash_code(&self) -> Option<i32> {
+        match self.alloc_header().identity_hash_code() {
+            0 => None,
+            hash_code => Some(
+                hash_code
+                    .narrow()
+                    .expect("identity hash codes should have 30 bits"),
+            ),
+        }
+    }
+
+    pub(crate) fn set_identity_hash_code(&self, hash_code: i32) {
+        let hash_code = hash_code
+            .narrow()
+            .expect("identity hash codes should be positive");
+        unsafe { (*(self.data as *mut AllocHeader)).set_identity_hash_code(hash_code) }
     }
 
     pub fn kind(&self) -> ObjectKind {
@@ -226,8 +397,9 @@ impl<'a> Debug for AbstractObject<'a> {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         write!(
             f,
-            "{:?} ptr {:#0x} size {}",
+            "{:?} id {} ptr {:#0x} size {}",
             self.kind(),
+            self.id(),
             self.data as usize,
             self.alloc_size(),
         )?;
@@ -243,20 +415,6 @@ impl<'a> Debug for AbstractObject<'a> {
     }
 }
 
-fn hash(data: u64) -> u64 {
-    (data >> 32) ^ (data)
-}
-
-fn identity_hash_code(ptr: *mut u8) -> i32 {
-    let hash = hash(ptr as u64);
-
-    // Note: we'll take some of the least significant bits here,
-    // since we'll store this in AllocHeader!
-    let hash = (hash & ((1 << 30) - 1)) as u32;
-
-    unsafe { std::mem::transmute(hash) }
-}
-
 unsafe fn write_value(ptr: *mut u8, value: Value) {
     match value {
         Value::Int(int) => std::ptr::write(ptr as *mut i32, int),
@@ -265,6 +423,8 @@ unsafe fn write_value(ptr: *mut u8, value: Value) {
         Value::Double(double) => std::ptr::write(ptr as *mut f64, double),
         Value::Uninitialized | Value::Null => std::ptr::write(ptr as *mut u64, 0),
         Value::Object(obj) => std::ptr::write(ptr as *mut AbstractObject, obj),
+        // Stores are type checked, and no field or array element can have this type
+        Value::ReturnAddress(_) => unreachable!("return addresses cannot be stored in objects"),
     }
 }
 
@@ -296,7 +456,7 @@ unsafe fn read_value2<'a>(ptr: *const u8, field_type: &ArrayEntryType) -> Value<
         ArrayEntryType::Base(BaseType::Long) => Value::Long(std::ptr::read(ptr as *const i64)),
         ArrayEntryType::Base(BaseType::Float) => Value::Float(std::ptr::read(ptr as *const f32)),
         ArrayEntryType::Base(BaseType::Double) => Value::Double(std::ptr::read(ptr as *const f64)),
-        ArrayEntryType::Object(_) | ArrayEntryType::Array => {
+        ArrayEntryType::Object(_) | ArrayEntryType::Array(_) => {
             match std::ptr::read(ptr as *const i64) {
                 0 => Value::Null,
                 _ => Value::Object(std::ptr::read(ptr as *const AbstractObject)),
@@ -337,9 +497,13 @@ impl<'a> Object<'a> for AbstractObject<'a> {
 
     fn get_field(&self, object_class: ClassRef, index: usize) -> Value<'a> {
         let field = object_class.field_at_index(index).unwrap();
+        self.get_field_of_type(index, &field.type_descriptor)
+    }
+
+    fn get_field_of_type(&self, index: usize, field_type: &FieldType) -> Value<'a> {
         unsafe {
             let ptr = self.ptr_to_field_value(index);
-            read_value(ptr, &field.type_descriptor)
+            read_value(ptr, field_type)
         }
     }
 }
@@ -360,6 +524,31 @@ impl<'a> AbstractObject<'a> {
         let offset = ALLOC_HEADER_SIZE + ARRAY_HEADER_SIZE + entry_location;
         self.data.add(offset)
     }
+
+    /// Writes the given primitive values in the first entries of the array, without wrapping
+    /// each of them in a [Value]. The caller must ensure that `T` is the type in which
+    /// [read_value2] reads the entries of this array, i.e. `i32` for a `byte[]`.
+    pub(crate) unsafe fn write_primitive_elements<T: Copy>(
+        &self,
+        values: impl ExactSizeIterator<Item = T>,
+    ) {
+        assert!(values.len() <= self.len().into_usize_safe());
+        for (index, value) in values.enumerate() {
+            std::ptr::write(self.ptr_to_array_element(index) as *mut T, value);
+        }
+    }
+
+    /// Like [write_primitive_elements](Self::write_primitive_elements), but for types that
+    /// take 8 bytes, exactly like an entry: the values can thus be copied with a single memcpy
+    pub(crate) unsafe fn copy_primitive_elements<T: Copy>(&self, values: &[T]) {
+        assert_eq!(8, size_of::<T>());
+        assert!(values.len() <= self.len().into_usize_safe());
+        std::ptr::copy_nonoverlapping(
+            values.as_ptr() as *const u8,
+            self.ptr_to_array_element(0),
+            8 * values.len(),
+        );
+    }
 }
 
 impl<'a> Array<'a> for AbstractObject<'a> {
@@ -373,7 +562,9 @@ impl<'a> Array<'a> for AbstractObject<'a> {
 
     fn set_element(&self, index: usize, value: Value<'a>) -> Result<(), VmError> {
         if index >= self.len().into_usize_safe() {
-            Err(VmError::ArrayIndexOutOfBoundsException)
+            Err(VmError::ArrayIndexOutOfBoundsException(
+                i32::try_from(index).unwrap_or(i32::MAX),
+            ))
         } else {
             unsafe {
                 let ptr = self.ptr_to_array_element(index);
@@ -385,7 +576,9 @@ impl<'a> Array<'a> for AbstractObject<'a> {
 
     fn get_element(&self, index: usize) -> Result<Value<'a>, VmError> {
         if index >= self.len().into_usize_safe() {
-            Err(VmError::ArrayIndexOutOfBoundsException)
+            Err(VmError::ArrayIndexOutOfBoundsException(
+                i32::try_from(index).unwrap_or(i32::MAX),
+            ))
         } else {
             unsafe {
                 let ptr = self.ptr_to_array_element(index);
@@ -411,7 +604,9 @@ pub fn string_from_char_array(array: AbstractObject) -> Result<String, VmError>
         let ptr = array.data.add(ALLOC_HEADER_SIZE + ARRAY_HEADER_SIZE) as *const i64;
         for i in 0..len {
             let ptr = ptr.add(i);
-            let next_codepoint = std::ptr::read(ptr as *const i32) as u16;
+            let next_codepoint = std::ptr::read(ptr as *const i32)
+                .narrow()
+                .map_err(|_| VmError::ValidationException)?;
             string_chars.push(next_codepoint);
         }
     }
@@ -0,0 +1,121 @@
+use rjvm_reader::{
+    class_access_flags::ClassAccessFlags, class_file_field::ClassFileField,
+    field_flags::FieldFlags, method_flags::MethodFlags,
+};
+
+use crate::{class::Class, class_and_method::ClassAndMethod, vm_error::VmError};
+
+/// The access level of a field or a method, derived from its flags
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum MemberAccess {
+    Public,
+    Protected,
+    Package,
+    Private,
+}
+
+impl From<FieldFlags> for MemberAccess {
+    fn from(flags: FieldFlags) -> Self {
+        if flags.contains(FieldFlags::PUBLIC) {
+            MemberAccess::Public
+        } else if flags.contains(FieldFlags::PROTECTED) {
+            MemberAccess::Protected
+        } else if flags.contains(FieldFlags::PRIVATE) {
+            MemberAccess::Private
+        } else {
+            MemberAccess::Package
+        }
+    }
+}
+
+impl From<MethodFlags> for MemberAccess {
+    fn from(flags: MethodFlags) -> Self {
+        if flags.contains(MethodFlags::PUBLIC) {
+            MemberAccess::Public
+        } else if flags.contains(MethodFlags::PROTECTED) {
+            MemberAccess::Protected
+        } else if flags.contains(MethodFlags::PRIVATE) {
+            MemberAccess::Private
+        } else {
+            MemberAccess::Package
+        }
+    }
+}
+
+// The messages follow the format of the classic HotSpot ones
+
+/// Checks that the code of the class `accessor` can reference the given class,
+/// as described in section 5.4.4 of the JVM specs
+pub(crate) fn check_class_access(accessor: &Class, class: &Class) -> Result<(), VmError> {
+    if class.flags.contains(ClassAccessFlags::PUBLIC) || is_same_package(accessor, class) {
+        Ok(())
+    } else {
+        Err(VmError::IllegalAccessError(format!(
+            "tried to access class {} from class {}",
+            binary_name(&class.name),
+            binary_name(&accessor.name)
+        )))
+    }
+}
+
+/// Checks that the code of the class `accessor` can invoke the given method
+pub(crate) fn check_method_access(
+    accessor: &Class,
+    class_and_method: &ClassAndMethod,
+) -> Result<(), VmError> {
+    let access = MemberAccess::from(class_and_method.method.flags);
+    if is_member_accessible(accessor, class_and_method.class, access) {
+        Ok(())
+    } else {
+        Err(VmError::IllegalAccessError(format!(
+            "tried to access method {}.{}{} from class {}",
+            binary_name(&class_and_method.class.name),
+            class_and_method.method.name,
+            class_and_method.method.type_descriptor,
+            binary_name(&accessor.name)
+        )))
+    }
+}
+
+/// Checks that the code of the class `accessor` can read or write the given field
+pub(crate) fn check_field_access(
+    accessor: &Class,
+    declaring_class: &Class,
+    field: &ClassFileField,
+) -> Result<(), VmError> {
+    let access = MemberAccess::from(field.flags);
+    if is_member_accessible(accessor, declaring_class, access) {
+        Ok(())
+    } else {
+        Err(VmError::IllegalAccessError(format!(
+            "tried to access field {}.{} from class {}",
+            binary_name(&declaring_class.name),
+            field.name,
+            binary_name(&accessor.name)
+        )))
+    }
+}
+
+fn is_member_accessible(accessor: &Class, declaring_class: &Class, access: MemberAccess) -> bool {
+    match access {
+        MemberAccess::Public => true,
+        MemberAccess::Protected => {
+            accessor.is_subclass_of(declaring_class) || is_same_package(accessor, declaring_class)
+        }
+        MemberAccess::Package => is_same_package(accessor, declaring_class),
+        MemberAccess::Private => nest_host(accessor) == nest_host(declaring_class),
+    }
+}
+
+fn is_same_package(class: &Class, other: &Class) -> bool {
+    class.package_name() == other.package_name()
+}
+
+/// Classes without a `NestHost` attribute are the hosts of their own nest
+fn nest_host<'c>(class: &'c Class) -> &'c str {
+    class.nest_host.as_deref().unwrap_or(&class.name)
+}
+
+fn binary_name(class_name: &str) -> String {
+    class_name.replace('/', ".")
+}
@@ -0,0 +1,65 @@
+use std::{fmt, fmt::Formatter, rc::Rc};
+
+use crate::array_entry_type::ArrayEntryType;
+
+/// What has been allocated on the heap
+#[derive(Debug, Clone, PartialEq)]
+pub enum AllocationKind<'e> {
+    /// An instance of the class with the given name
+    Object { class_name: &'e str },
+    /// An array with the given entries type and length
+    Array {
+        elements_type: &'e ArrayEntryType,
+        length: usize,
+    },
+}
+
+/// An allocation of the vm, reported to the [AllocationHook]s
+#[derive(Debug, Clone, PartialEq)]
+pub struct AllocationEvent<'e> {
+    pub kind: AllocationKind<'e>,
+    /// The number of bytes used on the heap by the new object
+    pub size: usize,
+}
+
+/// Counters of all the allocations performed by the vm, including the ones of objects
+/// that have since been garbage collected
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct AllocationCounters {
+    /// The number of objects, excluding arrays, allocated
+    pub objects: u64,
+    /// The number of arrays allocated
+    pub arrays: u64,
+    /// The total number of bytes allocated
+    pub bytes: u64,
+}
+
+impl AllocationCounters {
+    pub(crate) fn record(&mut self, event: &AllocationEvent) {
+        match event.kind {
+            AllocationKind::Object { .. } => self.objects += 1,
+            AllocationKind::Array { .. } => self.arrays += 1,
+        }
+        self.bytes += u64::try_from(event.size).unwrap_or(u64::MAX);
+    }
+}
+
+/// A callback invoked after every allocation of the vm, i.e. for profiling
+#[derive(Clone)]
+pub struct AllocationHook(Rc<dyn Fn(&AllocationEvent)>);
+
+impl AllocationHook {
+    pub fn new(hook: impl Fn(&AllocationEvent) + 'static) -> Self {
+        Self(Rc::new(hook))
+    }
+
+    pub(crate) fn notify(&self, event: &AllocationEvent) {
+        (self.0)(event)
+    }
+}
+
+impl fmt::Debug for AllocationHook {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str("AllocationHook")
+    }
+}
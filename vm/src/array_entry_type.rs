@@ -12,12 +12,63 @@ use crate::{class::ClassId, class_resolver_by_id::ClassByIdResolver};
 pub enum ArrayEntryType {
     Base(BaseType),
     Object(ClassId),
-    // Note: here we would have to keep the sub-element type. Not doing this means that we do not
-    // correctly support arrays of arrays!
-    Array,
+    /// The entries are arrays themselves. For the same reason as above, we do not store the
+    /// entries' type recursively, but only the number of dimensions and the innermost type.
+    Array(NestedArrayType),
+}
+
+/// The type of the entries of an array of arrays, i.e. `int[]` for an `int[][]`
+#[derive(PartialEq, Clone, Debug)]
+pub struct NestedArrayType {
+    /// The number of dimensions of the entries, always at least one
+    pub dimensions: u8,
+    pub innermost: InnermostType,
+}
+
+/// The type of the elements of the innermost arrays of a multidimensional array
+#[derive(PartialEq, Clone, Debug)]
+pub enum InnermostType {
+    Base(BaseType),
+    Object(ClassId),
 }
 
 impl ArrayEntryType {
+    /// Returns the type of the entries of an array whose type is the given one, i.e.
+    /// `int[]` for an `int[][]`
+    pub fn array_of(array_type: &ArrayEntryType) -> ArrayEntryType {
+        match array_type {
+            ArrayEntryType::Base(base_type) => ArrayEntryType::Array(NestedArrayType {
+                dimensions: 1,
+                innermost: InnermostType::Base(base_type.clone()),
+            }),
+            ArrayEntryType::Object(class_id) => ArrayEntryType::Array(NestedArrayType {
+                dimensions: 1,
+                innermost: InnermostType::Object(*class_id),
+            }),
+            ArrayEntryType::Array(nested) => ArrayEntryType::Array(NestedArrayType {
+                dimensions: nested.dimensions + 1,
+                innermost: nested.innermost.clone(),
+            }),
+        }
+    }
+
+    /// For an array of arrays, returns the type of the entries of the inner arrays
+    pub fn component_type(&self) -> Option<ArrayEntryType> {
+        match self {
+            ArrayEntryType::Array(nested) if nested.dimensions > 1 => {
+                Some(ArrayEntryType::Array(NestedArrayType {
+                    dimensions: nested.dimensions - 1,
+                    innermost: nested.innermost.clone(),
+                }))
+            }
+            ArrayEntryType::Array(nested) => Some(match &nested.innermost {
+                InnermostType::Base(base_type) => ArrayEntryType::Base(base_type.clone()),
+                InnermostType::Object(class_id) => ArrayEntryType::Object(*class_id),
+            }),
+            _ => None,
+        }
+    }
+
     pub fn into_field_type<'a>(
         self,
         class_resolver: &impl ClassByIdResolver<'a>,
@@ -27,9 +78,25 @@ impl ArrayEntryType {
             ArrayEntryType::Object(class_id) => class_resolver
                 .find_class_by_id(class_id)
                 .map(|class| FieldType::Object(class.name.clone())),
-            ArrayEntryType::Array => {
-                todo!("Arrays of arrays are not supported at the moment")
+            ArrayEntryType::Array(_) => {
+                let component_type = self.component_type()?.into_field_type(class_resolver)?;
+                Some(FieldType::Array(Box::new(component_type)))
             }
         }
     }
+
+    /// The name of the type, for diagnostics
+    pub fn type_name<'a>(&self, class_resolver: &impl ClassByIdResolver<'a>) -> String {
+        match self {
+            ArrayEntryType::Base(base_type) => base_type.to_string(),
+            ArrayEntryType::Object(class_id) => match class_resolver.find_class_by_id(*class_id) {
+                Some(class) => class.name.clone(),
+                None => format!("<unknown class {class_id}>"),
+            },
+            ArrayEntryType::Array(_) => match self.component_type() {
+                Some(component_type) => format!("{}[]", component_type.type_name(class_resolver)),
+                None => "<invalid array>".to_string(),
+            },
+        }
+    }
 }
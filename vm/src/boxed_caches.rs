@@ -0,0 +1,148 @@
+use std::collections::HashMap;
+
+use crate::{
+    abstract_object::AbstractObject,
+    call_frame::MethodCallResult,
+    call_stack::CallStack,
+    exceptions::MethodCallFailed,
+    native_methods_registry::NativeMethodsRegistry,
+    object_fields::set_object_field,
+    value::{expect_int_at, expect_long_at, Value},
+    vm::Vm,
+};
+
+/// The boxed primitive types whose `valueOf` method returns a cached instance for small values
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum BoxedType {
+    Byte,
+    Short,
+    Character,
+    Integer,
+    Long,
+}
+
+impl BoxedType {
+    fn class_name(self) -> &'static str {
+        match self {
+            BoxedType::Byte => "java/lang/Byte",
+            BoxedType::Short => "java/lang/Short",
+            BoxedType::Character => "java/lang/Character",
+            BoxedType::Integer => "java/lang/Integer",
+            BoxedType::Long => "java/lang/Long",
+        }
+    }
+
+    /// The specs require boxing to return the same instance for the values in -128..=127,
+    /// or \u0000..=\u007f for chars. Like the JDK, we use the same range for longs.
+    fn is_cached(self, value: i64) -> bool {
+        match self {
+            BoxedType::Character => (0..=127).contains(&value),
+            _ => (-128..=127).contains(&value),
+        }
+    }
+}
+
+/// The instances returned by `Integer.valueOf` and the other boxing methods for the cached
+/// values. Our rt.jar keeps them in nested classes such as `Integer$IntegerCache`, but the
+/// initialization of the integer cache reads a system property via `sun.misc.VM`, which
+/// pulls in most of the class library. Thus, the vm maintains the caches instead, creating
+/// each instance the first time that it is requested.
+///
+/// `Boolean.valueOf` is not intrinsified, since it just returns `Boolean.TRUE` or
+/// `Boolean.FALSE`.
+#[derive(Debug, Default)]
+pub(crate) struct BoxedCaches<'a> {
+    objects: HashMap<(BoxedType, i64), AbstractObject<'a>>,
+}
+
+impl<'a> BoxedCaches<'a> {
+    /// The cached instances must never be collected, or identity comparisons would break
+    pub fn gc_roots(&mut self) -> impl Iterator<Item = *mut AbstractObject<'a>> + '_ {
+        self.objects
+            .values_mut()
+            .map(|object| object as *mut AbstractObject<'a>)
+    }
+}
+
+/// Registers the intrinsics for the `valueOf` methods of the boxed integral types.
+/// Note that `byte`, `short` and `char` arguments are passed as `Value::Int`.
+pub(crate) fn register_boxed_caches(registry: &mut NativeMethodsRegistry) {
+    registry.register_intrinsic(
+        "java/lang/Byte",
+        "valueOf",
+        "(B)Ljava/lang/Byte;",
+        |vm, stack, _, args| int_value_of(vm, stack, BoxedType::Byte, &args),
+    );
+    registry.register_intrinsic(
+        "java/lang/Short",
+        "valueOf",
+        "(S)Ljava/lang/Short;",
+        |vm, stack, _, args| int_value_of(vm, stack, BoxedType::Short, &args),
+    );
+    registry.register_intrinsic(
+        "java/lang/Character",
+        "valueOf",
+        "(C)Ljava/lang/Character;",
+        |vm, stack, _, args| int_value_of(vm, stack, BoxedType::Character, &args),
+    );
+    registry.register_intrinsic(
+        "java/lang/Integer",
+        "valueOf",
+        "(I)Ljava/lang/Integer;",
+        |vm, stack, _, args| int_value_of(vm, stack, BoxedType::Integer, &args),
+    );
+    registry.register_intrinsic(
+        "java/lang/Long",
+        "valueOf",
+        "(J)Ljava/lang/Long;",
+        |vm, stack, _, args| {
+            let value = expect_long_at(&args, 0)?;
+            value_of(vm, stack, BoxedType::Long, Value::Long(value), value)
+        },
+    );
+}
+
+fn int_value_of<'a>(
+    vm: &mut Vm<'a>,
+    call_stack: &mut CallStack<'a>,
+    boxed_type: BoxedType,
+    args: &[Value<'a>],
+) -> MethodCallResult<'a> {
+    let value = expect_int_at(args, 0)?;
+    value_of(vm, call_stack, boxed_type, Value::Int(value), value.into())
+}
+
+/// Returns the cached instance for the given value, or a new one if it is not in the
+/// cached range
+fn value_of<'a>(
+    vm: &mut Vm<'a>,
+    call_stack: &mut CallStack<'a>,
+    boxed_type: BoxedType,
+    value: Value<'a>,
+    numeric_value: i64,
+) -> MethodCallResult<'a> {
+    if !boxed_type.is_cached(numeric_value) {
+        let object = new_boxed_object(vm, call_stack, boxed_type, value)?;
+        return Ok(Some(Value::Object(object)));
+    }
+    if let Some(object) = vm.boxed_caches.objects.get(&(boxed_type, numeric_value)) {
+        return Ok(Some(Value::Object(object.clone())));
+    }
+    let object = new_boxed_object(vm, call_stack, boxed_type, value)?;
+    vm.boxed_caches
+        .objects
+        .insert((boxed_type, numeric_value), object.clone());
+    Ok(Some(Value::Object(object)))
+}
+
+/// Allocates a new instance of the boxed type, as its constructor would
+fn new_boxed_object<'a>(
+    vm: &mut Vm<'a>,
+    call_stack: &mut CallStack<'a>,
+    boxed_type: BoxedType,
+    value: Value<'a>,
+) -> Result<AbstractObject<'a>, MethodCallFailed<'a>> {
+    let object = vm.new_object(call_stack, boxed_type.class_name())?;
+    set_object_field(vm, &object, "value", value)?;
+    Ok(object)
+}
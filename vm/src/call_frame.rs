@@ -1,36 +1,50 @@
-use log::{debug, warn};
+// Narrowing casts silently truncate values, so they must be replaced by checked conversions
+#![deny(clippy::as_conversions)]
+
+use std::cmp::Ordering;
+
+use log::{debug, warn, Level};
 
 use rjvm_reader::{
+    class_access_flags::ClassAccessFlags,
     class_file_field::ClassFileField,
-    class_file_method::ClassFileMethod,
     constant_pool::ConstantPoolEntry,
     field_type::{BaseType, FieldType, FieldType::Base},
     instruction::{Instruction, NewArrayType},
     line_number::LineNumber,
+    method_flags::MethodFlags,
     program_counter::ProgramCounter,
-    type_conversion::ToUsizeSafe,
+    type_conversion::{CheckedNarrowing, ToUsizeSafe},
 };
 
 use crate::{
     abstract_object::{AbstractObject, ObjectKind},
+    access_control,
     array::Array,
     array_entry_type::ArrayEntryType,
     call_frame::InstructionCompleted::{ContinueMethodExecution, ReturnFromMethod},
     call_stack::CallStack,
-    class::Class,
+    class::{Class, ClassRef},
     class_and_method::ClassAndMethod,
     class_resolver_by_id::ClassByIdResolver,
     exceptions::{JavaException, MethodCallFailed},
-    java_objects_creation::{new_java_lang_class_object, new_java_lang_string_object},
+    instruction_site::InstructionSite,
+    java_objects_creation::{
+        get_interned_java_lang_string, get_java_lang_class_object, new_java_lang_throwable_object,
+        new_java_lang_throwable_object_without_message, throw_exception,
+    },
+    lambda_metafactory,
+    log_config::{vm_log, LogCategory},
     object::Object,
     stack_trace_element::StackTraceElement,
+    thread_scheduler::ThreadId,
     value::{
         Value,
         Value::{Double, Float, Int, Long, Null},
     },
     value_stack::ValueStack,
     vm::Vm,
-    vm_error::VmError,
+    vm_error::{ArrayTypeMismatch, VmError},
 };
 
 /// A method call can return:
@@ -38,7 +52,7 @@ use crate::{
 /// - for failures: a MethodCallFailed error
 pub type MethodCallResult<'a> = Result<Option<Value<'a>>, MethodCallFailed<'a>>;
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy)]
 struct MethodReference<'a> {
     class_name: &'a str,
     method_name: &'a str,
@@ -62,6 +76,10 @@ pub struct CallFrame<'a> {
     /// The current program counter
     pc: ProgramCounter,
 
+    /// The address of the instruction being executed; [pc](Self::pc) is moved to the
+    /// following one _before_ executing it
+    instruction_pc: ProgramCounter,
+
     /// The locals variables' map of the method
     locals: Vec<Value<'a>>,
 
@@ -69,14 +87,26 @@ pub struct CallFrame<'a> {
     stack: ValueStack<'a>,
 
     /// The bytecode to execute
-    code: &'a Vec<u8>,
+    code: &'a [u8],
+
+    /// The object whose monitor a synchronized method holds while executing: it is acquired
+    /// before executing the first instruction, and released when the method completes
+    monitor: Option<AbstractObject<'a>>,
+
+    /// Whether the thread has acquired [monitor](Self::monitor): it may have been suspended
+    /// while waiting for it
+    monitor_entered: bool,
+
+    /// The method invoked by the instruction being executed, while its thread is suspended:
+    /// its result is pushed when the frame is [resumed](Self::resume)
+    pending_call: Option<ClassAndMethod<'a>>,
 }
 
 /// One of the possible invocation kind of methods in the JVM.
 #[derive(Clone, Copy)]
 enum InvokeKind {
-    /// Special instance methods include constructors and calls to method of this class,
-    /// bypassing virtual function resolution
+    /// Special instance methods include constructors, private methods and calls to methods
+    /// of the superclass (`super.method()`), bypassing virtual function resolution
     Special,
     /// Static methods do not take a receiver object
     Static,
@@ -122,7 +152,6 @@ macro_rules! generate_execute_return {
                 ));
             }
             let result = self.pop()?;
-            self.debug_done_execution(Some(&result));
             return Ok(Some(result));
         }
     };
@@ -167,19 +196,21 @@ macro_rules! generate_execute_coerce {
     };
 }
 
-/// Pops two values, compares them, and pushes the result (+1, -1, or zero)
+/// Pops two values, compares them, and pushes the result (+1, -1, or zero).
+/// If either value is NaN, pushes the given result instead: +1 for `fcmpg` and `dcmpg`,
+/// -1 for `fcmpl` and `dcmpl`.
 macro_rules! generate_compare {
     ($name:ident, $pop_fn:ident) => {
-        fn $name(&mut self, sign_for_greater: i32) -> Result<(), MethodCallFailed<'a>> {
+        fn $name(&mut self, result_if_nan: i32) -> Result<(), MethodCallFailed<'a>> {
             let val2 = self.$pop_fn()?;
             let val1 = self.$pop_fn()?;
-            if val1 > val2 {
-                self.push(Int(sign_for_greater))
-            } else if val1 < val2 {
-                self.push(Int(-sign_for_greater))
-            } else {
-                self.push(Int(0))
-            }
+            let result = match val1.partial_cmp(&val2) {
+                Some(Ordering::Greater) => 1,
+                Some(Ordering::Less) => -1,
+                Some(Ordering::Equal) => 0,
+                None => result_if_nan,
+            };
+            self.push(Int(result))
         }
     };
 }
@@ -220,14 +251,19 @@ macro_rules! generate_execute_store {
 /// Pops the index and the array and pushes the element at the index
 macro_rules! generate_execute_array_load {
     ($name:ident, $($variant:pat),+) => {
-        fn $name(&mut self) -> Result<(), MethodCallFailed<'a>> {
-            let index = self.pop_int()?.into_usize_safe();
+        fn $name(&mut self, vm: &Vm<'a>) -> Result<(), MethodCallFailed<'a>> {
+            let index = self.pop_array_index()?;
             let array = self.pop_array()?;
             let value = match array.elements_type() {
                 $($variant => {
                     array.get_element(index)
                 })+
-                _ => return Err(MethodCallFailed::InternalError(VmError::ValidationException)),
+                _ => return Err(self.array_type_mismatch(
+                    vm,
+                    stringify!($name).trim_start_matches("execute_"),
+                    &array,
+                    None,
+                )),
             }?;
             self.push(value)
         }
@@ -237,15 +273,20 @@ macro_rules! generate_execute_array_load {
 /// Pops the value, the index, and the array, and sets the element at the index
 macro_rules! generate_execute_array_store {
     ($name:ident, $pop_fn:ident, $map_fn:ident, $($variant:pat),+) => {
-        fn $name(&mut self) -> Result<(), MethodCallFailed<'a>> {
+        fn $name(&mut self, vm: &Vm<'a>) -> Result<(), MethodCallFailed<'a>> {
             let value = Self::$map_fn(self.$pop_fn()?);
-            let index = self.pop_int()?.into_usize_safe();
+            let index = self.pop_array_index()?;
             let array = self.pop_array()?;
             match array.elements_type() {
                 $($variant => {
                      array.set_element(index, value)?
                 })+
-                _ => return Err(MethodCallFailed::InternalError(VmError::ValidationException)),
+                _ => return Err(self.array_type_mismatch(
+                    vm,
+                    stringify!($name).trim_start_matches("execute_"),
+                    &array,
+                    Some(&value),
+                )),
             }
             Ok(())
         }
@@ -270,12 +311,55 @@ impl<'a> CallFrame<'a> {
         CallFrame {
             class_and_method,
             pc: ProgramCounter(0),
+            instruction_pc: ProgramCounter(0),
             locals,
             stack: ValueStack::with_max_size(max_stack_size),
             code,
+            monitor: None,
+            monitor_entered: false,
+            pending_call: None,
         }
     }
 
+    /// Makes the frame hold the monitor of the given object while executing the method,
+    /// as needed for synchronized methods
+    pub(crate) fn set_monitor(&mut self, object: AbstractObject<'a>) {
+        self.monitor = Some(object);
+    }
+
+    /// Acquires the monitor set via [set_monitor](Self::set_monitor), if any, letting the
+    /// other threads run while it is owned by one of them. The object is read from the frame
+    /// after each switch, since the gc may have moved it. If the thread is suspended, the
+    /// monitor is acquired when it is resumed.
+    fn enter_monitor(&mut self, vm: &mut Vm<'a>) -> Result<(), VmError> {
+        if self.monitor_entered {
+            return Ok(());
+        }
+        let thread = vm.current_thread_id();
+        while let Some(object) = &self.monitor {
+            if object.try_monitor_enter(thread) {
+                break;
+            }
+            vm.yield_thread()?;
+        }
+        self.monitor_entered = true;
+        Ok(())
+    }
+
+    /// Releases the monitor acquired by [enter_monitor](Self::enter_monitor), if any.
+    /// Fails if the method has already released it via an unbalanced `monitorexit`.
+    pub(crate) fn exit_monitor(&mut self, thread: ThreadId) -> Result<(), VmError> {
+        match self.monitor.take() {
+            Some(object) if self.monitor_entered => object.monitor_exit(thread),
+            _ => Ok(()),
+        }
+    }
+
+    /// The class declaring the method being executed
+    pub(crate) fn class(&self) -> ClassRef<'a> {
+        self.class_and_method.class
+    }
+
     pub fn to_stack_trace_element(&self) -> StackTraceElement<'a> {
         StackTraceElement {
             class_name: &self.class_and_method.class.name,
@@ -294,58 +378,267 @@ impl<'a> CallFrame<'a> {
         None
     }
 
-    /// Executes the whole method
+    /// Executes the whole method. If the thread is suspended, fails with
+    /// [VmError::ThreadSuspended], and the execution continues via [resume](Self::resume).
     pub fn execute(
         &mut self,
         vm: &mut Vm<'a>,
         call_stack: &mut CallStack<'a>,
     ) -> MethodCallResult<'a> {
-        self.debug_start_execution();
+        self.debug_start_execution(vm);
+        self.run(vm, call_stack)
+    }
+
+    /// Continues the execution of the method after its thread was suspended, given the
+    /// result of the method that the suspended instruction invoked, if any
+    pub(crate) fn resume(
+        &mut self,
+        vm: &mut Vm<'a>,
+        call_stack: &mut CallStack<'a>,
+        callee_result: Option<MethodCallResult<'a>>,
+    ) -> MethodCallResult<'a> {
+        if let Some(class_and_method) = self.pending_call.take() {
+            let callee_result = callee_result
+                .expect("the result of the pending call should be given")
+                .and_then(|result| self.push_method_result(vm, class_and_method, result));
+            let executed_instruction_pc = self.instruction_pc;
+            let instruction_result = callee_result.map(|_| ContinueMethodExecution);
+            let instruction_result =
+                Self::raise_java_exceptions(vm, call_stack, instruction_result);
+            if let Some(result) =
+                self.complete_instruction(vm, executed_instruction_pc, instruction_result)
+            {
+                return result;
+            }
+        }
+        self.run(vm, call_stack)
+    }
+
+    fn run(&mut self, vm: &mut Vm<'a>, call_stack: &mut CallStack<'a>) -> MethodCallResult<'a> {
+        let code = self
+            .class_and_method
+            .class
+            .decoded_code(self.class_and_method.method)?;
+        self.enter_monitor(vm)?;
 
         loop {
+            // The first instruction and the targets of backward branches are the switch
+            // points of the green threads, so that a loop cannot starve the other threads
+            vm.safepoint(self.pc <= self.instruction_pc)?;
             let executed_instruction_pc = self.pc;
-            let (instruction, new_address) =
-                Instruction::parse(self.code, executed_instruction_pc.0.into_usize_safe())
-                    .map_err(|_| MethodCallFailed::InternalError(VmError::ValidationException))?;
-            self.debug_print_status(&instruction);
+            self.instruction_pc = executed_instruction_pc;
+            let (instruction, next_instruction_pc) =
+                code.instruction_at(executed_instruction_pc).ok_or(
+                    MethodCallFailed::InternalError(VmError::ValidationException),
+                )?;
+            if vm.should_log_instruction(call_stack.depth()) {
+                self.debug_print_status(&instruction);
+            }
 
             // Move pc to the next instruction, _before_ executing it, since we want a "goto" to override this
-            self.pc = ProgramCounter(new_address as u16);
+            self.pc = next_instruction_pc;
+
+            let instruction_result = match instruction {
+                Instruction::Invokespecial(_)
+                | Instruction::Invokestatic(_)
+                | Instruction::Invokevirtual(_)
+                | Instruction::Invokeinterface(_, _)
+                | Instruction::Invokedynamic(_) => {
+                    self.execute_invoke_instruction(vm, call_stack, instruction)
+                }
+                _ => self.execute_instruction(vm, call_stack, instruction),
+            };
+            let instruction_result =
+                Self::raise_java_exceptions(vm, call_stack, instruction_result);
+            if let Some(result) =
+                self.complete_instruction(vm, executed_instruction_pc, instruction_result)
+            {
+                return result;
+            }
+        }
+    }
 
-            let instruction_result = self.execute_instruction(vm, call_stack, instruction);
-            match instruction_result {
-                Ok(ReturnFromMethod(return_value)) => return Ok(return_value),
-                Ok(ContinueMethodExecution) => { /* continue the loop */ }
+    /// Handles the outcome of the instruction at the given address: returns the result of
+    /// the method if it completes, or `None` if the execution continues, possibly from an
+    /// exception handler
+    fn complete_instruction(
+        &mut self,
+        vm: &mut Vm<'a>,
+        executed_instruction_pc: ProgramCounter,
+        instruction_result: Result<InstructionCompleted<'a>, MethodCallFailed<'a>>,
+    ) -> Option<MethodCallResult<'a>> {
+        match instruction_result {
+            Ok(ReturnFromMethod(return_value)) => {
+                self.debug_done_execution(vm, return_value.as_ref());
+                Some(Ok(return_value))
+            }
+            Ok(ContinueMethodExecution) => None,
 
-                Err(MethodCallFailed::InternalError(err)) => {
-                    return Err(MethodCallFailed::InternalError(err))
-                }
+            Err(err @ (MethodCallFailed::InternalError(_) | MethodCallFailed::VmExit(_))) => {
+                Some(Err(err))
+            }
 
-                Err(MethodCallFailed::ExceptionThrown(exception)) => {
-                    let exception_handler = self.find_exception_handler(
-                        vm,
-                        call_stack,
-                        executed_instruction_pc,
-                        &exception,
-                    );
-                    match exception_handler {
-                        Err(err) => return Err(err),
-                        Ok(None) => {
-                            // Bubble exception up to the caller
-                            return Err(MethodCallFailed::ExceptionThrown(exception));
-                        }
-                        Ok(Some(catch_handler_pc)) => {
-                            // Re-push exception on the stack and continue execution of this method from the catch handler
-                            self.stack.push(Value::Object(exception.0))?;
-                            self.pc = catch_handler_pc
+            Err(MethodCallFailed::ExceptionThrown(exception)) => {
+                let exception_handler =
+                    self.find_exception_handler(vm, executed_instruction_pc, &exception);
+                match exception_handler {
+                    Err(err) => Some(Err(err)),
+                    Ok(None) => {
+                        // Bubble exception up to the caller
+                        Some(Err(MethodCallFailed::ExceptionThrown(exception)))
+                    }
+                    Ok(Some(catch_handler_pc)) => {
+                        // The handler starts with only the exception on the stack, then
+                        // execution continues from there
+                        let handler_start = self
+                            .stack
+                            .truncate(0)
+                            .and_then(|_| self.stack.push(Value::Object(exception.0)));
+                        if let Err(err) = handler_start {
+                            return Some(Err(err.into()));
                         }
+                        self.pc = catch_handler_pc;
+                        None
                     }
                 }
             }
         }
     }
 
+    /// Replaces the errors that java code should see as exceptions, i.e. null dereferences,
+    /// divisions by zero, stack overflows or missing classes, with the corresponding java
+    /// exception, so that the exception handlers can catch them
+    fn raise_java_exceptions(
+        vm: &mut Vm<'a>,
+        call_stack: &mut CallStack<'a>,
+        instruction_result: Result<InstructionCompleted<'a>, MethodCallFailed<'a>>,
+    ) -> Result<InstructionCompleted<'a>, MethodCallFailed<'a>> {
+        let exception = match instruction_result {
+            Err(MethodCallFailed::InternalError(VmError::NullPointerException)) => {
+                new_java_lang_throwable_object_without_message(
+                    vm,
+                    call_stack,
+                    "java/lang/NullPointerException",
+                )?
+            }
+            Err(MethodCallFailed::InternalError(VmError::IllegalMonitorStateException)) => {
+                new_java_lang_throwable_object_without_message(
+                    vm,
+                    call_stack,
+                    "java/lang/IllegalMonitorStateException",
+                )?
+            }
+            Err(MethodCallFailed::InternalError(VmError::StackOverflowError)) => {
+                call_stack.set_overflowing(true);
+                let error = new_java_lang_throwable_object_without_message(
+                    vm,
+                    call_stack,
+                    "java/lang/StackOverflowError",
+                );
+                call_stack.set_overflowing(false);
+                error?
+            }
+            Err(MethodCallFailed::InternalError(
+                ref error @ (VmError::ArithmeticException
+                | VmError::ArrayIndexOutOfBoundsException(_)
+                | VmError::ArrayStoreException(_)
+                | VmError::ClassCastException { .. }
+                | VmError::OutOfMemoryError
+                | VmError::VerifyError(_)
+                | VmError::ClassFormatError(_)
+                | VmError::UnsupportedClassVersionError(..)
+                | VmError::IllegalAccessError(_)
+                | VmError::AbstractMethodError(_)
+                | VmError::InstantiationError(_)
+                | VmError::ErroneousClass(_)),
+            )) => {
+                let class_name = match error {
+                    VmError::ArithmeticException => "java/lang/ArithmeticException",
+                    VmError::ArrayIndexOutOfBoundsException(_) => {
+                        "java/lang/ArrayIndexOutOfBoundsException"
+                    }
+                    VmError::ArrayStoreException(_) => "java/lang/ArrayStoreException",
+                    VmError::OutOfMemoryError => "java/lang/OutOfMemoryError",
+                    VmError::VerifyError(_) => "java/lang/VerifyError",
+                    VmError::ClassFormatError(_) => "java/lang/ClassFormatError",
+                    VmError::UnsupportedClassVersionError(..) => {
+                        "java/lang/UnsupportedClassVersionError"
+                    }
+                    VmError::IllegalAccessError(_) => "java/lang/IllegalAccessError",
+                    VmError::AbstractMethodError(_) => "java/lang/AbstractMethodError",
+                    VmError::InstantiationError(_) => "java/lang/InstantiationError",
+                    VmError::ErroneousClass(_) => "java/lang/NoClassDefFoundError",
+                    _ => "java/lang/ClassCastException",
+                };
+                let message = error.to_string();
+                new_java_lang_throwable_object(vm, call_stack, class_name, &message)?
+            }
+            // The messages of the linkage errors follow the format of the classic HotSpot ones
+            Err(MethodCallFailed::InternalError(VmError::ClassNotFoundException(ref name))) => {
+                new_java_lang_throwable_object(
+                    vm,
+                    call_stack,
+                    "java/lang/NoClassDefFoundError",
+                    name,
+                )?
+            }
+            Err(MethodCallFailed::InternalError(VmError::MethodNotFoundException(
+                ref class_name,
+                ref name,
+                ref descriptor,
+            ))) => {
+                let message = format!("{}.{name}{descriptor}", class_name.replace('/', "."));
+                new_java_lang_throwable_object(
+                    vm,
+                    call_stack,
+                    "java/lang/NoSuchMethodError",
+                    &message,
+                )?
+            }
+            Err(MethodCallFailed::InternalError(VmError::FieldNotFoundException(_, ref name))) => {
+                new_java_lang_throwable_object(vm, call_stack, "java/lang/NoSuchFieldError", name)?
+            }
+            _ => return instruction_result,
+        };
+        Err(MethodCallFailed::ExceptionThrown(JavaException(exception)))
+    }
+
     // Reference: https://docs.oracle.com/javase/specs/jvms/se7/html/jvms-6.html
+    /// Executes the instructions that invoke a method. These are kept out of
+    /// [Self::execute_instruction] because they recurse into the interpreter, and the frame of
+    /// that function is quite large, at least in debug builds: each java frame would need a lot
+    /// of native stack, making the native stack overflow before the java one.
+    fn execute_invoke_instruction(
+        &mut self,
+        vm: &mut Vm<'a>,
+        call_stack: &mut CallStack<'a>,
+        instruction: Instruction,
+    ) -> Result<InstructionCompleted<'a>, MethodCallFailed<'a>> {
+        match instruction {
+            Instruction::Invokespecial(constant_index) => {
+                self.invoke_method(vm, call_stack, constant_index, InvokeKind::Special)?
+            }
+            Instruction::Invokestatic(constant_index) => {
+                self.invoke_method(vm, call_stack, constant_index, InvokeKind::Static)?
+            }
+            Instruction::Invokevirtual(constant_index) => {
+                self.invoke_method(vm, call_stack, constant_index, InvokeKind::Virtual)?
+            }
+            Instruction::Invokeinterface(constant_index, _) => {
+                self.invoke_method(vm, call_stack, constant_index, InvokeKind::Interface)?
+            }
+            Instruction::Invokedynamic(constant_index) => {
+                self.execute_invokedynamic(vm, call_stack, constant_index)?
+            }
+            _ => {
+                return Err(MethodCallFailed::InternalError(
+                    VmError::ValidationException,
+                ))
+            }
+        };
+        Ok(ContinueMethodExecution)
+    }
+
     fn execute_instruction(
         &mut self,
         vm: &mut Vm<'a>,
@@ -408,7 +701,7 @@ impl<'a> CallFrame<'a> {
             Instruction::Lstore_2 => self.execute_lstore(2)?,
             Instruction::Lstore_3 => self.execute_lstore(3)?,
 
-            Instruction::Ldc(index) => self.execute_ldc(vm, call_stack, index as u16)?,
+            Instruction::Ldc(index) => self.execute_ldc(vm, call_stack, u16::from(index))?,
             Instruction::Ldc_w(index) => self.execute_ldc(vm, call_stack, index)?,
             Instruction::Ldc2_w(index) => self.execute_ldc_long_double(index)?,
 
@@ -455,11 +748,7 @@ impl<'a> CallFrame<'a> {
             Instruction::D2l => self.coerce_double(Self::d2l)?,
             Instruction::D2f => self.coerce_double(Self::d2f)?,
 
-            Instruction::New(constant_index) => {
-                let new_object_class_name = self.get_constant_class_reference(constant_index)?;
-                let new_object = vm.new_object(call_stack, new_object_class_name)?;
-                self.push(Value::Object(new_object))?;
-            }
+            Instruction::New(constant_index) => self.execute_new(vm, call_stack, constant_index)?,
 
             Instruction::Dup => self.stack.dup()?,
             Instruction::Dup_x1 => self.stack.dup_x1()?,
@@ -471,21 +760,8 @@ impl<'a> CallFrame<'a> {
             Instruction::Pop2 => self.stack.pop2().map(|_| ())?,
             Instruction::Swap => self.stack.swap()?,
 
-            Instruction::Bipush(byte_value) => self.push(Int(byte_value as i32))?,
-            Instruction::Sipush(short_value) => self.push(Int(short_value as i32))?,
-
-            Instruction::Invokespecial(constant_index) => {
-                self.invoke_method(vm, call_stack, constant_index, InvokeKind::Special)?
-            }
-            Instruction::Invokestatic(constant_index) => {
-                self.invoke_method(vm, call_stack, constant_index, InvokeKind::Static)?
-            }
-            Instruction::Invokevirtual(constant_index) => {
-                self.invoke_method(vm, call_stack, constant_index, InvokeKind::Virtual)?
-            }
-            Instruction::Invokeinterface(constant_index, _) => {
-                self.invoke_method(vm, call_stack, constant_index, InvokeKind::Interface)?
-            }
+            Instruction::Bipush(byte_value) => self.push(Int(i32::from(byte_value)))?,
+            Instruction::Sipush(short_value) => self.push(Int(i32::from(short_value)))?,
 
             Instruction::Return => {
                 if !self.class_and_method.is_void() {
@@ -493,7 +769,6 @@ impl<'a> CallFrame<'a> {
                         VmError::ValidationException,
                     ));
                 }
-                self.debug_done_execution(None);
                 return Ok(ReturnFromMethod(None));
             }
             Instruction::Areturn => return Ok(ReturnFromMethod(self.execute_areturn()?)),
@@ -539,7 +814,7 @@ impl<'a> CallFrame<'a> {
                     if a > 0 {
                         a >> (b & 0x1f)
                     } else {
-                        ((a as u32) >> (b & 0x1f)) as i32
+                        (a.cast_unsigned() >> (b & 0x1f)).cast_signed()
                     }
                 })
             })?,
@@ -547,7 +822,7 @@ impl<'a> CallFrame<'a> {
             Instruction::Iinc(index, constant) => {
                 let index = index.into_usize_safe();
                 let local = self.get_local_int_as_int(vm, index)?;
-                self.locals[index] = Int(local + constant as i32);
+                self.locals[index] = Int(local.wrapping_add(i32::from(constant)));
             }
 
             Instruction::Ladd => self.execute_long_math(|a, b| Ok(a + b))?,
@@ -564,14 +839,14 @@ impl<'a> CallFrame<'a> {
             Instruction::Land => self.execute_long_math(|a, b| Ok(a & b))?,
             Instruction::Lor => self.execute_long_math(|a, b| Ok(a | b))?,
             Instruction::Lxor => self.execute_long_math(|a, b| Ok(a ^ b))?,
-            Instruction::Lshr => self.execute_long_shift(|a, b| Ok(a >> (b & 0x1f)))?,
-            Instruction::Lshl => self.execute_long_shift(|a, b| Ok(a << (b & 0x1f)))?,
+            Instruction::Lshr => self.execute_long_shift(|a, b| Ok(a >> (b & 0x3f)))?,
+            Instruction::Lshl => self.execute_long_shift(|a, b| Ok(a << (b & 0x3f)))?,
             Instruction::Lushr => self.execute_long_shift(|a, b| {
                 Ok({
                     if a > 0 {
-                        a >> (b & 0x1f)
+                        a >> (b & 0x3f)
                     } else {
-                        ((a as u64) >> (b & 0x1f)) as i64
+                        (a.cast_unsigned() >> (b & 0x3f)).cast_signed()
                     }
                 })
             })?,
@@ -581,7 +856,7 @@ impl<'a> CallFrame<'a> {
             Instruction::Fmul => self.execute_float_math(|a, b| Ok(a * b))?,
             Instruction::Fdiv => self.execute_float_math(|a, b| {
                 Ok(
-                    if Self::is_double_division_returning_nan(a as f64, b as f64) {
+                    if Self::is_double_division_returning_nan(f64::from(a), f64::from(b)) {
                         f32::NAN
                     } else {
                         a / b
@@ -590,7 +865,7 @@ impl<'a> CallFrame<'a> {
             })?,
             Instruction::Frem => self.execute_float_math(|a, b| {
                 Ok(
-                    if Self::is_double_division_returning_nan(a as f64, b as f64) {
+                    if Self::is_double_division_returning_nan(f64::from(a), f64::from(b)) {
                         f32::NAN
                     } else {
                         a % b
@@ -621,7 +896,13 @@ impl<'a> CallFrame<'a> {
             Instruction::Fneg => self.execute_fneg()?,
             Instruction::Dneg => self.execute_dneg()?,
 
-            Instruction::Goto(jump_address) => self.goto(jump_address),
+            Instruction::Goto(jump_address) | Instruction::Goto_w(jump_address) => {
+                self.goto(jump_address)
+            }
+            Instruction::Jsr(jump_address) | Instruction::Jsr_w(jump_address) => {
+                self.execute_jsr(jump_address)?
+            }
+            Instruction::Ret(index) => self.execute_ret(index.into_usize_safe())?,
 
             Instruction::Ifeq(jump_address) => self.execute_if(jump_address, |v| v == 0)?,
             Instruction::Ifne(jump_address) => self.execute_if(jump_address, |v| v != 0)?,
@@ -653,55 +934,55 @@ impl<'a> CallFrame<'a> {
                 self.execute_if_icmp(jump_address, |a, b| a >= b)?
             }
 
-            Instruction::Lcmp => self.execute_long_compare(1)?,
-            Instruction::Fcmpg => self.execute_float_compare(-1)?,
-            Instruction::Fcmpl => self.execute_float_compare(1)?,
-            Instruction::Dcmpg => self.execute_double_compare(-1)?,
-            Instruction::Dcmpl => self.execute_double_compare(1)?,
+            // Longs are never NaN
+            Instruction::Lcmp => self.execute_long_compare(0)?,
+            Instruction::Fcmpg => self.execute_float_compare(1)?,
+            Instruction::Fcmpl => self.execute_float_compare(-1)?,
+            Instruction::Dcmpg => self.execute_double_compare(1)?,
+            Instruction::Dcmpl => self.execute_double_compare(-1)?,
 
             Instruction::Newarray(array_type) => {
-                self.execute_newarray(vm, array_type)?;
+                self.execute_newarray(vm, call_stack, array_type)?;
             }
             Instruction::Anewarray(constant_index) => {
                 self.execute_anewarray(vm, call_stack, constant_index)?;
             }
+            Instruction::Multianewarray(constant_index, dimensions) => {
+                self.execute_multianewarray(vm, call_stack, constant_index, dimensions)?;
+            }
 
             Instruction::Arraylength => self.execute_array_length()?,
 
-            Instruction::Baload => self.execute_baload()?,
-            Instruction::Caload => self.execute_caload()?,
-            Instruction::Saload => self.execute_saload()?,
-            Instruction::Iaload => self.execute_iaload()?,
-            Instruction::Laload => self.execute_laload()?,
-            Instruction::Faload => self.execute_faload()?,
-            Instruction::Daload => self.execute_daload()?,
-            Instruction::Aaload => self.execute_aaload()?,
-
-            Instruction::Bastore => self.execute_bastore()?,
-            Instruction::Castore => self.execute_castore()?,
-            Instruction::Sastore => self.execute_sastore()?,
-            Instruction::Iastore => self.execute_iastore()?,
-            Instruction::Lastore => self.execute_lastore()?,
-            Instruction::Fastore => self.execute_fastore()?,
-            Instruction::Dastore => self.execute_dastore()?,
+            Instruction::Baload => self.execute_baload(vm)?,
+            Instruction::Caload => self.execute_caload(vm)?,
+            Instruction::Saload => self.execute_saload(vm)?,
+            Instruction::Iaload => self.execute_iaload(vm)?,
+            Instruction::Laload => self.execute_laload(vm)?,
+            Instruction::Faload => self.execute_faload(vm)?,
+            Instruction::Daload => self.execute_daload(vm)?,
+            Instruction::Aaload => self.execute_aaload(vm)?,
+
+            Instruction::Bastore => self.execute_bastore(vm)?,
+            Instruction::Castore => self.execute_castore(vm)?,
+            Instruction::Sastore => self.execute_sastore(vm)?,
+            Instruction::Iastore => self.execute_iastore(vm)?,
+            Instruction::Lastore => self.execute_lastore(vm)?,
+            Instruction::Fastore => self.execute_fastore(vm)?,
+            Instruction::Dastore => self.execute_dastore(vm)?,
             Instruction::Aastore => self.execute_aastore(vm)?,
 
-            Instruction::Monitorenter => self.execute_monitorenter()?,
-            Instruction::Monitorexit => self.execute_monitorexit()?,
+            Instruction::Monitorenter => self.execute_monitorenter(vm)?,
+            Instruction::Monitorexit => self.execute_monitorexit(vm)?,
 
             Instruction::Athrow => self.execute_athrow()?,
 
-            /* Unimplemented instructions:
-            Instruction::Goto_w => {}
-            Instruction::Invokedynamic(_) => {}
-            Instruction::Jsr(_) => {}
-            Instruction::Jsr_w => {}
-            Instruction::Lookupswitch => {}
-            Instruction::Multianewarray(_, _) => {}
-            Instruction::Ret(_) => {}
-            Instruction::Tableswitch => {}
-            Instruction::Wide => {}
-            */
+            Instruction::Tableswitch(table) | Instruction::Lookupswitch(table) => {
+                let key = self.pop_int()?;
+                let jump_address = table
+                    .jump_address(self.code, key)
+                    .map_err(|_| VmError::ValidationException)?;
+                self.goto(jump_address);
+            }
             Instruction::Nop => {}
 
             _ => {
@@ -720,74 +1001,88 @@ impl<'a> CallFrame<'a> {
         self.stack.pop().map_err(|err| err.into())
     }
 
-    fn i2b(value: i32) -> Value<'a> {
-        Int((value as i8) as i32)
-    }
-    fn i2c(value: i32) -> Value<'a> {
-        Int((value as u16) as i32)
-    }
-    fn i2s(value: i32) -> Value<'a> {
-        Int((value as i16) as i32)
-    }
-    fn i2i(value: i32) -> Value<'a> {
-        Int(value)
-    }
-    fn i2f(value: i32) -> Value<'a> {
-        Float(value as f32)
-    }
-    fn i2l(value: i32) -> Value<'a> {
-        Long(value as i64)
-    }
-    fn i2d(value: i32) -> Value<'a> {
-        Double(value as f64)
-    }
-
-    fn l2i(value: i64) -> Value<'a> {
-        Int(value as i32)
-    }
-    fn l2f(value: i64) -> Value<'a> {
-        Float(value as f32)
-    }
-    fn l2l(value: i64) -> Value<'a> {
-        Long(value)
-    }
-    fn l2d(value: i64) -> Value<'a> {
-        Double(value as f64)
-    }
+    fn invoke_method(
+        &mut self,
+        vm: &mut Vm<'a>,
+        call_stack: &mut CallStack<'a>,
+        constant_index: u16,
+        kind: InvokeKind,
+    ) -> Result<(), MethodCallFailed<'a>> {
+        let call_site = match kind {
+            InvokeKind::Virtual | InvokeKind::Interface => {
+                InstructionSite::of(&self.class_and_method, self.instruction_pc)
+            }
+            _ => None,
+        };
+        let cached_method =
+            call_site.and_then(|call_site| vm.inline_caches.resolved_method(call_site));
+        let static_method_reference = match cached_method {
+            Some(method) => method,
+            None => match self.resolve_invoked_method(vm, call_stack, constant_index, kind)? {
+                Some(method) => {
+                    if let Some(call_site) = call_site {
+                        vm.inline_caches
+                            .set_resolved_method(call_site, method.clone());
+                    }
+                    method
+                }
+                // The clone of an array, which has already been executed
+                None => return Ok(()),
+            },
+        };
+        let (receiver, params, new_stack_len) =
+            self.get_method_receiver_and_params(&static_method_reference)?;
+        let class_and_method = match kind {
+            // Private methods are never overridden, even if invoked via invokevirtual or
+            // invokeinterface, as happens for private methods of nestmates and interfaces
+            InvokeKind::Virtual | InvokeKind::Interface
+                if !static_method_reference
+                    .method
+                    .flags
+                    .contains(MethodFlags::PRIVATE) =>
+            {
+                Self::resolve_virtual_method(
+                    vm,
+                    call_site,
+                    receiver.clone(),
+                    static_method_reference,
+                )?
+            }
+            _ => static_method_reference,
+        };
+        self.stack.truncate(new_stack_len)?;
 
-    fn f2i(value: f32) -> Value<'a> {
-        Int(value as i32)
-    }
-    fn f2l(value: f32) -> Value<'a> {
-        Long(value as i64)
-    }
-    fn f2f(value: f32) -> Value<'a> {
-        Float(value)
-    }
-    fn f2d(value: f32) -> Value<'a> {
-        Double(value as f64)
+        let result = vm.invoke_suspendable(call_stack, class_and_method.clone(), receiver, params);
+        if let Err(MethodCallFailed::InternalError(VmError::ThreadSuspended)) = result {
+            self.pending_call = Some(class_and_method);
+            return result.map(|_| ());
+        }
+        self.push_method_result(vm, class_and_method, result?)
     }
 
-    fn d2i(value: f64) -> Value<'a> {
-        Int(value as i32)
-    }
-    fn d2f(value: f64) -> Value<'a> {
-        Float(value as f32)
-    }
-    fn d2l(value: f64) -> Value<'a> {
-        Long(value as i64)
-    }
-    fn d2d(value: f64) -> Value<'a> {
-        Double(value)
+    /// Pushes the value returned by the given method, after checking its type
+    fn push_method_result(
+        &mut self,
+        vm: &Vm<'a>,
+        class_and_method: ClassAndMethod<'a>,
+        result: Option<Value<'a>>,
+    ) -> Result<(), MethodCallFailed<'a>> {
+        Self::validate_type_opt(vm, class_and_method.return_type(), &result)?;
+        if let Some(value) = result {
+            self.push(value)?;
+        }
+        Ok(())
     }
 
-    fn invoke_method(
+    /// Resolves the method referred by an invoke instruction, or executes it directly and
+    /// returns `None` if it is the `clone` of an array
+    fn resolve_invoked_method(
         &mut self,
         vm: &mut Vm<'a>,
         call_stack: &mut CallStack<'a>,
         constant_index: u16,
         kind: InvokeKind,
-    ) -> Result<(), MethodCallFailed<'a>> {
+    ) -> Result<Option<ClassAndMethod<'a>>, MethodCallFailed<'a>> {
         let method_reference = self.get_constant_method_reference(constant_index)?;
         if method_reference.class_name.starts_with('[') && method_reference.method_name == "clone" {
             // TODO:
@@ -796,41 +1091,94 @@ impl<'a> CallFrame<'a> {
             //  invoking "clone" on an array.
             let array = self.pop()?;
             let clone = vm.clone_array(array)?;
-            return self.push(clone);
+            self.push(clone)?;
+            return Ok(None);
         }
 
-        let static_method_reference =
-            self.get_method_to_invoke_statically(vm, call_stack, method_reference, kind)?;
-        let (receiver, params, new_stack_len) =
-            self.get_method_receiver_and_params(&static_method_reference)?;
-        let class_and_method = match kind {
-            InvokeKind::Virtual | InvokeKind::Interface => {
-                Self::resolve_virtual_method(vm, receiver.clone(), static_method_reference)?
+        let method = match kind {
+            InvokeKind::Special => {
+                self.get_method_to_invoke_special(vm, call_stack, method_reference)?
             }
-            _ => static_method_reference,
+            _ => self.get_method_to_invoke_statically(vm, call_stack, method_reference)?,
         };
-        self.stack.truncate(new_stack_len)?;
+        if let InvokeKind::Static = kind {
+            self.initialize_class(vm, call_stack, method.class)?;
+        }
+        Ok(Some(method))
+    }
 
-        let method_return_type = class_and_method.return_type();
-        let result = vm.invoke(call_stack, class_and_method, receiver, params)?;
+    fn execute_invokedynamic(
+        &mut self,
+        vm: &mut Vm<'a>,
+        call_stack: &mut CallStack<'a>,
+        constant_index: u16,
+    ) -> Result<(), MethodCallFailed<'a>> {
+        let lambda_class = lambda_metafactory::invoke_dynamic(
+            vm,
+            call_stack,
+            self.class_and_method.class,
+            constant_index,
+        )?;
+
+        // The captured values are popped only after the allocation, since it might trigger
+        // a garbage collection, which needs them to be on the stack
+        let num_captured_values = lambda_metafactory::num_captured_values(lambda_class);
+        let new_stack_len = self
+            .stack
+            .len()
+            .checked_sub(num_captured_values)
+            .ok_or(VmError::ValidationException)?;
+        let lambda = vm.new_object_of_class(lambda_class)?;
+        let captured_values = self.stack.iter().skip(new_stack_len).cloned().collect();
+        lambda_metafactory::set_captured_values(vm, lambda_class, &lambda, captured_values);
+        self.stack.truncate(new_stack_len)?;
+        self.push(Value::Object(lambda))
+    }
 
-        Self::validate_type_opt(vm, method_return_type, &result)?;
-        if let Some(value) = result {
-            self.push(value)?;
+    /// Like [get_field](Self::get_field), for static fields, which can also be declared by an
+    /// interface. Returns the class declaring the field, whose static instance stores it.
+    fn get_static_field(
+        &self,
+        vm: &Vm<'a>,
+        class: &'a Class,
+        field_reference: FieldReference,
+    ) -> Result<(&'a Class<'a>, usize, &'a ClassFileField<'a>), VmError> {
+        let (declaring_class, index, field) = class
+            .find_static_field(field_reference.field_name)
+            .ok_or(VmError::FieldNotFoundException(
+            field_reference.class_name.to_string(),
+            field_reference.field_name.to_string(),
+        ))?;
+        if vm.access_checks() {
+            access_control::check_field_access(
+                self.class_and_method.class,
+                declaring_class,
+                field,
+            )?;
         }
-        Ok(())
+        Ok((declaring_class, index, field))
     }
 
     fn get_field(
+        &self,
+        vm: &Vm<'a>,
         class: &'a Class,
         field_reference: FieldReference,
-    ) -> Result<(usize, &'a ClassFileField), VmError> {
-        class
-            .find_field(field_reference.field_name)
+    ) -> Result<(usize, &'a ClassFileField<'a>), VmError> {
+        let (declaring_class, index, field) = class
+            .find_field_and_declaring_class(field_reference.field_name)
             .ok_or(VmError::FieldNotFoundException(
                 field_reference.class_name.to_string(),
                 field_reference.field_name.to_string(),
-            ))
+            ))?;
+        if vm.access_checks() {
+            access_control::check_field_access(
+                self.class_and_method.class,
+                declaring_class,
+                field,
+            )?;
+        }
+        Ok((index, field))
     }
 
     generate_pop!(pop_int, Int, i32);
@@ -853,13 +1201,24 @@ impl<'a> CallFrame<'a> {
         let receiver = self.pop()?;
         match receiver {
             Value::Object(object) if object.kind() == ObjectKind::Array => Ok(object),
+            Null => Err(MethodCallFailed::InternalError(
+                VmError::NullPointerException,
+            )),
             _ => Err(MethodCallFailed::InternalError(
                 VmError::ValidationException,
             )),
         }
     }
 
-    fn get_constant(&self, constant_index: u16) -> Result<&ConstantPoolEntry, VmError> {
+    /// Pops the index of an array element; negative indexes are always out of bounds
+    fn pop_array_index(&mut self) -> Result<usize, MethodCallFailed<'a>> {
+        let index = self.pop_int()?;
+        index.narrow().map_err(|_| {
+            MethodCallFailed::InternalError(VmError::ArrayIndexOutOfBoundsException(index))
+        })
+    }
+
+    fn get_constant(&self, constant_index: u16) -> Result<&ConstantPoolEntry<'a>, VmError> {
         self.class_and_method
             .class
             .constants
@@ -879,7 +1238,7 @@ impl<'a> CallFrame<'a> {
     fn get_constant_method_reference(
         &self,
         constant_index: u16,
-    ) -> Result<MethodReference, VmError> {
+    ) -> Result<MethodReference<'_>, VmError> {
         let constant = self.get_constant(constant_index)?;
 
         let (class_name_index, name_and_type_descriptor_index) = match *constant {
@@ -905,7 +1264,10 @@ impl<'a> CallFrame<'a> {
         }
     }
 
-    fn get_constant_field_reference(&self, constant_index: u16) -> Result<FieldReference, VmError> {
+    fn get_constant_field_reference(
+        &self,
+        constant_index: u16,
+    ) -> Result<FieldReference<'_>, VmError> {
         let constant = self.get_constant(constant_index)?;
         if let &ConstantPoolEntry::FieldReference(
             class_name_index,
@@ -943,87 +1305,193 @@ impl<'a> CallFrame<'a> {
         vm: &mut Vm<'a>,
         call_stack: &mut CallStack<'a>,
         method_reference: MethodReference,
-        kind: InvokeKind,
     ) -> Result<ClassAndMethod<'a>, MethodCallFailed<'a>> {
-        let class = vm.get_or_resolve_class(call_stack, method_reference.class_name)?;
-        match kind {
-            InvokeKind::Special | InvokeKind::Static => {
-                Self::get_method_of_class(class, method_reference)
-                    .map(|method| ClassAndMethod { class, method })
-            }
-            InvokeKind::Virtual | InvokeKind::Interface => {
-                Self::get_method_checking_superclasses(class, method_reference)
-            }
+        // Arrays have no class of their own: the methods invoked on them are those of Object
+        let class_name = if method_reference.class_name.starts_with('[') {
+            "java/lang/Object"
+        } else {
+            method_reference.class_name
+        };
+        let class = self.resolve_class(vm, call_stack, class_name)?;
+        // Method resolution is the same for all kinds of invocation: the referenced method
+        // need not be declared by the referenced class, i.e. `super.method()` refers to the
+        // direct superclass even when the method is inherited from further up the hierarchy,
+        // and static methods can be invoked through a subclass
+        let class_and_method = Self::get_method_checking_superclasses(class, method_reference)?;
+        if vm.access_checks() {
+            access_control::check_class_access(self.class_and_method.class, class)?;
+            access_control::check_method_access(self.class_and_method.class, &class_and_method)?;
         }
+        Ok(class_and_method)
     }
 
-    fn get_method_of_class<'b>(
-        class: &'b Class<'a>,
+    /// Selects the method invoked by `invokespecial`. When the current class has the
+    /// `ACC_SUPER` flag, which all compilers since java 1.0.2 set, a call to a method of one
+    /// of its superclasses invokes the version that its direct superclass inherits, even if
+    /// the bytecode refers to a class further up the hierarchy, i.e. because an intermediate
+    /// class started overriding the method after the current class was compiled.
+    fn get_method_to_invoke_special(
+        &self,
+        vm: &mut Vm<'a>,
+        call_stack: &mut CallStack<'a>,
         method_reference: MethodReference,
-    ) -> Result<&'b ClassFileMethod, MethodCallFailed<'a>> {
-        class
-            .find_method(
-                method_reference.method_name,
-                method_reference.type_descriptor,
-            )
-            .ok_or(MethodCallFailed::InternalError(
-                VmError::MethodNotFoundException(
-                    class.name.to_string(),
-                    method_reference.method_name.to_string(),
-                    method_reference.type_descriptor.to_string(),
-                ),
-            ))
+    ) -> Result<ClassAndMethod<'a>, MethodCallFailed<'a>> {
+        let class_and_method =
+            self.get_method_to_invoke_statically(vm, call_stack, method_reference)?;
+        let current_class = self.class_and_method.class;
+        if method_reference.method_name == "<init>"
+            || !current_class.flags.contains(ClassAccessFlags::SUPER)
+        {
+            return Ok(class_and_method);
+        }
+        let Some(superclass) = current_class.superclass else {
+            return Ok(class_and_method);
+        };
+        let refers_to_further_superclass = superclass.name != method_reference.class_name
+            && vm
+                .find_class_by_name(method_reference.class_name)
+                .is_some_and(|referenced_class| {
+                    !referenced_class.flags.contains(ClassAccessFlags::INTERFACE)
+                        && superclass.is_subclass_of(referenced_class)
+                });
+        if refers_to_further_superclass {
+            Self::get_method_checking_superclasses(superclass, method_reference)
+        } else {
+            Ok(class_and_method)
+        }
     }
 
+    /// Looks up the method that `invokevirtual` selects for an instance of the given class,
+    /// which can be a default method of one of its superinterfaces
+    pub(crate) fn find_virtual_method<'b>(
+        class: &'b Class<'a>,
+        method_name: &str,
+        type_descriptor: &str,
+    ) -> Result<ClassAndMethod<'b>, MethodCallFailed<'a>> {
+        Self::get_method_checking_superclasses(
+            class,
+            MethodReference {
+                class_name: &class.name,
+                method_name,
+                type_descriptor,
+            },
+        )
+    }
+
+    /// Looks up a method in the given class and its superclasses and, failing that, in its
+    /// superinterfaces, preferring default methods over abstract ones
     fn get_method_checking_superclasses<'b>(
         class: &'b Class<'a>,
         method_reference: MethodReference,
     ) -> Result<ClassAndMethod<'b>, MethodCallFailed<'a>> {
-        let mut curr_class = class;
-        loop {
-            if let Some(method) = curr_class.find_method(
+        let mut curr_class = Some(class);
+        while let Some(candidate) = curr_class {
+            if let Some(method) = candidate.find_method(
                 method_reference.method_name,
                 method_reference.type_descriptor,
             ) {
                 return Ok(ClassAndMethod {
-                    class: curr_class,
+                    class: candidate,
                     method,
                 });
             }
+            curr_class = candidate.superclass;
+        }
 
-            if let Some(superclass) = curr_class.superclass {
-                curr_class = superclass;
-            } else {
-                return Err(MethodCallFailed::InternalError(
-                    VmError::MethodNotFoundException(
-                        class.name.to_string(),
-                        method_reference.method_name.to_string(),
-                        method_reference.type_descriptor.to_string(),
-                    ),
-                ));
+        Self::get_method_of_superinterfaces(class, &method_reference).ok_or(
+            MethodCallFailed::InternalError(VmError::MethodNotFoundException(
+                class.name.to_string(),
+                method_reference.method_name.to_string(),
+                method_reference.type_descriptor.to_string(),
+            )),
+        )
+    }
+
+    fn get_method_of_superinterfaces<'b>(
+        class: &'b Class<'a>,
+        method_reference: &MethodReference,
+    ) -> Option<ClassAndMethod<'b>> {
+        let mut interfaces: Vec<&'b Class<'a>> = Vec::new();
+        let mut curr_class = Some(class);
+        while let Some(candidate) = curr_class {
+            interfaces.extend(candidate.interfaces.iter().copied());
+            curr_class = candidate.superclass;
+        }
+
+        let mut abstract_method = None;
+        let mut index = 0;
+        while let Some(interface) = interfaces.get(index).copied() {
+            index += 1;
+            let method = interface
+                .find_method(
+                    method_reference.method_name,
+                    method_reference.type_descriptor,
+                )
+                .filter(|method| {
+                    !method
+                        .flags
+                        .intersects(MethodFlags::STATIC | MethodFlags::PRIVATE)
+                });
+            match method {
+                Some(method) if !method.flags.contains(MethodFlags::ABSTRACT) => {
+                    return Some(ClassAndMethod {
+                        class: interface,
+                        method,
+                    })
+                }
+                Some(method) => {
+                    abstract_method.get_or_insert(ClassAndMethod {
+                        class: interface,
+                        method,
+                    });
+                }
+                None => {}
             }
+            interfaces.extend(interface.interfaces.iter().copied());
         }
+        abstract_method
     }
 
+    /// Selects the method to invoke on the given receiver, using and updating the inline cache
+    /// of the call site
     fn resolve_virtual_method(
-        vm: &Vm<'a>,
+        vm: &mut Vm<'a>,
+        call_site: Option<InstructionSite>,
         receiver: Option<AbstractObject>,
-        class_and_method: ClassAndMethod,
+        class_and_method: ClassAndMethod<'a>,
     ) -> Result<ClassAndMethod<'a>, MethodCallFailed<'a>> {
         match receiver {
+            // Arrays do not override any method of Object
+            Some(receiver) if receiver.kind() == ObjectKind::Array => Ok(class_and_method),
             Some(receiver) if receiver.kind() == ObjectKind::Object => {
+                let cached_target = call_site
+                    .and_then(|call_site| vm.inline_caches.target(call_site, receiver.class_id()));
+                if let Some(target) = cached_target {
+                    return Ok(target);
+                }
                 let receiver_class = vm.find_class_by_id(receiver.class_id()).ok_or(
                     VmError::ClassNotFoundException(receiver.class_id().to_string()),
                 )?;
-                let resolved_method = Self::get_method_checking_superclasses(
-                    receiver_class,
-                    MethodReference {
-                        class_name: &class_and_method.class.name,
-                        method_name: &class_and_method.method.name,
-                        type_descriptor: &class_and_method.method.type_descriptor,
-                    },
-                )?;
-                debug!(
+                let vtable_method = class_and_method
+                    .class
+                    .vtable_index_of(class_and_method.method)
+                    .and_then(|index| receiver_class.vtable_method(index));
+                let resolved_method = match vtable_method {
+                    Some(resolved_method) => resolved_method,
+                    // Interface methods have no entry in the virtual method tables
+                    None => Self::get_method_checking_superclasses(
+                        receiver_class,
+                        MethodReference {
+                            class_name: &class_and_method.class.name,
+                            method_name: &class_and_method.method.name,
+                            type_descriptor: &class_and_method.method.type_descriptor,
+                        },
+                    )?,
+                };
+                vm_log!(
+                    vm.log_config(),
+                    LogCategory::Resolution,
+                    Level::Debug,
                     "resolved virtual method {}.{}:{} on object of class {}: using version of class {}",
                     class_and_method.class.name,
                     class_and_method.method.name,
@@ -1031,6 +1499,13 @@ impl<'a> CallFrame<'a> {
                     receiver_class.name,
                     resolved_method.class.name,
                 );
+                if let Some(call_site) = call_site {
+                    vm.inline_caches.set_target(
+                        call_site,
+                        receiver.class_id(),
+                        resolved_method.clone(),
+                    );
+                }
                 Ok(resolved_method)
             }
             _ => Err(MethodCallFailed::InternalError(
@@ -1103,13 +1578,14 @@ impl<'a> CallFrame<'a> {
                 // TODO: here we should check "instanceof" the expected class of a subclass
                 Ok(object.clone())
             }
+            Null => Err(VmError::NullPointerException),
             _ => Err(VmError::ValidationException),
         }
     }
 
     fn validate_type_opt(
         vm: &Vm,
-        expected_type: Option<FieldType>,
+        expected_type: Option<&FieldType>,
         value: &Option<Value<'a>>,
     ) -> Result<(), VmError> {
         match expected_type {
@@ -1124,7 +1600,7 @@ impl<'a> CallFrame<'a> {
         }
     }
 
-    fn validate_type(vm: &Vm, expected_type: FieldType, value: &Value) -> Result<(), VmError> {
+    fn validate_type(vm: &Vm, expected_type: &FieldType, value: &Value) -> Result<(), VmError> {
         if value.matches_type(expected_type, vm, |class_name| {
             vm.find_class_by_name(class_name)
         }) {
@@ -1136,7 +1612,6 @@ impl<'a> CallFrame<'a> {
 
     fn execute_areturn(&mut self) -> MethodCallResult<'a> {
         let result = self.pop()?;
-        self.debug_done_execution(Some(&result));
         Ok(Some(result))
     }
 
@@ -1147,7 +1622,7 @@ impl<'a> CallFrame<'a> {
 
     fn get_local_int(&self, vm: &Vm, index: usize) -> Result<Value<'a>, VmError> {
         let variable = self.locals.get(index).ok_or(VmError::ValidationException)?;
-        Self::validate_type(vm, Base(BaseType::Int), variable)?;
+        Self::validate_type(vm, &Base(BaseType::Int), variable)?;
         Ok(variable.clone())
     }
 
@@ -1191,13 +1666,33 @@ impl<'a> CallFrame<'a> {
     generate_execute_coerce!(coerce_float, pop_float, f32);
     generate_execute_coerce!(coerce_double, pop_double, f64);
 
-    fn goto(&mut self, jump_address: u16) {
+    fn goto(&mut self, jump_address: u32) {
         self.pc = ProgramCounter(jump_address);
     }
 
+    /// Jumps to a subroutine, pushing the address of the next instruction so that the
+    /// subroutine can return to it with `ret`
+    fn execute_jsr(&mut self, jump_address: u32) -> Result<(), MethodCallFailed<'a>> {
+        self.push(Value::ReturnAddress(self.pc))?;
+        self.goto(jump_address);
+        Ok(())
+    }
+
+    fn execute_ret(&mut self, index: usize) -> Result<(), MethodCallFailed<'a>> {
+        match self.locals.get(index) {
+            Some(&Value::ReturnAddress(return_address)) => {
+                self.pc = return_address;
+                Ok(())
+            }
+            _ => Err(MethodCallFailed::InternalError(
+                VmError::ValidationException,
+            )),
+        }
+    }
+
     fn execute_if<T>(
         &mut self,
-        jump_address: u16,
+        jump_address: u32,
         comparator: T,
     ) -> Result<(), MethodCallFailed<'a>>
     where
@@ -1212,7 +1707,7 @@ impl<'a> CallFrame<'a> {
 
     fn execute_if_icmp<T>(
         &mut self,
-        jump_address: u16,
+        jump_address: u32,
         comparator: T,
     ) -> Result<(), MethodCallFailed<'a>>
     where
@@ -1228,7 +1723,7 @@ impl<'a> CallFrame<'a> {
 
     fn execute_if_null(
         &mut self,
-        jump_address: u16,
+        jump_address: u32,
         jump_on_null: bool,
     ) -> Result<(), MethodCallFailed<'a>> {
         let value = self.pop()?;
@@ -1254,7 +1749,7 @@ impl<'a> CallFrame<'a> {
 
     fn execute_if_acmp(
         &mut self,
-        jump_address: u16,
+        jump_address: u32,
         jump_on_equal: bool,
     ) -> Result<(), MethodCallFailed<'a>> {
         let value2 = self.pop()?;
@@ -1296,6 +1791,7 @@ impl<'a> CallFrame<'a> {
 
     fn execute_aload(&mut self, index: usize) -> Result<(), MethodCallFailed<'a>> {
         let local = self.locals.get(index).ok_or(VmError::ValidationException)?;
+        // Unlike astore, aload cannot be used with return addresses
         match local {
             Value::Object(..) | Null => self.push(local.clone()),
             _ => Err(MethodCallFailed::InternalError(
@@ -1320,6 +1816,11 @@ impl<'a> CallFrame<'a> {
                 self.locals[index] = value;
                 Ok(())
             }
+            // Subroutines start by storing the return address pushed by `jsr` in a local
+            Value::ReturnAddress(_) => {
+                self.locals[index] = value;
+                Ok(())
+            }
             _ => Err(MethodCallFailed::InternalError(
                 VmError::ValidationException,
             )),
@@ -1345,7 +1846,7 @@ impl<'a> CallFrame<'a> {
                 let constant = self.get_constant(*string_index)?;
                 match constant {
                     ConstantPoolEntry::Utf8(string) => {
-                        let string_object = new_java_lang_string_object(vm, call_stack, string)?;
+                        let string_object = get_interned_java_lang_string(vm, call_stack, string)?;
                         self.push(Value::Object(string_object))
                     }
                     _ => Err(MethodCallFailed::InternalError(
@@ -1357,7 +1858,7 @@ impl<'a> CallFrame<'a> {
                 let constant = self.get_constant(*class_index)?;
                 match constant {
                     ConstantPoolEntry::Utf8(class_name) => {
-                        let class_object = new_java_lang_class_object(vm, call_stack, class_name)?;
+                        let class_object = get_java_lang_class_object(vm, call_stack, class_name)?;
                         self.push(Value::Object(class_object))
                     }
                     _ => Err(MethodCallFailed::InternalError(
@@ -1383,12 +1884,32 @@ impl<'a> CallFrame<'a> {
         }
     }
 
+    /// Pops the length of an array about to be allocated, throwing a
+    /// `NegativeArraySizeException` if it is negative
+    fn pop_array_length(
+        &mut self,
+        vm: &mut Vm<'a>,
+        call_stack: &mut CallStack<'a>,
+    ) -> Result<usize, MethodCallFailed<'a>> {
+        let length = self.pop_int()?;
+        match usize::try_from(length) {
+            Ok(length) => Ok(length),
+            Err(_) => throw_exception(
+                vm,
+                call_stack,
+                "java/lang/NegativeArraySizeException",
+                &length.to_string(),
+            ),
+        }
+    }
+
     fn execute_newarray(
         &mut self,
         vm: &mut Vm<'a>,
+        call_stack: &mut CallStack<'a>,
         array_type: NewArrayType,
     ) -> Result<(), MethodCallFailed<'a>> {
-        let length = self.pop_int()?.into_usize_safe();
+        let length = self.pop_array_length(vm, call_stack)?;
         let elements_type = match array_type {
             NewArrayType::Boolean => ArrayEntryType::Base(BaseType::Boolean),
             NewArrayType::Char => ArrayEntryType::Base(BaseType::Char),
@@ -1400,7 +1921,7 @@ impl<'a> CallFrame<'a> {
             NewArrayType::Long => ArrayEntryType::Base(BaseType::Long),
         };
 
-        let array = vm.new_array(elements_type, length);
+        let array = vm.new_array(elements_type, length)?;
         self.push(Value::Object(array))
     }
 
@@ -1410,18 +1931,142 @@ impl<'a> CallFrame<'a> {
         call_stack: &mut CallStack<'a>,
         constant_index: u16,
     ) -> Result<(), MethodCallFailed<'a>> {
-        let length = self.pop_int()?.into_usize_safe();
+        let length = self.pop_array_length(vm, call_stack)?;
         let class_name = self.get_constant_class_reference(constant_index)?;
-        let class = vm.get_or_resolve_class(call_stack, class_name)?;
-        let elements_type = ArrayEntryType::Object(class.id);
+        // The elements can be arrays themselves, i.e. for `new int[3][]`
+        let elements_type = if class_name.starts_with('[') {
+            Self::parse_array_type(class_name)?
+        } else {
+            FieldType::Object(class_name.to_string())
+        };
+        let elements_type = self.resolve_array_entry_type(vm, call_stack, &elements_type)?;
 
-        let array = vm.new_array(elements_type, length);
+        let array = vm.new_array(elements_type, length)?;
         self.push(Value::Object(array))
     }
 
+    fn execute_multianewarray(
+        &mut self,
+        vm: &mut Vm<'a>,
+        call_stack: &mut CallStack<'a>,
+        constant_index: u16,
+        dimensions: u8,
+    ) -> Result<(), MethodCallFailed<'a>> {
+        let class_name = self.get_constant_class_reference(constant_index)?;
+        let FieldType::Array(elements_type) = Self::parse_array_type(class_name)? else {
+            return Err(MethodCallFailed::InternalError(
+                VmError::ValidationException,
+            ));
+        };
+        let elements_type = self.resolve_array_entry_type(vm, call_stack, &elements_type)?;
+
+        // The lengths are pushed starting from the outermost dimension
+        let mut lengths = (0..dimensions)
+            .map(|_| self.pop_array_length(vm, call_stack))
+            .collect::<Result<Vec<usize>, MethodCallFailed<'a>>>()?;
+        lengths.reverse();
+        self.push_multi_dimensional_array(vm, elements_type, &lengths)
+    }
+
+    /// Allocates an array whose dimensions have the given lengths, starting from the outermost,
+    /// and pushes it on the stack. The arrays being filled are kept on the stack, since it is
+    /// a gc root and allocating the inner arrays could trigger the gc and move them.
+    fn push_multi_dimensional_array(
+        &mut self,
+        vm: &mut Vm<'a>,
+        elements_type: ArrayEntryType,
+        lengths: &[usize],
+    ) -> Result<(), MethodCallFailed<'a>> {
+        let (&length, inner_lengths) = lengths.split_first().ok_or(VmError::ValidationException)?;
+        let inner_elements_type = if inner_lengths.is_empty() {
+            None
+        } else {
+            Some(
+                elements_type
+                    .component_type()
+                    .ok_or(VmError::ValidationException)?,
+            )
+        };
+
+        let array = vm.new_array(elements_type, length)?;
+        self.push(Value::Object(array))?;
+        if let Some(inner_elements_type) = inner_elements_type {
+            for index in 0..length {
+                self.push_multi_dimensional_array(vm, inner_elements_type.clone(), inner_lengths)?;
+                let inner_array = self.pop()?;
+                let array = match self.stack.get(self.stack.len() - 1) {
+                    Some(Value::Object(array)) => array,
+                    _ => {
+                        return Err(MethodCallFailed::InternalError(
+                            VmError::ValidationException,
+                        ))
+                    }
+                };
+                vm.write_barrier(&inner_array);
+                array.set_element(index, inner_array)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn parse_array_type(descriptor: &str) -> Result<FieldType, VmError> {
+        FieldType::parse(descriptor).map_err(|_| VmError::ValidationException)
+    }
+
+    /// Returns the [ArrayEntryType] for the given type, resolving its class if needed
+    fn resolve_array_entry_type(
+        &self,
+        vm: &mut Vm<'a>,
+        call_stack: &mut CallStack<'a>,
+        field_type: &FieldType,
+    ) -> Result<ArrayEntryType, MethodCallFailed<'a>> {
+        Ok(match field_type {
+            Base(base_type) => ArrayEntryType::Base(base_type.clone()),
+            FieldType::Object(class_name) => {
+                ArrayEntryType::Object(self.resolve_class(vm, call_stack, class_name)?.id)
+            }
+            FieldType::Array(component_type) => ArrayEntryType::array_of(
+                &self.resolve_array_entry_type(vm, call_stack, component_type)?,
+            ),
+        })
+    }
+
+    /// Resolves a class referenced by the code of the current class, through the loader that
+    /// defined it; see [Vm::resolve_class_referenced_by]
+    fn resolve_class(
+        &self,
+        vm: &mut Vm<'a>,
+        call_stack: &mut CallStack<'a>,
+        class_name: &str,
+    ) -> Result<ClassRef<'a>, MethodCallFailed<'a>> {
+        vm.resolve_class_referenced_by(call_stack, self.class_and_method.class, class_name)
+    }
+
+    /// Abstract classes and interfaces cannot be instantiated
+    fn execute_new(
+        &mut self,
+        vm: &mut Vm<'a>,
+        call_stack: &mut CallStack<'a>,
+        constant_index: u16,
+    ) -> Result<(), MethodCallFailed<'a>> {
+        let class_name = self.get_constant_class_reference(constant_index)?;
+        let class = self.resolve_class(vm, call_stack, class_name)?;
+        self.initialize_class(vm, call_stack, class)?;
+        if class
+            .flags
+            .intersects(ClassAccessFlags::ABSTRACT | ClassAccessFlags::INTERFACE)
+        {
+            return Err(MethodCallFailed::InternalError(
+                VmError::InstantiationError(class.name.replace('/', ".")),
+            ));
+        }
+        let new_object = vm.new_object_of_class(class)?;
+        self.push(Value::Object(new_object))
+    }
+
     fn execute_array_length(&mut self) -> Result<(), MethodCallFailed<'a>> {
         let array = self.pop_array()?;
-        let len = array.len() as i32;
+        let len = array.len().narrow()?;
         self.push(Int(len))?;
         Ok(())
     }
@@ -1437,7 +2082,11 @@ impl<'a> CallFrame<'a> {
     generate_execute_array_load!(execute_laload, ArrayEntryType::Base(BaseType::Long));
     generate_execute_array_load!(execute_faload, ArrayEntryType::Base(BaseType::Float));
     generate_execute_array_load!(execute_daload, ArrayEntryType::Base(BaseType::Double));
-    generate_execute_array_load!(execute_aaload, ArrayEntryType::Object(..));
+    generate_execute_array_load!(
+        execute_aaload,
+        ArrayEntryType::Object(..),
+        ArrayEntryType::Array(..)
+    );
 
     generate_execute_array_store!(
         execute_bastore,
@@ -1483,29 +2132,48 @@ impl<'a> CallFrame<'a> {
         ArrayEntryType::Base(BaseType::Double)
     );
 
-    fn execute_aastore(&mut self, vm: &Vm) -> Result<(), MethodCallFailed<'a>> {
+    fn execute_aastore(&mut self, vm: &Vm<'a>) -> Result<(), MethodCallFailed<'a>> {
         let value = self.pop_object_or_null()?;
-        let index = self.pop_int()?.into_usize_safe();
+        let index = self.pop_array_index()?;
         let array = self.pop_array()?;
         match array.elements_type() {
-            ArrayEntryType::Object(elements_class_id) => {
-                let elements_class_name = vm.get_class_by_id(elements_class_id)?;
-                Self::validate_type(
-                    vm,
-                    FieldType::Object(elements_class_name.name.clone()),
-                    &value,
-                )?;
+            elements_type @ (ArrayEntryType::Object(_) | ArrayEntryType::Array(_)) => {
+                let elements_type = elements_type
+                    .into_field_type(vm)
+                    .ok_or(VmError::ValidationException)?;
+                // Arrays are covariant, so the type of the value can only be checked at runtime
+                if Self::validate_type(vm, &elements_type, &value).is_err() {
+                    return Err(MethodCallFailed::InternalError(
+                        VmError::ArrayStoreException(value.runtime_type_name(vm).replace('/', ".")),
+                    ));
+                }
+                vm.write_barrier(&value);
                 array.set_element(index, value)?
             }
-            _ => {
-                return Err(MethodCallFailed::InternalError(
-                    VmError::ValidationException,
-                ))
-            }
+            _ => return Err(self.array_type_mismatch(vm, "aastore", &array, Some(&value))),
         }
         Ok(())
     }
 
+    /// Builds the error for an array load or store, executed by the current instruction,
+    /// whose array's elements do not match the instruction or the stored value
+    fn array_type_mismatch(
+        &self,
+        vm: &Vm<'a>,
+        instruction: &'static str,
+        array: &impl Array<'a>,
+        value: Option<&Value<'a>>,
+    ) -> MethodCallFailed<'a> {
+        MethodCallFailed::InternalError(VmError::ArrayTypeMismatch(Box::new(ArrayTypeMismatch {
+            class_name: self.class_and_method.class.name.clone(),
+            method_name: self.class_and_method.method.name.to_string(),
+            pc: self.instruction_pc,
+            instruction,
+            array_element_type: array.elements_type().type_name(vm),
+            value_type: value.map(|value| value.runtime_type_name(vm)),
+        })))
+    }
+
     fn execute_instanceof(
         &mut self,
         vm: &mut Vm<'a>,
@@ -1514,7 +2182,7 @@ impl<'a> CallFrame<'a> {
     ) -> Result<(), MethodCallFailed<'a>> {
         let value = self.pop()?;
         let is_instance_of = self.is_instanceof(vm, call_stack, constant_index, &value)?;
-        self.push(Int(is_instance_of as i32))
+        self.push(Int(i32::from(is_instance_of)))
     }
 
     fn execute_checkcast(
@@ -1525,10 +2193,17 @@ impl<'a> CallFrame<'a> {
     ) -> Result<(), MethodCallFailed<'a>> {
         let value = self.pop()?;
         let is_instance_of = self.is_instanceof(vm, call_stack, constant_index, &value)?;
-        if is_instance_of {
+        // null can be cast to any type
+        if is_instance_of || value == Null {
             self.push(value)
         } else {
-            Err(MethodCallFailed::InternalError(VmError::ClassCastException))
+            let target_class_name = self.get_constant_class_reference(constant_index)?;
+            Err(MethodCallFailed::InternalError(
+                VmError::ClassCastException {
+                    class_name: value.runtime_type_name(vm).replace('/', "."),
+                    target_class_name: target_class_name.replace('/', "."),
+                },
+            ))
         }
     }
 
@@ -1543,40 +2218,30 @@ impl<'a> CallFrame<'a> {
         let class_name = self.get_constant_class_reference(constant_index)?;
 
         // TODO: we should model classes of arrays
-        // TODO: multidimensional arrays are not supported!
-        let (is_array, expected_class) = {
-            if class_name.starts_with("[L") && class_name.ends_with(';') {
-                (
-                    true,
-                    vm.get_or_resolve_class(call_stack, &class_name[2..class_name.len() - 1])?,
-                )
-            } else {
-                (false, vm.get_or_resolve_class(call_stack, class_name)?)
+        let resolution_result = if class_name.starts_with('[') {
+            let expected_type = Self::parse_array_type(class_name)?;
+            // Ensures that the innermost class, if any, is loaded
+            self.resolve_array_entry_type(vm, call_stack, &expected_type)
+                .map(|_| expected_type)
+        } else {
+            self.resolve_class(vm, call_stack, class_name)
+                .map(|_| FieldType::Object(class_name.to_string()))
+        };
+        let expected_type = match resolution_result {
+            // No object can be an instance of a class that does not exist
+            Err(MethodCallFailed::InternalError(VmError::ClassNotFoundException(_)))
+                if vm.lazy_linkage() =>
+            {
+                return Ok(false)
             }
+            result => result?,
         };
 
         let is_instance_of = match &value {
             Null => false,
-
-            Value::Object(object) => match object.kind() {
-                ObjectKind::Object => {
-                    if is_array {
-                        false
-                    } else {
-                        let object_class = vm.get_class_by_id(object.class_id())?;
-                        object_class.is_subclass_of(expected_class)
-                    }
-                }
-                ObjectKind::Array => match object.elements_type() {
-                    ArrayEntryType::Base(_) => false,
-                    ArrayEntryType::Object(elements_class_id) => {
-                        let components_class = vm.get_class_by_id(elements_class_id)?;
-                        components_class.is_subclass_of(expected_class)
-                    }
-                    ArrayEntryType::Array => false,
-                },
-            },
-
+            Value::Object(_) => value.matches_type(&expected_type, vm, |class_name| {
+                vm.find_class_by_name(class_name)
+            }),
             _ => {
                 return Err(MethodCallFailed::InternalError(
                     VmError::ValidationException,
@@ -1592,20 +2257,19 @@ impl<'a> CallFrame<'a> {
         field_index: u16,
     ) -> Result<(), MethodCallFailed<'a>> {
         let object = self.pop()?;
-        if let Value::Object(object_ref) = object {
+        if let Value::Object(object_ref) = &object {
             if object_ref.kind() == ObjectKind::Object {
-                let field_reference = self.get_constant_field_reference(field_index)?;
-                let object_class = vm.get_class_by_id(object_ref.class_id())?;
-                let (index, field) = Self::get_field(object_class, field_reference)?;
-                let field_value = object_ref.get_field(object_class, index);
-                Self::validate_type(vm, field.type_descriptor.clone(), &field_value)?;
+                let (index, field) = self.resolve_instance_field(vm, object_ref, field_index)?;
+                // No need to validate the value, since it was validated by putfield
+                let field_value = object_ref.get_field_of_type(index, &field.type_descriptor);
                 self.push(field_value)?;
                 return Ok(());
             }
         }
-        Err(MethodCallFailed::InternalError(
-            VmError::ValidationException,
-        ))
+        Err(MethodCallFailed::InternalError(match object {
+            Null => VmError::NullPointerException,
+            _ => VmError::ValidationException,
+        }))
     }
 
     fn execute_putfield(
@@ -1615,19 +2279,44 @@ impl<'a> CallFrame<'a> {
     ) -> Result<(), MethodCallFailed<'a>> {
         let value = self.pop()?;
         let object = self.pop()?;
-        if let Value::Object(object_ref) = object {
+        if let Value::Object(object_ref) = &object {
             if object_ref.kind() == ObjectKind::Object {
-                let field_reference = self.get_constant_field_reference(field_index)?;
-                let object_class = vm.get_class_by_id(object_ref.class_id())?;
-                let (index, field) = Self::get_field(object_class, field_reference)?;
-                Self::validate_type(vm, field.type_descriptor.clone(), &value)?;
+                let (index, field) = self.resolve_instance_field(vm, object_ref, field_index)?;
+                Self::validate_type(vm, &field.type_descriptor, &value)?;
+                vm.write_barrier(&value);
                 object_ref.set_field(index, value);
                 return Ok(());
             }
         }
-        Err(MethodCallFailed::InternalError(
-            VmError::ValidationException,
-        ))
+        Err(MethodCallFailed::InternalError(match object {
+            Null => VmError::NullPointerException,
+            _ => VmError::ValidationException,
+        }))
+    }
+
+    /// Finds the field accessed by a getfield or putfield on the given receiver, quickening the
+    /// instruction the first time it is executed with a receiver of that class
+    fn resolve_instance_field(
+        &self,
+        vm: &mut Vm<'a>,
+        object: &AbstractObject<'a>,
+        field_index: u16,
+    ) -> Result<(usize, &'a ClassFileField<'a>), VmError> {
+        let instruction_site = InstructionSite::of(&self.class_and_method, self.instruction_pc);
+        let quickened =
+            instruction_site.and_then(|site| vm.quickened_fields.get(site, object.class_id()));
+        if let Some(quickened) = quickened {
+            return Ok((quickened.index, quickened.field));
+        }
+
+        let field_reference = self.get_constant_field_reference(field_index)?;
+        let object_class = vm.get_class_by_id(object.class_id())?;
+        let (index, field) = self.get_field(vm, object_class, field_reference)?;
+        if let Some(site) = instruction_site {
+            vm.quickened_fields
+                .insert(site, object.class_id(), index, field);
+        }
+        Ok((index, field))
     }
 
     fn execute_getstatic(
@@ -1637,13 +2326,15 @@ impl<'a> CallFrame<'a> {
         field_index: u16,
     ) -> Result<(), MethodCallFailed<'a>> {
         let field_reference = self.get_constant_field_reference(field_index)?;
-        let object_class = vm.get_or_resolve_class(call_stack, field_reference.class_name)?;
-        let (index, field) = Self::get_field(object_class, field_reference)?;
-        let object = vm.get_static_instance(self.class_and_method.class.id);
+        let referenced_class = self.resolve_class(vm, call_stack, field_reference.class_name)?;
+        let (declaring_class, index, field) =
+            self.get_static_field(vm, referenced_class, field_reference)?;
+        self.initialize_class(vm, call_stack, declaring_class)?;
+        let object = vm.get_static_instance(declaring_class.id);
         if let Some(object_ref) = object {
             if object_ref.kind() == ObjectKind::Object {
-                let field_value = object_ref.get_field(object_class, index);
-                Self::validate_type(vm, field.type_descriptor.clone(), &field_value)?;
+                let field_value = object_ref.get_field(declaring_class, index);
+                Self::validate_type(vm, &field.type_descriptor, &field_value)?;
                 self.push(field_value)?;
                 return Ok(());
             }
@@ -1660,13 +2351,16 @@ impl<'a> CallFrame<'a> {
         field_index: u16,
     ) -> Result<(), MethodCallFailed<'a>> {
         let field_reference = self.get_constant_field_reference(field_index)?;
-        let object_class = vm.get_or_resolve_class(call_stack, field_reference.class_name)?;
-        let (index, field) = Self::get_field(object_class, field_reference)?;
+        let referenced_class = self.resolve_class(vm, call_stack, field_reference.class_name)?;
+        let (declaring_class, index, field) =
+            self.get_static_field(vm, referenced_class, field_reference)?;
+        self.initialize_class(vm, call_stack, declaring_class)?;
         let value = self.pop()?;
-        Self::validate_type(vm, field.type_descriptor.clone(), &value)?;
-        let object = vm.get_static_instance(self.class_and_method.class.id);
+        Self::validate_type(vm, &field.type_descriptor, &value)?;
+        let object = vm.get_static_instance(declaring_class.id);
         if let Some(object_ref) = object {
             if object_ref.kind() == ObjectKind::Object {
+                vm.write_barrier(&value);
                 object_ref.set_field(index, value);
                 return Ok(());
             }
@@ -1676,28 +2370,58 @@ impl<'a> CallFrame<'a> {
         ))
     }
 
-    fn execute_monitorenter(&mut self) -> Result<(), MethodCallFailed<'a>> {
+    /// Initializes a class used by the instruction being executed, which must not have
+    /// modified the frame yet: if another thread is initializing the class, and this one can
+    /// be suspended, the instruction is executed again when the thread is resumed
+    fn initialize_class(
+        &mut self,
+        vm: &mut Vm<'a>,
+        call_stack: &mut CallStack<'a>,
+        class: ClassRef<'a>,
+    ) -> Result<(), MethodCallFailed<'a>> {
+        if vm.can_suspend_current_thread() && vm.is_class_initialization_blocked(class) {
+            self.pc = self.instruction_pc;
+            return Err(MethodCallFailed::InternalError(VmError::ThreadSuspended));
+        }
+        vm.initialize_class(call_stack, class)
+    }
+
+    fn execute_monitorenter(&mut self, vm: &mut Vm<'a>) -> Result<(), MethodCallFailed<'a>> {
         let obj = self.pop()?;
         match obj {
-            Value::Object(_) => {
-                // We don't really have monitors or lock, since we are single-threaded,
-                // so any monitor access will succeed!
+            Value::Object(mut object) => {
+                // If another thread owns the monitor, we let it run until it releases it
+                let thread = vm.current_thread_id();
+                if object.try_monitor_enter(thread) {
+                    return Ok(());
+                }
+                if vm.can_suspend_current_thread() {
+                    // The instruction is executed again when the thread is resumed
+                    self.push(Value::Object(object))?;
+                    self.pc = self.instruction_pc;
+                    return Err(MethodCallFailed::InternalError(VmError::ThreadSuspended));
+                }
+                while !object.try_monitor_enter(thread) {
+                    object = vm.yield_thread_keeping(object)?;
+                }
                 Ok(())
             }
+            Null => Err(MethodCallFailed::InternalError(
+                VmError::NullPointerException,
+            )),
             _ => Err(MethodCallFailed::InternalError(
                 VmError::ValidationException,
             )),
         }
     }
 
-    fn execute_monitorexit(&mut self) -> Result<(), MethodCallFailed<'a>> {
+    fn execute_monitorexit(&mut self, vm: &Vm<'a>) -> Result<(), MethodCallFailed<'a>> {
         let obj = self.pop()?;
         match obj {
-            Value::Object(_) => {
-                // We don't really have monitors or lock, since we are single-threaded,
-                // so any monitor access will succeed!
-                Ok(())
-            }
+            Value::Object(object) => Ok(object.monitor_exit(vm.current_thread_id())?),
+            Null => Err(MethodCallFailed::InternalError(
+                VmError::NullPointerException,
+            )),
             _ => Err(MethodCallFailed::InternalError(
                 VmError::ValidationException,
             )),
@@ -1710,6 +2434,9 @@ impl<'a> CallFrame<'a> {
             Value::Object(exception) => {
                 Err(MethodCallFailed::ExceptionThrown(JavaException(exception)))
             }
+            Null => Err(MethodCallFailed::InternalError(
+                VmError::NullPointerException,
+            )),
             _ => Err(MethodCallFailed::InternalError(
                 VmError::ValidationException,
             )),
@@ -1719,7 +2446,6 @@ impl<'a> CallFrame<'a> {
     fn find_exception_handler(
         &self,
         vm: &mut Vm<'a>,
-        call_stack: &mut CallStack<'a>,
         executed_instruction_pc: ProgramCounter,
         exception: &JavaException<'a>,
     ) -> Result<Option<ProgramCounter>, MethodCallFailed<'a>> {
@@ -1740,7 +2466,17 @@ impl<'a> CallFrame<'a> {
             match &catch_handler.catch_class {
                 None => return Ok(Some(catch_handler.handler_pc)),
                 Some(class_name) => {
-                    let catch_class = vm.get_or_resolve_class(call_stack, class_name)?;
+                    let catch_class = match vm.resolve_class(class_name) {
+                        // The class of the exception exists, so it cannot extend a missing one,
+                        // nor one that the loader of the current class has not loaded yet
+                        Err(VmError::ClassNotFoundException(_))
+                            if vm.lazy_linkage()
+                                || vm.defining_loader(self.class_and_method.class).is_some() =>
+                        {
+                            continue
+                        }
+                        result => result?,
+                    };
                     let exception_class = vm.get_class_by_id(exception.0.class_id())?;
                     if exception_class.is_subclass_of(catch_class) {
                         return Ok(Some(catch_handler.handler_pc));
@@ -1751,48 +2487,128 @@ impl<'a> CallFrame<'a> {
         Ok(None)
     }
 
-    fn debug_start_execution(&self) {
-        debug!(
+    fn debug_start_execution(&self, vm: &Vm<'a>) {
+        vm_log!(
+            vm.log_config(),
+            LogCategory::Instructions,
+            Level::Debug,
             "starting execution of method {}::{} - locals are {:?}",
-            self.class_and_method.class.name, self.class_and_method.method.name, self.locals
+            self.class_and_method.class.name,
+            self.class_and_method.method.name,
+            self.locals
         )
     }
 
     fn debug_print_status(&self, instruction: &Instruction) {
+        let target = LogCategory::Instructions.target();
         debug!(
+            target: target,
             "FRAME STATUS: executing {} signature {} pc: {}",
             self.to_stack_trace_element(),
             self.class_and_method.method.type_descriptor,
             self.pc
         );
-        debug!("  stack:");
+        debug!(target: target, "  stack:");
         for stack_entry in self.stack.iter() {
-            debug!("  - {:?}", stack_entry);
+            debug!(target: target, "  - {:?}", stack_entry);
         }
-        debug!("  locals:");
+        debug!(target: target, "  locals:");
         for local_variable in self.locals.iter() {
-            debug!("  - {:?}", local_variable);
+            debug!(target: target, "  - {:?}", local_variable);
         }
-        debug!("  next instruction: {:?}", instruction)
+        debug!(target: target, "  next instruction: {:?}", instruction)
     }
 
-    fn debug_done_execution(&self, result: Option<&Value>) {
-        debug!(
+    fn debug_done_execution(&self, vm: &Vm<'a>, result: Option<&Value>) {
+        vm_log!(
+            vm.log_config(),
+            LogCategory::Instructions,
+            Level::Debug,
             "completed execution of method {}::{} - result is {:?}",
-            self.class_and_method.class.name, self.class_and_method.method.name, result
+            self.class_and_method.class.name,
+            self.class_and_method.method.name,
+            result
         )
     }
 
     pub fn gc_roots(&mut self) -> impl Iterator<Item = *mut AbstractObject<'a>> {
         let mut roots = vec![];
         roots.extend(self.stack.iter_mut().filter_map(|v| match v {
-            Value::Object(o) => Some(o as *mut AbstractObject),
+            Value::Object(o) => Some(std::ptr::from_mut(o)),
             _ => None,
         }));
         roots.extend(self.locals.iter_mut().filter_map(|v| match v {
-            Value::Object(o) => Some(o as *mut AbstractObject),
+            Value::Object(o) => Some(std::ptr::from_mut(o)),
             _ => None,
         }));
+        roots.extend(self.monitor.as_mut().map(std::ptr::from_mut));
         roots.into_iter()
     }
 }
+
+/// Numeric conversions, used by the conversion instructions (e.g. `i2b`) and when storing
+/// values in arrays. The JVM specification defines them with the same truncating and
+/// saturating semantics of Rust's `as` casts.
+#[allow(clippy::as_conversions, clippy::cast_possible_truncation)]
+impl<'a> CallFrame<'a> {
+    fn i2b(value: i32) -> Value<'a> {
+        Int((value as i8) as i32)
+    }
+    fn i2c(value: i32) -> Value<'a> {
+        Int((value as u16) as i32)
+    }
+    fn i2s(value: i32) -> Value<'a> {
+        Int((value as i16) as i32)
+    }
+    fn i2i(value: i32) -> Value<'a> {
+        Int(value)
+    }
+    fn i2f(value: i32) -> Value<'a> {
+        Float(value as f32)
+    }
+    fn i2l(value: i32) -> Value<'a> {
+        Long(i64::from(value))
+    }
+    fn i2d(value: i32) -> Value<'a> {
+        Double(f64::from(value))
+    }
+
+    fn l2i(value: i64) -> Value<'a> {
+        Int(value as i32)
+    }
+    fn l2f(value: i64) -> Value<'a> {
+        Float(value as f32)
+    }
+    fn l2l(value: i64) -> Value<'a> {
+        Long(value)
+    }
+    fn l2d(value: i64) -> Value<'a> {
+        Double(value as f64)
+    }
+
+    fn f2i(value: f32) -> Value<'a> {
+        Int(value as i32)
+    }
+    fn f2l(value: f32) -> Value<'a> {
+        Long(value as i64)
+    }
+    fn f2f(value: f32) -> Value<'a> {
+        Float(value)
+    }
+    fn f2d(value: f32) -> Value<'a> {
+        Double(f64::from(value))
+    }
+
+    fn d2i(value: f64) -> Value<'a> {
+        Int(value as i32)
+    }
+    fn d2f(value: f64) -> Value<'a> {
+        Float(value as f32)
+    }
+    fn d2l(value: f64) -> Value<'a> {
+        Long(value as i64)
+    }
+    fn d2d(value: f64) -> Value<'a> {
+        Double(value)
+    }
+}
@@ -7,8 +7,14 @@ use rjvm_reader::{
 };
 
 use crate::{
-    abstract_object::AbstractObject, call_frame::CallFrame, class_and_method::ClassAndMethod,
-    stack_trace_element::StackTraceElement, value::Value, vm_error::VmError,
+    abstract_object::AbstractObject,
+    call_frame::{CallFrame, MethodCallResult},
+    class::ClassRef,
+    class_and_method::ClassAndMethod,
+    stack_trace_element::StackTraceElement,
+    thread_scheduler::ThreadId,
+    value::Value,
+    vm_error::VmError,
 };
 
 /// A call stack, which will include multiple frames, one for each method call.
@@ -17,10 +23,32 @@ use crate::{
 // we try to get a stack trace _while_ executing a method, which we need for exceptions.
 // This also means that we _never_ deallocate the call frames, even after we have finished
 // executing them!
-#[derive(Default)]
 pub struct CallStack<'a> {
     frames: Vec<CallFrameReference<'a>>,
     allocator: Arena<CallFrame<'a>>,
+    /// The maximum number of frames; adding more fails with a `StackOverflowError`
+    max_depth: usize,
+    /// Whether a `StackOverflowError` is being constructed, which needs some more frames
+    overflowing: bool,
+}
+
+/// The default maximum depth of the call stacks. Each java frame also uses some native
+/// stack, since the interpreter is recursive, so this must not be too high: with this value,
+/// 8 MiB of native stack (the usual size for the main thread) are enough even in debug builds.
+pub const DEFAULT_MAX_CALL_STACK_DEPTH: usize = 1024;
+
+/// The frames that can be added beyond the maximum depth, to construct the `StackOverflowError`
+const STACK_OVERFLOW_HEADROOM: usize = 64;
+
+impl<'a> Default for CallStack<'a> {
+    fn default() -> Self {
+        Self {
+            frames: Default::default(),
+            allocator: Default::default(),
+            max_depth: DEFAULT_MAX_CALL_STACK_DEPTH,
+            overflowing: false,
+        }
+    }
 }
 
 // SAFETY: The pointer will be valid until the generating call stack is,
@@ -54,12 +82,30 @@ impl<'a> CallStack<'a> {
         receiver: Option<AbstractObject<'a>>,
         args: Vec<Value<'a>>,
     ) -> Result<CallFrameReference<'a>, VmError> {
+        let max_depth = if self.overflowing {
+            self.max_depth + STACK_OVERFLOW_HEADROOM
+        } else {
+            self.max_depth
+        };
+        if self.frames.len() >= max_depth {
+            return Err(VmError::StackOverflowError);
+        }
         Self::check_receiver(&class_and_method, receiver.clone())?;
         let code = Self::get_code(&class_and_method)?;
+        // Synchronized native methods have no frame and are not locked: they run to completion
+        // without letting any other code observe the object.
+        // TODO: static synchronized methods should lock the java.lang.Class instance of their
+        //  class, which would have to be created here if it does not exist yet
+        let monitor = receiver
+            .clone()
+            .filter(|_| class_and_method.method.is_synchronized());
         let locals = Self::prepare_locals(code, receiver, args);
         let new_frame = self
             .allocator
             .alloc(CallFrame::new(class_and_method, locals));
+        if let Some(monitor) = monitor {
+            new_frame.set_monitor(monitor);
+        }
 
         let reference = CallFrameReference(new_frame);
         self.frames.push(reference.clone());
@@ -81,11 +127,23 @@ impl<'a> CallStack<'a> {
     }
 
     fn get_code<'b>(
-        class_and_method: &'b ClassAndMethod,
-    ) -> Result<&'b ClassFileMethodCode, VmError> {
+        class_and_method: &'b ClassAndMethod<'a>,
+    ) -> Result<&'b ClassFileMethodCode<'a>, VmError> {
         if class_and_method.is_native() {
             return Err(VmError::NotImplemented);
         };
+        if class_and_method
+            .method
+            .flags
+            .contains(MethodFlags::ABSTRACT)
+        {
+            return Err(VmError::AbstractMethodError(format!(
+                "{}.{}{}",
+                class_and_method.class.name.replace('/', "."),
+                class_and_method.method.name,
+                class_and_method.method.type_descriptor
+            )));
+        }
 
         let code = &class_and_method.method.code.as_ref().unwrap();
         Ok(code)
@@ -100,7 +158,7 @@ impl<'a> CallStack<'a> {
         let mut locals: Vec<Value<'a>> = receiver
             .map(Value::Object)
             .into_iter()
-            .chain(args.into_iter())
+            .chain(args)
             .collect();
         while locals.len() < code.max_locals.into_usize_safe() {
             locals.push(Value::Uninitialized);
@@ -108,6 +166,25 @@ impl<'a> CallStack<'a> {
         locals
     }
 
+    /// Returns the number of frames currently in the stack
+    pub fn depth(&self) -> usize {
+        self.frames.len()
+    }
+
+    pub fn max_depth(&self) -> usize {
+        self.max_depth
+    }
+
+    pub fn set_max_depth(&mut self, max_depth: usize) {
+        self.max_depth = max_depth;
+    }
+
+    /// While set, some frames can be added beyond the maximum depth,
+    /// so that the `StackOverflowError` can be constructed
+    pub(crate) fn set_overflowing(&mut self, overflowing: bool) {
+        self.overflowing = overflowing;
+    }
+
     pub fn pop_frame(&mut self) -> Result<(), VmError> {
         self.frames
             .pop()
@@ -115,6 +192,30 @@ impl<'a> CallStack<'a> {
             .ok_or(VmError::ValidationException)
     }
 
+    /// The frame of the innermost method, if any
+    pub(crate) fn top_frame(&self) -> Option<CallFrameReference<'a>> {
+        self.frames.last().cloned()
+    }
+
+    /// Pops the frame of a method that has completed, normally or not, with the given result,
+    /// on the given thread. The monitor of a synchronized method is released even when it
+    /// completes abruptly; an exception thrown by the method takes precedence over a failure
+    /// to release it.
+    pub(crate) fn pop_completed_frame(
+        &mut self,
+        result: MethodCallResult<'a>,
+        thread: ThreadId,
+    ) -> MethodCallResult<'a> {
+        let mut frame = self
+            .frames
+            .pop()
+            .expect("should be able to pop the frame we just pushed");
+        let monitor_exit_result = frame.as_mut().exit_monitor(thread);
+        let value = result?;
+        monitor_exit_result?;
+        Ok(value)
+    }
+
     pub fn get_stack_trace_elements(&self) -> Vec<StackTraceElement<'a>> {
         self.frames
             .iter()
@@ -123,6 +224,16 @@ impl<'a> CallStack<'a> {
             .collect()
     }
 
+    /// The class of the method executing in the given frame, counting from the top of the
+    /// stack, i.e. `0` is the innermost method
+    pub(crate) fn class_of_frame(&self, depth_from_top: usize) -> Option<ClassRef<'a>> {
+        self.frames
+            .iter()
+            .rev()
+            .nth(depth_from_top)
+            .map(|frame| frame.as_ref().class())
+    }
+
     pub fn gc_roots(&mut self) -> impl Iterator<Item = *mut AbstractObject<'a>> {
         let mut roots = vec![];
         roots.extend(
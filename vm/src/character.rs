@@ -0,0 +1,211 @@
+// Implementation of the hot static methods of `java.lang.Character`.
+//
+// ASCII characters are classified exactly as java does. For the rest of Unicode, we rely on
+// a compact table for decimal digits and delegate everything else to Rust's `char` methods.
+// Neither follows the Unicode version of the class library: java 7 uses Unicode 6.0, the
+// digit table is frozen at Unicode 14.0, and the `char` methods follow the Unicode version of
+// the Rust toolchain. Characters encoded after Unicode 6.0, e.g. the digits of Sora Sompeng
+// (`U+110F0`), are thus classified although java 7 considers them unassigned.
+// Known deviations:
+// - `is_letter` uses the `Alphabetic` property, which also includes letter numbers (e.g.
+//   roman numerals) and some combining marks, whereas java only accepts the `L*` categories;
+// - `to_upper_case` and `to_lower_case` use the full case mapping, and return the character
+//   unchanged when it maps to more than one character, whereas java uses the simple case
+//   mapping. The two only differ for a handful of characters, such as `U+1F80`.
+
+/// The first code point of each range of ten characters in the Unicode
+/// general category `Nd` (decimal digit number), as of Unicode 14.0. Generated from the
+/// `UnicodeData.txt` of that version; regenerate it when moving to a newer class library.
+const DECIMAL_DIGIT_ZEROS: &[u32] = &[
+    0x30, 0x660, 0x6F0, 0x7C0, 0x966, 0x9E6, 0xA66, 0xAE6, 0xB66, 0xBE6, 0xC66, 0xCE6, 0xD66,
+    0xDE6, 0xE50, 0xED0, 0xF20, 0x1040, 0x1090, 0x17E0, 0x1810, 0x1946, 0x19D0, 0x1A80, 0x1A90,
+    0x1B50, 0x1BB0, 0x1C40, 0x1C50, 0xA620, 0xA8D0, 0xA900, 0xA9D0, 0xA9F0, 0xAA50, 0xABF0, 0xFF10,
+    0x104A0, 0x10D30, 0x11066, 0x110F0, 0x11136, 0x111D0, 0x112F0, 0x11450, 0x114D0, 0x11650,
+    0x116C0, 0x11730, 0x118E0, 0x11950, 0x11C50, 0x11D50, 0x11DA0, 0x16A60, 0x16AC0, 0x16B50,
+    0x1D7CE, 0x1D7D8, 0x1D7E2, 0x1D7EC, 0x1D7F6, 0x1E140, 0x1E2F0, 0x1E950, 0x1FBF0,
+];
+
+pub const MIN_RADIX: i32 = 2;
+pub const MAX_RADIX: i32 = 36;
+
+fn to_char(code_point: i32) -> Option<char> {
+    u32::try_from(code_point).ok().and_then(char::from_u32)
+}
+
+/// Returns the value of the given decimal digit, or `None` if it is not in category `Nd`
+fn decimal_digit_value(code_point: u32) -> Option<u32> {
+    let index = match DECIMAL_DIGIT_ZEROS.binary_search(&code_point) {
+        Ok(index) => index,
+        Err(0) => return None,
+        Err(index) => index - 1,
+    };
+    let value = code_point - DECIMAL_DIGIT_ZEROS[index];
+    if value < 10 {
+        Some(value)
+    } else {
+        None
+    }
+}
+
+/// Equivalent of `Character.isDigit(int)`
+pub fn is_digit(code_point: i32) -> bool {
+    u32::try_from(code_point)
+        .ok()
+        .and_then(decimal_digit_value)
+        .is_some()
+}
+
+/// Equivalent of `Character.isLetter(int)`
+pub fn is_letter(code_point: i32) -> bool {
+    to_char(code_point).is_some_and(|c| c.is_alphabetic())
+}
+
+/// Equivalent of `Character.isLetterOrDigit(int)`
+pub fn is_letter_or_digit(code_point: i32) -> bool {
+    is_letter(code_point) || is_digit(code_point)
+}
+
+/// Equivalent of `Character.isUpperCase(int)`
+pub fn is_upper_case(code_point: i32) -> bool {
+    to_char(code_point).is_some_and(|c| c.is_uppercase())
+}
+
+/// Equivalent of `Character.isLowerCase(int)`
+pub fn is_lower_case(code_point: i32) -> bool {
+    to_char(code_point).is_some_and(|c| c.is_lowercase())
+}
+
+/// Equivalent of `Character.isWhitespace(int)`: all Unicode space separators except the
+/// non-breaking ones, plus the ASCII control characters used as separators
+pub fn is_whitespace(code_point: i32) -> bool {
+    match to_char(code_point) {
+        Some('\t' | '\n' | '\u{b}' | '\u{c}' | '\r' | '\u{1c}'..='\u{1f}') => true,
+        Some('\u{85}' | '\u{a0}' | '\u{2007}' | '\u{202f}') => false,
+        Some(c) => c.is_whitespace(),
+        None => false,
+    }
+}
+
+/// Equivalent of `Character.toUpperCase(int)`
+pub fn to_upper_case(code_point: i32) -> i32 {
+    match to_char(code_point) {
+        Some(c) => single_char_or(c.to_uppercase(), code_point),
+        None => code_point,
+    }
+}
+
+/// Equivalent of `Character.toLowerCase(int)`
+pub fn to_lower_case(code_point: i32) -> i32 {
+    match to_char(code_point) {
+        Some(c) => single_char_or(c.to_lowercase(), code_point),
+        None => code_point,
+    }
+}
+
+fn single_char_or(mut mapped: impl Iterator<Item = char>, default: i32) -> i32 {
+    match (mapped.next(), mapped.next()) {
+        (Some(c), None) => c as i32,
+        _ => default,
+    }
+}
+
+/// Equivalent of `Character.digit(int, int)`: returns the value of the given character
+/// in the given radix, or -1 if it is not a valid digit
+pub fn digit(code_point: i32, radix: i32) -> i32 {
+    if !(MIN_RADIX..=MAX_RADIX).contains(&radix) || code_point < 0 {
+        return -1;
+    }
+    let code_point = code_point as u32;
+    let value = match code_point {
+        // ASCII and fullwidth latin letters
+        0x41..=0x5A => Some(code_point - 0x41 + 10),
+        0x61..=0x7A => Some(code_point - 0x61 + 10),
+        0xFF21..=0xFF3A => Some(code_point - 0xFF21 + 10),
+        0xFF41..=0xFF5A => Some(code_point - 0xFF41 + 10),
+        _ => decimal_digit_value(code_point),
+    };
+    match value {
+        Some(value) if (value as i32) < radix => value as i32,
+        _ => -1,
+    }
+}
+
+/// Equivalent of `Character.forDigit(int, int)`: returns the lowercase character
+/// representing the given digit, or the null character if it is not valid
+pub fn for_digit(digit: i32, radix: i32) -> char {
+    if !(MIN_RADIX..=MAX_RADIX).contains(&radix) || digit < 0 || digit >= radix {
+        return '\0';
+    }
+    char::from_digit(digit as u32, radix as u32).unwrap_or('\0')
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::character::{
+        digit, for_digit, is_digit, is_letter, is_letter_or_digit, is_lower_case, is_upper_case,
+        is_whitespace, to_lower_case, to_upper_case,
+    };
+
+    #[test]
+    fn ascii_is_classified_like_java() {
+        for c in 0..128 {
+            let ch = char::from_u32(c as u32).unwrap();
+            assert_eq!(ch.is_ascii_digit(), is_digit(c), "{c}");
+            assert_eq!(ch.is_ascii_alphabetic(), is_letter(c), "{c}");
+            assert_eq!(ch.is_ascii_alphanumeric(), is_letter_or_digit(c), "{c}");
+            assert_eq!(ch.is_ascii_uppercase(), is_upper_case(c), "{c}");
+            assert_eq!(ch.is_ascii_lowercase(), is_lower_case(c), "{c}");
+            assert_eq!(ch.to_ascii_uppercase() as i32, to_upper_case(c), "{c}");
+            assert_eq!(ch.to_ascii_lowercase() as i32, to_lower_case(c), "{c}");
+        }
+    }
+
+    #[test]
+    fn can_classify_unicode_digits() {
+        assert!(is_digit(0x0663)); // Arabic-Indic digit three
+        assert!(is_digit(0xFF19)); // Fullwidth digit nine
+        assert!(!is_digit(0x2167)); // Roman numeral eight
+        assert!(!is_digit(0xFF1A)); // Fullwidth colon
+        assert!(!is_digit(-1));
+    }
+
+    #[test]
+    fn whitespace_excludes_non_breaking_spaces() {
+        assert!(is_whitespace(' ' as i32));
+        assert!(is_whitespace('\t' as i32));
+        assert!(is_whitespace(0x1F));
+        assert!(is_whitespace(0x2003)); // Em space
+        assert!(!is_whitespace(0xA0));
+        assert!(!is_whitespace(0x202F));
+        assert!(!is_whitespace('a' as i32));
+    }
+
+    #[test]
+    fn case_conversion_keeps_characters_with_multi_char_mappings() {
+        assert_eq!('Ä' as i32, to_upper_case('ä' as i32));
+        assert_eq!('ß' as i32, to_upper_case('ß' as i32));
+        assert_eq!('σ' as i32, to_lower_case('Σ' as i32));
+        assert_eq!(0xD800, to_upper_case(0xD800));
+    }
+
+    #[test]
+    fn can_get_digit_values() {
+        assert_eq!(7, digit('7' as i32, 10));
+        assert_eq!(15, digit('F' as i32, 16));
+        assert_eq!(15, digit('f' as i32, 16));
+        assert_eq!(-1, digit('g' as i32, 16));
+        assert_eq!(3, digit(0x0663, 10));
+        assert_eq!(35, digit(0xFF5A, 36));
+        assert_eq!(-1, digit('1' as i32, 1));
+        assert_eq!(-1, digit('1' as i32, 37));
+    }
+
+    #[test]
+    fn can_format_digits() {
+        assert_eq!('7', for_digit(7, 10));
+        assert_eq!('f', for_digit(15, 16));
+        assert_eq!('\0', for_digit(10, 10));
+        assert_eq!('\0', for_digit(-1, 10));
+        assert_eq!('\0', for_digit(1, 37));
+    }
+}
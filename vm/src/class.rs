@@ -1,14 +1,26 @@
-use std::{fmt, fmt::Formatter};
+use std::{
+    cell::{Cell, OnceCell},
+    fmt,
+    fmt::Formatter,
+    rc::Rc,
+};
 
 use rjvm_reader::{
-    class_access_flags::ClassAccessFlags, class_file_field::ClassFileField,
-    class_file_method::ClassFileMethod, constant_pool::ConstantPool,
+    bootstrap_method::BootstrapMethod, class_access_flags::ClassAccessFlags, class_file::ClassFile,
+    class_file_field::ClassFileField, class_file_method::ClassFileMethod, class_reader,
+    class_reader_error::ClassReaderError, constant_pool::ConstantPool,
+};
+
+use crate::{
+    class_and_method::ClassAndMethod, decoded_code::DecodedCode, reference_objects::ReferenceKind,
+    thread_scheduler::ThreadId, vm_error::VmError, vtable::Vtable,
 };
 
 /// In various data structures, we store the class id of the object, i..e. a progressive
 /// number assigned when we load the class. Note that, while we do not support it yet,
 /// multiple class loaders could load the same class more than once, but they would be
-/// required to assign different id to them.
+/// required to assign different id to them. Two classes are the same class only if they have
+/// the same id.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 #[repr(transparent)]
 pub struct ClassId(u32);
@@ -29,26 +41,126 @@ impl ClassId {
     }
 }
 
+/// The states of the initialization of a class, as defined by the JVM spec. A class is
+/// initialized, after its superclass, on its first active use: the creation of an instance,
+/// the access to one of its static fields, the invocation of one of its static methods,
+/// or a reflective request such as `Class.forName`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InitializationState {
+    NotInitialized,
+    /// The static initializer is running on the given thread. The same thread can use the
+    /// class meanwhile, seeing its static fields before they are set, while the other threads
+    /// wait for the initialization to complete.
+    BeingInitialized(ThreadId),
+    Initialized,
+    /// The static initializer failed, thus the class cannot be used
+    Erroneous,
+}
+
+/// The class loader that defined a class. The bootstrap loader is the parent of the
+/// application one, which in turn is the parent of the user-defined loaders by default: each
+/// loader sees the classes defined by its ancestors, but not the ones of its descendants.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DefiningLoader {
+    /// The classes of the boot class path, i.e. the JDK ones
+    Bootstrap,
+    /// The classes of the application class path
+    Application,
+    /// The classes given to `ClassLoader.defineClass` by an instance of a subclass of
+    /// `java.lang.ClassLoader`, which the vm keeps track of
+    User,
+}
+
+impl DefiningLoader {
+    /// Whether a class defined by this loader can be returned when the given loader is asked
+    /// for it, i.e. whether this loader is the given one or one of its ancestors
+    pub fn is_visible_from(self, initiating_loader: DefiningLoader) -> bool {
+        match initiating_loader {
+            DefiningLoader::Bootstrap => self == DefiningLoader::Bootstrap,
+            DefiningLoader::Application => self != DefiningLoader::User,
+            DefiningLoader::User => true,
+        }
+    }
+}
+
 /// A loaded java class
 #[derive(Debug)]
 pub struct Class<'a> {
     pub id: ClassId,
+    /// How many times the class was redefined before this version was loaded; see
+    /// [Vm::redefine_class](crate::vm::Vm::redefine_class). All the versions share the id.
+    pub version: u32,
     pub name: String,
     /// Source file is stored as an attribute in the .class file, but might be missing
     /// for synthetic classes or if the compiler didn't write it.
     pub source_file: Option<String>,
-    pub constants: ConstantPool,
+    /// The class whose nest this class belongs to, if it is a nested class compiled for
+    /// java 11 or later
+    pub nest_host: Option<String>,
+    pub constants: ConstantPool<'a>,
     pub flags: ClassAccessFlags,
+    pub defining_loader: DefiningLoader,
     pub superclass: Option<ClassRef<'a>>,
     pub interfaces: Vec<ClassRef<'a>>,
-    pub fields: Vec<ClassFileField>,
-    pub methods: Vec<ClassFileMethod>,
+    pub fields: Vec<ClassFileField<'a>>,
+    pub methods: Vec<ClassFileMethod<'a>>,
+    pub bootstrap_methods: Vec<BootstrapMethod>,
     // Base classes field have the same index they have in the base class, and our own
     // field come after. This is the index of the first "owned" field.
     // Note that this will include the static fields, as required by the bytecode specs.
     pub first_field_index: usize,
     // The total number of fields in this class, including those in the base class.
     pub num_total_fields: usize,
+    /// Whether the instances are soft, weak or phantom references, whose referent the gc
+    /// does not keep alive
+    pub reference_kind: Option<ReferenceKind>,
+    /// Whether the class, or one of its base classes, overrides `Object.finalize` with a
+    /// non-empty method, which the gc must invoke before reclaiming the instances
+    pub has_finalizer: bool,
+    pub(crate) vtable: Vtable<'a>,
+    /// The decoded code of each method, filled the first time the method is executed
+    pub(crate) decoded_code: Vec<OnceCell<DecodedCode>>,
+    /// Whether each method has an intrinsic, looked up in the registry the first time that
+    /// the method is invoked. Most methods do not have one, so this avoids the lookups.
+    pub(crate) has_intrinsic: Vec<OnceCell<bool>>,
+    /// Shared by all the versions of the class
+    pub(crate) initialization_state: Rc<Cell<InitializationState>>,
+    /// Whether a newer version of the class replaced this one
+    pub(crate) redefined: Cell<bool>,
+    /// The bytes of the class file, which the constants, fields and methods borrow from.
+    /// Declared last, so that they are dropped after everything that refers to them.
+    #[allow(dead_code)]
+    pub(crate) class_file_bytes: ClassFileBytes,
+}
+
+/// The bytes of a class file, owned by the [Class] defined from it, so that they are freed
+/// together with it. The vm parses them without copying their strings and code. The classes
+/// synthesized by the vm have no bytes.
+#[derive(Default)]
+pub(crate) struct ClassFileBytes(Vec<u8>);
+
+impl fmt::Debug for ClassFileBytes {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{} bytes", self.0.len())
+    }
+}
+
+impl ClassFileBytes {
+    pub fn new(bytes: Vec<u8>) -> Self {
+        Self(bytes)
+    }
+
+    /// Parses the class file, borrowing from the bytes rather than copying them.
+    ///
+    /// # Safety
+    ///
+    /// The returned class file, and anything borrowed from it, must not outlive these bytes,
+    /// which should thus be stored in the [Class] defined from it. Moving the bytes is fine,
+    /// since their heap buffer does not move.
+    pub unsafe fn parse<'a>(&self) -> Result<ClassFile<'a>, ClassReaderError> {
+        let bytes_ptr: *const [u8] = self.0.as_slice();
+        class_reader::read_buffer(&*bytes_ptr)
+    }
 }
 
 pub type ClassRef<'a> = &'a Class<'a>;
@@ -57,10 +169,10 @@ impl<'a> Class<'a> {
     /// Returns whether self is a subclass of the given class, or implements
     /// the given interface
     pub fn is_subclass_of(&self, base: ClassRef) -> bool {
-        self.name == base.name
+        self.id == base.id
             || self
                 .superclass
-                .map_or(false, |superclass| superclass.is_subclass_of(base))
+                .is_some_and(|superclass| superclass.is_subclass_of(base))
             || self.interfaces.iter().any(|intf| intf.is_subclass_of(base))
     }
 
@@ -68,30 +180,142 @@ impl<'a> Class<'a> {
         &self,
         method_name: &str,
         type_descriptor: &str,
-    ) -> Option<&ClassFileMethod> {
+    ) -> Option<&ClassFileMethod<'a>> {
         // Maybe replace linear search with something faster...
         self.methods
             .iter()
             .find(|method| method.name == method_name && method.type_descriptor == type_descriptor)
     }
 
-    pub fn find_field(&self, field_name: &str) -> Option<(usize, &ClassFileField)> {
+    pub fn find_field(&self, field_name: &str) -> Option<(usize, &ClassFileField<'a>)> {
+        self.find_field_and_declaring_class(field_name)
+            .map(|(_, index, field)| (index, field))
+    }
+
+    /// Like [Class::find_field], but also returns the class declaring the field, which is
+    /// either this class or one of its superclasses
+    pub fn find_field_and_declaring_class(
+        &self,
+        field_name: &str,
+    ) -> Option<(&Class<'a>, usize, &ClassFileField<'a>)> {
         // Maybe replace linear search with something faster...
         self.fields
             .iter()
             .enumerate()
             .find(|entry| entry.1.name == field_name)
-            .map(|(index, field)| (index + self.first_field_index, field))
+            .map(|(index, field)| (self, index + self.first_field_index, field))
             .or_else(|| {
                 if let Some(superclass) = &self.superclass {
-                    superclass.find_field(field_name)
+                    superclass.find_field_and_declaring_class(field_name)
                 } else {
                     None
                 }
             })
     }
 
-    pub fn field_at_index(&self, index: usize) -> Option<&ClassFileField> {
+    pub fn initialization_state(&self) -> InitializationState {
+        self.initialization_state.get()
+    }
+
+    pub(crate) fn set_initialization_state(&self, state: InitializationState) {
+        self.initialization_state.set(state);
+    }
+
+    /// Whether a newer version of the class replaced this one; see
+    /// [Vm::redefine_class](crate::vm::Vm::redefine_class)
+    pub fn is_redefined(&self) -> bool {
+        self.redefined.get()
+    }
+
+    /// Finds a static field, looking first in the fields declared by this class, then in its
+    /// superinterfaces, and finally in its superclass, as the JVM spec mandates. Returns the
+    /// declaring class and the index of the field in its instances.
+    pub fn find_static_field(
+        &self,
+        field_name: &str,
+    ) -> Option<(&Class<'a>, usize, &ClassFileField<'a>)> {
+        self.fields
+            .iter()
+            .position(|field| field.name == field_name)
+            .map(|index| (self, index + self.first_field_index, &self.fields[index]))
+            .or_else(|| {
+                self.interfaces
+                    .iter()
+                    .find_map(|interface| interface.find_static_field(field_name))
+            })
+            .or_else(|| {
+                self.superclass
+                    .and_then(|superclass| superclass.find_static_field(field_name))
+            })
+    }
+
+    /// The name of the package of the class, i.e. `java/lang` for `java/lang/String`. Since
+    /// we have only one class loader, two classes are in the same runtime package if
+    /// their packages have the same name.
+    pub fn package_name(&self) -> &str {
+        package_name_of(&self.name)
+    }
+
+    /// Returns the index of the entry of the given method, declared by this class, in the
+    /// virtual method tables; see [Vtable]
+    pub(crate) fn vtable_index_of(&self, method: &ClassFileMethod<'a>) -> Option<usize> {
+        self.vtable.index_of(self.method_index(method)?)
+    }
+
+    /// Returns the index in [Self::methods] of the given method, if it is declared by this
+    /// class. It is computed from the method's address, without comparing any name.
+    pub(crate) fn method_index(&self, method: &ClassFileMethod<'a>) -> Option<usize> {
+        let offset = (method as *const ClassFileMethod as usize)
+            .checked_sub(self.methods.as_ptr() as usize)?
+            / std::mem::size_of::<ClassFileMethod>();
+        self.methods
+            .get(offset)
+            .filter(|candidate| std::ptr::eq(*candidate, method))
+            .map(|_| offset)
+    }
+
+    /// Returns the decoded code of the given method, which must be declared by this class and
+    /// not be native, decoding it on its first execution
+    pub(crate) fn decoded_code(
+        &self,
+        method: &ClassFileMethod<'a>,
+    ) -> Result<&DecodedCode, VmError> {
+        let cell = self
+            .method_index(method)
+            .and_then(|index| self.decoded_code.get(index))
+            .ok_or(VmError::ValidationException)?;
+        if let Some(decoded_code) = cell.get() {
+            return Ok(decoded_code);
+        }
+        let code = method.code.as_ref().ok_or(VmError::ValidationException)?;
+        let decoded_code =
+            DecodedCode::decode(&code.code).map_err(|_| VmError::ValidationException)?;
+        Ok(cell.get_or_init(|| decoded_code))
+    }
+
+    /// Returns whether the given method, which should be declared by this class, has an
+    /// intrinsic, using the given lookup only on its first invocation
+    pub(crate) fn has_intrinsic(
+        &self,
+        method: &ClassFileMethod<'a>,
+        lookup: impl FnOnce() -> bool,
+    ) -> bool {
+        match self
+            .method_index(method)
+            .and_then(|index| self.has_intrinsic.get(index))
+        {
+            Some(cell) => *cell.get_or_init(lookup),
+            None => lookup(),
+        }
+    }
+
+    /// Returns the method invoked on the instances of this class for the given entry of the
+    /// virtual method tables
+    pub(crate) fn vtable_method(&self, index: usize) -> Option<ClassAndMethod<'_>> {
+        self.vtable.get(self, index)
+    }
+
+    pub fn field_at_index(&self, index: usize) -> Option<&ClassFileField<'a>> {
         if index < self.first_field_index {
             self.superclass
                 .and_then(|superclass| superclass.field_at_index(index))
@@ -100,7 +324,7 @@ impl<'a> Class<'a> {
         }
     }
 
-    pub fn all_fields(&self) -> impl Iterator<Item = &ClassFileField> {
+    pub fn all_fields(&self) -> impl Iterator<Item = &ClassFileField<'a>> {
         let mut all_fields = Vec::from_iter(
             self.superclass
                 .iter()
@@ -110,3 +334,39 @@ impl<'a> Class<'a> {
         all_fields.into_iter()
     }
 }
+
+/// The name of the package of the given class, i.e. `java/lang` for `java/lang/String`
+pub(crate) fn package_name_of(class_name: &str) -> &str {
+    class_name
+        .rsplit_once('/')
+        .map(|(package, _)| package)
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{class::DefiningLoader, class_manager::ClassManager};
+
+    #[test]
+    fn loaded_classes_borrow_their_data_from_the_class_file_bytes() {
+        let dir = env!("CARGO_MANIFEST_DIR");
+        let mut class_manager = ClassManager::default();
+        class_manager
+            .append_class_path(&format!("{dir}/rt.jar:{dir}/tests/resources"))
+            .expect("should be able to parse classpath");
+        let class = class_manager
+            .get_or_resolve_class_of("rjvm/SimpleMain", DefiningLoader::Application)
+            .expect("should be able to load the class")
+            .get_class();
+
+        let bytes = class.class_file_bytes.0.as_ptr_range();
+        let is_borrowed = |data: &[u8]| bytes.contains(&data.as_ptr());
+        for method in class.methods.iter() {
+            assert!(is_borrowed(method.name.as_bytes()));
+            assert!(is_borrowed(method.type_descriptor.as_bytes()));
+            if let Some(code) = &method.code {
+                assert!(is_borrowed(&code.code));
+            }
+        }
+    }
+}
@@ -6,16 +6,18 @@ use crate::class::ClassRef;
 #[derive(Debug, Clone)]
 pub struct ClassAndMethod<'a> {
     pub class: ClassRef<'a>,
-    pub method: &'a ClassFileMethod,
+    pub method: &'a ClassFileMethod<'a>,
 }
 
 impl<'a> ClassAndMethod<'a> {
+    /// Uses the descriptor parsed when the class was loaded, like [Self::return_type]
     pub fn num_arguments(&self) -> usize {
         self.method.parsed_type_descriptor.num_arguments()
     }
 
-    pub fn return_type(&self) -> Option<FieldType> {
-        self.method.parsed_type_descriptor.return_type.clone()
+    /// The declared return type, or `None` for `void` methods
+    pub fn return_type(&self) -> Option<&'a FieldType> {
+        self.method.parsed_type_descriptor.return_type.as_ref()
     }
 
     pub fn is_static(&self) -> bool {
@@ -0,0 +1,29 @@
+use std::{fmt, fmt::Formatter};
+
+/// A hook that can rewrite the bytes of the classes before they are defined, like the
+/// `java.lang.instrument.ClassFileTransformer`s of the java agents, e.g. to add
+/// instrumentation without modifying the jars on disk. It sees the classes read from the
+/// class paths, the ones given to `ClassLoader.defineClass`, and the new versions given to
+/// [Vm::redefine_class](crate::vm::Vm::redefine_class), but not the ones synthesized by the vm.
+///
+/// Any closure taking the name and the bytes of a class is a transformer.
+pub trait ClassFileTransformer {
+    /// Returns the new bytes of the class with the given name, in internal form
+    /// (i.e. `java/lang/Object`), or `None` to leave them unchanged
+    fn transform(&self, class_name: &str, class_file_bytes: &[u8]) -> Option<Vec<u8>>;
+}
+
+impl<F> ClassFileTransformer for F
+where
+    F: Fn(&str, &[u8]) -> Option<Vec<u8>>,
+{
+    fn transform(&self, class_name: &str, class_file_bytes: &[u8]) -> Option<Vec<u8>> {
+        self(class_name, class_file_bytes)
+    }
+}
+
+impl fmt::Debug for dyn ClassFileTransformer {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str("ClassFileTransformer")
+    }
+}
@@ -0,0 +1,57 @@
+use std::{fmt, fmt::Formatter};
+
+use crate::{
+    class::{Class, DefiningLoader},
+    vm_error::VmError,
+};
+
+/// Where the bytes of a defined class came from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ClassSource {
+    /// An entry of the boot or of the application class path, with its description,
+    /// e.g. the path of a jar
+    ClassPathEntry(String),
+    /// The bytes given to `ClassLoader.defineClass`
+    DefineClass,
+    /// A class generated by the vm, e.g. one implementing a lambda
+    Synthesized,
+    /// A new version of an already loaded class; see
+    /// [Vm::redefine_class](crate::vm::Vm::redefine_class)
+    Redefinition,
+}
+
+impl fmt::Display for ClassSource {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            ClassSource::ClassPathEntry(description) => f.write_str(description),
+            // The names used by hotspot's -verbose:class
+            ClassSource::DefineClass => f.write_str("__JVM_DefineClass__"),
+            ClassSource::Synthesized => f.write_str("__JVM_LookupDefineClass__"),
+            ClassSource::Redefinition => f.write_str("__VM_RedefineClasses__"),
+        }
+    }
+}
+
+/// Notified of the loading of the classes, e.g. to diagnose class path problems like the
+/// `-verbose:class` option of the `java` launcher does. All the methods do nothing by default.
+pub trait ClassLoadObserver {
+    /// A class that is not loaded yet is searched on behalf of the given loader. This
+    /// precedes its definition, or the failure to load it.
+    fn class_looked_up(&self, _class_name: &str, _initiating_loader: DefiningLoader) {}
+
+    /// A class was defined; its superclass and its interfaces are defined before it
+    fn class_defined(&self, _class: &Class, _source: &ClassSource) {}
+
+    /// The static initializer of a class completed successfully
+    fn class_initialized(&self, _class: &Class) {}
+
+    /// A class could not be loaded, e.g. because it is not in the class paths, because its
+    /// bytes are invalid, or because its superclass failed to load
+    fn class_load_failed(&self, _class_name: &str, _error: &VmError) {}
+}
+
+impl fmt::Debug for dyn ClassLoadObserver {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str("ClassLoadObserver")
+    }
+}
@@ -1,21 +1,60 @@
-use std::{collections::HashMap, fmt, fmt::Formatter};
+#[cfg(feature = "std")]
+use std::num::NonZeroUsize;
+use std::{
+    cell::{Cell, OnceCell},
+    collections::{HashMap, HashSet},
+    fmt,
+    fmt::Formatter,
+    mem,
+    rc::Rc,
+};
 
 use indexmap::IndexMap;
-use log::debug;
+use log::Level;
 use typed_arena::Arena;
 
-use rjvm_reader::{class_file::ClassFile, class_reader};
+use rjvm_reader::{
+    class_file::ClassFile, class_reader, class_reader_error::ClassReaderError,
+    constant_pool::ConstantPoolEntry,
+};
 
 use crate::{
-    class::{Class, ClassId, ClassRef},
-    class_loader::ClassLoader,
-    class_path::{ClassPath, ClassPathParseError},
+    abstract_object::AbstractObject,
+    class::{Class, ClassFileBytes, ClassId, ClassRef, DefiningLoader, InitializationState},
+    class_file_transformer::ClassFileTransformer,
+    class_load_observer::{ClassLoadObserver, ClassSource},
+    class_path::{
+        ClassBytesAndEntry, ClassNamesListing, ClassPath, ClassPathParseError, DuplicateClass,
+    },
+    class_path_entry::{ClassLoadingError, ClassPathEntry},
+    class_path_scan::ScanProgressListener,
     class_resolver_by_id::ClassByIdResolver,
+    io::JvmIo,
+    jar_manifest::JarManifest,
+    linker,
+    log_config::{vm_log, LogCategory, LogConfig},
+    reference_objects::ReferenceKind,
+    verification_report::{VerificationProblem, VerificationProblemKind, VerificationReport},
+    verifier,
     vm_error::VmError,
+    vtable::Vtable,
 };
 
+#[cfg(feature = "std")]
+use crate::class_prefetch;
+
+/// A class read and parsed ahead of its definition, with the loader that will define it and
+/// where it comes from
+#[cfg(feature = "std")]
+type PrefetchedClass<'a> = (ClassFile<'a>, ClassFileBytes, DefiningLoader, ClassSource);
+
 /// An object that will allocate and manage Class objects
 pub(crate) struct ClassManager<'a> {
+    /// The class path of the bootstrap loader, holding the JDK classes. While it is empty, the
+    /// application class path holds them too, and all of its classes belong to the bootstrap
+    /// loader.
+    boot_class_path: ClassPath,
+    /// The class path of the application loader, searched after the boot one
     class_path: ClassPath,
     classes_by_id: HashMap<ClassId, ClassRef<'a>>,
     classes_by_name: HashMap<String, ClassRef<'a>>,
@@ -26,20 +65,75 @@ pub(crate) struct ClassManager<'a> {
     /// Used to generate ClassId
     next_id: u32,
 
-    /// In a real implementation, we would have a current class loader for each thread,
-    /// in a hierarchy. Currently, we only have exactly ONE global class loader.
-    current_class_loader: ClassLoader<'a>,
+    /// The classes whose static initializer is currently running, outermost first. An
+    /// initializer that (directly or not) needs a class that is still being initialized will
+    /// just see it partially initialized, as the JVM spec mandates for recursive initialization
+    /// on the same thread; we track the chain for diagnostics. The other threads wait for the
+    /// initialization to complete, see [InitializationState].
+    initializing: Vec<ClassRef<'a>>,
+
+    /// To model static fields, we create one special instance of each initialized class.
+    /// This is a bit hacky, and wastes memory because we allocate space for non-static fields,
+    /// but it works easily! Each static field is stored only in the instance of the class
+    /// declaring it, even when it is accessed via one of its subclasses, or via one of the
+    /// classes implementing the interface declaring it.
+    statics: HashMap<ClassId, AbstractObject<'a>>,
+
+    /// The instances of `java.lang.ClassLoader` that defined the classes not loaded from the
+    /// class path, via `defineClass`, i.e. the ones of [DefiningLoader::User]
+    defining_loaders: HashMap<ClassId, AbstractObject<'a>>,
+
+    /// The instance of `java.lang.ClassLoader` standing for [DefiningLoader::Application],
+    /// created the first time that the java code asks for it
+    system_class_loader: Option<AbstractObject<'a>>,
+
+    /// Applied in order to the bytes of the classes before they are parsed
+    class_file_transformers: Vec<Rc<dyn ClassFileTransformer>>,
+
+    /// Notified of the classes looked up, defined, initialized, or failing to load
+    class_load_observers: Vec<Rc<dyn ClassLoadObserver>>,
+
+    /// Whether the missing interfaces of a class are ignored, rather than making it fail to load
+    lazy_linkage: bool,
+
+    /// Whether the bytecode of the classes is verified when they are loaded
+    verify_classes: bool,
+
+    /// How many threads parse the class files; with just one, the classes are parsed when
+    /// they are defined
+    #[cfg(feature = "std")]
+    class_loading_threads: NonZeroUsize,
+
+    /// The classes that were read and parsed ahead of their definition, see
+    /// [ClassManager::prefetch_classes]. Defining a class consumes its entry.
+    #[cfg(feature = "std")]
+    prefetched: HashMap<String, Result<PrefetchedClass<'a>, VmError>>,
+
+    log_config: LogConfig,
 }
 
 impl<'a> Default for ClassManager<'a> {
     fn default() -> Self {
         Self {
+            boot_class_path: Default::default(),
             class_path: Default::default(),
             classes_by_id: Default::default(),
             classes_by_name: Default::default(),
             arena: Arena::with_capacity(100),
             next_id: 1,
-            current_class_loader: Default::default(),
+            initializing: Default::default(),
+            statics: Default::default(),
+            defining_loaders: Default::default(),
+            system_class_loader: None,
+            class_file_transformers: Vec::new(),
+            class_load_observers: Vec::new(),
+            lazy_linkage: false,
+            verify_classes: true,
+            #[cfg(feature = "std")]
+            class_loading_threads: NonZeroUsize::MIN,
+            #[cfg(feature = "std")]
+            prefetched: Default::default(),
+            log_config: Default::default(),
         }
     }
 }
@@ -55,25 +149,25 @@ impl<'a> fmt::Debug for ClassManager<'a> {
 #[derive(Debug, Clone)]
 pub(crate) enum ResolvedClass<'a> {
     AlreadyLoaded(ClassRef<'a>),
-    NewClass(ClassesToInitialize<'a>),
+    NewClass(LoadedClasses<'a>),
 }
 
 impl<'a> ResolvedClass<'a> {
     pub fn get_class(&self) -> ClassRef<'a> {
         match self {
             ResolvedClass::AlreadyLoaded(class) => class,
-            ResolvedClass::NewClass(classes_to_initialize) => classes_to_initialize.resolved_class,
+            ResolvedClass::NewClass(loaded_classes) => loaded_classes.resolved_class,
         }
     }
 }
 
-/// In case a new class was loaded, maps the whole list of the classes that require
-/// initialization, in order so that a base class is initialized _before_ the derived classes.
-/// Includes the newly resolved class in the list [to_initialize].
+/// In case a new class was loaded, the whole list of the classes that were loaded to resolve
+/// it, in order so that a base class comes _before_ the derived classes. Includes the newly
+/// resolved class in the list [loaded].
 #[derive(Debug, Clone)]
-pub(crate) struct ClassesToInitialize<'a> {
+pub(crate) struct LoadedClasses<'a> {
     resolved_class: ClassRef<'a>,
-    pub(crate) to_initialize: Vec<ClassRef<'a>>,
+    pub(crate) loaded: Vec<ClassRef<'a>>,
 }
 
 impl<'a> ClassByIdResolver<'a> for ClassManager<'a> {
@@ -87,50 +181,576 @@ impl<'a> ClassManager<'a> {
         self.class_path.push(class_path)
     }
 
+    /// Appends the given jar to the class path and returns its manifest, if any
+    pub fn append_jar(
+        &mut self,
+        jar_path: &str,
+    ) -> Result<Option<JarManifest>, ClassPathParseError> {
+        self.class_path.push_jar(jar_path)
+    }
+
+    pub fn append_boot_class_path(&mut self, class_path: &str) -> Result<(), ClassPathParseError> {
+        self.boot_class_path.push(class_path)
+    }
+
+    pub fn add_class_path_entry(
+        &mut self,
+        entry: Box<dyn ClassPathEntry>,
+    ) -> Result<(), ClassPathParseError> {
+        self.class_path.push_entry(entry)
+    }
+
+    /// Reads a resource from the boot class path or, if it is not there, from the application one
+    pub fn resolve_resource(&self, path: &str) -> Result<Option<Vec<u8>>, VmError> {
+        let resolve = || match self.boot_class_path.resolve_resource(path)? {
+            Some(bytes) => Ok(Some(bytes)),
+            None => self.class_path.resolve_resource(path),
+        };
+        resolve().map_err(|err: ClassLoadingError| VmError::ClassLoadingError(err.to_string()))
+    }
+
+    pub fn set_strict_class_path(&mut self, strict: bool) {
+        self.boot_class_path.set_strict(strict);
+        self.class_path.set_strict(strict)
+    }
+
+    pub fn set_class_path_scan_listener(&mut self, listener: Option<ScanProgressListener>) {
+        self.boot_class_path
+            .set_scan_progress_listener(listener.clone());
+        self.class_path.set_scan_progress_listener(listener)
+    }
+
+    pub fn set_class_path_io(&mut self, io: Option<Rc<dyn JvmIo>>) {
+        self.boot_class_path.set_io(io.clone());
+        self.class_path.set_io(io)
+    }
+
+    pub fn set_lazy_linkage(&mut self, lazy_linkage: bool) {
+        self.lazy_linkage = lazy_linkage
+    }
+
+    pub fn lazy_linkage(&self) -> bool {
+        self.lazy_linkage
+    }
+
+    pub fn set_verify_classes(&mut self, verify_classes: bool) {
+        self.verify_classes = verify_classes
+    }
+
+    pub fn verify_classes(&self) -> bool {
+        self.verify_classes
+    }
+
+    #[cfg(feature = "std")]
+    pub fn set_class_loading_threads(&mut self, threads: NonZeroUsize) {
+        self.class_loading_threads = threads
+    }
+
+    #[cfg(feature = "std")]
+    pub fn class_loading_threads(&self) -> NonZeroUsize {
+        self.class_loading_threads
+    }
+
+    pub fn duplicate_classes(&self) -> &[DuplicateClass] {
+        self.class_path.duplicate_classes()
+    }
+
+    /// Loads, links and verifies the classes of the given class path, without initializing
+    /// them, and reports their problems; see [Vm::verify](crate::vm::Vm::verify). The classes
+    /// are loaded by a scratch class manager, searching the class paths of this one followed by
+    /// the given one, which is dropped afterwards together with all the classes it loaded. The
+    /// class paths are then given back to this manager, without the new entries.
+    pub fn verify(&mut self, class_path: &str) -> Result<VerificationReport, ClassPathParseError> {
+        let first_new_entry = self.class_path.len();
+        let mut scratch = ClassManager {
+            boot_class_path: mem::take(&mut self.boot_class_path),
+            class_path: mem::take(&mut self.class_path),
+            class_file_transformers: self.class_file_transformers.clone(),
+            lazy_linkage: self.lazy_linkage,
+            verify_classes: true,
+            log_config: self.log_config,
+            ..Default::default()
+        };
+        let report = scratch
+            .class_path
+            .push(class_path)
+            .map(|()| scratch.verify_entries_from(first_new_entry));
+        self.boot_class_path = mem::take(&mut scratch.boot_class_path);
+        self.class_path = mem::take(&mut scratch.class_path);
+        self.class_path.truncate(first_new_entry);
+        report
+    }
+
+    /// Loads the classes of the application class path entries starting from the given index,
+    /// collecting their problems
+    fn verify_entries_from(&mut self, first_entry: usize) -> VerificationReport {
+        let (mut class_names, unlisted_entries) =
+            self.class_path.class_names_of_entries_from(first_entry);
+        let mut listed = HashSet::new();
+        class_names.retain(|class_name| listed.insert(class_name.clone()));
+
+        let mut report = VerificationReport {
+            classes: class_names.len(),
+            problems: unlisted_entries
+                .into_iter()
+                .map(|(entry, err)| VerificationProblem {
+                    class_name: entry,
+                    kind: VerificationProblemKind::UnreadableEntry,
+                    error: VmError::ClassLoadingError(err.to_string()),
+                })
+                .collect(),
+        };
+        for class_name in class_names {
+            match self.get_or_resolve_class_of(&class_name, DefiningLoader::User) {
+                Ok(class) => {
+                    for missing_class in self.missing_referenced_classes(class.get_class()) {
+                        report.problems.push(VerificationProblem {
+                            class_name: class_name.clone(),
+                            kind: VerificationProblemKind::MissingDependency(missing_class.clone()),
+                            error: VmError::ClassNotFoundException(missing_class),
+                        });
+                    }
+                }
+                Err(err) => report.problems.push(VerificationProblem {
+                    kind: VerificationProblemKind::of_load_error(&class_name, &err),
+                    class_name,
+                    error: err,
+                }),
+            }
+        }
+        report
+    }
+
+    /// The classes referenced by the constant pool of the given one that cannot be found by
+    /// its loader. Those that are found are loaded, but not initialized.
+    fn missing_referenced_classes(&mut self, class: ClassRef<'a>) -> Vec<String> {
+        let referenced_classes: Vec<String> = class
+            .constants
+            .iter()
+            .filter_map(|(_, constant)| match constant {
+                ConstantPoolEntry::ClassReference(name_index) => {
+                    class.constants.string_of(*name_index).ok()
+                }
+                _ => None,
+            })
+            .filter_map(|class_name| {
+                // Arrays need their element class, unless it is a primitive type
+                let element_descriptor = class_name.trim_start_matches('[');
+                if element_descriptor.len() == class_name.len() {
+                    return Some(class_name.to_string());
+                }
+                element_descriptor
+                    .strip_prefix('L')
+                    .and_then(|descriptor| descriptor.strip_suffix(';'))
+                    .map(|element_class_name| element_class_name.to_string())
+            })
+            .collect();
+
+        let mut missing_classes = Vec::new();
+        for referenced_class in referenced_classes {
+            if let Err(VmError::ClassNotFoundException(_)) =
+                self.get_or_resolve_class_of(&referenced_class, class.defining_loader)
+            {
+                if !missing_classes.contains(&referenced_class) {
+                    missing_classes.push(referenced_class);
+                }
+            }
+        }
+        missing_classes
+    }
+
+    /// Lists the classes of the boot class path and then of the application one, each only
+    /// once, returning apart the descriptions of the entries that cannot be listed
+    pub fn class_path_class_names(&self) -> ClassNamesListing {
+        let (mut class_names, mut failures) = self.boot_class_path.class_names();
+        let (mut application_class_names, mut application_failures) = self.class_path.class_names();
+        class_names.append(&mut application_class_names);
+        failures.append(&mut application_failures);
+
+        let mut listed = HashSet::new();
+        class_names.retain(|class_name| listed.insert(class_name.clone()));
+        (class_names, failures)
+    }
+
+    pub fn set_log_config(&mut self, log_config: LogConfig) {
+        self.log_config = log_config;
+        self.boot_class_path.log_config = log_config;
+        self.class_path.log_config = log_config;
+    }
+
+    /// Records that the static initializer of the given class is about to run
+    pub fn begin_initialization(&mut self, class: ClassRef<'a>) {
+        self.initializing.push(class);
+    }
+
+    /// Records that the static initializer of the innermost class being initialized has
+    /// completed, either normally or not
+    pub fn end_initialization(&mut self) {
+        self.initializing.pop();
+    }
+
+    pub fn is_being_initialized(&self, class: ClassRef<'a>) -> bool {
+        self.initializing
+            .iter()
+            .any(|initializing| initializing.id == class.id)
+    }
+
+    /// The names of the classes currently being initialized, outermost first
+    pub fn initialization_chain(&self) -> Vec<String> {
+        self.initializing
+            .iter()
+            .map(|class| class.name.clone())
+            .collect()
+    }
+
     pub fn find_class_by_name(&self, class_name: &str) -> Option<ClassRef<'a>> {
         self.classes_by_name.get(class_name).cloned()
     }
 
-    pub fn get_or_resolve_class(&mut self, class_name: &str) -> Result<ResolvedClass<'a>, VmError> {
-        if let Some(already_loaded_class) = self.find_class_by_name(class_name) {
-            Ok(ResolvedClass::AlreadyLoaded(already_loaded_class))
-        } else {
-            self.resolve_and_load_class(class_name)
-                .map(ResolvedClass::NewClass)
+    /// The object holding the static fields declared by the given class, if it was initialized
+    pub fn statics_of(&self, class_id: ClassId) -> Option<AbstractObject<'a>> {
+        self.statics.get(&class_id).cloned()
+    }
+
+    pub fn set_statics(&mut self, class_id: ClassId, statics: AbstractObject<'a>) {
+        self.statics.insert(class_id, statics);
+    }
+
+    /// The objects holding the static fields, with the id of their class
+    pub fn all_statics(&self) -> impl Iterator<Item = (ClassId, &AbstractObject<'a>)> {
+        self.statics
+            .iter()
+            .map(|(class_id, statics)| (*class_id, statics))
+    }
+
+    /// The class loader that defined the given class, or `None` for the classes of the boot and
+    /// of the application class paths
+    pub fn defining_loader(&self, class_id: ClassId) -> Option<AbstractObject<'a>> {
+        self.defining_loaders.get(&class_id).cloned()
+    }
+
+    pub fn set_defining_loader(&mut self, class_id: ClassId, loader: AbstractObject<'a>) {
+        self.defining_loaders.insert(class_id, loader);
+    }
+
+    pub fn system_class_loader(&self) -> Option<AbstractObject<'a>> {
+        self.system_class_loader.clone()
+    }
+
+    pub fn set_system_class_loader(&mut self, loader: AbstractObject<'a>) {
+        self.system_class_loader = Some(loader);
+    }
+
+    /// The instances of `java.lang.ClassLoader` are alive as long as the classes they defined
+    pub fn defining_loaders_gc_roots(
+        &mut self,
+    ) -> impl Iterator<Item = *mut AbstractObject<'a>> + '_ {
+        self.defining_loaders
+            .values_mut()
+            .chain(self.system_class_loader.iter_mut())
+            .map(|loader| loader as *mut AbstractObject<'a>)
+    }
+
+    /// The objects holding the static fields are always alive, and the gc must update them
+    /// when it moves them
+    pub fn statics_gc_roots(&mut self) -> impl Iterator<Item = *mut AbstractObject<'a>> + '_ {
+        self.statics
+            .values_mut()
+            .map(|statics| statics as *mut AbstractObject<'a>)
+    }
+
+    /// Returns the class with the given name as seen by the given loader, i.e. only if it was
+    /// defined by the loader or by one of its ancestors, loading it if needed. Following the
+    /// delegation model, the boot class path is searched before the application one.
+    pub fn get_or_resolve_class_of(
+        &mut self,
+        class_name: &str,
+        initiating_loader: DefiningLoader,
+    ) -> Result<ResolvedClass<'a>, VmError> {
+        match self.find_class_by_name(class_name) {
+            Some(class) if class.defining_loader.is_visible_from(initiating_loader) => {
+                Ok(ResolvedClass::AlreadyLoaded(class))
+            }
+            Some(_) => Err(VmError::ClassNotFoundException(class_name.to_string())),
+            None => {
+                for observer in self.class_load_observers.iter() {
+                    observer.class_looked_up(class_name, initiating_loader);
+                }
+                self.resolve_and_load_class(class_name, initiating_loader)
+                    .map(ResolvedClass::NewClass)
+                    .inspect_err(|err| self.notify_class_load_failed(class_name, err))
+            }
+        }
+    }
+
+    pub fn add_class_load_observer(&mut self, observer: Rc<dyn ClassLoadObserver>) {
+        self.class_load_observers.push(observer);
+    }
+
+    fn notify_class_load_failed(&self, class_name: &str, error: &VmError) {
+        for observer in self.class_load_observers.iter() {
+            observer.class_load_failed(class_name, error);
+        }
+    }
+
+    /// Notifies the observers that the static initializer of the class completed
+    pub fn notify_class_initialized(&self, class: ClassRef<'a>) {
+        for observer in self.class_load_observers.iter() {
+            observer.class_initialized(class);
         }
     }
 
     fn resolve_and_load_class(
         &mut self,
         class_name: &str,
-    ) -> Result<ClassesToInitialize<'a>, VmError> {
-        let class_file_bytes = self
-            .class_path
-            .resolve(class_name)
+        initiating_loader: DefiningLoader,
+    ) -> Result<LoadedClasses<'a>, VmError> {
+        #[cfg(feature = "std")]
+        {
+            if self.class_loading_threads.get() > 1 && !self.prefetched.contains_key(class_name) {
+                self.prefetch_classes(vec![class_name.to_string()]);
+            }
+            if let Some(Ok((_, _, loader, _))) = self.prefetched.get(class_name) {
+                if !loader.is_visible_from(initiating_loader) {
+                    return Err(VmError::ClassNotFoundException(class_name.to_string()));
+                }
+            }
+            if let Some(prefetched) = self.prefetched.remove(class_name) {
+                let (class_file, class_file_bytes, loader, source) = prefetched?;
+                return self.load_class(class_file, class_file_bytes, loader, source);
+            }
+        }
+
+        let (class_file_bytes, loader, source) =
+            self.read_class_bytes(class_name, initiating_loader)?;
+        // SAFETY: the bytes are moved into the class defined from the class file, which the
+        // arena keeps alive for 'a, or dropped together with the class file if that fails
+        let class_file = unsafe { class_file_bytes.parse() }
+            .map_err(|err| class_reader_error(class_name, err))?;
+        self.load_class(class_file, class_file_bytes, loader, source)
+    }
+
+    /// Parses the bytes of a class that does not come from the class path, i.e. the ones given
+    /// to `ClassLoader.defineClass`. The name, when not known upfront, is the one that the
+    /// bytes declare. The class file borrows from the returned bytes, which must thus be given
+    /// to [ClassManager::define_class] together with it.
+    pub fn parse_class(
+        &mut self,
+        class_name: Option<&str>,
+        class_file_bytes: Vec<u8>,
+    ) -> Result<(ClassFile<'a>, ClassFileBytes), VmError> {
+        let class_file_bytes = match class_name {
+            _ if self.class_file_transformers.is_empty() => class_file_bytes,
+            Some(class_name) => self.transform_class_bytes(class_name, class_file_bytes),
+            None => match class_reader::read_buffer(&class_file_bytes) {
+                Ok(class_file) => {
+                    let class_name = class_file.name.to_string();
+                    self.transform_class_bytes(&class_name, class_file_bytes)
+                }
+                // The error is reported below
+                Err(_) => class_file_bytes,
+            },
+        };
+        let class_file_bytes = ClassFileBytes::new(class_file_bytes);
+        // SAFETY: the bytes are returned together with the class file, and then moved into the
+        // class defined from it, just like in `resolve_and_load_class`
+        let class_file = unsafe { class_file_bytes.parse() }
+            .map_err(|err| VmError::ClassLoadingError(err.to_string()))?;
+        Ok((class_file, class_file_bytes))
+    }
+
+    pub fn add_class_file_transformer(&mut self, transformer: Rc<dyn ClassFileTransformer>) {
+        self.class_file_transformers.push(transformer);
+    }
+
+    /// Passes the bytes of the class through all the transformers, each one receiving the
+    /// bytes returned by the previous one
+    fn transform_class_bytes(&self, class_name: &str, class_file_bytes: Vec<u8>) -> Vec<u8> {
+        self.class_file_transformers.iter().fold(
+            class_file_bytes,
+            |class_file_bytes, transformer| match transformer
+                .transform(class_name, &class_file_bytes)
+            {
+                Some(transformed_bytes) => {
+                    vm_log!(
+                        self.log_config,
+                        LogCategory::ClassLoad,
+                        Level::Debug,
+                        "transformed class {class_name}"
+                    );
+                    transformed_bytes
+                }
+                None => class_file_bytes,
+            },
+        )
+    }
+
+    /// Reads a class from the class paths that the given loader can see, returning the loader
+    /// that will define it and the entry providing it
+    fn read_class_bytes(
+        &mut self,
+        class_name: &str,
+        initiating_loader: DefiningLoader,
+    ) -> Result<(ClassFileBytes, DefiningLoader, ClassSource), VmError> {
+        let (class_file_bytes, loader, source) = self
+            .find_class_bytes(class_name, initiating_loader)
             .map_err(|err| VmError::ClassLoadingError(err.to_string()))?
             .ok_or(VmError::ClassNotFoundException(class_name.to_string()))?;
-        let class_file = class_reader::read_buffer(&class_file_bytes)
-            .map_err(|err| VmError::ClassLoadingError(err.to_string()))?;
-        self.load_class(class_file)
+        let class_file_bytes = self.transform_class_bytes(class_name, class_file_bytes);
+        Ok((ClassFileBytes::new(class_file_bytes), loader, source))
+    }
+
+    fn find_class_bytes(
+        &self,
+        class_name: &str,
+        initiating_loader: DefiningLoader,
+    ) -> Result<Option<(Vec<u8>, DefiningLoader, ClassSource)>, ClassLoadingError> {
+        let with_source = |loader| {
+            move |(bytes, entry): ClassBytesAndEntry<'_>| {
+                (
+                    bytes,
+                    loader,
+                    ClassSource::ClassPathEntry(entry.description()),
+                )
+            }
+        };
+        if self.boot_class_path.is_empty() {
+            let class_file_bytes = self.class_path.resolve(class_name)?;
+            return Ok(class_file_bytes.map(with_source(DefiningLoader::Bootstrap)));
+        }
+        if let Some(class_file_bytes) = self.boot_class_path.resolve(class_name)? {
+            return Ok(Some(with_source(DefiningLoader::Bootstrap)(
+                class_file_bytes,
+            )));
+        }
+        if initiating_loader == DefiningLoader::Bootstrap {
+            return Ok(None);
+        }
+        let class_file_bytes = self.class_path.resolve(class_name)?;
+        Ok(class_file_bytes.map(with_source(DefiningLoader::Application)))
+    }
+
+    /// Reads the given classes, and all their superclasses and interfaces that are not loaded
+    /// yet, and parses them on [ClassManager::class_loading_threads] threads, one level of the
+    /// hierarchy at a time. The class path is only accessed from the current thread, and the
+    /// classes are still defined one at a time, in the same order and with the same ids as
+    /// without prefetching, when they are resolved. The classes that cannot be found are
+    /// skipped, so that their resolution fails as usual.
+    #[cfg(feature = "std")]
+    pub fn prefetch_classes(&mut self, class_names: Vec<String>) {
+        let mut level = class_names;
+        while !level.is_empty() {
+            let mut class_files: Vec<(String, ClassFileBytes)> = Vec::new();
+            let mut loaders: HashMap<String, (DefiningLoader, ClassSource)> = HashMap::new();
+            for class_name in level {
+                if self.find_class_by_name(&class_name).is_some()
+                    || self.prefetched.contains_key(&class_name)
+                    || class_files.iter().any(|(name, _)| *name == class_name)
+                {
+                    continue;
+                }
+                match self.read_class_bytes(&class_name, DefiningLoader::Application) {
+                    Ok((bytes, loader, source)) => {
+                        loaders.insert(class_name.clone(), (loader, source));
+                        class_files.push((class_name, bytes));
+                    }
+                    Err(VmError::ClassNotFoundException(_)) => {}
+                    Err(err) => {
+                        self.prefetched.insert(class_name, Err(err));
+                    }
+                }
+            }
+
+            level = Vec::new();
+            // SAFETY: each class file is kept together with its bytes, which are moved into
+            // the class defined from it like in `resolve_and_load_class`
+            let parsed_classes =
+                unsafe { class_prefetch::parse_classes(class_files, self.class_loading_threads) };
+            for (class_name, class_file, class_file_bytes) in parsed_classes {
+                let class_file = class_file.map_err(|err| class_reader_error(&class_name, err));
+                if let Ok(class_file) = &class_file {
+                    level.extend(class_file.superclass.iter().map(|name| name.to_string()));
+                    level.extend(class_file.interfaces.iter().map(|name| name.to_string()));
+                }
+                let (loader, source) = loaders
+                    .remove(&class_name)
+                    .expect("every parsed class should have been read");
+                self.prefetched.insert(
+                    class_name,
+                    class_file.map(|class_file| (class_file, class_file_bytes, loader, source)),
+                );
+            }
+        }
     }
 
-    fn load_class(&mut self, class_file: ClassFile) -> Result<ClassesToInitialize<'a>, VmError> {
-        let referenced_classes = self.resolve_super_and_interfaces(&class_file)?;
-        let loaded_class = self.allocate(class_file, referenced_classes)?;
+    /// Defines a class that was not loaded from the class path, i.e. one synthesized by the vm,
+    /// which has no bytes, or one given to `ClassLoader.defineClass`, on behalf of the given
+    /// loader
+    pub fn define_class(
+        &mut self,
+        class_file: ClassFile<'a>,
+        class_file_bytes: ClassFileBytes,
+        loader: DefiningLoader,
+        source: ClassSource,
+    ) -> Result<LoadedClasses<'a>, VmError> {
+        let class_name = class_file.name.to_string();
+        let result = if self.find_class_by_name(&class_name).is_some() {
+            Err(VmError::ClassLoadingError(format!(
+                "class {class_name} is already defined"
+            )))
+        } else {
+            self.load_class(class_file, class_file_bytes, loader, source)
+        };
+        result.inspect_err(|err| self.notify_class_load_failed(&class_name, err))
+    }
+
+    fn load_class(
+        &mut self,
+        class_file: ClassFile<'a>,
+        class_file_bytes: ClassFileBytes,
+        loader: DefiningLoader,
+        source: ClassSource,
+    ) -> Result<LoadedClasses<'a>, VmError> {
+        linker::link_class(&class_file)?;
+        if self.verify_classes {
+            verifier::verify_class(&class_file)?;
+        }
+        let referenced_classes = self.resolve_super_and_interfaces(&class_file, loader)?;
+        let loaded_class =
+            self.allocate(class_file, class_file_bytes, loader, referenced_classes)?;
         self.register_loaded_class(loaded_class.resolved_class);
+        for observer in self.class_load_observers.iter() {
+            observer.class_defined(loaded_class.resolved_class, &source);
+        }
         Ok(loaded_class)
     }
 
+    /// The superclass and the interfaces are requested to the loader defining the class
     fn resolve_super_and_interfaces(
         &mut self,
-        class_file: &ClassFile,
+        class_file: &ClassFile<'a>,
+        loader: DefiningLoader,
     ) -> Result<IndexMap<String, ResolvedClass<'a>>, VmError> {
         let mut resolved_classes: IndexMap<String, ResolvedClass<'a>> = Default::default();
         if let Some(superclass_name) = &class_file.superclass {
-            self.resolve_and_collect_class(superclass_name, &mut resolved_classes)?;
+            self.resolve_and_collect_class(superclass_name, loader, &mut resolved_classes)?;
         }
         for interface_name in class_file.interfaces.iter() {
-            self.resolve_and_collect_class(interface_name, &mut resolved_classes)?;
+            match self.resolve_and_collect_class(interface_name, loader, &mut resolved_classes) {
+                // The class can still be used, it just won't be an instance of the interface
+                Err(VmError::ClassNotFoundException(missing_class)) if self.lazy_linkage => {
+                    vm_log!(
+                        self.log_config,
+                        LogCategory::ClassLoad,
+                        Level::Warn,
+                        "class {} implements {}, whose class {} cannot be found; ignoring it",
+                        class_file.name,
+                        interface_name,
+                        missing_class
+                    );
+                }
+                result => result?,
+            }
         }
         Ok(resolved_classes)
     }
@@ -138,74 +758,154 @@ impl<'a> ClassManager<'a> {
     fn resolve_and_collect_class(
         &mut self,
         class_name: &str,
+        loader: DefiningLoader,
         resolved_classes: &mut IndexMap<String, ResolvedClass<'a>>,
     ) -> Result<(), VmError> {
-        let class = self.get_or_resolve_class(class_name)?;
+        let class = self.get_or_resolve_class_of(class_name, loader)?;
         resolved_classes.insert(class_name.to_string(), class);
         Ok(())
     }
 
     fn allocate(
         &mut self,
-        class_file: ClassFile,
+        class_file: ClassFile<'a>,
+        class_file_bytes: ClassFileBytes,
+        loader: DefiningLoader,
         referenced_classes: IndexMap<String, ResolvedClass<'a>>,
-    ) -> Result<ClassesToInitialize<'a>, VmError> {
+    ) -> Result<LoadedClasses<'a>, VmError> {
         let next_id = self.next_id;
         self.next_id += 1;
 
         let id = ClassId::new(next_id);
-        debug!("loading class {} from file {}", id, class_file.name);
-        let class = Self::new_class(class_file, id, &referenced_classes)?;
-        let class_ref = self.arena.alloc(class);
-
-        // SAFETY: our reference class_ref is alive only for 'b.
-        // However we actually know that the arena will keep the value alive for 'a,
-        // and I cannot find a way to convince the compiler of this fact. Thus
-        // I'm using this pointer "trick" to make the compiler happy.
-        // I expect this can be done with safe Rust, I just do not know how at the moment...
-        let class_ref = unsafe {
-            let class_ptr: *const Class<'a> = class_ref;
-            &*class_ptr
-        };
+        vm_log!(
+            self.log_config,
+            LogCategory::ClassLoad,
+            Level::Debug,
+            "loading class {} from file {}",
+            id,
+            class_file.name
+        );
+        let class = Self::new_class(
+            class_file,
+            class_file_bytes,
+            id,
+            loader,
+            &referenced_classes,
+        )?;
+        let class_ref = self.alloc_class(class);
 
-        let mut classes_to_init: Vec<ClassRef<'a>> = Vec::new();
+        let mut loaded_classes: Vec<ClassRef<'a>> = Vec::new();
         for resolved_class in referenced_classes.values() {
             if let ResolvedClass::NewClass(new_class) = resolved_class {
-                for to_initialize in new_class.to_initialize.iter() {
-                    classes_to_init.push(to_initialize)
+                for loaded_class in new_class.loaded.iter() {
+                    loaded_classes.push(loaded_class)
                 }
             }
         }
-        classes_to_init.push(class_ref);
+        loaded_classes.push(class_ref);
 
-        debug!(
-            "initializing class {}, classes to init {:?}",
+        vm_log!(
+            self.log_config,
+            LogCategory::ClassLoad,
+            Level::Debug,
+            "loaded class {}, classes loaded {:?}",
             class_ref.name,
-            classes_to_init
+            loaded_classes
                 .iter()
                 .map(|c| &c.name)
                 .collect::<Vec<&String>>()
         );
 
-        Ok(ClassesToInitialize {
+        Ok(LoadedClasses {
             resolved_class: class_ref,
-            to_initialize: classes_to_init,
+            loaded: loaded_classes,
         })
     }
 
+    fn alloc_class(&mut self, class: Class<'a>) -> ClassRef<'a> {
+        let class_ref = self.arena.alloc(class);
+
+        // SAFETY: our reference class_ref is alive only for 'b.
+        // However we actually know that the arena will keep the value alive for 'a,
+        // and I cannot find a way to convince the compiler of this fact. Thus
+        // I'm using this pointer "trick" to make the compiler happy.
+        // I expect this can be done with safe Rust, I just do not know how at the moment...
+        unsafe {
+            let class_ptr: *const Class<'a> = class_ref;
+            &*class_ptr
+        }
+    }
+
+    /// Replaces an already loaded class with a new version, defined by the given class file,
+    /// which must differ only in the code of its methods; see
+    /// [Vm::redefine_class](crate::vm::Vm::redefine_class). The new version keeps the id,
+    /// the defining loader, the hierarchy and the initialization state of the class.
+    pub fn redefine_class(
+        &mut self,
+        class_file: ClassFile<'a>,
+        class_file_bytes: ClassFileBytes,
+    ) -> Result<ClassRef<'a>, VmError> {
+        let old_version = self
+            .find_class_by_name(&class_file.name)
+            .ok_or_else(|| VmError::ClassNotFoundException(class_file.name.to_string()))?;
+        check_redefinition(old_version, &class_file)?;
+        linker::link_class(&class_file)?;
+        if self.verify_classes {
+            verifier::verify_class(&class_file)?;
+        }
+
+        let referenced_classes = old_version
+            .superclass
+            .iter()
+            .chain(old_version.interfaces.iter())
+            .map(|class| (class.name.clone(), ResolvedClass::AlreadyLoaded(class)))
+            .collect();
+        let mut class = Self::new_class(
+            class_file,
+            class_file_bytes,
+            old_version.id,
+            old_version.defining_loader,
+            &referenced_classes,
+        )?;
+        class.version = old_version.version + 1;
+        class.initialization_state = old_version.initialization_state.clone();
+        let class_ref = self.alloc_class(class);
+        old_version.redefined.set(true);
+        self.register_loaded_class(class_ref);
+        for observer in self.class_load_observers.iter() {
+            observer.class_defined(class_ref, &ClassSource::Redefinition);
+        }
+
+        vm_log!(
+            self.log_config,
+            LogCategory::ClassLoad,
+            Level::Debug,
+            "redefined class {}, now at version {}",
+            class_ref.name,
+            class_ref.version
+        );
+        Ok(class_ref)
+    }
+
     fn new_class(
-        class_file: ClassFile,
+        class_file: ClassFile<'a>,
+        class_file_bytes: ClassFileBytes,
         id: ClassId,
+        defining_loader: DefiningLoader,
         resolved_classes: &IndexMap<String, ResolvedClass<'a>>,
     ) -> Result<Class<'a>, VmError> {
-        let superclass = class_file
-            .superclass
-            .as_ref()
-            .map(|superclass_name| resolved_classes.get(superclass_name).unwrap().get_class());
+        let superclass = class_file.superclass.as_ref().map(|superclass_name| {
+            resolved_classes
+                .get(superclass_name.as_ref())
+                .unwrap()
+                .get_class()
+        });
+        // With lazy linkage, the missing interfaces are not resolved
         let interfaces: Vec<ClassRef<'a>> = class_file
             .interfaces
             .iter()
-            .map(|interface_name| resolved_classes.get(interface_name).unwrap().get_class())
+            .filter_map(|interface_name| resolved_classes.get(interface_name.as_ref()))
+            .map(|interface| interface.get_class())
             .collect();
 
         let num_superclass_fields = match superclass {
@@ -213,25 +913,124 @@ impl<'a> ClassManager<'a> {
             None => 0,
         };
         let num_this_class_fields = class_file.fields.len();
+        let reference_kind = ReferenceKind::of_class(&class_file.name, superclass);
+        let has_finalizer = has_finalizer(&class_file, superclass);
+        let vtable = Vtable::new(
+            &class_file.name,
+            class_file.flags,
+            superclass,
+            &class_file.methods,
+        );
+
+        let decoded_code = class_file.methods.iter().map(|_| OnceCell::new()).collect();
+        let has_intrinsic = class_file.methods.iter().map(|_| OnceCell::new()).collect();
 
         Ok(Class {
             id,
-            name: class_file.name,
-            source_file: class_file.source_file,
+            version: 0,
+            name: class_file.name.into_owned(),
+            source_file: class_file.source_file.map(|name| name.into_owned()),
+            nest_host: class_file.nest_host.map(|name| name.into_owned()),
             constants: class_file.constants,
             flags: class_file.flags,
+            defining_loader,
             superclass,
             interfaces,
             fields: class_file.fields,
             methods: class_file.methods,
+            bootstrap_methods: class_file.bootstrap_methods,
             num_total_fields: num_superclass_fields + num_this_class_fields,
             first_field_index: num_superclass_fields,
+            reference_kind,
+            has_finalizer,
+            vtable,
+            decoded_code,
+            has_intrinsic,
+            initialization_state: Rc::new(Cell::new(InitializationState::NotInitialized)),
+            redefined: Cell::new(false),
+            class_file_bytes,
         })
     }
 
     fn register_loaded_class(&mut self, class: ClassRef<'a>) {
         self.classes_by_name.insert(class.name.clone(), class);
         self.classes_by_id.insert(class.id, class);
-        self.current_class_loader.register_class(class);
+    }
+}
+
+/// Converts an error parsing the given class from the class path
+fn class_reader_error(class_name: &str, err: ClassReaderError) -> VmError {
+    match err {
+        ClassReaderError::UnsupportedVersion(major, minor) => {
+            VmError::UnsupportedClassVersionError(class_name.to_string(), major, minor)
+        }
+        err => VmError::ClassLoadingError(err.to_string()),
+    }
+}
+
+/// Checks that the new version of a class differs from the loaded one only in the code of its
+/// methods, like the JVM tools interface requires: the instances and the static fields keep
+/// their layout, and the methods keep their slots in the virtual method tables.
+fn check_redefinition(old_version: ClassRef, class_file: &ClassFile) -> Result<(), VmError> {
+    let unsupported = |reason: &str| {
+        VmError::UnsupportedClassRedefinition(class_file.name.to_string(), reason.to_string())
+    };
+    let superclass_name = old_version
+        .superclass
+        .map(|superclass| superclass.name.as_str());
+    if superclass_name != class_file.superclass.as_deref() {
+        return Err(unsupported("the superclass changed"));
+    }
+    let interface_names = old_version
+        .interfaces
+        .iter()
+        .map(|interface| interface.name.as_str());
+    if !interface_names.eq(class_file.interfaces.iter().map(|name| name.as_ref())) {
+        return Err(unsupported("the interfaces changed"));
+    }
+    if old_version.flags != class_file.flags {
+        return Err(unsupported("the modifiers of the class changed"));
+    }
+    let same_fields = old_version.fields.len() == class_file.fields.len()
+        && old_version
+            .fields
+            .iter()
+            .zip(class_file.fields.iter())
+            .all(|(old, new)| {
+                old.name == new.name
+                    && old.type_descriptor == new.type_descriptor
+                    && old.flags == new.flags
+            });
+    if !same_fields {
+        return Err(unsupported("the fields changed"));
+    }
+    let same_methods = old_version.methods.len() == class_file.methods.len()
+        && old_version.methods.iter().all(|old| {
+            class_file.methods.iter().any(|new| {
+                old.name == new.name
+                    && old.type_descriptor == new.type_descriptor
+                    && old.flags == new.flags
+            })
+        });
+    if !same_methods {
+        return Err(unsupported(
+            "methods were added or removed, or their modifiers changed",
+        ));
+    }
+    Ok(())
+}
+
+/// Whether the instances of the class need to be finalized. Like the JVM does, we ignore the
+/// finalizers that consist of a bare `return`, such as the one of `java.lang.Object`.
+fn has_finalizer(class_file: &ClassFile, superclass: Option<ClassRef>) -> bool {
+    const RETURN_OPCODE: u8 = 0xb1;
+    match class_file.methods.iter().find(|method| {
+        method.name == "finalize" && method.type_descriptor == "()V" && !method.is_static()
+    }) {
+        Some(method) => method
+            .code
+            .as_ref()
+            .is_some_and(|code| *code.code != [RETURN_OPCODE]),
+        None => superclass.is_some_and(|superclass| superclass.has_finalizer),
     }
 }
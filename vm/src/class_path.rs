@@ -1,17 +1,70 @@
-use log::debug;
+use std::{
+    collections::BTreeMap,
+    fmt,
+    fmt::Formatter,
+    path::{Path, PathBuf},
+    rc::Rc,
+};
+
+use log::Level;
 use thiserror::Error;
 
 use crate::{
     class_path_entry::{ClassLoadingError, ClassPathEntry},
+    class_path_scan::ScanProgressListener,
     file_system_class_path_entry::FileSystemClassPathEntry,
+    io::{FileKind, JvmIo, StdJvmIo},
     jar_file_class_path_entry::JarFileClassPathEntry,
+    jar_manifest::JarManifest,
+    jimage_class_path_entry::JImageClassPathEntry,
+    jmod_class_path_entry::JmodClassPathEntry,
+    log_config::{vm_log, LogCategory, LogConfig},
+    nested_jar_class_path_entry::NestedJarClassPathEntry,
 };
 
 /// Models a class path, i.e. a list of [ClassPathEntry]
+///
+/// When the same class is provided by more than one entry, the first one wins, just like in a
+/// real JVM. Since this can easily hide problems, the class path can optionally be put in
+/// strict mode, in which it scans all entries for duplicate classes whenever some entries are
+/// added, and logs a warning whenever a class that shadows other ones is resolved.
 #[allow(dead_code)]
 #[derive(Default, Debug)]
 pub struct ClassPath {
     entries: Vec<Box<dyn ClassPathEntry>>,
+    pub(crate) log_config: LogConfig,
+    strict: bool,
+    duplicate_classes: Vec<DuplicateClass>,
+    scan_progress_listener: Option<ScanProgressListener>,
+    /// How jars are read; by default, through the file system of the operating system
+    io: Option<Rc<dyn JvmIo>>,
+}
+
+/// The bytes of a class, along with the entry that provided them
+pub type ClassBytesAndEntry<'e> = (Vec<u8>, &'e dyn ClassPathEntry);
+
+/// The names of the classes of some class path entries, along with the descriptions of the
+/// entries whose classes cannot be listed
+pub type ClassNamesListing = (Vec<String>, Vec<(String, ClassLoadingError)>);
+
+/// A class provided by more than one class path entry
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DuplicateClass {
+    pub class_name: String,
+    /// The descriptions of the entries providing the class, in class path order;
+    /// the first one is the one actually used
+    pub entries: Vec<String>,
+}
+
+impl fmt::Display for DuplicateClass {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "class {} is provided by multiple class path entries: {}",
+            self.class_name,
+            self.entries.join(", ")
+        )
+    }
 }
 
 /// Error that models the fact that a class path entry was not valid
@@ -19,32 +72,362 @@ pub struct ClassPath {
 pub enum ClassPathParseError {
     #[error("invalid classpath entry: {0}")]
     InvalidEntry(String),
+
+    #[error("cannot scan classpath for duplicate classes: {0}")]
+    ScanFailed(String),
 }
 
 impl ClassPath {
     /// Parses and adds class path entries.
     /// These should be separated by a colon (:), just like in a real JVM.
+    /// Like the java launcher does, the entries listed in the `Class-Path` attribute of the
+    /// manifest of a jar are added right after it, skipping the ones that do not exist.
+    /// An entry made of a directory followed by `/*`, or just `*` for the current directory,
+    /// is expanded to all the jars of the directory, sorted by name.
     pub fn push(&mut self, string: &str) -> Result<(), ClassPathParseError> {
         let mut entries_to_add: Vec<Box<dyn ClassPathEntry>> = Vec::new();
         for entry in string.split(':') {
-            debug!("trying to parse class path entry {}", entry);
-            let parsed_entry = Self::try_parse_entry(entry)?;
-            entries_to_add.push(parsed_entry);
+            vm_log!(
+                self.log_config,
+                LogCategory::ClassLoad,
+                Level::Debug,
+                "trying to parse class path entry {}",
+                entry
+            );
+            if let Some(directory) = Self::wildcard_directory(entry) {
+                self.add_jars_of_directory(entry, directory, &mut entries_to_add)?;
+                continue;
+            }
+            let parsed_entry = self.try_parse_entry(entry)?;
+            self.add_with_manifest_class_path(parsed_entry, &mut entries_to_add);
         }
         self.entries.append(&mut entries_to_add);
+
+        if self.strict {
+            self.check_duplicate_classes()?;
+        }
+        Ok(())
+    }
+
+    /// Adds an entry implemented outside of this crate, e.g. one backed by a database, to
+    /// the end of the class path. Just like with [ClassPath::push], the class path of its
+    /// manifest, if any, is followed.
+    pub fn push_entry(
+        &mut self,
+        entry: Box<dyn ClassPathEntry>,
+    ) -> Result<(), ClassPathParseError> {
+        vm_log!(
+            self.log_config,
+            LogCategory::ClassLoad,
+            Level::Debug,
+            "adding class path entry {}",
+            entry.description()
+        );
+        let mut entries_to_add: Vec<Box<dyn ClassPathEntry>> = Vec::new();
+        self.add_with_manifest_class_path(entry, &mut entries_to_add);
+        self.entries.append(&mut entries_to_add);
+
+        if self.strict {
+            self.check_duplicate_classes()?;
+        }
+        Ok(())
+    }
+
+    /// Adds the given jar, read like the other entries, and returns its manifest, if any, e.g.
+    /// to find the main class of `java -jar`. Just like with [ClassPath::push], the class path
+    /// of its manifest is followed.
+    pub fn push_jar(&mut self, path: &str) -> Result<Option<JarManifest>, ClassPathParseError> {
+        let entry = self.try_parse_entry_as_jar(path)?;
+        let manifest = entry.manifest().cloned();
+        self.push_entry(entry)?;
+        Ok(manifest)
+    }
+
+    /// Enables or disables the strict mode. Note that enabling it does not scan the entries
+    /// that were already added; that will happen when the next entries are added.
+    pub fn set_strict(&mut self, strict: bool) {
+        self.strict = strict;
+        if !strict {
+            self.duplicate_classes.clear();
+        }
+    }
+
+    /// Sets the listener notified with the progress of the scan of the entries added later
+    pub fn set_scan_progress_listener(&mut self, listener: Option<ScanProgressListener>) {
+        self.scan_progress_listener = listener;
+    }
+
+    /// Sets how the jars added later are read, and how the directories of wildcards are
+    /// listed, e.g. from a storage that is not a file system of the operating system.
+    /// Directories of classes are always read from the latter.
+    pub fn set_io(&mut self, io: Option<Rc<dyn JvmIo>>) {
+        self.io = io;
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// The duplicate classes found by the last scan done in strict mode
+    pub fn duplicate_classes(&self) -> &[DuplicateClass] {
+        &self.duplicate_classes
+    }
+
+    fn check_duplicate_classes(&mut self) -> Result<(), ClassPathParseError> {
+        self.duplicate_classes = self
+            .find_duplicate_classes()
+            .map_err(|err| ClassPathParseError::ScanFailed(err.to_string()))?;
+        for duplicate in self.duplicate_classes.iter() {
+            vm_log!(
+                self.log_config,
+                LogCategory::ClassLoad,
+                Level::Warn,
+                "{}",
+                duplicate
+            );
+        }
+        Ok(())
+    }
+
+    /// Lists the classes of all the entries, in class path order, returning apart the
+    /// descriptions of the entries that cannot be listed. A class provided by more than one
+    /// entry is listed more than once.
+    pub fn class_names(&self) -> ClassNamesListing {
+        self.class_names_of_entries_from(0)
+    }
+
+    /// Like [ClassPath::class_names], but only for the entries starting from the given index,
+    /// e.g. the ones that were just pushed
+    pub fn class_names_of_entries_from(&self, first_entry: usize) -> ClassNamesListing {
+        let mut class_names = Vec::new();
+        let mut failures = Vec::new();
+        for entry in self.entries.iter().skip(first_entry) {
+            match entry.class_names() {
+                Ok(entry_class_names) => class_names.extend(
+                    entry_class_names
+                        .into_iter()
+                        // Module descriptors and the versioned classes of multi-release jars
+                        // are not classes
+                        .filter(|name| {
+                            !name.starts_with("META-INF/") && !name.ends_with("module-info")
+                        }),
+                ),
+                Err(err) => failures.push((entry.description(), err)),
+            }
+        }
+        (class_names, failures)
+    }
+
+    /// The number of entries
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Removes the entries past the first `len` ones, e.g. the ones pushed only temporarily,
+    /// and forgets them in the duplicate classes found in strict mode
+    pub fn truncate(&mut self, len: usize) {
+        if len >= self.entries.len() {
+            return;
+        }
+        let removed_entries: Vec<String> = self
+            .entries
+            .drain(len..)
+            .map(|entry| entry.description())
+            .collect();
+        for duplicate in self.duplicate_classes.iter_mut() {
+            for removed_entry in removed_entries.iter() {
+                if let Some(index) = duplicate.entries.iter().rposition(|e| e == removed_entry) {
+                    duplicate.entries.remove(index);
+                }
+            }
+        }
+        self.duplicate_classes
+            .retain(|duplicate| duplicate.entries.len() > 1);
+    }
+
+    /// Scans all the entries and returns the classes that are provided by more than one,
+    /// sorted by name. This can be expensive, since it has to list the content of all entries.
+    pub fn find_duplicate_classes(&self) -> Result<Vec<DuplicateClass>, ClassLoadingError> {
+        let mut entries_by_class: BTreeMap<String, Vec<String>> = BTreeMap::new();
+        for entry in self.entries.iter() {
+            let description = entry.description();
+            for class_name in entry.class_names()? {
+                entries_by_class
+                    .entry(class_name)
+                    .or_default()
+                    .push(description.clone());
+            }
+        }
+        Ok(entries_by_class
+            .into_iter()
+            .filter(|(_, entries)| entries.len() > 1)
+            .map(|(class_name, entries)| DuplicateClass {
+                class_name,
+                entries,
+            })
+            .collect())
+    }
+
+    fn add_with_manifest_class_path(
+        &self,
+        entry: Box<dyn ClassPathEntry>,
+        entries_to_add: &mut Vec<Box<dyn ClassPathEntry>>,
+    ) {
+        let base_directory = PathBuf::from(entry.description())
+            .parent()
+            .map(Path::to_path_buf)
+            .unwrap_or_default();
+        let referenced_paths: Vec<String> = entry
+            .manifest()
+            .map(|manifest| {
+                manifest
+                    .class_path()
+                    .into_iter()
+                    .map(|path| base_directory.join(path).to_string_lossy().to_string())
+                    .collect()
+            })
+            .unwrap_or_default();
+        entries_to_add.push(entry);
+
+        for path in referenced_paths {
+            if self.contains(&path, entries_to_add) {
+                continue;
+            }
+            match self.try_parse_entry(&path) {
+                Ok(referenced_entry) => {
+                    vm_log!(
+                        self.log_config,
+                        LogCategory::ClassLoad,
+                        Level::Debug,
+                        "adding class path entry {} from a manifest",
+                        path
+                    );
+                    self.add_with_manifest_class_path(referenced_entry, entries_to_add);
+                }
+                Err(_) => vm_log!(
+                    self.log_config,
+                    LogCategory::ClassLoad,
+                    Level::Debug,
+                    "ignoring invalid class path entry {} from a manifest",
+                    path
+                ),
+            }
+        }
+    }
+
+    /// Like in the java launcher, only a `*` as the last component is a wildcard, so that e.g.
+    /// `lib/*.jar` is a plain entry
+    fn wildcard_directory(entry: &str) -> Option<&str> {
+        if entry == "*" {
+            Some("")
+        } else {
+            entry.strip_suffix("/*")
+        }
+    }
+
+    /// Adds the files of the directory whose extension is `.jar`, ignoring the case, but not
+    /// the ones of its subdirectories. As the java launcher does, the jars that are not valid
+    /// are skipped.
+    fn add_jars_of_directory(
+        &self,
+        entry: &str,
+        directory: &str,
+        entries_to_add: &mut Vec<Box<dyn ClassPathEntry>>,
+    ) -> Result<(), ClassPathParseError> {
+        let io = self.io();
+        let listed_directory = if directory.is_empty() { "." } else { directory };
+        let mut file_names = io
+            .list_directory(listed_directory)
+            .map_err(|_| ClassPathParseError::InvalidEntry(entry.to_string()))?;
+        file_names.sort();
+
+        for file_name in file_names {
+            if !file_name.to_ascii_lowercase().ends_with(".jar") {
+                continue;
+            }
+            let path = if directory.is_empty() {
+                file_name
+            } else {
+                format!("{directory}/{file_name}")
+            };
+            if io
+                .file_metadata(&path)
+                .is_ok_and(|metadata| metadata.kind != FileKind::Regular)
+            {
+                continue;
+            }
+            match self.try_parse_entry_as_jar(&path) {
+                Ok(jar) => self.add_with_manifest_class_path(jar, entries_to_add),
+                Err(_) => vm_log!(
+                    self.log_config,
+                    LogCategory::ClassLoad,
+                    Level::Warn,
+                    "ignoring invalid jar {} matched by {}",
+                    path,
+                    entry
+                ),
+            }
+        }
         Ok(())
     }
 
-    fn try_parse_entry(path: &str) -> Result<Box<dyn ClassPathEntry>, ClassPathParseError> {
-        Self::try_parse_entry_as_jar(path).or_else(|_| Self::try_parse_entry_as_directory(path))
+    /// How jars are read and the directories of wildcards are listed
+    fn io(&self) -> Rc<dyn JvmIo> {
+        self.io.clone().unwrap_or_else(|| Rc::new(StdJvmIo::new()))
+    }
+
+    /// Whether the entry with the given path is already in the class path. Paths are compared
+    /// after having been canonicalized, so that manifests referencing each other do not loop.
+    fn contains(&self, path: &str, entries_to_add: &[Box<dyn ClassPathEntry>]) -> bool {
+        let Ok(canonical_path) = std::fs::canonicalize(path) else {
+            return false;
+        };
+        self.entries
+            .iter()
+            .chain(entries_to_add.iter())
+            .any(|entry| {
+                std::fs::canonicalize(entry.description())
+                    .is_ok_and(|entry_path| entry_path == canonical_path)
+            })
+    }
+
+    /// Jmods are zip archives too, so they must be recognized before jars are
+    fn try_parse_entry(&self, path: &str) -> Result<Box<dyn ClassPathEntry>, ClassPathParseError> {
+        Self::try_parse_entry_as_jmod(path)
+            .or_else(|_| Self::try_parse_entry_as_jimage(path))
+            .or_else(|_| self.try_parse_entry_as_jar(path))
+            .or_else(|_| Self::try_parse_entry_as_directory(path))
+    }
+
+    fn try_parse_entry_as_jmod(path: &str) -> Result<Box<dyn ClassPathEntry>, ClassPathParseError> {
+        let entry = JmodClassPathEntry::new(path)
+            .map_err(|_| ClassPathParseError::InvalidEntry(path.to_string()))?;
+        Ok(Box::new(entry))
     }
 
-    fn try_parse_entry_as_jar(path: &str) -> Result<Box<dyn ClassPathEntry>, ClassPathParseError> {
-        let entry = JarFileClassPathEntry::new(path)
+    fn try_parse_entry_as_jimage(
+        path: &str,
+    ) -> Result<Box<dyn ClassPathEntry>, ClassPathParseError> {
+        let entry = JImageClassPathEntry::new(path)
             .map_err(|_| ClassPathParseError::InvalidEntry(path.to_string()))?;
         Ok(Box::new(entry))
     }
 
+    fn try_parse_entry_as_jar(
+        &self,
+        path: &str,
+    ) -> Result<Box<dyn ClassPathEntry>, ClassPathParseError> {
+        let entry =
+            JarFileClassPathEntry::new(path, self.io(), self.scan_progress_listener.clone())
+                .map_err(|_| ClassPathParseError::InvalidEntry(path.to_string()))?;
+        if NestedJarClassPathEntry::is_fat_jar(&entry) {
+            let entry = NestedJarClassPathEntry::new(entry)
+                .map_err(|_| ClassPathParseError::InvalidEntry(path.to_string()))?;
+            return Ok(Box::new(entry));
+        }
+        Ok(Box::new(entry))
+    }
+
     fn try_parse_entry_as_directory(
         path: &str,
     ) -> Result<Box<dyn ClassPathEntry>, ClassPathParseError> {
@@ -53,23 +436,98 @@ impl ClassPath {
         Ok(Box::new(entry))
     }
 
-    /// Attempts to resolve a class from the various entries.
-    /// Stops at the first entry that has a match or an error.
-    pub fn resolve(&self, class_name: &str) -> Result<Option<Vec<u8>>, ClassLoadingError> {
+    /// Attempts to resolve a class from the various entries, returning also the entry that
+    /// provided it. Stops at the first entry that has a match or an error.
+    pub fn resolve(
+        &self,
+        class_name: &str,
+    ) -> Result<Option<ClassBytesAndEntry<'_>>, ClassLoadingError> {
         for entry in self.entries.iter() {
-            debug!("looking up class {} in {:?}", class_name, entry);
+            vm_log!(
+                self.log_config,
+                LogCategory::ClassLoad,
+                Level::Debug,
+                "looking up class {} in {:?}",
+                class_name,
+                entry
+            );
             let entry_result = entry.resolve(class_name)?;
             if let Some(class_bytes) = entry_result {
-                return Ok(Some(class_bytes));
+                self.warn_if_shadowing(class_name);
+                return Ok(Some((class_bytes, entry.as_ref())));
             }
         }
         Ok(None)
     }
+
+    /// Attempts to read a resource, i.e. an arbitrary file such as `rjvm/data.properties`,
+    /// from the various entries. Just like [ClassPath::resolve], the first match wins.
+    pub fn resolve_resource(&self, path: &str) -> Result<Option<Vec<u8>>, ClassLoadingError> {
+        for entry in self.entries.iter() {
+            vm_log!(
+                self.log_config,
+                LogCategory::ClassLoad,
+                Level::Debug,
+                "looking up resource {} in {:?}",
+                path,
+                entry
+            );
+            if let Some(bytes) = entry.resolve_resource(path)? {
+                return Ok(Some(bytes));
+            }
+        }
+        Ok(None)
+    }
+
+    fn warn_if_shadowing(&self, class_name: &str) {
+        if let Ok(index) = self
+            .duplicate_classes
+            .binary_search_by(|duplicate| duplicate.class_name.as_str().cmp(class_name))
+        {
+            let duplicate = &self.duplicate_classes[index];
+            vm_log!(
+                self.log_config,
+                LogCategory::ClassLoad,
+                Level::Warn,
+                "resolved class {} from {}, shadowing the one(s) in {}",
+                class_name,
+                duplicate.entries[0],
+                duplicate.entries[1..].join(", ")
+            );
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::ClassPath;
+    use std::collections::HashMap;
+
+    use super::{ClassPath, ClassPathParseError, DuplicateClass};
+    use crate::class_path_entry::{ClassLoadingError, ClassPathEntry};
+
+    #[derive(Debug)]
+    struct InMemoryClassPathEntry {
+        files: HashMap<String, Vec<u8>>,
+    }
+
+    impl ClassPathEntry for InMemoryClassPathEntry {
+        fn resolve_resource(&self, path: &str) -> Result<Option<Vec<u8>>, ClassLoadingError> {
+            Ok(self.files.get(path).cloned())
+        }
+
+        fn description(&self) -> String {
+            "in-memory".to_string()
+        }
+
+        fn class_names(&self) -> Result<Vec<String>, ClassLoadingError> {
+            Ok(self
+                .files
+                .keys()
+                .filter_map(|path| path.strip_suffix(".class"))
+                .map(str::to_string)
+                .collect())
+        }
+    }
 
     #[test]
     fn can_parse_valid_classpath_entries() {
@@ -85,8 +543,206 @@ mod tests {
         assert_cannot_find_class(&class_path, "foo");
     }
 
+    #[test]
+    fn follows_the_class_path_of_jar_manifests() {
+        let dir = env!("CARGO_MANIFEST_DIR");
+        let mut class_path: ClassPath = Default::default();
+        class_path
+            .push(&format!(
+                "{dir}/tests/resources/jars/app.jar:{dir}/tests/resources/sample.jar"
+            ))
+            .expect("should be able to parse classpath");
+        assert_can_find_class(&class_path, "rjvm/jars/App");
+        assert_can_find_class(&class_path, "rjvm/jars/lib/Greeter");
+
+        // The missing entry is skipped and the reference back to app.jar is not added again
+        let descriptions: Vec<String> = class_path
+            .entries
+            .iter()
+            .map(|entry| entry.description())
+            .collect();
+        assert_eq!(
+            vec![
+                format!("{dir}/tests/resources/jars/app.jar"),
+                format!("{dir}/tests/resources/jars/lib/greeter.jar"),
+                format!("{dir}/tests/resources/sample.jar"),
+            ],
+            descriptions
+        );
+    }
+
+    #[test]
+    fn can_find_classes_in_nested_jars() {
+        let dir = env!("CARGO_MANIFEST_DIR");
+        let mut class_path: ClassPath = Default::default();
+        class_path
+            .push(&format!("{dir}/tests/resources/jars/fat.jar"))
+            .expect("should be able to parse classpath");
+        assert_can_find_class(&class_path, "rjvm/jars/App");
+        assert_can_find_class(&class_path, "rjvm/jars/lib/Greeter");
+    }
+
+    #[test]
+    fn can_find_classes_in_jimages_and_jmods() {
+        let dir = env!("CARGO_MANIFEST_DIR");
+        for entry in ["modules/modules", "modules/rjvm.sample.jmod"] {
+            let mut class_path: ClassPath = Default::default();
+            class_path
+                .push(&format!("{dir}/tests/resources/{entry}"))
+                .expect("should be able to parse classpath");
+            assert_can_find_class(&class_path, "rjvm/SimpleMain");
+            assert_cannot_find_class(&class_path, "rjvm/Foo");
+        }
+    }
+
+    #[test]
+    fn expands_wildcards_to_the_jars_of_a_directory() {
+        let dir = env!("CARGO_MANIFEST_DIR");
+        let mut class_path: ClassPath = Default::default();
+        class_path
+            .push(&format!("{dir}/tests/resources/jars/*"))
+            .expect("should be able to parse classpath");
+        assert_can_find_class(&class_path, "rjvm/jars/App");
+
+        // Subdirectories are not searched, but the manifest of app.jar references lib/greeter.jar
+        let descriptions: Vec<String> = class_path
+            .entries
+            .iter()
+            .map(|entry| entry.description())
+            .collect();
+        assert_eq!(
+            vec![
+                format!("{dir}/tests/resources/jars/app.jar"),
+                format!("{dir}/tests/resources/jars/lib/greeter.jar"),
+                format!("{dir}/tests/resources/jars/fat.jar"),
+            ],
+            descriptions
+        );
+
+        assert_eq!(
+            Err(ClassPathParseError::InvalidEntry(format!(
+                "{dir}/tests/resources/jars/*.jar"
+            ))),
+            class_path.push(&format!("{dir}/tests/resources/jars/*.jar"))
+        );
+        assert_eq!(
+            Err(ClassPathParseError::InvalidEntry(format!(
+                "{dir}/tests/resources/missing/*"
+            ))),
+            class_path.push(&format!("{dir}/tests/resources/missing/*"))
+        );
+    }
+
+    #[test]
+    fn can_find_resources() {
+        let dir = env!("CARGO_MANIFEST_DIR");
+        let mut class_path: ClassPath = Default::default();
+        class_path
+            .push(&format!("{dir}/rt.jar:{dir}/tests/resources"))
+            .expect("should be able to parse classpath");
+        let find = |path: &str| {
+            class_path
+                .resolve_resource(path)
+                .expect("should not have had any errors")
+        };
+        assert!(find("META-INF/MANIFEST.MF").is_some()); // From jar
+        assert!(find("rjvm/SimpleMain.java").is_some()); // From directory
+        assert!(find("rjvm/Foo.java").is_none());
+    }
+
+    #[test]
+    fn can_find_duplicate_classes() {
+        let dir = env!("CARGO_MANIFEST_DIR");
+        let mut class_path: ClassPath = Default::default();
+        class_path
+            .push(&format!(
+                "{dir}/tests/resources/sample.jar:{dir}/tests/resources",
+            ))
+            .expect("should be able to parse classpath");
+
+        let expected = vec![
+            DuplicateClass {
+                class_name: "rjvm/ControlFlow".to_string(),
+                entries: vec![
+                    format!("{dir}/tests/resources/sample.jar"),
+                    format!("{dir}/tests/resources"),
+                ],
+            },
+            DuplicateClass {
+                class_name: "rjvm/NumericTypes".to_string(),
+                entries: vec![
+                    format!("{dir}/tests/resources/sample.jar"),
+                    format!("{dir}/tests/resources"),
+                ],
+            },
+        ];
+        assert_eq!(
+            expected,
+            class_path
+                .find_duplicate_classes()
+                .expect("should be able to scan the classpath")
+        );
+        // Not in strict mode, so nothing is scanned when adding entries
+        assert!(class_path.duplicate_classes().is_empty());
+    }
+
+    #[test]
+    fn strict_mode_scans_for_duplicate_classes_when_adding_entries() {
+        let dir = env!("CARGO_MANIFEST_DIR");
+        let mut class_path: ClassPath = Default::default();
+        class_path.set_strict(true);
+        class_path
+            .push(&format!("{dir}/tests/resources/sample.jar"))
+            .expect("should be able to parse classpath");
+        assert!(class_path.duplicate_classes().is_empty());
+
+        class_path
+            .push(&format!("{dir}/tests/resources"))
+            .expect("should be able to parse classpath");
+        let duplicate_names: Vec<&str> = class_path
+            .duplicate_classes()
+            .iter()
+            .map(|duplicate| duplicate.class_name.as_str())
+            .collect();
+        assert_eq!(
+            vec!["rjvm/ControlFlow", "rjvm/NumericTypes"],
+            duplicate_names
+        );
+        assert_can_find_class(&class_path, "rjvm/NumericTypes");
+    }
+
+    #[test]
+    fn can_add_custom_entries() {
+        let dir = env!("CARGO_MANIFEST_DIR");
+        let class_bytes = std::fs::read(format!("{dir}/tests/resources/rjvm/SimpleMain.class"))
+            .expect("should be able to read the class file");
+        let mut class_path: ClassPath = Default::default();
+        class_path.set_strict(true);
+        class_path
+            .push(&format!("{dir}/tests/resources/sample.jar"))
+            .expect("should be able to parse classpath");
+        class_path
+            .push_entry(Box::new(InMemoryClassPathEntry {
+                files: HashMap::from([
+                    ("rjvm/SimpleMain.class".to_string(), class_bytes),
+                    ("rjvm/data.txt".to_string(), b"data".to_vec()),
+                ]),
+            }))
+            .expect("should be able to add the entry");
+
+        assert_can_find_class(&class_path, "rjvm/NumericTypes"); // From jar
+        assert_can_find_class(&class_path, "rjvm/SimpleMain"); // From the custom entry
+        assert_eq!(
+            Some(b"data".to_vec()),
+            class_path
+                .resolve_resource("rjvm/data.txt")
+                .expect("should not have had any errors")
+        );
+        assert!(class_path.duplicate_classes().is_empty());
+    }
+
     fn assert_can_find_class(class_path: &ClassPath, class_name: &str) {
-        let buf = class_path
+        let (buf, _) = class_path
             .resolve(class_name)
             .expect("should not have had any errors")
             .expect("should have been able to find file");
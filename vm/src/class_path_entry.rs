@@ -1,9 +1,29 @@
 use std::{error::Error, fmt, fmt::Formatter};
 
+use crate::jar_manifest::JarManifest;
+
 /// Models an entry in the class path, i.e. a single Jar or directory
 pub trait ClassPathEntry: fmt::Debug {
+    /// Reads the `.class` file of the given class, if the entry contains it
     // TODO: should `class_name` be a newtype?
-    fn resolve(&self, class_name: &str) -> Result<Option<Vec<u8>>, ClassLoadingError>;
+    fn resolve(&self, class_name: &str) -> Result<Option<Vec<u8>>, ClassLoadingError> {
+        self.resolve_resource(&format!("{class_name}.class"))
+    }
+
+    /// Reads an arbitrary file of the entry, such as a `.properties` file. The path is
+    /// relative to the root of the entry and uses `/` as separator, e.g. `rjvm/data.txt`
+    fn resolve_resource(&self, path: &str) -> Result<Option<Vec<u8>>, ClassLoadingError>;
+
+    /// A human readable description of the entry, e.g. its path, used in diagnostics
+    fn description(&self) -> String;
+
+    /// The manifest of the entry, which only jars can have
+    fn manifest(&self) -> Option<&JarManifest> {
+        None
+    }
+
+    /// Lists the names of all the classes provided by this entry, in no particular order
+    fn class_names(&self) -> Result<Vec<String>, ClassLoadingError>;
 }
 
 /// Error returned when loading a class does not work
@@ -55,4 +75,11 @@ pub mod tests {
             .expect("should not have had any errors")
             .is_none());
     }
+
+    pub fn assert_cannot_find_resource(entry: &impl ClassPathEntry, path: &str) {
+        assert!(entry
+            .resolve_resource(path)
+            .expect("should not have had any errors")
+            .is_none());
+    }
 }
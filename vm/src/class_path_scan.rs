@@ -0,0 +1,37 @@
+use std::{fmt, fmt::Formatter, rc::Rc};
+
+/// Progress of the scan of a class path entry, reported while the entry is opened. Opening a
+/// large jar requires reading its whole central directory, which can take a while on slow
+/// storage, so embedders can use these to show some progress rather than appearing frozen.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClassPathScanProgress {
+    /// The description of the entry being scanned, i.e. its path
+    pub entry: String,
+    /// The number of files found in the entry. Only known once the whole entry has been
+    /// scanned, so it is zero for the intermediate reports.
+    pub entries_scanned: usize,
+    /// The number of bytes read from the entry so far
+    pub bytes_processed: u64,
+    /// Whether this is the last report for the entry
+    pub done: bool,
+}
+
+/// A callback invoked with the progress of class path scans; see [ClassPathScanProgress]
+#[derive(Clone)]
+pub struct ScanProgressListener(Rc<dyn Fn(&ClassPathScanProgress)>);
+
+impl ScanProgressListener {
+    pub fn new(listener: impl Fn(&ClassPathScanProgress) + 'static) -> Self {
+        Self(Rc::new(listener))
+    }
+
+    pub(crate) fn notify(&self, progress: &ClassPathScanProgress) {
+        (self.0)(progress)
+    }
+}
+
+impl fmt::Debug for ScanProgressListener {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str("ScanProgressListener")
+    }
+}
@@ -0,0 +1,86 @@
+use std::{num::NonZeroUsize, thread};
+
+use rjvm_reader::{class_file::ClassFile, class_reader_error::ClassReaderError};
+
+use crate::class::ClassFileBytes;
+
+/// The result of parsing a class file on a worker thread, together with the bytes it borrows
+pub(crate) type ParsedClass<'a> = (
+    String,
+    Result<ClassFile<'a>, ClassReaderError>,
+    ClassFileBytes,
+);
+
+/// Parses the given class files on up to `threads` scoped threads. Each thread parses a
+/// contiguous chunk of the input, and the results are returned in the same order as the input,
+/// so that the outcome does not depend on how the threads were scheduled.
+///
+/// # Safety
+///
+/// Just like with [ClassFileBytes::parse], each class file must not outlive the bytes
+/// returned with it.
+pub(crate) unsafe fn parse_classes<'a>(
+    class_files: Vec<(String, ClassFileBytes)>,
+    threads: NonZeroUsize,
+) -> Vec<ParsedClass<'a>> {
+    if threads.get() == 1 || class_files.len() <= 1 {
+        return class_files.into_iter().map(parse).collect();
+    }
+
+    let chunk_size = class_files.len().div_ceil(threads.get());
+    let mut chunks: Vec<Vec<(String, ClassFileBytes)>> = Vec::new();
+    let mut class_files = class_files.into_iter().peekable();
+    while class_files.peek().is_some() {
+        chunks.push(class_files.by_ref().take(chunk_size).collect());
+    }
+
+    thread::scope(|scope| {
+        let workers: Vec<_> = chunks
+            .into_iter()
+            .map(|chunk| scope.spawn(move || chunk.into_iter().map(parse).collect::<Vec<_>>()))
+            .collect();
+        workers
+            .into_iter()
+            .flat_map(|worker| worker.join().expect("class parsing thread panicked"))
+            .collect()
+    })
+}
+
+fn parse<'a>((name, bytes): (String, ClassFileBytes)) -> ParsedClass<'a> {
+    // SAFETY: the bytes are returned together with the class file, see `parse_classes`
+    let class_file = unsafe { bytes.parse() };
+    (name, class_file, bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZeroUsize;
+
+    use crate::{class::ClassFileBytes, class_prefetch::parse_classes};
+
+    #[test]
+    fn parses_classes_in_input_order() {
+        let valid: &[u8] = include_bytes!("../tests/resources/rjvm/SimpleMain.class");
+        let invalid: &[u8] = &[0xCA, 0xFE];
+        let class_files: Vec<(String, ClassFileBytes)> = (0..10)
+            .map(|i| {
+                let bytes = if i % 3 == 0 { invalid } else { valid };
+                (format!("class{i}"), ClassFileBytes::new(bytes.to_vec()))
+            })
+            .collect();
+
+        let parsed = unsafe { parse_classes(class_files, NonZeroUsize::new(4).unwrap()) };
+
+        assert_eq!(10, parsed.len());
+        for (i, (name, result, _)) in parsed.iter().enumerate() {
+            assert_eq!(&format!("class{i}"), name);
+            match result {
+                Ok(class_file) => {
+                    assert!(i % 3 != 0);
+                    assert_eq!("rjvm/SimpleMain", class_file.name);
+                }
+                Err(_) => assert_eq!(0, i % 3),
+            }
+        }
+    }
+}
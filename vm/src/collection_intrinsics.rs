@@ -0,0 +1,523 @@
+use std::collections::{HashMap, HashSet};
+
+use log::info;
+
+use rjvm_reader::{
+    field_flags::FieldFlags,
+    type_conversion::{CheckedNarrowing, ToUsizeSafe},
+};
+
+use crate::{
+    abstract_object::AbstractObject,
+    call_frame::MethodCallResult,
+    call_stack::CallStack,
+    class::{Class, ClassId, ClassRef},
+    exceptions::MethodCallFailed,
+    gc::Ephemeron,
+    java_objects_creation::throw_exception,
+    native_methods_registry::NativeMethodsRegistry,
+    object_side_table::ObjectSideTable,
+    value::{expect_int_at, Value},
+    vm::Vm,
+    vm_error::VmError,
+};
+
+const ARRAY_LIST: &str = "java/util/ArrayList";
+const HASH_MAP: &str = "java/util/HashMap";
+
+/// Minimal class libraries can let the vm implement `java.util.ArrayList` and
+/// `java.util.HashMap` with a Rust `Vec` and `HashMap`, which is a lot faster than
+/// interpreting their bytecode. The data of each collection is stored outside the java heap,
+/// in side tables that are kept in sync with the gc.
+///
+/// This only works if the java classes do not keep any state in their own fields, since
+/// the methods that are not intrinsified would not see the data stored by the vm. Thus,
+/// a class is intrinsified only if it declares no instance fields, unlike the ones of any real
+/// class library such as our rt.jar. The decision is taken for each class when it is loaded,
+/// before any of its instances exists, and never changes afterwards: a class that
+/// switched from its bytecode to the intrinsics would not see the data of its existing
+/// instances, and vice versa.
+#[derive(Debug, Default)]
+pub(crate) struct CollectionIntrinsics<'a> {
+    /// Whether the classes loaded from now on are intrinsified
+    enabled: bool,
+    intrinsified_classes: HashSet<ClassId>,
+    lists: ObjectSideTable<'a, Vec<Value<'a>>>,
+    maps: ObjectSideTable<'a, IntrinsicMap<'a>>,
+    /// The map operations that are invoking `hashCode` or `equals`, which could run a gc
+    lookups_in_progress: Vec<MapLookup<'a>>,
+}
+
+/// The data of an intrinsified `HashMap`: entries are grouped by the `hashCode` of the key,
+/// and then compared with `equals`
+#[derive(Debug, Default)]
+struct IntrinsicMap<'a> {
+    buckets: HashMap<i32, Vec<(Value<'a>, Value<'a>)>>,
+    len: usize,
+}
+
+/// The objects used by a map operation while it looks up its key: they are kept alive by
+/// the gc, which also updates them when it moves them
+#[derive(Debug)]
+struct MapLookup<'a> {
+    map: AbstractObject<'a>,
+    key: Value<'a>,
+    /// The value stored by `put`
+    value: Value<'a>,
+}
+
+impl<'a> CollectionIntrinsics<'a> {
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    pub fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+    }
+
+    /// Whether the methods of the given class are implemented by the intrinsics
+    pub fn is_intrinsified(&self, class: &Class<'a>) -> bool {
+        self.intrinsified_classes.contains(&class.id)
+    }
+
+    /// Decides whether the given class, which was just loaded, is intrinsified
+    pub fn on_class_loaded(&mut self, class: ClassRef<'a>) {
+        if !self.enabled || (class.name != ARRAY_LIST && class.name != HASH_MAP) {
+            return;
+        }
+        if class
+            .fields
+            .iter()
+            .any(|field| !field.flags.contains(FieldFlags::STATIC))
+        {
+            info!(
+                "class {} declares instance fields, not using the collection intrinsics",
+                class.name
+            );
+        } else {
+            self.intrinsified_classes.insert(class.id);
+        }
+    }
+
+    /// The objects stored in a collection must be kept alive by the gc, but only as long as
+    /// the collection itself is, otherwise a collection containing itself would never die
+    pub fn ephemerons(&mut self) -> impl Iterator<Item = Ephemeron<'a>> {
+        let mut ephemerons: Vec<_> = self
+            .lists
+            .entries_mut()
+            .map(|(key, list)| Ephemeron {
+                key,
+                values: list.iter_mut().filter_map(value_as_root).collect(),
+            })
+            .collect();
+        ephemerons.extend(self.maps.entries_mut().map(|(key, map)| {
+            Ephemeron {
+                key,
+                values: map
+                    .buckets
+                    .values_mut()
+                    .flatten()
+                    .flat_map(|(key, value)| {
+                        value_as_root(key).into_iter().chain(value_as_root(value))
+                    })
+                    .collect(),
+            }
+        }));
+        ephemerons.into_iter()
+    }
+
+    /// The maps and the keys of the lookups in progress
+    pub fn gc_roots(&mut self) -> impl Iterator<Item = *mut AbstractObject<'a>> + '_ {
+        self.lookups_in_progress.iter_mut().flat_map(|lookup| {
+            std::iter::once(&mut lookup.map as *mut AbstractObject<'a>)
+                .chain(value_as_root(&mut lookup.key))
+                .chain(value_as_root(&mut lookup.value))
+        })
+    }
+
+    /// The collections themselves, instead, are kept alive only by the java objects
+    pub fn weak_roots(&mut self) -> impl Iterator<Item = *mut Option<AbstractObject<'a>>> {
+        let mut roots: Vec<_> = self.lists.weak_roots().collect();
+        roots.extend(self.maps.weak_roots());
+        roots.into_iter()
+    }
+
+    pub fn after_garbage_collection(&mut self) {
+        self.lists.after_garbage_collection();
+        self.maps.after_garbage_collection();
+    }
+}
+
+fn value_as_root<'a>(value: &mut Value<'a>) -> Option<*mut AbstractObject<'a>> {
+    match value {
+        Value::Object(object) => Some(object as *mut AbstractObject<'a>),
+        _ => None,
+    }
+}
+
+/// Registers the intrinsics for `ArrayList` and `HashMap`. Note that the `modCount`
+/// field of `AbstractList` is not updated, so iterators cannot detect concurrent modifications.
+pub(crate) fn register_collection_intrinsics(registry: &mut NativeMethodsRegistry) {
+    registry.register_collection_intrinsic(ARRAY_LIST, "<init>", "()V", |vm, _, receiver, _| {
+        let receiver = expect_receiver(receiver)?;
+        vm.collection_intrinsics.lists.insert(&receiver, Vec::new());
+        Ok(None)
+    });
+    registry.register_collection_intrinsic(
+        ARRAY_LIST,
+        "<init>",
+        "(I)V",
+        |vm, stack, receiver, args| {
+            let receiver = expect_receiver(receiver)?;
+            let capacity = expect_int_at(&args, 0)?;
+            if capacity < 0 {
+                return throw_exception(
+                    vm,
+                    stack,
+                    "java/lang/IllegalArgumentException",
+                    &format!("Illegal Capacity: {capacity}"),
+                );
+            }
+            vm.collection_intrinsics
+                .lists
+                .insert(&receiver, Vec::with_capacity(capacity.into_usize_safe()));
+            Ok(None)
+        },
+    );
+    registry.register_collection_intrinsic(ARRAY_LIST, "size", "()I", |vm, _, receiver, _| {
+        let size = with_list(vm, receiver, |list| list.len())?;
+        Ok(Some(Value::Int(size.narrow()?)))
+    });
+    registry.register_collection_intrinsic(ARRAY_LIST, "isEmpty", "()Z", |vm, _, receiver, _| {
+        let is_empty = with_list(vm, receiver, |list| list.is_empty())?;
+        Ok(Some(Value::Int(is_empty.into())))
+    });
+    registry.register_collection_intrinsic(
+        ARRAY_LIST,
+        "get",
+        "(I)Ljava/lang/Object;",
+        |vm, stack, receiver, args| {
+            let index = expect_int_at(&args, 0)?;
+            let element = with_list(vm, receiver, |list| {
+                check_index(index, list.len()).map(|index| list[index].clone())
+            })?;
+            return_or_throw_index_out_of_bounds(vm, stack, element)
+        },
+    );
+    registry.register_collection_intrinsic(
+        ARRAY_LIST,
+        "set",
+        "(ILjava/lang/Object;)Ljava/lang/Object;",
+        |vm, stack, receiver, args| {
+            let index = expect_int_at(&args, 0)?;
+            let element = expect_value_at(&args, 1)?;
+            let previous = with_list(vm, receiver, |list| {
+                check_index(index, list.len())
+                    .map(|index| std::mem::replace(&mut list[index], element))
+            })?;
+            return_or_throw_index_out_of_bounds(vm, stack, previous)
+        },
+    );
+    registry.register_collection_intrinsic(
+        ARRAY_LIST,
+        "add",
+        "(Ljava/lang/Object;)Z",
+        |vm, _, receiver, args| {
+            let element = expect_value_at(&args, 0)?;
+            with_list(vm, receiver, |list| list.push(element))?;
+            Ok(Some(Value::Int(1)))
+        },
+    );
+    registry.register_collection_intrinsic(
+        ARRAY_LIST,
+        "add",
+        "(ILjava/lang/Object;)V",
+        |vm, stack, receiver, args| {
+            let index = expect_int_at(&args, 0)?;
+            let element = expect_value_at(&args, 1)?;
+            let inserted = with_list(vm, receiver, |list| {
+                check_index(index, list.len() + 1).map(|index| list.insert(index, element))
+            })?;
+            return_or_throw_index_out_of_bounds(vm, stack, inserted.map(|_| Value::Null))?;
+            Ok(None)
+        },
+    );
+    registry.register_collection_intrinsic(
+        ARRAY_LIST,
+        "remove",
+        "(I)Ljava/lang/Object;",
+        |vm, stack, receiver, args| {
+            let index = expect_int_at(&args, 0)?;
+            let removed = with_list(vm, receiver, |list| {
+                check_index(index, list.len()).map(|index| list.remove(index))
+            })?;
+            return_or_throw_index_out_of_bounds(vm, stack, removed)
+        },
+    );
+    registry.register_collection_intrinsic(ARRAY_LIST, "clear", "()V", |vm, _, receiver, _| {
+        with_list(vm, receiver, |list| list.clear())?;
+        Ok(None)
+    });
+
+    registry.register_collection_intrinsic(HASH_MAP, "<init>", "()V", |vm, _, receiver, _| {
+        let receiver = expect_receiver(receiver)?;
+        vm.collection_intrinsics
+            .maps
+            .insert(&receiver, IntrinsicMap::default());
+        Ok(None)
+    });
+    registry.register_collection_intrinsic(
+        HASH_MAP,
+        "<init>",
+        "(I)V",
+        |vm, stack, receiver, args| {
+            let receiver = expect_receiver(receiver)?;
+            let capacity = expect_int_at(&args, 0)?;
+            if capacity < 0 {
+                return throw_exception(
+                    vm,
+                    stack,
+                    "java/lang/IllegalArgumentException",
+                    &format!("Illegal initial capacity: {capacity}"),
+                );
+            }
+            vm.collection_intrinsics
+                .maps
+                .insert(&receiver, IntrinsicMap::default());
+            Ok(None)
+        },
+    );
+    registry.register_collection_intrinsic(HASH_MAP, "size", "()I", |vm, _, receiver, _| {
+        let receiver = expect_receiver(receiver)?;
+        let size = get_map(vm, &receiver).len;
+        Ok(Some(Value::Int(size.narrow()?)))
+    });
+    registry.register_collection_intrinsic(HASH_MAP, "isEmpty", "()Z", |vm, _, receiver, _| {
+        let receiver = expect_receiver(receiver)?;
+        let is_empty = get_map(vm, &receiver).len == 0;
+        Ok(Some(Value::Int(is_empty.into())))
+    });
+    registry.register_collection_intrinsic(
+        HASH_MAP,
+        "get",
+        "(Ljava/lang/Object;)Ljava/lang/Object;",
+        |vm, stack, receiver, args| {
+            let lookup = new_lookup(receiver, &args, Value::Null)?;
+            let (lookup, hash, index) = find_map_entry(vm, stack, lookup)?;
+            let value = index.map(|index| get_map(vm, &lookup.map).buckets[&hash][index].1.clone());
+            Ok(Some(value.unwrap_or(Value::Null)))
+        },
+    );
+    registry.register_collection_intrinsic(
+        HASH_MAP,
+        "containsKey",
+        "(Ljava/lang/Object;)Z",
+        |vm, stack, receiver, args| {
+            let lookup = new_lookup(receiver, &args, Value::Null)?;
+            let (_, _, index) = find_map_entry(vm, stack, lookup)?;
+            Ok(Some(Value::Int(index.is_some().into())))
+        },
+    );
+    registry.register_collection_intrinsic(
+        HASH_MAP,
+        "put",
+        "(Ljava/lang/Object;Ljava/lang/Object;)Ljava/lang/Object;",
+        |vm, stack, receiver, args| {
+            let value = expect_value_at(&args, 1)?;
+            let lookup = new_lookup(receiver, &args, value)?;
+            let (lookup, hash, index) = find_map_entry(vm, stack, lookup)?;
+            let map = get_map(vm, &lookup.map);
+            let bucket = map.buckets.entry(hash).or_default();
+            let previous = match index {
+                Some(index) => std::mem::replace(&mut bucket[index].1, lookup.value),
+                None => {
+                    bucket.push((lookup.key, lookup.value));
+                    map.len += 1;
+                    Value::Null
+                }
+            };
+            Ok(Some(previous))
+        },
+    );
+    registry.register_collection_intrinsic(
+        HASH_MAP,
+        "remove",
+        "(Ljava/lang/Object;)Ljava/lang/Object;",
+        |vm, stack, receiver, args| {
+            let lookup = new_lookup(receiver, &args, Value::Null)?;
+            let (lookup, hash, index) = find_map_entry(vm, stack, lookup)?;
+            let previous = match index {
+                Some(index) => {
+                    let map = get_map(vm, &lookup.map);
+                    map.len -= 1;
+                    let bucket = map.buckets.entry(hash).or_default();
+                    bucket.remove(index).1
+                }
+                None => Value::Null,
+            };
+            Ok(Some(previous))
+        },
+    );
+    registry.register_collection_intrinsic(HASH_MAP, "clear", "()V", |vm, _, receiver, _| {
+        let receiver = expect_receiver(receiver)?;
+        *get_map(vm, &receiver) = IntrinsicMap::default();
+        Ok(None)
+    });
+}
+
+fn expect_receiver(receiver: Option<AbstractObject>) -> Result<AbstractObject, VmError> {
+    receiver.ok_or(VmError::NullPointerException)
+}
+
+fn expect_value_at<'a>(args: &[Value<'a>], index: usize) -> Result<Value<'a>, VmError> {
+    args.get(index).cloned().ok_or(VmError::ValidationException)
+}
+
+fn with_list<'a, T>(
+    vm: &mut Vm<'a>,
+    receiver: Option<AbstractObject<'a>>,
+    callback: impl FnOnce(&mut Vec<Value<'a>>) -> T,
+) -> Result<T, VmError> {
+    let receiver = expect_receiver(receiver)?;
+    let list = vm
+        .collection_intrinsics
+        .lists
+        .get_or_insert_with(&receiver, Vec::new);
+    Ok(callback(list))
+}
+
+fn check_index(index: i32, len: usize) -> Result<usize, String> {
+    match usize::try_from(index) {
+        Ok(checked_index) if checked_index < len => Ok(checked_index),
+        _ => Err(format!("Index: {index}, Size: {len}")),
+    }
+}
+
+fn return_or_throw_index_out_of_bounds<'a>(
+    vm: &mut Vm<'a>,
+    call_stack: &mut CallStack<'a>,
+    result: Result<Value<'a>, String>,
+) -> MethodCallResult<'a> {
+    match result {
+        Ok(value) => Ok(Some(value)),
+        Err(message) => throw_exception(
+            vm,
+            call_stack,
+            "java/lang/IndexOutOfBoundsException",
+            &message,
+        ),
+    }
+}
+
+fn get_map<'a, 'b>(vm: &'b mut Vm<'a>, receiver: &AbstractObject<'a>) -> &'b mut IntrinsicMap<'a> {
+    vm.collection_intrinsics
+        .maps
+        .get_or_insert_with(receiver, IntrinsicMap::default)
+}
+
+fn new_lookup<'a>(
+    receiver: Option<AbstractObject<'a>>,
+    args: &[Value<'a>],
+    value: Value<'a>,
+) -> Result<MapLookup<'a>, VmError> {
+    Ok(MapLookup {
+        map: expect_receiver(receiver)?,
+        key: expect_value_at(args, 0)?,
+        value,
+    })
+}
+
+/// Finds the entry with the key of the given lookup, returning its hash and its index in the
+/// bucket. Invoking `hashCode` and `equals` might trigger a gc, which moves the objects: thus,
+/// the lookup is kept among the gc roots meanwhile, and we re-read it and the bucket at each
+/// step. The lookup is returned with the current addresses of its objects.
+fn find_map_entry<'a>(
+    vm: &mut Vm<'a>,
+    call_stack: &mut CallStack<'a>,
+    lookup: MapLookup<'a>,
+) -> Result<(MapLookup<'a>, i32, Option<usize>), MethodCallFailed<'a>> {
+    let lookups = &mut vm.collection_intrinsics.lookups_in_progress;
+    let lookup_index = lookups.len();
+    lookups.push(lookup);
+    let result = find_entry_of_lookup_in_progress(vm, call_stack, lookup_index);
+    let lookup = vm
+        .collection_intrinsics
+        .lookups_in_progress
+        .pop()
+        .expect("the lookups should be completed in reverse order");
+    let (hash, index) = result?;
+    Ok((lookup, hash, index))
+}
+
+fn find_entry_of_lookup_in_progress<'a>(
+    vm: &mut Vm<'a>,
+    call_stack: &mut CallStack<'a>,
+    lookup_index: usize,
+) -> Result<(i32, Option<usize>), MethodCallFailed<'a>> {
+    let key = vm.collection_intrinsics.lookups_in_progress[lookup_index]
+        .key
+        .clone();
+    let hash = hash_code(vm, call_stack, &key)?;
+    for index in 0.. {
+        let lookup = &vm.collection_intrinsics.lookups_in_progress[lookup_index];
+        let (map, key) = (lookup.map.clone(), lookup.key.clone());
+        let candidate = match get_map(vm, &map)
+            .buckets
+            .get(&hash)
+            .and_then(|bucket| bucket.get(index))
+        {
+            Some((candidate, _)) => candidate.clone(),
+            None => break,
+        };
+        if keys_are_equal(vm, call_stack, &key, &candidate)? {
+            return Ok((hash, Some(index)));
+        }
+    }
+    Ok((hash, None))
+}
+
+fn hash_code<'a>(
+    vm: &mut Vm<'a>,
+    call_stack: &mut CallStack<'a>,
+    key: &Value<'a>,
+) -> Result<i32, MethodCallFailed<'a>> {
+    match key {
+        Value::Null => Ok(0),
+        Value::Object(object) => {
+            match vm.invoke_virtual(call_stack, object.clone(), "hashCode", "()I", vec![])? {
+                Some(Value::Int(hash)) => Ok(hash),
+                _ => Err(MethodCallFailed::InternalError(
+                    VmError::ValidationException,
+                )),
+            }
+        }
+        _ => Err(MethodCallFailed::InternalError(
+            VmError::ValidationException,
+        )),
+    }
+}
+
+fn keys_are_equal<'a>(
+    vm: &mut Vm<'a>,
+    call_stack: &mut CallStack<'a>,
+    key: &Value<'a>,
+    candidate: &Value<'a>,
+) -> Result<bool, MethodCallFailed<'a>> {
+    match (key, candidate) {
+        (Value::Null, Value::Null) => Ok(true),
+        (Value::Object(key), Value::Object(candidate)) => {
+            if key.is_same_as(candidate) {
+                return Ok(true);
+            }
+            let result = vm.invoke_virtual(
+                call_stack,
+                key.clone(),
+                "equals",
+                "(Ljava/lang/Object;)Z",
+                vec![Value::Object(candidate.clone())],
+            )?;
+            Ok(result == Some(Value::Int(1)))
+        }
+        _ => Ok(false),
+    }
+}
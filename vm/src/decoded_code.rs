@@ -0,0 +1,87 @@
+use rjvm_reader::{
+    class_reader_error::ClassReaderError,
+    instruction::Instruction,
+    program_counter::ProgramCounter,
+    type_conversion::{CheckedNarrowing, ToUsizeSafe},
+};
+
+/// The bytecode of a method, decoded once into its instructions, so that executing it does
+/// not parse the raw bytes of every instruction again each time it is reached.
+#[derive(Debug)]
+pub(crate) struct DecodedCode {
+    /// The instructions, in order, each with the address of the following one
+    instructions: Vec<(Instruction, ProgramCounter)>,
+    /// The index in `instructions` of the instruction starting at each address of the code,
+    /// or `None` for the addresses of the operands
+    indexes: Vec<Option<u32>>,
+}
+
+impl DecodedCode {
+    pub fn decode(code: &[u8]) -> Result<Self, ClassReaderError> {
+        let mut instructions = Vec::new();
+        let mut indexes = vec![None; code.len()];
+        let mut address = 0;
+        while address < code.len() {
+            let (instruction, next_address) = Instruction::parse(code, address)?;
+            indexes[address] = Some(Self::narrow(instructions.len())?);
+            instructions.push((instruction, ProgramCounter(Self::narrow(next_address)?)));
+            address = next_address;
+        }
+        Ok(Self {
+            instructions,
+            indexes,
+        })
+    }
+
+    fn narrow(value: usize) -> Result<u32, ClassReaderError> {
+        value
+            .narrow()
+            .map_err(|err| ClassReaderError::invalid_class_data(err.to_string()))
+    }
+
+    /// The instruction starting at the given address, together with the address of the
+    /// following one, or `None` if no instruction starts there
+    pub fn instruction_at(&self, pc: ProgramCounter) -> Option<(Instruction, ProgramCounter)> {
+        let index = (*self.indexes.get(pc.0.into_usize_safe())?)?;
+        self.instructions.get(index.into_usize_safe()).copied()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rjvm_reader::{instruction::Instruction, program_counter::ProgramCounter};
+
+    use crate::decoded_code::DecodedCode;
+
+    #[test]
+    fn can_find_instructions_by_address() {
+        // bipush 42; istore_1; iload_1; ireturn
+        let decoded_code = DecodedCode::decode(&[0x10, 42, 0x3c, 0x1b, 0xac]).unwrap();
+
+        assert_eq!(
+            Some((Instruction::Bipush(42), ProgramCounter(2))),
+            decoded_code.instruction_at(ProgramCounter(0))
+        );
+        assert_eq!(
+            Some((Instruction::Istore_1, ProgramCounter(3))),
+            decoded_code.instruction_at(ProgramCounter(2))
+        );
+        assert_eq!(
+            Some((Instruction::Ireturn, ProgramCounter(5))),
+            decoded_code.instruction_at(ProgramCounter(4))
+        );
+    }
+
+    #[test]
+    fn no_instruction_starts_at_operands_or_past_the_end() {
+        let decoded_code = DecodedCode::decode(&[0x10, 42, 0xac]).unwrap();
+
+        assert_eq!(None, decoded_code.instruction_at(ProgramCounter(1)));
+        assert_eq!(None, decoded_code.instruction_at(ProgramCounter(3)));
+    }
+
+    #[test]
+    fn truncated_code_is_rejected() {
+        assert!(DecodedCode::decode(&[0x11, 1]).is_err());
+    }
+}
@@ -0,0 +1,63 @@
+use rjvm_reader::field_type::BaseType;
+
+use crate::{
+    abstract_object::AbstractObject,
+    array::Array,
+    array_entry_type::ArrayEntryType,
+    java_objects_creation::{extract_str_from_java_lang_string, new_java_lang_string_object},
+    native_methods_registry::NativeMethodsRegistry,
+    value::{expect_abstract_object_at, Value},
+    vm::Vm,
+    vm_error::VmError,
+};
+
+const PROCESS_ENVIRONMENT: &str = "java/lang/ProcessEnvironment";
+const SYSTEM: &str = "java/lang/System";
+
+/// The environment variables seen by the java code are those set by the embedder, together
+/// with those of the host exposed by the vm's [JvmIo](crate::io::JvmIo).
+/// Note that `System.getenv()` needs the initialization of `ProcessEnvironment`, which our
+/// rt.jar cannot run since it uses `sun.misc.Unsafe`; only the lookup of single variables
+/// works with it.
+pub(crate) fn register_environment_methods(registry: &mut NativeMethodsRegistry) {
+    registry.register(PROCESS_ENVIRONMENT, "environ", "()[[B", |vm, _, _, _| {
+        Ok(Some(Value::Object(environ(vm)?)))
+    });
+
+    // Looking up a single variable does not need the maps that the initialization of
+    // `ProcessEnvironment` builds from `environ`, so we skip it altogether
+    registry.register_intrinsic(
+        SYSTEM,
+        "getenv",
+        "(Ljava/lang/String;)Ljava/lang/String;",
+        |vm, stack, _, args| {
+            let name = expect_abstract_object_at(&args, 0)?;
+            let name = extract_str_from_java_lang_string(vm, &name)?;
+            match vm.visible_environment_variable(&name) {
+                Some(value) => {
+                    let value = new_java_lang_string_object(vm, stack, &value)?;
+                    Ok(Some(Value::Object(value)))
+                }
+                None => Ok(Some(Value::Null)),
+            }
+        },
+    );
+}
+
+/// Returns a `byte[][]` with the name and value of each variable, one after the other,
+/// encoded in UTF-8
+fn environ<'a>(vm: &mut Vm<'a>) -> Result<AbstractObject<'a>, VmError> {
+    let environment = vm.visible_environment();
+    let scope = vm.handle_scope();
+    let result = scope.handle(vm.new_array(
+        ArrayEntryType::array_of(&ArrayEntryType::Base(BaseType::Byte)),
+        environment.len() * 2,
+    )?);
+    let strings = environment.iter().flat_map(|(name, value)| [name, value]);
+    for (index, string) in strings.enumerate() {
+        let bytes = vm.new_byte_array(string.as_bytes())?;
+        vm.write_barrier(&Value::Object(bytes.clone()));
+        result.get().set_element(index, Value::Object(bytes))?;
+    }
+    Ok(result.get())
+}
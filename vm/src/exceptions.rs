@@ -1,3 +1,6 @@
+use rjvm_reader::type_conversion::NarrowingError;
+use thiserror::Error;
+
 use crate::{abstract_object::AbstractObject, value_stack::ValueStackError, vm_error::VmError};
 
 /// Models the fact that a method execution has failed
@@ -5,6 +8,10 @@ use crate::{abstract_object::AbstractObject, value_stack::ValueStackError, vm_er
 pub enum MethodCallFailed<'a> {
     InternalError(VmError),
     ExceptionThrown(JavaException<'a>),
+    /// The program has terminated the vm with the given status, e.g. via `System.exit`.
+    /// It is not an error: all the frames are unwound, without running any handler, up to
+    /// the embedder.
+    VmExit(i32),
 }
 
 impl<'a> From<VmError> for MethodCallFailed<'a> {
@@ -13,6 +20,12 @@ impl<'a> From<VmError> for MethodCallFailed<'a> {
     }
 }
 
+impl<'a> From<NarrowingError> for MethodCallFailed<'a> {
+    fn from(value: NarrowingError) -> Self {
+        Self::InternalError(value.into())
+    }
+}
+
 // TODO: need to remove this eventually and manage it with real exceptions
 impl<'a> From<ValueStackError> for MethodCallFailed<'a> {
     fn from(_: ValueStackError) -> Self {
@@ -23,3 +36,28 @@ impl<'a> From<ValueStackError> for MethodCallFailed<'a> {
 /// Newtype that wraps a java exception
 #[derive(Debug, PartialEq)]
 pub struct JavaException<'a>(pub AbstractObject<'a>);
+
+/// Models the failure of a program run via [Vm::run_main](crate::vm::Vm::run_main)
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum RunMainError {
+    /// A java exception escaped from `main`
+    #[error("Exception in thread \"main\" {}", .0.stack_trace.trim_end())]
+    UncaughtException(UncaughtException),
+
+    /// The vm could not run the program, e.g. because the class does not exist
+    #[error(transparent)]
+    VmError(#[from] VmError),
+}
+
+/// A java exception rendered as text, like the `java` launcher prints it when it escapes from
+/// `main`, so that it can outlive the vm
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UncaughtException {
+    /// The name of the class of the exception, e.g. `java.lang.IllegalStateException`
+    pub class_name: String,
+    /// The result of `toString()`, i.e. the class name followed by the message, if any
+    pub description: String,
+    /// The output of `printStackTrace()`, which starts with the description and includes
+    /// the causes
+    pub stack_trace: String,
+}
@@ -0,0 +1,649 @@
+use std::collections::HashMap;
+
+use rjvm_reader::type_conversion::ToUsizeSafe;
+
+use crate::{
+    abstract_object::AbstractObject,
+    array::Array,
+    call_frame::MethodCallResult,
+    call_stack::CallStack,
+    exceptions::MethodCallFailed,
+    io::{FileHandle, OpenOptions},
+    java_objects_creation::{extract_str_from_java_lang_string, throw_exception},
+    native_methods_registry::NativeMethodsRegistry,
+    object_fields::{get_object_field, set_object_field},
+    standard_streams::StandardStream,
+    value::{expect_abstract_object_at, expect_int_at, expect_long_at, Value},
+    vm::Vm,
+    vm_error::VmError,
+};
+
+const FILE_DESCRIPTOR: &str = "java/io/FileDescriptor";
+const FILE_INPUT_STREAM: &str = "java/io/FileInputStream";
+const FILE_OUTPUT_STREAM: &str = "java/io/FileOutputStream";
+const RANDOM_ACCESS_FILE: &str = "java/io/RandomAccessFile";
+
+/// The mode passed by `RandomAccessFile` to its native `open` when the file is writable
+const O_RDWR: i32 = 2;
+
+/// The descriptors below this one are the standard streams
+const FIRST_FILE_DESCRIPTOR: i32 = 3;
+
+/// The files, or the sockets, opened by the java code. Like in the JDK, a `FileDescriptor`
+/// holds an int, which we map to the handle returned by the [JvmIo](crate::io::JvmIo) or
+/// the [JvmNet](crate::io::JvmNet) of the vm. As in Unix, the descriptors 0, 1 and 2 of the
+/// files are the standard input, output and error.
+#[derive(Debug)]
+pub(crate) struct DescriptorTable<H> {
+    handles: HashMap<i32, H>,
+    next_descriptor: i32,
+}
+
+impl<H> Default for DescriptorTable<H> {
+    fn default() -> Self {
+        Self {
+            handles: HashMap::new(),
+            next_descriptor: FIRST_FILE_DESCRIPTOR,
+        }
+    }
+}
+
+impl<H: Copy> DescriptorTable<H> {
+    pub(crate) fn insert(&mut self, handle: H) -> i32 {
+        let descriptor = self.next_descriptor;
+        self.next_descriptor += 1;
+        self.handles.insert(descriptor, handle);
+        descriptor
+    }
+
+    pub(crate) fn get(&self, descriptor: i32) -> Option<H> {
+        self.handles.get(&descriptor).copied()
+    }
+
+    pub(crate) fn remove(&mut self, descriptor: i32) -> Option<H> {
+        self.handles.remove(&descriptor)
+    }
+}
+
+/// What a `FileDescriptor` refers to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Descriptor {
+    StandardInput,
+    StandardOutput(StandardStream),
+    File(FileHandle),
+}
+
+/// Registers the natives of the streams that read and write files. Our rt.jar relies on
+/// `sun.misc.Unsafe`, which we do not support, to count how many streams share a
+/// `FileDescriptor` and to know whether a stream is being finalized: thus, we also replace
+/// the methods that do so with intrinsics. Closing a stream always closes its file, as it
+/// happens in the JDK when the stream is not being finalized.
+pub(crate) fn register_file_io_methods(registry: &mut NativeMethodsRegistry) {
+    for class_name in [
+        FILE_DESCRIPTOR,
+        FILE_INPUT_STREAM,
+        FILE_OUTPUT_STREAM,
+        RANDOM_ACCESS_FILE,
+    ] {
+        registry.register(class_name, "initIDs", "()V", |_, _, _, _| Ok(None));
+    }
+    register_file_descriptor_methods(registry);
+    register_file_input_stream_methods(registry);
+    register_file_output_stream_methods(registry);
+    register_random_access_file_methods(registry);
+}
+
+fn register_file_descriptor_methods(registry: &mut NativeMethodsRegistry) {
+    registry.register_intrinsic(FILE_DESCRIPTOR, "<clinit>", "()V", |vm, stack, _, _| {
+        for (field_name, number) in [("in", 0), ("out", 1), ("err", 2)] {
+            let descriptor = vm.new_object(stack, FILE_DESCRIPTOR)?;
+            set_object_field(vm, &descriptor, "fd", Value::Int(number))?;
+            vm.set_static_field(FILE_DESCRIPTOR, field_name, Value::Object(descriptor))?;
+        }
+        Ok(None)
+    });
+    registry.register_intrinsic(FILE_DESCRIPTOR, "<init>", "()V", |vm, _, receiver, _| {
+        let descriptor = receiver.ok_or(VmError::ValidationException)?;
+        set_object_field(vm, &descriptor, "fd", Value::Int(-1))?;
+        Ok(None)
+    });
+    registry.register_intrinsic(
+        FILE_DESCRIPTOR,
+        "<init>",
+        "(I)V",
+        |vm, _, receiver, args| {
+            let descriptor = receiver.ok_or(VmError::ValidationException)?;
+            set_object_field(vm, &descriptor, "fd", Value::Int(expect_int_at(&args, 0)?))?;
+            Ok(None)
+        },
+    );
+    registry.register_intrinsic(
+        FILE_DESCRIPTOR,
+        "incrementAndGetUseCount",
+        "()I",
+        |_, _, _, _| Ok(Some(Value::Int(1))),
+    );
+    registry.register_intrinsic(
+        FILE_DESCRIPTOR,
+        "decrementAndGetUseCount",
+        "()I",
+        |_, _, _, _| Ok(Some(Value::Int(0))),
+    );
+    registry.register(FILE_DESCRIPTOR, "sync", "()V", |_, _, _, _| Ok(None));
+}
+
+fn register_file_input_stream_methods(registry: &mut NativeMethodsRegistry) {
+    registry.register_intrinsic(FILE_INPUT_STREAM, "<clinit>", "()V", |_, _, _, _| Ok(None));
+    registry.register_intrinsic(
+        FILE_INPUT_STREAM,
+        "finalize",
+        "()V",
+        |vm, _, receiver, _| finalize(vm, receiver),
+    );
+    registry.register(
+        FILE_INPUT_STREAM,
+        "open",
+        "(Ljava/lang/String;)V",
+        |vm, stack, receiver, args| {
+            let options = OpenOptions {
+                read: true,
+                ..Default::default()
+            };
+            open(vm, stack, receiver, &args, options)
+        },
+    );
+    registry.register(
+        FILE_INPUT_STREAM,
+        "read0",
+        "()I",
+        |vm, stack, receiver, _| read_byte(vm, stack, receiver),
+    );
+    registry.register(
+        FILE_INPUT_STREAM,
+        "readBytes",
+        "([BII)I",
+        |vm, stack, receiver, args| read_bytes(vm, stack, receiver, &args),
+    );
+    registry.register(
+        FILE_INPUT_STREAM,
+        "skip",
+        "(J)J",
+        |vm, stack, receiver, args| {
+            let descriptor = resolve_descriptor(vm, stack, receiver)?;
+            let to_skip = expect_long_at(&args, 0)?;
+            let skipped = skip(vm, stack, descriptor, to_skip)?;
+            Ok(Some(Value::Long(skipped)))
+        },
+    );
+    registry.register(
+        FILE_INPUT_STREAM,
+        "available",
+        "()I",
+        |vm, stack, receiver, _| {
+            let available = match resolve_descriptor(vm, stack, receiver)? {
+                Descriptor::File(handle) => {
+                    let length = io_result(vm, stack, vm.io().file_length(handle))?;
+                    let position = io_result(vm, stack, vm.io().file_position(handle))?;
+                    i32::try_from(length.saturating_sub(position)).unwrap_or(i32::MAX)
+                }
+                // We cannot know without blocking, which the JDK allows to report as zero
+                _ => 0,
+            };
+            Ok(Some(Value::Int(available)))
+        },
+    );
+    registry.register(
+        FILE_INPUT_STREAM,
+        "close0",
+        "()V",
+        |vm, stack, receiver, _| close(vm, stack, receiver),
+    );
+}
+
+fn register_file_output_stream_methods(registry: &mut NativeMethodsRegistry) {
+    registry.register_intrinsic(FILE_OUTPUT_STREAM, "<clinit>", "()V", |_, _, _, _| Ok(None));
+    registry.register_intrinsic(
+        FILE_OUTPUT_STREAM,
+        "finalize",
+        "()V",
+        |vm, _, receiver, _| finalize(vm, receiver),
+    );
+    registry.register(
+        FILE_OUTPUT_STREAM,
+        "open",
+        "(Ljava/lang/String;Z)V",
+        |vm, stack, receiver, args| {
+            let append = expect_int_at(&args, 1)? != 0;
+            let options = OpenOptions {
+                write: true,
+                create: true,
+                append,
+                truncate: !append,
+                ..Default::default()
+            };
+            open(vm, stack, receiver, &args, options)
+        },
+    );
+    // The boolean argument says whether the file was opened for appending, which the
+    // JvmIo already knows
+    registry.register(
+        FILE_OUTPUT_STREAM,
+        "write",
+        "(IZ)V",
+        |vm, stack, receiver, args| write_byte(vm, stack, receiver, &args),
+    );
+    registry.register(
+        FILE_OUTPUT_STREAM,
+        "writeBytes",
+        "([BIIZ)V",
+        |vm, stack, receiver, args| write_bytes(vm, stack, receiver, &args),
+    );
+    registry.register(
+        FILE_OUTPUT_STREAM,
+        "close0",
+        "()V",
+        |vm, stack, receiver, _| close(vm, stack, receiver),
+    );
+}
+
+fn register_random_access_file_methods(registry: &mut NativeMethodsRegistry) {
+    registry.register(
+        RANDOM_ACCESS_FILE,
+        "open",
+        "(Ljava/lang/String;I)V",
+        |vm, stack, receiver, args| {
+            let writable = expect_int_at(&args, 1)? & O_RDWR != 0;
+            let options = OpenOptions {
+                read: true,
+                write: writable,
+                create: writable,
+                ..Default::default()
+            };
+            open(vm, stack, receiver, &args, options)
+        },
+    );
+    registry.register(
+        RANDOM_ACCESS_FILE,
+        "read0",
+        "()I",
+        |vm, stack, receiver, _| read_byte(vm, stack, receiver),
+    );
+    registry.register(
+        RANDOM_ACCESS_FILE,
+        "readBytes0",
+        "([BII)I",
+        |vm, stack, receiver, args| read_bytes(vm, stack, receiver, &args),
+    );
+    registry.register(
+        RANDOM_ACCESS_FILE,
+        "write0",
+        "(I)V",
+        |vm, stack, receiver, args| write_byte(vm, stack, receiver, &args),
+    );
+    registry.register(
+        RANDOM_ACCESS_FILE,
+        "writeBytes0",
+        "([BII)V",
+        |vm, stack, receiver, args| write_bytes(vm, stack, receiver, &args),
+    );
+    registry.register(
+        RANDOM_ACCESS_FILE,
+        "getFilePointer",
+        "()J",
+        |vm, stack, receiver, _| {
+            let handle = resolve_file(vm, stack, receiver)?;
+            let position = io_result(vm, stack, vm.io().file_position(handle))?;
+            Ok(Some(Value::Long(
+                i64::try_from(position).unwrap_or(i64::MAX),
+            )))
+        },
+    );
+    registry.register(
+        RANDOM_ACCESS_FILE,
+        "seek",
+        "(J)V",
+        |vm, stack, receiver, args| {
+            let handle = resolve_file(vm, stack, receiver)?;
+            let Ok(position) = u64::try_from(expect_long_at(&args, 0)?) else {
+                return throw_exception(vm, stack, "java/io/IOException", "Negative seek offset");
+            };
+            io_result(vm, stack, vm.io().seek_file(handle, position))?;
+            Ok(None)
+        },
+    );
+    registry.register(
+        RANDOM_ACCESS_FILE,
+        "length",
+        "()J",
+        |vm, stack, receiver, _| {
+            let handle = resolve_file(vm, stack, receiver)?;
+            let length = io_result(vm, stack, vm.io().file_length(handle))?;
+            Ok(Some(Value::Long(i64::try_from(length).unwrap_or(i64::MAX))))
+        },
+    );
+    registry.register(
+        RANDOM_ACCESS_FILE,
+        "setLength",
+        "(J)V",
+        |vm, stack, receiver, args| {
+            let handle = resolve_file(vm, stack, receiver)?;
+            let Ok(length) = u64::try_from(expect_long_at(&args, 0)?) else {
+                return throw_exception(vm, stack, "java/io/IOException", "Negative length");
+            };
+            // Like the JDK, we keep the position within the file
+            let position = io_result(vm, stack, vm.io().file_position(handle))?;
+            io_result(vm, stack, vm.io().set_file_length(handle, length))?;
+            if position > length {
+                io_result(vm, stack, vm.io().seek_file(handle, length))?;
+            }
+            Ok(None)
+        },
+    );
+    registry.register(
+        RANDOM_ACCESS_FILE,
+        "close0",
+        "()V",
+        |vm, stack, receiver, _| close(vm, stack, receiver),
+    );
+}
+
+/// Returns the number stored in the `FileDescriptor` of the given stream
+fn descriptor_number<'a>(vm: &Vm<'a>, stream: &AbstractObject<'a>) -> Result<i32, VmError> {
+    match get_object_field(vm, stream, "fd")? {
+        Value::Object(descriptor) => match get_object_field(vm, &descriptor, "fd")? {
+            Value::Int(number) => Ok(number),
+            _ => Err(VmError::ValidationException),
+        },
+        _ => Err(VmError::NullPointerException),
+    }
+}
+
+fn set_descriptor_number<'a>(
+    vm: &Vm<'a>,
+    stream: &AbstractObject<'a>,
+    number: i32,
+) -> Result<(), VmError> {
+    match get_object_field(vm, stream, "fd")? {
+        Value::Object(descriptor) => set_object_field(vm, &descriptor, "fd", Value::Int(number)),
+        _ => Err(VmError::NullPointerException),
+    }
+}
+
+/// Returns what the `FileDescriptor` of the given stream refers to, throwing an
+/// `IOException` if it has been closed
+fn resolve_descriptor<'a>(
+    vm: &mut Vm<'a>,
+    call_stack: &mut CallStack<'a>,
+    stream: Option<AbstractObject<'a>>,
+) -> Result<Descriptor, MethodCallFailed<'a>> {
+    let stream = stream.ok_or(VmError::ValidationException)?;
+    match descriptor_number(vm, &stream)? {
+        0 => Ok(Descriptor::StandardInput),
+        1 => Ok(Descriptor::StandardOutput(StandardStream::Out)),
+        2 => Ok(Descriptor::StandardOutput(StandardStream::Err)),
+        number => match vm.open_files.get(number) {
+            Some(handle) => Ok(Descriptor::File(handle)),
+            None => throw_exception(vm, call_stack, "java/io/IOException", "Stream Closed"),
+        },
+    }
+}
+
+/// Like [resolve_descriptor], for the operations that only make sense on files
+fn resolve_file<'a>(
+    vm: &mut Vm<'a>,
+    call_stack: &mut CallStack<'a>,
+    stream: Option<AbstractObject<'a>>,
+) -> Result<FileHandle, MethodCallFailed<'a>> {
+    match resolve_descriptor(vm, call_stack, stream)? {
+        Descriptor::File(handle) => Ok(handle),
+        _ => throw_exception(vm, call_stack, "java/io/IOException", "Illegal seek"),
+    }
+}
+
+/// Throws the error of the [JvmIo](crate::io::JvmIo) as an `IOException`
+fn io_result<'a, T>(
+    vm: &mut Vm<'a>,
+    call_stack: &mut CallStack<'a>,
+    result: Result<T, String>,
+) -> Result<T, MethodCallFailed<'a>> {
+    match result {
+        Ok(value) => Ok(value),
+        Err(message) => throw_exception(vm, call_stack, "java/io/IOException", &message),
+    }
+}
+
+/// Opens the file whose path is the first argument, storing the new descriptor in the
+/// `FileDescriptor` of the stream, which its constructor has already created
+fn open<'a>(
+    vm: &mut Vm<'a>,
+    call_stack: &mut CallStack<'a>,
+    stream: Option<AbstractObject<'a>>,
+    args: &[Value<'a>],
+    options: OpenOptions,
+) -> MethodCallResult<'a> {
+    let stream = stream.ok_or(VmError::ValidationException)?;
+    let path = expect_abstract_object_at(args, 0)?;
+    let path = extract_str_from_java_lang_string(vm, &path)?;
+    match vm.io().open_file(&path, options) {
+        Ok(handle) => {
+            let number = vm.open_files.insert(handle);
+            set_descriptor_number(vm, &stream, number)?;
+            Ok(None)
+        }
+        Err(reason) => throw_exception(
+            vm,
+            call_stack,
+            "java/io/FileNotFoundException",
+            &format!("{path} ({reason})"),
+        ),
+    }
+}
+
+/// Closes the file of the stream, and marks its `FileDescriptor` as invalid. The standard
+/// streams are never closed, but their descriptor becomes invalid.
+fn close<'a>(
+    vm: &mut Vm<'a>,
+    call_stack: &mut CallStack<'a>,
+    stream: Option<AbstractObject<'a>>,
+) -> MethodCallResult<'a> {
+    let stream = stream.ok_or(VmError::ValidationException)?;
+    let number = descriptor_number(vm, &stream)?;
+    if number == -1 {
+        return Ok(None);
+    }
+    set_descriptor_number(vm, &stream, -1)?;
+    if let Some(handle) = vm.open_files.remove(number) {
+        io_result(vm, call_stack, vm.io().close_file(handle))?;
+    }
+    Ok(None)
+}
+
+/// Closes the file of a stream that is being collected. The standard streams stay open.
+fn finalize<'a>(vm: &mut Vm<'a>, stream: Option<AbstractObject<'a>>) -> MethodCallResult<'a> {
+    let stream = stream.ok_or(VmError::ValidationException)?;
+    if let Ok(number) = descriptor_number(vm, &stream) {
+        if let Some(handle) = vm.open_files.remove(number) {
+            set_descriptor_number(vm, &stream, -1)?;
+            // Like the JDK, we ignore the errors of the finalizers
+            let _ = vm.io().close_file(handle);
+        }
+    }
+    Ok(None)
+}
+
+/// Reads from the given descriptor, returning zero at the end of the file
+fn read_descriptor<'a>(
+    vm: &mut Vm<'a>,
+    call_stack: &mut CallStack<'a>,
+    descriptor: Descriptor,
+    buffer: &mut [u8],
+) -> Result<usize, MethodCallFailed<'a>> {
+    let result = match descriptor {
+        Descriptor::StandardInput => vm.io().read_stdin(buffer),
+        Descriptor::File(handle) => vm.io().read_file(handle, buffer),
+        Descriptor::StandardOutput(_) => Err("Bad file descriptor".to_string()),
+    };
+    let read = io_result(vm, call_stack, result)?;
+    Ok(read.min(buffer.len()))
+}
+
+fn write_descriptor<'a>(
+    vm: &mut Vm<'a>,
+    call_stack: &mut CallStack<'a>,
+    descriptor: Descriptor,
+    bytes: &[u8],
+) -> Result<(), MethodCallFailed<'a>> {
+    let result = match descriptor {
+        Descriptor::StandardOutput(stream) => {
+            vm.output_sink(stream).write(bytes);
+            Ok(())
+        }
+        Descriptor::File(handle) => vm.io().write_file(handle, bytes),
+        Descriptor::StandardInput => Err("Bad file descriptor".to_string()),
+    };
+    io_result(vm, call_stack, result)
+}
+
+fn read_byte<'a>(
+    vm: &mut Vm<'a>,
+    call_stack: &mut CallStack<'a>,
+    stream: Option<AbstractObject<'a>>,
+) -> MethodCallResult<'a> {
+    let descriptor = resolve_descriptor(vm, call_stack, stream)?;
+    let mut byte = [0];
+    let read = read_descriptor(vm, call_stack, descriptor, &mut byte)?;
+    Ok(Some(Value::Int(if read == 0 {
+        -1
+    } else {
+        byte[0].into()
+    })))
+}
+
+/// Reads into the array passed as first argument, at the offset and for at most the length
+/// passed as second and third arguments
+fn read_bytes<'a>(
+    vm: &mut Vm<'a>,
+    call_stack: &mut CallStack<'a>,
+    stream: Option<AbstractObject<'a>>,
+    args: &[Value<'a>],
+) -> MethodCallResult<'a> {
+    let array = expect_abstract_object_at(args, 0)?;
+    let Some((offset, length)) =
+        checked_range(&array, expect_int_at(args, 1)?, expect_int_at(args, 2)?)
+    else {
+        return throw_exception(vm, call_stack, "java/lang/IndexOutOfBoundsException", "");
+    };
+    if length == 0 {
+        return Ok(Some(Value::Int(0)));
+    }
+    let descriptor = resolve_descriptor(vm, call_stack, stream)?;
+    let mut buffer = vec![0; length];
+    let read = read_descriptor(vm, call_stack, descriptor, &mut buffer)?;
+    if read == 0 {
+        return Ok(Some(Value::Int(-1)));
+    }
+    copy_into_byte_array(&array, offset, &buffer[..read])?;
+    Ok(Some(Value::Int(i32::try_from(read).unwrap_or(i32::MAX))))
+}
+
+fn write_byte<'a>(
+    vm: &mut Vm<'a>,
+    call_stack: &mut CallStack<'a>,
+    stream: Option<AbstractObject<'a>>,
+    args: &[Value<'a>],
+) -> MethodCallResult<'a> {
+    let byte = expect_int_at(args, 0)?.to_le_bytes()[0];
+    let descriptor = resolve_descriptor(vm, call_stack, stream)?;
+    write_descriptor(vm, call_stack, descriptor, &[byte])?;
+    Ok(None)
+}
+
+/// Writes the part of the array passed as first argument delimited by the offset and
+/// length passed as second and third arguments
+fn write_bytes<'a>(
+    vm: &mut Vm<'a>,
+    call_stack: &mut CallStack<'a>,
+    stream: Option<AbstractObject<'a>>,
+    args: &[Value<'a>],
+) -> MethodCallResult<'a> {
+    let array = expect_abstract_object_at(args, 0)?;
+    let Some((offset, length)) =
+        checked_range(&array, expect_int_at(args, 1)?, expect_int_at(args, 2)?)
+    else {
+        return throw_exception(vm, call_stack, "java/lang/IndexOutOfBoundsException", "");
+    };
+    let bytes = bytes_of_byte_array(&array, offset, length)?;
+    let descriptor = resolve_descriptor(vm, call_stack, stream)?;
+    write_descriptor(vm, call_stack, descriptor, &bytes)?;
+    Ok(None)
+}
+
+/// Moves forward in a file, or reads and discards the standard input
+fn skip<'a>(
+    vm: &mut Vm<'a>,
+    call_stack: &mut CallStack<'a>,
+    descriptor: Descriptor,
+    to_skip: i64,
+) -> Result<i64, MethodCallFailed<'a>> {
+    if let Descriptor::File(handle) = descriptor {
+        let position = io_result(vm, call_stack, vm.io().file_position(handle))?;
+        let Some(new_position) = position.checked_add_signed(to_skip) else {
+            return throw_exception(vm, call_stack, "java/io/IOException", "Invalid argument");
+        };
+        io_result(vm, call_stack, vm.io().seek_file(handle, new_position))?;
+        return Ok(to_skip);
+    }
+    let to_skip = usize::try_from(to_skip).unwrap_or(0);
+    let mut buffer = vec![0; to_skip.min(SKIP_BUFFER_SIZE)];
+    let mut skipped = 0;
+    while skipped < to_skip {
+        let chunk = buffer.len().min(to_skip - skipped);
+        let read = read_descriptor(vm, call_stack, descriptor, &mut buffer[..chunk])?;
+        if read == 0 {
+            break;
+        }
+        skipped += read;
+    }
+    Ok(i64::try_from(skipped).unwrap_or(i64::MAX))
+}
+
+/// The maximum number of bytes that [skip] reads at once
+const SKIP_BUFFER_SIZE: usize = 4096;
+
+/// Returns the given offset and length if they delimit a valid range of the array
+pub(crate) fn checked_range(
+    array: &AbstractObject,
+    offset: i32,
+    length: i32,
+) -> Option<(usize, usize)> {
+    let offset = usize::try_from(offset).ok()?;
+    let length = usize::try_from(length).ok()?;
+    let end = offset.checked_add(length)?;
+    (end <= array.len().into_usize_safe()).then_some((offset, length))
+}
+
+/// Returns the given range of a `byte[]`
+pub(crate) fn bytes_of_byte_array(
+    array: &AbstractObject,
+    offset: usize,
+    length: usize,
+) -> Result<Vec<u8>, VmError> {
+    (offset..offset + length)
+        .map(|index| match array.get_element(index)? {
+            Value::Int(byte) => Ok(byte.to_le_bytes()[0]),
+            _ => Err(VmError::ValidationException),
+        })
+        .collect()
+}
+
+/// Copies the given bytes into a `byte[]`, starting at the given offset
+pub(crate) fn copy_into_byte_array(
+    array: &AbstractObject,
+    offset: usize,
+    bytes: &[u8],
+) -> Result<(), VmError> {
+    for (index, byte) in bytes.iter().enumerate() {
+        array.set_element(offset + index, Value::Int(byte.cast_signed().into()))?;
+    }
+    Ok(())
+}
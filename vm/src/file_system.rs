@@ -0,0 +1,257 @@
+use crate::{
+    call_frame::MethodCallResult,
+    call_stack::CallStack,
+    io::{FileKind, OpenOptions},
+    java_objects_creation::{
+        extract_str_from_java_lang_string, new_java_lang_string_array, new_java_lang_string_object,
+        throw_exception,
+    },
+    native_methods_registry::NativeMethodsRegistry,
+    object_fields::get_object_field,
+    value::{expect_abstract_object_at, expect_int_at, expect_long_at, Value},
+    vm::Vm,
+    vm_error::VmError,
+};
+
+const UNIX_FILE_SYSTEM: &str = "java/io/UnixFileSystem";
+
+/// The flags returned by `getBooleanAttributes0`, as defined in `java.io.FileSystem`
+const BA_EXISTS: i32 = 0x01;
+const BA_REGULAR: i32 = 0x02;
+const BA_DIRECTORY: i32 = 0x04;
+
+/// The flags passed to `checkAccess`, as defined in `java.io.FileSystem`
+const ACCESS_READ: i32 = 0x04;
+const ACCESS_WRITE: i32 = 0x02;
+const ACCESS_EXECUTE: i32 = 0x01;
+
+/// Registers the natives of `java.io.UnixFileSystem`, which implements the queries and the
+/// operations of `java.io.File`, on top of the [JvmIo](crate::io::JvmIo) of the vm.
+/// Like in the JDK, the failures are reported to the java code as `false`, zero or null,
+/// except where `File` declares an `IOException`.
+pub(crate) fn register_file_system_methods(registry: &mut NativeMethodsRegistry) {
+    registry.register(UNIX_FILE_SYSTEM, "initIDs", "()V", |_, _, _, _| Ok(None));
+    registry.register(
+        "java/io/FileSystem",
+        "getFileSystem",
+        "()Ljava/io/FileSystem;",
+        |vm, stack, _, _| new_file_system(vm, stack),
+    );
+
+    registry.register(
+        UNIX_FILE_SYSTEM,
+        "getBooleanAttributes0",
+        "(Ljava/io/File;)I",
+        |vm, _, _, args| {
+            let path = file_path(vm, &args, 0)?;
+            let attributes = match vm.io().file_metadata(&path) {
+                Ok(metadata) => match metadata.kind {
+                    FileKind::Regular => BA_EXISTS | BA_REGULAR,
+                    FileKind::Directory => BA_EXISTS | BA_DIRECTORY,
+                    FileKind::Other => BA_EXISTS,
+                },
+                Err(_) => 0,
+            };
+            Ok(Some(Value::Int(attributes)))
+        },
+    );
+    registry.register(
+        UNIX_FILE_SYSTEM,
+        "checkAccess",
+        "(Ljava/io/File;I)Z",
+        |vm, _, _, args| {
+            let path = file_path(vm, &args, 0)?;
+            let access = expect_int_at(&args, 1)?;
+            let allowed = vm.io().file_metadata(&path).is_ok_and(|metadata| {
+                (access & ACCESS_READ == 0 || metadata.readable)
+                    && (access & ACCESS_WRITE == 0 || metadata.writable)
+                    && (access & ACCESS_EXECUTE == 0 || metadata.executable)
+            });
+            Ok(Some(Value::Int(i32::from(allowed))))
+        },
+    );
+    registry.register(
+        UNIX_FILE_SYSTEM,
+        "getLastModifiedTime",
+        "(Ljava/io/File;)J",
+        |vm, _, _, args| {
+            let path = file_path(vm, &args, 0)?;
+            let last_modified = vm
+                .io()
+                .file_metadata(&path)
+                .ok()
+                .and_then(|metadata| metadata.last_modified)
+                .unwrap_or(0);
+            Ok(Some(Value::Long(last_modified)))
+        },
+    );
+    registry.register(
+        UNIX_FILE_SYSTEM,
+        "getLength",
+        "(Ljava/io/File;)J",
+        |vm, _, _, args| {
+            let path = file_path(vm, &args, 0)?;
+            let length = vm
+                .io()
+                .file_metadata(&path)
+                .map_or(0, |metadata| metadata.length);
+            Ok(Some(Value::Long(i64::try_from(length).unwrap_or(i64::MAX))))
+        },
+    );
+    registry.register(
+        UNIX_FILE_SYSTEM,
+        "createFileExclusively",
+        "(Ljava/lang/String;)Z",
+        |vm, stack, _, args| {
+            let path = expect_abstract_object_at(&args, 0)?;
+            let path = extract_str_from_java_lang_string(vm, &path)?;
+            create_file_exclusively(vm, stack, &path)
+        },
+    );
+    registry.register(
+        UNIX_FILE_SYSTEM,
+        "delete0",
+        "(Ljava/io/File;)Z",
+        |vm, _, _, args| {
+            let path = file_path(vm, &args, 0)?;
+            let deleted = vm.io().delete_file(&path).is_ok();
+            Ok(Some(Value::Int(i32::from(deleted))))
+        },
+    );
+    registry.register(
+        UNIX_FILE_SYSTEM,
+        "list",
+        "(Ljava/io/File;)[Ljava/lang/String;",
+        |vm, stack, _, args| {
+            let path = file_path(vm, &args, 0)?;
+            match vm.io().list_directory(&path) {
+                Ok(names) => {
+                    let names = new_java_lang_string_array(vm, stack, &names)?;
+                    Ok(Some(Value::Object(names)))
+                }
+                Err(_) => Ok(Some(Value::Null)),
+            }
+        },
+    );
+    registry.register(
+        UNIX_FILE_SYSTEM,
+        "createDirectory",
+        "(Ljava/io/File;)Z",
+        |vm, _, _, args| {
+            let path = file_path(vm, &args, 0)?;
+            let created = vm.io().create_directory(&path).is_ok();
+            Ok(Some(Value::Int(i32::from(created))))
+        },
+    );
+    registry.register(
+        UNIX_FILE_SYSTEM,
+        "rename0",
+        "(Ljava/io/File;Ljava/io/File;)Z",
+        |vm, _, _, args| {
+            let from = file_path(vm, &args, 0)?;
+            let to = file_path(vm, &args, 1)?;
+            let renamed = vm.io().rename_file(&from, &to).is_ok();
+            Ok(Some(Value::Int(i32::from(renamed))))
+        },
+    );
+    registry.register(
+        UNIX_FILE_SYSTEM,
+        "setLastModifiedTime",
+        "(Ljava/io/File;J)Z",
+        |vm, _, _, args| {
+            let path = file_path(vm, &args, 0)?;
+            let last_modified = expect_long_at(&args, 1)?;
+            let updated = vm.io().set_last_modified(&path, last_modified).is_ok();
+            Ok(Some(Value::Int(i32::from(updated))))
+        },
+    );
+
+    // Changing the permissions is not supported by the JvmIo, thus it always fails
+    registry.register(
+        UNIX_FILE_SYSTEM,
+        "setPermission",
+        "(Ljava/io/File;IZZ)Z",
+        |_, _, _, _| Ok(Some(Value::Int(0))),
+    );
+    registry.register(
+        UNIX_FILE_SYSTEM,
+        "setReadOnly",
+        "(Ljava/io/File;)Z",
+        |_, _, _, _| Ok(Some(Value::Int(0))),
+    );
+    // Zero is what the JDK returns when the space cannot be known
+    registry.register(
+        UNIX_FILE_SYSTEM,
+        "getSpace",
+        "(Ljava/io/File;I)J",
+        |_, _, _, _| Ok(Some(Value::Long(0))),
+    );
+
+    // The caches used by `canonicalize` need the property "java.home", which we do not
+    // have, so we skip them and always ask the JvmIo
+    for method_name in ["canonicalize", "canonicalize0"] {
+        registry.register_intrinsic(
+            UNIX_FILE_SYSTEM,
+            method_name,
+            "(Ljava/lang/String;)Ljava/lang/String;",
+            |vm, stack, _, args| canonicalize(vm, stack, &args),
+        );
+    }
+}
+
+fn new_file_system<'a>(vm: &mut Vm<'a>, call_stack: &mut CallStack<'a>) -> MethodCallResult<'a> {
+    let scope = vm.handle_scope();
+    let file_system = scope.handle(vm.new_object(call_stack, UNIX_FILE_SYSTEM)?);
+    let constructor = vm.resolve_class_method(call_stack, UNIX_FILE_SYSTEM, "<init>", "()V")?;
+    vm.invoke(call_stack, constructor, Some(file_system.get()), Vec::new())?;
+    Ok(Some(file_system.value()))
+}
+
+/// Returns the path of the `java.io.File` passed at the given index
+fn file_path<'a>(vm: &Vm<'a>, args: &[Value<'a>], index: usize) -> Result<String, VmError> {
+    let file = expect_abstract_object_at(args, index)?;
+    match get_object_field(vm, &file, "path")? {
+        Value::Object(path) => extract_str_from_java_lang_string(vm, &path),
+        _ => Err(VmError::NullPointerException),
+    }
+}
+
+/// Creates an empty file, returning false if it already exists
+fn create_file_exclusively<'a>(
+    vm: &mut Vm<'a>,
+    call_stack: &mut CallStack<'a>,
+    path: &str,
+) -> MethodCallResult<'a> {
+    if vm.io().file_metadata(path).is_ok() {
+        return Ok(Some(Value::Int(0)));
+    }
+    let options = OpenOptions {
+        write: true,
+        create_new: true,
+        ..Default::default()
+    };
+    let result = vm
+        .io()
+        .open_file(path, options)
+        .and_then(|handle| vm.io().close_file(handle));
+    match result {
+        Ok(()) => Ok(Some(Value::Int(1))),
+        Err(message) => throw_exception(vm, call_stack, "java/io/IOException", &message),
+    }
+}
+
+fn canonicalize<'a>(
+    vm: &mut Vm<'a>,
+    call_stack: &mut CallStack<'a>,
+    args: &[Value<'a>],
+) -> MethodCallResult<'a> {
+    let path = expect_abstract_object_at(args, 0)?;
+    let path = extract_str_from_java_lang_string(vm, &path)?;
+    match vm.io().canonicalize_path(&path) {
+        Ok(canonical) => {
+            let canonical = new_java_lang_string_object(vm, call_stack, &canonical)?;
+            Ok(Some(Value::Object(canonical)))
+        }
+        Err(message) => throw_exception(vm, call_stack, "java/io/IOException", &message),
+    }
+}
@@ -1,12 +1,12 @@
 use std::{
     fmt,
     fmt::Formatter,
-    path::{Path, PathBuf},
+    path::{Component, Path, PathBuf},
 };
 
 use crate::class_path_entry::{ClassLoadingError, ClassPathEntry};
 
-/// Implementation of [ClassPathEntry] that searches for `.class` files,
+/// Implementation of [ClassPathEntry] that searches for `.class` files and other resources,
 /// using the given directory as the root package
 #[derive(Debug)]
 pub struct FileSystemClassPathEntry {
@@ -29,11 +29,18 @@ impl FileSystemClassPathEntry {
 }
 
 impl ClassPathEntry for FileSystemClassPathEntry {
-    fn resolve(&self, class_name: &str) -> Result<Option<Vec<u8>>, ClassLoadingError> {
-        let mut candidate = self.base_directory.clone();
-        candidate.push(class_name);
-        candidate.set_extension("class");
-        if candidate.exists() {
+    fn resolve_resource(&self, path: &str) -> Result<Option<Vec<u8>>, ClassLoadingError> {
+        // Only plain relative paths are allowed, so that we never read outside the directory
+        let relative_path = Path::new(path);
+        if !relative_path
+            .components()
+            .all(|component| matches!(component, Component::Normal(_)))
+        {
+            return Ok(None);
+        }
+
+        let candidate = self.base_directory.join(relative_path);
+        if candidate.is_file() {
             std::fs::read(candidate)
                 .map(Some)
                 .map_err(ClassLoadingError::new)
@@ -41,6 +48,35 @@ impl ClassPathEntry for FileSystemClassPathEntry {
             Ok(None)
         }
     }
+
+    fn description(&self) -> String {
+        self.base_directory.to_string_lossy().to_string()
+    }
+
+    fn class_names(&self) -> Result<Vec<String>, ClassLoadingError> {
+        let mut class_names = Vec::new();
+        let mut directories_to_visit = vec![self.base_directory.clone()];
+        while let Some(directory) = directories_to_visit.pop() {
+            for dir_entry in std::fs::read_dir(directory).map_err(ClassLoadingError::new)? {
+                let path = dir_entry.map_err(ClassLoadingError::new)?.path();
+                if path.is_dir() {
+                    directories_to_visit.push(path);
+                } else if path.extension().is_some_and(|ext| ext == "class") {
+                    let relative_path = path
+                        .strip_prefix(&self.base_directory)
+                        .expect("walked paths should be inside the base directory")
+                        .with_extension("");
+                    let class_name = relative_path
+                        .components()
+                        .map(|component| component.as_os_str().to_string_lossy())
+                        .collect::<Vec<_>>()
+                        .join("/");
+                    class_names.push(class_name);
+                }
+            }
+        }
+        Ok(class_names)
+    }
 }
 
 /// Error returned when a directory is not valid
@@ -62,7 +98,10 @@ mod tests {
     use std::path::PathBuf;
 
     use crate::{
-        class_path_entry::tests::{assert_can_find_class, assert_cannot_find_class},
+        class_path_entry::{
+            tests::{assert_can_find_class, assert_cannot_find_class, assert_cannot_find_resource},
+            ClassPathEntry,
+        },
         file_system_class_path_entry::{FileSystemClassPathEntry, InvalidDirectoryError},
     };
 
@@ -88,4 +127,33 @@ mod tests {
         assert_can_find_class(&entry, "rjvm/ControlFlow");
         assert_cannot_find_class(&entry, "rjvm/Foo");
     }
+
+    #[test]
+    fn file_system_class_path_entry_can_search_for_resources() {
+        let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        path.push("tests/resources");
+        let entry = FileSystemClassPathEntry::new(path).expect("should find directory");
+
+        let source = entry
+            .resolve_resource("rjvm/NumericTypes.java")
+            .expect("should have been able to read file")
+            .expect("should have been able to find file");
+        assert!(String::from_utf8_lossy(&source).contains("class NumericTypes"));
+        assert_cannot_find_resource(&entry, "rjvm");
+        assert_cannot_find_resource(&entry, "rjvm/Foo.java");
+        assert_cannot_find_resource(&entry, "../Cargo.toml");
+        assert_cannot_find_resource(&entry, "/etc/passwd");
+    }
+
+    #[test]
+    fn file_system_class_path_entry_can_list_classes() {
+        let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        path.push("tests/resources");
+        let entry = FileSystemClassPathEntry::new(path).expect("should find directory");
+
+        let class_names = entry.class_names().expect("should be able to list classes");
+        assert!(class_names.contains(&"rjvm/NumericTypes".to_string()));
+        assert!(class_names.contains(&"minimal_runtime/java/util/ArrayList".to_string()));
+        assert!(!class_names.iter().any(|name| name.ends_with(".java")));
+    }
 }
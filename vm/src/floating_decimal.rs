@@ -0,0 +1,152 @@
+// Implementation of `Double.toString` and `Float.toString`, which our rt.jar implements in
+// `sun.misc.FloatingDecimal`.
+//
+// Like the JDK since java 19, we print the shortest decimal that rounds to the same value,
+// which Rust's formatting already computes, but with at least two significant digits: when one
+// digit is enough, java picks the two digits closest to the value, e.g. "4.9E-324" rather
+// than "5.0E-324" for `Double.MIN_VALUE`. We then lay out the digits as java does:
+// plain notation for magnitudes in [10^-3, 10^7), and "computerized scientific notation"
+// otherwise, always with at least one digit after the decimal point.
+// Known deviations: the `FloatingDecimal` of java 7 sometimes prints one more digit than
+// needed, e.g. `2.0E-3` becomes "0.0020" there and "0.002" here.
+
+/// Formats the given double as `Double.toString` does
+pub fn double_to_string(value: f64) -> String {
+    if value.is_nan() {
+        "NaN".to_string()
+    } else if value.is_infinite() {
+        infinity_to_string(value.is_sign_negative())
+    } else {
+        let scientific = at_least_two_digits(format!("{:e}", value.abs()), || {
+            format!("{:.1e}", value.abs())
+        });
+        lay_out(&scientific, value.is_sign_negative())
+    }
+}
+
+/// Formats the given float as `Float.toString` does. The digits are the shortest ones that
+/// identify the float, which are usually fewer than those of the equivalent double.
+pub fn float_to_string(value: f32) -> String {
+    if value.is_nan() {
+        "NaN".to_string()
+    } else if value.is_infinite() {
+        infinity_to_string(value.is_sign_negative())
+    } else {
+        let scientific = at_least_two_digits(format!("{:e}", value.abs()), || {
+            format!("{:.1e}", value.abs())
+        });
+        lay_out(&scientific, value.is_sign_negative())
+    }
+}
+
+/// Given the shortest digits in scientific notation, returns the closest two digits instead
+/// if the shortest have only one
+fn at_least_two_digits(shortest: String, two_digits: impl FnOnce() -> String) -> String {
+    if shortest.contains('.') {
+        shortest
+    } else {
+        two_digits()
+    }
+}
+
+fn infinity_to_string(is_negative: bool) -> String {
+    if is_negative {
+        "-Infinity".to_string()
+    } else {
+        "Infinity".to_string()
+    }
+}
+
+/// Lays out the digits of the given number, formatted by Rust in scientific notation such
+/// as `1.2345e-7`, in the java format
+fn lay_out(scientific: &str, is_negative: bool) -> String {
+    let (mantissa, exponent) = scientific
+        .split_once('e')
+        .expect("scientific notation should have an exponent");
+    let exponent: i32 = exponent.parse().expect("the exponent should be an integer");
+    let digits: String = mantissa.chars().filter(|c| *c != '.').collect();
+    // The closest two digits could end with a zero, which java does not print
+    let digits = match digits.trim_end_matches('0') {
+        "" => "0",
+        trimmed => trimmed,
+    };
+
+    let mut result = String::with_capacity(digits.len() + 8);
+    if is_negative {
+        result.push('-');
+    }
+    if digits == "0" {
+        result.push_str("0.0");
+    } else if (0..7).contains(&exponent) {
+        let integer_digits = usize::try_from(exponent).expect("exponent is positive") + 1;
+        if digits.len() <= integer_digits {
+            result.push_str(digits);
+            result.extend(std::iter::repeat_n('0', integer_digits - digits.len()));
+            result.push_str(".0");
+        } else {
+            result.push_str(&digits[..integer_digits]);
+            result.push('.');
+            result.push_str(&digits[integer_digits..]);
+        }
+    } else if (-3..0).contains(&exponent) {
+        let leading_zeros = usize::try_from(-exponent - 1).expect("exponent is negative");
+        result.push_str("0.");
+        result.extend(std::iter::repeat_n('0', leading_zeros));
+        result.push_str(digits);
+    } else {
+        result.push_str(&digits[..1]);
+        result.push('.');
+        result.push_str(if digits.len() > 1 { &digits[1..] } else { "0" });
+        result.push('E');
+        result.push_str(&exponent.to_string());
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::floating_decimal::{double_to_string, float_to_string};
+
+    #[test]
+    fn doubles_in_plain_notation() {
+        assert_eq!("0.0", double_to_string(0.0));
+        assert_eq!("-0.0", double_to_string(-0.0));
+        assert_eq!("1.0", double_to_string(1.0));
+        assert_eq!("0.1", double_to_string(0.1));
+        assert_eq!("-2.5", double_to_string(-2.5));
+        assert_eq!("100.0", double_to_string(100.0));
+        assert_eq!("0.30000000000000004", double_to_string(0.1 + 0.2));
+        assert_eq!("123456.789", double_to_string(123456.789));
+        assert_eq!("9999999.0", double_to_string(9999999.0));
+        assert_eq!("0.001", double_to_string(0.001));
+        assert_eq!("0.00123", double_to_string(0.00123));
+    }
+
+    #[test]
+    fn doubles_in_scientific_notation() {
+        assert_eq!("1.0E7", double_to_string(1e7));
+        assert_eq!("1.2345678E7", double_to_string(12345678.0));
+        assert_eq!("1.0E-4", double_to_string(1e-4));
+        assert_eq!("-1.234E-5", double_to_string(-1.234e-5));
+        assert_eq!("1.7976931348623157E308", double_to_string(f64::MAX));
+        assert_eq!("4.9E-324", double_to_string(4.9e-324));
+    }
+
+    #[test]
+    fn doubles_special_values() {
+        assert_eq!("NaN", double_to_string(f64::NAN));
+        assert_eq!("Infinity", double_to_string(f64::INFINITY));
+        assert_eq!("-Infinity", double_to_string(f64::NEG_INFINITY));
+    }
+
+    #[test]
+    fn floats_use_their_own_shortest_digits() {
+        assert_eq!("0.1", float_to_string(0.1));
+        assert_eq!("1.1", float_to_string(1.1));
+        assert_eq!("-0.0", float_to_string(-0.0));
+        assert_eq!("3.4028235E38", float_to_string(f32::MAX));
+        assert_eq!("1.4E-45", float_to_string(1.4e-45));
+        assert_eq!("1.0E10", float_to_string(1e10));
+        assert_eq!("NaN", float_to_string(f32::NAN));
+    }
+}
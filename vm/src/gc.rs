@@ -1,17 +1,34 @@
-use std::{alloc::Layout, fmt, fmt::Formatter, marker::PhantomData, ptr::null};
+use std::{
+    alloc::Layout,
+    cell::RefCell,
+    collections::HashMap,
+    fmt,
+    fmt::Formatter,
+    marker::PhantomData,
+    ptr::{null, null_mut},
+    time::Instant,
+};
 
-use log::{debug, info};
+use log::Level;
 
 use rjvm_reader::{field_type::FieldType, type_conversion::ToUsizeSafe};
 
 use crate::{
-    abstract_object::{AbstractObject, AllocHeader, GcState, ObjectKind, ALLOC_HEADER_SIZE},
+    abstract_object::{
+        AbstractObject, AllocHeader, GcState, ObjectId, ObjectKind, ALLOC_HEADER_SIZE,
+    },
     alloc_entry::AllocEntry,
     array::Array,
     array_entry_type::ArrayEntryType,
     class::Class,
     class_resolver_by_id::ClassByIdResolver,
+    gc_algorithm::GcAlgorithm,
+    gc_stats::GcStats,
+    heap_growth::HeapGrowthPolicy,
+    incremental_marking::IncrementalMarkingConfig,
+    log_config::{vm_log, LogCategory, LogConfig},
     object::Object,
+    reference_objects::{ReferenceKind, NEXT_FIELD_INDEX, REFERENT_FIELD_INDEX},
     value::Value,
     vm_error::VmError,
 };
@@ -35,9 +52,9 @@ impl fmt::Debug for MemoryChunk {
 
 impl MemoryChunk {
     fn new(capacity: usize) -> Self {
-        let layout = Layout::from_size_align(capacity, 8).unwrap();
-        let ptr = unsafe { std::alloc::alloc_zeroed(layout) };
-        debug!(
+        let ptr = unsafe { std::alloc::alloc_zeroed(Self::layout(capacity)) };
+        log::debug!(
+            target: LogCategory::Gc.target(),
             "allocated memory chunk of size {} at {:#0x}",
             capacity, ptr as u64
         );
@@ -72,18 +89,34 @@ impl MemoryChunk {
     }
 
     fn reset(&mut self) {
-        self.used = 0;
+        self.truncate(0);
+    }
 
+    /// Frees the memory after the given size
+    fn truncate(&mut self, used: usize) {
         // Zero the memory, to attempt and catch bugs
         unsafe {
-            std::ptr::write_bytes(self.memory, 0, self.capacity);
+            std::ptr::write_bytes(self.memory.add(used), 0, self.capacity - used);
         }
+        self.used = used;
+    }
+
+    fn layout(capacity: usize) -> Layout {
+        // Zero-sized allocations are not allowed
+        Layout::from_size_align(capacity.max(8), 8).unwrap()
+    }
+}
+
+impl Drop for MemoryChunk {
+    fn drop(&mut self) {
+        unsafe { std::alloc::dealloc(self.memory, Self::layout(self.capacity)) }
     }
 }
 
 /// Models the object allocator and the garbage collector!
 ///
-/// For the garbage collection, we use a very simple semi-space copying collector. We split the
+/// By default, for the garbage collection we use a very simple semi-space copying collector.
+/// We split the
 /// memory in two chunks (semi-spaces), and we allocate objects in one of them. When the current
 /// chunk is full, we run the garbage collector, which copies all reachable objects to the other
 /// chunk, and then swaps the chunks. Finally, it updates all the given gc roots.
@@ -91,28 +124,248 @@ impl MemoryChunk {
 /// Obviously, this wastes half the memory, which is why nobody uses this algorithm
 /// in any real implementation. However, it is quite simple, and handles reference cycles,
 /// so it is the one I have chosen here.
+///
+/// Optionally, the marking can be done incrementally, interleaved with the execution of the
+/// java code; see [IncrementalMarkingConfig]. In that case, a classical tri-color marking is
+/// used: the objects reachable from the roots are marked and put in the grey list, and each
+/// marking slice scans a bounded number of grey objects, marking their references in turn.
+/// The [write_barrier] marks any object stored into another one, so that the objects already
+/// scanned can never end up pointing to an unmarked object. When the memory is full, the
+/// collection re-scans the roots, finishes the marking, and copies all the marked objects to
+/// the other chunk. Objects allocated during the cycle are not marked, so that the ones which
+/// are already garbage can be collected: the live ones will be found when re-scanning the roots.
+/// However, the objects that became garbage after being marked will survive the collection.
+///
+/// Alternatively, the collector can use the whole memory with a mark-compact algorithm (see
+/// [GcAlgorithm]). Then, the marking is always done by scanning the grey objects, like the
+/// incremental one, but without moving anything. Then, the collection computes the address that
+/// each marked object will have once they are all packed at the start of the memory, fixes the
+/// references in the marked objects and the roots, and finally slides the objects in place.
+///
+/// The heap can start smaller than its maximum size, and grow following a [HeapGrowthPolicy]
+/// when it is still full after a collection. Growing is a copying collection into a newly
+/// allocated, larger, chunk, whatever the algorithm; the old chunks are then freed.
+///
+/// The soft, weak and phantom references (see [ReferenceKind]) do not keep their referent
+/// alive: the marking skips it, and records the reference. After the marking, the referents
+/// that were not reached are cleared, and the references are returned to the vm, which will
+/// enqueue them. Soft references are treated like the strong ones, unless the collection is
+/// asked to clear them, and so is the marking done incrementally before the collection.
+///
+/// The allocator also tracks the instances of the classes with a finalizer. When the marking
+/// does not reach them, they are marked together with everything they refer to, so that they
+/// survive the collection, and they are returned to the vm, which will invoke their finalizer.
+/// They are not tracked anymore, thus the next collection will reclaim them unless the
+/// finalizer made them reachable again. Since the marking reaches them, the references to
+/// them, or to the objects they refer to, are cleared only after the finalization.
 pub struct ObjectAllocator<'a> {
     current: MemoryChunk,
     other: MemoryChunk,
     marker: PhantomData<&'a AbstractObject<'a>>,
+    pub(crate) log_config: LogConfig,
+    next_object_id: u64,
+    pub(crate) incremental_marking: Option<IncrementalMarkingConfig>,
+    /// Whether an incremental marking cycle is in progress
+    marking: bool,
+    /// The objects that have been marked, but whose references have not been scanned yet.
+    /// It is in a cell because the write barrier must be usable with a shared reference.
+    grey_objects: RefCell<Vec<*mut u8>>,
+    /// The active references found by the marking, whose referent might have to be cleared
+    discovered_references: RefCell<Vec<*mut u8>>,
+    /// Whether the current collection clears the soft references
+    clear_soft_references: bool,
+    /// The objects with a finalizer that has not been invoked yet
+    finalizable_objects: Vec<*mut u8>,
+    algorithm: GcAlgorithm,
+    /// Whether the current collection compacts the objects in place, rather than copying them
+    /// to the other chunk. A collection that grows the heap always copies them.
+    compacting: bool,
+    /// The new address of each marked object, computed during a mark-compact collection
+    forwarding_addresses: HashMap<*mut u8, *mut u8>,
+    /// The size that the heap, i.e. both chunks together, can grow to
+    max_size: usize,
+    growth_policy: HeapGrowthPolicy,
+    stats: GcStats,
+}
+
+/// The objects that a garbage collection hands back to the vm
+#[derive(Debug, Default)]
+pub struct CollectedObjects<'a> {
+    /// The references whose referent has been cleared
+    pub cleared_references: Vec<AbstractObject<'a>>,
+    /// The unreachable objects that have been kept alive to run their finalizer
+    pub objects_to_finalize: Vec<AbstractObject<'a>>,
+}
+
+/// Objects that must be kept alive only as long as the key is: like a weak root, the key does
+/// not keep anything alive, and the values are traced only once the marking reaches the key.
+/// The vm uses them for the data that it stores outside the heap on behalf of a java object.
+#[derive(Debug)]
+pub struct Ephemeron<'a> {
+    /// Must also be passed among the weak roots, which will update it
+    pub key: *mut Option<AbstractObject<'a>>,
+    pub values: Vec<*mut AbstractObject<'a>>,
+}
+
+impl<'a> Ephemeron<'a> {
+    unsafe fn is_key_marked(&self) -> bool {
+        match &*self.key {
+            Some(key) => {
+                let header =
+                    &*(*(key as *const AbstractObject as *const *const u8) as *const AllocHeader);
+                header.state() == GcState::Marked
+            }
+            None => false,
+        }
+    }
 }
 
 impl<'a> ObjectAllocator<'a> {
     pub fn with_maximum_memory(max_size: usize) -> Self {
-        let semi_space_capacity = max_size / 2;
+        Self::new(max_size, GcAlgorithm::default())
+    }
+
+    pub fn new(max_size: usize, algorithm: GcAlgorithm) -> Self {
+        let (current, other) = Self::memory_chunks(max_size, algorithm);
         Self {
-            current: MemoryChunk::new(semi_space_capacity),
-            other: MemoryChunk::new(semi_space_capacity),
+            current,
+            other,
             marker: Default::default(),
+            log_config: Default::default(),
+            next_object_id: 1,
+            incremental_marking: None,
+            marking: false,
+            grey_objects: Default::default(),
+            discovered_references: Default::default(),
+            clear_soft_references: false,
+            finalizable_objects: Vec::new(),
+            algorithm,
+            compacting: false,
+            forwarding_addresses: HashMap::new(),
+            max_size,
+            growth_policy: HeapGrowthPolicy::Fixed,
+            stats: GcStats::default(),
+        }
+    }
+
+    fn memory_chunks(heap_size: usize, algorithm: GcAlgorithm) -> (MemoryChunk, MemoryChunk) {
+        let (current_capacity, other_capacity) = Self::chunk_capacities(heap_size, algorithm);
+        (
+            MemoryChunk::new(current_capacity),
+            MemoryChunk::new(other_capacity),
+        )
+    }
+
+    /// How a heap of the given size is split between the current and the other chunk
+    fn chunk_capacities(heap_size: usize, algorithm: GcAlgorithm) -> (usize, usize) {
+        match algorithm {
+            GcAlgorithm::SemiSpaceCopying => (heap_size / 2, heap_size / 2),
+            // The other chunk is never used
+            GcAlgorithm::MarkCompact => (heap_size, 0),
+        }
+    }
+
+    /// The size of a heap whose current chunk has the given capacity
+    fn heap_size_for(&self, current_capacity: usize) -> usize {
+        match self.algorithm {
+            GcAlgorithm::SemiSpaceCopying => current_capacity.saturating_mul(2),
+            GcAlgorithm::MarkCompact => current_capacity,
         }
     }
 
+    pub fn algorithm(&self) -> GcAlgorithm {
+        self.algorithm
+    }
+
+    /// Changes the algorithm of the collector, which must happen before the first allocation,
+    /// since the memory is split differently
+    pub fn set_algorithm(&mut self, algorithm: GcAlgorithm) {
+        assert_eq!(
+            0, self.current.used,
+            "the gc algorithm must be chosen before allocating any object"
+        );
+        let heap_size = self.heap_size();
+        (self.current, self.other) = Self::memory_chunks(heap_size, algorithm);
+        self.algorithm = algorithm;
+    }
+
+    /// Makes the heap start from the given size, and grow up to the maximum one following the
+    /// given policy. Like [set_algorithm], it must happen before the first allocation.
+    /// With [HeapGrowthPolicy::Fixed] the heap has its maximum size, regardless of the
+    /// initial one.
+    pub fn set_heap_growth(&mut self, initial_size: usize, policy: HeapGrowthPolicy) {
+        assert_eq!(
+            0, self.current.used,
+            "the heap size must be chosen before allocating any object"
+        );
+        let initial_size = match policy {
+            HeapGrowthPolicy::Fixed => self.max_size,
+            _ => initial_size.min(self.max_size),
+        };
+        (self.current, self.other) = Self::memory_chunks(initial_size, self.algorithm);
+        self.growth_policy = policy;
+    }
+
+    pub fn heap_growth_policy(&self) -> HeapGrowthPolicy {
+        self.growth_policy
+    }
+
+    /// The memory currently reserved for the heap, including the semi-space that is not used
+    /// for allocating
+    pub fn heap_size(&self) -> usize {
+        self.current.capacity + self.other.capacity
+    }
+
+    pub fn max_heap_size(&self) -> usize {
+        self.max_size
+    }
+
+    /// The memory that can still be allocated before the next collection
+    pub fn free_heap_size(&self) -> usize {
+        self.current.capacity - self.current.used
+    }
+
+    /// Whether the heap has not reached its maximum size yet
+    pub fn can_grow(&self) -> bool {
+        Self::chunk_capacities(self.max_size, self.algorithm).0 > self.current.capacity
+    }
+
+    /// The size that the heap grows to, when an allocation of the given size did not fit in
+    /// it even after a collection: the one given by the policy, or the one needed for the
+    /// allocation to succeed if larger, but never more than the maximum size
+    fn next_heap_size(&self, required_size: usize) -> usize {
+        let needed = self.heap_size_for(self.current.used.saturating_add(required_size));
+        self.growth_policy
+            .grow(self.heap_size())
+            .max(needed)
+            .min(self.max_size)
+    }
+
+    /// The number of objects allocated since the creation of the allocator, including the
+    /// ones that have since been garbage collected
+    pub fn allocated_objects_count(&self) -> u64 {
+        self.next_object_id - 1
+    }
+
+    fn next_object_id(&mut self) -> ObjectId {
+        let id = ObjectId::new(self.next_object_id);
+        self.next_object_id += 1;
+        id
+    }
+
     /// Allocates a new object, or returns None if the memory is full
     pub fn allocate_object(&mut self, class: &Class<'a>) -> Option<AbstractObject<'a>> {
         let size = AbstractObject::size_of_object(class);
-        self.current
-            .alloc(size)
-            .map(|alloc_entry| AbstractObject::new_object(class, alloc_entry))
+        let alloc_entry = self.current.alloc(size)?;
+        self.stats.record_allocation(size);
+        if class.has_finalizer {
+            self.finalizable_objects.push(alloc_entry.ptr);
+        }
+        Some(AbstractObject::new_object(
+            class,
+            alloc_entry,
+            self.next_object_id(),
+        ))
     }
 
     /// Allocates a new array, or returns None if the memory is full
@@ -122,41 +375,452 @@ impl<'a> ObjectAllocator<'a> {
         length: usize,
     ) -> Option<AbstractObject<'a>> {
         let size = AbstractObject::size_of_array(length);
-        self.current
-            .alloc(size)
-            .map(|alloc_entry| AbstractObject::new_array(elements_type, length, &alloc_entry))
+        let alloc_entry = self.current.alloc(size)?;
+        self.stats.record_allocation(size);
+        Some(AbstractObject::new_array(
+            elements_type,
+            length,
+            &alloc_entry,
+            self.next_object_id(),
+        ))
+    }
+
+    /// Returns all the objects allocated in the current chunk, in address order. Those that are
+    /// unreachable but have not been collected yet are included.
+    pub(crate) fn allocated_objects(&self) -> Vec<AbstractObject<'a>> {
+        let mut objects = Vec::new();
+        unsafe {
+            let end_ptr = self.current.memory.add(self.current.used);
+            let mut ptr = self.current.memory;
+            while ptr < end_ptr {
+                objects.push(AbstractObject::from_raw_ptr(ptr));
+                ptr = ptr.add(header_at(ptr).size());
+            }
+        }
+        objects
+    }
+
+    /// The statistics of all the collections and marking slices done so far
+    pub fn stats(&self) -> GcStats {
+        self.stats
+    }
+
+    /// Whether an incremental marking cycle is in progress
+    pub fn is_marking(&self) -> bool {
+        self.marking
+    }
+
+    /// Whether the incremental marking is enabled and the memory used has crossed the
+    /// threshold at which a new marking cycle should begin
+    pub fn should_start_marking(&self) -> bool {
+        match self.incremental_marking {
+            Some(config) if !self.marking => {
+                self.current.used * 100
+                    >= self.current.capacity * usize::from(config.start_threshold_percent)
+            }
+            _ => false,
+        }
+    }
+
+    /// Begins an incremental marking cycle, by marking the given roots
+    pub unsafe fn start_marking(&mut self, roots: Vec<*mut AbstractObject<'a>>) {
+        vm_log!(
+            self.log_config,
+            LogCategory::Gc,
+            Level::Info,
+            "starting incremental marking; currently allocated memory = {}, gc roots count: {}",
+            self.current.used,
+            roots.len()
+        );
+        self.marking = true;
+        for root in roots {
+            self.shade(address_of(root));
+        }
+    }
+
+    /// Executes a marking slice, scanning at most `budget` grey objects, and stopping at the
+    /// given deadline once at least one object has been scanned.
+    /// Returns whether there are no more grey objects to scan.
+    pub unsafe fn do_marking_slice_until(
+        &mut self,
+        budget: usize,
+        deadline: Option<Instant>,
+        class_resolver: &impl ClassByIdResolver<'a>,
+    ) -> Result<bool, VmError> {
+        let start = Instant::now();
+        let done = self.scan_grey_objects(budget, deadline, class_resolver)?;
+        self.stats.record_marking_slice(start.elapsed());
+        Ok(done)
+    }
+
+    /// Scans at most `budget` grey objects, stopping at the deadline once at least one object
+    /// has been scanned. Returns whether there are no more grey objects to scan.
+    unsafe fn scan_grey_objects(
+        &mut self,
+        budget: usize,
+        deadline: Option<Instant>,
+        class_resolver: &impl ClassByIdResolver<'a>,
+    ) -> Result<bool, VmError> {
+        for scanned in 0..budget {
+            if scanned > 0 && deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+                break;
+            }
+            let next = self.grey_objects.borrow_mut().pop();
+            match next {
+                Some(address) => self.scan_references(address, class_resolver)?,
+                None => break,
+            }
+        }
+        Ok(self.grey_objects.borrow().is_empty())
+    }
+
+    /// The write barrier, which must be invoked whenever a reference is stored into a field of
+    /// an object or an element of an array. While a marking cycle is in progress, the object
+    /// that contains the reference might have already been scanned, so we mark the stored
+    /// object eagerly, since the marking would otherwise never find it.
+    pub fn write_barrier(&self, value: &Value<'a>) {
+        if self.marking {
+            if let Value::Object(object) = value {
+                unsafe {
+                    let address = address_of(object);
+                    assert!(self.current.contains(address));
+                    self.shade(address);
+                }
+            }
+        }
+    }
+
+    /// Marks the object at the given address, if it was not marked already, and adds it to the
+    /// grey list, so that its references will be scanned by one of the next marking slices
+    unsafe fn shade(&self, address: *mut u8) {
+        let header = header_at(address);
+        if let GcState::Unmarked = header.state() {
+            header.set_state(GcState::Marked);
+            self.grey_objects.borrow_mut().push(address);
+        }
+    }
+
+    /// Marks all the objects referred by the fields or the entries of the given grey object
+    unsafe fn scan_references(
+        &self,
+        address: *mut u8,
+        class_resolver: &impl ClassByIdResolver<'a>,
+    ) -> Result<(), VmError> {
+        let object = AbstractObject::from_raw_ptr(address);
+        if header_at(address).kind() == ObjectKind::Object {
+            let class = class_resolver
+                .find_class_by_id(object.class_id())
+                .ok_or(VmError::ValidationException)?;
+            let skip_referent = self.discover_reference(&object, class);
+            for (index, _) in class.all_fields().enumerate().filter(|(index, f)| {
+                matches!(
+                    f.type_descriptor,
+                    FieldType::Object(_) | FieldType::Array(_)
+                ) && !(skip_referent && *index == REFERENT_FIELD_INDEX)
+            }) {
+                let referred_object =
+                    std::ptr::read(object.ptr_to_field_value(index) as *const *mut u8);
+                if !referred_object.is_null() {
+                    self.shade(referred_object);
+                }
+            }
+            return Ok(());
+        }
+
+        match object.elements_type() {
+            ArrayEntryType::Base(_) => Ok(()),
+            ArrayEntryType::Object(_) | ArrayEntryType::Array(_) => {
+                for i in 0..object.len().into_usize_safe() {
+                    match object.get_element(i) {
+                        Ok(Value::Object(array_element)) => self.shade(address_of(&array_element)),
+                        Ok(Value::Null) => {}
+                        _ => return Err(VmError::ValidationException),
+                    }
+                }
+                Ok(())
+            }
+        }
     }
 
     /// Runs the garbage collection! Will update the roots with the new addresses of the objects.
+    /// Weak roots do not keep their objects alive: they are updated if the object survived
+    /// the collection, and reset to `None` otherwise. The values of the ephemerons are updated
+    /// like the roots when their key survives, and left dangling otherwise.
     pub unsafe fn do_garbage_collection(
         &mut self,
         roots: Vec<*mut AbstractObject<'a>>,
+        weak_roots: Vec<*mut Option<AbstractObject<'a>>>,
+        ephemerons: Vec<Ephemeron<'a>>,
+        clear_soft_references: bool,
         class_resolver: &impl ClassByIdResolver<'a>,
-    ) -> Result<(), VmError> {
-        info!(
+    ) -> Result<CollectedObjects<'a>, VmError> {
+        self.collect(
+            roots,
+            weak_roots,
+            ephemerons,
+            clear_soft_references,
+            None,
+            class_resolver,
+        )
+    }
+
+    /// Like [do_garbage_collection], but also grows the heap, so that an allocation of the
+    /// given size will fit in it. The live objects are copied to a new, larger, chunk of memory,
+    /// whatever the algorithm. Soft references are not cleared, since there is more memory.
+    pub unsafe fn do_garbage_collection_growing_heap(
+        &mut self,
+        roots: Vec<*mut AbstractObject<'a>>,
+        weak_roots: Vec<*mut Option<AbstractObject<'a>>>,
+        ephemerons: Vec<Ephemeron<'a>>,
+        required_size: usize,
+        class_resolver: &impl ClassByIdResolver<'a>,
+    ) -> Result<CollectedObjects<'a>, VmError> {
+        let new_heap_size = self.next_heap_size(required_size);
+        self.collect(
+            roots,
+            weak_roots,
+            ephemerons,
+            false,
+            Some(new_heap_size),
+            class_resolver,
+        )
+    }
+
+    unsafe fn collect(
+        &mut self,
+        roots: Vec<*mut AbstractObject<'a>>,
+        weak_roots: Vec<*mut Option<AbstractObject<'a>>>,
+        ephemerons: Vec<Ephemeron<'a>>,
+        clear_soft_references: bool,
+        new_heap_size: Option<usize>,
+        class_resolver: &impl ClassByIdResolver<'a>,
+    ) -> Result<CollectedObjects<'a>, VmError> {
+        let start = Instant::now();
+        vm_log!(
+            self.log_config,
+            LogCategory::Gc,
+            Level::Info,
             "running gc; currently allocated memory = {}, gc roots count: {}",
             self.current.used,
             roots.len()
         );
 
-        // Copy all reachable objects to the other region
-        for root in roots.iter() {
-            self.visit(*root, class_resolver)?;
+        match new_heap_size {
+            Some(new_heap_size) => {
+                vm_log!(
+                    self.log_config,
+                    LogCategory::Gc,
+                    Level::Info,
+                    "growing heap from {} to {}",
+                    self.heap_size(),
+                    new_heap_size
+                );
+                let (capacity, _) = Self::chunk_capacities(new_heap_size, self.algorithm);
+                self.other = MemoryChunk::new(capacity);
+                self.compacting = false;
+            }
+            None => self.compacting = self.algorithm == GcAlgorithm::MarkCompact,
+        }
+
+        self.clear_soft_references = clear_soft_references;
+        let mut live_ephemerons = Vec::new();
+        let objects_to_finalize = if !self.compacting && !self.marking {
+            // Copy all reachable objects to the other region
+            for root in roots.iter() {
+                self.visit(*root, class_resolver)?;
+            }
+            let ephemerons =
+                self.mark_ephemerons(ephemerons, &mut live_ephemerons, class_resolver)?;
+            let objects_to_finalize = self.unmarked_finalizable_objects();
+            for address in objects_to_finalize.iter() {
+                let object = AbstractObject::from_raw_ptr(*address);
+                self.visit(&object, class_resolver)?;
+            }
+            self.mark_ephemerons(ephemerons, &mut live_ephemerons, class_resolver)?;
+            objects_to_finalize
+        } else {
+            // If a marking cycle is in progress, the roots might have changed since it began,
+            // and they are not covered by the write barrier: re-scan them, then finish it
+            for root in roots.iter() {
+                self.shade(address_of(*root));
+            }
+            while !self.scan_grey_objects(usize::MAX, None, class_resolver)? {}
+            let ephemerons =
+                self.mark_ephemerons(ephemerons, &mut live_ephemerons, class_resolver)?;
+            let objects_to_finalize = self.unmarked_finalizable_objects();
+            for address in objects_to_finalize.iter() {
+                self.shade(*address);
+            }
+            while !self.scan_grey_objects(usize::MAX, None, class_resolver)? {}
+            self.mark_ephemerons(ephemerons, &mut live_ephemerons, class_resolver)?;
+            self.marking = false;
+            if self.compacting {
+                self.compute_forwarding_addresses();
+            } else {
+                self.copy_marked_objects();
+            }
+            objects_to_finalize
+        };
+        self.clear_soft_references = false;
+        let cleared_references = self.clear_unreachable_referents();
+        let objects_to_finalize: Vec<*mut u8> = objects_to_finalize
+            .into_iter()
+            .map(|address| self.new_address_of(address))
+            .collect();
+        self.finalizable_objects = self
+            .finalizable_objects
+            .iter()
+            .map(|address| self.new_address_of(*address))
+            .collect();
+        if self.compacting {
+            self.fix_references_in_marked_objects(class_resolver)?;
+        } else {
+            self.fix_references_in_new_region(class_resolver)?;
         }
-        self.fix_references_in_new_region(class_resolver)?;
         for root in roots {
             self.fix_gc_root(root);
         }
+        for value in live_ephemerons
+            .into_iter()
+            .flat_map(|ephemeron| ephemeron.values)
+        {
+            self.fix_gc_root(value);
+        }
+        for weak_root in weak_roots {
+            self.fix_weak_root(weak_root);
+        }
 
-        // Swap regions and reset alloc pointer
-        std::mem::swap(&mut self.current, &mut self.other);
-        info!(
+        let previous_used = self.current.used;
+        if self.compacting {
+            self.slide_marked_objects();
+        } else {
+            // Swap regions and reset alloc pointer
+            std::mem::swap(&mut self.current, &mut self.other);
+            match new_heap_size {
+                None => self.other.reset(),
+                Some(new_heap_size) => {
+                    let (_, capacity) = Self::chunk_capacities(new_heap_size, self.algorithm);
+                    self.other = MemoryChunk::new(capacity);
+                }
+            }
+        }
+        vm_log!(
+            self.log_config,
+            LogCategory::Gc,
+            Level::Info,
             "gc done; previous allocated memory = {}, new allocated memory = {}",
-            self.other.used, self.current.used
+            previous_used,
+            self.current.used
         );
-        self.other.reset();
+        self.stats
+            .record_collection(start.elapsed(), previous_used, self.current.used);
 
-        Ok(())
+        Ok(CollectedObjects {
+            cleared_references: cleared_references
+                .into_iter()
+                .map(AbstractObject::from_raw_ptr)
+                .collect(),
+            objects_to_finalize: objects_to_finalize
+                .into_iter()
+                .map(AbstractObject::from_raw_ptr)
+                .collect(),
+        })
+    }
+
+    /// Removes from the tracked finalizable objects the ones that the marking did not reach,
+    /// and returns them
+    unsafe fn unmarked_finalizable_objects(&mut self) -> Vec<*mut u8> {
+        let (marked, unmarked) = self
+            .finalizable_objects
+            .iter()
+            .partition(|address| header_at(**address).state() == GcState::Marked);
+        self.finalizable_objects = marked;
+        unmarked
+    }
+
+    /// If the given object is an active reference with a referent, that must not be kept alive
+    /// by it, records it among the discovered ones and returns true
+    unsafe fn discover_reference(&self, object: &AbstractObject<'a>, class: &Class<'a>) -> bool {
+        match class.reference_kind {
+            None => return false,
+            Some(ReferenceKind::Soft) if !self.clear_soft_references => return false,
+            Some(_) => {}
+        }
+        let referent =
+            std::ptr::read(object.ptr_to_field_value(REFERENT_FIELD_INDEX) as *const *mut u8);
+        let next = std::ptr::read(object.ptr_to_field_value(NEXT_FIELD_INDEX) as *const *mut u8);
+        if referent.is_null() || !next.is_null() {
+            return false;
+        }
+        self.discovered_references
+            .borrow_mut()
+            .push(address_of(object));
+        true
+    }
+
+    /// Invoked after the marking, once the new addresses of the objects are known: clears the
+    /// referent of the discovered references whose referent was not marked, and returns the
+    /// new addresses of the references. Must run before the references are fixed, since the
+    /// referents that were not marked do not have a new address.
+    unsafe fn clear_unreachable_referents(&mut self) -> Vec<*mut u8> {
+        let mut cleared = Vec::new();
+        for address in self.discovered_references.take() {
+            let new_address = self.new_address_of(address);
+            // When compacting, the objects are moved only at the end of the collection
+            let object_address = if self.compacting {
+                address
+            } else {
+                new_address
+            };
+            let referent_ptr = AbstractObject::from_raw_ptr(object_address)
+                .ptr_to_field_value(REFERENT_FIELD_INDEX)
+                as *mut *mut u8;
+            let referent = std::ptr::read(referent_ptr);
+            if !referent.is_null() && header_at(referent).state() == GcState::Unmarked {
+                vm_log!(
+                    self.log_config,
+                    LogCategory::Gc,
+                    Level::Debug,
+                    "clearing referent of reference {:#0x}",
+                    new_address as u64
+                );
+                std::ptr::write(referent_ptr, null_mut());
+                cleared.push(new_address);
+            }
+        }
+        cleared
+    }
+
+    /// Visits, or marks if a marking cycle is in progress, the values of the ephemerons whose key
+    /// has been reached, and everything they refer to, until no more keys get reached. Moves
+    /// these ephemerons to `live_ephemerons`, and returns the ones whose key is still unreached.
+    unsafe fn mark_ephemerons(
+        &mut self,
+        mut ephemerons: Vec<Ephemeron<'a>>,
+        live_ephemerons: &mut Vec<Ephemeron<'a>>,
+        class_resolver: &impl ClassByIdResolver<'a>,
+    ) -> Result<Vec<Ephemeron<'a>>, VmError> {
+        let copying = !self.compacting && !self.marking;
+        loop {
+            let (reached, unreached): (Vec<_>, Vec<_>) = ephemerons
+                .into_iter()
+                .partition(|ephemeron| ephemeron.is_key_marked());
+            if reached.is_empty() {
+                return Ok(unreached);
+            }
+            for value in reached.iter().flat_map(|ephemeron| ephemeron.values.iter()) {
+                if copying {
+                    self.visit(*value, class_resolver)?;
+                } else {
+                    self.shade(address_of(*value));
+                }
+            }
+            if !copying {
+                while !self.scan_grey_objects(usize::MAX, None, class_resolver)? {}
+            }
+            live_ephemerons.extend(reached);
+            ephemerons = unreached;
+        }
     }
 
     /// Visits a given object, unless it was already processed.
@@ -212,6 +876,80 @@ impl<'a> ObjectAllocator<'a> {
         Ok(())
     }
 
+    /// Copies all the objects marked by an incremental marking cycle to the other region,
+    /// replacing their content with the forward reference to the copy, like [visit] does
+    unsafe fn copy_marked_objects(&mut self) {
+        let end_ptr = self.current.memory.add(self.current.used);
+        let mut ptr = self.current.memory;
+        while ptr < end_ptr {
+            let header = header_at(ptr);
+            let size = header.size();
+            if let GcState::Marked = header.state() {
+                let new_address = self
+                    .other
+                    .alloc(size)
+                    .expect("should have enough space in the other region")
+                    .ptr;
+                std::ptr::copy_nonoverlapping(ptr, new_address, size);
+                std::ptr::write(ptr.add(ALLOC_HEADER_SIZE) as *mut *mut u8, new_address);
+            }
+            ptr = ptr.add(size);
+        }
+    }
+
+    /// Assigns to each marked object the address it will have once all of them are packed at
+    /// the start of the memory, preserving their order
+    unsafe fn compute_forwarding_addresses(&mut self) {
+        let end_ptr = self.current.memory.add(self.current.used);
+        let mut ptr = self.current.memory;
+        let mut new_address = self.current.memory;
+        while ptr < end_ptr {
+            let header = header_at(ptr);
+            if let GcState::Marked = header.state() {
+                self.forwarding_addresses.insert(ptr, new_address);
+                new_address = new_address.add(header.size());
+            }
+            ptr = ptr.add(header.size());
+        }
+    }
+
+    /// Moves each marked object to its new address. Since they are walked in address order,
+    /// and every object moves towards the start of the memory, an object never overwrites
+    /// one that has not been moved yet.
+    unsafe fn slide_marked_objects(&mut self) {
+        let end_ptr = self.current.memory.add(self.current.used);
+        let mut ptr = self.current.memory;
+        let mut used = 0;
+        while ptr < end_ptr {
+            let header = header_at(ptr);
+            let size = header.size();
+            if let GcState::Marked = header.state() {
+                header.set_state(GcState::Unmarked);
+                std::ptr::copy(ptr, self.new_address_of(ptr), size);
+                used += size;
+            }
+            ptr = ptr.add(size);
+        }
+        self.current.truncate(used);
+        self.forwarding_addresses.clear();
+    }
+
+    /// Returns the address that a marked object will have after the collection: the address
+    /// of its copy, stored by [visit] in the word after its header, or the one computed by
+    /// [compute_forwarding_addresses]
+    unsafe fn new_address_of(&self, address: *mut u8) -> *mut u8 {
+        if self.compacting {
+            *self
+                .forwarding_addresses
+                .get(&address)
+                .expect("marked objects should have a forwarding address")
+        } else {
+            let new_address = std::ptr::read(address.add(ALLOC_HEADER_SIZE) as *const *mut u8);
+            assert!(self.other.contains(new_address));
+            new_address
+        }
+    }
+
     /// Invokes recursively [visit] on all field of the given object.
     unsafe fn visit_fields_of_object(
         &mut self,
@@ -222,18 +960,29 @@ impl<'a> ObjectAllocator<'a> {
             .find_class_by_id(object.class_id())
             .ok_or(VmError::ValidationException)?;
 
-        debug!("should visit members of {object:?} of class {}", class.name);
+        vm_log!(
+            self.log_config,
+            LogCategory::Gc,
+            Level::Debug,
+            "should visit members of {object:?} of class {}",
+            class.name
+        );
 
-        for (index, field) in class.all_fields().enumerate().filter(|(_, f)| {
+        let skip_referent = self.discover_reference(object, class);
+        for (index, field) in class.all_fields().enumerate().filter(|(index, f)| {
             matches!(
                 f.type_descriptor,
                 FieldType::Object(_) | FieldType::Array(_)
-            )
+            ) && !(skip_referent && *index == REFERENT_FIELD_INDEX)
         }) {
             let field_value_ptr = object.ptr_to_field_value(index);
-            debug!(
+            vm_log!(
+                self.log_config,
+                LogCategory::Gc,
+                Level::Debug,
                 "  should visit recursively field {} at offset {:#0x}",
-                field.name, field_value_ptr as u64
+                field.name,
+                field_value_ptr as u64
             );
 
             if 0 == std::ptr::read(field_value_ptr as *const u64) {
@@ -257,12 +1006,18 @@ impl<'a> ObjectAllocator<'a> {
                 // No objects are kept alive by this GC-reachable array!
                 Ok(())
             }
-            ArrayEntryType::Object(_) => {
+            ArrayEntryType::Object(_) | ArrayEntryType::Array(_) => {
                 for i in 0..array.len().into_usize_safe() {
                     let value = array.get_element(i);
                     match value {
                         Ok(Value::Object(array_element)) => {
-                            debug!("  should visit recursively element at index {}", i);
+                            vm_log!(
+                                self.log_config,
+                                LogCategory::Gc,
+                                Level::Debug,
+                                "  should visit recursively element at index {}",
+                                i
+                            );
                             self.visit(&array_element as *const AbstractObject, class_resolver)?;
                         }
                         Ok(Value::Null) => {
@@ -273,9 +1028,6 @@ impl<'a> ObjectAllocator<'a> {
                 }
                 Ok(())
             }
-            ArrayEntryType::Array => {
-                todo!("arrays of arrays are not supported yet")
-            }
         }
     }
 
@@ -303,6 +1055,29 @@ impl<'a> ObjectAllocator<'a> {
         Ok(())
     }
 
+    /// Updates the references in the marked objects, which have not been moved yet, with the
+    /// addresses computed by [compute_forwarding_addresses]
+    unsafe fn fix_references_in_marked_objects(
+        &mut self,
+        class_resolver: &impl ClassByIdResolver<'a>,
+    ) -> Result<(), VmError> {
+        let end_ptr = self.current.memory.add(self.current.used);
+        let mut ptr = self.current.memory;
+        while ptr < end_ptr {
+            let header = header_at(ptr);
+            if let GcState::Marked = header.state() {
+                let object = AbstractObject::from_raw_ptr(ptr);
+                if header.kind() == ObjectKind::Object {
+                    self.fix_references_in_object(object, class_resolver)?;
+                } else {
+                    self.fix_references_in_array(object)?;
+                }
+            }
+            ptr = ptr.add(header.size());
+        }
+        Ok(())
+    }
+
     /// Fixes all the references for each field in the given object
     unsafe fn fix_references_in_object(
         &self,
@@ -313,7 +1088,13 @@ impl<'a> ObjectAllocator<'a> {
             .find_class_by_id(object.class_id())
             .ok_or(VmError::ValidationException)?;
 
-        debug!("fixing members of {object:?} of class {}", class.name);
+        vm_log!(
+            self.log_config,
+            LogCategory::Gc,
+            Level::Debug,
+            "fixing members of {object:?} of class {}",
+            class.name
+        );
 
         for (index, field) in class.all_fields().enumerate().filter(|(_, f)| {
             matches!(
@@ -322,15 +1103,24 @@ impl<'a> ObjectAllocator<'a> {
             )
         }) {
             let field_value_ptr = object.ptr_to_field_value(index);
-            debug!(
+            vm_log!(
+                self.log_config,
+                LogCategory::Gc,
+                Level::Debug,
                 "  need to fix field {} at offset {:#0x}",
-                field.name, field_value_ptr as u64
+                field.name,
+                field_value_ptr as u64
             );
 
             let new_address = self.fix_reference(field_value_ptr);
-            debug!(
+            vm_log!(
+                self.log_config,
+                LogCategory::Gc,
+                Level::Debug,
                 "  fixed field {} at offset {:#0x} - new value is {:#0x}",
-                field.name, field_value_ptr as u64, new_address as u64
+                field.name,
+                field_value_ptr as u64,
+                new_address as u64
             );
         }
         Ok(())
@@ -343,45 +1133,48 @@ impl<'a> ObjectAllocator<'a> {
                 // No objects are kept alive by this GC-reachable array!
                 Ok(())
             }
-            ArrayEntryType::Object(class_id) => {
-                debug!("fixing entries of array {array:?} of type {class_id}");
+            elements_type @ (ArrayEntryType::Object(_) | ArrayEntryType::Array(_)) => {
+                vm_log!(
+                    self.log_config,
+                    LogCategory::Gc,
+                    Level::Debug,
+                    "fixing entries of array {array:?} of type {elements_type:?}"
+                );
                 for i in 0..array.len().into_usize_safe() {
                     let element_ptr = array.ptr_to_array_element(i);
-                    debug!(
+                    vm_log!(
+                        self.log_config,
+                        LogCategory::Gc,
+                        Level::Debug,
                         "  need to fix element {i} at offset {:#0x}",
                         element_ptr as u64
                     );
 
                     let new_address = self.fix_reference(element_ptr);
-                    debug!(
+                    vm_log!(
+                        self.log_config,
+                        LogCategory::Gc,
+                        Level::Debug,
                         "  fixed element {i} at offset {:#0x} - new value is {:#0x}",
-                        element_ptr as u64, new_address as u64
+                        element_ptr as u64,
+                        new_address as u64
                     );
                 }
                 Ok(())
             }
-            ArrayEntryType::Array => {
-                todo!("arrays of arrays are not supported yet")
-            }
         }
     }
 
-    /// Updates a reference from the old semispace by replacing it with the new value.
-    /// Assumes that the word after the old reference's header contains the address of the new
-    /// object, as set by [visit].
+    /// Updates a reference to a marked object by replacing it with the object's new address
     unsafe fn fix_reference(&self, field_value_ptr: *mut u8) -> *const u8 {
         if 0 == std::ptr::read(field_value_ptr as *const u64) {
             // Skip nulls
             return null();
         }
 
-        // Write new address, stored in the word after the header in the old object
-        let old_referred_object = std::ptr::read(field_value_ptr as *const *const u8);
+        let old_referred_object = std::ptr::read(field_value_ptr as *const *mut u8);
         assert!(self.current.contains(old_referred_object));
-
-        let word_after_header = old_referred_object.add(ALLOC_HEADER_SIZE) as *const *const u8;
-        let new_referred_object_address = std::ptr::read(word_after_header);
-        assert!(self.other.contains(new_referred_object_address));
+        let new_referred_object_address = self.new_address_of(old_referred_object);
 
         std::ptr::write(
             field_value_ptr as *mut *const u8,
@@ -392,14 +1185,499 @@ impl<'a> ObjectAllocator<'a> {
 
     /// Updates a gc root so that it points to the new object
     unsafe fn fix_gc_root(&self, root: *mut AbstractObject<'a>) {
-        debug!("fixing gc root {:#0x}", root as u64);
+        vm_log!(
+            self.log_config,
+            LogCategory::Gc,
+            Level::Debug,
+            "fixing gc root {:#0x}",
+            root as u64
+        );
         self.fix_reference(root as *mut u8);
-        debug!("  fixed gc root - new pointer is {:#0x}", root as u64);
+        vm_log!(
+            self.log_config,
+            LogCategory::Gc,
+            Level::Debug,
+            "  fixed gc root - new pointer is {:#0x}",
+            root as u64
+        );
+    }
+
+    /// Updates a weak root so that it points to the new object, or clears it if the object
+    /// was not reachable from any strong root, i.e. it was not copied by [visit]
+    unsafe fn fix_weak_root(&self, weak_root: *mut Option<AbstractObject<'a>>) {
+        if let Some(object) = &mut *weak_root {
+            let header =
+                &*(*(object as *const AbstractObject as *const *const u8) as *const AllocHeader);
+            match header.state() {
+                GcState::Marked => {
+                    self.fix_reference(object as *mut AbstractObject as *mut u8);
+                }
+                GcState::Unmarked => {
+                    vm_log!(
+                        self.log_config,
+                        LogCategory::Gc,
+                        Level::Debug,
+                        "clearing weak root {:#0x}",
+                        weak_root as u64
+                    );
+                    *weak_root = None;
+                }
+            }
+        }
     }
 }
 
+/// Returns the address of the given object, i.e. the address of its header
+unsafe fn address_of(object: *const AbstractObject) -> *mut u8 {
+    *(object as *const *mut u8)
+}
+
+unsafe fn header_at<'h>(address: *mut u8) -> &'h mut AllocHeader {
+    &mut *(address as *mut AllocHeader)
+}
+
 impl<'a> fmt::Debug for ObjectAllocator<'a> {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-        write!(f, "{{current_space={:?}}}", self.current)
+        write!(
+            f,
+            "{{current_space={:?}, allocated_objects={}}}",
+            self.current,
+            self.allocated_objects_count()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Instant;
+
+    use rjvm_reader::field_type::BaseType;
+
+    use crate::{
+        abstract_object::{AbstractObject, ObjectId},
+        array::Array,
+        array_entry_type::ArrayEntryType,
+        class::{ClassId, ClassRef},
+        class_resolver_by_id::ClassByIdResolver,
+        gc::{Ephemeron, ObjectAllocator},
+        gc_algorithm::GcAlgorithm,
+        heap_growth::HeapGrowthPolicy,
+        incremental_marking::IncrementalMarkingConfig,
+        value::Value,
+    };
+
+    struct NoClasses;
+
+    impl<'a> ClassByIdResolver<'a> for NoClasses {
+        fn find_class_by_id(&self, _: ClassId) -> Option<ClassRef<'a>> {
+            None
+        }
+    }
+
+    fn allocator_with_incremental_marking<'a>() -> ObjectAllocator<'a> {
+        let mut allocator = ObjectAllocator::with_maximum_memory(1024);
+        allocator.incremental_marking = Some(IncrementalMarkingConfig {
+            start_threshold_percent: 0,
+            objects_per_slice: 1,
+            slice_time_budget: None,
+        });
+        allocator
+    }
+
+    fn allocate_object_array<'a>(allocator: &mut ObjectAllocator<'a>) -> AbstractObject<'a> {
+        allocator
+            .allocate_array(ArrayEntryType::Object(ClassId::new(1)), 2)
+            .expect("should have enough memory")
+    }
+
+    fn allocate_int_array<'a>(allocator: &mut ObjectAllocator<'a>) -> AbstractObject<'a> {
+        allocator
+            .allocate_array(ArrayEntryType::Base(BaseType::Int), 2)
+            .expect("should have enough memory")
+    }
+
+    #[test]
+    fn object_ids_are_assigned_in_allocation_order() {
+        let mut allocator = ObjectAllocator::with_maximum_memory(1024);
+        let first = allocate_int_array(&mut allocator);
+        let second = allocate_int_array(&mut allocator);
+        assert_eq!(ObjectId::new(1), first.id());
+        assert_eq!(ObjectId::new(2), second.id());
+        assert_eq!(2, allocator.allocated_objects_count());
+    }
+
+    #[test]
+    fn object_ids_are_preserved_by_gc() {
+        let mut allocator = ObjectAllocator::with_maximum_memory(1024);
+        let _garbage = allocate_int_array(&mut allocator);
+        let mut survivor = allocate_int_array(&mut allocator);
+        survivor.set_element(1, Value::Int(42)).unwrap();
+        let address_before_gc = survivor.address();
+
+        unsafe {
+            allocator
+                .do_garbage_collection(vec![&mut survivor], vec![], vec![], false, &NoClasses)
+                .unwrap();
+        }
+
+        assert_ne!(address_before_gc, survivor.address());
+        assert_eq!(ObjectId::new(2), survivor.id());
+        assert_eq!(Value::Int(42), survivor.get_element(1).unwrap());
+
+        let new_object = allocate_int_array(&mut allocator);
+        assert_eq!(ObjectId::new(3), new_object.id());
+    }
+
+    #[test]
+    fn identity_hash_codes_are_preserved_by_gc() {
+        for algorithm in [GcAlgorithm::SemiSpaceCopying, GcAlgorithm::MarkCompact] {
+            let mut allocator = ObjectAllocator::new(1024, algorithm);
+            let _garbage = allocate_int_array(&mut allocator);
+            let mut survivor = allocate_int_array(&mut allocator);
+            assert_eq!(None, survivor.identity_hash_code());
+            // The highest bit of the field must not be read back as a sign
+            let hash_code = (1 << 29) | 12345;
+            survivor.set_identity_hash_code(hash_code);
+            let address_before_gc = survivor.address();
+
+            unsafe {
+                allocator
+                    .do_garbage_collection(vec![&mut survivor], vec![], vec![], false, &NoClasses)
+                    .unwrap();
+            }
+
+            assert_ne!(address_before_gc, survivor.address());
+            assert_eq!(Some(hash_code), survivor.identity_hash_code());
+        }
+    }
+
+    #[test]
+    fn stats_count_allocated_reclaimed_and_live_bytes() {
+        let array_size = u64::try_from(AbstractObject::size_of_array(2)).unwrap();
+        let mut allocator = ObjectAllocator::with_maximum_memory(1024);
+        let _garbage = allocate_int_array(&mut allocator);
+        let mut survivor = allocate_int_array(&mut allocator);
+        assert_eq!(2 * array_size, allocator.stats().bytes_allocated);
+        assert_eq!(0, allocator.stats().collections);
+
+        unsafe {
+            allocator
+                .do_garbage_collection(vec![&mut survivor], vec![], vec![], false, &NoClasses)
+                .unwrap();
+        }
+
+        let stats = allocator.stats();
+        assert_eq!(1, stats.collections);
+        assert_eq!(0, stats.marking_slices);
+        assert_eq!(2 * array_size, stats.bytes_allocated);
+        assert_eq!(array_size, stats.bytes_reclaimed);
+        assert_eq!(array_size, stats.live_bytes);
+        assert!(stats.max_pause <= stats.total_pause);
+    }
+
+    #[test]
+    fn mark_compact_slides_survivors_to_the_start_of_the_memory() {
+        let mut allocator = ObjectAllocator::new(1024, GcAlgorithm::MarkCompact);
+        let first_garbage = allocate_int_array(&mut allocator);
+        let start_address = first_garbage.address();
+        let mut garbage = Some(allocate_int_array(&mut allocator));
+        let mut root = allocate_object_array(&mut allocator);
+        let referred = allocate_int_array(&mut allocator);
+        referred.set_element(0, Value::Int(7)).unwrap();
+        root.set_element(1, Value::Object(referred)).unwrap();
+
+        unsafe {
+            allocator
+                .do_garbage_collection(
+                    vec![&mut root],
+                    vec![&mut garbage],
+                    vec![],
+                    false,
+                    &NoClasses,
+                )
+                .unwrap();
+        }
+
+        assert!(garbage.is_none());
+        assert_eq!(start_address, root.address());
+        assert_eq!(ObjectId::new(3), root.id());
+        assert_eq!(Value::Null, root.get_element(0).unwrap());
+        match root.get_element(1).unwrap() {
+            Value::Object(referred) => {
+                assert_eq!(ObjectId::new(4), referred.id());
+                assert_eq!(Value::Int(7), referred.get_element(0).unwrap());
+            }
+            other => panic!("expected an object but got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn mark_compact_can_use_the_whole_memory() {
+        let mut copying = ObjectAllocator::with_maximum_memory(1024);
+        assert!(copying
+            .allocate_array(ArrayEntryType::Base(BaseType::Int), 80)
+            .is_none());
+
+        let mut compacting = ObjectAllocator::new(1024, GcAlgorithm::MarkCompact);
+        assert!(compacting
+            .allocate_array(ArrayEntryType::Base(BaseType::Int), 80)
+            .is_some());
+    }
+
+    #[test]
+    fn growing_the_heap_preserves_the_survivors() {
+        let small_array_size = AbstractObject::size_of_array(2);
+        let large_array_size = AbstractObject::size_of_array(16);
+        // The survivor and the large array do not fit together in a semi-space
+        let initial_size = 2 * (small_array_size + large_array_size - 8);
+        let mut allocator = ObjectAllocator::with_maximum_memory(1024 * 1024);
+        allocator.set_heap_growth(initial_size, HeapGrowthPolicy::Doubling);
+        assert_eq!(initial_size, allocator.heap_size());
+
+        let mut survivor = allocate_int_array(&mut allocator);
+        survivor.set_element(1, Value::Int(42)).unwrap();
+        assert!(allocator
+            .allocate_array(ArrayEntryType::Base(BaseType::Int), 16)
+            .is_none());
+
+        unsafe {
+            allocator
+                .do_garbage_collection_growing_heap(
+                    vec![&mut survivor],
+                    vec![],
+                    vec![],
+                    large_array_size,
+                    &NoClasses,
+                )
+                .unwrap();
+        }
+
+        assert_eq!(2 * initial_size, allocator.heap_size());
+        assert_eq!(ObjectId::new(1), survivor.id());
+        assert_eq!(Value::Int(42), survivor.get_element(1).unwrap());
+        assert!(allocator
+            .allocate_array(ArrayEntryType::Base(BaseType::Int), 16)
+            .is_some());
+    }
+
+    #[test]
+    fn heap_grows_enough_for_the_allocation_but_not_beyond_the_maximum() {
+        let large_array_size = AbstractObject::size_of_array(16);
+        let max_size = 2 * large_array_size;
+        let mut allocator = ObjectAllocator::new(max_size, GcAlgorithm::MarkCompact);
+        allocator.set_heap_growth(large_array_size / 2, HeapGrowthPolicy::Percentage(10));
+        assert!(allocator.can_grow());
+
+        // Ten percent more would not be enough for the array
+        unsafe {
+            allocator
+                .do_garbage_collection_growing_heap(
+                    vec![],
+                    vec![],
+                    vec![],
+                    large_array_size,
+                    &NoClasses,
+                )
+                .unwrap();
+        }
+        assert_eq!(large_array_size, allocator.heap_size());
+        assert!(allocator.can_grow());
+
+        let mut survivor = allocator
+            .allocate_array(ArrayEntryType::Base(BaseType::Int), 16)
+            .unwrap();
+        unsafe {
+            allocator
+                .do_garbage_collection_growing_heap(
+                    vec![&mut survivor],
+                    vec![],
+                    vec![],
+                    2 * large_array_size,
+                    &NoClasses,
+                )
+                .unwrap();
+        }
+        assert_eq!(max_size, allocator.heap_size());
+        assert!(!allocator.can_grow());
+        assert_eq!(ObjectId::new(1), survivor.id());
+    }
+
+    #[test]
+    fn fixed_heap_has_the_maximum_size() {
+        let mut allocator = ObjectAllocator::with_maximum_memory(1024);
+        allocator.set_heap_growth(128, HeapGrowthPolicy::Fixed);
+        assert_eq!(1024, allocator.heap_size());
+        assert!(!allocator.can_grow());
+    }
+
+    #[test]
+    fn incremental_marking_keeps_reachable_objects_alive() {
+        let mut allocator = allocator_with_incremental_marking();
+        let mut root = allocate_object_array(&mut allocator);
+        let referred = allocate_int_array(&mut allocator);
+        referred.set_element(0, Value::Int(7)).unwrap();
+        root.set_element(0, Value::Object(referred)).unwrap();
+        let mut garbage = Some(allocate_int_array(&mut allocator));
+
+        assert!(allocator.should_start_marking());
+        unsafe {
+            allocator.start_marking(vec![&mut root]);
+            assert!(allocator.is_marking());
+            assert!(!allocator
+                .do_marking_slice_until(1, None, &NoClasses)
+                .unwrap());
+            assert!(allocator
+                .do_marking_slice_until(1, None, &NoClasses)
+                .unwrap());
+        }
+        assert_eq!(2, allocator.stats().marking_slices);
+        unsafe {
+            allocator
+                .do_garbage_collection(
+                    vec![&mut root],
+                    vec![&mut garbage],
+                    vec![],
+                    false,
+                    &NoClasses,
+                )
+                .unwrap();
+        }
+
+        assert!(!allocator.is_marking());
+        assert!(garbage.is_none());
+        // Finishing the marking during the collection is not a slice
+        assert_eq!(2, allocator.stats().marking_slices);
+        assert_eq!(1, allocator.stats().collections);
+        match root.get_element(0).unwrap() {
+            Value::Object(referred) => assert_eq!(Value::Int(7), referred.get_element(0).unwrap()),
+            other => panic!("expected an object but got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn marking_slice_stops_at_deadline_after_scanning_one_object() {
+        let mut allocator = allocator_with_incremental_marking();
+        let mut root = allocate_object_array(&mut allocator);
+        root.set_element(0, Value::Object(allocate_int_array(&mut allocator)))
+            .unwrap();
+        root.set_element(1, Value::Object(allocate_int_array(&mut allocator)))
+            .unwrap();
+
+        let expired = Some(Instant::now());
+        unsafe {
+            allocator.start_marking(vec![&mut root]);
+            // The root, and then each of the arrays
+            assert!(!allocator
+                .do_marking_slice_until(usize::MAX, expired, &NoClasses)
+                .unwrap());
+            assert!(!allocator
+                .do_marking_slice_until(usize::MAX, expired, &NoClasses)
+                .unwrap());
+            assert!(allocator
+                .do_marking_slice_until(usize::MAX, expired, &NoClasses)
+                .unwrap());
+        }
+    }
+
+    #[test]
+    fn write_barrier_marks_objects_stored_into_scanned_objects() {
+        let mut allocator = allocator_with_incremental_marking();
+        let mut root = allocate_object_array(&mut allocator);
+        let stored_later = allocate_int_array(&mut allocator);
+        stored_later.set_element(1, Value::Int(3)).unwrap();
+        let mut stored_later_weak_ref = Some(stored_later.clone());
+
+        unsafe {
+            allocator.start_marking(vec![&mut root]);
+            assert!(allocator
+                .do_marking_slice_until(1, None, &NoClasses)
+                .unwrap());
+        }
+
+        // The root has already been scanned, so without the barrier the stored array
+        // would never be marked
+        let stored_value = Value::Object(stored_later);
+        allocator.write_barrier(&stored_value);
+        root.set_element(1, stored_value).unwrap();
+
+        unsafe {
+            allocator
+                .do_garbage_collection(
+                    vec![&mut root],
+                    vec![&mut stored_later_weak_ref],
+                    vec![],
+                    false,
+                    &NoClasses,
+                )
+                .unwrap();
+        }
+
+        assert!(stored_later_weak_ref.is_some());
+        match root.get_element(1).unwrap() {
+            Value::Object(stored) => assert_eq!(Value::Int(3), stored.get_element(1).unwrap()),
+            other => panic!("expected an object but got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn ephemerons_keep_their_values_alive_only_with_their_key() {
+        for (algorithm, marking) in [
+            (GcAlgorithm::SemiSpaceCopying, false),
+            (GcAlgorithm::SemiSpaceCopying, true),
+            (GcAlgorithm::MarkCompact, false),
+        ] {
+            let mut allocator = ObjectAllocator::new(2048, algorithm);
+            let mut root = allocate_object_array(&mut allocator);
+            let mut root_key = Some(root.clone());
+            // Reachable only through the value of the ephemeron of the root
+            let chained = allocate_int_array(&mut allocator);
+            let mut chained_key = Some(chained.clone());
+            let mut root_value = allocate_object_array(&mut allocator);
+            root_value.set_element(0, Value::Object(chained)).unwrap();
+            let mut chained_value = allocate_int_array(&mut allocator);
+            chained_value.set_element(0, Value::Int(5)).unwrap();
+            let mut garbage_key = Some(allocate_int_array(&mut allocator));
+            let mut garbage_value = allocate_int_array(&mut allocator);
+            let mut garbage_value_weak_ref = Some(garbage_value.clone());
+
+            unsafe {
+                if marking {
+                    allocator.start_marking(vec![&mut root]);
+                }
+                allocator
+                    .do_garbage_collection(
+                        vec![&mut root],
+                        vec![
+                            &mut root_key,
+                            &mut chained_key,
+                            &mut garbage_key,
+                            &mut garbage_value_weak_ref,
+                        ],
+                        vec![
+                            Ephemeron {
+                                key: &mut garbage_key,
+                                values: vec![&mut garbage_value],
+                            },
+                            Ephemeron {
+                                key: &mut chained_key,
+                                values: vec![&mut chained_value],
+                            },
+                            Ephemeron {
+                                key: &mut root_key,
+                                values: vec![&mut root_value],
+                            },
+                        ],
+                        false,
+                        &NoClasses,
+                    )
+                    .unwrap();
+            }
+
+            assert!(root_key.is_some());
+            assert!(chained_key.is_some());
+            assert_eq!(Value::Int(5), chained_value.get_element(0).unwrap());
+            assert!(garbage_key.is_none());
+            assert!(garbage_value_weak_ref.is_none());
+        }
     }
 }
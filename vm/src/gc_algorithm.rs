@@ -0,0 +1,18 @@
+/// Selects how the garbage collector reclaims the memory of the unreachable objects.
+///
+/// Both algorithms compact the surviving objects at the start of the heap, so that the free
+/// memory is always contiguous: the heap never gets fragmented, and allocating an object just
+/// bumps a pointer. They differ in how much of the memory they can use, and in how much work
+/// a collection does.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum GcAlgorithm {
+    /// Splits the memory in two semi-spaces, and allocates in one of them. The collection
+    /// copies the reachable objects to the other one while tracing them, so the heap is
+    /// traversed only once, but only half of the memory can be used.
+    #[default]
+    SemiSpaceCopying,
+    /// Uses the whole memory as a single space. The collection marks the reachable objects in
+    /// place, computes the address of each of them once they are packed at the start of the
+    /// heap, fixes the references, and finally slides the objects to their new address.
+    MarkCompact,
+}
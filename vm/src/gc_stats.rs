@@ -0,0 +1,56 @@
+use std::time::Duration;
+
+/// Statistics of the garbage collector, accumulated since the vm was created.
+///
+/// The pauses include both the collections and the slices of the incremental marking (see
+/// [IncrementalMarkingConfig](crate::incremental_marking::IncrementalMarkingConfig)), since
+/// the java code does not run during either of them.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct GcStats {
+    /// The number of collections, including the ones that grew the heap
+    pub collections: u64,
+    /// The number of incremental marking slices
+    pub marking_slices: u64,
+    /// The total time spent in collections and marking slices
+    pub total_pause: Duration,
+    /// The longest single pause
+    pub max_pause: Duration,
+    /// The total number of bytes allocated on the heap
+    pub bytes_allocated: u64,
+    /// The total number of bytes freed by the collections
+    pub bytes_reclaimed: u64,
+    /// The number of bytes used by the objects that survived the last collection
+    pub live_bytes: u64,
+}
+
+impl GcStats {
+    pub(crate) fn record_allocation(&mut self, size: usize) {
+        self.bytes_allocated += to_u64(size);
+    }
+
+    pub(crate) fn record_marking_slice(&mut self, pause: Duration) {
+        self.marking_slices += 1;
+        self.record_pause(pause);
+    }
+
+    pub(crate) fn record_collection(
+        &mut self,
+        pause: Duration,
+        used_before: usize,
+        used_after: usize,
+    ) {
+        self.collections += 1;
+        self.bytes_reclaimed += to_u64(used_before.saturating_sub(used_after));
+        self.live_bytes = to_u64(used_after);
+        self.record_pause(pause);
+    }
+
+    fn record_pause(&mut self, pause: Duration) {
+        self.total_pause += pause;
+        self.max_pause = self.max_pause.max(pause);
+    }
+}
+
+fn to_u64(size: usize) -> u64 {
+    u64::try_from(size).unwrap_or(u64::MAX)
+}
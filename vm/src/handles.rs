@@ -0,0 +1,260 @@
+use std::{cell::RefCell, rc::Rc};
+
+use crate::{abstract_object::AbstractObject, value::Value};
+
+/// The objects referred by the handles, shared by the vm, all the [HandleScope]s and all the
+/// [WeakHandle]s.
+///
+/// Slots are reused once released but never removed, so that the gc can update the objects
+/// through pointers to the slots. Scopes are not necessarily released in the order in which
+/// they were created, since each java thread can have its own.
+#[derive(Debug, Default)]
+pub(crate) struct HandleTable<'a> {
+    slots: Vec<Option<AbstractObject<'a>>>,
+    free_slots: Vec<usize>,
+    /// The objects of the weak handles, which the gc resets to `None` once collected
+    weak_slots: Vec<Option<AbstractObject<'a>>>,
+    free_weak_slots: Vec<usize>,
+}
+
+impl<'a> HandleTable<'a> {
+    fn register(&mut self, object: AbstractObject<'a>) -> usize {
+        match self.free_slots.pop() {
+            Some(index) => {
+                self.slots[index] = Some(object);
+                index
+            }
+            None => {
+                self.slots.push(Some(object));
+                self.slots.len() - 1
+            }
+        }
+    }
+
+    fn release(&mut self, index: usize) {
+        self.slots[index] = None;
+        self.free_slots.push(index);
+    }
+
+    fn get(&self, index: usize) -> AbstractObject<'a> {
+        self.slots[index]
+            .clone()
+            .expect("handles should refer to a registered object")
+    }
+
+    fn register_weak(&mut self, object: Option<AbstractObject<'a>>) -> usize {
+        match self.free_weak_slots.pop() {
+            Some(index) => {
+                self.weak_slots[index] = object;
+                index
+            }
+            None => {
+                self.weak_slots.push(object);
+                self.weak_slots.len() - 1
+            }
+        }
+    }
+
+    fn release_weak(&mut self, index: usize) {
+        self.weak_slots[index] = None;
+        self.free_weak_slots.push(index);
+    }
+
+    pub(crate) fn gc_roots(&mut self) -> impl Iterator<Item = *mut AbstractObject<'a>> + '_ {
+        self.slots
+            .iter_mut()
+            .filter_map(|slot| slot.as_mut())
+            .map(|object| object as *mut AbstractObject<'a>)
+    }
+
+    /// The released slots are `None`, so the gc ignores them
+    pub(crate) fn weak_roots(
+        &mut self,
+    ) -> impl Iterator<Item = *mut Option<AbstractObject<'a>>> + '_ {
+        self.weak_slots
+            .iter_mut()
+            .map(|slot| slot as *mut Option<AbstractObject<'a>>)
+    }
+}
+
+/// Keeps alive the objects held by Rust code, i.e. by native methods or by embedders, until
+/// it is dropped. Since the gc moves the objects, an [AbstractObject] held in a local variable
+/// is invalid after anything that can allocate, such as creating a string, invoking a method
+/// or yielding to another thread, unless it is rooted by a [Handle], which must then be used
+/// to get its current address.
+///
+/// A scope does not borrow the vm, so it can be kept while invoking the vm's methods.
+#[derive(Debug)]
+pub struct HandleScope<'a> {
+    table: Rc<RefCell<HandleTable<'a>>>,
+    slots: RefCell<Vec<usize>>,
+}
+
+impl<'a> HandleScope<'a> {
+    pub(crate) fn new(table: Rc<RefCell<HandleTable<'a>>>) -> Self {
+        Self {
+            table,
+            slots: RefCell::new(Vec::new()),
+        }
+    }
+
+    /// Roots the given object until the scope is dropped
+    pub fn handle(&self, object: AbstractObject<'a>) -> Handle<'_, 'a> {
+        let index = self.table.borrow_mut().register(object);
+        self.slots.borrow_mut().push(index);
+        Handle { scope: self, index }
+    }
+
+    /// Roots the given value, if it is an object
+    pub fn handle_value(&self, value: &Value<'a>) -> Option<Handle<'_, 'a>> {
+        match value {
+            Value::Object(object) => Some(self.handle(object.clone())),
+            _ => None,
+        }
+    }
+}
+
+impl<'a> Drop for HandleScope<'a> {
+    fn drop(&mut self) {
+        let mut table = self.table.borrow_mut();
+        for index in self.slots.get_mut().drain(..) {
+            table.release(index);
+        }
+    }
+}
+
+/// An object rooted by a [HandleScope], which the gc keeps alive and updates when moving it
+#[derive(Debug, Clone, Copy)]
+pub struct Handle<'s, 'a> {
+    scope: &'s HandleScope<'a>,
+    index: usize,
+}
+
+impl<'s, 'a> Handle<'s, 'a> {
+    /// Returns the object, at its current address
+    pub fn get(&self) -> AbstractObject<'a> {
+        self.scope.table.borrow().get(self.index)
+    }
+
+    pub fn value(&self) -> Value<'a> {
+        Value::Object(self.get())
+    }
+
+    /// Creates a weak handle to the same object
+    pub fn downgrade(&self) -> WeakHandle<'a> {
+        WeakHandle::new(self.scope.table.clone(), Some(self.get()))
+    }
+}
+
+/// Refers to an object without keeping it alive: once the gc has collected the object, the
+/// handle returns `None`. Unlike a [Handle], it is not bound to a scope, so that it can be
+/// kept as long as needed, i.e. in the caches maintained by the embedder.
+#[derive(Debug)]
+pub struct WeakHandle<'a> {
+    table: Rc<RefCell<HandleTable<'a>>>,
+    index: usize,
+}
+
+impl<'a> WeakHandle<'a> {
+    pub(crate) fn new(
+        table: Rc<RefCell<HandleTable<'a>>>,
+        object: Option<AbstractObject<'a>>,
+    ) -> Self {
+        let index = table.borrow_mut().register_weak(object);
+        Self { table, index }
+    }
+
+    /// Returns the object, at its current address, or `None` if it has been collected.
+    /// To keep using it across an allocation, it must be rooted with a [Handle].
+    pub fn get(&self) -> Option<AbstractObject<'a>> {
+        self.table.borrow().weak_slots[self.index].clone()
+    }
+
+    /// Roots the object in the given scope, if it has not been collected yet
+    pub fn upgrade<'s>(&self, scope: &'s HandleScope<'a>) -> Option<Handle<'s, 'a>> {
+        self.get().map(|object| scope.handle(object))
+    }
+
+    pub fn is_collected(&self) -> bool {
+        self.get().is_none()
+    }
+}
+
+impl<'a> Clone for WeakHandle<'a> {
+    fn clone(&self) -> Self {
+        Self::new(self.table.clone(), self.get())
+    }
+}
+
+impl<'a> Drop for WeakHandle<'a> {
+    fn drop(&mut self) {
+        self.table.borrow_mut().release_weak(self.index);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{cell::RefCell, rc::Rc};
+
+    use rjvm_reader::field_type::BaseType;
+
+    use crate::{
+        array_entry_type::ArrayEntryType,
+        gc::ObjectAllocator,
+        handles::{HandleScope, HandleTable, WeakHandle},
+    };
+
+    #[test]
+    fn scopes_release_their_slots_in_any_order() {
+        let mut allocator = ObjectAllocator::with_maximum_memory(1024);
+        let mut allocate = || {
+            allocator
+                .allocate_array(ArrayEntryType::Base(BaseType::Int), 1)
+                .unwrap()
+        };
+        let table = Rc::new(RefCell::new(HandleTable::default()));
+
+        let first_scope = HandleScope::new(table.clone());
+        let first_index = first_scope.handle(allocate()).index;
+        let second_scope = HandleScope::new(table.clone());
+        second_scope.handle(allocate());
+        second_scope.handle(allocate());
+        assert_eq!(3, table.borrow_mut().gc_roots().count());
+
+        drop(first_scope);
+        assert_eq!(2, table.borrow_mut().gc_roots().count());
+
+        // The released slot is reused
+        let third_scope = HandleScope::new(table.clone());
+        let third = third_scope.handle(allocate());
+        assert_eq!(first_index, third.index);
+        assert_eq!(3, table.borrow_mut().gc_roots().count());
+
+        drop(second_scope);
+        drop(third_scope);
+        assert_eq!(0, table.borrow_mut().gc_roots().count());
+    }
+
+    #[test]
+    fn weak_handles_release_their_slots_when_dropped() {
+        let mut allocator = ObjectAllocator::with_maximum_memory(1024);
+        let object = allocator
+            .allocate_array(ArrayEntryType::Base(BaseType::Int), 1)
+            .unwrap();
+        let table = Rc::new(RefCell::new(HandleTable::default()));
+
+        let weak = WeakHandle::new(table.clone(), Some(object.clone()));
+        let copy = weak.clone();
+        assert_ne!(weak.index, copy.index);
+        assert!(copy.get().is_some_and(|copy| copy.is_same_as(&object)));
+
+        let released_index = weak.index;
+        drop(weak);
+        assert!(!copy.is_collected());
+        let other = WeakHandle::new(table.clone(), None);
+        assert_eq!(released_index, other.index);
+        assert!(other.is_collected());
+        // Weak handles never root their objects
+        assert_eq!(0, table.borrow_mut().gc_roots().count());
+    }
+}
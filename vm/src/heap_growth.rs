@@ -0,0 +1,43 @@
+/// How the heap grows, from its initial size up to its maximum one.
+///
+/// The heap grows only when it is still too full for an allocation after a garbage collection.
+/// Growing moves all the live objects to the newly allocated memory, like a collection of the
+/// semi-space copying algorithm would do. Each growth is large enough for the pending
+/// allocation to succeed, and the heap never becomes larger than its maximum size.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum HeapGrowthPolicy {
+    /// The heap has its maximum size from the start, and never grows
+    #[default]
+    Fixed,
+    /// The heap doubles its size each time it grows
+    Doubling,
+    /// The heap grows by the given percentage of its current size
+    Percentage(u16),
+}
+
+impl HeapGrowthPolicy {
+    /// Returns the size that a heap of the given size should grow to, regardless of the
+    /// maximum size
+    pub fn grow(&self, heap_size: usize) -> usize {
+        match self {
+            HeapGrowthPolicy::Fixed => heap_size,
+            HeapGrowthPolicy::Doubling => heap_size.saturating_mul(2),
+            HeapGrowthPolicy::Percentage(percent) => {
+                heap_size.saturating_add(heap_size / 100 * usize::from(*percent))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::heap_growth::HeapGrowthPolicy;
+
+    #[test]
+    fn growth_policies() {
+        assert_eq!(1000, HeapGrowthPolicy::Fixed.grow(1000));
+        assert_eq!(2000, HeapGrowthPolicy::Doubling.grow(1000));
+        assert_eq!(1250, HeapGrowthPolicy::Percentage(25).grow(1000));
+        assert_eq!(usize::MAX, HeapGrowthPolicy::Doubling.grow(usize::MAX));
+    }
+}
@@ -0,0 +1,77 @@
+use rjvm_reader::type_conversion::ToUsizeSafe;
+
+use crate::{
+    abstract_object::{AbstractObject, ObjectId, ObjectKind},
+    array::Array,
+    array_entry_type::ArrayEntryType,
+    class::ClassRef,
+    object::Object,
+    object_fields::{get_object_fields, static_fields, ObjectField},
+    value::Value,
+    vm::Vm,
+    vm_error::VmError,
+};
+
+/// A snapshot of a live object of the heap, as reported by [Vm::for_each_live_object]
+#[derive(Debug, Clone)]
+pub struct HeapObject<'a> {
+    pub id: ObjectId,
+    /// The number of bytes used on the heap by the object, including its headers
+    pub size: usize,
+    pub contents: HeapObjectContents<'a>,
+}
+
+/// What a [HeapObject] contains. The values that are objects remain valid only until the
+/// next allocation, since the gc might move them.
+#[derive(Debug, Clone)]
+pub enum HeapObjectContents<'a> {
+    /// An instance of a class, with all its instance fields, including the inherited ones,
+    /// in the order of [get_object_fields]
+    Instance {
+        class: ClassRef<'a>,
+        fields: Vec<ObjectField<'a>>,
+    },
+    /// The object that the vm uses to store the static fields of a class, with the fields
+    /// declared by the class. It is alive as long as the class is loaded.
+    Statics {
+        class: ClassRef<'a>,
+        fields: Vec<ObjectField<'a>>,
+    },
+    /// An array, with all its elements
+    Array {
+        elements_type: ArrayEntryType,
+        elements: Vec<Value<'a>>,
+    },
+}
+
+impl<'a> HeapObject<'a> {
+    /// Takes a snapshot of the given object. If it stores the static fields of a class, that
+    /// class must be given.
+    pub(crate) fn of(
+        vm: &Vm<'a>,
+        object: &AbstractObject<'a>,
+        statics_of: Option<ClassRef<'a>>,
+    ) -> Result<Self, VmError> {
+        let contents = match (object.kind(), statics_of) {
+            (ObjectKind::Object, Some(class)) => HeapObjectContents::Statics {
+                class,
+                fields: static_fields(class, object),
+            },
+            (ObjectKind::Object, None) => HeapObjectContents::Instance {
+                class: vm.get_class_by_id(object.class_id())?,
+                fields: get_object_fields(vm, object)?,
+            },
+            (ObjectKind::Array, _) => HeapObjectContents::Array {
+                elements_type: object.elements_type(),
+                elements: (0..object.len())
+                    .map(|index| object.get_element(index.into_usize_safe()))
+                    .collect::<Result<_, _>>()?,
+            },
+        };
+        Ok(Self {
+            id: object.id(),
+            size: object.alloc_size(),
+            contents,
+        })
+    }
+}
@@ -0,0 +1,56 @@
+/// The number of bits of the identity hash codes, which are stored in the [AllocHeader]
+/// together with other data
+///
+/// [AllocHeader]: crate::abstract_object::AllocHeader
+pub(crate) const IDENTITY_HASH_BITS: u32 = 30;
+
+/// Generates the identity hash codes, which are assigned lazily, the first time that
+/// `Object.hashCode` or `System.identityHashCode` is invoked on an object. Since the gc moves
+/// the objects, the hash cannot be derived from their address; rather, like HotSpot does, we
+/// use Marsaglia's xor-shift generator, and store the result in the object's header.
+#[derive(Debug)]
+pub(crate) struct IdentityHashGenerator {
+    state: [u32; 4],
+}
+
+impl Default for IdentityHashGenerator {
+    fn default() -> Self {
+        Self {
+            state: [0x75bc_d15b, 842_502_087, 0x8767, 273_326_509],
+        }
+    }
+}
+
+impl IdentityHashGenerator {
+    /// Returns a new hash code, which is never zero, since zero marks the objects that have
+    /// not been assigned one yet
+    pub fn next_hash_code(&mut self) -> i32 {
+        loop {
+            let [x, y, z, w] = self.state;
+            let t = x ^ (x << 11);
+            let next = (w ^ (w >> 19)) ^ (t ^ (t >> 8));
+            self.state = [y, z, w, next];
+            let hash = next & ((1 << IDENTITY_HASH_BITS) - 1);
+            if hash != 0 {
+                return hash as i32;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use crate::identity_hash::{IdentityHashGenerator, IDENTITY_HASH_BITS};
+
+    #[test]
+    fn hash_codes_are_positive_and_fit_in_the_header() {
+        let mut generator = IdentityHashGenerator::default();
+        let hash_codes: HashSet<i32> = (0..1000).map(|_| generator.next_hash_code()).collect();
+        assert_eq!(1000, hash_codes.len());
+        assert!(hash_codes
+            .iter()
+            .all(|hash_code| *hash_code > 0 && *hash_code < (1 << IDENTITY_HASH_BITS)));
+    }
+}
@@ -0,0 +1,37 @@
+use std::time::Duration;
+
+/// Configures the incremental marking mode of the garbage collector.
+///
+/// By default, the whole heap is traced in a single stop-the-world pause when the memory is
+/// full. With incremental marking, once the heap occupancy passes a threshold, the tracing is
+/// instead split in many small slices, executed between two instructions, each of which scans
+/// at most [objects_per_slice](Self::objects_per_slice) objects, and lasts at most
+/// [slice_time_budget](Self::slice_time_budget), if given. A write barrier on the stores
+/// of references keeps the marking correct while the java code keeps running.
+///
+/// Note that the final collection still happens in one pause: it re-scans the gc roots,
+/// finishes tracing whatever was left, and copies the surviving objects to the other
+/// semi-space, or compacts them (see [GcAlgorithm](crate::gc_algorithm::GcAlgorithm)).
+/// Only the tracing of the heap is spread over time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IncrementalMarkingConfig {
+    /// The heap occupancy, as a percentage of the available memory, at which
+    /// a new marking cycle is started
+    pub start_threshold_percent: u8,
+    /// The pause budget: the maximum number of objects scanned by a single marking slice
+    pub objects_per_slice: usize,
+    /// An additional pause budget, measured in time: a slice stops once it is exhausted, even
+    /// if it has scanned fewer objects than allowed. Each slice scans at least one object,
+    /// so that the marking always progresses.
+    pub slice_time_budget: Option<Duration>,
+}
+
+impl Default for IncrementalMarkingConfig {
+    fn default() -> Self {
+        Self {
+            start_threshold_percent: 50,
+            objects_per_slice: 64,
+            slice_time_budget: None,
+        }
+    }
+}
@@ -0,0 +1,68 @@
+use std::collections::HashMap;
+
+use crate::{class::ClassId, class_and_method::ClassAndMethod, instruction_site::InstructionSite};
+
+/// The monomorphic inline caches of the virtual call sites. Each site remembers the method
+/// its constant pool reference resolved to, so that the reference is resolved only once, and
+/// the method invoked the last time, together with the class of the receiver: as long as the
+/// site keeps being executed with receivers of the same class, no dispatch is needed. A site
+/// invoked with a receiver of a different class simply replaces the cached one.
+///
+/// Classes are never unloaded, but they can be redefined: since the cached methods could
+/// belong to the old versions, all the caches are then dropped.
+#[derive(Debug, Default)]
+pub(crate) struct InlineCaches<'a> {
+    caches: HashMap<InstructionSite, InlineCache<'a>>,
+}
+
+#[derive(Debug)]
+struct InlineCache<'a> {
+    resolved_method: ClassAndMethod<'a>,
+    target: Option<(ClassId, ClassAndMethod<'a>)>,
+}
+
+impl<'a> InlineCaches<'a> {
+    /// The method that the reference of the given call site resolved to, if already resolved
+    pub fn resolved_method(&self, call_site: InstructionSite) -> Option<ClassAndMethod<'a>> {
+        self.caches
+            .get(&call_site)
+            .map(|cache| cache.resolved_method.clone())
+    }
+
+    pub fn set_resolved_method(&mut self, call_site: InstructionSite, method: ClassAndMethod<'a>) {
+        self.caches.insert(
+            call_site,
+            InlineCache {
+                resolved_method: method,
+                target: None,
+            },
+        );
+    }
+
+    pub fn clear(&mut self) {
+        self.caches.clear();
+    }
+
+    /// The method invoked on a receiver of the given class, if it is the cached one
+    pub fn target(
+        &self,
+        call_site: InstructionSite,
+        receiver_class: ClassId,
+    ) -> Option<ClassAndMethod<'a>> {
+        match &self.caches.get(&call_site)?.target {
+            Some((class_id, target)) if *class_id == receiver_class => Some(target.clone()),
+            _ => None,
+        }
+    }
+
+    pub fn set_target(
+        &mut self,
+        call_site: InstructionSite,
+        receiver_class: ClassId,
+        target: ClassAndMethod<'a>,
+    ) {
+        if let Some(cache) = self.caches.get_mut(&call_site) {
+            cache.target = Some((receiver_class, target));
+        }
+    }
+}
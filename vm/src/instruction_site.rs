@@ -0,0 +1,33 @@
+use rjvm_reader::program_counter::ProgramCounter;
+
+use crate::class::ClassId;
+use crate::class_and_method::ClassAndMethod;
+
+/// Identifies an instruction of a method, used as key of the side tables that cache what
+/// the instruction resolved to the first time it was executed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) struct InstructionSite {
+    class_id: ClassId,
+    /// The code of a redefined class changes, while its id stays the same
+    class_version: u32,
+    /// The index of the method in the methods of its class
+    method_index: usize,
+    pc: u32,
+}
+
+impl InstructionSite {
+    /// Returns `None` if the method is not declared by its class, which cannot happen for
+    /// the methods being executed
+    pub fn of(method: &ClassAndMethod, pc: ProgramCounter) -> Option<Self> {
+        Some(Self {
+            class_id: method.class.id,
+            class_version: method.class.version,
+            method_index: method.class.method_index(method.method)?,
+            pc: pc.0,
+        })
+    }
+
+    pub fn class_id(&self) -> ClassId {
+        self.class_id
+    }
+}
@@ -0,0 +1,749 @@
+use std::{
+    cell::{Cell, RefCell},
+    collections::HashMap,
+    fmt::Debug,
+    fs::File,
+    io::{stderr, stdin, stdout, Read, Seek, SeekFrom, Write},
+    net::{IpAddr, Shutdown, SocketAddr},
+    rc::Rc,
+    time::{Duration, Instant, UNIX_EPOCH},
+};
+
+#[cfg(feature = "std")]
+use std::{
+    io::ErrorKind,
+    net::{TcpListener, TcpStream, ToSocketAddrs},
+};
+
+/// The error returned by the file methods of the [JvmIo] that do not support files
+const NO_FILE_SYSTEM: &str = "no file system available";
+
+/// Identifies a file opened by a [JvmIo]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct FileHandle(pub u64);
+
+/// How to open a file, with the same meaning as in [std::fs::OpenOptions]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct OpenOptions {
+    pub read: bool,
+    pub write: bool,
+    pub append: bool,
+    pub create: bool,
+    pub create_new: bool,
+    pub truncate: bool,
+}
+
+/// What a [JvmIo] knows about a file or a directory
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct FileMetadata {
+    pub kind: FileKind,
+    pub length: u64,
+    /// The time of the last modification, in milliseconds since the epoch, if known
+    pub last_modified: Option<i64>,
+    pub readable: bool,
+    pub writable: bool,
+    pub executable: bool,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum FileKind {
+    #[default]
+    Regular,
+    Directory,
+    /// Anything else, such as a device or a socket
+    Other,
+}
+
+/// The services of the host that the natives need, such as its environment. Every method has
+/// a default implementation that exposes nothing, so that embedders can implement only what
+/// they want to provide, and the vm itself never depends on an operating system.
+pub trait JvmIo: Debug {
+    /// Returns the value of the given variable of the host environment
+    fn env(&self, _name: &str) -> Option<String> {
+        None
+    }
+
+    /// Returns all the variables of the host environment
+    fn env_vars(&self) -> Vec<(String, String)> {
+        Vec::new()
+    }
+
+    /// The monotonic clock of `Thread.sleep` and of the timeouts of `Object.wait` and
+    /// `Thread.join`. The default implementation uses the one of the host; embedders can
+    /// replace it, e.g. with a virtual clock advanced by [sleep](JvmIo::sleep).
+    fn monotonic_now(&self) -> Instant {
+        Instant::now()
+    }
+
+    /// Blocks until the given time has passed on the [monotonic clock](JvmIo::monotonic_now).
+    /// The vm only sleeps when all the java threads are waiting for a timeout; note that it
+    /// blocks the whole vm. The default implementation sleeps on the host.
+    fn sleep(&self, duration: Duration) {
+        std::thread::sleep(duration);
+    }
+
+    /// Reads some bytes of the standard input into the buffer, which is never empty,
+    /// returning how many were read. Zero means that the input has ended, which is what the
+    /// default implementation always returns. Errors are thrown to the java code as
+    /// `IOException`s with the given message.
+    ///
+    /// Note that the whole vm, not only the reading thread, waits until some input is
+    /// available.
+    fn read_stdin(&self, _buffer: &mut [u8]) -> Result<usize, String> {
+        Ok(0)
+    }
+
+    /// Opens the file with the given path. The error is the reason why it could not be
+    /// opened, such as "No such file or directory".
+    fn open_file(&self, _path: &str, _options: OpenOptions) -> Result<FileHandle, String> {
+        Err(NO_FILE_SYSTEM.to_string())
+    }
+
+    /// Reads some bytes from the current position of the file, which then advances.
+    /// Like [JvmIo::read_stdin], zero means that the end of the file has been reached.
+    fn read_file(&self, _handle: FileHandle, _buffer: &mut [u8]) -> Result<usize, String> {
+        Err(NO_FILE_SYSTEM.to_string())
+    }
+
+    /// Reads some bytes starting from the given position of the file, after which the current
+    /// position is unspecified. This is how the vm reads jars lazily, one class at a time; the
+    /// default implementation seeks and then reads.
+    fn read_file_at(
+        &self,
+        handle: FileHandle,
+        position: u64,
+        buffer: &mut [u8],
+    ) -> Result<usize, String> {
+        self.seek_file(handle, position)?;
+        self.read_file(handle, buffer)
+    }
+
+    /// Writes all the given bytes at the current position of the file, or at its end if it
+    /// was opened for appending
+    fn write_file(&self, _handle: FileHandle, _bytes: &[u8]) -> Result<(), String> {
+        Err(NO_FILE_SYSTEM.to_string())
+    }
+
+    /// Moves the current position of the file, which can be past its end
+    fn seek_file(&self, _handle: FileHandle, _position: u64) -> Result<(), String> {
+        Err(NO_FILE_SYSTEM.to_string())
+    }
+
+    fn file_position(&self, _handle: FileHandle) -> Result<u64, String> {
+        Err(NO_FILE_SYSTEM.to_string())
+    }
+
+    fn file_length(&self, _handle: FileHandle) -> Result<u64, String> {
+        Err(NO_FILE_SYSTEM.to_string())
+    }
+
+    /// Truncates or extends the file
+    fn set_file_length(&self, _handle: FileHandle, _length: u64) -> Result<(), String> {
+        Err(NO_FILE_SYSTEM.to_string())
+    }
+
+    /// Closes the file. The handle will not be used anymore.
+    fn close_file(&self, _handle: FileHandle) -> Result<(), String> {
+        Err(NO_FILE_SYSTEM.to_string())
+    }
+
+    /// Returns the metadata of the file or directory with the given path, following the
+    /// symbolic links
+    fn file_metadata(&self, _path: &str) -> Result<FileMetadata, String> {
+        Err(NO_FILE_SYSTEM.to_string())
+    }
+
+    /// Returns the names of the entries of the directory, without "." and ".."
+    fn list_directory(&self, _path: &str) -> Result<Vec<String>, String> {
+        Err(NO_FILE_SYSTEM.to_string())
+    }
+
+    /// Creates a directory, whose parent must exist
+    fn create_directory(&self, _path: &str) -> Result<(), String> {
+        Err(NO_FILE_SYSTEM.to_string())
+    }
+
+    /// Deletes a file or an empty directory
+    fn delete_file(&self, _path: &str) -> Result<(), String> {
+        Err(NO_FILE_SYSTEM.to_string())
+    }
+
+    fn rename_file(&self, _from: &str, _to: &str) -> Result<(), String> {
+        Err(NO_FILE_SYSTEM.to_string())
+    }
+
+    /// Sets the time of the last modification, in milliseconds since the epoch
+    fn set_last_modified(&self, _path: &str, _last_modified: i64) -> Result<(), String> {
+        Err(NO_FILE_SYSTEM.to_string())
+    }
+
+    /// Returns the absolute path of the file without symbolic links, "." and "..". The file
+    /// does not need to exist.
+    fn canonicalize_path(&self, _path: &str) -> Result<String, String> {
+        Err(NO_FILE_SYSTEM.to_string())
+    }
+}
+
+/// A [JvmIo] that exposes nothing of the host. This is what the vm uses by default.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct NoJvmIo;
+
+impl JvmIo for NoJvmIo {}
+
+/// A [JvmIo] backed by the standard library, i.e. by the process running the vm
+#[derive(Debug, Default)]
+pub struct StdJvmIo {
+    files: RefCell<HashMap<FileHandle, File>>,
+    next_handle: Cell<u64>,
+}
+
+impl StdJvmIo {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn with_file<T>(
+        &self,
+        handle: FileHandle,
+        action: impl FnOnce(&mut File) -> std::io::Result<T>,
+    ) -> Result<T, String> {
+        let mut files = self.files.borrow_mut();
+        let file = files
+            .get_mut(&handle)
+            .ok_or_else(|| "Bad file descriptor".to_string())?;
+        action(file).map_err(error_message)
+    }
+}
+
+impl JvmIo for StdJvmIo {
+    fn env(&self, name: &str) -> Option<String> {
+        std::env::var(name).ok()
+    }
+
+    /// Variables whose name or value is not valid unicode are skipped
+    fn env_vars(&self) -> Vec<(String, String)> {
+        std::env::vars_os()
+            .filter_map(|(name, value)| Some((name.into_string().ok()?, value.into_string().ok()?)))
+            .collect()
+    }
+
+    fn read_stdin(&self, buffer: &mut [u8]) -> Result<usize, String> {
+        stdin().read(buffer).map_err(error_message)
+    }
+
+    fn open_file(&self, path: &str, options: OpenOptions) -> Result<FileHandle, String> {
+        let file = std::fs::OpenOptions::new()
+            .read(options.read)
+            .write(options.write)
+            .append(options.append)
+            .create(options.create)
+            .create_new(options.create_new)
+            .truncate(options.truncate)
+            .open(path)
+            .map_err(error_message)?;
+        let handle = FileHandle(self.next_handle.get());
+        self.next_handle.set(handle.0 + 1);
+        self.files.borrow_mut().insert(handle, file);
+        Ok(handle)
+    }
+
+    fn read_file(&self, handle: FileHandle, buffer: &mut [u8]) -> Result<usize, String> {
+        self.with_file(handle, |file| file.read(buffer))
+    }
+
+    fn write_file(&self, handle: FileHandle, bytes: &[u8]) -> Result<(), String> {
+        self.with_file(handle, |file| file.write_all(bytes))
+    }
+
+    fn seek_file(&self, handle: FileHandle, position: u64) -> Result<(), String> {
+        self.with_file(handle, |file| {
+            file.seek(SeekFrom::Start(position)).map(|_| ())
+        })
+    }
+
+    fn file_position(&self, handle: FileHandle) -> Result<u64, String> {
+        self.with_file(handle, |file| file.stream_position())
+    }
+
+    fn file_length(&self, handle: FileHandle) -> Result<u64, String> {
+        self.with_file(handle, |file| {
+            file.metadata().map(|metadata| metadata.len())
+        })
+    }
+
+    fn set_file_length(&self, handle: FileHandle, length: u64) -> Result<(), String> {
+        self.with_file(handle, |file| file.set_len(length))
+    }
+
+    fn close_file(&self, handle: FileHandle) -> Result<(), String> {
+        match self.files.borrow_mut().remove(&handle) {
+            Some(_) => Ok(()),
+            None => Err("Bad file descriptor".to_string()),
+        }
+    }
+
+    fn file_metadata(&self, path: &str) -> Result<FileMetadata, String> {
+        let metadata = std::fs::metadata(path).map_err(error_message)?;
+        let kind = if metadata.is_file() {
+            FileKind::Regular
+        } else if metadata.is_dir() {
+            FileKind::Directory
+        } else {
+            FileKind::Other
+        };
+        let last_modified = metadata
+            .modified()
+            .ok()
+            .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+            .and_then(|duration| i64::try_from(duration.as_millis()).ok());
+        #[cfg(unix)]
+        let (readable, executable) = {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = metadata.permissions().mode();
+            (mode & 0o444 != 0, mode & 0o111 != 0)
+        };
+        #[cfg(not(unix))]
+        let (readable, executable) = (true, kind == FileKind::Directory);
+        Ok(FileMetadata {
+            kind,
+            length: metadata.len(),
+            last_modified,
+            readable,
+            writable: !metadata.permissions().readonly(),
+            executable,
+        })
+    }
+
+    fn list_directory(&self, path: &str) -> Result<Vec<String>, String> {
+        std::fs::read_dir(path)
+            .and_then(|entries| {
+                entries
+                    .map(|entry| Ok(entry?.file_name().to_string_lossy().into_owned()))
+                    .collect()
+            })
+            .map_err(error_message)
+    }
+
+    fn create_directory(&self, path: &str) -> Result<(), String> {
+        std::fs::create_dir(path).map_err(error_message)
+    }
+
+    fn delete_file(&self, path: &str) -> Result<(), String> {
+        let metadata = std::fs::symlink_metadata(path).map_err(error_message)?;
+        if metadata.is_dir() {
+            std::fs::remove_dir(path).map_err(error_message)
+        } else {
+            std::fs::remove_file(path).map_err(error_message)
+        }
+    }
+
+    fn rename_file(&self, from: &str, to: &str) -> Result<(), String> {
+        std::fs::rename(from, to).map_err(error_message)
+    }
+
+    fn set_last_modified(&self, path: &str, last_modified: i64) -> Result<(), String> {
+        let time = u64::try_from(last_modified)
+            .ok()
+            .and_then(|millis| UNIX_EPOCH.checked_add(Duration::from_millis(millis)))
+            .ok_or_else(|| "Invalid argument".to_string())?;
+        File::options()
+            .write(true)
+            .open(path)
+            .and_then(|file| file.set_modified(time))
+            .map_err(error_message)
+    }
+
+    fn canonicalize_path(&self, path: &str) -> Result<String, String> {
+        let path = std::path::absolute(path).map_err(error_message)?;
+        // Like the JDK, we resolve the longest prefix that exists, and append the rest
+        let mut existing = path.as_path();
+        let mut missing = Vec::new();
+        let resolved = loop {
+            match std::fs::canonicalize(existing) {
+                Ok(resolved) => break resolved,
+                Err(_) => match (existing.parent(), existing.file_name()) {
+                    (Some(parent), Some(name)) => {
+                        missing.push(name);
+                        existing = parent;
+                    }
+                    _ => break existing.to_path_buf(),
+                },
+            }
+        };
+        let canonical = missing
+            .into_iter()
+            .rev()
+            .fold(resolved, |path, name| path.join(name));
+        Ok(canonical.to_string_lossy().into_owned())
+    }
+}
+
+/// Returns the description of the error without the code that [std::io::Error] appends for
+/// the errors of the operating system, so that it reads like the messages of the JDK
+fn error_message(err: std::io::Error) -> String {
+    let message = err.to_string();
+    match message.find(" (os error ") {
+        Some(index) => message[..index].to_string(),
+        None => message,
+    }
+}
+
+/// The error returned by the methods of the [JvmNet] that do not support networking
+const NO_NETWORK: &str = "no network available";
+
+/// Identifies a socket, either connected or listening, opened by a [JvmNet]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SocketHandle(pub u64);
+
+/// An error of a [JvmNet]. The kind decides which exception the java code sees.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NetError {
+    pub kind: NetErrorKind,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NetErrorKind {
+    ConnectionRefused,
+    TimedOut,
+    AddressInUse,
+    Other,
+}
+
+impl NetError {
+    pub fn new(kind: NetErrorKind, message: impl Into<String>) -> Self {
+        Self {
+            kind,
+            message: message.into(),
+        }
+    }
+
+    pub fn other(message: impl Into<String>) -> Self {
+        Self::new(NetErrorKind::Other, message)
+    }
+}
+
+/// The TCP networking of the host, used by `java.net.Socket` and `java.net.ServerSocket`.
+/// Like [JvmIo], every method has a default implementation that fails, and the vm uses
+/// [NoJvmNet] unless the embedder provides another one. The calls block the whole vm until
+/// they complete.
+pub trait JvmNet: Debug {
+    /// Returns the addresses of the given host name
+    fn resolve_host(&self, _host: &str) -> Result<Vec<IpAddr>, NetError> {
+        Err(NetError::other(NO_NETWORK))
+    }
+
+    /// Connects to the given address, failing after the timeout if there is one
+    fn connect(
+        &self,
+        _address: SocketAddr,
+        _timeout: Option<Duration>,
+    ) -> Result<SocketHandle, NetError> {
+        Err(NetError::other(NO_NETWORK))
+    }
+
+    /// Creates a socket bound to the given address that listens for connections. The port
+    /// can be zero, in which case one is chosen by the host.
+    fn listen(&self, _address: SocketAddr, _backlog: u32) -> Result<SocketHandle, NetError> {
+        Err(NetError::other(NO_NETWORK))
+    }
+
+    /// Waits for a connection on a listening socket, returning the connected socket and the
+    /// address of the peer
+    fn accept(
+        &self,
+        _listener: SocketHandle,
+        _timeout: Option<Duration>,
+    ) -> Result<(SocketHandle, SocketAddr), NetError> {
+        Err(NetError::other(NO_NETWORK))
+    }
+
+    fn local_address(&self, _socket: SocketHandle) -> Result<SocketAddr, NetError> {
+        Err(NetError::other(NO_NETWORK))
+    }
+
+    /// Reads some bytes from a connected socket; zero means that the peer closed it
+    fn read_socket(
+        &self,
+        _socket: SocketHandle,
+        _buffer: &mut [u8],
+        _timeout: Option<Duration>,
+    ) -> Result<usize, NetError> {
+        Err(NetError::other(NO_NETWORK))
+    }
+
+    fn write_socket(&self, _socket: SocketHandle, _bytes: &[u8]) -> Result<(), NetError> {
+        Err(NetError::other(NO_NETWORK))
+    }
+
+    /// Returns how many bytes can be read without blocking
+    fn available(&self, _socket: SocketHandle) -> Result<usize, NetError> {
+        Ok(0)
+    }
+
+    fn shutdown_socket(&self, _socket: SocketHandle, _how: Shutdown) -> Result<(), NetError> {
+        Err(NetError::other(NO_NETWORK))
+    }
+
+    /// Closes the socket. The handle will not be used anymore.
+    fn close_socket(&self, _socket: SocketHandle) -> Result<(), NetError> {
+        Err(NetError::other(NO_NETWORK))
+    }
+}
+
+/// A [JvmNet] without any network. This is what the vm uses by default.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct NoJvmNet;
+
+impl JvmNet for NoJvmNet {}
+
+/// A [JvmNet] backed by [std::net]
+#[cfg(feature = "std")]
+#[derive(Debug, Default)]
+pub struct StdJvmNet {
+    sockets: RefCell<HashMap<SocketHandle, StdSocket>>,
+    next_handle: Cell<u64>,
+}
+
+#[cfg(feature = "std")]
+#[derive(Debug)]
+enum StdSocket {
+    Stream(TcpStream),
+    Listener(TcpListener),
+}
+
+/// How long [StdJvmNet] waits between two attempts to accept a connection with a timeout
+#[cfg(feature = "std")]
+const ACCEPT_POLL_INTERVAL: Duration = Duration::from_millis(1);
+
+#[cfg(feature = "std")]
+impl StdJvmNet {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn insert(&self, socket: StdSocket) -> SocketHandle {
+        let handle = SocketHandle(self.next_handle.get());
+        self.next_handle.set(handle.0 + 1);
+        self.sockets.borrow_mut().insert(handle, socket);
+        handle
+    }
+
+    fn with_stream<T>(
+        &self,
+        handle: SocketHandle,
+        action: impl FnOnce(&mut TcpStream) -> std::io::Result<T>,
+    ) -> Result<T, NetError> {
+        match self.sockets.borrow_mut().get_mut(&handle) {
+            Some(StdSocket::Stream(stream)) => action(stream).map_err(net_error),
+            Some(StdSocket::Listener(_)) => Err(NetError::other("Socket is not connected")),
+            None => Err(NetError::other("Socket closed")),
+        }
+    }
+
+    fn accept_before(
+        listener: &TcpListener,
+        deadline: Instant,
+    ) -> std::io::Result<(TcpStream, SocketAddr)> {
+        listener.set_nonblocking(true)?;
+        let result = loop {
+            match listener.accept() {
+                Err(err) if err.kind() == ErrorKind::WouldBlock => {
+                    if Instant::now() >= deadline {
+                        break Err(ErrorKind::TimedOut.into());
+                    }
+                    std::thread::sleep(ACCEPT_POLL_INTERVAL);
+                }
+                result => break result,
+            }
+        };
+        listener.set_nonblocking(false)?;
+        let (stream, peer) = result?;
+        stream.set_nonblocking(false)?;
+        Ok((stream, peer))
+    }
+}
+
+#[cfg(feature = "std")]
+impl JvmNet for StdJvmNet {
+    fn resolve_host(&self, host: &str) -> Result<Vec<IpAddr>, NetError> {
+        let addresses = (host, 0).to_socket_addrs().map_err(net_error)?;
+        let mut result: Vec<IpAddr> = Vec::new();
+        for address in addresses {
+            if !result.contains(&address.ip()) {
+                result.push(address.ip());
+            }
+        }
+        Ok(result)
+    }
+
+    fn connect(
+        &self,
+        address: SocketAddr,
+        timeout: Option<Duration>,
+    ) -> Result<SocketHandle, NetError> {
+        let stream = match timeout {
+            Some(timeout) => TcpStream::connect_timeout(&address, timeout),
+            None => TcpStream::connect(address),
+        }
+        .map_err(net_error)?;
+        Ok(self.insert(StdSocket::Stream(stream)))
+    }
+
+    fn listen(&self, address: SocketAddr, _backlog: u32) -> Result<SocketHandle, NetError> {
+        let listener = TcpListener::bind(address).map_err(net_error)?;
+        Ok(self.insert(StdSocket::Listener(listener)))
+    }
+
+    fn accept(
+        &self,
+        listener: SocketHandle,
+        timeout: Option<Duration>,
+    ) -> Result<(SocketHandle, SocketAddr), NetError> {
+        let (stream, peer) = match self.sockets.borrow().get(&listener) {
+            Some(StdSocket::Listener(listener)) => match timeout {
+                Some(timeout) => Self::accept_before(listener, Instant::now() + timeout),
+                None => listener.accept(),
+            }
+            .map_err(net_error)?,
+            Some(StdSocket::Stream(_)) => return Err(NetError::other("Socket is not listening")),
+            None => return Err(NetError::other("Socket closed")),
+        };
+        Ok((self.insert(StdSocket::Stream(stream)), peer))
+    }
+
+    fn local_address(&self, socket: SocketHandle) -> Result<SocketAddr, NetError> {
+        match self.sockets.borrow().get(&socket) {
+            Some(StdSocket::Stream(stream)) => stream.local_addr().map_err(net_error),
+            Some(StdSocket::Listener(listener)) => listener.local_addr().map_err(net_error),
+            None => Err(NetError::other("Socket closed")),
+        }
+    }
+
+    fn read_socket(
+        &self,
+        socket: SocketHandle,
+        buffer: &mut [u8],
+        timeout: Option<Duration>,
+    ) -> Result<usize, NetError> {
+        self.with_stream(socket, |stream| {
+            stream.set_read_timeout(timeout)?;
+            stream.read(buffer)
+        })
+    }
+
+    fn write_socket(&self, socket: SocketHandle, bytes: &[u8]) -> Result<(), NetError> {
+        self.with_stream(socket, |stream| stream.write_all(bytes))
+    }
+
+    fn available(&self, socket: SocketHandle) -> Result<usize, NetError> {
+        self.with_stream(socket, |stream| {
+            let mut buffer = [0; 65536];
+            stream.set_nonblocking(true)?;
+            let result = stream.peek(&mut buffer);
+            stream.set_nonblocking(false)?;
+            match result {
+                Err(err) if err.kind() == ErrorKind::WouldBlock => Ok(0),
+                result => result,
+            }
+        })
+    }
+
+    fn shutdown_socket(&self, socket: SocketHandle, how: Shutdown) -> Result<(), NetError> {
+        self.with_stream(socket, |stream| stream.shutdown(how))
+    }
+
+    fn close_socket(&self, socket: SocketHandle) -> Result<(), NetError> {
+        match self.sockets.borrow_mut().remove(&socket) {
+            Some(_) => Ok(()),
+            None => Err(NetError::other("Socket closed")),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+fn net_error(err: std::io::Error) -> NetError {
+    let kind = match err.kind() {
+        ErrorKind::ConnectionRefused => NetErrorKind::ConnectionRefused,
+        ErrorKind::TimedOut | ErrorKind::WouldBlock => NetErrorKind::TimedOut,
+        ErrorKind::AddrInUse | ErrorKind::AddrNotAvailable => NetErrorKind::AddressInUse,
+        _ => NetErrorKind::Other,
+    };
+    NetError::new(kind, error_message(err))
+}
+
+/// Where the bytes written by the java code to `System.out` or `System.err` end up.
+/// Unlike [std::io::Write] it does not depend on an operating system, and it cannot fail:
+/// like the JDK's `PrintStream`, the vm never reports output errors to the java code.
+/// Like [JvmIo], it takes `&self` so that the vm can share it; implementations that need
+/// to mutate their state can use a `RefCell`.
+pub trait OutputSink: Debug {
+    fn write(&self, bytes: &[u8]);
+
+    /// Invoked when the java code flushes the stream, or prints a new line
+    fn flush(&self) {}
+}
+
+/// An [OutputSink] that writes to the standard output of the process running the vm.
+/// This is where `System.out` goes by default.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct StdoutSink;
+
+impl OutputSink for StdoutSink {
+    fn write(&self, bytes: &[u8]) {
+        let _ = stdout().write_all(bytes);
+    }
+
+    fn flush(&self) {
+        let _ = stdout().flush();
+    }
+}
+
+/// An [OutputSink] that writes to the standard error of the process running the vm.
+/// This is where `System.err` goes by default.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct StderrSink;
+
+impl OutputSink for StderrSink {
+    fn write(&self, bytes: &[u8]) {
+        let _ = stderr().write_all(bytes);
+    }
+
+    fn flush(&self) {
+        let _ = stderr().flush();
+    }
+}
+
+/// An [OutputSink] that accumulates everything written to it in memory. It can be cloned
+/// before being given to the vm, to read the captured output afterwards.
+#[derive(Debug, Default, Clone)]
+pub struct CapturedOutput {
+    bytes: Rc<RefCell<Vec<u8>>>,
+}
+
+impl CapturedOutput {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the bytes written so far
+    pub fn bytes(&self) -> Vec<u8> {
+        self.bytes.borrow().clone()
+    }
+
+    /// Returns the output written so far, replacing any invalid utf-8 sequence
+    pub fn contents(&self) -> String {
+        String::from_utf8_lossy(&self.bytes.borrow()).into_owned()
+    }
+
+    /// Discards the output written so far
+    pub fn clear(&self) {
+        self.bytes.borrow_mut().clear();
+    }
+}
+
+impl OutputSink for CapturedOutput {
+    fn write(&self, bytes: &[u8]) {
+        self.bytes.borrow_mut().extend_from_slice(bytes);
+    }
+}
@@ -1,20 +1,36 @@
 use std::{
-    cell::RefCell,
+    cell::{Cell, RefCell},
     fmt::{Debug, Formatter},
-    fs::File,
-    io::{BufReader, Read},
+    io,
+    io::{BufReader, Read, Seek, SeekFrom},
     path::Path,
+    rc::Rc,
 };
 
+use rjvm_reader::type_conversion::CheckedNarrowing;
 use thiserror::Error;
 use zip::{result::ZipError, ZipArchive};
 
-use crate::class_path_entry::{ClassLoadingError, ClassPathEntry};
+use crate::{
+    class_path_entry::{ClassLoadingError, ClassPathEntry},
+    class_path_scan::{ClassPathScanProgress, ScanProgressListener},
+    io::{FileHandle, JvmIo, OpenOptions},
+    jar_manifest::{JarManifest, ManifestError, MANIFEST_PATH},
+    zip_central_directory::ZipCentralDirectory,
+};
 
-/// Implementation of [ClassPathEntry] that searches for `.class` file inside a `.jar` file
+/// Implementation of [ClassPathEntry] that searches for `.class` files and other resources
+/// inside a `.jar` file.
+///
+/// Only the central directory of the jar is read when opening it, and each file is then read
+/// and decompressed on demand, so that even large jars, such as the 60 MB `rt.jar`, use
+/// little memory. The jar is read through a [JvmIo], so it does not need to be stored in a
+/// file system of the operating system.
 pub struct JarFileClassPathEntry {
     file_name: String,
-    zip: RefCell<ZipArchive<BufReader<File>>>,
+    reader: RefCell<ScanProgressReader>,
+    directory: ZipCentralDirectory,
+    manifest: Option<JarManifest>,
 }
 
 impl Debug for JarFileClassPathEntry {
@@ -28,40 +44,225 @@ impl Debug for JarFileClassPathEntry {
 }
 
 impl JarFileClassPathEntry {
-    pub fn new<P: AsRef<Path>>(path: P) -> Result<Self, JarFileError> {
-        let path = path.as_ref();
-        if !path.exists() {
-            return Err(JarFileError::NotFound(path.to_string_lossy().to_string()));
+    /// Opens the jar, reading it through the given [JvmIo] and reporting the progress of the
+    /// scan of its content to the given listener
+    pub fn new<P: AsRef<Path>>(
+        path: P,
+        io: Rc<dyn JvmIo>,
+        listener: Option<ScanProgressListener>,
+    ) -> Result<Self, JarFileError> {
+        let file_name = path.as_ref().to_string_lossy().to_string();
+        if io.file_metadata(&file_name).is_err() {
+            return Err(JarFileError::NotFound(file_name));
+        }
+
+        let file = JvmIoFile::open(io, &file_name)
+            .map_err(|_| JarFileError::ReadingError(file_name.clone()))?;
+        let scan = listener.map(|listener| {
+            Rc::new(ScanState {
+                listener,
+                entry: file_name.clone(),
+                bytes_processed: Cell::new(0),
+                bytes_reported: Cell::new(0),
+                done: Cell::new(false),
+            })
+        });
+        let mut reader = ScanProgressReader {
+            inner: BufReader::new(file),
+            scan: scan.clone(),
+        };
+        let directory = ZipCentralDirectory::read(&mut reader)
+            .map_err(|_| JarFileError::InvalidJar(file_name.clone()))?;
+        if let Some(scan) = scan {
+            scan.finish(directory.len());
         }
 
-        let file = File::open(path)
-            .map_err(|_| JarFileError::ReadingError(path.to_string_lossy().to_string()))?;
-        let buf_reader = BufReader::new(file);
-        let zip = ZipArchive::new(buf_reader)
-            .map_err(|_| JarFileError::InvalidJar(path.to_string_lossy().to_string()))?;
+        let invalid_manifest = |err| JarFileError::InvalidManifest(file_name.clone(), err);
+        let manifest = directory
+            .read_file(&mut reader, MANIFEST_PATH)
+            .map_err(|_| invalid_manifest(ManifestError::InvalidJar(file_name.clone())))?
+            .map(|bytes| JarManifest::parse(&bytes))
+            .transpose()
+            .map_err(invalid_manifest)?;
         Ok(Self {
-            file_name: path.to_string_lossy().to_string(),
-            zip: RefCell::new(zip),
+            file_name,
+            reader: RefCell::new(reader),
+            directory,
+            manifest,
         })
     }
 }
 
+/// A file opened through a [JvmIo], which is closed when dropped
+struct JvmIoFile {
+    io: Rc<dyn JvmIo>,
+    handle: FileHandle,
+    position: u64,
+    length: u64,
+}
+
+impl JvmIoFile {
+    fn open(io: Rc<dyn JvmIo>, path: &str) -> Result<Self, String> {
+        let options = OpenOptions {
+            read: true,
+            ..Default::default()
+        };
+        let handle = io.open_file(path, options)?;
+        // Built before reading the length, so that the file is closed if that fails
+        let mut file = Self {
+            io,
+            handle,
+            position: 0,
+            length: 0,
+        };
+        file.length = file.io.file_length(handle)?;
+        Ok(file)
+    }
+}
+
+impl Read for JvmIoFile {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let bytes = self
+            .io
+            .read_file_at(self.handle, self.position, buf)
+            .map_err(io::Error::other)?;
+        self.position += bytes as u64;
+        Ok(bytes)
+    }
+}
+
+impl Seek for JvmIoFile {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        let position = match pos {
+            SeekFrom::Start(offset) => Some(offset),
+            SeekFrom::End(offset) => self.length.checked_add_signed(offset),
+            SeekFrom::Current(offset) => self.position.checked_add_signed(offset),
+        }
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "invalid seek position"))?;
+        self.position = position;
+        Ok(position)
+    }
+}
+
+impl Drop for JvmIoFile {
+    fn drop(&mut self) {
+        let _ = self.io.close_file(self.handle);
+    }
+}
+
+/// Bytes read between two intermediate progress reports
+const PROGRESS_REPORT_INTERVAL: u64 = 64 * 1024;
+
+/// The state of the scan done while opening a jar, shared with the reader that does the scan
+struct ScanState {
+    listener: ScanProgressListener,
+    entry: String,
+    bytes_processed: Cell<u64>,
+    bytes_reported: Cell<u64>,
+    /// Set once the jar has been opened, since later reads are due to loading classes
+    done: Cell<bool>,
+}
+
+impl ScanState {
+    fn on_read(&self, bytes: u64) {
+        if self.done.get() {
+            return;
+        }
+        self.bytes_processed.set(self.bytes_processed.get() + bytes);
+        if self.bytes_processed.get() - self.bytes_reported.get() >= PROGRESS_REPORT_INTERVAL {
+            self.report(0);
+        }
+    }
+
+    fn finish(&self, entries_scanned: usize) {
+        self.done.set(true);
+        self.report(entries_scanned);
+    }
+
+    fn report(&self, entries_scanned: usize) {
+        self.bytes_reported.set(self.bytes_processed.get());
+        self.listener.notify(&ClassPathScanProgress {
+            entry: self.entry.clone(),
+            entries_scanned,
+            bytes_processed: self.bytes_processed.get(),
+            done: self.done.get(),
+        });
+    }
+}
+
+/// Reader of the jar file, that reports the bytes read while the jar is being opened
+struct ScanProgressReader {
+    inner: BufReader<JvmIoFile>,
+    scan: Option<Rc<ScanState>>,
+}
+
+impl Read for ScanProgressReader {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let bytes = self.inner.read(buf)?;
+        if let Some(scan) = &self.scan {
+            scan.on_read(bytes as u64);
+        }
+        Ok(bytes)
+    }
+}
+
+impl Seek for ScanProgressReader {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        self.inner.seek(pos)
+    }
+}
+
+impl JarFileClassPathEntry {
+    /// The names of all the files and directories of the jar
+    pub(crate) fn file_names(&self) -> Vec<String> {
+        self.directory.file_names().map(str::to_string).collect()
+    }
+}
+
 impl ClassPathEntry for JarFileClassPathEntry {
-    fn resolve(&self, class_name: &str) -> Result<Option<Vec<u8>>, ClassLoadingError> {
-        let class_file_name = class_name.to_string() + ".class";
-        match self.zip.borrow_mut().by_name(&class_file_name) {
-            Ok(mut zip_file) => {
-                let mut buffer: Vec<u8> = Vec::with_capacity(zip_file.size() as usize);
-                zip_file
-                    .read_to_end(&mut buffer)
-                    .map_err(ClassLoadingError::new)?;
-                Ok(Some(buffer))
-            }
-            Err(err) => match err {
-                ZipError::FileNotFound => Ok(None),
-                _ => Err(ClassLoadingError::new(err)),
-            },
+    fn resolve_resource(&self, path: &str) -> Result<Option<Vec<u8>>, ClassLoadingError> {
+        self.directory
+            .read_file(&mut *self.reader.borrow_mut(), path)
+            .map_err(ClassLoadingError::new)
+    }
+
+    fn description(&self) -> String {
+        self.file_name.clone()
+    }
+
+    fn manifest(&self) -> Option<&JarManifest> {
+        self.manifest.as_ref()
+    }
+
+    fn class_names(&self) -> Result<Vec<String>, ClassLoadingError> {
+        Ok(self
+            .directory
+            .file_names()
+            .filter_map(|file_name| file_name.strip_suffix(".class"))
+            .map(|class_name| class_name.to_string())
+            .collect())
+    }
+}
+
+/// Reads a file of a zip archive, returning `None` if it does not exist or is a directory
+pub(crate) fn read_zip_file<R: Read + Seek>(
+    zip: &mut ZipArchive<R>,
+    path: &str,
+) -> Result<Option<Vec<u8>>, ClassLoadingError> {
+    match zip.by_name(path) {
+        Ok(zip_file) if zip_file.is_dir() => Ok(None),
+        Ok(mut zip_file) => {
+            let size = zip_file.size().narrow().map_err(ClassLoadingError::new)?;
+            let mut buffer: Vec<u8> = Vec::with_capacity(size);
+            zip_file
+                .read_to_end(&mut buffer)
+                .map_err(ClassLoadingError::new)?;
+            Ok(Some(buffer))
         }
+        Err(err) => match err {
+            ZipError::FileNotFound => Ok(None),
+            _ => Err(ClassLoadingError::new(err)),
+        },
     }
 }
 
@@ -79,14 +280,25 @@ pub enum JarFileError {
     /// The file is not actually a valid jar
     #[error("file {0} is not a valid jar")]
     InvalidJar(String),
+
+    /// The manifest of the jar cannot be parsed
+    #[error("file {0} has an invalid manifest: {1}")]
+    InvalidManifest(String, ManifestError),
 }
 
 #[cfg(test)]
 mod tests {
-    use std::path::PathBuf;
+    use std::{cell::RefCell, fs::File, io::Write, path::PathBuf, rc::Rc};
+
+    use zip::{write::FileOptions, CompressionMethod, ZipWriter};
 
     use crate::{
-        class_path_entry::tests::{assert_can_find_class, assert_cannot_find_class},
+        class_path_entry::{
+            tests::{assert_can_find_class, assert_cannot_find_class, assert_cannot_find_resource},
+            ClassPathEntry,
+        },
+        class_path_scan::{ClassPathScanProgress, ScanProgressListener},
+        io::StdJvmIo,
         jar_file_class_path_entry::{JarFileClassPathEntry, JarFileError},
     };
 
@@ -94,7 +306,7 @@ mod tests {
     fn jar_file_not_found() {
         let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
         path.push("tests/resources/not_found.jar");
-        let entry = JarFileClassPathEntry::new(path.clone());
+        let entry = JarFileClassPathEntry::new(path.clone(), Rc::new(StdJvmIo::new()), None);
         assert_eq!(
             JarFileError::NotFound(path.to_string_lossy().to_string()),
             entry.expect_err("should have thrown an error")
@@ -106,7 +318,7 @@ mod tests {
         let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
         path.push("tests/resources/compile.sh");
 
-        let entry = JarFileClassPathEntry::new(path.clone());
+        let entry = JarFileClassPathEntry::new(path.clone(), Rc::new(StdJvmIo::new()), None);
         assert_eq!(
             JarFileError::InvalidJar(path.to_string_lossy().to_string()),
             entry.expect_err("should have thrown an error")
@@ -117,10 +329,113 @@ mod tests {
     fn valid_jar_file_can_search_for_class_file() {
         let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
         path.push("tests/resources/sample.jar");
-        let entry = JarFileClassPathEntry::new(path).expect("should have read the jar file");
+        let entry = JarFileClassPathEntry::new(path, Rc::new(StdJvmIo::new()), None)
+            .expect("should have read the jar file");
 
         assert_can_find_class(&entry, "rjvm/NumericTypes");
         assert_can_find_class(&entry, "rjvm/ControlFlow");
         assert_cannot_find_class(&entry, "rjvm/Foo");
     }
+
+    #[test]
+    fn valid_jar_file_can_search_for_resources() {
+        let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        path.push("rt.jar");
+        let entry = JarFileClassPathEntry::new(path, Rc::new(StdJvmIo::new()), None)
+            .expect("should have read the jar file");
+
+        let manifest = entry
+            .resolve_resource("META-INF/MANIFEST.MF")
+            .expect("should have been able to read file")
+            .expect("should have been able to find file");
+        assert!(manifest.starts_with(b"Manifest-Version: "));
+        assert_cannot_find_resource(&entry, "META-INF/");
+        assert_cannot_find_resource(&entry, "META-INF/foo.txt");
+    }
+
+    #[test]
+    fn valid_jar_file_exposes_its_manifest() {
+        let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        path.push("tests/resources/jars/app.jar");
+        let entry = JarFileClassPathEntry::new(path, Rc::new(StdJvmIo::new()), None)
+            .expect("should have read the jar file");
+
+        let manifest = entry.manifest().expect("jar should have a manifest");
+        assert_eq!(Some("rjvm.jars.App"), manifest.main_class());
+        assert_eq!(
+            vec!["lib/greeter.jar", "missing.jar"],
+            manifest.class_path()
+        );
+    }
+
+    #[test]
+    fn valid_jar_file_can_list_classes() {
+        let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        path.push("tests/resources/sample.jar");
+        let entry = JarFileClassPathEntry::new(path, Rc::new(StdJvmIo::new()), None)
+            .expect("should have read the jar file");
+
+        let mut class_names = entry.class_names().expect("should be able to list classes");
+        class_names.sort();
+        assert_eq!(vec!["rjvm/ControlFlow", "rjvm/NumericTypes"], class_names);
+    }
+
+    #[test]
+    fn zip64_jar_file_can_search_for_class_file() {
+        // More than 65535 entries require the Zip64 end of central directory record, and
+        // large files store their sizes and offsets in the Zip64 extra field
+        let path = std::env::temp_dir().join(format!("rjvm-zip64-{}.jar", std::process::id()));
+        let mut class_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        class_path.push("tests/resources/rjvm/SimpleMain.class");
+        let class_bytes = std::fs::read(class_path).expect("should be able to read the class");
+
+        let mut writer =
+            ZipWriter::new(File::create(&path).expect("should be able to create the jar"));
+        let options = FileOptions::default().compression_method(CompressionMethod::Stored);
+        writer
+            .start_file("rjvm/SimpleMain.class", options.large_file(true))
+            .and_then(|_| Ok(writer.write_all(&class_bytes)?))
+            .expect("should be able to write the class");
+        for index in 0..70_000 {
+            writer
+                .start_file(format!("filler/{index}.txt"), options)
+                .expect("should be able to write the entry");
+        }
+        writer.finish().expect("should be able to write the jar");
+
+        let entry = JarFileClassPathEntry::new(&path, Rc::new(StdJvmIo::new()), None)
+            .expect("should have read the jar file");
+        assert_can_find_class(&entry, "rjvm/SimpleMain");
+        assert_eq!(70_001, entry.file_names().len());
+        std::fs::remove_file(&path).expect("should be able to remove the jar");
+    }
+
+    #[test]
+    fn opening_a_jar_reports_the_scan_progress() {
+        let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        path.push("rt.jar");
+        let reports: Rc<RefCell<Vec<ClassPathScanProgress>>> = Default::default();
+        let listener = {
+            let reports = reports.clone();
+            ScanProgressListener::new(move |progress| reports.borrow_mut().push(progress.clone()))
+        };
+        let entry =
+            JarFileClassPathEntry::new(path.clone(), Rc::new(StdJvmIo::new()), Some(listener))
+                .expect("should have read the jar file");
+        assert_can_find_class(&entry, "java/lang/Object");
+
+        let reports = reports.borrow();
+        assert!(reports.len() > 1, "expected intermediate reports");
+        let (last, intermediate) = reports.split_last().unwrap();
+        assert!(intermediate
+            .iter()
+            .all(|progress| !progress.done && progress.entries_scanned == 0));
+        assert!(intermediate
+            .windows(2)
+            .all(|pair| pair[0].bytes_processed < pair[1].bytes_processed));
+        assert!(last.done);
+        assert_eq!(path.to_string_lossy(), last.entry);
+        assert_eq!(entry.directory.len(), last.entries_scanned);
+        assert!(last.bytes_processed >= intermediate.last().unwrap().bytes_processed);
+    }
 }
@@ -0,0 +1,276 @@
+use std::{
+    collections::BTreeMap,
+    fs::File,
+    io::{Read, Seek},
+    path::Path,
+};
+
+use thiserror::Error;
+use zip::{result::ZipError, ZipArchive};
+
+/// The path of the manifest inside a jar
+pub(crate) const MANIFEST_PATH: &str = "META-INF/MANIFEST.MF";
+
+/// Models the manifest of a jar, i.e. the file `META-INF/MANIFEST.MF`. It is made of a main
+/// section, with the attributes of the whole jar, followed by optional sections with the
+/// attributes of single entries, each introduced by a `Name` attribute.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct JarManifest {
+    main_attributes: Attributes,
+    entries: BTreeMap<String, Attributes>,
+}
+
+/// The attributes of a section of a manifest, in the order they appear in it.
+/// As in the JDK, the names of the attributes are case-insensitive.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Attributes(Vec<(String, String)>);
+
+/// Error returned when a manifest cannot be read or is not valid
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum ManifestError {
+    #[error("cannot read jar {0}")]
+    InvalidJar(String),
+
+    #[error("manifest is not valid UTF-8")]
+    InvalidEncoding,
+
+    #[error("invalid manifest header on line {0}")]
+    InvalidHeader(usize),
+
+    #[error("manifest section on line {0} has no Name attribute")]
+    MissingName(usize),
+}
+
+impl Attributes {
+    /// Returns the value of the given attribute, ignoring the case of its name
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.0
+            .iter()
+            .find(|(attribute_name, _)| attribute_name.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.0
+            .iter()
+            .map(|(name, value)| (name.as_str(), value.as_str()))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl JarManifest {
+    /// Parses the content of a manifest. Lines can be continued on the next one by starting
+    /// it with a single space, and sections are separated by blank lines.
+    pub fn parse(bytes: &[u8]) -> Result<Self, ManifestError> {
+        let text = std::str::from_utf8(bytes).map_err(|_| ManifestError::InvalidEncoding)?;
+        let mut manifest = Self::default();
+        let mut section: Vec<(String, String)> = Vec::new();
+        let mut section_start = 1;
+        let mut in_main_section = true;
+
+        for (index, line) in text.split('\n').enumerate() {
+            let line_number = index + 1;
+            let line = line.strip_suffix('\r').unwrap_or(line);
+            if line.is_empty() {
+                if in_main_section {
+                    manifest.main_attributes = Attributes(std::mem::take(&mut section));
+                    in_main_section = false;
+                } else if !section.is_empty() {
+                    manifest.add_entry(std::mem::take(&mut section), section_start)?;
+                }
+                continue;
+            }
+
+            if let Some(continuation) = line.strip_prefix(' ') {
+                let (_, value) = section
+                    .last_mut()
+                    .ok_or(ManifestError::InvalidHeader(line_number))?;
+                value.push_str(continuation);
+                continue;
+            }
+
+            let (name, value) = line
+                .split_once(':')
+                .filter(|(name, _)| is_valid_attribute_name(name))
+                .ok_or(ManifestError::InvalidHeader(line_number))?;
+            if section.is_empty() {
+                section_start = line_number;
+            }
+            let value = value.strip_prefix(' ').unwrap_or(value);
+            section.push((name.to_string(), value.to_string()));
+        }
+
+        if in_main_section {
+            manifest.main_attributes = Attributes(section);
+        } else if !section.is_empty() {
+            manifest.add_entry(section, section_start)?;
+        }
+        Ok(manifest)
+    }
+
+    /// Reads the manifest of the given jar, if it has one
+    pub fn read_from_jar<P: AsRef<Path>>(path: P) -> Result<Option<Self>, ManifestError> {
+        let path = path.as_ref();
+        let invalid_jar = || ManifestError::InvalidJar(path.to_string_lossy().to_string());
+        let file = File::open(path).map_err(|_| invalid_jar())?;
+        let mut zip = ZipArchive::new(file).map_err(|_| invalid_jar())?;
+        Self::read_from_zip(&mut zip, path)
+    }
+
+    pub(crate) fn read_from_zip<R: Read + Seek>(
+        zip: &mut ZipArchive<R>,
+        path: &Path,
+    ) -> Result<Option<Self>, ManifestError> {
+        let invalid_jar = || ManifestError::InvalidJar(path.to_string_lossy().to_string());
+        let mut manifest_file = match zip.by_name(MANIFEST_PATH) {
+            Ok(manifest_file) => manifest_file,
+            Err(ZipError::FileNotFound) => return Ok(None),
+            Err(_) => return Err(invalid_jar()),
+        };
+        let mut bytes = Vec::new();
+        manifest_file
+            .read_to_end(&mut bytes)
+            .map_err(|_| invalid_jar())?;
+        Self::parse(&bytes).map(Some)
+    }
+
+    fn add_entry(
+        &mut self,
+        attributes: Vec<(String, String)>,
+        line_number: usize,
+    ) -> Result<(), ManifestError> {
+        let attributes = Attributes(attributes);
+        let name = attributes
+            .get("Name")
+            .ok_or(ManifestError::MissingName(line_number))?
+            .to_string();
+        self.entries.insert(name, attributes);
+        Ok(())
+    }
+
+    pub fn main_attributes(&self) -> &Attributes {
+        &self.main_attributes
+    }
+
+    /// Returns the attributes of the section of the given entry, e.g. `rjvm/Foo.class`
+    pub fn entry_attributes(&self, entry_name: &str) -> Option<&Attributes> {
+        self.entries.get(entry_name)
+    }
+
+    /// The value of the `Main-Class` attribute, i.e. the class launched by `java -jar`
+    pub fn main_class(&self) -> Option<&str> {
+        self.main_attributes.get("Main-Class")
+    }
+
+    /// The entries listed in the `Class-Path` attribute. They are separated by spaces and
+    /// relative to the directory containing the jar.
+    pub fn class_path(&self) -> Vec<&str> {
+        self.main_attributes
+            .get("Class-Path")
+            .map(|class_path| class_path.split_whitespace().collect())
+            .unwrap_or_default()
+    }
+}
+
+fn is_valid_attribute_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|char| char.is_ascii_alphanumeric() || char == '-' || char == '_')
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::jar_manifest::{JarManifest, ManifestError};
+
+    #[test]
+    fn can_parse_main_attributes() {
+        let manifest = JarManifest::parse(
+            b"Manifest-Version: 1.0\r\nMain-Class: rjvm.app.App\r\nClass-Path: lib/a.jar\r\n  lib/b.jar classes/\r\n\r\n",
+        )
+        .expect("manifest should be valid");
+        assert_eq!(
+            Some("1.0"),
+            manifest.main_attributes().get("Manifest-Version")
+        );
+        assert_eq!(Some("rjvm.app.App"), manifest.main_class());
+        assert_eq!(
+            Some("rjvm.app.App"),
+            manifest.main_attributes().get("main-class")
+        );
+        assert_eq!(
+            vec!["lib/a.jar", "lib/b.jar", "classes/"],
+            manifest.class_path()
+        );
+        assert_eq!(None, manifest.main_attributes().get("Created-By"));
+    }
+
+    #[test]
+    fn can_parse_entry_sections() {
+        let manifest = JarManifest::parse(
+            b"Manifest-Version: 1.0\n\nName: rjvm/Foo.class\nSealed: true\n\n\nName: rjvm/Bar.cl\n ass\nSealed: false",
+        )
+        .expect("manifest should be valid");
+        assert_eq!(
+            Some("true"),
+            manifest
+                .entry_attributes("rjvm/Foo.class")
+                .and_then(|attributes| attributes.get("Sealed"))
+        );
+        assert_eq!(
+            Some("false"),
+            manifest
+                .entry_attributes("rjvm/Bar.class")
+                .and_then(|attributes| attributes.get("Sealed"))
+        );
+        assert_eq!(None, manifest.entry_attributes("rjvm/Baz.class"));
+        assert!(manifest.class_path().is_empty());
+        assert_eq!(None, manifest.main_class());
+    }
+
+    #[test]
+    fn invalid_manifests_are_rejected() {
+        assert_eq!(
+            Err(ManifestError::InvalidHeader(2)),
+            JarManifest::parse(b"Manifest-Version: 1.0\nnot a header\n")
+        );
+        assert_eq!(
+            Err(ManifestError::InvalidHeader(1)),
+            JarManifest::parse(b" continuation\n")
+        );
+        assert_eq!(
+            Err(ManifestError::MissingName(3)),
+            JarManifest::parse(b"Manifest-Version: 1.0\n\nSealed: true\n")
+        );
+        assert_eq!(
+            Err(ManifestError::InvalidEncoding),
+            JarManifest::parse(b"Main-Class: \xff\n")
+        );
+    }
+
+    #[test]
+    fn can_read_the_manifest_of_a_jar() {
+        let dir = env!("CARGO_MANIFEST_DIR");
+        let manifest = JarManifest::read_from_jar(format!("{dir}/rt.jar"))
+            .expect("should be able to read the jar")
+            .expect("jar should have a manifest");
+        assert_eq!(
+            Some("1.0"),
+            manifest.main_attributes().get("Manifest-Version")
+        );
+
+        assert_eq!(
+            Ok(None),
+            JarManifest::read_from_jar(format!("{dir}/tests/resources/sample.jar"))
+        );
+        assert_eq!(
+            Err(ManifestError::InvalidJar(format!(
+                "{dir}/tests/resources/compile.sh"
+            ))),
+            JarManifest::read_from_jar(format!("{dir}/tests/resources/compile.sh"))
+        );
+    }
+}
@@ -5,9 +5,11 @@ use crate::{
     array::Array,
     array_entry_type::ArrayEntryType,
     call_stack::CallStack,
-    exceptions::MethodCallFailed,
+    exceptions::{JavaException, MethodCallFailed},
     object::Object,
+    object_fields::set_object_field,
     stack_trace_element::StackTraceElement,
+    thread_scheduler::THREAD_STATUS_RUNNABLE,
     value::Value,
     vm::Vm,
     vm_error::VmError,
@@ -21,14 +23,16 @@ pub fn new_java_lang_string_object<'a>(
 ) -> Result<AbstractObject<'a>, MethodCallFailed<'a>> {
     let char_array: Vec<Value<'a>> = content
         .encode_utf16()
-        .map(|c| Value::Int(c as i32))
+        .map(|c| Value::Int(i32::from(c)))
         .collect();
 
-    let java_array = vm.new_array(ArrayEntryType::Base(BaseType::Char), char_array.len());
+    let scope = vm.handle_scope();
+    let java_array = vm.new_array(ArrayEntryType::Base(BaseType::Char), char_array.len())?;
     char_array
         .into_iter()
         .enumerate()
         .for_each(|(index, value)| java_array.set_element(index, value).unwrap());
+    let java_array = scope.handle(java_array);
 
     // In our JRE's rt.jar, the fields for String are:
     //    private final char[] value;
@@ -39,12 +43,55 @@ pub fn new_java_lang_string_object<'a>(
     //    private static final int HASHING_SEED;
     //    private transient int hash32;
     let string_object = vm.new_object(call_stack, "java/lang/String")?;
-    string_object.set_field(0, Value::Object(java_array));
+    let java_array = java_array.value();
+    vm.write_barrier(&java_array);
+    string_object.set_field(0, java_array);
     string_object.set_field(1, Value::Int(0));
     string_object.set_field(6, Value::Int(0));
     Ok(string_object)
 }
 
+/// Returns the canonical instance of the `java.lang.String` with the given content, so that
+/// all the string literals with the same content are the same object. It is created the first
+/// time that it is requested, unless `String.intern` registered another one before.
+pub fn get_interned_java_lang_string<'a>(
+    vm: &mut Vm<'a>,
+    call_stack: &mut CallStack<'a>,
+    content: &str,
+) -> Result<AbstractObject<'a>, MethodCallFailed<'a>> {
+    if let Some(string_object) = vm.find_interned_string(content) {
+        return Ok(string_object);
+    }
+    let string_object = new_java_lang_string_object(vm, call_stack, content)?;
+    vm.register_interned_string(content.to_string(), string_object.clone());
+    Ok(string_object)
+}
+
+/// Creates a new `java.lang.String[]` with the given content
+pub fn new_java_lang_string_array<'a>(
+    vm: &mut Vm<'a>,
+    call_stack: &mut CallStack<'a>,
+    strings: &[String],
+) -> Result<AbstractObject<'a>, MethodCallFailed<'a>> {
+    let class_id_java_lang_string = vm.get_or_resolve_class(call_stack, "java/lang/String")?.id;
+
+    let scope = vm.handle_scope();
+    let strings = strings
+        .iter()
+        .map(|s| new_java_lang_string_object(vm, call_stack, s).map(|s| scope.handle(s)))
+        .collect::<Result<Vec<_>, MethodCallFailed<'a>>>()?;
+    let array = vm.new_array(
+        ArrayEntryType::Object(class_id_java_lang_string),
+        strings.len(),
+    )?;
+    for (index, string) in strings.into_iter().enumerate() {
+        let string = string.value();
+        vm.write_barrier(&string);
+        array.set_element(index, string)?;
+    }
+    Ok(array)
+}
+
 /// Given an instance of `java.lang.String`, extracts the content as a Rust `String`
 pub fn extract_str_from_java_lang_string<'a>(
     vm: &Vm<'a>,
@@ -61,16 +108,105 @@ pub fn extract_str_from_java_lang_string<'a>(
     Err(VmError::ValidationException)
 }
 
-pub fn new_java_lang_class_object<'a>(
+/// Creates a new instance of the given subclass of `java.lang.Throwable`, invoking
+/// its constructor that takes the message as a `java.lang.String`
+pub fn new_java_lang_throwable_object<'a>(
+    vm: &mut Vm<'a>,
+    call_stack: &mut CallStack<'a>,
+    class_name: &str,
+    message: &str,
+) -> Result<AbstractObject<'a>, MethodCallFailed<'a>> {
+    let scope = vm.handle_scope();
+    let throwable = scope.handle(vm.new_object(call_stack, class_name)?);
+    let message = new_java_lang_string_object(vm, call_stack, message)?;
+    let constructor =
+        vm.resolve_class_method(call_stack, class_name, "<init>", "(Ljava/lang/String;)V")?;
+    vm.invoke(
+        call_stack,
+        constructor,
+        Some(throwable.get()),
+        vec![Value::Object(message)],
+    )?;
+    Ok(throwable.get())
+}
+
+/// Creates a new throwable of the given class without a message, like the
+/// `NullPointerException`s thrown by the JVM on null dereferences
+pub fn new_java_lang_throwable_object_without_message<'a>(
     vm: &mut Vm<'a>,
     call_stack: &mut CallStack<'a>,
     class_name: &str,
 ) -> Result<AbstractObject<'a>, MethodCallFailed<'a>> {
-    let class_object = vm.new_object(call_stack, "java/lang/Class")?;
+    let scope = vm.handle_scope();
+    let exception = scope.handle(vm.new_object(call_stack, class_name)?);
+    let constructor = vm.resolve_class_method(call_stack, class_name, "<init>", "()V")?;
+    vm.invoke(call_stack, constructor, Some(exception.get()), Vec::new())?;
+    Ok(exception.get())
+}
+
+/// Creates a new `java.lang.ExceptionInInitializerError`, wrapping the given exception
+/// thrown by a static initializer
+pub fn new_java_lang_exception_in_initializer_error<'a>(
+    vm: &mut Vm<'a>,
+    call_stack: &mut CallStack<'a>,
+    exception: AbstractObject<'a>,
+) -> Result<AbstractObject<'a>, MethodCallFailed<'a>> {
+    let class_name = "java/lang/ExceptionInInitializerError";
+    let scope = vm.handle_scope();
+    let exception = scope.handle(exception);
+    let error = scope.handle(vm.new_object(call_stack, class_name)?);
+    let constructor =
+        vm.resolve_class_method(call_stack, class_name, "<init>", "(Ljava/lang/Throwable;)V")?;
+    vm.invoke(
+        call_stack,
+        constructor,
+        Some(error.get()),
+        vec![Value::Object(exception.get())],
+    )?;
+    Ok(error.get())
+}
+
+/// Creates a new exception of the given class and returns it as an error, so that it
+/// can be propagated to the java code with just `return throw_exception(...)`
+pub fn throw_exception<'a, T>(
+    vm: &mut Vm<'a>,
+    call_stack: &mut CallStack<'a>,
+    class_name: &str,
+    message: &str,
+) -> Result<T, MethodCallFailed<'a>> {
+    let throwable = new_java_lang_throwable_object(vm, call_stack, class_name, message)?;
+    Err(MethodCallFailed::ExceptionThrown(JavaException(throwable)))
+}
+
+/// Returns the `java.lang.Class` instance of the given class, primitive type or array, so
+/// that the same object is used for all the class literals and `getClass` calls referring
+/// to it. It is created the first time that it is requested.
+pub fn get_java_lang_class_object<'a>(
+    vm: &mut Vm<'a>,
+    call_stack: &mut CallStack<'a>,
+    class_name: &str,
+) -> Result<AbstractObject<'a>, MethodCallFailed<'a>> {
+    if let Some(class_object) = vm.find_class_object(class_name) {
+        return Ok(class_object);
+    }
+    let class_object = new_java_lang_class_object(vm, call_stack, class_name)?;
+    vm.register_class_object(class_name, class_object.clone());
+    Ok(class_object)
+}
+
+fn new_java_lang_class_object<'a>(
+    vm: &mut Vm<'a>,
+    call_stack: &mut CallStack<'a>,
+    class_name: &str,
+) -> Result<AbstractObject<'a>, MethodCallFailed<'a>> {
+    let scope = vm.handle_scope();
+    let class_object = scope.handle(vm.new_object(call_stack, "java/lang/Class")?);
     // TODO: build a proper instance of Class object
     let string_object = new_java_lang_string_object(vm, call_stack, class_name)?;
-    class_object.set_field(5, Value::Object(string_object));
-    Ok(class_object)
+    let string_object = Value::Object(string_object);
+    vm.write_barrier(&string_object);
+    class_object.get().set_field(5, string_object);
+    Ok(class_object.get())
 }
 
 pub fn new_java_lang_stack_trace_element_object<'a>(
@@ -78,21 +214,30 @@ pub fn new_java_lang_stack_trace_element_object<'a>(
     call_stack: &mut CallStack<'a>,
     stack_trace_element: &StackTraceElement<'a>,
 ) -> Result<AbstractObject<'a>, MethodCallFailed<'a>> {
-    let class_name = Value::Object(new_java_lang_string_object(
+    let scope = vm.handle_scope();
+    let class_name = scope.handle(new_java_lang_string_object(
         vm,
         call_stack,
         stack_trace_element.class_name,
     )?);
-    let method_name = Value::Object(new_java_lang_string_object(
+    let method_name = scope.handle(new_java_lang_string_object(
         vm,
         call_stack,
         stack_trace_element.method_name,
     )?);
     let file_name = match stack_trace_element.source_file {
-        Some(file_name) => Value::Object(new_java_lang_string_object(vm, call_stack, file_name)?),
-        _ => Value::Null,
+        Some(file_name) => {
+            Some(scope.handle(new_java_lang_string_object(vm, call_stack, file_name)?))
+        }
+        _ => None,
     };
-    let line_number = Value::Int(stack_trace_element.line_number.unwrap_or(LineNumber(0)).0 as i32);
+    let line_number = Value::Int(
+        stack_trace_element
+            .line_number
+            .unwrap_or(LineNumber(0))
+            .0
+            .into(),
+    );
 
     // The class StackTraceElement has this layout:
     //     private String declaringClass;
@@ -101,6 +246,12 @@ pub fn new_java_lang_stack_trace_element_object<'a>(
     //     private int    lineNumber;
     let stack_trace_element_java_object =
         vm.new_object(call_stack, "java/lang/StackTraceElement")?;
+    let class_name = class_name.value();
+    let method_name = method_name.value();
+    let file_name = file_name.map_or(Value::Null, |file_name| file_name.value());
+    for value in [&class_name, &method_name, &file_name] {
+        vm.write_barrier(value);
+    }
     stack_trace_element_java_object.set_field(0, class_name);
     stack_trace_element_java_object.set_field(1, method_name);
     stack_trace_element_java_object.set_field(2, file_name);
@@ -108,3 +259,79 @@ pub fn new_java_lang_stack_trace_element_object<'a>(
 
     Ok(stack_trace_element_java_object)
 }
+
+/// The value of `java.lang.Thread.NORM_PRIORITY`
+const NORM_PRIORITY: i32 = 5;
+
+/// Creates the instance of `java.lang.Thread` for the main thread, in the group "main", and
+/// makes it the current thread's one. The thread is registered before its constructor runs,
+/// since the constructor needs `Thread.currentThread()`.
+pub(crate) fn new_main_thread_object<'a>(
+    vm: &mut Vm<'a>,
+    call_stack: &mut CallStack<'a>,
+) -> Result<AbstractObject<'a>, MethodCallFailed<'a>> {
+    let scope = vm.handle_scope();
+    let system_group = scope.handle(vm.new_object(call_stack, "java/lang/ThreadGroup")?);
+    let constructor =
+        vm.resolve_class_method(call_stack, "java/lang/ThreadGroup", "<init>", "()V")?;
+    vm.invoke(
+        call_stack,
+        constructor,
+        Some(system_group.get()),
+        Vec::new(),
+    )?;
+
+    let main_group = scope.handle(vm.new_object(call_stack, "java/lang/ThreadGroup")?);
+    let name = new_java_lang_string_object(vm, call_stack, "main")?;
+    let constructor = vm.resolve_class_method(
+        call_stack,
+        "java/lang/ThreadGroup",
+        "<init>",
+        "(Ljava/lang/ThreadGroup;Ljava/lang/String;)V",
+    )?;
+    vm.invoke(
+        call_stack,
+        constructor,
+        Some(main_group.get()),
+        vec![system_group.value(), Value::Object(name)],
+    )?;
+
+    // Thread's constructor copies the priority of the current thread, i.e. of this one
+    let thread = vm.new_object(call_stack, "java/lang/Thread")?;
+    set_object_field(vm, &thread, "priority", Value::Int(NORM_PRIORITY))?;
+    set_object_field(
+        vm,
+        &thread,
+        "threadStatus",
+        Value::Int(THREAD_STATUS_RUNNABLE),
+    )?;
+    vm.set_current_thread_object(thread.clone());
+    let thread = scope.handle(thread);
+
+    let name = new_java_lang_string_object(vm, call_stack, "main")?;
+    let constructor = vm.resolve_class_method(
+        call_stack,
+        "java/lang/Thread",
+        "<init>",
+        "(Ljava/lang/ThreadGroup;Ljava/lang/String;)V",
+    )?;
+    vm.invoke(
+        call_stack,
+        constructor,
+        Some(thread.get()),
+        vec![main_group.value(), Value::Object(name)],
+    )?;
+    let add = vm.resolve_class_method(
+        call_stack,
+        "java/lang/ThreadGroup",
+        "add",
+        "(Ljava/lang/Thread;)V",
+    )?;
+    vm.invoke(
+        call_stack,
+        add,
+        Some(main_group.get()),
+        vec![thread.value()],
+    )?;
+    Ok(thread.get())
+}
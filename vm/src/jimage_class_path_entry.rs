@@ -0,0 +1,379 @@
+use std::{
+    cell::RefCell,
+    collections::HashMap,
+    fmt::{Debug, Formatter},
+    fs::File,
+    io::{Read, Seek, SeekFrom},
+    path::Path,
+};
+
+use flate2::read::ZlibDecoder;
+use thiserror::Error;
+
+use rjvm_reader::type_conversion::CheckedNarrowing;
+
+use crate::class_path_entry::{ClassLoadingError, ClassPathEntry};
+
+/// Magic number at the start of a jimage, written in the byte order of the image
+const IMAGE_MAGIC: u32 = 0xCAFEDADA;
+/// The only major version of the format
+const IMAGE_MAJOR_VERSION: u32 = 1;
+/// Size of the header: magic, version, flags, resource count, table length, locations size
+/// and strings size, each of them an `u32`
+const IMAGE_HEADER_SIZE: usize = 7 * 4;
+
+/// Magic number of the header that precedes each layer of compression of a resource
+const COMPRESSED_RESOURCE_MAGIC: u32 = 0xCAFEFAFA;
+/// Size of the header of a compressed resource: magic, compressed size, uncompressed size,
+/// decompressor name, decompressor config and whether this is the last layer
+const COMPRESSED_RESOURCE_HEADER_SIZE: usize = 4 + 8 + 8 + 4 + 4 + 1;
+
+/// The pseudo-modules under which jimages list their modules and packages
+const DIRECTORY_MODULES: [&str; 2] = ["modules", "packages"];
+
+/// Kinds of the attributes of a location
+const ATTRIBUTE_END: usize = 0;
+const ATTRIBUTE_MODULE: usize = 1;
+const ATTRIBUTE_PARENT: usize = 2;
+const ATTRIBUTE_BASE: usize = 3;
+const ATTRIBUTE_EXTENSION: usize = 4;
+const ATTRIBUTE_OFFSET: usize = 5;
+const ATTRIBUTE_COMPRESSED: usize = 6;
+const ATTRIBUTE_UNCOMPRESSED: usize = 7;
+const ATTRIBUTE_COUNT: usize = 8;
+
+/// Implementation of [ClassPathEntry] that reads a jimage, the container format in which
+/// JDK 9+ stores its modules, i.e. the file `lib/modules` of a JDK.
+///
+/// The image is made of an index, that lists the location of each resource, e.g.
+/// `/java.base/java/lang/Object.class`, followed by the content of the resources. Since a
+/// class path has no notion of modules, all the resources are indexed by their path inside
+/// their module when the entry is created; only their content is read on demand.
+pub struct JImageClassPathEntry {
+    file_name: String,
+    file: RefCell<File>,
+    big_endian: bool,
+    /// Offset in the file of the content of the resources
+    content_start: u64,
+    strings: Vec<u8>,
+    resources: HashMap<String, ResourceLocation>,
+}
+
+/// Where a resource is stored, relative to the start of the content
+#[derive(Debug, Clone, Copy)]
+struct ResourceLocation {
+    offset: u64,
+    /// Zero if the resource is not compressed
+    compressed_size: u64,
+    uncompressed_size: u64,
+}
+
+impl Debug for JImageClassPathEntry {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "JImageClassPathEntry {{ file_name: {} }}",
+            self.file_name
+        )
+    }
+}
+
+impl JImageClassPathEntry {
+    /// Opens the image and indexes its resources
+    pub fn new<P: AsRef<Path>>(path: P) -> Result<Self, JImageError> {
+        let path = path.as_ref();
+        let file_name = path.to_string_lossy().to_string();
+        if !path.exists() {
+            return Err(JImageError::NotFound(file_name));
+        }
+        let mut file =
+            File::open(path).map_err(|_| JImageError::ReadingError(file_name.clone()))?;
+
+        let mut header = [0u8; IMAGE_HEADER_SIZE];
+        file.read_exact(&mut header)
+            .map_err(|_| JImageError::InvalidImage(file_name.clone()))?;
+        let big_endian = match header[0..4].try_into().unwrap() {
+            bytes if u32::from_le_bytes(bytes) == IMAGE_MAGIC => false,
+            bytes if u32::from_be_bytes(bytes) == IMAGE_MAGIC => true,
+            _ => return Err(JImageError::InvalidImage(file_name)),
+        };
+        let header_field = |index: usize| read_u32(&header, index * 4, big_endian).unwrap();
+        if header_field(1) >> 16 != IMAGE_MAJOR_VERSION {
+            return Err(JImageError::InvalidImage(file_name));
+        }
+        let size_field = |index: usize| {
+            header_field(index)
+                .narrow::<usize>()
+                .map_err(|_| JImageError::InvalidImage(file_name.clone()))
+        };
+        let table_length = size_field(4)?;
+        let locations_size = size_field(5)?;
+        let strings_size = size_field(6)?;
+
+        // The redirect table is only needed to look up locations by hash, which we do not do
+        let redirect_size = table_length * 4;
+        let offsets_size = table_length * 4;
+        let mut index = vec![0u8; redirect_size + offsets_size + locations_size + strings_size];
+        file.read_exact(&mut index)
+            .map_err(|_| JImageError::InvalidImage(file_name.clone()))?;
+        let (offsets, rest) = index[redirect_size..].split_at(offsets_size);
+        let (locations, strings) = rest.split_at(locations_size);
+
+        let mut entry = Self {
+            content_start: (IMAGE_HEADER_SIZE + index.len()) as u64,
+            file_name,
+            file: RefCell::new(file),
+            big_endian,
+            strings: strings.to_vec(),
+            resources: HashMap::new(),
+        };
+        for location_index in 0..table_length {
+            read_u32(offsets, location_index * 4, big_endian)
+                .and_then(|location_offset| location_offset.narrow().ok())
+                .and_then(|location_offset| entry.index_location(locations, location_offset))
+                .ok_or_else(|| JImageError::InvalidImage(entry.file_name.clone()))?;
+        }
+        Ok(entry)
+    }
+
+    fn index_location(&mut self, locations: &[u8], offset: usize) -> Option<()> {
+        let attributes = decode_location(locations.get(offset..)?)?;
+        let module = self.string(attributes[ATTRIBUTE_MODULE])?;
+        if module.is_empty() || DIRECTORY_MODULES.contains(&module.as_str()) {
+            return Some(());
+        }
+
+        let mut path = String::new();
+        let parent = self.string(attributes[ATTRIBUTE_PARENT])?;
+        if !parent.is_empty() {
+            path.push_str(&parent);
+            path.push('/');
+        }
+        path.push_str(&self.string(attributes[ATTRIBUTE_BASE])?);
+        let extension = self.string(attributes[ATTRIBUTE_EXTENSION])?;
+        if !extension.is_empty() {
+            path.push('.');
+            path.push_str(&extension);
+        }
+
+        // Packages cannot be split across modules, so only module-info.class can be repeated
+        self.resources.entry(path).or_insert(ResourceLocation {
+            offset: attributes[ATTRIBUTE_OFFSET],
+            compressed_size: attributes[ATTRIBUTE_COMPRESSED],
+            uncompressed_size: attributes[ATTRIBUTE_UNCOMPRESSED],
+        });
+        Some(())
+    }
+
+    /// Reads the zero-terminated string at the given offset of the strings table
+    fn string(&self, offset: u64) -> Option<String> {
+        let bytes = self.strings.get(offset.narrow().ok()?..)?;
+        let length = bytes.iter().position(|byte| *byte == 0)?;
+        Some(String::from_utf8_lossy(&bytes[..length]).to_string())
+    }
+
+    fn read_content(&self, location: &ResourceLocation) -> Result<Vec<u8>, ClassLoadingError> {
+        let size = if location.compressed_size != 0 {
+            location.compressed_size
+        } else {
+            location.uncompressed_size
+        };
+        let mut buffer = vec![0u8; size.narrow().map_err(ClassLoadingError::new)?];
+        let mut file = self.file.borrow_mut();
+        file.seek(SeekFrom::Start(self.content_start + location.offset))
+            .map_err(ClassLoadingError::new)?;
+        file.read_exact(&mut buffer)
+            .map_err(ClassLoadingError::new)?;
+        Ok(buffer)
+    }
+
+    /// Removes the layers of compression of a resource, each of which is preceded by a header
+    /// that names the algorithm used to compress it
+    fn decompress(&self, mut bytes: Vec<u8>) -> Result<Vec<u8>, ClassLoadingError> {
+        while read_u32(&bytes, 0, self.big_endian) == Some(COMPRESSED_RESOURCE_MAGIC) {
+            let invalid = || {
+                ClassLoadingError::new(JImageError::InvalidCompressedResource(
+                    self.file_name.clone(),
+                ))
+            };
+            let compressed_size: usize = read_u64(&bytes, 4, self.big_endian)
+                .and_then(|size| size.narrow().ok())
+                .ok_or_else(invalid)?;
+            let uncompressed_size: usize = read_u64(&bytes, 12, self.big_endian)
+                .and_then(|size| size.narrow().ok())
+                .ok_or_else(invalid)?;
+            let decompressor = read_u32(&bytes, 20, self.big_endian)
+                .and_then(|offset| self.string(u64::from(offset)))
+                .ok_or_else(invalid)?;
+            let payload = COMPRESSED_RESOURCE_HEADER_SIZE
+                .checked_add(compressed_size)
+                .and_then(|end| bytes.get(COMPRESSED_RESOURCE_HEADER_SIZE..end))
+                .ok_or_else(invalid)?;
+
+            bytes = match decompressor.as_str() {
+                "zip" => {
+                    let mut decompressed = Vec::with_capacity(uncompressed_size);
+                    ZlibDecoder::new(payload)
+                        .read_to_end(&mut decompressed)
+                        .map_err(ClassLoadingError::new)?;
+                    decompressed
+                }
+                _ => {
+                    return Err(ClassLoadingError::new(JImageError::UnsupportedCompression(
+                        self.file_name.clone(),
+                        decompressor,
+                    )))
+                }
+            };
+        }
+        Ok(bytes)
+    }
+}
+
+/// Decodes the attributes of a location. Each attribute starts with a byte whose top five
+/// bits are its kind and whose low three bits are its length minus one, followed by its
+/// value in big endian order; the list is terminated by an attribute of kind zero.
+fn decode_location(bytes: &[u8]) -> Option<[u64; ATTRIBUTE_COUNT]> {
+    let mut attributes = [0u64; ATTRIBUTE_COUNT];
+    let mut position = 0;
+    loop {
+        let byte = *bytes.get(position)?;
+        let kind = (byte >> 3) as usize;
+        if kind == ATTRIBUTE_END {
+            return Some(attributes);
+        }
+        let length = (byte & 0x7) as usize + 1;
+        let value = bytes
+            .get(position + 1..position + 1 + length)?
+            .iter()
+            .fold(0u64, |value, byte| (value << 8) | *byte as u64);
+        *attributes.get_mut(kind)? = value;
+        position += 1 + length;
+    }
+}
+
+fn read_u32(bytes: &[u8], offset: usize, big_endian: bool) -> Option<u32> {
+    let bytes: [u8; 4] = bytes.get(offset..offset + 4)?.try_into().ok()?;
+    Some(if big_endian {
+        u32::from_be_bytes(bytes)
+    } else {
+        u32::from_le_bytes(bytes)
+    })
+}
+
+fn read_u64(bytes: &[u8], offset: usize, big_endian: bool) -> Option<u64> {
+    let bytes: [u8; 8] = bytes.get(offset..offset + 8)?.try_into().ok()?;
+    Some(if big_endian {
+        u64::from_be_bytes(bytes)
+    } else {
+        u64::from_le_bytes(bytes)
+    })
+}
+
+impl ClassPathEntry for JImageClassPathEntry {
+    fn resolve_resource(&self, path: &str) -> Result<Option<Vec<u8>>, ClassLoadingError> {
+        match self.resources.get(path) {
+            Some(location) => {
+                let content = self.read_content(location)?;
+                if location.compressed_size != 0 {
+                    self.decompress(content).map(Some)
+                } else {
+                    Ok(Some(content))
+                }
+            }
+            None => Ok(None),
+        }
+    }
+
+    fn description(&self) -> String {
+        self.file_name.clone()
+    }
+
+    fn class_names(&self) -> Result<Vec<String>, ClassLoadingError> {
+        Ok(self
+            .resources
+            .keys()
+            .filter_map(|path| path.strip_suffix(".class"))
+            .map(str::to_string)
+            .collect())
+    }
+}
+
+/// Error returned if reading a jimage fails
+#[derive(Error, Debug, PartialEq)]
+pub enum JImageError {
+    #[error("file {0} not found")]
+    NotFound(String),
+
+    #[error("error reading file {0}")]
+    ReadingError(String),
+
+    #[error("file {0} is not a valid jimage")]
+    InvalidImage(String),
+
+    #[error("file {0} contains an invalid compressed resource")]
+    InvalidCompressedResource(String),
+
+    /// Only the `zip` compression is supported, and not the string sharing one
+    #[error("file {0} contains a resource compressed with the unsupported algorithm {1}")]
+    UnsupportedCompression(String, String),
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use crate::{
+        class_path_entry::{
+            tests::{assert_can_find_class, assert_cannot_find_class, assert_cannot_find_resource},
+            ClassPathEntry,
+        },
+        jimage_class_path_entry::{JImageClassPathEntry, JImageError},
+    };
+
+    #[test]
+    fn jimage_not_found() {
+        let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        path.push("tests/resources/modules/not_found");
+        assert_eq!(
+            JImageError::NotFound(path.to_string_lossy().to_string()),
+            JImageClassPathEntry::new(path).expect_err("should have thrown an error")
+        );
+    }
+
+    #[test]
+    fn file_is_not_a_jimage() {
+        let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        path.push("tests/resources/sample.jar");
+        assert_eq!(
+            JImageError::InvalidImage(path.to_string_lossy().to_string()),
+            JImageClassPathEntry::new(path).expect_err("should have thrown an error")
+        );
+    }
+
+    #[test]
+    fn valid_jimage_can_search_for_classes() {
+        let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        path.push("tests/resources/modules/modules");
+        let entry = JImageClassPathEntry::new(path).expect("should have read the jimage");
+
+        assert_can_find_class(&entry, "rjvm/SimpleMain");
+        assert_can_find_class(&entry, "rjvm/SimpleMain$Generator"); // Compressed
+        assert_cannot_find_class(&entry, "rjvm/Foo");
+        assert_cannot_find_resource(&entry, "rjvm/rjvm.sample");
+    }
+
+    #[test]
+    fn valid_jimage_can_list_classes() {
+        let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        path.push("tests/resources/modules/modules");
+        let entry = JImageClassPathEntry::new(path).expect("should have read the jimage");
+
+        let mut class_names = entry.class_names().expect("should be able to list classes");
+        class_names.sort();
+        assert_eq!(
+            vec!["rjvm/SimpleMain", "rjvm/SimpleMain$Generator"],
+            class_names
+        );
+    }
+}
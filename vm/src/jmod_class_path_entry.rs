@@ -0,0 +1,141 @@
+use std::{
+    cell::RefCell,
+    fmt::{Debug, Formatter},
+    fs::File,
+    io::{BufReader, Read},
+    path::Path,
+};
+
+use thiserror::Error;
+use zip::ZipArchive;
+
+use crate::{
+    class_path_entry::{ClassLoadingError, ClassPathEntry},
+    jar_file_class_path_entry::read_zip_file,
+};
+
+/// The bytes preceding the zip archive in a jmod: the characters `JM` and the version, 1.0
+const JMOD_MAGIC: [u8; 4] = [b'J', b'M', 1, 0];
+
+/// The directory of a jmod containing the classes and resources of the module; the other
+/// ones contain native libraries, executables and configuration files
+const CLASSES_DIRECTORY: &str = "classes/";
+
+/// Implementation of [ClassPathEntry] that reads the classes of a `.jmod` file, the format
+/// in which JDK 9+ ships its modules for `jlink`
+pub struct JmodClassPathEntry {
+    file_name: String,
+    zip: RefCell<ZipArchive<BufReader<File>>>,
+}
+
+impl Debug for JmodClassPathEntry {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "JmodClassPathEntry {{ file_name: {} }}", self.file_name)
+    }
+}
+
+impl JmodClassPathEntry {
+    pub fn new<P: AsRef<Path>>(path: P) -> Result<Self, JmodFileError> {
+        let path = path.as_ref();
+        let file_name = path.to_string_lossy().to_string();
+        if !path.exists() {
+            return Err(JmodFileError::NotFound(file_name));
+        }
+        let mut file =
+            File::open(path).map_err(|_| JmodFileError::ReadingError(file_name.clone()))?;
+
+        let mut magic = [0u8; 4];
+        if file.read_exact(&mut magic).is_err() || magic != JMOD_MAGIC {
+            return Err(JmodFileError::InvalidJmod(file_name));
+        }
+        // The zip archive takes care of the bytes that precede it
+        let zip = ZipArchive::new(BufReader::new(file))
+            .map_err(|_| JmodFileError::InvalidJmod(file_name.clone()))?;
+        Ok(Self {
+            file_name,
+            zip: RefCell::new(zip),
+        })
+    }
+}
+
+impl ClassPathEntry for JmodClassPathEntry {
+    fn resolve_resource(&self, path: &str) -> Result<Option<Vec<u8>>, ClassLoadingError> {
+        read_zip_file(
+            &mut self.zip.borrow_mut(),
+            &format!("{CLASSES_DIRECTORY}{path}"),
+        )
+    }
+
+    fn description(&self) -> String {
+        self.file_name.clone()
+    }
+
+    fn class_names(&self) -> Result<Vec<String>, ClassLoadingError> {
+        Ok(self
+            .zip
+            .borrow()
+            .file_names()
+            .filter_map(|file_name| file_name.strip_prefix(CLASSES_DIRECTORY))
+            .filter_map(|file_name| file_name.strip_suffix(".class"))
+            .map(|class_name| class_name.to_string())
+            .collect())
+    }
+}
+
+/// Error returned if reading a jmod fails
+#[derive(Error, Debug, PartialEq)]
+pub enum JmodFileError {
+    #[error("file {0} not found")]
+    NotFound(String),
+
+    #[error("error reading file {0}")]
+    ReadingError(String),
+
+    #[error("file {0} is not a valid jmod")]
+    InvalidJmod(String),
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use crate::{
+        class_path_entry::{
+            tests::{assert_can_find_class, assert_cannot_find_class, assert_cannot_find_resource},
+            ClassPathEntry,
+        },
+        jmod_class_path_entry::{JmodClassPathEntry, JmodFileError},
+    };
+
+    #[test]
+    fn file_is_not_a_jmod() {
+        let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        path.push("tests/resources/sample.jar");
+        assert_eq!(
+            JmodFileError::InvalidJmod(path.to_string_lossy().to_string()),
+            JmodClassPathEntry::new(path).expect_err("should have thrown an error")
+        );
+    }
+
+    #[test]
+    fn valid_jmod_can_search_for_classes() {
+        let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        path.push("tests/resources/modules/rjvm.sample.jmod");
+        let entry = JmodClassPathEntry::new(path).expect("should have read the jmod");
+
+        assert_can_find_class(&entry, "rjvm/SimpleMain");
+        assert_cannot_find_class(&entry, "rjvm/Foo");
+        assert_cannot_find_resource(&entry, "rjvm/");
+
+        let mut class_names = entry.class_names().expect("should be able to list classes");
+        class_names.sort();
+        assert_eq!(
+            vec![
+                "module-info",
+                "rjvm/SimpleMain",
+                "rjvm/SimpleMain$Generator"
+            ],
+            class_names
+        );
+    }
+}
@@ -0,0 +1,581 @@
+use std::{borrow::Cow, collections::HashMap};
+
+use log::{warn, Level};
+
+use rjvm_reader::{
+    class_access_flags::ClassAccessFlags,
+    class_file::ClassFile,
+    class_file_field::ClassFileField,
+    class_file_method::ClassFileMethod,
+    constant_pool::{ConstantPool, ConstantPoolEntry},
+    field_flags::FieldFlags,
+    field_type::FieldType,
+    method_descriptor::MethodDescriptor,
+    method_flags::MethodFlags,
+    method_handle_kind::MethodHandleKind,
+};
+
+use crate::{
+    abstract_object::AbstractObject,
+    call_frame::MethodCallResult,
+    call_stack::CallStack,
+    class::{ClassId, ClassRef},
+    class_and_method::ClassAndMethod,
+    exceptions::MethodCallFailed,
+    log_config::{vm_log, LogCategory},
+    object::Object,
+    value::Value,
+    vm::Vm,
+    vm_error::VmError,
+};
+
+const LAMBDA_METAFACTORY: &str = "java/lang/invoke/LambdaMetafactory";
+
+/// Flags of `LambdaMetafactory.altMetafactory`
+const FLAG_SERIALIZABLE: i32 = 1;
+const FLAG_MARKERS: i32 = 2;
+const FLAG_BRIDGES: i32 = 4;
+
+/// The method invoked by the functional interface's method of a lambda
+#[derive(Debug, Clone)]
+enum LambdaTarget<'a> {
+    /// A static method, i.e. the synthetic method generated by the compiler for a lambda
+    /// that does not use `this`, or a reference to a static method
+    Static(ClassAndMethod<'a>),
+    /// A private instance method, invoked without virtual resolution
+    Special(ClassAndMethod<'a>),
+    /// A reference to an instance method, resolved on the receiver
+    Virtual {
+        method_name: String,
+        type_descriptor: String,
+    },
+    /// A reference to a constructor, i.e. `Foo::new`
+    Constructor(ClassAndMethod<'a>),
+}
+
+/// Stores the `invokedynamic` call sites that have been linked by the `LambdaMetafactory`.
+///
+/// Rather than spinning bytecode like the JDK does, for each call site we define a synthetic
+/// class that implements the functional interface: its instances store the captured values
+/// in their fields, and its interface method is a native method that forwards the captured
+/// values and the arguments to the target method.
+#[derive(Debug, Default)]
+pub(crate) struct LambdaCallSites<'a> {
+    /// Lambda classes, by caller class, version of the caller class and index of the
+    /// `InvokeDynamic` constant, which can change when the class is redefined
+    call_sites: HashMap<(ClassId, u32, u16), ClassRef<'a>>,
+    implementations: HashMap<ClassId, LambdaImplementation<'a>>,
+}
+
+#[derive(Debug, Clone)]
+struct LambdaImplementation<'a> {
+    target: LambdaTarget<'a>,
+    /// Whether the interface method is void, in which case the target's result is discarded
+    discards_result: bool,
+}
+
+/// The information extracted from the bootstrap method of a lambda call site
+struct LambdaCallSite<'c> {
+    interface_name: Cow<'c, str>,
+    method_name: Cow<'c, str>,
+    captured_types: Vec<FieldType>,
+    method_descriptors: Vec<String>,
+    marker_interfaces: Vec<String>,
+    target_kind: MethodHandleKind,
+    target_class: Cow<'c, str>,
+    target_method: Cow<'c, str>,
+    target_descriptor: Cow<'c, str>,
+}
+
+/// Executes an `invokedynamic` instruction: links the call site the first time, and then
+/// creates a new lambda object capturing the given values
+pub(crate) fn invoke_dynamic<'a>(
+    vm: &mut Vm<'a>,
+    call_stack: &mut CallStack<'a>,
+    caller: ClassRef<'a>,
+    constant_index: u16,
+) -> Result<ClassRef<'a>, MethodCallFailed<'a>> {
+    if let Some(lambda_class) =
+        vm.lambda_call_sites
+            .call_sites
+            .get(&(caller.id, caller.version, constant_index))
+    {
+        return Ok(lambda_class);
+    }
+
+    let call_site = parse_call_site(caller, constant_index)?;
+    let implementation = LambdaImplementation {
+        target: resolve_target(vm, call_stack, &call_site)?,
+        discards_result: parse_method_descriptor(&call_site.method_descriptors[0])?
+            .return_type
+            .is_none(),
+    };
+    let class_name = format!(
+        "{}$$Lambda${}",
+        caller.name,
+        vm.lambda_call_sites.implementations.len() + 1
+    );
+    vm_log!(
+        vm.log_config(),
+        LogCategory::Resolution,
+        Level::Debug,
+        "linking invokedynamic call site {} of class {} to {} {}.{}:{}, implemented by {}",
+        constant_index,
+        caller.name,
+        call_site.target_kind,
+        call_site.target_class,
+        call_site.target_method,
+        call_site.target_descriptor,
+        class_name
+    );
+
+    let lambda_class = vm.define_class(
+        call_stack,
+        lambda_class_file(&class_name, &call_site)?,
+        caller,
+    )?;
+    for method_descriptor in call_site.method_descriptors.iter() {
+        vm.native_methods_registry.register(
+            &class_name,
+            &call_site.method_name,
+            method_descriptor,
+            invoke_lambda_target,
+        );
+    }
+    vm.lambda_call_sites
+        .implementations
+        .insert(lambda_class.id, implementation);
+    vm.lambda_call_sites
+        .call_sites
+        .insert((caller.id, caller.version, constant_index), lambda_class);
+    Ok(lambda_class)
+}
+
+/// Stores the captured values in the fields of a lambda
+pub(crate) fn set_captured_values<'a>(
+    vm: &Vm<'a>,
+    lambda_class: ClassRef<'a>,
+    lambda: &AbstractObject<'a>,
+    captured_values: Vec<Value<'a>>,
+) {
+    for (index, value) in captured_values.into_iter().enumerate() {
+        vm.write_barrier(&value);
+        lambda.set_field(lambda_class.first_field_index + index, value);
+    }
+}
+
+/// Returns the number of values captured by the instances of the given lambda class
+pub(crate) fn num_captured_values(lambda_class: ClassRef) -> usize {
+    lambda_class.num_total_fields - lambda_class.first_field_index
+}
+
+fn parse_call_site<'a>(
+    caller: ClassRef<'a>,
+    constant_index: u16,
+) -> Result<LambdaCallSite<'a>, VmError> {
+    let constants = &caller.constants;
+    let &ConstantPoolEntry::InvokeDynamic(bootstrap_method_index, name_and_type) =
+        get_constant(constants, constant_index)?
+    else {
+        return Err(VmError::ValidationException);
+    };
+    let (method_name, call_site_descriptor) = get_name_and_type(constants, name_and_type)?;
+    let call_site_descriptor = parse_method_descriptor(&call_site_descriptor)?;
+    let Some(FieldType::Object(interface_name)) = call_site_descriptor.return_type else {
+        return Err(VmError::ValidationException);
+    };
+
+    let bootstrap_method = caller
+        .bootstrap_methods
+        .get(usize::from(bootstrap_method_index))
+        .ok_or(VmError::ValidationException)?;
+    let (bootstrap_kind, bootstrap_class, bootstrap_name, _) =
+        get_method_handle(constants, bootstrap_method.method_ref)?;
+    let is_alt_metafactory = match bootstrap_name.as_ref() {
+        "metafactory" => false,
+        "altMetafactory" => true,
+        _ => {
+            return Err(unsupported_bootstrap_method(
+                constants,
+                bootstrap_method.method_ref,
+            ))
+        }
+    };
+    if bootstrap_kind != MethodHandleKind::InvokeStatic || bootstrap_class != LAMBDA_METAFACTORY {
+        return Err(unsupported_bootstrap_method(
+            constants,
+            bootstrap_method.method_ref,
+        ));
+    }
+
+    // The arguments are: the erased descriptor of the interface method, the target,
+    // and the instantiated descriptor of the interface method, which we do not need since
+    // we do not check casts. altMetafactory adds some flags and their arguments.
+    let arguments = &bootstrap_method.arguments;
+    if arguments.len() < 3 {
+        return Err(VmError::ValidationException);
+    }
+    let mut method_descriptors = vec![get_method_type(constants, arguments[0])?];
+    let (target_kind, target_class, target_method, target_descriptor) =
+        get_method_handle(constants, arguments[1])?;
+
+    let mut marker_interfaces = Vec::new();
+    if is_alt_metafactory {
+        let mut arguments = arguments[3..].iter();
+        let mut next_argument = || arguments.next().ok_or(VmError::ValidationException);
+        let &ConstantPoolEntry::Integer(flags) = get_constant(constants, *next_argument()?)? else {
+            return Err(VmError::ValidationException);
+        };
+        if flags & FLAG_SERIALIZABLE != 0 {
+            marker_interfaces.push("java/io/Serializable".to_string());
+        }
+        if flags & FLAG_MARKERS != 0 {
+            let count = get_int(constants, *next_argument()?)?;
+            for _ in 0..count {
+                let marker = constants
+                    .string_of(*next_argument()?)
+                    .map_err(|_| VmError::ValidationException)?;
+                if !marker_interfaces.iter().any(|m| *m == marker) {
+                    marker_interfaces.push(marker.into_owned());
+                }
+            }
+        }
+        if flags & FLAG_BRIDGES != 0 {
+            let count = get_int(constants, *next_argument()?)?;
+            for _ in 0..count {
+                method_descriptors.push(get_method_type(constants, *next_argument()?)?);
+            }
+        }
+    }
+
+    Ok(LambdaCallSite {
+        interface_name: Cow::Owned(interface_name),
+        method_name,
+        captured_types: call_site_descriptor.parameters,
+        method_descriptors,
+        marker_interfaces,
+        target_kind,
+        target_class,
+        target_method,
+        target_descriptor,
+    })
+}
+
+fn resolve_target<'a>(
+    vm: &mut Vm<'a>,
+    call_stack: &mut CallStack<'a>,
+    call_site: &LambdaCallSite,
+) -> Result<LambdaTarget<'a>, MethodCallFailed<'a>> {
+    let target_descriptor = parse_method_descriptor(&call_site.target_descriptor)?;
+    let interface_descriptor = parse_method_descriptor(&call_site.method_descriptors[0])?;
+    check_no_boxing_required(call_site, &target_descriptor, &interface_descriptor)?;
+
+    match call_site.target_kind {
+        MethodHandleKind::InvokeVirtual | MethodHandleKind::InvokeInterface => {
+            Ok(LambdaTarget::Virtual {
+                method_name: call_site.target_method.to_string(),
+                type_descriptor: call_site.target_descriptor.to_string(),
+            })
+        }
+        kind @ (MethodHandleKind::InvokeStatic
+        | MethodHandleKind::InvokeSpecial
+        | MethodHandleKind::NewInvokeSpecial) => {
+            let class_and_method = vm.resolve_class_method(
+                call_stack,
+                &call_site.target_class,
+                &call_site.target_method,
+                &call_site.target_descriptor,
+            )?;
+            Ok(match kind {
+                MethodHandleKind::InvokeStatic => LambdaTarget::Static(class_and_method),
+                MethodHandleKind::InvokeSpecial => LambdaTarget::Special(class_and_method),
+                _ => LambdaTarget::Constructor(class_and_method),
+            })
+        }
+        _ => {
+            warn!(
+                "unsupported lambda target kind {} for {}.{}",
+                call_site.target_kind, call_site.target_class, call_site.target_method
+            );
+            Err(MethodCallFailed::InternalError(VmError::NotImplemented))
+        }
+    }
+}
+
+/// Since we do not support boxing yet, we need the primitive types of the interface method
+/// to match those of the target
+fn check_no_boxing_required(
+    call_site: &LambdaCallSite,
+    target_descriptor: &MethodDescriptor,
+    interface_descriptor: &MethodDescriptor,
+) -> Result<(), VmError> {
+    let has_receiver = matches!(
+        call_site.target_kind,
+        MethodHandleKind::InvokeVirtual
+            | MethodHandleKind::InvokeInterface
+            | MethodHandleKind::InvokeSpecial
+    );
+    let mut target_parameters: Vec<&FieldType> = Vec::new();
+    let receiver_type = FieldType::Object(call_site.target_class.to_string());
+    if has_receiver {
+        target_parameters.push(&receiver_type);
+    }
+    target_parameters.extend(target_descriptor.parameters.iter());
+
+    let source_parameters = call_site
+        .captured_types
+        .iter()
+        .chain(interface_descriptor.parameters.iter());
+    let parameters_match = target_parameters.len()
+        == call_site.captured_types.len() + interface_descriptor.parameters.len()
+        && source_parameters
+            .zip(target_parameters)
+            .all(|(source, target)| is_primitive(source) == is_primitive(target));
+
+    let target_return_type = match call_site.target_kind {
+        MethodHandleKind::NewInvokeSpecial => Some(receiver_type.clone()),
+        _ => target_descriptor.return_type.clone(),
+    };
+    let return_types_match = match (&interface_descriptor.return_type, &target_return_type) {
+        (None, _) => true,
+        (Some(interface_type), Some(target_type)) => {
+            is_primitive(interface_type) == is_primitive(target_type)
+        }
+        (Some(_), None) => false,
+    };
+
+    if parameters_match && return_types_match {
+        Ok(())
+    } else {
+        warn!(
+            "lambda {}.{}{} requires boxing or unboxing, which is not supported",
+            call_site.interface_name, call_site.method_name, call_site.method_descriptors[0]
+        );
+        Err(VmError::NotImplemented)
+    }
+}
+
+fn is_primitive(field_type: &FieldType) -> bool {
+    matches!(field_type, FieldType::Base(_))
+}
+
+/// Creates the synthetic class implementing the functional interface of the given call site
+fn lambda_class_file(
+    class_name: &str,
+    call_site: &LambdaCallSite,
+) -> Result<ClassFile<'static>, VmError> {
+    let fields = call_site
+        .captured_types
+        .iter()
+        .enumerate()
+        .map(|(index, field_type)| ClassFileField {
+            flags: FieldFlags::PRIVATE | FieldFlags::FINAL,
+            name: Cow::Owned(format!("arg${}", index + 1)),
+            type_descriptor: field_type.clone(),
+            constant_value: None,
+            deprecated: false,
+            attributes: Vec::new(),
+        })
+        .collect();
+    let methods = call_site
+        .method_descriptors
+        .iter()
+        .map(|descriptor| {
+            Ok(ClassFileMethod {
+                flags: MethodFlags::PUBLIC | MethodFlags::NATIVE | MethodFlags::SYNTHETIC,
+                name: Cow::Owned(call_site.method_name.to_string()),
+                type_descriptor: Cow::Owned(descriptor.clone()),
+                parsed_type_descriptor: parse_method_descriptor(descriptor)?,
+                attributes: Vec::new(),
+                code: None,
+                deprecated: false,
+                thrown_exceptions: Vec::new(),
+            })
+        })
+        .collect::<Result<Vec<ClassFileMethod>, VmError>>()?;
+
+    let mut interfaces = vec![Cow::Owned(call_site.interface_name.to_string())];
+    interfaces.extend(
+        call_site
+            .marker_interfaces
+            .iter()
+            .filter(|marker| **marker != call_site.interface_name)
+            .map(|marker| Cow::Owned(marker.clone())),
+    );
+
+    Ok(ClassFile {
+        constants: ConstantPool::new(),
+        flags: ClassAccessFlags::FINAL | ClassAccessFlags::SUPER | ClassAccessFlags::SYNTHETIC,
+        name: Cow::Owned(class_name.to_string()),
+        superclass: Some(Cow::Borrowed("java/lang/Object")),
+        interfaces,
+        fields,
+        methods,
+        ..Default::default()
+    })
+}
+
+/// Native implementation of the interface method of all the lambda classes
+fn invoke_lambda_target<'a>(
+    vm: &mut Vm<'a>,
+    call_stack: &mut CallStack<'a>,
+    receiver: Option<AbstractObject<'a>>,
+    args: Vec<Value<'a>>,
+) -> MethodCallResult<'a> {
+    let lambda = receiver.ok_or(VmError::ValidationException)?;
+    let lambda_class = vm.get_class_by_id(lambda.class_id())?;
+    let implementation = vm
+        .lambda_call_sites
+        .implementations
+        .get(&lambda_class.id)
+        .cloned()
+        .ok_or(VmError::ValidationException)?;
+
+    // Like the arguments, long and double captured values must be followed by an empty slot
+    let mut arguments: Vec<Value<'a>> = Vec::with_capacity(args.len());
+    for index in lambda_class.first_field_index..lambda_class.num_total_fields {
+        let value = lambda.get_field(lambda_class, index);
+        let takes_two_slots = matches!(value, Value::Long(_) | Value::Double(_));
+        arguments.push(value);
+        if takes_two_slots {
+            arguments.push(Value::Uninitialized);
+        }
+    }
+    arguments.extend(args);
+
+    let result = match implementation.target {
+        LambdaTarget::Static(class_and_method) => {
+            vm.invoke(call_stack, class_and_method, None, arguments)
+        }
+        LambdaTarget::Special(class_and_method) => {
+            let receiver = pop_receiver(&mut arguments)?;
+            vm.invoke(call_stack, class_and_method, Some(receiver), arguments)
+        }
+        LambdaTarget::Virtual {
+            method_name,
+            type_descriptor,
+        } => {
+            let receiver = pop_receiver(&mut arguments)?;
+            vm.invoke_virtual(
+                call_stack,
+                receiver,
+                &method_name,
+                &type_descriptor,
+                arguments,
+            )
+        }
+        LambdaTarget::Constructor(class_and_method) => {
+            let object = vm.new_object_of_class(class_and_method.class)?;
+            vm.invoke(
+                call_stack,
+                class_and_method,
+                Some(object.clone()),
+                arguments,
+            )?;
+            Ok(Some(Value::Object(object)))
+        }
+    }?;
+    Ok(if implementation.discards_result {
+        None
+    } else {
+        result
+    })
+}
+
+/// Extracts the receiver of an instance method from the arguments of a lambda
+fn pop_receiver<'a>(
+    arguments: &mut Vec<Value<'a>>,
+) -> Result<AbstractObject<'a>, MethodCallFailed<'a>> {
+    if arguments.is_empty() {
+        return Err(MethodCallFailed::InternalError(
+            VmError::ValidationException,
+        ));
+    }
+    match arguments.remove(0) {
+        Value::Object(receiver) => Ok(receiver),
+        Value::Null => Err(MethodCallFailed::InternalError(
+            VmError::NullPointerException,
+        )),
+        _ => Err(MethodCallFailed::InternalError(
+            VmError::ValidationException,
+        )),
+    }
+}
+
+fn get_constant<'c, 'a>(
+    constants: &'c ConstantPool<'a>,
+    index: u16,
+) -> Result<&'c ConstantPoolEntry<'a>, VmError> {
+    constants
+        .get(index)
+        .map_err(|_| VmError::ValidationException)
+}
+
+fn get_int(constants: &ConstantPool, index: u16) -> Result<i32, VmError> {
+    match get_constant(constants, index)? {
+        ConstantPoolEntry::Integer(value) => Ok(*value),
+        _ => Err(VmError::ValidationException),
+    }
+}
+
+fn get_name_and_type<'a>(
+    constants: &ConstantPool<'a>,
+    index: u16,
+) -> Result<(Cow<'a, str>, Cow<'a, str>), VmError> {
+    let &ConstantPoolEntry::NameAndTypeDescriptor(name, type_descriptor) =
+        get_constant(constants, index)?
+    else {
+        return Err(VmError::ValidationException);
+    };
+    let name = constants
+        .string_of(name)
+        .map_err(|_| VmError::ValidationException)?;
+    let type_descriptor = constants
+        .string_of(type_descriptor)
+        .map_err(|_| VmError::ValidationException)?;
+    Ok((name, type_descriptor))
+}
+
+fn get_method_type(constants: &ConstantPool, index: u16) -> Result<String, VmError> {
+    match get_constant(constants, index)? {
+        ConstantPoolEntry::MethodType(descriptor) => constants
+            .text_of(*descriptor)
+            .map_err(|_| VmError::ValidationException),
+        _ => Err(VmError::ValidationException),
+    }
+}
+
+/// Returns the kind, class name, method name and descriptor of a method handle
+#[allow(clippy::type_complexity)]
+fn get_method_handle<'a>(
+    constants: &ConstantPool<'a>,
+    index: u16,
+) -> Result<(MethodHandleKind, Cow<'a, str>, Cow<'a, str>, Cow<'a, str>), VmError> {
+    let &ConstantPoolEntry::MethodHandle(kind, reference) = get_constant(constants, index)? else {
+        return Err(VmError::ValidationException);
+    };
+    let (ConstantPoolEntry::MethodReference(class, name_and_type)
+    | ConstantPoolEntry::InterfaceMethodReference(class, name_and_type)
+    | ConstantPoolEntry::FieldReference(class, name_and_type)) =
+        *get_constant(constants, reference)?
+    else {
+        return Err(VmError::ValidationException);
+    };
+    let class_name = constants
+        .string_of(class)
+        .map_err(|_| VmError::ValidationException)?;
+    let (name, type_descriptor) = get_name_and_type(constants, name_and_type)?;
+    Ok((kind, class_name, name, type_descriptor))
+}
+
+fn unsupported_bootstrap_method(constants: &ConstantPool, index: u16) -> VmError {
+    VmError::UnsupportedBootstrapMethod(
+        constants
+            .text_of(index)
+            .unwrap_or_else(|_| format!("#{index}")),
+    )
+}
+
+fn parse_method_descriptor(descriptor: &str) -> Result<MethodDescriptor, VmError> {
+    MethodDescriptor::parse(descriptor).map_err(|_| VmError::ValidationException)
+}
@@ -1,27 +1,82 @@
+// Sizes and offsets read from class files and archives must never be silently truncated
+#![deny(clippy::cast_possible_truncation)]
+
 pub mod abstract_object;
+mod access_control;
 pub mod alloc_entry;
+pub mod allocation;
 pub mod array;
 pub mod array_entry_type;
+mod boxed_caches;
 mod call_frame;
 pub mod call_stack;
+mod character;
 pub mod class;
 pub mod class_and_method;
-mod class_loader;
+pub mod class_file_transformer;
+pub mod class_load_observer;
 mod class_manager;
 mod class_path;
-mod class_path_entry;
+pub mod class_path_entry;
+pub mod class_path_scan;
+#[cfg(feature = "std")]
+mod class_prefetch;
 mod class_resolver_by_id;
+mod collection_intrinsics;
+mod decoded_code;
+mod environment;
 pub mod exceptions;
+mod file_io;
+mod file_system;
 mod file_system_class_path_entry;
+mod floating_decimal;
 mod gc;
+pub mod gc_algorithm;
+pub mod gc_stats;
+pub mod handles;
+pub mod heap_growth;
+pub mod heap_object;
+mod identity_hash;
+pub mod incremental_marking;
+mod inline_cache;
+mod instruction_site;
+pub mod io;
 mod jar_file_class_path_entry;
+pub mod jar_manifest;
 pub mod java_objects_creation;
+mod jimage_class_path_entry;
+mod jmod_class_path_entry;
+mod lambda_metafactory;
+mod linker;
+pub mod log_config;
+mod math;
+pub mod memory_class_path_entry;
 mod native_methods_impl;
 pub mod native_methods_registry;
+mod nested_jar_class_path_entry;
 pub mod object;
+pub mod object_fields;
+mod object_side_table;
+pub mod preload_summary;
+mod quickened_fields;
+pub mod reference_objects;
+mod resources;
+pub mod run_config;
+pub mod safepoint;
+mod shutdown_hooks;
+mod sockets;
 pub mod stack_trace_element;
+mod standard_streams;
+mod system_properties;
+pub mod thread_scheduler;
 mod time;
+mod user_class_loaders;
 pub mod value;
 mod value_stack;
+pub mod verification_report;
+mod verifier;
 pub mod vm;
+pub mod vm_builder;
 pub mod vm_error;
+mod vtable;
+mod zip_central_directory;
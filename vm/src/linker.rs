@@ -0,0 +1,403 @@
+use rjvm_reader::{
+    class_file::ClassFile, constant_pool::ConstantPoolEntry, field_type::FieldType,
+    method_descriptor::MethodDescriptor, method_handle_kind::MethodHandleKind,
+};
+
+use crate::vm_error::{ClassFormatError, VmError};
+
+/// Links a class, checking that every entry of its constant pool references entries of the
+/// right kind and that the descriptors are well formed. This way a broken class is rejected
+/// when it is defined, rather than when an instruction happens to use the broken entry.
+pub(crate) fn link_class(class_file: &ClassFile) -> Result<(), VmError> {
+    let linker = ConstantPoolLinker { class_file };
+    for (index, entry) in class_file.constants.iter() {
+        linker
+            .check_entry(entry)
+            .map_err(|reason| class_format_error(class_file, index, reason))?;
+    }
+    for bootstrap_method in class_file.bootstrap_methods.iter() {
+        linker
+            .check_bootstrap_method_handle(bootstrap_method.method_ref)
+            .map_err(|reason| {
+                class_format_error(class_file, bootstrap_method.method_ref, reason)
+            })?;
+        for argument in bootstrap_method.arguments.iter() {
+            linker
+                .check_loadable(*argument)
+                .map_err(|reason| class_format_error(class_file, *argument, reason))?;
+        }
+    }
+    Ok(())
+}
+
+fn class_format_error(class_file: &ClassFile, index: u16, reason: String) -> VmError {
+    VmError::ClassFormatError(Box::new(ClassFormatError {
+        class_name: class_file.name.to_string(),
+        index,
+        reason,
+    }))
+}
+
+/// The reason why an entry is invalid
+type LinkResult<T> = Result<T, String>;
+
+/// What a reference to a method is used for, which restricts the names it can have
+#[derive(Clone, Copy, PartialEq)]
+enum MethodUse {
+    Any,
+    Constructor,
+    NotConstructor,
+}
+
+struct ConstantPoolLinker<'c, 'a> {
+    class_file: &'c ClassFile<'a>,
+}
+
+impl<'c, 'a> ConstantPoolLinker<'c, 'a> {
+    fn check_entry(&self, entry: &ConstantPoolEntry) -> LinkResult<()> {
+        match entry {
+            ConstantPoolEntry::Utf8(_)
+            | ConstantPoolEntry::Integer(_)
+            | ConstantPoolEntry::Float(_)
+            | ConstantPoolEntry::Long(_)
+            | ConstantPoolEntry::Double(_) => Ok(()),
+            ConstantPoolEntry::ClassReference(name_index) => {
+                self.class_name(*name_index).map(|_| ())
+            }
+            ConstantPoolEntry::StringReference(string_index) => {
+                self.utf8(*string_index).map(|_| ())
+            }
+            ConstantPoolEntry::FieldReference(class_index, name_and_type_index) => {
+                self.check_field_reference(*class_index, *name_and_type_index)
+            }
+            ConstantPoolEntry::MethodReference(class_index, name_and_type_index)
+            | ConstantPoolEntry::InterfaceMethodReference(class_index, name_and_type_index) => {
+                self.check_method_reference(*class_index, *name_and_type_index, MethodUse::Any)
+            }
+            ConstantPoolEntry::NameAndTypeDescriptor(name_index, descriptor_index) => self
+                .name_and_type(*name_index, *descriptor_index)
+                .map(|_| ()),
+            ConstantPoolEntry::MethodHandle(kind, reference_index) => {
+                self.check_method_handle(kind, *reference_index)
+            }
+            ConstantPoolEntry::MethodType(descriptor_index) => {
+                self.method_descriptor(*descriptor_index).map(|_| ())
+            }
+            ConstantPoolEntry::InvokeDynamic(bootstrap_method_index, name_and_type_index) => {
+                if usize::from(*bootstrap_method_index) >= self.class_file.bootstrap_methods.len() {
+                    return Err(format!(
+                        "invalid bootstrap method index {bootstrap_method_index}"
+                    ));
+                }
+                let (_, descriptor_index) = self.name_and_type_indexes(*name_and_type_index)?;
+                self.method_descriptor(descriptor_index).map(|_| ())
+            }
+        }
+    }
+
+    fn get(&self, index: u16) -> LinkResult<&'c ConstantPoolEntry<'a>> {
+        self.class_file
+            .constants
+            .get(index)
+            .map_err(|_| format!("invalid constant pool index {index}"))
+    }
+
+    fn utf8(&self, index: u16) -> LinkResult<&'c str> {
+        match self.get(index)? {
+            ConstantPoolEntry::Utf8(string) => Ok(string),
+            _ => Err(format!("constant pool index {index} is not a utf8 string")),
+        }
+    }
+
+    /// Checks a class name in the internal form, i.e. `java/lang/String`, or an array
+    /// descriptor, i.e. `[Ljava/lang/String;`
+    fn class_name(&self, index: u16) -> LinkResult<&'c str> {
+        let name = self.utf8(index)?;
+        let is_valid = if name.starts_with('[') {
+            FieldType::parse(name).is_ok()
+        } else {
+            !name.is_empty() && !name.contains(['.', ';', '['])
+        };
+        if !is_valid {
+            return Err(format!("illegal class name \"{name}\""));
+        }
+        Ok(name)
+    }
+
+    fn class_reference(&self, index: u16) -> LinkResult<&'c str> {
+        match self.get(index)? {
+            ConstantPoolEntry::ClassReference(name_index) => self.class_name(*name_index),
+            _ => Err(format!("constant pool index {index} is not a class")),
+        }
+    }
+
+    fn name_and_type_indexes(&self, index: u16) -> LinkResult<(u16, u16)> {
+        match self.get(index)? {
+            ConstantPoolEntry::NameAndTypeDescriptor(name_index, descriptor_index) => {
+                Ok((*name_index, *descriptor_index))
+            }
+            _ => Err(format!(
+                "constant pool index {index} is not a name and type"
+            )),
+        }
+    }
+
+    fn name_and_type(&self, name_index: u16, descriptor_index: u16) -> LinkResult<&'c str> {
+        let name = self.utf8(name_index)?;
+        if name.is_empty() {
+            return Err("empty member name".to_string());
+        }
+        self.utf8(descriptor_index)?;
+        Ok(name)
+    }
+
+    fn method_descriptor(&self, index: u16) -> LinkResult<MethodDescriptor> {
+        let descriptor = self.utf8(index)?;
+        MethodDescriptor::parse(descriptor)
+            .map_err(|_| format!("illegal method descriptor \"{descriptor}\""))
+    }
+
+    fn check_field_reference(&self, class_index: u16, name_and_type_index: u16) -> LinkResult<()> {
+        self.class_reference(class_index)?;
+        let (name_index, descriptor_index) = self.name_and_type_indexes(name_and_type_index)?;
+        self.name_and_type(name_index, descriptor_index)?;
+        let descriptor = self.utf8(descriptor_index)?;
+        FieldType::parse(descriptor)
+            .map_err(|_| format!("illegal field descriptor \"{descriptor}\""))?;
+        Ok(())
+    }
+
+    fn check_method_reference(
+        &self,
+        class_index: u16,
+        name_and_type_index: u16,
+        method_use: MethodUse,
+    ) -> LinkResult<()> {
+        self.class_reference(class_index)?;
+        let (name_index, descriptor_index) = self.name_and_type_indexes(name_and_type_index)?;
+        let name = self.name_and_type(name_index, descriptor_index)?;
+        let descriptor = self.method_descriptor(descriptor_index)?;
+
+        // Only constructors can be referenced among the special methods
+        if name.starts_with('<') && name != "<init>" {
+            return Err(format!("illegal method name \"{name}\""));
+        }
+        if name == "<init>" && descriptor.return_type.is_some() {
+            return Err("constructors must return void".to_string());
+        }
+        match method_use {
+            MethodUse::Constructor if name != "<init>" => Err(format!(
+                "method handle must reference a constructor, not \"{name}\""
+            )),
+            MethodUse::NotConstructor if name == "<init>" => {
+                Err("method handle cannot reference a constructor".to_string())
+            }
+            _ => Ok(()),
+        }
+    }
+
+    fn check_method_handle(&self, kind: &MethodHandleKind, reference_index: u16) -> LinkResult<()> {
+        let (class_index, name_and_type_index) = match (kind, self.get(reference_index)?) {
+            (
+                MethodHandleKind::GetField
+                | MethodHandleKind::GetStatic
+                | MethodHandleKind::PutField
+                | MethodHandleKind::PutStatic,
+                ConstantPoolEntry::FieldReference(class_index, name_and_type_index),
+            ) => return self.check_field_reference(*class_index, *name_and_type_index),
+            (
+                MethodHandleKind::InvokeVirtual | MethodHandleKind::NewInvokeSpecial,
+                ConstantPoolEntry::MethodReference(class_index, name_and_type_index),
+            )
+            | (
+                MethodHandleKind::InvokeStatic | MethodHandleKind::InvokeSpecial,
+                ConstantPoolEntry::MethodReference(class_index, name_and_type_index)
+                | ConstantPoolEntry::InterfaceMethodReference(class_index, name_and_type_index),
+            )
+            | (
+                MethodHandleKind::InvokeInterface,
+                ConstantPoolEntry::InterfaceMethodReference(class_index, name_and_type_index),
+            ) => (*class_index, *name_and_type_index),
+            _ => {
+                return Err(format!(
+                    "constant pool index {reference_index} is not applicable to a method handle of kind {kind}"
+                ));
+            }
+        };
+        let method_use = if *kind == MethodHandleKind::NewInvokeSpecial {
+            MethodUse::Constructor
+        } else {
+            MethodUse::NotConstructor
+        };
+        self.check_method_reference(class_index, name_and_type_index, method_use)
+    }
+
+    fn check_bootstrap_method_handle(&self, index: u16) -> LinkResult<()> {
+        match self.get(index)? {
+            ConstantPoolEntry::MethodHandle(_, _) => Ok(()),
+            _ => Err(format!(
+                "constant pool index {index} of a bootstrap method is not a method handle"
+            )),
+        }
+    }
+
+    /// Checks that a bootstrap method's argument is a constant that `ldc` could load
+    fn check_loadable(&self, index: u16) -> LinkResult<()> {
+        match self.get(index)? {
+            ConstantPoolEntry::Integer(_)
+            | ConstantPoolEntry::Float(_)
+            | ConstantPoolEntry::Long(_)
+            | ConstantPoolEntry::Double(_)
+            | ConstantPoolEntry::ClassReference(_)
+            | ConstantPoolEntry::StringReference(_)
+            | ConstantPoolEntry::MethodHandle(_, _)
+            | ConstantPoolEntry::MethodType(_) => Ok(()),
+            _ => Err(format!(
+                "constant pool index {index} is not a valid bootstrap method argument"
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rjvm_reader::{
+        bootstrap_method::BootstrapMethod,
+        class_file::ClassFile,
+        constant_pool::{ConstantPool, ConstantPoolEntry},
+        method_handle_kind::MethodHandleKind,
+    };
+
+    use crate::{
+        linker::link_class,
+        vm_error::{ClassFormatError, VmError},
+    };
+
+    /// Creates a pool whose first entries are a valid reference to `Object.hashCode()`
+    fn constant_pool_with(entries: Vec<ConstantPoolEntry<'static>>) -> ConstantPool<'static> {
+        let mut constants = ConstantPool::new();
+        constants.add(ConstantPoolEntry::Utf8("java/lang/Object".into()));
+        constants.add(ConstantPoolEntry::ClassReference(1));
+        constants.add(ConstantPoolEntry::Utf8("hashCode".into()));
+        constants.add(ConstantPoolEntry::Utf8("()I".into()));
+        constants.add(ConstantPoolEntry::NameAndTypeDescriptor(3, 4));
+        constants.add(ConstantPoolEntry::MethodReference(2, 5));
+        for entry in entries {
+            constants.add(entry);
+        }
+        constants
+    }
+
+    fn link(constants: ConstantPool<'static>) -> Result<(), VmError> {
+        link_class(&ClassFile {
+            name: "rjvm/Linked".into(),
+            constants,
+            ..Default::default()
+        })
+    }
+
+    fn class_format_error(index: u16, reason: &str) -> Result<(), VmError> {
+        Err(VmError::ClassFormatError(Box::new(ClassFormatError {
+            class_name: "rjvm/Linked".to_string(),
+            index,
+            reason: reason.to_string(),
+        })))
+    }
+
+    #[test]
+    fn valid_constant_pool_links() {
+        let constants = constant_pool_with(vec![
+            ConstantPoolEntry::StringReference(3),
+            ConstantPoolEntry::MethodType(4),
+            ConstantPoolEntry::MethodHandle(MethodHandleKind::InvokeVirtual, 6),
+        ]);
+        assert_eq!(Ok(()), link(constants));
+    }
+
+    #[test]
+    fn references_to_entries_of_the_wrong_kind_are_rejected() {
+        let constants = constant_pool_with(vec![ConstantPoolEntry::FieldReference(1, 5)]);
+        assert_eq!(
+            class_format_error(7, "constant pool index 1 is not a class"),
+            link(constants)
+        );
+    }
+
+    #[test]
+    fn references_to_missing_entries_are_rejected() {
+        let constants = constant_pool_with(vec![ConstantPoolEntry::StringReference(42)]);
+        assert_eq!(
+            class_format_error(7, "invalid constant pool index 42"),
+            link(constants)
+        );
+    }
+
+    #[test]
+    fn malformed_descriptors_are_rejected() {
+        let constants = constant_pool_with(vec![
+            ConstantPoolEntry::Utf8("(I".into()),
+            ConstantPoolEntry::MethodType(7),
+        ]);
+        assert_eq!(
+            class_format_error(8, "illegal method descriptor \"(I\""),
+            link(constants)
+        );
+    }
+
+    #[test]
+    fn malformed_class_names_are_rejected() {
+        let constants = constant_pool_with(vec![
+            ConstantPoolEntry::Utf8("java.lang.String".into()),
+            ConstantPoolEntry::ClassReference(7),
+        ]);
+        assert_eq!(
+            class_format_error(8, "illegal class name \"java.lang.String\""),
+            link(constants)
+        );
+    }
+
+    #[test]
+    fn method_handles_must_match_their_kind() {
+        let constants = constant_pool_with(vec![ConstantPoolEntry::MethodHandle(
+            MethodHandleKind::GetField,
+            6,
+        )]);
+        assert_eq!(
+            class_format_error(
+                7,
+                "constant pool index 6 is not applicable to a method handle of kind GetField"
+            ),
+            link(constants)
+        );
+    }
+
+    #[test]
+    fn invoke_dynamic_requires_an_existing_bootstrap_method() {
+        let constants = constant_pool_with(vec![ConstantPoolEntry::InvokeDynamic(0, 5)]);
+        assert_eq!(
+            class_format_error(7, "invalid bootstrap method index 0"),
+            link(constants)
+        );
+    }
+
+    #[test]
+    fn bootstrap_methods_must_be_method_handles() {
+        let constants = constant_pool_with(vec![ConstantPoolEntry::InvokeDynamic(0, 5)]);
+        let result = link_class(&ClassFile {
+            name: "rjvm/Linked".into(),
+            constants,
+            bootstrap_methods: vec![BootstrapMethod {
+                method_ref: 6,
+                arguments: vec![],
+            }],
+            ..Default::default()
+        });
+        assert_eq!(
+            class_format_error(
+                6,
+                "constant pool index 6 of a bootstrap method is not a method handle"
+            ),
+            result
+        );
+    }
+}
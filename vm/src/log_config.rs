@@ -0,0 +1,185 @@
+/// The categories of the log messages emitted by the vm. Each one uses its own log target
+/// (e.g. `rjvm::gc`), so that it can also be filtered with the usual `RUST_LOG` syntax.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LogCategory {
+    /// Every executed instruction, with the status of the stack and of the locals
+    Instructions,
+    /// Resolution of methods and fields
+    Resolution,
+    /// Allocations and garbage collection
+    Gc,
+    /// Invocation of native methods
+    Natives,
+    /// Loading and initialization of classes
+    ClassLoad,
+}
+
+impl LogCategory {
+    pub const ALL: [LogCategory; 5] = [
+        LogCategory::Instructions,
+        LogCategory::Resolution,
+        LogCategory::Gc,
+        LogCategory::Natives,
+        LogCategory::ClassLoad,
+    ];
+
+    /// The target used for the log messages of this category
+    pub fn target(&self) -> &'static str {
+        match self {
+            LogCategory::Instructions => "rjvm::instructions",
+            LogCategory::Resolution => "rjvm::resolution",
+            LogCategory::Gc => "rjvm::gc",
+            LogCategory::Natives => "rjvm::natives",
+            LogCategory::ClassLoad => "rjvm::classload",
+        }
+    }
+
+    fn mask(&self) -> u8 {
+        1 << (*self as u8)
+    }
+}
+
+/// Configures which categories of log messages the vm emits. Note that messages are
+/// still filtered by the level configured in the logger, i.e. enabling a category does not
+/// enable debug messages if the logger is set to `info`.
+///
+/// Since instructions are the vast majority of the messages, they can be throttled further:
+/// by logging only one instruction out of every `n`, or only those executed by frames
+/// up to a given call depth.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LogConfig {
+    enabled_categories: u8,
+    instructions_sampling: u32,
+    instructions_max_call_depth: Option<usize>,
+}
+
+impl Default for LogConfig {
+    fn default() -> Self {
+        Self {
+            enabled_categories: LogCategory::ALL
+                .iter()
+                .fold(0, |mask, category| mask | category.mask()),
+            instructions_sampling: 1,
+            instructions_max_call_depth: None,
+        }
+    }
+}
+
+impl LogConfig {
+    /// A configuration with all categories disabled
+    pub fn none() -> Self {
+        Self {
+            enabled_categories: 0,
+            ..Default::default()
+        }
+    }
+
+    pub fn with_category(mut self, category: LogCategory, enabled: bool) -> Self {
+        if enabled {
+            self.enabled_categories |= category.mask();
+        } else {
+            self.enabled_categories &= !category.mask();
+        }
+        self
+    }
+
+    /// Logs only one instruction out of every `every_n`
+    pub fn with_instructions_sampling(mut self, every_n: u32) -> Self {
+        self.instructions_sampling = every_n.max(1);
+        self
+    }
+
+    /// Logs only the instructions executed by frames whose depth in the call stack
+    /// is at most `max_depth`, where 1 is the first invoked method
+    pub fn with_instructions_max_call_depth(mut self, max_depth: Option<usize>) -> Self {
+        self.instructions_max_call_depth = max_depth;
+        self
+    }
+
+    pub fn is_enabled(&self, category: LogCategory) -> bool {
+        self.enabled_categories & category.mask() != 0
+    }
+}
+
+/// Keeps the state needed to throttle the instructions' log messages
+#[derive(Debug, Default)]
+pub(crate) struct InstructionsLogThrottler {
+    executed_instructions: u64,
+}
+
+impl InstructionsLogThrottler {
+    /// Returns whether the instruction about to be executed at the given depth should be logged
+    pub fn should_log(&mut self, config: &LogConfig, call_depth: usize) -> bool {
+        if !config.is_enabled(LogCategory::Instructions) {
+            return false;
+        }
+        if config
+            .instructions_max_call_depth
+            .is_some_and(|max_depth| call_depth > max_depth)
+        {
+            return false;
+        }
+        let counter = self.executed_instructions;
+        self.executed_instructions += 1;
+        counter.is_multiple_of(u64::from(config.instructions_sampling))
+    }
+}
+
+/// Logs a message in the given category, if it is enabled in the given [LogConfig]
+macro_rules! vm_log {
+    ($config:expr, $category:expr, $level:expr, $($arg:tt)+) => {
+        if $config.is_enabled($category) {
+            log::log!(target: $category.target(), $level, $($arg)+);
+        }
+    };
+}
+
+pub(crate) use vm_log;
+
+#[cfg(test)]
+mod tests {
+    use crate::log_config::{InstructionsLogThrottler, LogCategory, LogConfig};
+
+    #[test]
+    fn all_categories_are_enabled_by_default() {
+        let config = LogConfig::default();
+        for category in LogCategory::ALL {
+            assert!(config.is_enabled(category));
+        }
+    }
+
+    #[test]
+    fn can_toggle_categories() {
+        let config = LogConfig::none()
+            .with_category(LogCategory::Gc, true)
+            .with_category(LogCategory::Natives, true)
+            .with_category(LogCategory::Natives, false);
+        assert!(config.is_enabled(LogCategory::Gc));
+        assert!(!config.is_enabled(LogCategory::Natives));
+        assert!(!config.is_enabled(LogCategory::Instructions));
+    }
+
+    #[test]
+    fn instructions_can_be_sampled() {
+        let config = LogConfig::default().with_instructions_sampling(3);
+        let mut throttler = InstructionsLogThrottler::default();
+        let logged: Vec<bool> = (0..7).map(|_| throttler.should_log(&config, 1)).collect();
+        assert_eq!(vec![true, false, false, true, false, false, true], logged);
+    }
+
+    #[test]
+    fn instructions_can_be_limited_by_call_depth() {
+        let config = LogConfig::default().with_instructions_max_call_depth(Some(2));
+        let mut throttler = InstructionsLogThrottler::default();
+        assert!(throttler.should_log(&config, 1));
+        assert!(throttler.should_log(&config, 2));
+        assert!(!throttler.should_log(&config, 3));
+    }
+
+    #[test]
+    fn disabled_instructions_are_never_logged() {
+        let config = LogConfig::default().with_category(LogCategory::Instructions, false);
+        let mut throttler = InstructionsLogThrottler::default();
+        assert!(!throttler.should_log(&config, 1));
+    }
+}
@@ -0,0 +1,103 @@
+// Implementation of the native methods of `java.lang.StrictMath`, also used for `java.lang.Math`.
+//
+// Most of them map directly to the functions of Rust's `f64`, which follow the C standard
+// for the special cases, just like java does. The functions here are the ones whose special
+// cases differ from C, or that have no equivalent in Rust.
+// Known deviations: `StrictMath` requires the exact results of the fdlibm algorithms, whereas
+// Rust delegates to the platform's libm, which can differ in the last bit for some inputs.
+
+/// `Math.pow`: unlike C's `pow`, the result is NaN whenever the exponent is NaN, and also
+/// when raising 1 or -1 to an infinite power
+pub fn pow(base: f64, exponent: f64) -> f64 {
+    if exponent.is_nan() || (base.abs() == 1.0 && exponent.is_infinite()) {
+        f64::NAN
+    } else {
+        base.powf(exponent)
+    }
+}
+
+/// `Math.IEEEremainder`: the remainder of the division rounded to the nearest integer,
+/// choosing the even one on ties, i.e. `dividend - divisor * n`. This is the fdlibm algorithm,
+/// which computes it exactly.
+pub fn ieee_remainder(dividend: f64, divisor: f64) -> f64 {
+    if dividend.is_nan() || divisor.is_nan() || dividend.is_infinite() || divisor == 0.0 {
+        return f64::NAN;
+    }
+    if divisor.is_infinite() {
+        return dividend;
+    }
+
+    let divisor = divisor.abs();
+    // Here `%` is exact, and brings the dividend in (-2 * divisor, 2 * divisor)
+    let mut remainder = if divisor <= f64::MAX / 2.0 {
+        dividend % (divisor + divisor)
+    } else {
+        dividend
+    };
+    if remainder.abs() == divisor {
+        return 0.0 * dividend;
+    }
+
+    remainder = remainder.abs();
+    if divisor < 2.0 * f64::MIN_POSITIVE {
+        // Halving the divisor could lose precision
+        if remainder + remainder > divisor {
+            remainder -= divisor;
+            if remainder + remainder >= divisor {
+                remainder -= divisor;
+            }
+        }
+    } else {
+        let half_divisor = 0.5 * divisor;
+        if remainder > half_divisor {
+            remainder -= divisor;
+            if remainder >= half_divisor {
+                remainder -= divisor;
+            }
+        }
+    }
+    if dividend.is_sign_negative() {
+        -remainder
+    } else {
+        remainder
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::math::{ieee_remainder, pow};
+
+    #[test]
+    fn pow_follows_java_special_cases() {
+        assert_eq!(8.0, pow(2.0, 3.0));
+        assert_eq!(1.0, pow(f64::NAN, 0.0));
+        assert!(pow(1.0, f64::NAN).is_nan());
+        assert!(pow(1.0, f64::INFINITY).is_nan());
+        assert!(pow(-1.0, f64::NEG_INFINITY).is_nan());
+        assert_eq!(0.0, pow(0.5, f64::INFINITY));
+        assert!(pow(-8.0, 1.0 / 3.0).is_nan());
+    }
+
+    #[test]
+    fn ieee_remainder_rounds_to_even() {
+        assert_eq!(1.0, ieee_remainder(10.0, 3.0));
+        assert_eq!(-1.0, ieee_remainder(11.0, 3.0));
+        assert_eq!(-1.0, ieee_remainder(-10.0, 3.0));
+        // 5 / 2 = 2.5 rounds to 2, 7 / 2 = 3.5 rounds to 4
+        assert_eq!(1.0, ieee_remainder(5.0, 2.0));
+        assert_eq!(-1.0, ieee_remainder(7.0, 2.0));
+        assert_eq!(1.0, ieee_remainder(5.0, -2.0));
+        assert_eq!(-0.5, ieee_remainder(1.5, 1.0));
+        assert_eq!(2.5, ieee_remainder(2.5, f64::INFINITY));
+        assert!(ieee_remainder(3.0, 3.0).is_sign_positive());
+        assert!(ieee_remainder(-3.0, 3.0).is_sign_negative());
+    }
+
+    #[test]
+    fn ieee_remainder_special_cases() {
+        assert!(ieee_remainder(f64::NAN, 1.0).is_nan());
+        assert!(ieee_remainder(1.0, f64::NAN).is_nan());
+        assert!(ieee_remainder(f64::INFINITY, 1.0).is_nan());
+        assert!(ieee_remainder(1.0, 0.0).is_nan());
+    }
+}
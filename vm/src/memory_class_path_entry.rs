@@ -0,0 +1,119 @@
+use std::{borrow::Cow, collections::BTreeMap};
+
+use crate::class_path_entry::{ClassLoadingError, ClassPathEntry};
+
+/// Implementation of [ClassPathEntry] that keeps its `.class` files in memory, for instance
+/// the ones embedded in the binary via `include_bytes!`, so that no file system is needed.
+/// Borrowed `'static` slices are not copied.
+#[derive(Debug, Default, Clone)]
+pub struct MemoryClassPathEntry {
+    name: String,
+    files: BTreeMap<String, Cow<'static, [u8]>>,
+}
+
+impl MemoryClassPathEntry {
+    /// Creates an empty entry; the name is only used in diagnostics
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            files: BTreeMap::new(),
+        }
+    }
+
+    /// Adds the bytes of the `.class` file of the given class, e.g. `rjvm/SimpleMain`,
+    /// replacing the previous ones, if any
+    pub fn add_class(&mut self, class_name: &str, bytes: impl Into<Cow<'static, [u8]>>) {
+        self.files
+            .insert(format!("{class_name}.class"), bytes.into());
+    }
+
+    /// Adds an arbitrary resource, e.g. `rjvm/data.properties`
+    pub fn add_resource(&mut self, path: &str, bytes: impl Into<Cow<'static, [u8]>>) {
+        self.files.insert(path.to_string(), bytes.into());
+    }
+
+    pub fn with_class(mut self, class_name: &str, bytes: impl Into<Cow<'static, [u8]>>) -> Self {
+        self.add_class(class_name, bytes);
+        self
+    }
+
+    pub fn with_resource(mut self, path: &str, bytes: impl Into<Cow<'static, [u8]>>) -> Self {
+        self.add_resource(path, bytes);
+        self
+    }
+}
+
+/// Builds an entry from `(class name, bytes)` pairs
+impl<'n> FromIterator<(&'n str, &'static [u8])> for MemoryClassPathEntry {
+    fn from_iter<T: IntoIterator<Item = (&'n str, &'static [u8])>>(iter: T) -> Self {
+        let mut entry = Self::new("memory");
+        for (class_name, bytes) in iter {
+            entry.add_class(class_name, bytes);
+        }
+        entry
+    }
+}
+
+impl ClassPathEntry for MemoryClassPathEntry {
+    fn resolve_resource(&self, path: &str) -> Result<Option<Vec<u8>>, ClassLoadingError> {
+        Ok(self.files.get(path).map(|bytes| bytes.to_vec()))
+    }
+
+    fn description(&self) -> String {
+        self.name.clone()
+    }
+
+    fn class_names(&self) -> Result<Vec<String>, ClassLoadingError> {
+        Ok(self
+            .files
+            .keys()
+            .filter_map(|path| path.strip_suffix(".class"))
+            .map(str::to_string)
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        class_path_entry::{
+            tests::{assert_can_find_class, assert_cannot_find_class, assert_cannot_find_resource},
+            ClassPathEntry,
+        },
+        memory_class_path_entry::MemoryClassPathEntry,
+    };
+
+    const SIMPLE_MAIN: &[u8] = include_bytes!("../tests/resources/rjvm/SimpleMain.class");
+
+    #[test]
+    fn memory_class_path_entry_works() {
+        let entry: MemoryClassPathEntry = [("rjvm/SimpleMain", SIMPLE_MAIN)].into_iter().collect();
+
+        assert_can_find_class(&entry, "rjvm/SimpleMain");
+        assert_cannot_find_class(&entry, "rjvm/Foo");
+        assert_eq!(
+            vec!["rjvm/SimpleMain".to_string()],
+            entry.class_names().expect("should be able to list classes")
+        );
+    }
+
+    #[test]
+    fn memory_class_path_entry_can_hold_resources() {
+        let entry = MemoryClassPathEntry::new("embedded")
+            .with_class("rjvm/SimpleMain", SIMPLE_MAIN)
+            .with_resource("rjvm/data.txt", b"data".to_vec());
+
+        assert_eq!(
+            Some(b"data".to_vec()),
+            entry
+                .resolve_resource("rjvm/data.txt")
+                .expect("should not have had any errors")
+        );
+        assert_cannot_find_resource(&entry, "rjvm/other.txt");
+        assert_eq!(
+            vec!["rjvm/SimpleMain".to_string()],
+            entry.class_names().expect("should be able to list classes")
+        );
+        assert_eq!("embedded", entry.description());
+    }
+}
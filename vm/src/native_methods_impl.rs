@@ -1,23 +1,47 @@
-use log::{debug, info};
+use std::time::Duration;
 
-use rjvm_reader::type_conversion::ToUsizeSafe;
+use log::Level;
+
+use rjvm_reader::{
+    field_type::FieldType,
+    type_conversion::{CheckedNarrowing, ToUsizeSafe},
+};
 
 use crate::{
     abstract_object::{AbstractObject, ObjectKind},
     array::Array,
+    array_entry_type::ArrayEntryType,
+    boxed_caches::register_boxed_caches,
     call_frame::MethodCallResult,
     call_stack::CallStack,
-    exceptions::MethodCallFailed,
+    character,
+    character::{MAX_RADIX, MIN_RADIX},
+    class::DefiningLoader,
+    collection_intrinsics::register_collection_intrinsics,
+    environment::register_environment_methods,
+    exceptions::{JavaException, MethodCallFailed},
+    file_io::register_file_io_methods,
+    file_system::register_file_system_methods,
+    floating_decimal::{double_to_string, float_to_string},
     java_objects_creation::{
-        extract_str_from_java_lang_string, new_java_lang_class_object,
-        new_java_lang_stack_trace_element_object,
+        extract_str_from_java_lang_string, get_java_lang_class_object,
+        new_java_lang_stack_trace_element_object, new_java_lang_string_object,
+        new_java_lang_throwable_object_without_message, throw_exception,
     },
-    native_methods_registry::NativeMethodsRegistry,
+    log_config::{vm_log, LogCategory},
+    math,
+    native_methods_registry::{NativeCallback, NativeMethodsRegistry},
     object::Object,
+    resources::register_resource_methods,
+    shutdown_hooks::register_shutdown_hook_methods,
+    sockets::register_socket_methods,
+    standard_streams::register_standard_streams_methods,
+    system_properties::register_system_properties_methods,
     time::{get_current_time_millis, get_nano_time},
+    user_class_loaders::{self, register_class_loader_methods},
     value::{
-        expect_abstract_object_at, expect_array_at, expect_concrete_object_at, expect_double_at,
-        expect_float_at, expect_int_at, Value,
+        expect_abstract_object_at, expect_concrete_object_at, expect_double_at, expect_float_at,
+        expect_int_at, expect_long_at, Value,
     },
     vm::Vm,
     vm_error::VmError,
@@ -28,10 +52,29 @@ pub(crate) fn register_natives(registry: &mut NativeMethodsRegistry) {
     registry.register_temp_print(|vm, _, _, args| temp_print(vm, args));
     register_noops(registry);
     register_time_methods(registry);
+    register_shutdown_methods(registry);
     register_gc_methods(registry);
     register_native_repr_methods(registry);
     register_reflection_methods(registry);
     register_throwable_methods(registry);
+    register_thread_methods(registry);
+    register_object_monitor_methods(registry);
+    register_number_conversion_methods(registry);
+    register_string_methods(registry);
+    register_character_methods(registry);
+    register_math_methods(registry);
+    register_access_controller_methods(registry);
+    register_system_properties_methods(registry);
+    register_environment_methods(registry);
+    register_standard_streams_methods(registry);
+    register_file_io_methods(registry);
+    register_file_system_methods(registry);
+    register_socket_methods(registry);
+    register_resource_methods(registry);
+    register_class_loader_methods(registry);
+    register_shutdown_hook_methods(registry);
+    register_boxed_caches(registry);
+    register_collection_intrinsics(registry);
 }
 
 /// These various methods are noop, i.e. they do not do anything
@@ -57,6 +100,23 @@ fn register_noops(registry: &mut NativeMethodsRegistry) {
         "()V",
         |_, _, _, _| Ok(None),
     );
+    registry.register(
+        "java/lang/Thread",
+        "registerNatives",
+        "()V",
+        |_, _, _, _| Ok(None),
+    );
+    registry.register("java/lang/Thread", "setPriority0", "(I)V", |_, _, _, _| {
+        Ok(None)
+    });
+    registry.register("sun/misc/VM", "initialize", "()V", |_, _, _, _| Ok(None));
+    // All our natives are built into the vm, so there is no library to load
+    registry.register_intrinsic(
+        "java/lang/System",
+        "loadLibrary",
+        "(Ljava/lang/String;)V",
+        |_, _, _, _| Ok(None),
+    );
 }
 
 /// Methods to access the system clock
@@ -78,12 +138,73 @@ fn register_gc_methods(registry: &mut NativeMethodsRegistry) {
         "java/lang/System",
         "identityHashCode",
         "(Ljava/lang/Object;)I",
-        |_, _, _, args| identity_hash_code(args),
+        |vm, _, _, args| identity_hash_code(vm, args.first()),
+    );
+    registry.register(
+        "java/lang/Object",
+        "hashCode",
+        "()I",
+        |vm, _, receiver, _| {
+            let receiver = expect_some_receiver(receiver)?;
+            Ok(Some(Value::Int(vm.identity_hash_code(&receiver))))
+        },
+    );
+    registry.register(
+        "java/lang/Object",
+        "clone",
+        "()Ljava/lang/Object;",
+        |vm, stack, receiver, _| object_clone(vm, stack, expect_some_receiver(receiver)?),
     );
     registry.register("java/lang/System", "gc", "()V", |vm, _, _, _| {
         vm.run_garbage_collection()?;
         Ok(None)
     });
+    // What System.gc invokes in our rt.jar
+    registry.register("java/lang/Runtime", "gc", "()V", |vm, _, _, _| {
+        vm.run_garbage_collection()?;
+        Ok(None)
+    });
+    registry.register(
+        "java/lang/Runtime",
+        "runFinalization0",
+        "()V",
+        |vm, _, _, _| {
+            vm.run_finalization()?;
+            Ok(None)
+        },
+    );
+    registry.register("java/lang/Runtime", "freeMemory", "()J", |vm, _, _, _| {
+        Ok(Some(Value::Long(memory_size(vm.free_heap_size()))))
+    });
+    registry.register("java/lang/Runtime", "totalMemory", "()J", |vm, _, _, _| {
+        Ok(Some(Value::Long(memory_size(vm.heap_size()))))
+    });
+    registry.register("java/lang/Runtime", "maxMemory", "()J", |vm, _, _, _| {
+        Ok(Some(Value::Long(memory_size(vm.max_heap_size()))))
+    });
+}
+
+fn memory_size(bytes: usize) -> i64 {
+    i64::try_from(bytes).unwrap_or(i64::MAX)
+}
+
+/// Native methods of java.lang.Shutdown, which `System.exit` invokes once the shutdown hooks
+/// have run, and `Runtime.halt` directly
+fn register_shutdown_methods(registry: &mut NativeMethodsRegistry) {
+    registry.register("java/lang/Shutdown", "halt0", "(I)V", |vm, _, _, args| {
+        let status = expect_int_at(&args, 0)?;
+        Err(vm.halt(status))
+    });
+    // Only invoked if the deprecated Runtime.runFinalizersOnExit has been enabled
+    registry.register(
+        "java/lang/Shutdown",
+        "runAllFinalizers",
+        "()V",
+        |vm, _, _, _| {
+            vm.run_finalization()?;
+            Ok(None)
+        },
+    );
 }
 
 /// Native methods that deal with the internal representation of data
@@ -92,7 +213,7 @@ fn register_native_repr_methods(registry: &mut NativeMethodsRegistry) {
         "java/lang/System",
         "arraycopy",
         "(Ljava/lang/Object;ILjava/lang/Object;II)V",
-        |_, _, _, args| native_array_copy(args),
+        |vm, stack, _, args| native_array_copy(vm, stack, args),
     );
     registry.register(
         "java/lang/Float",
@@ -106,16 +227,28 @@ fn register_native_repr_methods(registry: &mut NativeMethodsRegistry) {
         "(D)J",
         |_, _, _, args| double_to_raw_long_bits(&args),
     );
+    registry.register(
+        "java/lang/Float",
+        "intBitsToFloat",
+        "(I)F",
+        |_, _, _, args| {
+            let bits = expect_int_at(&args, 0)?;
+            Ok(Some(Value::Float(f32::from_bits(bits.cast_unsigned()))))
+        },
+    );
+    registry.register(
+        "java/lang/Double",
+        "longBitsToDouble",
+        "(J)D",
+        |_, _, _, args| {
+            let bits = expect_long_at(&args, 0)?;
+            Ok(Some(Value::Double(f64::from_bits(bits.cast_unsigned()))))
+        },
+    );
 }
 
 /// Methods related to reflection
 fn register_reflection_methods(registry: &mut NativeMethodsRegistry) {
-    registry.register(
-        "java/lang/Class",
-        "getClassLoader0",
-        "()Ljava/lang/ClassLoader;",
-        |_, _, receiver, _| get_class_loader(receiver),
-    );
     registry.register(
         "java/lang/Class",
         "desiredAssertionStatus0",
@@ -128,6 +261,24 @@ fn register_reflection_methods(registry: &mut NativeMethodsRegistry) {
         "(Ljava/lang/String;)Ljava/lang/Class;",
         |vm, stack, _, args| get_primitive_class(vm, stack, &args),
     );
+    registry.register(
+        "java/lang/Object",
+        "getClass",
+        "()Ljava/lang/Class;",
+        |vm, stack, receiver, _| get_class(vm, stack, expect_some_receiver(receiver)?),
+    );
+    registry.register(
+        "java/lang/Class",
+        "forName0",
+        "(Ljava/lang/String;ZLjava/lang/ClassLoader;Ljava/lang/Class;)Ljava/lang/Class;",
+        |vm, stack, _, args| class_for_name(vm, stack, &args),
+    );
+    registry.register(
+        "sun/reflect/Reflection",
+        "getCallerClass",
+        "()Ljava/lang/Class;",
+        |vm, stack, _, _| get_caller_class(vm, stack),
+    );
 }
 
 /// Methods of java.lang.Throwable
@@ -152,9 +303,396 @@ fn register_throwable_methods(registry: &mut NativeMethodsRegistry) {
     );
 }
 
+/// Methods of java.lang.Thread
+fn register_thread_methods(registry: &mut NativeMethodsRegistry) {
+    registry.register(
+        "java/lang/Thread",
+        "holdsLock",
+        "(Ljava/lang/Object;)Z",
+        |vm, _, _, args| holds_lock(vm, args),
+    );
+    registry.register(
+        "java/lang/Thread",
+        "currentThread",
+        "()Ljava/lang/Thread;",
+        |vm, call_stack, _, _| Ok(Some(Value::Object(vm.current_thread_object(call_stack)?))),
+    );
+    registry.register("java/lang/Thread", "start0", "()V", |vm, _, receiver, _| {
+        vm.start_thread(expect_some_receiver(receiver)?)?;
+        Ok(None)
+    });
+    registry.register(
+        "java/lang/Thread",
+        "isAlive",
+        "()Z",
+        |vm, _, receiver, _| {
+            let receiver = expect_some_receiver(receiver)?;
+            Ok(Some(Value::Int(vm.is_thread_alive(&receiver).into())))
+        },
+    );
+    registry.register(
+        "java/lang/Thread",
+        "yield",
+        "()V",
+        |vm, call_stack, _, _| vm.block_current_thread(call_stack, Box::new(|_, _| Ok(None))),
+    );
+    registry.register(
+        "java/lang/Thread",
+        "sleep",
+        "(J)V",
+        |vm, call_stack, _, args| thread_sleep(vm, call_stack, args),
+    );
+    registry.register(
+        "java/lang/Thread",
+        "interrupt0",
+        "()V",
+        |vm, _, receiver, _| {
+            vm.interrupt_thread(&expect_some_receiver(receiver)?);
+            Ok(None)
+        },
+    );
+    registry.register(
+        "java/lang/Thread",
+        "isInterrupted",
+        "(Z)Z",
+        |vm, _, receiver, args| {
+            let receiver = expect_some_receiver(receiver)?;
+            let clear = expect_int_at(&args, 0)? != 0;
+            Ok(Some(Value::Int(
+                vm.is_thread_interrupted(&receiver, clear).into(),
+            )))
+        },
+    );
+    registry.register(
+        "java/security/AccessController",
+        "getStackAccessControlContext",
+        "()Ljava/security/AccessControlContext;",
+        |_, _, _, _| Ok(Some(Value::Null)),
+    );
+}
+
+/// Methods of java.lang.Object that use the monitors
+fn register_object_monitor_methods(registry: &mut NativeMethodsRegistry) {
+    registry.register("java/lang/Object", "wait", "(J)V", object_wait);
+    registry.register("java/lang/Object", "notify", "()V", |vm, _, receiver, _| {
+        object_notify(vm, receiver, false)
+    });
+    registry.register(
+        "java/lang/Object",
+        "notifyAll",
+        "()V",
+        |vm, _, receiver, _| object_notify(vm, receiver, true),
+    );
+}
+
+/// Methods of java.lang.Integer and java.lang.Long that convert numbers from and to strings.
+/// Our rt.jar implements them in bytecode, but interpreting them is slow and some minimal
+/// class libraries declare them as native.
+/// The conversions of floats and doubles to strings are registered as intrinsics too, since
+/// our rt.jar implements them with `sun.misc.FloatingDecimal`, whose initialization pulls in
+/// most of the class library.
+fn register_number_conversion_methods(registry: &mut NativeMethodsRegistry) {
+    registry.register(
+        "java/lang/Integer",
+        "toString",
+        "(I)Ljava/lang/String;",
+        |vm, stack, _, args| {
+            let value = expect_int_at(&args, 0)?;
+            number_to_string(vm, stack, value.into(), 10)
+        },
+    );
+    registry.register(
+        "java/lang/Integer",
+        "toString",
+        "(II)Ljava/lang/String;",
+        |vm, stack, _, args| {
+            let value = expect_int_at(&args, 0)?;
+            let radix = expect_int_at(&args, 1)?;
+            number_to_string(vm, stack, value.into(), radix)
+        },
+    );
+    registry.register(
+        "java/lang/Integer",
+        "parseInt",
+        "(Ljava/lang/String;)I",
+        |vm, stack, _, args| parse_int(vm, stack, &args, 10),
+    );
+    registry.register(
+        "java/lang/Integer",
+        "parseInt",
+        "(Ljava/lang/String;I)I",
+        |vm, stack, _, args| {
+            let radix = expect_int_at(&args, 1)?;
+            parse_int(vm, stack, &args, radix)
+        },
+    );
+
+    // Remember that longs take two slots in the arguments
+    registry.register(
+        "java/lang/Long",
+        "toString",
+        "(J)Ljava/lang/String;",
+        |vm, stack, _, args| {
+            let value = expect_long_at(&args, 0)?;
+            number_to_string(vm, stack, value, 10)
+        },
+    );
+    registry.register(
+        "java/lang/Long",
+        "toString",
+        "(JI)Ljava/lang/String;",
+        |vm, stack, _, args| {
+            let value = expect_long_at(&args, 0)?;
+            let radix = expect_int_at(&args, 2)?;
+            number_to_string(vm, stack, value, radix)
+        },
+    );
+    registry.register(
+        "java/lang/Long",
+        "parseLong",
+        "(Ljava/lang/String;)J",
+        |vm, stack, _, args| parse_long(vm, stack, &args, 10),
+    );
+    registry.register(
+        "java/lang/Long",
+        "parseLong",
+        "(Ljava/lang/String;I)J",
+        |vm, stack, _, args| {
+            let radix = expect_int_at(&args, 1)?;
+            parse_long(vm, stack, &args, radix)
+        },
+    );
+
+    let to_string: [(&str, &str, NativeCallback); 2] = [
+        (
+            "java/lang/Double",
+            "(D)Ljava/lang/String;",
+            |vm, stack, _, args| {
+                let value = expect_double_at(&args, 0)?;
+                string_result(vm, stack, &double_to_string(value))
+            },
+        ),
+        (
+            "java/lang/Float",
+            "(F)Ljava/lang/String;",
+            |vm, stack, _, args| {
+                let value = expect_float_at(&args, 0)?;
+                string_result(vm, stack, &float_to_string(value))
+            },
+        ),
+    ];
+    for (class_name, descriptor, callback) in to_string {
+        registry.register(class_name, "toString", descriptor, callback);
+        registry.register_intrinsic(class_name, "toString", descriptor, callback);
+    }
+    registry.register_intrinsic(
+        "java/lang/AbstractStringBuilder",
+        "append",
+        "(D)Ljava/lang/AbstractStringBuilder;",
+        |vm, stack, receiver, args| {
+            let value = expect_double_at(&args, 0)?;
+            append_to_string_builder(vm, stack, receiver, &double_to_string(value))
+        },
+    );
+    registry.register_intrinsic(
+        "java/lang/AbstractStringBuilder",
+        "append",
+        "(F)Ljava/lang/AbstractStringBuilder;",
+        |vm, stack, receiver, args| {
+            let value = expect_float_at(&args, 0)?;
+            append_to_string_builder(vm, stack, receiver, &float_to_string(value))
+        },
+    );
+}
+
+/// Methods of java.lang.String
+fn register_string_methods(registry: &mut NativeMethodsRegistry) {
+    registry.register(
+        "java/lang/String",
+        "intern",
+        "()Ljava/lang/String;",
+        |vm, _, receiver, _| string_intern(vm, expect_some_receiver(receiver)?),
+    );
+    // The initializer only prepares the serialization, which needs reflection
+    registry.register_intrinsic("java/lang/StringBuffer", "<clinit>", "()V", |_, _, _, _| {
+        Ok(None)
+    });
+}
+
+/// Static methods of java.lang.Character. They are used a lot by parsers and formatters,
+/// and the Unicode tables are quite expensive to load and use in interpreted code.
+/// Note that `char` arguments are passed as `Value::Int`.
+fn register_character_methods(registry: &mut NativeMethodsRegistry) {
+    for descriptor in ["(C)Z", "(I)Z"] {
+        registry.register(
+            "java/lang/Character",
+            "isDigit",
+            descriptor,
+            |_, _, _, args| character_predicate(&args, character::is_digit),
+        );
+        registry.register(
+            "java/lang/Character",
+            "isLetter",
+            descriptor,
+            |_, _, _, args| character_predicate(&args, character::is_letter),
+        );
+        registry.register(
+            "java/lang/Character",
+            "isLetterOrDigit",
+            descriptor,
+            |_, _, _, args| character_predicate(&args, character::is_letter_or_digit),
+        );
+        registry.register(
+            "java/lang/Character",
+            "isWhitespace",
+            descriptor,
+            |_, _, _, args| character_predicate(&args, character::is_whitespace),
+        );
+        registry.register(
+            "java/lang/Character",
+            "isUpperCase",
+            descriptor,
+            |_, _, _, args| character_predicate(&args, character::is_upper_case),
+        );
+        registry.register(
+            "java/lang/Character",
+            "isLowerCase",
+            descriptor,
+            |_, _, _, args| character_predicate(&args, character::is_lower_case),
+        );
+    }
+    for descriptor in ["(C)C", "(I)I"] {
+        registry.register(
+            "java/lang/Character",
+            "toUpperCase",
+            descriptor,
+            |_, _, _, args| {
+                let code_point = expect_int_at(&args, 0)?;
+                Ok(Some(Value::Int(character::to_upper_case(code_point))))
+            },
+        );
+        registry.register(
+            "java/lang/Character",
+            "toLowerCase",
+            descriptor,
+            |_, _, _, args| {
+                let code_point = expect_int_at(&args, 0)?;
+                Ok(Some(Value::Int(character::to_lower_case(code_point))))
+            },
+        );
+    }
+    for descriptor in ["(CI)I", "(II)I"] {
+        registry.register(
+            "java/lang/Character",
+            "digit",
+            descriptor,
+            |_, _, _, args| {
+                let code_point = expect_int_at(&args, 0)?;
+                let radix = expect_int_at(&args, 1)?;
+                Ok(Some(Value::Int(character::digit(code_point, radix))))
+            },
+        );
+    }
+    registry.register(
+        "java/lang/Character",
+        "forDigit",
+        "(II)C",
+        |_, _, _, args| {
+            let digit = expect_int_at(&args, 0)?;
+            let radix = expect_int_at(&args, 1)?;
+            Ok(Some(Value::Int(character::for_digit(digit, radix) as i32)))
+        },
+    );
+}
+
+/// Since there is no security manager, privileged actions just run
+fn register_access_controller_methods(registry: &mut NativeMethodsRegistry) {
+    registry.register(
+        "java/security/AccessController",
+        "doPrivileged",
+        "(Ljava/security/PrivilegedAction;)Ljava/lang/Object;",
+        |vm, stack, _, args| run_privileged_action(vm, stack, &args),
+    );
+    registry.register(
+        "java/security/AccessController",
+        "doPrivileged",
+        "(Ljava/security/PrivilegedAction;Ljava/security/AccessControlContext;)Ljava/lang/Object;",
+        |vm, stack, _, args| run_privileged_action(vm, stack, &args),
+    );
+    registry.register(
+        "java/security/AccessController",
+        "doPrivileged",
+        "(Ljava/security/PrivilegedExceptionAction;)Ljava/lang/Object;",
+        |vm, stack, _, args| run_privileged_exception_action(vm, stack, &args),
+    );
+    registry.register(
+        "java/security/AccessController",
+        "doPrivileged",
+        "(Ljava/security/PrivilegedExceptionAction;Ljava/security/AccessControlContext;)\
+         Ljava/lang/Object;",
+        |vm, stack, _, args| run_privileged_exception_action(vm, stack, &args),
+    );
+}
+
+fn run_privileged_action<'a>(
+    vm: &mut Vm<'a>,
+    call_stack: &mut CallStack<'a>,
+    args: &[Value<'a>],
+) -> MethodCallResult<'a> {
+    let action = expect_abstract_object_at(args, 0)?;
+    vm.invoke_virtual(
+        call_stack,
+        action,
+        "run",
+        "()Ljava/lang/Object;",
+        Vec::new(),
+    )
+}
+
+/// Runs a `PrivilegedExceptionAction`, wrapping the checked exceptions that it throws in a
+/// `PrivilegedActionException`
+fn run_privileged_exception_action<'a>(
+    vm: &mut Vm<'a>,
+    call_stack: &mut CallStack<'a>,
+    args: &[Value<'a>],
+) -> MethodCallResult<'a> {
+    let result = run_privileged_action(vm, call_stack, args);
+    let Err(MethodCallFailed::ExceptionThrown(exception)) = result else {
+        return result;
+    };
+    let class = vm.get_class_by_id(exception.0.class_id())?;
+    let is_unchecked = ["java/lang/RuntimeException", "java/lang/Error"]
+        .into_iter()
+        .filter_map(|name| vm.find_class_by_name(name))
+        .any(|unchecked_class| class.is_subclass_of(unchecked_class));
+    if is_unchecked {
+        return Err(MethodCallFailed::ExceptionThrown(exception));
+    }
+
+    let scope = vm.handle_scope();
+    let cause = scope.handle(exception.0);
+    let wrapper =
+        scope.handle(vm.new_object(call_stack, "java/security/PrivilegedActionException")?);
+    let constructor = vm.resolve_class_method(
+        call_stack,
+        "java/security/PrivilegedActionException",
+        "<init>",
+        "(Ljava/lang/Exception;)V",
+    )?;
+    vm.invoke(
+        call_stack,
+        constructor,
+        Some(wrapper.get()),
+        vec![cause.value()],
+    )?;
+    Err(MethodCallFailed::ExceptionThrown(JavaException(
+        wrapper.get(),
+    )))
+}
+
 /// Debug method that does a "println", useful since we do not have real I/O
 fn temp_print<'a>(vm: &mut Vm<'a>, args: Vec<Value<'a>>) -> MethodCallResult<'a> {
-    let arg = args.get(0).ok_or(VmError::ValidationException)?;
+    let arg = args.first().ok_or(VmError::ValidationException)?;
 
     let formatted = match arg {
         Value::Object(object) if object.kind() == ObjectKind::Object => {
@@ -170,45 +708,325 @@ fn temp_print<'a>(vm: &mut Vm<'a>, args: Vec<Value<'a>>) -> MethodCallResult<'a>
         }
         _ => format!("{:?}", arg),
     };
-    info!("TEMP implementation of native method: printing value {formatted}",);
+    vm_log!(
+        vm.log_config(),
+        LogCategory::Natives,
+        Level::Info,
+        "TEMP implementation of native method: printing value {formatted}",
+    );
     vm.printed.push(arg.clone());
     Ok(None)
 }
 
-fn identity_hash_code(args: Vec<Value<'_>>) -> MethodCallResult<'_> {
+/// Like in the JVM, the identity hash code of null is zero
+fn identity_hash_code<'a>(vm: &mut Vm<'a>, arg: Option<&Value<'a>>) -> MethodCallResult<'a> {
+    match arg {
+        Some(Value::Object(object)) => Ok(Some(Value::Int(vm.identity_hash_code(object)))),
+        Some(Value::Null) => Ok(Some(Value::Int(0))),
+        _ => Err(MethodCallFailed::InternalError(
+            VmError::ValidationException,
+        )),
+    }
+}
+
+/// Arrays are always cloneable, while instances only if their class implements `Cloneable`
+fn object_clone<'a>(
+    vm: &mut Vm<'a>,
+    call_stack: &mut CallStack<'a>,
+    object: AbstractObject<'a>,
+) -> MethodCallResult<'a> {
+    if object.kind() == ObjectKind::Array {
+        return Ok(Some(vm.clone_array(Value::Object(object))?));
+    }
+    let class = vm.get_class_by_id(object.class_id())?;
+    let is_cloneable = vm
+        .find_class_by_name("java/lang/Cloneable")
+        .is_some_and(|cloneable| class.is_subclass_of(cloneable));
+    if !is_cloneable {
+        return throw_exception(
+            vm,
+            call_stack,
+            "java/lang/CloneNotSupportedException",
+            &class.name,
+        );
+    }
+    Ok(Some(Value::Object(vm.clone_object(&object)?)))
+}
+
+/// Returns the canonical instance of the given string, which becomes the receiver itself if
+/// there is none yet, so that a string literal evaluated later is the same object
+fn string_intern<'a>(vm: &mut Vm<'a>, string: AbstractObject<'a>) -> MethodCallResult<'a> {
+    let content = extract_str_from_java_lang_string(vm, &string)?;
+    let interned = match vm.find_interned_string(&content) {
+        Some(interned) => interned,
+        None => {
+            vm.register_interned_string(content, string.clone());
+            string
+        }
+    };
+    Ok(Some(Value::Object(interned)))
+}
+
+fn holds_lock<'a>(vm: &Vm<'a>, args: Vec<Value<'a>>) -> MethodCallResult<'a> {
     let object = expect_abstract_object_at(&args, 0)?;
-    Ok(Some(Value::Int(object.identity_hash_code())))
+    let holds_lock = object.monitor_owner() == Some(vm.current_thread_id());
+    Ok(Some(Value::Int(holds_lock.into())))
 }
 
-fn native_array_copy(args: Vec<Value>) -> MethodCallResult {
-    // TODO: handle NullPointerException with the correct error
+fn object_wait<'a>(
+    vm: &mut Vm<'a>,
+    call_stack: &mut CallStack<'a>,
+    receiver: Option<AbstractObject<'a>>,
+    args: Vec<Value<'a>>,
+) -> MethodCallResult<'a> {
+    let object = expect_some_receiver(receiver)?;
+    let timeout = timeout_from_millis(vm, call_stack, expect_long_at(&args, 0)?)?;
+    let thread = vm.current_thread_id();
+    object.check_monitor_owner(thread)?;
+    if vm.take_current_thread_interrupt() {
+        return throw_interrupted_exception(vm, call_stack);
+    }
 
-    let src = expect_array_at(&args, 0)?;
+    // The thread releases the monitor completely while waiting, then enters it again
+    // as many times as it had
+    let entry_count = object.monitor_exit_all(thread)?;
+    let timeout = Some(timeout).filter(|timeout| !timeout.is_zero());
+    vm.wait_for_notification(
+        call_stack,
+        object,
+        timeout,
+        move |vm, call_stack, object| {
+            reenter_monitor_after_wait(vm, call_stack, object, entry_count)
+        },
+    )
+}
+
+/// Completes `Object.wait` once the thread has been woken up, entering the monitor of the
+/// object as many times as it had before waiting, then checking whether it was interrupted
+fn reenter_monitor_after_wait<'a>(
+    vm: &mut Vm<'a>,
+    call_stack: &mut CallStack<'a>,
+    object: AbstractObject<'a>,
+    entry_count: u32,
+) -> MethodCallResult<'a> {
+    if !object.try_monitor_enter_times(vm.current_thread_id(), entry_count) {
+        return vm.block_current_thread_keeping(
+            call_stack,
+            object,
+            move |vm, call_stack, object| {
+                reenter_monitor_after_wait(vm, call_stack, object, entry_count)
+            },
+        );
+    }
+    if vm.take_current_thread_interrupt() {
+        return throw_interrupted_exception(vm, call_stack);
+    }
+    Ok(None)
+}
+
+fn thread_sleep<'a>(
+    vm: &mut Vm<'a>,
+    call_stack: &mut CallStack<'a>,
+    args: Vec<Value<'a>>,
+) -> MethodCallResult<'a> {
+    let duration = timeout_from_millis(vm, call_stack, expect_long_at(&args, 0)?)?;
+    if vm.take_current_thread_interrupt() {
+        return throw_sleep_interrupted(vm, call_stack);
+    }
+    vm.sleep_thread(call_stack, duration, |vm, call_stack| {
+        if vm.take_current_thread_interrupt() {
+            return throw_sleep_interrupted(vm, call_stack);
+        }
+        Ok(None)
+    })
+}
+
+fn throw_sleep_interrupted<'a>(
+    vm: &mut Vm<'a>,
+    call_stack: &mut CallStack<'a>,
+) -> MethodCallResult<'a> {
+    throw_exception(
+        vm,
+        call_stack,
+        "java/lang/InterruptedException",
+        "sleep interrupted",
+    )
+}
+
+/// Converts the timeout of `Object.wait` and `Thread.sleep`, which must not be negative
+fn timeout_from_millis<'a>(
+    vm: &mut Vm<'a>,
+    call_stack: &mut CallStack<'a>,
+    millis: i64,
+) -> Result<Duration, MethodCallFailed<'a>> {
+    match u64::try_from(millis) {
+        Ok(millis) => Ok(Duration::from_millis(millis)),
+        Err(_) => throw_exception(
+            vm,
+            call_stack,
+            "java/lang/IllegalArgumentException",
+            "timeout value is negative",
+        ),
+    }
+}
+
+fn throw_interrupted_exception<'a>(
+    vm: &mut Vm<'a>,
+    call_stack: &mut CallStack<'a>,
+) -> MethodCallResult<'a> {
+    let exception = new_java_lang_throwable_object_without_message(
+        vm,
+        call_stack,
+        "java/lang/InterruptedException",
+    )?;
+    Err(MethodCallFailed::ExceptionThrown(JavaException(exception)))
+}
+
+fn object_notify<'a>(
+    vm: &mut Vm<'a>,
+    receiver: Option<AbstractObject<'a>>,
+    all: bool,
+) -> MethodCallResult<'a> {
+    let object = expect_some_receiver(receiver)?;
+    object.check_monitor_owner(vm.current_thread_id())?;
+    vm.notify_waiting_threads(&object, all);
+    Ok(None)
+}
+
+/// `System.arraycopy`: null arguments throw `NullPointerException`, invalid ranges
+/// `ArrayIndexOutOfBoundsException` and incompatible arrays `ArrayStoreException`, all before
+/// anything is copied. The source and destination can be the same array, with overlapping ranges.
+fn native_array_copy<'a>(
+    vm: &mut Vm<'a>,
+    call_stack: &mut CallStack<'a>,
+    args: Vec<Value<'a>>,
+) -> MethodCallResult<'a> {
+    let src = expect_abstract_object_at(&args, 0)?;
     let src_pos = expect_int_at(&args, 1)?;
-    let dest = expect_array_at(&args, 2)?;
+    let dest = expect_abstract_object_at(&args, 2)?;
     let dest_pos = expect_int_at(&args, 3)?;
     let length = expect_int_at(&args, 4)?;
-    array_copy(&src, src_pos, &dest, dest_pos, length.into_usize_safe())?;
+
+    for (object, role) in [(&src, "source"), (&dest, "destination")] {
+        if object.kind() != ObjectKind::Array {
+            let type_name = Value::Object(object.clone()).runtime_type_name(vm);
+            return Err(VmError::ArrayStoreException(format!(
+                "arraycopy: {role} type {} is not an array",
+                type_name.replace('/', ".")
+            ))
+            .into());
+        }
+    }
+    let dest_type_to_check = array_copy_type_to_check(vm, &src, &dest)?;
+    if let Some(message) = array_copy_bounds_error(&src, src_pos, &dest, dest_pos, length) {
+        return throw_exception(
+            vm,
+            call_stack,
+            "java/lang/ArrayIndexOutOfBoundsException",
+            &message,
+        );
+    }
+    copy_elements(
+        vm,
+        &src,
+        src_pos.into_usize_safe(),
+        &dest,
+        dest_pos.into_usize_safe(),
+        length.into_usize_safe(),
+        dest_type_to_check,
+    )?;
     Ok(None)
 }
 
+/// Checks that both ranges of `System.arraycopy` are inside their arrays, returning the
+/// message of the exception to throw otherwise
+fn array_copy_bounds_error(
+    src: &AbstractObject,
+    src_pos: i32,
+    dest: &AbstractObject,
+    dest_pos: i32,
+    length: i32,
+) -> Option<String> {
+    if length < 0 {
+        return Some(format!("arraycopy: length {length} is negative"));
+    }
+    for (array, pos, role) in [(src, src_pos, "source"), (dest, dest_pos, "destination")] {
+        let array_len = i64::from(array.len());
+        if pos < 0 || i64::from(pos) > array_len {
+            return Some(format!(
+                "arraycopy: {role} index {pos} out of bounds for length {array_len}"
+            ));
+        }
+        let end = i64::from(pos) + i64::from(length);
+        if end > array_len {
+            return Some(format!(
+                "arraycopy: last {role} index {end} out of bounds for length {array_len}"
+            ));
+        }
+    }
+    None
+}
+
+/// Copies `length` elements between two arrays, whose ranges must have already been checked
 pub fn array_copy<'a>(
+    vm: &Vm<'a>,
     src: &impl Array<'a>,
-    src_pos: i32,
+    src_pos: usize,
     dest: &impl Array<'a>,
-    dest_pos: i32,
+    dest_pos: usize,
     length: usize,
 ) -> Result<(), VmError> {
-    if dest.elements_type() != src.elements_type() {
-        // TODO: we should throw an instance of ArrayStoreException
-        return Err(VmError::ValidationException);
+    let dest_type_to_check = array_copy_type_to_check(vm, src, dest)?;
+    copy_elements(vm, src, src_pos, dest, dest_pos, length, dest_type_to_check)
+}
+
+/// Copying between arrays of different reference types is allowed, but then each element
+/// must be checked against the type of the destination array, which is returned here
+fn array_copy_type_to_check<'a>(
+    vm: &Vm<'a>,
+    src: &impl Array<'a>,
+    dest: &impl Array<'a>,
+) -> Result<Option<FieldType>, VmError> {
+    match (src.elements_type(), dest.elements_type()) {
+        (src_type, dest_type) if src_type == dest_type => Ok(None),
+        (ArrayEntryType::Base(_), _) | (_, ArrayEntryType::Base(_)) => Err(
+            VmError::ArrayStoreException("arraycopy: type mismatch".to_string()),
+        ),
+        (_, dest_type) => Ok(Some(
+            dest_type
+                .into_field_type(vm)
+                .ok_or(VmError::ValidationException)?,
+        )),
     }
+}
 
-    for i in 0..length {
-        let src_index = src_pos.into_usize_safe() + i;
-        let src_item = src.get_element(src_index)?;
+/// The elements are read before any of them is written, so that the ranges can overlap
+/// when copying inside the same array
+fn copy_elements<'a>(
+    vm: &Vm<'a>,
+    src: &impl Array<'a>,
+    src_pos: usize,
+    dest: &impl Array<'a>,
+    dest_pos: usize,
+    length: usize,
+    dest_type_to_check: Option<FieldType>,
+) -> Result<(), VmError> {
+    let src_items = (src_pos..src_pos + length)
+        .map(|src_index| src.get_element(src_index))
+        .collect::<Result<Vec<_>, _>>()?;
+    for (dest_index, src_item) in (dest_pos..).zip(src_items) {
+        // As in the real JVM, the elements before the first incompatible one are copied
+        if let Some(dest_type) = &dest_type_to_check {
+            if !src_item.matches_type(dest_type, vm, |class_name| {
+                vm.find_class_by_name(class_name)
+            }) {
+                return Err(VmError::ArrayStoreException(
+                    "arraycopy: element type mismatch".to_string(),
+                ));
+            }
+        }
 
-        let dest_index = dest_pos.into_usize_safe() + i;
+        vm.write_barrier(&src_item);
         dest.set_element(dest_index, src_item)?;
     }
 
@@ -217,23 +1035,16 @@ pub fn array_copy<'a>(
 
 fn float_to_raw_int_bits<'a>(args: &[Value<'a>]) -> MethodCallResult<'a> {
     let arg = expect_float_at(args, 0)?;
-    let int_bits: i32 = arg.to_bits() as i32;
+    let int_bits: i32 = arg.to_bits().cast_signed();
     Ok(Some(Value::Int(int_bits)))
 }
 
 fn double_to_raw_long_bits<'a>(args: &[Value<'a>]) -> MethodCallResult<'a> {
     let arg = expect_double_at(args, 0)?;
-    let long_bits: i64 = arg.to_bits() as i64;
+    let long_bits: i64 = arg.to_bits().cast_signed();
     Ok(Some(Value::Long(long_bits)))
 }
 
-fn get_class_loader(receiver: Option<AbstractObject>) -> MethodCallResult {
-    debug!("invoked get class loader for object {:?}", receiver);
-
-    // It seems ok to return just null for the moment
-    Ok(Some(Value::Null))
-}
-
 fn get_primitive_class<'a>(
     vm: &mut Vm<'a>,
     stack: &mut CallStack<'a>,
@@ -241,10 +1052,85 @@ fn get_primitive_class<'a>(
 ) -> MethodCallResult<'a> {
     let arg = expect_concrete_object_at(args, 0)?;
     let class_name = extract_str_from_java_lang_string(vm, &arg)?;
-    let java_lang_class_instance = new_java_lang_class_object(vm, stack, &class_name)?;
+    let java_lang_class_instance = get_java_lang_class_object(vm, stack, &class_name)?;
     Ok(Some(Value::Object(java_lang_class_instance)))
 }
 
+/// Loads the class with the given binary name, i.e. `java.lang.String` or `[I`, and
+/// initializes it if requested. The classes that are not in the class path are requested to
+/// the given loader, if any.
+fn class_for_name<'a>(
+    vm: &mut Vm<'a>,
+    stack: &mut CallStack<'a>,
+    args: &[Value<'a>],
+) -> MethodCallResult<'a> {
+    let name = extract_str_from_java_lang_string(vm, &expect_concrete_object_at(args, 0)?)?;
+    let initialize = expect_int_at(args, 1)? != 0;
+    let class_name = name.replace('.', "/");
+    if !class_name.starts_with('[') {
+        // The bootstrap loader, i.e. `null`, cannot see the classes of the other loaders
+        let resolved_class = match args.get(2) {
+            Some(Value::Null) => vm.resolve_class_of(&class_name, DefiningLoader::Bootstrap),
+            _ => vm.resolve_class(&class_name),
+        };
+        let class = match (resolved_class, args.get(2)) {
+            (Err(VmError::ClassNotFoundException(_)), Some(Value::Object(loader))) => {
+                match user_class_loaders::load_class(vm, stack, loader.clone(), &class_name) {
+                    Err(MethodCallFailed::InternalError(VmError::ClassNotFoundException(_))) => {
+                        return throw_exception(
+                            vm,
+                            stack,
+                            "java/lang/ClassNotFoundException",
+                            &name,
+                        );
+                    }
+                    result => result?,
+                }
+            }
+            (Err(VmError::ClassNotFoundException(_)), _) => {
+                return throw_exception(vm, stack, "java/lang/ClassNotFoundException", &name);
+            }
+            (result, _) => result?,
+        };
+        if initialize {
+            vm.initialize_class(stack, class)?;
+        }
+    }
+    let class_object = get_java_lang_class_object(vm, stack, &class_name)?;
+    Ok(Some(Value::Object(class_object)))
+}
+
+/// Returns the class of the method that invoked the caller-sensitive method calling this one.
+/// Natives do not have a frame, so that is the second one from the top of the stack.
+fn get_caller_class<'a>(vm: &mut Vm<'a>, stack: &mut CallStack<'a>) -> MethodCallResult<'a> {
+    match stack.class_of_frame(1) {
+        Some(class) => {
+            let class_object = get_java_lang_class_object(vm, stack, &class.name)?;
+            Ok(Some(Value::Object(class_object)))
+        }
+        None => Ok(Some(Value::Null)),
+    }
+}
+
+fn get_class<'a>(
+    vm: &mut Vm<'a>,
+    stack: &mut CallStack<'a>,
+    object: AbstractObject<'a>,
+) -> MethodCallResult<'a> {
+    let class_name = match object.kind() {
+        ObjectKind::Object => vm.get_class_by_id(object.class_id())?.name.clone(),
+        ObjectKind::Array => FieldType::Array(Box::new(
+            object
+                .elements_type()
+                .into_field_type(vm)
+                .ok_or(VmError::ValidationException)?,
+        ))
+        .to_descriptor(),
+    };
+    let class_object = get_java_lang_class_object(vm, stack, &class_name)?;
+    Ok(Some(Value::Object(class_object)))
+}
+
 fn fill_in_stack_trace<'a>(
     vm: &mut Vm<'a>,
     call_stack: &mut CallStack<'a>,
@@ -262,7 +1148,7 @@ fn get_stack_trace_depth<'a>(
 ) -> MethodCallResult<'a> {
     let receiver = expect_some_receiver(receiver)?;
     match vm.get_stack_trace_associated_with_throwable(receiver) {
-        Some(stack_trace_elements) => Ok(Some(Value::Int(stack_trace_elements.len() as i32))),
+        Some(stack_trace_elements) => Ok(Some(Value::Int(stack_trace_elements.len().narrow()?))),
         None => Err(MethodCallFailed::InternalError(
             VmError::ValidationException,
         )),
@@ -290,9 +1176,269 @@ fn get_stack_trace_element<'a>(
     }
 }
 
+/// The native methods of java.lang.StrictMath. In our rt.jar, the ones of java.lang.Math are
+/// implemented in bytecode by invoking them, but we register them as intrinsics to save
+/// a call; they are also registered as natives for the class libraries that declare them so.
+fn register_math_methods(registry: &mut NativeMethodsRegistry) {
+    let unary: [(&str, NativeCallback); 16] = [
+        ("sin", |_, _, _, args| unary_math(&args, f64::sin)),
+        ("cos", |_, _, _, args| unary_math(&args, f64::cos)),
+        ("tan", |_, _, _, args| unary_math(&args, f64::tan)),
+        ("asin", |_, _, _, args| unary_math(&args, f64::asin)),
+        ("acos", |_, _, _, args| unary_math(&args, f64::acos)),
+        ("atan", |_, _, _, args| unary_math(&args, f64::atan)),
+        ("exp", |_, _, _, args| unary_math(&args, f64::exp)),
+        ("log", |_, _, _, args| unary_math(&args, f64::ln)),
+        ("log10", |_, _, _, args| unary_math(&args, f64::log10)),
+        ("sqrt", |_, _, _, args| unary_math(&args, f64::sqrt)),
+        ("cbrt", |_, _, _, args| unary_math(&args, f64::cbrt)),
+        ("sinh", |_, _, _, args| unary_math(&args, f64::sinh)),
+        ("cosh", |_, _, _, args| unary_math(&args, f64::cosh)),
+        ("tanh", |_, _, _, args| unary_math(&args, f64::tanh)),
+        ("expm1", |_, _, _, args| unary_math(&args, f64::exp_m1)),
+        ("log1p", |_, _, _, args| unary_math(&args, f64::ln_1p)),
+    ];
+    let binary: [(&str, NativeCallback); 4] = [
+        ("atan2", |_, _, _, args| binary_math(&args, f64::atan2)),
+        ("pow", |_, _, _, args| binary_math(&args, math::pow)),
+        ("hypot", |_, _, _, args| binary_math(&args, f64::hypot)),
+        ("IEEEremainder", |_, _, _, args| {
+            binary_math(&args, math::ieee_remainder)
+        }),
+    ];
+    let methods = unary
+        .into_iter()
+        .map(|(name, callback)| (name, "(D)D", callback))
+        .chain(
+            binary
+                .into_iter()
+                .map(|(name, callback)| (name, "(DD)D", callback)),
+        );
+    for (name, descriptor, callback) in methods {
+        registry.register("java/lang/StrictMath", name, descriptor, callback);
+        registry.register("java/lang/Math", name, descriptor, callback);
+        registry.register_intrinsic("java/lang/Math", name, descriptor, callback);
+    }
+}
+
+fn unary_math<'a>(args: &[Value<'a>], function: fn(f64) -> f64) -> MethodCallResult<'a> {
+    let arg = expect_double_at(args, 0)?;
+    Ok(Some(Value::Double(function(arg))))
+}
+
+/// Remember that doubles take two slots in the arguments
+fn binary_math<'a>(args: &[Value<'a>], function: fn(f64, f64) -> f64) -> MethodCallResult<'a> {
+    let first = expect_double_at(args, 0)?;
+    let second = expect_double_at(args, 2)?;
+    Ok(Some(Value::Double(function(first, second))))
+}
+
+fn character_predicate<'a>(args: &[Value<'a>], predicate: fn(i32) -> bool) -> MethodCallResult<'a> {
+    let code_point = expect_int_at(args, 0)?;
+    Ok(Some(Value::Int(if predicate(code_point) { 1 } else { 0 })))
+}
+
+fn string_result<'a>(
+    vm: &mut Vm<'a>,
+    call_stack: &mut CallStack<'a>,
+    content: &str,
+) -> MethodCallResult<'a> {
+    let string = new_java_lang_string_object(vm, call_stack, content)?;
+    Ok(Some(Value::Object(string)))
+}
+
+/// Appends the given string to a `StringBuilder` or `StringBuffer`, returning the receiver
+fn append_to_string_builder<'a>(
+    vm: &mut Vm<'a>,
+    call_stack: &mut CallStack<'a>,
+    receiver: Option<AbstractObject<'a>>,
+    content: &str,
+) -> MethodCallResult<'a> {
+    let scope = vm.handle_scope();
+    let receiver = scope.handle(expect_some_receiver(receiver)?);
+    let string = new_java_lang_string_object(vm, call_stack, content)?;
+    vm.invoke_virtual(
+        call_stack,
+        receiver.get(),
+        "append",
+        "(Ljava/lang/String;)Ljava/lang/AbstractStringBuilder;",
+        vec![Value::Object(string)],
+    )?;
+    Ok(Some(receiver.value()))
+}
+
+fn number_to_string<'a>(
+    vm: &mut Vm<'a>,
+    call_stack: &mut CallStack<'a>,
+    value: i64,
+    radix: i32,
+) -> MethodCallResult<'a> {
+    let formatted = format_with_radix(value, radix);
+    let string = new_java_lang_string_object(vm, call_stack, &formatted)?;
+    Ok(Some(Value::Object(string)))
+}
+
+fn parse_int<'a>(
+    vm: &mut Vm<'a>,
+    call_stack: &mut CallStack<'a>,
+    args: &[Value<'a>],
+    radix: i32,
+) -> MethodCallResult<'a> {
+    let parsed = parse_number_argument(vm, call_stack, args, radix)?;
+    match i32::try_from(parsed) {
+        Ok(int) => Ok(Some(Value::Int(int))),
+        Err(_) => {
+            let string = expect_concrete_object_at(args, 0)?;
+            let string = extract_str_from_java_lang_string(vm, &string)?;
+            throw_number_format_exception(vm, call_stack, &for_input_string(&string))
+        }
+    }
+}
+
+fn parse_long<'a>(
+    vm: &mut Vm<'a>,
+    call_stack: &mut CallStack<'a>,
+    args: &[Value<'a>],
+    radix: i32,
+) -> MethodCallResult<'a> {
+    let parsed = parse_number_argument(vm, call_stack, args, radix)?;
+    Ok(Some(Value::Long(parsed)))
+}
+
+/// Parses the string at index 0 of the arguments with the same semantics of
+/// `Long.parseLong`, throwing `NumberFormatException` for invalid inputs
+fn parse_number_argument<'a>(
+    vm: &mut Vm<'a>,
+    call_stack: &mut CallStack<'a>,
+    args: &[Value<'a>],
+    radix: i32,
+) -> Result<i64, MethodCallFailed<'a>> {
+    if let Some(Value::Null) = args.first() {
+        return throw_number_format_exception(vm, call_stack, "null");
+    }
+    let string = expect_concrete_object_at(args, 0)?;
+    let string = extract_str_from_java_lang_string(vm, &string)?;
+    match parse_with_radix(&string, radix) {
+        Ok(value) => Ok(value),
+        Err(message) => throw_number_format_exception(vm, call_stack, &message),
+    }
+}
+
+fn throw_number_format_exception<'a, T>(
+    vm: &mut Vm<'a>,
+    call_stack: &mut CallStack<'a>,
+    message: &str,
+) -> Result<T, MethodCallFailed<'a>> {
+    throw_exception(vm, call_stack, "java/lang/NumberFormatException", message)
+}
+
+/// Formats the given number as `Long.toString(long, int)` does: lowercase digits, a leading
+/// minus for negative numbers, and radix 10 if the given one is not valid.
+fn format_with_radix(value: i64, radix: i32) -> String {
+    let radix: u32 = if (MIN_RADIX..=MAX_RADIX).contains(&radix) {
+        radix.narrow().expect("radix should be positive")
+    } else {
+        10
+    };
+
+    let mut digits = Vec::new();
+    let mut magnitude = value.unsigned_abs();
+    loop {
+        let digit = (magnitude % u64::from(radix))
+            .narrow()
+            .ok()
+            .and_then(|digit| char::from_digit(digit, radix))
+            .expect("digit should be smaller than the radix");
+        digits.push(digit);
+        magnitude /= u64::from(radix);
+        if magnitude == 0 {
+            break;
+        }
+    }
+    if value < 0 {
+        digits.push('-');
+    }
+    digits.iter().rev().collect()
+}
+
+/// Parses the given string as `Long.parseLong(String, int)` does, returning the message of the
+/// `NumberFormatException` to throw in case of errors. Note that, unlike java, we only
+/// support ASCII digits.
+fn parse_with_radix(string: &str, radix: i32) -> Result<i64, String> {
+    if radix < MIN_RADIX {
+        return Err(format!("radix {radix} less than Character.MIN_RADIX"));
+    }
+    if radix > MAX_RADIX {
+        return Err(format!("radix {radix} greater than Character.MAX_RADIX"));
+    }
+    i64::from_str_radix(string, radix as u32).map_err(|_| for_input_string(string))
+}
+
+fn for_input_string(string: &str) -> String {
+    format!("For input string: \"{string}\"")
+}
+
 fn expect_some_receiver(receiver: Option<AbstractObject>) -> Result<AbstractObject, VmError> {
     match receiver {
         Some(v) => Ok(v),
         None => Err(VmError::ValidationException),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::native_methods_impl::{format_with_radix, parse_with_radix};
+
+    #[test]
+    fn can_format_numbers_with_radix() {
+        assert_eq!("0", format_with_radix(0, 10));
+        assert_eq!("-255", format_with_radix(-255, 10));
+        assert_eq!("ff", format_with_radix(255, 16));
+        assert_eq!("-101", format_with_radix(-5, 2));
+        assert_eq!("zz", format_with_radix(1295, 36));
+        assert_eq!("-9223372036854775808", format_with_radix(i64::MIN, 10));
+    }
+
+    #[test]
+    fn format_uses_radix_10_when_invalid() {
+        assert_eq!("42", format_with_radix(42, 1));
+        assert_eq!("42", format_with_radix(42, 37));
+    }
+
+    #[test]
+    fn can_parse_numbers_with_radix() {
+        assert_eq!(Ok(42), parse_with_radix("42", 10));
+        assert_eq!(Ok(42), parse_with_radix("+42", 10));
+        assert_eq!(Ok(-255), parse_with_radix("-FF", 16));
+        assert_eq!(Ok(1295), parse_with_radix("zz", 36));
+        assert_eq!(Ok(i64::MIN), parse_with_radix("-9223372036854775808", 10));
+    }
+
+    #[test]
+    fn parse_errors_have_the_same_messages_as_java() {
+        assert_eq!(
+            Err("For input string: \"\"".to_string()),
+            parse_with_radix("", 10)
+        );
+        assert_eq!(
+            Err("For input string: \"-\"".to_string()),
+            parse_with_radix("-", 10)
+        );
+        assert_eq!(
+            Err("For input string: \"12a\"".to_string()),
+            parse_with_radix("12a", 10)
+        );
+        assert_eq!(
+            Err("For input string: \"9223372036854775808\"".to_string()),
+            parse_with_radix("9223372036854775808", 10)
+        );
+        assert_eq!(
+            Err("radix 1 less than Character.MIN_RADIX".to_string()),
+            parse_with_radix("1", 1)
+        );
+        assert_eq!(
+            Err("radix 37 greater than Character.MAX_RADIX".to_string()),
+            parse_with_radix("1", 37)
+        );
+    }
+}
@@ -13,11 +13,39 @@ pub type NativeCallback<'a> = fn(
     Vec<Value<'a>>,
 ) -> MethodCallResult<'a>;
 
+/// A [NativeCallback] that works with a vm of any lifetime, so that it can be provided before
+/// the vm is created, i.e. to a [VmBuilder](crate::vm_builder::VmBuilder). All functions and
+/// closures that do not capture anything can be used as such.
+pub type AnyVmNativeCallback = for<'a> fn(
+    &mut Vm<'a>,
+    &mut CallStack<'a>,
+    Option<AbstractObject<'a>>,
+    Vec<Value<'a>>,
+) -> MethodCallResult<'a>;
+
+/// Decides whether an intrinsic applies to the given receiver
+pub type ReceiverFilter<'a> = fn(&Vm<'a>, &AbstractObject<'a>) -> bool;
+
+/// A native implementation of a method that is NOT marked as native, which the vm can use
+/// instead of interpreting its bytecode
+#[derive(Debug, Clone, Copy)]
+pub struct Intrinsic<'a> {
+    pub callback: NativeCallback<'a>,
+    /// Collection intrinsics are used only while they are enabled; see
+    /// [Vm::set_collection_intrinsics_enabled]
+    pub is_collection_intrinsic: bool,
+    /// If set, the intrinsic is used only for the receivers that the filter accepts, while
+    /// the bytecode runs for the others
+    pub receiver_filter: Option<ReceiverFilter<'a>>,
+}
+
 /// The registry of all known native methods
 #[derive(Default)]
 pub struct NativeMethodsRegistry<'a> {
     methods: HashMap<ClassMethodAndDescriptor, NativeCallback<'a>>,
 
+    intrinsics: HashMap<ClassMethodAndDescriptor, Intrinsic<'a>>,
+
     // Hack for checking that integration tests can actually print the correct values:
     // this just stores the values printed by a method named `tempPrint` into an array
     // in the Vm object. This method is used for all classes whose name starts with rjvm.
@@ -48,6 +76,83 @@ impl<'a> NativeMethodsRegistry<'a> {
         );
     }
 
+    pub fn register_intrinsic(
+        &mut self,
+        class_name: &str,
+        method_name: &str,
+        type_descriptor: &str,
+        callback: NativeCallback<'a>,
+    ) {
+        self.insert_intrinsic(
+            class_name,
+            method_name,
+            type_descriptor,
+            Intrinsic {
+                callback,
+                is_collection_intrinsic: false,
+                receiver_filter: None,
+            },
+        );
+    }
+
+    /// Registers an intrinsic that is used only for some instances of the class, such as the
+    /// ones created by the vm itself
+    pub fn register_intrinsic_for_receivers(
+        &mut self,
+        class_name: &str,
+        method_name: &str,
+        type_descriptor: &str,
+        receiver_filter: ReceiverFilter<'a>,
+        callback: NativeCallback<'a>,
+    ) {
+        self.insert_intrinsic(
+            class_name,
+            method_name,
+            type_descriptor,
+            Intrinsic {
+                callback,
+                is_collection_intrinsic: false,
+                receiver_filter: Some(receiver_filter),
+            },
+        );
+    }
+
+    pub fn register_collection_intrinsic(
+        &mut self,
+        class_name: &str,
+        method_name: &str,
+        type_descriptor: &str,
+        callback: NativeCallback<'a>,
+    ) {
+        self.insert_intrinsic(
+            class_name,
+            method_name,
+            type_descriptor,
+            Intrinsic {
+                callback,
+                is_collection_intrinsic: true,
+                receiver_filter: None,
+            },
+        );
+    }
+
+    fn insert_intrinsic(
+        &mut self,
+        class_name: &str,
+        method_name: &str,
+        type_descriptor: &str,
+        intrinsic: Intrinsic<'a>,
+    ) {
+        self.intrinsics.insert(
+            ClassMethodAndDescriptor {
+                class: class_name.to_string(),
+                method: method_name.to_string(),
+                descriptor: type_descriptor.to_string(),
+            },
+            intrinsic,
+        );
+    }
+
     pub(crate) fn register_temp_print(&mut self, callback: NativeCallback<'a>) {
         self.temp_print_callback = Some(callback);
     }
@@ -60,6 +165,16 @@ impl<'a> NativeMethodsRegistry<'a> {
         )
     }
 
+    pub fn get_intrinsic(&self, class_and_method: &ClassAndMethod) -> Option<Intrinsic<'a>> {
+        self.intrinsics
+            .get(&ClassMethodAndDescriptor {
+                class: class_and_method.class.name.to_string(),
+                method: class_and_method.method.name.to_string(),
+                descriptor: class_and_method.method.type_descriptor.to_string(),
+            })
+            .cloned()
+    }
+
     pub fn get(
         &self,
         class_name: &str,
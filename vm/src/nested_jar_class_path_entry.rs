@@ -0,0 +1,226 @@
+use std::{
+    cell::RefCell,
+    collections::{BTreeSet, HashMap},
+    fmt::{Debug, Formatter},
+    io::Cursor,
+};
+
+use zip::ZipArchive;
+
+use crate::{
+    class_path_entry::{ClassLoadingError, ClassPathEntry},
+    jar_file_class_path_entry::{read_zip_file, JarFileClassPathEntry, JarFileError},
+    jar_manifest::JarManifest,
+};
+
+/// The directory of a Spring Boot jar containing the classes of the application
+const CLASSES_DIRECTORY: &str = "BOOT-INF/classes/";
+
+/// Implementation of [ClassPathEntry] for "fat" jars, such as the ones built by Spring Boot,
+/// that contain their dependencies as nested jars, e.g. in `BOOT-INF/lib/`.
+///
+/// Files are searched, in order, in the outer jar, in its `BOOT-INF/classes/` directory and
+/// in the nested jars. Nested jars are read into memory when the entry is created and can, in
+/// turn, contain other jars. As in Spring Boot, the manifests of the nested jars are ignored.
+pub struct NestedJarClassPathEntry {
+    outer: JarFileClassPathEntry,
+    archives: Vec<NestedArchive>,
+    /// For each file of the nested jars, the index of the archive providing it;
+    /// the first one wins
+    index: HashMap<String, usize>,
+}
+
+/// A jar stored inside another one
+struct NestedArchive {
+    /// The name of the archive, e.g. `app.jar!/BOOT-INF/lib/lib.jar`
+    name: String,
+    zip: RefCell<ZipArchive<Cursor<Vec<u8>>>>,
+}
+
+impl Debug for NestedJarClassPathEntry {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let archive_names: Vec<&str> = self
+            .archives
+            .iter()
+            .map(|archive| archive.name.as_str())
+            .collect();
+        write!(
+            f,
+            "NestedJarClassPathEntry {{ file_name: {}, nested: {:?} }}",
+            self.outer.description(),
+            archive_names
+        )
+    }
+}
+
+impl NestedJarClassPathEntry {
+    /// Indexes all the jars nested in the given one, recursively
+    pub fn new(outer: JarFileClassPathEntry) -> Result<Self, JarFileError> {
+        let outer_name = outer.description();
+        let nested_jars: Vec<String> = outer
+            .file_names()
+            .into_iter()
+            .filter(|file_name| is_jar(file_name))
+            .collect();
+        let mut entry = Self {
+            outer,
+            archives: Vec::new(),
+            index: HashMap::new(),
+        };
+        for file_name in nested_jars {
+            let name = format!("{outer_name}!/{file_name}");
+            let bytes = entry
+                .outer
+                .resolve_resource(&file_name)
+                .map_err(|_| JarFileError::ReadingError(name.clone()))?
+                .ok_or_else(|| JarFileError::ReadingError(name.clone()))?;
+            entry.add_archive(name, bytes)?;
+        }
+        Ok(entry)
+    }
+
+    /// Whether the jar contains other jars or a Spring Boot classes directory, i.e. whether
+    /// it needs to be wrapped in a [NestedJarClassPathEntry] for all its classes to be found
+    pub fn is_fat_jar(jar: &JarFileClassPathEntry) -> bool {
+        jar.file_names()
+            .iter()
+            .any(|file_name| is_jar(file_name) || file_name.starts_with(CLASSES_DIRECTORY))
+    }
+
+    fn add_archive(&mut self, name: String, bytes: Vec<u8>) -> Result<(), JarFileError> {
+        let mut zip = ZipArchive::new(Cursor::new(bytes))
+            .map_err(|_| JarFileError::InvalidJar(name.clone()))?;
+        let file_names: Vec<String> = zip.file_names().map(str::to_string).collect();
+
+        let mut nested_archives = Vec::new();
+        for file_name in file_names.iter().filter(|file_name| is_jar(file_name)) {
+            let nested_name = format!("{name}!/{file_name}");
+            let bytes = read_zip_file(&mut zip, file_name)
+                .map_err(|_| JarFileError::ReadingError(nested_name.clone()))?
+                .ok_or_else(|| JarFileError::ReadingError(nested_name.clone()))?;
+            nested_archives.push((nested_name, bytes));
+        }
+
+        let archive_index = self.archives.len();
+        for file_name in file_names {
+            if !file_name.ends_with('/') {
+                self.index.entry(file_name).or_insert(archive_index);
+            }
+        }
+        self.archives.push(NestedArchive {
+            name,
+            zip: RefCell::new(zip),
+        });
+
+        for (nested_name, bytes) in nested_archives {
+            self.add_archive(nested_name, bytes)?;
+        }
+        Ok(())
+    }
+}
+
+fn is_jar(file_name: &str) -> bool {
+    file_name.ends_with(".jar")
+}
+
+impl ClassPathEntry for NestedJarClassPathEntry {
+    fn resolve_resource(&self, path: &str) -> Result<Option<Vec<u8>>, ClassLoadingError> {
+        if let Some(bytes) = self.outer.resolve_resource(path)? {
+            return Ok(Some(bytes));
+        }
+        if let Some(bytes) = self
+            .outer
+            .resolve_resource(&format!("{CLASSES_DIRECTORY}{path}"))?
+        {
+            return Ok(Some(bytes));
+        }
+        match self.index.get(path) {
+            Some(&archive_index) => {
+                read_zip_file(&mut self.archives[archive_index].zip.borrow_mut(), path)
+            }
+            None => Ok(None),
+        }
+    }
+
+    fn description(&self) -> String {
+        self.outer.description()
+    }
+
+    fn manifest(&self) -> Option<&JarManifest> {
+        self.outer.manifest()
+    }
+
+    fn class_names(&self) -> Result<Vec<String>, ClassLoadingError> {
+        let outer_class_names = self.outer.class_names()?.into_iter().map(|class_name| {
+            class_name
+                .strip_prefix(CLASSES_DIRECTORY)
+                .map(str::to_string)
+                .unwrap_or(class_name)
+        });
+        let nested_class_names = self
+            .index
+            .keys()
+            .filter_map(|file_name| file_name.strip_suffix(".class"))
+            .map(str::to_string);
+        let class_names: BTreeSet<String> = outer_class_names.chain(nested_class_names).collect();
+        Ok(class_names.into_iter().collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{path::PathBuf, rc::Rc};
+
+    use crate::{
+        class_path_entry::{
+            tests::{assert_can_find_class, assert_cannot_find_class, assert_cannot_find_resource},
+            ClassPathEntry,
+        },
+        io::StdJvmIo,
+        jar_file_class_path_entry::JarFileClassPathEntry,
+        nested_jar_class_path_entry::NestedJarClassPathEntry,
+    };
+
+    fn open_jar(path: &str) -> JarFileClassPathEntry {
+        let mut full_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        full_path.push(path);
+        JarFileClassPathEntry::new(full_path, Rc::new(StdJvmIo::new()), None)
+            .expect("should have read the jar file")
+    }
+
+    #[test]
+    fn only_jars_with_nested_content_are_fat_jars() {
+        assert!(NestedJarClassPathEntry::is_fat_jar(&open_jar(
+            "tests/resources/jars/fat.jar"
+        )));
+        assert!(!NestedJarClassPathEntry::is_fat_jar(&open_jar(
+            "tests/resources/jars/app.jar"
+        )));
+    }
+
+    #[test]
+    fn can_find_classes_in_nested_jars() {
+        let entry = NestedJarClassPathEntry::new(open_jar("tests/resources/jars/fat.jar"))
+            .expect("should have indexed the nested jars");
+
+        assert_can_find_class(&entry, "rjvm/jars/App"); // From BOOT-INF/classes
+        assert_can_find_class(&entry, "rjvm/jars/lib/Greeter"); // From a jar in a nested jar
+        assert_cannot_find_class(&entry, "rjvm/jars/Foo");
+        assert_cannot_find_resource(&entry, "BOOT-INF/");
+        assert_eq!(
+            Some("rjvm.jars.App"),
+            entry.manifest().and_then(|manifest| manifest.main_class())
+        );
+    }
+
+    #[test]
+    fn can_list_the_classes_of_nested_jars() {
+        let entry = NestedJarClassPathEntry::new(open_jar("tests/resources/jars/fat.jar"))
+            .expect("should have indexed the nested jars");
+
+        assert_eq!(
+            vec!["rjvm/jars/App", "rjvm/jars/lib/Greeter"],
+            entry.class_names().expect("should be able to list classes")
+        );
+    }
+}
@@ -1,3 +1,5 @@
+use rjvm_reader::field_type::FieldType;
+
 use crate::{
     class::{ClassId, ClassRef},
     value::Value,
@@ -12,4 +14,7 @@ pub trait Object<'a> {
 
     /// Errors will be returned if the index is invalid
     fn get_field(&self, object_class: ClassRef, index: usize) -> Value<'a>;
+
+    /// Like `get_field`, for callers that already know the type of the field
+    fn get_field_of_type(&self, index: usize, field_type: &FieldType) -> Value<'a>;
 }
@@ -0,0 +1,167 @@
+use rjvm_reader::{
+    class_file_field::ClassFileField, field_flags::FieldFlags, field_type::FieldType,
+};
+
+use crate::{
+    abstract_object::{AbstractObject, ObjectKind},
+    class::ClassRef,
+    object::Object,
+    value::Value,
+    vm::Vm,
+    vm_error::VmError,
+};
+
+/// An instance field of an object, together with its current value
+#[derive(Debug, Clone)]
+pub struct ObjectField<'a> {
+    pub declaring_class: ClassRef<'a>,
+    pub name: &'a str,
+    pub field_type: &'a FieldType,
+    pub value: Value<'a>,
+}
+
+/// Returns all the instance fields of the given object, including the inherited ones.
+/// The order is stable: the fields of the base classes come first, and each class'
+/// fields are in the order in which they are declared in the class file.
+pub fn get_object_fields<'a>(
+    vm: &Vm<'a>,
+    object: &AbstractObject<'a>,
+) -> Result<Vec<ObjectField<'a>>, VmError> {
+    let class = object_class(vm, object)?;
+    Ok(instance_fields(class)
+        .map(|(declaring_class, index, field)| ObjectField {
+            declaring_class,
+            name: &field.name,
+            field_type: &field.type_descriptor,
+            value: object.get_field(class, index),
+        })
+        .collect())
+}
+
+/// Returns the value of the instance field with the given name. If both the object's class
+/// and one of its base classes declare a field with the same name, the one of the most
+/// derived class is returned, as [set_object_field] does.
+pub fn get_object_field<'a>(
+    vm: &Vm<'a>,
+    object: &AbstractObject<'a>,
+    field_name: &str,
+) -> Result<Value<'a>, VmError> {
+    let class = object_class(vm, object)?;
+    let (_, index, _) = instance_fields(class)
+        .filter(|(_, _, field)| field.name == field_name)
+        .last()
+        .ok_or_else(|| {
+            VmError::FieldNotFoundException(class.name.clone(), field_name.to_string())
+        })?;
+    Ok(object.get_field(class, index))
+}
+
+/// Sets the instance field with the given name, checking that the value matches the
+/// field's type. If both the object's class and one of its base classes declare a field with
+/// the same name, the one of the most derived class is set; use
+/// [set_object_field_of_class] to set the others.
+pub fn set_object_field<'a>(
+    vm: &Vm<'a>,
+    object: &AbstractObject<'a>,
+    field_name: &str,
+    value: Value<'a>,
+) -> Result<(), VmError> {
+    let class = object_class(vm, object)?;
+    let (_, index, field) = instance_fields(class)
+        .filter(|(_, _, field)| field.name == field_name)
+        .last()
+        .ok_or_else(|| {
+            VmError::FieldNotFoundException(class.name.clone(), field_name.to_string())
+        })?;
+    set_checked(vm, object, index, field, class, value)
+}
+
+/// Sets the instance field with the given name declared by the given class, which must be the
+/// object's class or one of its base classes, checking that the value matches the field's type.
+pub fn set_object_field_of_class<'a>(
+    vm: &Vm<'a>,
+    object: &AbstractObject<'a>,
+    declaring_class_name: &str,
+    field_name: &str,
+    value: Value<'a>,
+) -> Result<(), VmError> {
+    let class = object_class(vm, object)?;
+    let (declaring_class, index, field) = instance_fields(class)
+        .find(|(declaring_class, _, field)| {
+            declaring_class.name == declaring_class_name && field.name == field_name
+        })
+        .ok_or_else(|| {
+            VmError::FieldNotFoundException(
+                declaring_class_name.to_string(),
+                field_name.to_string(),
+            )
+        })?;
+    set_checked(vm, object, index, field, declaring_class, value)
+}
+
+/// Returns the static fields declared by the given class, stored in the given object, which
+/// must be the one holding the static fields of the class
+pub(crate) fn static_fields<'a>(
+    class: ClassRef<'a>,
+    statics: &AbstractObject<'a>,
+) -> Vec<ObjectField<'a>> {
+    class
+        .fields
+        .iter()
+        .enumerate()
+        .filter(|(_, field)| field.flags.contains(FieldFlags::STATIC))
+        .map(|(index, field)| ObjectField {
+            declaring_class: class,
+            name: &field.name,
+            field_type: &field.type_descriptor,
+            value: statics.get_field(class, class.first_field_index + index),
+        })
+        .collect()
+}
+
+fn object_class<'a>(vm: &Vm<'a>, object: &AbstractObject<'a>) -> Result<ClassRef<'a>, VmError> {
+    if object.kind() != ObjectKind::Object {
+        return Err(VmError::ValidationException);
+    }
+    vm.get_class_by_id(object.class_id())
+}
+
+/// Iterates over the instance fields of the given class and of its base classes, returning
+/// the class declaring each field and the index of the field in the object
+fn instance_fields<'a>(
+    class: ClassRef<'a>,
+) -> impl Iterator<Item = (ClassRef<'a>, usize, &'a ClassFileField<'a>)> {
+    let mut hierarchy = vec![class];
+    while let Some(superclass) = hierarchy.last().and_then(|class| class.superclass) {
+        hierarchy.push(superclass);
+    }
+    hierarchy.into_iter().rev().flat_map(|class| {
+        class
+            .fields
+            .iter()
+            .enumerate()
+            .filter(|(_, field)| !field.flags.contains(FieldFlags::STATIC))
+            .map(move |(index, field)| (class, class.first_field_index + index, field))
+    })
+}
+
+fn set_checked<'a>(
+    vm: &Vm<'a>,
+    object: &AbstractObject<'a>,
+    index: usize,
+    field: &ClassFileField,
+    declaring_class: ClassRef<'a>,
+    value: Value<'a>,
+) -> Result<(), VmError> {
+    if !value.matches_type(&field.type_descriptor, vm, |class_name| {
+        vm.find_class_by_name(class_name)
+    }) {
+        return Err(VmError::FieldTypeMismatch(
+            declaring_class.name.clone(),
+            field.name.to_string(),
+        ));
+    }
+    vm.write_barrier(&value);
+    object.set_field(index, value);
+    Ok(())
+}
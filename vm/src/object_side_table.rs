@@ -0,0 +1,85 @@
+use std::collections::HashMap;
+
+use crate::abstract_object::AbstractObject;
+
+/// Associates some host-side data to java objects, without keeping the objects alive:
+/// when an object is garbage collected, its entry is dropped after the collection.
+///
+/// Since our gc moves objects, we cannot just use their address as a key. Rather, we store
+/// the objects as weak roots, that the gc will update, and we rebuild the index after each
+/// collection.
+#[derive(Debug)]
+pub(crate) struct ObjectSideTable<'a, T> {
+    entries: Vec<(Option<AbstractObject<'a>>, T)>,
+    index_by_address: HashMap<usize, usize>,
+}
+
+impl<'a, T> Default for ObjectSideTable<'a, T> {
+    fn default() -> Self {
+        Self {
+            entries: Vec::new(),
+            index_by_address: HashMap::new(),
+        }
+    }
+}
+
+impl<'a, T> ObjectSideTable<'a, T> {
+    /// Returns the data associated with the given object, creating it if missing
+    pub fn get_or_insert_with(
+        &mut self,
+        object: &AbstractObject<'a>,
+        default: impl FnOnce() -> T,
+    ) -> &mut T {
+        let index = match self.index_by_address.get(&object.address()) {
+            Some(index) => *index,
+            None => self.push(object, default()),
+        };
+        &mut self.entries[index].1
+    }
+
+    pub fn insert(&mut self, object: &AbstractObject<'a>, value: T) {
+        match self.index_by_address.get(&object.address()) {
+            Some(index) => self.entries[*index].1 = value,
+            None => {
+                self.push(object, value);
+            }
+        }
+    }
+
+    fn push(&mut self, object: &AbstractObject<'a>, value: T) -> usize {
+        self.entries.push((Some(object.clone()), value));
+        let index = self.entries.len() - 1;
+        self.index_by_address.insert(object.address(), index);
+        index
+    }
+
+    /// Returns the keys of the table, as in [Self::weak_roots], each with its data
+    pub fn entries_mut(
+        &mut self,
+    ) -> impl Iterator<Item = (*mut Option<AbstractObject<'a>>, &mut T)> + '_ {
+        self.entries
+            .iter_mut()
+            .map(|(object, value)| (object as *mut Option<AbstractObject<'a>>, value))
+    }
+
+    /// Returns the keys of the table, that must be passed to the gc as weak roots
+    pub fn weak_roots(&mut self) -> impl Iterator<Item = *mut Option<AbstractObject<'a>>> + '_ {
+        self.entries
+            .iter_mut()
+            .map(|(object, _)| object as *mut Option<AbstractObject<'a>>)
+    }
+
+    /// Must be invoked after a garbage collection: drops the entries whose object has been
+    /// collected and recomputes the index with the new addresses
+    pub fn after_garbage_collection(&mut self) {
+        self.entries.retain(|(object, _)| object.is_some());
+        self.index_by_address = self
+            .entries
+            .iter()
+            .enumerate()
+            .filter_map(|(index, (object, _))| {
+                object.as_ref().map(|object| (object.address(), index))
+            })
+            .collect();
+    }
+}
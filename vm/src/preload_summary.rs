@@ -0,0 +1,29 @@
+use std::{fmt, fmt::Formatter};
+
+use crate::vm_error::VmError;
+
+/// The outcome of [Vm::preload_all](crate::vm::Vm::preload_all)
+#[derive(Debug, Default, PartialEq)]
+pub struct PreloadSummary {
+    /// The number of classes of the class paths that are loaded, including the ones that
+    /// already were before the preload
+    pub loaded: usize,
+    /// The classes that could not be loaded, in class path order, with the reason. The class
+    /// path entries whose classes could not be listed appear here too, with their description.
+    pub failures: Vec<(String, VmError)>,
+}
+
+impl fmt::Display for PreloadSummary {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "preloaded {} classes, {} failed",
+            self.loaded,
+            self.failures.len()
+        )?;
+        for (name, error) in self.failures.iter() {
+            write!(f, "\n  {name}: {error}")?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,66 @@
+use std::collections::HashMap;
+
+use rjvm_reader::class_file_field::ClassFileField;
+
+use crate::{class::ClassId, instruction_site::InstructionSite};
+
+/// The `getfield` and `putfield` instructions quickened after their first execution: rather
+/// than reading the constant pool, looking up the field by name in the class hierarchy and
+/// checking its accessibility every time, they access the field directly by its index, as long
+/// as the receiver has the same class it had when the field was resolved. An instruction
+/// executed with a receiver of a different class goes through the slow path again, and is
+/// quickened for the new class.
+///
+/// The quickened accesses stay valid since classes are never unloaded, and their redefinitions
+/// keep the fields. The instructions of the old versions of a redefined class are dropped.
+#[derive(Debug, Default)]
+pub(crate) struct QuickenedFields<'a> {
+    fields: HashMap<InstructionSite, QuickenedField<'a>>,
+}
+
+/// A field access resolved for the receivers of a class
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct QuickenedField<'a> {
+    receiver_class: ClassId,
+    /// The index of the field in the instances of the receiver class
+    pub index: usize,
+    pub field: &'a ClassFileField<'a>,
+}
+
+impl<'a> QuickenedFields<'a> {
+    /// The field accessed by the given instruction on a receiver of the given class, if the
+    /// instruction was quickened for that class
+    pub fn get(
+        &self,
+        instruction_site: InstructionSite,
+        receiver_class: ClassId,
+    ) -> Option<QuickenedField<'a>> {
+        self.fields
+            .get(&instruction_site)
+            .filter(|quickened| quickened.receiver_class == receiver_class)
+            .copied()
+    }
+
+    /// Drops the instructions of the versions of the given class, e.g. when it is redefined
+    pub fn remove_class(&mut self, class_id: ClassId) {
+        self.fields
+            .retain(|instruction_site, _| instruction_site.class_id() != class_id);
+    }
+
+    pub fn insert(
+        &mut self,
+        instruction_site: InstructionSite,
+        receiver_class: ClassId,
+        index: usize,
+        field: &'a ClassFileField<'a>,
+    ) {
+        self.fields.insert(
+            instruction_site,
+            QuickenedField {
+                receiver_class,
+                index,
+                field,
+            },
+        );
+    }
+}
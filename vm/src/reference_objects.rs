@@ -0,0 +1,81 @@
+use crate::{
+    abstract_object::AbstractObject,
+    class::ClassRef,
+    object::Object,
+    object_fields::{get_object_field, set_object_field},
+    value::Value,
+    vm::Vm,
+    vm_error::VmError,
+};
+
+/// The subclasses of `java.lang.ref.Reference` that the gc treats specially: their referent is
+/// not kept alive by the reference object, and it is cleared once it is not strongly reachable
+/// anymore. Classes extending `Reference` in other ways (i.e. `FinalReference`) are strong.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReferenceKind {
+    /// Cleared only when the memory is exhausted, before failing with an out of memory error
+    Soft,
+    Weak,
+    /// Cleared like the weak ones; since `get` always returns null, the difference is invisible
+    Phantom,
+}
+
+impl ReferenceKind {
+    /// The kind of the references of the given class, which is inherited by its subclasses
+    pub(crate) fn of_class(class_name: &str, superclass: Option<ClassRef>) -> Option<Self> {
+        match class_name {
+            "java/lang/ref/SoftReference" => Some(Self::Soft),
+            "java/lang/ref/WeakReference" => Some(Self::Weak),
+            "java/lang/ref/PhantomReference" => Some(Self::Phantom),
+            _ => superclass.and_then(|superclass| superclass.reference_kind),
+        }
+    }
+}
+
+/// The index of `Reference.referent`, the first field declared by `Reference`
+pub(crate) const REFERENT_FIELD_INDEX: usize = 0;
+/// The index of `Reference.next`, which is null while the reference is active, i.e. until
+/// it has been enqueued
+pub(crate) const NEXT_FIELD_INDEX: usize = 2;
+
+/// Appends a reference cleared by the gc to its queue, like `ReferenceQueue.enqueue` does, and
+/// wakes up the threads blocked in `ReferenceQueue.remove`. Returns false, without changing
+/// anything, if some thread holds the lock of the reference or of the queue: the java code might
+/// be in the middle of updating them, so the enqueueing must be retried later.
+/// References that were not registered with a queue are simply dropped.
+pub(crate) fn try_enqueue_reference<'a>(
+    vm: &mut Vm<'a>,
+    reference: &AbstractObject<'a>,
+) -> Result<bool, VmError> {
+    let Value::Object(queue) = get_object_field(vm, reference, "queue")? else {
+        return Ok(true);
+    };
+    // Both ReferenceQueue.NULL, used for the references without a queue, and
+    // ReferenceQueue.ENQUEUED, for those already enqueued, are instances of this class
+    if vm.get_class_by_id(queue.class_id())?.name == "java/lang/ref/ReferenceQueue$Null" {
+        return Ok(true);
+    }
+    let Value::Object(lock) = get_object_field(vm, &queue, "lock")? else {
+        return Err(VmError::ValidationException);
+    };
+    if reference.monitor_owner().is_some() || lock.monitor_owner().is_some() {
+        return Ok(false);
+    }
+
+    let enqueued = vm.get_static_field("java/lang/ref/ReferenceQueue", "ENQUEUED")?;
+    set_object_field(vm, reference, "queue", enqueued)?;
+    // The last reference of the queue points to itself, since a null next means "active"
+    let next = match get_object_field(vm, &queue, "head")? {
+        Value::Null => Value::Object(reference.clone()),
+        head => head,
+    };
+    set_object_field(vm, reference, "next", next)?;
+    set_object_field(vm, &queue, "head", Value::Object(reference.clone()))?;
+    let Value::Long(queue_length) = get_object_field(vm, &queue, "queueLength")? else {
+        return Err(VmError::ValidationException);
+    };
+    set_object_field(vm, &queue, "queueLength", Value::Long(queue_length + 1))?;
+
+    vm.notify_waiting_threads(&lock, true);
+    Ok(true)
+}
@@ -0,0 +1,145 @@
+use log::Level;
+
+use crate::{
+    call_frame::MethodCallResult,
+    call_stack::CallStack,
+    java_objects_creation::extract_str_from_java_lang_string,
+    log_config::{vm_log, LogCategory},
+    native_methods_registry::NativeMethodsRegistry,
+    object_fields::get_object_field,
+    value::{expect_abstract_object_at, Value},
+    vm::Vm,
+    vm_error::VmError,
+};
+
+/// Registers the methods that read resources, i.e. arbitrary files such as `.properties`,
+/// from the class path. The JDK implementation goes through `URLClassPath` and the launcher's
+/// class loaders, which we do not have, so the entry points are replaced by intrinsics.
+pub(crate) fn register_resource_methods(registry: &mut NativeMethodsRegistry) {
+    // Replaced too because `Class.getName` returns the internal name, e.g. `rjvm/Foo`, which
+    // breaks the resolution of relative resource names done by the JDK
+    registry.register_intrinsic(
+        "java/lang/Class",
+        "getResourceAsStream",
+        "(Ljava/lang/String;)Ljava/io/InputStream;",
+        |vm, stack, receiver, args| {
+            let class_object = receiver.ok_or(VmError::NullPointerException)?;
+            let class_name = match get_object_field(vm, &class_object, "name")? {
+                Value::Object(class_name) => extract_str_from_java_lang_string(vm, &class_name)?,
+                _ => return Err(VmError::ValidationException.into()),
+            };
+            let name = string_argument(vm, &args)?;
+            open_resource_stream(vm, stack, &resolve_name(&class_name, &name))
+        },
+    );
+    registry.register_intrinsic(
+        "java/lang/ClassLoader",
+        "getSystemResourceAsStream",
+        "(Ljava/lang/String;)Ljava/io/InputStream;",
+        |vm, stack, _, args| {
+            let name = string_argument(vm, &args)?;
+            open_resource_stream(vm, stack, &name)
+        },
+    );
+    registry.register_intrinsic(
+        "java/lang/ClassLoader",
+        "getResourceAsStream",
+        "(Ljava/lang/String;)Ljava/io/InputStream;",
+        |vm, stack, _, args| {
+            let name = string_argument(vm, &args)?;
+            open_resource_stream(vm, stack, &name)
+        },
+    );
+}
+
+fn string_argument<'a>(vm: &Vm<'a>, args: &[Value<'a>]) -> Result<String, VmError> {
+    let string = expect_abstract_object_at(args, 0)?;
+    extract_str_from_java_lang_string(vm, &string)
+}
+
+/// Resolves a resource name relative to the package of the given class, unless it starts
+/// with `/`, like `Class.resolveName`. Arrays use the package of their element class.
+fn resolve_name(class_name: &str, name: &str) -> String {
+    if let Some(absolute_name) = name.strip_prefix('/') {
+        return absolute_name.to_string();
+    }
+    let element_class_name = if class_name.starts_with('[') {
+        let element_descriptor = class_name.trim_start_matches('[');
+        element_descriptor
+            .strip_prefix('L')
+            .and_then(|descriptor| descriptor.strip_suffix(';'))
+            .unwrap_or(element_descriptor)
+    } else {
+        class_name
+    };
+    match element_class_name.rsplit_once('/') {
+        Some((package, _)) => format!("{package}/{name}"),
+        None => name.to_string(),
+    }
+}
+
+/// Returns a `ByteArrayInputStream` with the content of the resource, or null if it cannot be
+/// found or read, like the JDK does
+fn open_resource_stream<'a>(
+    vm: &mut Vm<'a>,
+    call_stack: &mut CallStack<'a>,
+    name: &str,
+) -> MethodCallResult<'a> {
+    let bytes = match vm.resolve_resource(name) {
+        Ok(Some(bytes)) => bytes,
+        Ok(None) => return Ok(Some(Value::Null)),
+        Err(err) => {
+            vm_log!(
+                vm.log_config(),
+                LogCategory::ClassLoad,
+                Level::Warn,
+                "cannot read resource {}: {}",
+                name,
+                err
+            );
+            return Ok(Some(Value::Null));
+        }
+    };
+
+    let scope = vm.handle_scope();
+    let array = scope.handle(vm.new_byte_array(&bytes)?);
+    let stream = scope.handle(vm.new_object(call_stack, "java/io/ByteArrayInputStream")?);
+    let constructor = vm.resolve_class_method(
+        call_stack,
+        "java/io/ByteArrayInputStream",
+        "<init>",
+        "([B)V",
+    )?;
+    vm.invoke(
+        call_stack,
+        constructor,
+        Some(stream.get()),
+        vec![array.value()],
+    )?;
+    Ok(Some(stream.value()))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::resources::resolve_name;
+
+    #[test]
+    fn resolves_names_relative_to_the_package() {
+        assert_eq!("rjvm/data.txt", resolve_name("rjvm/Foo", "data.txt"));
+        assert_eq!(
+            "rjvm/sub/data.txt",
+            resolve_name("rjvm/Foo", "sub/data.txt")
+        );
+        assert_eq!("data.txt", resolve_name("Foo", "data.txt"));
+        assert_eq!("rjvm/data.txt", resolve_name("[[Lrjvm/Foo;", "data.txt"));
+        assert_eq!("data.txt", resolve_name("[I", "data.txt"));
+    }
+
+    #[test]
+    fn absolute_names_are_not_resolved() {
+        assert_eq!(
+            "other/data.txt",
+            resolve_name("rjvm/Foo", "/other/data.txt")
+        );
+    }
+}
@@ -0,0 +1,49 @@
+use std::collections::BTreeMap;
+
+/// Configuration of a program run, i.e. an execution of a `main` method via [crate::vm::Vm::invoke_main].
+///
+/// The vm maps it as follows:
+/// - `args` becomes the `String[]` passed to `main`;
+/// - `working_directory` becomes the `user.dir` system property;
+/// - `properties` are set as system properties, overriding the existing ones;
+/// - `environment` is added to the environment variables set by the embedder, overriding
+///   the existing ones. Variables of the host process are never exposed unless explicitly
+///   added here, or the vm is configured with a [JvmIo](crate::io::JvmIo) that exposes them,
+///   such as [StdJvmIo](crate::io::StdJvmIo).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RunConfig {
+    pub args: Vec<String>,
+    pub working_directory: Option<String>,
+    pub properties: BTreeMap<String, String>,
+    pub environment: BTreeMap<String, String>,
+}
+
+impl RunConfig {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    pub fn with_args<S: Into<String>>(mut self, args: impl IntoIterator<Item = S>) -> Self {
+        self.args = args.into_iter().map(Into::into).collect();
+        self
+    }
+
+    pub fn with_working_directory(mut self, working_directory: impl Into<String>) -> Self {
+        self.working_directory = Some(working_directory.into());
+        self
+    }
+
+    pub fn with_property(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.properties.insert(name.into(), value.into());
+        self
+    }
+
+    pub fn with_environment_variable(
+        mut self,
+        name: impl Into<String>,
+        value: impl Into<String>,
+    ) -> Self {
+        self.environment.insert(name.into(), value.into());
+        self
+    }
+}
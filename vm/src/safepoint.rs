@@ -0,0 +1,45 @@
+use std::{cell::RefCell, fmt, fmt::Formatter, rc::Rc};
+
+use crate::{abstract_object::AbstractObject, stack_trace_element::StackTraceElement, vm::Vm};
+
+type SafepointCallback = Box<dyn for<'v, 'a> FnOnce(&SafepointView<'v, 'a>)>;
+
+/// Lets the embedder ask the vm to stop at its next safepoint, i.e. before executing the next
+/// instruction, and to invoke a callback with a read-only view of the vm before resuming.
+/// It can be cloned and kept anywhere, i.e. in an allocation hook or in a native method.
+#[derive(Clone, Default)]
+pub struct SafepointRequester(Rc<RefCell<Vec<SafepointCallback>>>);
+
+impl SafepointRequester {
+    /// Queues a callback, to be invoked at the next safepoint
+    pub fn request(&self, callback: impl for<'v, 'a> FnOnce(&SafepointView<'v, 'a>) + 'static) {
+        self.0.borrow_mut().push(Box::new(callback));
+    }
+
+    pub fn has_pending_requests(&self) -> bool {
+        !self.0.borrow().is_empty()
+    }
+
+    pub(crate) fn take_requests(&self) -> Vec<SafepointCallback> {
+        std::mem::take(&mut *self.0.borrow_mut())
+    }
+}
+
+impl fmt::Debug for SafepointRequester {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "SafepointRequester{{pending={}}}", self.0.borrow().len())
+    }
+}
+
+/// The state of a vm stopped at a safepoint. Since no instruction is halfway executed,
+/// all the objects in use are reachable from the roots, and nothing changes until the
+/// callbacks return.
+pub struct SafepointView<'v, 'a> {
+    pub vm: &'v Vm<'a>,
+    /// The stack traces of the call stacks that are executing some method,
+    /// with the innermost frame first
+    pub call_stacks: Vec<Vec<StackTraceElement<'a>>>,
+    /// The objects directly referenced by the statics, the call stacks and the native
+    /// structures of the vm
+    pub roots: Vec<AbstractObject<'a>>,
+}
@@ -0,0 +1,139 @@
+use crate::{
+    abstract_object::AbstractObject,
+    call_frame::MethodCallResult,
+    call_stack::CallStack,
+    exceptions::MethodCallFailed,
+    java_objects_creation::throw_exception,
+    native_methods_registry::NativeMethodsRegistry,
+    value::{expect_abstract_object_at, Value},
+    vm::Vm,
+};
+
+/// The hooks registered via `Runtime.addShutdownHook`, i.e. threads that have not been
+/// started yet. The vm starts them all, and waits for them to complete, when it shuts down,
+/// either because the program has terminated or because it has invoked `System.exit`.
+#[derive(Debug, Default)]
+pub(crate) struct ShutdownHooks<'a> {
+    hooks: Vec<AbstractObject<'a>>,
+    /// Set once the hooks have been started; they cannot be modified anymore
+    started: bool,
+}
+
+impl<'a> ShutdownHooks<'a> {
+    /// The hooks must survive until they run, even if the program drops its references
+    pub fn gc_roots(&mut self) -> impl Iterator<Item = *mut AbstractObject<'a>> + '_ {
+        self.hooks
+            .iter_mut()
+            .map(|object| object as *mut AbstractObject<'a>)
+    }
+
+    fn contains(&self, hook: &AbstractObject<'a>) -> bool {
+        self.hooks.iter().any(|other| other.is_same_as(hook))
+    }
+}
+
+/// Registers the intrinsics that replace the methods of `ApplicationShutdownHooks`, to which
+/// `Runtime` delegates, so that the hooks are tracked by the vm. The JDK still invokes
+/// `runHooks` during its shutdown sequence, in `Shutdown.exit` or `Shutdown.shutdown`.
+pub(crate) fn register_shutdown_hook_methods(registry: &mut NativeMethodsRegistry) {
+    registry.register_intrinsic(
+        "java/lang/ApplicationShutdownHooks",
+        "add",
+        "(Ljava/lang/Thread;)V",
+        |vm, stack, _, args| add_hook(vm, stack, expect_abstract_object_at(&args, 0)?),
+    );
+    registry.register_intrinsic(
+        "java/lang/ApplicationShutdownHooks",
+        "remove",
+        "(Ljava/lang/Thread;)Z",
+        |vm, stack, _, args| remove_hook(vm, stack, expect_abstract_object_at(&args, 0)?),
+    );
+    registry.register_intrinsic(
+        "java/lang/ApplicationShutdownHooks",
+        "runHooks",
+        "()V",
+        |vm, stack, _, _| {
+            run_shutdown_hooks(vm, stack)?;
+            Ok(None)
+        },
+    );
+}
+
+/// Adds a hook, with the same checks and messages of the JDK
+fn add_hook<'a>(
+    vm: &mut Vm<'a>,
+    call_stack: &mut CallStack<'a>,
+    hook: AbstractObject<'a>,
+) -> MethodCallResult<'a> {
+    if vm.shutdown_hooks.started {
+        return throw_exception(
+            vm,
+            call_stack,
+            "java/lang/IllegalStateException",
+            "Shutdown in progress",
+        );
+    }
+    if vm.is_thread_alive(&hook) {
+        return throw_exception(
+            vm,
+            call_stack,
+            "java/lang/IllegalArgumentException",
+            "Hook already running",
+        );
+    }
+    if vm.shutdown_hooks.contains(&hook) {
+        return throw_exception(
+            vm,
+            call_stack,
+            "java/lang/IllegalArgumentException",
+            "Hook previously registered",
+        );
+    }
+    vm.shutdown_hooks.hooks.push(hook);
+    Ok(None)
+}
+
+/// Removes a hook, returning whether it was registered
+fn remove_hook<'a>(
+    vm: &mut Vm<'a>,
+    call_stack: &mut CallStack<'a>,
+    hook: AbstractObject<'a>,
+) -> MethodCallResult<'a> {
+    if vm.shutdown_hooks.started {
+        return throw_exception(
+            vm,
+            call_stack,
+            "java/lang/IllegalStateException",
+            "Shutdown in progress",
+        );
+    }
+    let hooks = &mut vm.shutdown_hooks.hooks;
+    let count = hooks.len();
+    hooks.retain(|other| !other.is_same_as(&hook));
+    let removed = hooks.len() < count;
+    Ok(Some(Value::Int(i32::from(removed))))
+}
+
+/// Starts all the registered shutdown hooks, concurrently, and waits for them to complete.
+/// Like in the JDK, the hooks run only once, and cannot be modified afterwards.
+fn run_shutdown_hooks<'a>(
+    vm: &mut Vm<'a>,
+    call_stack: &mut CallStack<'a>,
+) -> Result<(), MethodCallFailed<'a>> {
+    if vm.shutdown_hooks.started {
+        return Ok(());
+    }
+    vm.shutdown_hooks.started = true;
+    let scope = vm.handle_scope();
+    let hooks: Vec<_> = std::mem::take(&mut vm.shutdown_hooks.hooks)
+        .into_iter()
+        .map(|hook| scope.handle(hook))
+        .collect();
+    for hook in hooks.iter() {
+        vm.invoke_virtual(call_stack, hook.get(), "start", "()V", Vec::new())?;
+    }
+    for hook in hooks.iter() {
+        vm.invoke_virtual(call_stack, hook.get(), "join", "()V", Vec::new())?;
+    }
+    Ok(())
+}
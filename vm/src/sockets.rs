@@ -0,0 +1,512 @@
+use std::{
+    net::{IpAddr, Ipv4Addr, Ipv6Addr, Shutdown, SocketAddr},
+    time::Duration,
+};
+
+use rjvm_reader::type_conversion::ToUsizeSafe;
+
+use crate::{
+    abstract_object::AbstractObject,
+    array::Array,
+    array_entry_type::ArrayEntryType,
+    call_frame::MethodCallResult,
+    call_stack::CallStack,
+    exceptions::MethodCallFailed,
+    file_io::{bytes_of_byte_array, checked_range, copy_into_byte_array},
+    io::{NetError, NetErrorKind, SocketHandle},
+    java_objects_creation::{
+        extract_str_from_java_lang_string, new_java_lang_string_object, throw_exception,
+    },
+    native_methods_registry::NativeMethodsRegistry,
+    object_fields::{get_object_field, set_object_field},
+    value::{expect_abstract_object_at, expect_int_at, Value},
+    vm::Vm,
+    vm_error::VmError,
+};
+
+const INET_ADDRESS: &str = "java/net/InetAddress";
+const INET4_ADDRESS: &str = "java/net/Inet4Address";
+const INET6_ADDRESS: &str = "java/net/Inet6Address";
+const INET4_ADDRESS_IMPL: &str = "java/net/Inet4AddressImpl";
+const ABSTRACT_PLAIN_SOCKET_IMPL: &str = "java/net/AbstractPlainSocketImpl";
+const PLAIN_SOCKET_IMPL: &str = "java/net/PlainSocketImpl";
+const SOCKET_INPUT_STREAM: &str = "java/net/SocketInputStream";
+const SOCKET_OUTPUT_STREAM: &str = "java/net/SocketOutputStream";
+
+/// The maximum number of bytes read at once from a socket, as in the JDK
+const MAX_READ_LENGTH: usize = 65536;
+
+/// The backlog of the listening sockets, since `std::net` does not let us choose it
+const LISTEN_BACKLOG: u32 = 128;
+
+/// Registers the natives of `java.net.Socket` and `java.net.ServerSocket`, which use the
+/// [JvmNet](crate::io::JvmNet) of the vm. Only plain TCP sockets are supported: the
+/// options are ignored, and a client socket cannot be bound before connecting.
+/// Our rt.jar needs `sun.misc.Unsafe` to initialize `InetAddress`, and its caches of the
+/// resolved hosts need the security properties; thus, we replace them with intrinsics that
+/// always ask the JvmNet. Likewise, we skip the lookup of the SOCKS proxy when connecting,
+/// which needs reflection.
+pub(crate) fn register_socket_methods(registry: &mut NativeMethodsRegistry) {
+    register_inet_address_methods(registry);
+    register_socket_impl_methods(registry);
+    register_socket_streams_methods(registry);
+}
+
+fn register_inet_address_methods(registry: &mut NativeMethodsRegistry) {
+    registry.register_intrinsic(INET_ADDRESS, "<clinit>", "()V", |vm, stack, _, _| {
+        let scope = vm.handle_scope();
+        let address_impl = scope.handle(vm.new_object(stack, INET4_ADDRESS_IMPL)?);
+        let constructor = vm.resolve_class_method(stack, INET4_ADDRESS_IMPL, "<init>", "()V")?;
+        vm.invoke(stack, constructor, Some(address_impl.get()), Vec::new())?;
+        vm.set_static_field(INET_ADDRESS, "impl", address_impl.value())?;
+        Ok(None)
+    });
+    registry.register(INET4_ADDRESS, "init", "()V", |_, _, _, _| Ok(None));
+    // These initializers only prepare the serialization, which needs `sun.misc.Unsafe`
+    for class_name in [INET6_ADDRESS, "java/net/InetSocketAddress"] {
+        registry.register_intrinsic(class_name, "<clinit>", "()V", |_, _, _, _| Ok(None));
+    }
+
+    registry.register_intrinsic(
+        INET_ADDRESS,
+        "getAllByName0",
+        "(Ljava/lang/String;Ljava/net/InetAddress;Z)[Ljava/net/InetAddress;",
+        |vm, stack, _, args| {
+            let host = expect_abstract_object_at(&args, 0)?;
+            let host = extract_str_from_java_lang_string(vm, &host)?;
+            match vm.net().resolve_host(&host) {
+                Ok(addresses) if !addresses.is_empty() => {
+                    let addresses = new_inet_address_array(vm, stack, &host, &addresses)?;
+                    Ok(Some(Value::Object(addresses)))
+                }
+                Ok(_) => throw_exception(vm, stack, "java/net/UnknownHostException", &host),
+                Err(err) => throw_exception(
+                    vm,
+                    stack,
+                    "java/net/UnknownHostException",
+                    &format!("{host}: {}", err.message),
+                ),
+            }
+        },
+    );
+}
+
+fn register_socket_impl_methods(registry: &mut NativeMethodsRegistry) {
+    registry.register_intrinsic(
+        "java/net/SocksSocketImpl",
+        "connect",
+        "(Ljava/net/SocketAddress;I)V",
+        |vm, stack, receiver, args| {
+            let connect = vm.resolve_class_method(
+                stack,
+                ABSTRACT_PLAIN_SOCKET_IMPL,
+                "connect",
+                "(Ljava/net/SocketAddress;I)V",
+            )?;
+            vm.invoke(stack, connect, receiver, args)
+        },
+    );
+
+    registry.register(PLAIN_SOCKET_IMPL, "initProto", "()V", |_, _, _, _| Ok(None));
+    // These count the datagram sockets using an `AtomicInteger`, which needs
+    // `sun.misc.Unsafe`. Closing an accepted socket calls them too.
+    for method_name in ["<clinit>", "beforeUdpCreate", "afterUdpClose"] {
+        registry.register_intrinsic(
+            "sun/net/ResourceManager",
+            method_name,
+            "()V",
+            |_, _, _, _| Ok(None),
+        );
+    }
+    // The socket is created when it gets connected or bound
+    registry.register(PLAIN_SOCKET_IMPL, "socketCreate", "(Z)V", |_, _, _, _| {
+        Ok(None)
+    });
+    registry.register(
+        PLAIN_SOCKET_IMPL,
+        "socketConnect",
+        "(Ljava/net/InetAddress;II)V",
+        |vm, stack, receiver, args| {
+            let socket_impl = receiver.ok_or(VmError::ValidationException)?;
+            let address = socket_address(vm, stack, &args)?;
+            let timeout = timeout(expect_int_at(&args, 2)?);
+            let result = vm.net().connect(address, timeout);
+            let socket = net_result(vm, stack, result)?;
+            register_socket(vm, stack, &socket_impl, socket)?;
+            Ok(None)
+        },
+    );
+    registry.register(
+        PLAIN_SOCKET_IMPL,
+        "socketBind",
+        "(Ljava/net/InetAddress;I)V",
+        |vm, stack, receiver, args| {
+            let socket_impl = receiver.ok_or(VmError::ValidationException)?;
+            if get_object_field(vm, &socket_impl, "serverSocket")? == Value::Null {
+                return throw_exception(
+                    vm,
+                    stack,
+                    "java/net/SocketException",
+                    "Binding a client socket is not supported",
+                );
+            }
+            let address = socket_address(vm, stack, &args)?;
+            let result = vm.net().listen(address, LISTEN_BACKLOG);
+            let socket = net_result(vm, stack, result)?;
+            set_object_field(vm, &socket_impl, "address", args[0].clone())?;
+            register_socket(vm, stack, &socket_impl, socket)?;
+            Ok(None)
+        },
+    );
+    // We already listen since the socket has been bound
+    registry.register(PLAIN_SOCKET_IMPL, "socketListen", "(I)V", |_, _, _, _| {
+        Ok(None)
+    });
+    registry.register(
+        PLAIN_SOCKET_IMPL,
+        "socketAccept",
+        "(Ljava/net/SocketImpl;)V",
+        |vm, stack, receiver, args| {
+            let listener_impl = receiver.ok_or(VmError::ValidationException)?;
+            accept(vm, stack, &listener_impl, &args)
+        },
+    );
+    registry.register(
+        PLAIN_SOCKET_IMPL,
+        "socketAvailable",
+        "()I",
+        |vm, stack, receiver, _| {
+            let socket_impl = receiver.ok_or(VmError::ValidationException)?;
+            let socket = socket_of(vm, stack, &socket_impl)?;
+            let result = vm.net().available(socket);
+            let available = net_result(vm, stack, result)?;
+            Ok(Some(Value::Int(
+                i32::try_from(available).unwrap_or(i32::MAX),
+            )))
+        },
+    );
+    registry.register(
+        PLAIN_SOCKET_IMPL,
+        "socketClose0",
+        "(Z)V",
+        |vm, stack, receiver, _| {
+            let socket_impl = receiver.ok_or(VmError::ValidationException)?;
+            let Value::Object(descriptor) = get_object_field(vm, &socket_impl, "fd")? else {
+                return Ok(None);
+            };
+            let number = descriptor_number(vm, &descriptor)?;
+            set_object_field(vm, &descriptor, "fd", Value::Int(-1))?;
+            if let Some(socket) = vm.open_sockets.remove(number) {
+                let result = vm.net().close_socket(socket);
+                net_result(vm, stack, result)?;
+            }
+            Ok(None)
+        },
+    );
+    registry.register(
+        PLAIN_SOCKET_IMPL,
+        "socketShutdown",
+        "(I)V",
+        |vm, stack, receiver, args| {
+            let socket_impl = receiver.ok_or(VmError::ValidationException)?;
+            let socket = socket_of(vm, stack, &socket_impl)?;
+            // The values of `SocketImpl.SHUT_RD` and `SHUT_WR`
+            let how = match expect_int_at(&args, 0)? {
+                0 => Shutdown::Read,
+                _ => Shutdown::Write,
+            };
+            let result = vm.net().shutdown_socket(socket, how);
+            net_result(vm, stack, result)?;
+            Ok(None)
+        },
+    );
+    registry.register(
+        PLAIN_SOCKET_IMPL,
+        "socketSetOption",
+        "(IZLjava/lang/Object;)V",
+        |_, _, _, _| Ok(None),
+    );
+    // -1 means that the option is not set
+    registry.register(
+        PLAIN_SOCKET_IMPL,
+        "socketGetOption",
+        "(ILjava/lang/Object;)I",
+        |_, _, _, _| Ok(Some(Value::Int(-1))),
+    );
+    registry.register(
+        PLAIN_SOCKET_IMPL,
+        "socketSendUrgentData",
+        "(I)V",
+        |vm, stack, _, _| {
+            throw_exception(
+                vm,
+                stack,
+                "java/net/SocketException",
+                "Urgent data is not supported",
+            )
+        },
+    );
+}
+
+fn register_socket_streams_methods(registry: &mut NativeMethodsRegistry) {
+    registry.register(SOCKET_INPUT_STREAM, "init", "()V", |_, _, _, _| Ok(None));
+    registry.register(SOCKET_OUTPUT_STREAM, "init", "()V", |_, _, _, _| Ok(None));
+    registry.register(
+        SOCKET_INPUT_STREAM,
+        "socketRead0",
+        "(Ljava/io/FileDescriptor;[BIII)I",
+        |vm, stack, _, args| read(vm, stack, &args),
+    );
+    registry.register(
+        SOCKET_OUTPUT_STREAM,
+        "socketWrite0",
+        "(Ljava/io/FileDescriptor;[BII)V",
+        |vm, stack, _, args| {
+            let descriptor = expect_abstract_object_at(&args, 0)?;
+            let socket = socket_of_descriptor(vm, stack, &descriptor)?;
+            let array = expect_abstract_object_at(&args, 1)?;
+            let Some((offset, length)) =
+                checked_range(&array, expect_int_at(&args, 2)?, expect_int_at(&args, 3)?)
+            else {
+                return throw_exception(vm, stack, "java/lang/ArrayIndexOutOfBoundsException", "");
+            };
+            let bytes = bytes_of_byte_array(&array, offset, length)?;
+            let result = vm.net().write_socket(socket, &bytes);
+            net_result(vm, stack, result)?;
+            Ok(None)
+        },
+    );
+}
+
+/// Zero means no timeout in java
+fn timeout(millis: i32) -> Option<Duration> {
+    u64::try_from(millis)
+        .ok()
+        .filter(|millis| *millis > 0)
+        .map(Duration::from_millis)
+}
+
+/// Throws the error of the JvmNet as the corresponding java exception
+fn net_result<'a, T>(
+    vm: &mut Vm<'a>,
+    call_stack: &mut CallStack<'a>,
+    result: Result<T, NetError>,
+) -> Result<T, MethodCallFailed<'a>> {
+    let err = match result {
+        Ok(value) => return Ok(value),
+        Err(err) => err,
+    };
+    let class_name = match err.kind {
+        NetErrorKind::ConnectionRefused => "java/net/ConnectException",
+        NetErrorKind::TimedOut => "java/net/SocketTimeoutException",
+        NetErrorKind::AddressInUse => "java/net/BindException",
+        NetErrorKind::Other => "java/net/SocketException",
+    };
+    throw_exception(vm, call_stack, class_name, &err.message)
+}
+
+/// Replaces the message of a timeout with the one of the JDK
+fn timed_out_as(err: NetError, message: &str) -> NetError {
+    match err.kind {
+        NetErrorKind::TimedOut => NetError::new(NetErrorKind::TimedOut, message),
+        _ => err,
+    }
+}
+
+fn descriptor_number<'a>(vm: &Vm<'a>, descriptor: &AbstractObject<'a>) -> Result<i32, VmError> {
+    match get_object_field(vm, descriptor, "fd")? {
+        Value::Int(number) => Ok(number),
+        _ => Err(VmError::ValidationException),
+    }
+}
+
+/// Returns the socket of the given `FileDescriptor`, throwing if it has been closed
+fn socket_of_descriptor<'a>(
+    vm: &mut Vm<'a>,
+    call_stack: &mut CallStack<'a>,
+    descriptor: &AbstractObject<'a>,
+) -> Result<SocketHandle, MethodCallFailed<'a>> {
+    let number = descriptor_number(vm, descriptor)?;
+    match vm.open_sockets.get(number) {
+        Some(socket) => Ok(socket),
+        None => throw_exception(vm, call_stack, "java/net/SocketException", "Socket closed"),
+    }
+}
+
+/// Returns the socket of the given `SocketImpl`, throwing if it has been closed
+fn socket_of<'a>(
+    vm: &mut Vm<'a>,
+    call_stack: &mut CallStack<'a>,
+    socket_impl: &AbstractObject<'a>,
+) -> Result<SocketHandle, MethodCallFailed<'a>> {
+    match get_object_field(vm, socket_impl, "fd")? {
+        Value::Object(descriptor) => socket_of_descriptor(vm, call_stack, &descriptor),
+        _ => throw_exception(vm, call_stack, "java/net/SocketException", "Socket closed"),
+    }
+}
+
+/// Stores a new socket in the `FileDescriptor` of the given `SocketImpl`, and sets the local
+/// port of the latter
+fn register_socket<'a>(
+    vm: &mut Vm<'a>,
+    call_stack: &mut CallStack<'a>,
+    socket_impl: &AbstractObject<'a>,
+    socket: SocketHandle,
+) -> Result<(), MethodCallFailed<'a>> {
+    let number = vm.open_sockets.insert(socket);
+    let Value::Object(descriptor) = get_object_field(vm, socket_impl, "fd")? else {
+        return Err(VmError::NullPointerException.into());
+    };
+    set_object_field(vm, &descriptor, "fd", Value::Int(number))?;
+    let result = vm.net().local_address(socket);
+    let local_address = net_result(vm, call_stack, result)?;
+    set_object_field(
+        vm,
+        socket_impl,
+        "localport",
+        Value::Int(local_address.port().into()),
+    )?;
+    Ok(())
+}
+
+/// Returns the address made of the `InetAddress` and port passed as first two arguments
+fn socket_address<'a>(
+    vm: &mut Vm<'a>,
+    call_stack: &mut CallStack<'a>,
+    args: &[Value<'a>],
+) -> Result<SocketAddr, MethodCallFailed<'a>> {
+    let address = expect_abstract_object_at(args, 0)?;
+    let port = u16::try_from(expect_int_at(args, 1)?).map_err(|_| VmError::ValidationException)?;
+    let Some(Value::Object(bytes)) =
+        vm.invoke_virtual(call_stack, address, "getAddress", "()[B", Vec::new())?
+    else {
+        return Err(VmError::ValidationException.into());
+    };
+    let bytes = bytes_of_byte_array(&bytes, 0, bytes.len().into_usize_safe())?;
+    let ip = if let Ok(octets) = <[u8; 4]>::try_from(bytes.as_slice()) {
+        IpAddr::V4(Ipv4Addr::from(octets))
+    } else if let Ok(octets) = <[u8; 16]>::try_from(bytes.as_slice()) {
+        IpAddr::V6(Ipv6Addr::from(octets))
+    } else {
+        return Err(VmError::ValidationException.into());
+    };
+    Ok(SocketAddr::new(ip, port))
+}
+
+/// Creates an `Inet4Address` or an `Inet6Address`, with the given host name if any
+fn new_inet_address<'a>(
+    vm: &mut Vm<'a>,
+    call_stack: &mut CallStack<'a>,
+    host: Option<&str>,
+    ip: IpAddr,
+) -> Result<AbstractObject<'a>, MethodCallFailed<'a>> {
+    let (class_name, octets) = match ip {
+        IpAddr::V4(ip) => (INET4_ADDRESS, ip.octets().to_vec()),
+        IpAddr::V6(ip) => (INET6_ADDRESS, ip.octets().to_vec()),
+    };
+    let scope = vm.handle_scope();
+    let host = match host {
+        Some(host) => scope
+            .handle(new_java_lang_string_object(vm, call_stack, host)?)
+            .value(),
+        None => Value::Null,
+    };
+    let octets = scope.handle(vm.new_byte_array(&octets)?);
+    let address = scope.handle(vm.new_object(call_stack, class_name)?);
+    let constructor =
+        vm.resolve_class_method(call_stack, class_name, "<init>", "(Ljava/lang/String;[B)V")?;
+    vm.invoke(
+        call_stack,
+        constructor,
+        Some(address.get()),
+        vec![host, octets.value()],
+    )?;
+    Ok(address.get())
+}
+
+fn new_inet_address_array<'a>(
+    vm: &mut Vm<'a>,
+    call_stack: &mut CallStack<'a>,
+    host: &str,
+    ips: &[IpAddr],
+) -> Result<AbstractObject<'a>, MethodCallFailed<'a>> {
+    let class_id = vm.get_or_resolve_class(call_stack, INET_ADDRESS)?.id;
+    let scope = vm.handle_scope();
+    let array = scope.handle(vm.new_array(ArrayEntryType::Object(class_id), ips.len())?);
+    for (index, ip) in ips.iter().enumerate() {
+        let address = Value::Object(new_inet_address(vm, call_stack, Some(host), *ip)?);
+        vm.write_barrier(&address);
+        array.get().set_element(index, address)?;
+    }
+    Ok(array.get())
+}
+
+/// Waits for a connection on the listening socket, and stores it in the `SocketImpl`
+/// passed as argument
+fn accept<'a>(
+    vm: &mut Vm<'a>,
+    call_stack: &mut CallStack<'a>,
+    listener_impl: &AbstractObject<'a>,
+    args: &[Value<'a>],
+) -> MethodCallResult<'a> {
+    let listener = socket_of(vm, call_stack, listener_impl)?;
+    let Value::Int(timeout_millis) = get_object_field(vm, listener_impl, "timeout")? else {
+        return Err(VmError::ValidationException.into());
+    };
+    let result = vm
+        .net()
+        .accept(listener, timeout(timeout_millis))
+        .map_err(|err| timed_out_as(err, "Accept timed out"));
+    let (socket, peer) = net_result(vm, call_stack, result)?;
+
+    let scope = vm.handle_scope();
+    let socket_impl = scope.handle(expect_abstract_object_at(args, 0)?);
+    let peer_address = new_inet_address(vm, call_stack, None, peer.ip())?;
+    set_object_field(
+        vm,
+        &socket_impl.get(),
+        "address",
+        Value::Object(peer_address),
+    )?;
+    set_object_field(
+        vm,
+        &socket_impl.get(),
+        "port",
+        Value::Int(peer.port().into()),
+    )?;
+    register_socket(vm, call_stack, &socket_impl.get(), socket)?;
+    Ok(None)
+}
+
+/// Implements `socketRead0`, returning -1 when the peer has closed the connection
+fn read<'a>(
+    vm: &mut Vm<'a>,
+    call_stack: &mut CallStack<'a>,
+    args: &[Value<'a>],
+) -> MethodCallResult<'a> {
+    let descriptor = expect_abstract_object_at(args, 0)?;
+    let socket = socket_of_descriptor(vm, call_stack, &descriptor)?;
+    let array = expect_abstract_object_at(args, 1)?;
+    let Some((offset, length)) =
+        checked_range(&array, expect_int_at(args, 2)?, expect_int_at(args, 3)?)
+    else {
+        return throw_exception(
+            vm,
+            call_stack,
+            "java/lang/ArrayIndexOutOfBoundsException",
+            "",
+        );
+    };
+    let timeout = timeout(expect_int_at(args, 4)?);
+    let mut buffer = vec![0; length.min(MAX_READ_LENGTH)];
+    let result = vm
+        .net()
+        .read_socket(socket, &mut buffer, timeout)
+        .map_err(|err| timed_out_as(err, "Read timed out"));
+    let read = net_result(vm, call_stack, result)?.min(buffer.len());
+    if read == 0 {
+        return Ok(Some(Value::Int(-1)));
+    }
+    copy_into_byte_array(&array, offset, &buffer[..read])?;
+    Ok(Some(Value::Int(i32::try_from(read).unwrap_or(i32::MAX))))
+}
@@ -0,0 +1,527 @@
+use rjvm_reader::type_conversion::ToUsizeSafe;
+
+use crate::{
+    abstract_object::AbstractObject,
+    array::Array,
+    call_frame::MethodCallResult,
+    call_stack::CallStack,
+    exceptions::MethodCallFailed,
+    file_io::{bytes_of_byte_array, checked_range, copy_into_byte_array},
+    floating_decimal::{double_to_string, float_to_string},
+    java_objects_creation::{extract_str_from_java_lang_string, throw_exception},
+    native_methods_registry::{NativeCallback, NativeMethodsRegistry},
+    value::{
+        expect_abstract_object_at, expect_double_at, expect_float_at, expect_int_at,
+        expect_long_at, Value,
+    },
+    vm::Vm,
+    vm_error::VmError,
+};
+
+const SYSTEM: &str = "java/lang/System";
+const PRINT_STREAM: &str = "java/io/PrintStream";
+const FILTER_INPUT_STREAM: &str = "java/io/FilterInputStream";
+
+/// The standard streams of the java code, each written to an
+/// [OutputSink](crate::io::OutputSink) of the vm
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum StandardStream {
+    Out,
+    Err,
+}
+
+/// The `PrintStream` instances of `System.out` and `System.err`, and the `InputStream` of
+/// `System.in`.
+///
+/// The JVM creates them in `System.initializeSystemClass`, on top of a `FileOutputStream`
+/// and of the charset encoders, whose initialization our rt.jar cannot run since it needs
+/// `sun.misc.Unsafe`. Thus, the vm allocates the instances without invoking their
+/// constructor, and replaces the methods of `PrintStream` with intrinsics that write to the
+/// sinks of the vm, encoding the text in UTF-8. Note that `printf` and `format` are not
+/// supported, and that no other `PrintStream` can be created.
+///
+/// Likewise, `System.in` is a `FilterInputStream` without an underlying stream, whose methods
+/// are replaced with intrinsics that read from the [JvmIo](crate::io::JvmIo) of the vm.
+/// Since the other instances of `FilterInputStream`, such as a `DataInputStream` wrapping
+/// `System.in`, must keep working, the intrinsics apply only to `System.in`. It can be read
+/// only as bytes, since the readers that decode text, such as `InputStreamReader` or
+/// `Scanner`, need the charset decoders, and it does not support `mark` and `reset`.
+#[derive(Debug, Default)]
+pub(crate) struct StandardStreams<'a> {
+    out: Option<AbstractObject<'a>>,
+    err: Option<AbstractObject<'a>>,
+    input: Option<AbstractObject<'a>>,
+}
+
+impl<'a> StandardStreams<'a> {
+    /// Returns which stream the given `PrintStream` is
+    fn stream_of(&self, print_stream: &AbstractObject<'a>) -> Option<StandardStream> {
+        let is = |stream: &Option<AbstractObject<'a>>| {
+            stream
+                .as_ref()
+                .is_some_and(|stream| stream.is_same_as(print_stream))
+        };
+        if is(&self.out) {
+            Some(StandardStream::Out)
+        } else if is(&self.err) {
+            Some(StandardStream::Err)
+        } else {
+            None
+        }
+    }
+
+    fn is_input(&self, input_stream: &AbstractObject<'a>) -> bool {
+        self.input
+            .as_ref()
+            .is_some_and(|stream| stream.is_same_as(input_stream))
+    }
+
+    /// The instances must be kept alive even if the java code replaces them with
+    /// `System.setOut` or its siblings, since it could keep a reference to them
+    pub fn gc_roots(&mut self) -> impl Iterator<Item = *mut AbstractObject<'a>> + '_ {
+        self.out
+            .iter_mut()
+            .chain(self.err.iter_mut())
+            .chain(self.input.iter_mut())
+            .map(|object| object as *mut AbstractObject<'a>)
+    }
+}
+
+/// Creates `System.in`, `System.out` and `System.err`. Invoked after the initialization of `System`,
+/// which sets them to null.
+pub(crate) fn initialize_standard_streams<'a>(
+    vm: &mut Vm<'a>,
+    call_stack: &mut CallStack<'a>,
+) -> Result<(), MethodCallFailed<'a>> {
+    let out = vm.new_object(call_stack, PRINT_STREAM)?;
+    vm.standard_streams.out = Some(out.clone());
+    vm.set_static_field(SYSTEM, "out", Value::Object(out))?;
+
+    let err = vm.new_object(call_stack, PRINT_STREAM)?;
+    vm.standard_streams.err = Some(err.clone());
+    vm.set_static_field(SYSTEM, "err", Value::Object(err))?;
+
+    let input = vm.new_object(call_stack, FILTER_INPUT_STREAM)?;
+    vm.standard_streams.input = Some(input.clone());
+    vm.set_static_field(SYSTEM, "in", Value::Object(input))?;
+    Ok(())
+}
+
+pub(crate) fn register_standard_streams_methods(registry: &mut NativeMethodsRegistry) {
+    registry.register(
+        SYSTEM,
+        "setIn0",
+        "(Ljava/io/InputStream;)V",
+        |vm, _, _, args| set_stream(vm, "in", args),
+    );
+    registry.register(
+        SYSTEM,
+        "setOut0",
+        "(Ljava/io/PrintStream;)V",
+        |vm, _, _, args| set_stream(vm, "out", args),
+    );
+    registry.register(
+        SYSTEM,
+        "setErr0",
+        "(Ljava/io/PrintStream;)V",
+        |vm, _, _, args| set_stream(vm, "err", args),
+    );
+
+    register_print_methods(registry);
+    register_input_methods(registry);
+    registry.register_intrinsic(PRINT_STREAM, "write", "(I)V", |vm, _, receiver, args| {
+        let stream = expect_standard_stream(vm, receiver)?;
+        let byte = expect_int_at(&args, 0)?.to_le_bytes()[0];
+        vm.output_sink(stream).write(&[byte]);
+        Ok(None)
+    });
+    registry.register_intrinsic(
+        PRINT_STREAM,
+        "write",
+        "([BII)V",
+        |vm, stack, receiver, args| write_bytes(vm, stack, receiver, &args),
+    );
+    registry.register_intrinsic(PRINT_STREAM, "flush", "()V", |vm, _, receiver, _| {
+        let stream = expect_standard_stream(vm, receiver)?;
+        vm.output_sink(stream).flush();
+        Ok(None)
+    });
+    registry.register_intrinsic(PRINT_STREAM, "close", "()V", |vm, _, receiver, _| {
+        let stream = expect_standard_stream(vm, receiver)?;
+        vm.output_sink(stream).flush();
+        Ok(None)
+    });
+    registry.register_intrinsic(PRINT_STREAM, "checkError", "()Z", |vm, _, receiver, _| {
+        expect_standard_stream(vm, receiver)?;
+        Ok(Some(Value::Int(0)))
+    });
+    registry.register_intrinsic(
+        PRINT_STREAM,
+        "append",
+        "(C)Ljava/io/PrintStream;",
+        |vm, _, receiver, args| {
+            write_text(vm, receiver.clone(), &char_text(&args)?, false)?;
+            Ok(receiver.map(Value::Object))
+        },
+    );
+    registry.register_intrinsic(
+        PRINT_STREAM,
+        "append",
+        "(Ljava/lang/CharSequence;)Ljava/io/PrintStream;",
+        |vm, stack, receiver, args| {
+            let text = object_text(vm, stack, &args)?;
+            write_text(vm, receiver.clone(), &text, false)?;
+            Ok(receiver.map(Value::Object))
+        },
+    );
+}
+
+/// Registers `print` and `println` for each type of argument. Note that `boolean` and
+/// `char` arguments are passed as `Value::Int`.
+fn register_print_methods(registry: &mut NativeMethodsRegistry) {
+    let print_methods: [(&str, NativeCallback); 9] = [
+        ("(Z)V", |vm, _, receiver, args| {
+            write_text(vm, receiver, &boolean_text(&args)?, false)
+        }),
+        ("(C)V", |vm, _, receiver, args| {
+            write_text(vm, receiver, &char_text(&args)?, false)
+        }),
+        ("(I)V", |vm, _, receiver, args| {
+            write_text(vm, receiver, &int_text(&args)?, false)
+        }),
+        ("(J)V", |vm, _, receiver, args| {
+            write_text(vm, receiver, &long_text(&args)?, false)
+        }),
+        ("(F)V", |vm, _, receiver, args| {
+            write_text(vm, receiver, &float_text(&args)?, false)
+        }),
+        ("(D)V", |vm, _, receiver, args| {
+            write_text(vm, receiver, &double_text(&args)?, false)
+        }),
+        ("([C)V", |vm, _, receiver, args| {
+            write_text(vm, receiver, &char_array_text(&args)?, false)
+        }),
+        ("(Ljava/lang/String;)V", |vm, _, receiver, args| {
+            let text = string_text(vm, &args)?;
+            write_text(vm, receiver, &text, false)
+        }),
+        ("(Ljava/lang/Object;)V", |vm, stack, receiver, args| {
+            let text = object_text(vm, stack, &args)?;
+            write_text(vm, receiver, &text, false)
+        }),
+    ];
+    let println_methods: [(&str, NativeCallback); 10] = [
+        ("()V", |vm, _, receiver, _| {
+            write_text(vm, receiver, "", true)
+        }),
+        ("(Z)V", |vm, _, receiver, args| {
+            write_text(vm, receiver, &boolean_text(&args)?, true)
+        }),
+        ("(C)V", |vm, _, receiver, args| {
+            write_text(vm, receiver, &char_text(&args)?, true)
+        }),
+        ("(I)V", |vm, _, receiver, args| {
+            write_text(vm, receiver, &int_text(&args)?, true)
+        }),
+        ("(J)V", |vm, _, receiver, args| {
+            write_text(vm, receiver, &long_text(&args)?, true)
+        }),
+        ("(F)V", |vm, _, receiver, args| {
+            write_text(vm, receiver, &float_text(&args)?, true)
+        }),
+        ("(D)V", |vm, _, receiver, args| {
+            write_text(vm, receiver, &double_text(&args)?, true)
+        }),
+        ("([C)V", |vm, _, receiver, args| {
+            write_text(vm, receiver, &char_array_text(&args)?, true)
+        }),
+        ("(Ljava/lang/String;)V", |vm, _, receiver, args| {
+            let text = string_text(vm, &args)?;
+            write_text(vm, receiver, &text, true)
+        }),
+        ("(Ljava/lang/Object;)V", |vm, stack, receiver, args| {
+            let text = object_text(vm, stack, &args)?;
+            write_text(vm, receiver, &text, true)
+        }),
+    ];
+    let methods = print_methods
+        .into_iter()
+        .map(|(descriptor, callback)| ("print", descriptor, callback))
+        .chain(
+            println_methods
+                .into_iter()
+                .map(|(descriptor, callback)| ("println", descriptor, callback)),
+        );
+    for (name, descriptor, callback) in methods {
+        registry.register_intrinsic(PRINT_STREAM, name, descriptor, callback);
+    }
+}
+
+/// Registers the methods of `FilterInputStream` used to read `System.in`. The overload
+/// `read(byte[])` delegates to `read(byte[], int, int)`.
+fn register_input_methods(registry: &mut NativeMethodsRegistry) {
+    registry.register_intrinsic_for_receivers(
+        FILTER_INPUT_STREAM,
+        "read",
+        "()I",
+        is_standard_input,
+        |vm, stack, _, _| {
+            let mut byte = [0];
+            let read = read_input(vm, stack, &mut byte)?;
+            Ok(Some(Value::Int(if read == 0 {
+                -1
+            } else {
+                byte[0].into()
+            })))
+        },
+    );
+    registry.register_intrinsic_for_receivers(
+        FILTER_INPUT_STREAM,
+        "read",
+        "([BII)I",
+        is_standard_input,
+        |vm, stack, _, args| read_bytes(vm, stack, &args),
+    );
+    registry.register_intrinsic_for_receivers(
+        FILTER_INPUT_STREAM,
+        "skip",
+        "(J)J",
+        is_standard_input,
+        |vm, stack, _, args| {
+            let to_skip = usize::try_from(expect_long_at(&args, 0)?).unwrap_or(0);
+            let mut buffer = vec![0; to_skip.min(SKIP_BUFFER_SIZE)];
+            let mut skipped = 0;
+            while skipped < to_skip {
+                let chunk = buffer.len().min(to_skip - skipped);
+                let read = read_input(vm, stack, &mut buffer[..chunk])?;
+                if read == 0 {
+                    break;
+                }
+                skipped += read;
+            }
+            Ok(Some(Value::Long(
+                i64::try_from(skipped).unwrap_or(i64::MAX),
+            )))
+        },
+    );
+    registry.register_intrinsic_for_receivers(
+        FILTER_INPUT_STREAM,
+        "available",
+        "()I",
+        is_standard_input,
+        // We cannot know without blocking, which the JDK allows to report as zero
+        |_, _, _, _| Ok(Some(Value::Int(0))),
+    );
+    registry.register_intrinsic_for_receivers(
+        FILTER_INPUT_STREAM,
+        "markSupported",
+        "()Z",
+        is_standard_input,
+        |_, _, _, _| Ok(Some(Value::Int(0))),
+    );
+    registry.register_intrinsic_for_receivers(
+        FILTER_INPUT_STREAM,
+        "mark",
+        "(I)V",
+        is_standard_input,
+        |_, _, _, _| Ok(None),
+    );
+    registry.register_intrinsic_for_receivers(
+        FILTER_INPUT_STREAM,
+        "reset",
+        "()V",
+        is_standard_input,
+        |vm, stack, _, _| {
+            throw_exception(vm, stack, "java/io/IOException", "mark/reset not supported")
+        },
+    );
+    registry.register_intrinsic_for_receivers(
+        FILTER_INPUT_STREAM,
+        "close",
+        "()V",
+        is_standard_input,
+        |_, _, _, _| Ok(None),
+    );
+}
+
+/// The maximum number of bytes that `skip` reads at once
+const SKIP_BUFFER_SIZE: usize = 4096;
+
+fn set_stream<'a>(vm: &mut Vm<'a>, field_name: &str, args: Vec<Value<'a>>) -> MethodCallResult<'a> {
+    let stream = args
+        .into_iter()
+        .next()
+        .ok_or(VmError::ValidationException)?;
+    vm.set_static_field(SYSTEM, field_name, stream)?;
+    Ok(None)
+}
+
+/// Returns the stream that the receiver writes to. Since the constructors of `PrintStream`
+/// cannot run, the receiver is always one of the instances created by the vm.
+fn expect_standard_stream<'a>(
+    vm: &Vm<'a>,
+    receiver: Option<AbstractObject<'a>>,
+) -> Result<StandardStream, VmError> {
+    receiver
+        .and_then(|receiver| vm.standard_streams.stream_of(&receiver))
+        .ok_or(VmError::NotImplemented)
+}
+
+fn is_standard_input<'a>(vm: &Vm<'a>, receiver: &AbstractObject<'a>) -> bool {
+    vm.standard_streams.is_input(receiver)
+}
+
+/// Reads from the standard input of the vm, returning zero at the end of the input
+fn read_input<'a>(
+    vm: &mut Vm<'a>,
+    call_stack: &mut CallStack<'a>,
+    buffer: &mut [u8],
+) -> Result<usize, MethodCallFailed<'a>> {
+    match vm.io().read_stdin(buffer) {
+        Ok(read) => Ok(read.min(buffer.len())),
+        Err(message) => throw_exception(vm, call_stack, "java/io/IOException", &message),
+    }
+}
+
+/// Implements `read(byte[], int, int)`, with the same checks as the JDK
+fn read_bytes<'a>(
+    vm: &mut Vm<'a>,
+    call_stack: &mut CallStack<'a>,
+    args: &[Value<'a>],
+) -> MethodCallResult<'a> {
+    let array = expect_abstract_object_at(args, 0)?;
+    let Some((offset, length)) =
+        checked_range(&array, expect_int_at(args, 1)?, expect_int_at(args, 2)?)
+    else {
+        return throw_exception(vm, call_stack, "java/lang/IndexOutOfBoundsException", "");
+    };
+    if length == 0 {
+        return Ok(Some(Value::Int(0)));
+    }
+    let mut buffer = vec![0; length];
+    let read = read_input(vm, call_stack, &mut buffer)?;
+    if read == 0 {
+        return Ok(Some(Value::Int(-1)));
+    }
+    copy_into_byte_array(&array, offset, &buffer[..read])?;
+    Ok(Some(Value::Int(i32::try_from(read).unwrap_or(i32::MAX))))
+}
+
+/// Writes the given text, followed by the line separator if `new_line` is set, flushing
+/// the sink in that case like an auto-flushing `PrintStream` does
+fn write_text<'a>(
+    vm: &mut Vm<'a>,
+    receiver: Option<AbstractObject<'a>>,
+    text: &str,
+    new_line: bool,
+) -> MethodCallResult<'a> {
+    let stream = expect_standard_stream(vm, receiver)?;
+    if new_line {
+        let separator = vm
+            .system_property("line.separator")
+            .unwrap_or("\n")
+            .to_string();
+        let sink = vm.output_sink(stream);
+        sink.write(text.as_bytes());
+        sink.write(separator.as_bytes());
+        sink.flush();
+    } else {
+        vm.output_sink(stream).write(text.as_bytes());
+    }
+    Ok(None)
+}
+
+/// Implements `write(byte[], int, int)`, with the same checks as the JDK
+fn write_bytes<'a>(
+    vm: &mut Vm<'a>,
+    call_stack: &mut CallStack<'a>,
+    receiver: Option<AbstractObject<'a>>,
+    args: &[Value<'a>],
+) -> MethodCallResult<'a> {
+    let stream = expect_standard_stream(vm, receiver)?;
+    let array = expect_abstract_object_at(args, 0)?;
+    let Some((offset, length)) =
+        checked_range(&array, expect_int_at(args, 1)?, expect_int_at(args, 2)?)
+    else {
+        return throw_exception(vm, call_stack, "java/lang/IndexOutOfBoundsException", "");
+    };
+    let bytes = bytes_of_byte_array(&array, offset, length)?;
+    vm.output_sink(stream).write(&bytes);
+    Ok(None)
+}
+
+fn boolean_text(args: &[Value]) -> Result<String, VmError> {
+    let value = expect_int_at(args, 0)? != 0;
+    Ok(value.to_string())
+}
+
+fn char_text(args: &[Value]) -> Result<String, VmError> {
+    Ok(char_to_string(expect_int_at(args, 0)?))
+}
+
+fn int_text(args: &[Value]) -> Result<String, VmError> {
+    Ok(expect_int_at(args, 0)?.to_string())
+}
+
+fn long_text(args: &[Value]) -> Result<String, VmError> {
+    Ok(expect_long_at(args, 0)?.to_string())
+}
+
+fn float_text(args: &[Value]) -> Result<String, VmError> {
+    Ok(float_to_string(expect_float_at(args, 0)?))
+}
+
+fn double_text(args: &[Value]) -> Result<String, VmError> {
+    Ok(double_to_string(expect_double_at(args, 0)?))
+}
+
+/// Unlike the other overloads, `print(char[])` throws a `NullPointerException` for null
+fn char_array_text(args: &[Value]) -> Result<String, VmError> {
+    let array = expect_abstract_object_at(args, 0)?;
+    let chars = (0..array.len().into_usize_safe())
+        .map(|index| match array.get_element(index)? {
+            Value::Int(char) => u16::try_from(char).map_err(|_| VmError::ValidationException),
+            _ => Err(VmError::ValidationException),
+        })
+        .collect::<Result<Vec<_>, VmError>>()?;
+    Ok(utf16_to_string(chars))
+}
+
+fn string_text<'a>(vm: &Vm<'a>, args: &[Value<'a>]) -> Result<String, VmError> {
+    match args.first() {
+        Some(Value::Object(string)) => extract_str_from_java_lang_string(vm, string),
+        _ => Ok("null".to_string()),
+    }
+}
+
+/// Converts the argument to a string as `String.valueOf(Object)` does
+fn object_text<'a>(
+    vm: &mut Vm<'a>,
+    call_stack: &mut CallStack<'a>,
+    args: &[Value<'a>],
+) -> Result<String, MethodCallFailed<'a>> {
+    let Some(Value::Object(object)) = args.first() else {
+        return Ok("null".to_string());
+    };
+    let string = vm.invoke_virtual(
+        call_stack,
+        object.clone(),
+        "toString",
+        "()Ljava/lang/String;",
+        Vec::new(),
+    )?;
+    Ok(string_text(vm, &[string.unwrap_or(Value::Null)])?)
+}
+
+fn char_to_string(value: i32) -> String {
+    let unit = u16::try_from(value).unwrap_or_default();
+    utf16_to_string([unit])
+}
+
+/// Decodes the given utf-16 code units. Like the JDK's UTF-8 encoder, we replace the unpaired
+/// surrogates with a question mark.
+fn utf16_to_string(units: impl IntoIterator<Item = u16>) -> String {
+    char::decode_utf16(units)
+        .map(|char| char.unwrap_or('?'))
+        .collect()
+}
@@ -0,0 +1,168 @@
+use std::collections::HashMap;
+
+use crate::{
+    call_frame::MethodCallResult,
+    call_stack::CallStack,
+    exceptions::MethodCallFailed,
+    java_objects_creation::{
+        extract_str_from_java_lang_string, new_java_lang_string_object, throw_exception,
+    },
+    native_methods_registry::NativeMethodsRegistry,
+    value::Value,
+    vm::Vm,
+};
+
+const SYSTEM: &str = "java/lang/System";
+
+/// The properties that every vm starts with. They describe the host the vm is running on,
+/// and can be overridden by the embedder.
+pub(crate) fn default_system_properties() -> HashMap<String, String> {
+    let windows = cfg!(windows);
+    let os_name = match std::env::consts::OS {
+        "linux" => "Linux",
+        "macos" => "Mac OS X",
+        "windows" => "Windows",
+        other => other,
+    };
+    let os_arch = match std::env::consts::ARCH {
+        "x86_64" => "amd64",
+        other => other,
+    };
+    [
+        ("java.version", "1.7.0"),
+        ("java.vendor", "rjvm"),
+        ("java.vm.name", "rjvm"),
+        ("java.specification.version", "1.7"),
+        ("java.class.version", "51.0"),
+        ("os.name", os_name),
+        ("os.arch", os_arch),
+        ("file.separator", if windows { "\\" } else { "/" }),
+        ("path.separator", if windows { ";" } else { ":" }),
+        ("line.separator", if windows { "\r\n" } else { "\n" }),
+        ("file.encoding", "UTF-8"),
+    ]
+    .into_iter()
+    .map(|(name, value)| (name.to_string(), value.to_string()))
+    .collect()
+}
+
+/// Our rt.jar stores the properties in a `java.util.Properties`, filled by the initialization
+/// of `System` that we do not run. Thus, we replace the methods that access them with
+/// intrinsics that use the properties of the vm. Note that `System.getProperties` and
+/// `System.setProperties` are not supported.
+pub(crate) fn register_system_properties_methods(registry: &mut NativeMethodsRegistry) {
+    registry.register_intrinsic(
+        SYSTEM,
+        "getProperty",
+        "(Ljava/lang/String;)Ljava/lang/String;",
+        |vm, stack, _, args| get_property(vm, stack, &args, Value::Null),
+    );
+    registry.register_intrinsic(
+        SYSTEM,
+        "getProperty",
+        "(Ljava/lang/String;Ljava/lang/String;)Ljava/lang/String;",
+        |vm, stack, _, args| {
+            let default = args.get(1).cloned().unwrap_or(Value::Null);
+            get_property(vm, stack, &args, default)
+        },
+    );
+    registry.register_intrinsic(
+        SYSTEM,
+        "setProperty",
+        "(Ljava/lang/String;Ljava/lang/String;)Ljava/lang/String;",
+        |vm, stack, _, args| set_property(vm, stack, &args),
+    );
+    registry.register_intrinsic(
+        SYSTEM,
+        "clearProperty",
+        "(Ljava/lang/String;)Ljava/lang/String;",
+        |vm, stack, _, args| {
+            let key = property_key(vm, stack, &args)?;
+            let previous = vm.remove_system_property(&key);
+            optional_string(vm, stack, previous.as_deref())
+        },
+    );
+    registry.register_intrinsic(
+        SYSTEM,
+        "lineSeparator",
+        "()Ljava/lang/String;",
+        |vm, stack, _, _| {
+            let separator = vm
+                .system_property("line.separator")
+                .unwrap_or("\n")
+                .to_string();
+            optional_string(vm, stack, Some(&separator))
+        },
+    );
+}
+
+fn get_property<'a>(
+    vm: &mut Vm<'a>,
+    call_stack: &mut CallStack<'a>,
+    args: &[Value<'a>],
+    default: Value<'a>,
+) -> MethodCallResult<'a> {
+    let key = property_key(vm, call_stack, args)?;
+    match vm.system_property(&key).map(str::to_string) {
+        Some(value) => optional_string(vm, call_stack, Some(&value)),
+        None => Ok(Some(default)),
+    }
+}
+
+fn set_property<'a>(
+    vm: &mut Vm<'a>,
+    call_stack: &mut CallStack<'a>,
+    args: &[Value<'a>],
+) -> MethodCallResult<'a> {
+    let key = property_key(vm, call_stack, args)?;
+    let value = match args.get(1) {
+        Some(Value::Object(value)) => extract_str_from_java_lang_string(vm, value)?,
+        _ => return throw_exception(vm, call_stack, "java/lang/NullPointerException", ""),
+    };
+    let previous = vm.system_property(&key).map(str::to_string);
+    vm.set_system_property(&key, &value);
+    optional_string(vm, call_stack, previous.as_deref())
+}
+
+/// Extracts the key passed as first argument, throwing the same exceptions as
+/// `System.checkKey` if it is null or empty
+fn property_key<'a>(
+    vm: &mut Vm<'a>,
+    call_stack: &mut CallStack<'a>,
+    args: &[Value<'a>],
+) -> Result<String, MethodCallFailed<'a>> {
+    let key = match args.first() {
+        Some(Value::Object(key)) => extract_str_from_java_lang_string(vm, key)?,
+        _ => {
+            return throw_exception(
+                vm,
+                call_stack,
+                "java/lang/NullPointerException",
+                "key can't be null",
+            )
+        }
+    };
+    if key.is_empty() {
+        return throw_exception(
+            vm,
+            call_stack,
+            "java/lang/IllegalArgumentException",
+            "key can't be empty",
+        );
+    }
+    Ok(key)
+}
+
+fn optional_string<'a>(
+    vm: &mut Vm<'a>,
+    call_stack: &mut CallStack<'a>,
+    value: Option<&str>,
+) -> MethodCallResult<'a> {
+    match value {
+        Some(value) => {
+            let string = new_java_lang_string_object(vm, call_stack, value)?;
+            Ok(Some(Value::Object(string)))
+        }
+        None => Ok(Some(Value::Null)),
+    }
+}
@@ -0,0 +1,524 @@
+use std::{fmt, fmt::Formatter, time::Instant};
+
+use crate::{
+    abstract_object::AbstractObject, call_frame::MethodCallResult, call_stack::CallStack, vm::Vm,
+};
+
+/// The default number of switch points, i.e. method invocations and backward branches,
+/// that a thread executes before the scheduler lets the next one run
+pub const DEFAULT_THREAD_TIME_SLICE: usize = 10_000;
+
+/// The values of `java.lang.Thread.threadStatus` used by the vm, as defined by JVMTI
+pub(crate) const THREAD_STATUS_RUNNABLE: i32 = 0x5;
+pub(crate) const THREAD_STATUS_TERMINATED: i32 = 0x2;
+
+/// The rest of a native method whose thread was suspended, e.g. `Object.wait` once the
+/// thread has been notified: it runs when the scheduler resumes the thread, and its result
+/// is the one of the native method
+pub(crate) type NativeContinuation<'a> =
+    Box<dyn FnOnce(&mut Vm<'a>, &mut CallStack<'a>) -> MethodCallResult<'a> + 'a>;
+
+/// Identifies a java thread. The main thread, i.e. the one on which the embedder invokes
+/// the vm, has id 0; the threads started by the java code get increasing ids.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ThreadId(u32);
+
+impl ThreadId {
+    pub const MAIN: ThreadId = ThreadId(0);
+
+    pub fn new(id: u32) -> Self {
+        Self(id)
+    }
+
+    pub fn as_u32(&self) -> u32 {
+        self.0
+    }
+}
+
+impl fmt::Display for ThreadId {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// What a started thread executes when the scheduler resumes it without frames on its call
+/// stack
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ThreadPhase {
+    /// The `run` method has not been invoked yet
+    Run,
+    /// The `run` method has completed: `Thread.exit` must be invoked
+    Exit,
+}
+
+/// A java thread that has been started and has not terminated yet
+struct GreenThread<'a> {
+    id: ThreadId,
+    /// The instance of `java.lang.Thread`. For the main thread, it is created lazily,
+    /// the first time the java code asks for it.
+    thread_object: Option<AbstractObject<'a>>,
+    daemon: bool,
+    /// The call stack of a suspended thread, which the scheduler can resume. It is taken
+    /// while the thread runs, and the main thread never stores it, since only the embedder's
+    /// invocation can resume it.
+    call_stack: Option<&'a mut CallStack<'a>>,
+    phase: ThreadPhase,
+    /// The number of native methods, static initializers and other code invoking java
+    /// methods from Rust that the thread is executing. The thread can only be suspended,
+    /// leaving its frames on its call stack, when there is none; otherwise, it lets the
+    /// others run nested in its own execution.
+    pinned: usize,
+    /// Set when the thread is suspended at a switch point, which it executes again when it is
+    /// resumed: that time, the switch point is not counted, so that the thread makes progress
+    suspended_at_switch_point: bool,
+    /// The rest of the native method that suspended the thread, if any
+    continuation: Option<NativeContinuation<'a>>,
+    /// An object needed by the continuation, kept here so that the gc can update it
+    kept_object: Option<AbstractObject<'a>>,
+    /// Set while the thread is in `Object.wait` or `Thread.sleep`
+    wait: Option<WaitState<'a>>,
+    /// The interrupt status of the thread, set by `Thread.interrupt`
+    interrupted: bool,
+}
+
+/// A thread in the wait set of an object, or sleeping, which does not run until it is
+/// woken up
+struct WaitState<'a> {
+    /// The object the thread is waiting on, if any; kept here so that the gc can update it
+    object: Option<AbstractObject<'a>>,
+    /// Among the threads waiting on the same object, `notify` wakes the one that started
+    /// waiting first
+    sequence: u64,
+    deadline: Option<Instant>,
+    woken_up: bool,
+}
+
+impl<'a> GreenThread<'a> {
+    fn new(
+        id: ThreadId,
+        thread_object: Option<AbstractObject<'a>>,
+        daemon: bool,
+        call_stack: Option<&'a mut CallStack<'a>>,
+    ) -> Self {
+        Self {
+            id,
+            thread_object,
+            daemon,
+            call_stack,
+            phase: ThreadPhase::Run,
+            pinned: 0,
+            suspended_at_switch_point: false,
+            continuation: None,
+            kept_object: None,
+            wait: None,
+            interrupted: false,
+        }
+    }
+
+    fn can_run(&self, now: Instant) -> bool {
+        match &self.wait {
+            None => true,
+            Some(wait) => wait.woken_up || wait.deadline.is_some_and(|deadline| deadline <= now),
+        }
+    }
+
+    fn wait_deadline(&self) -> Option<Instant> {
+        self.wait.as_ref().and_then(|wait| wait.deadline)
+    }
+}
+
+/// Java threads are green threads: the vm decides when to switch between them, at method
+/// invocations and backward branches, and only one runs at any time, on the os thread of
+/// the embedder. Each java thread has its own [CallStack]: a thread is suspended by unwinding
+/// the interpreter up to the scheduler, leaving its frames on the call stack, from which
+/// they are resumed later.
+///
+/// A thread executing Rust code that invokes java methods, e.g. a native method or a static
+/// initializer, cannot be unwound: it is _pinned_, and it lets the others run nested in its
+/// own execution instead, until they get suspended. Thus, a pinned thread waiting for
+/// another one only makes progress when the latter can be suspended.
+pub(crate) struct ThreadScheduler<'a> {
+    /// The live threads, in round-robin order; the main thread is always the first
+    threads: Vec<GreenThread<'a>>,
+    /// The running thread
+    current: ThreadId,
+    next_id: u32,
+    time_slice: usize,
+    remaining_switch_points: usize,
+    /// Set while the main thread is stopping the daemon threads, when the program ends
+    terminating: bool,
+    /// Set while the embedder's invocation of a method on the main thread is running
+    running: bool,
+    next_wait_sequence: u64,
+}
+
+impl<'a> Default for ThreadScheduler<'a> {
+    fn default() -> Self {
+        Self {
+            threads: vec![GreenThread::new(ThreadId::MAIN, None, false, None)],
+            current: ThreadId::MAIN,
+            next_id: 1,
+            time_slice: DEFAULT_THREAD_TIME_SLICE,
+            remaining_switch_points: DEFAULT_THREAD_TIME_SLICE,
+            terminating: false,
+            running: false,
+            next_wait_sequence: 0,
+        }
+    }
+}
+
+impl<'a> fmt::Debug for ThreadScheduler<'a> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "ThreadScheduler{{threads={:?}, current={}}}",
+            self.threads.iter().map(|t| t.id).collect::<Vec<_>>(),
+            self.current
+        )
+    }
+}
+
+impl<'a> ThreadScheduler<'a> {
+    pub fn set_time_slice(&mut self, switch_points: usize) {
+        self.time_slice = switch_points.max(1);
+        self.remaining_switch_points = self.time_slice;
+    }
+
+    pub fn time_slice(&self) -> usize {
+        self.time_slice
+    }
+
+    pub fn current_id(&self) -> ThreadId {
+        self.current
+    }
+
+    pub fn current_thread_object(&self) -> Option<AbstractObject<'a>> {
+        self.current_thread().thread_object.clone()
+    }
+
+    pub fn set_current_thread_object(&mut self, thread_object: AbstractObject<'a>) {
+        self.current_thread_mut().thread_object = Some(thread_object);
+    }
+
+    pub fn has_other_threads(&self) -> bool {
+        self.threads.len() > 1
+    }
+
+    pub fn has_other_non_daemon_threads(&self) -> bool {
+        self.threads
+            .iter()
+            .any(|thread| thread.id != self.current && !thread.daemon)
+    }
+
+    pub fn is_terminating(&self) -> bool {
+        self.terminating
+    }
+
+    pub fn set_terminating(&mut self, terminating: bool) {
+        self.terminating = terminating;
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.running
+    }
+
+    pub fn set_running(&mut self, running: bool) {
+        self.running = running;
+    }
+
+    /// Whether the given instance of `java.lang.Thread` has been started and has not
+    /// terminated yet
+    pub fn is_alive(&self, thread_object: &AbstractObject<'a>) -> bool {
+        self.threads.iter().any(|thread| {
+            thread
+                .thread_object
+                .as_ref()
+                .is_some_and(|object| object.is_same_as(thread_object))
+        })
+    }
+
+    /// Registers a new thread, which will run its `run` method on the given call stack when
+    /// the scheduler first resumes it
+    pub fn add_thread(
+        &mut self,
+        thread_object: AbstractObject<'a>,
+        daemon: bool,
+        call_stack: &'a mut CallStack<'a>,
+    ) -> ThreadId {
+        let id = ThreadId(self.next_id);
+        self.next_id += 1;
+        self.threads.push(GreenThread::new(
+            id,
+            Some(thread_object),
+            daemon,
+            Some(call_stack),
+        ));
+        id
+    }
+
+    /// Removes the current thread, which has terminated
+    pub fn remove_current(&mut self) {
+        assert_ne!(
+            ThreadId::MAIN,
+            self.current,
+            "the main thread cannot be removed"
+        );
+        let current = self.current;
+        self.threads.retain(|thread| thread.id != current);
+    }
+
+    /// Counts one switch point of the current thread, returning whether its time slice
+    /// is over and the scheduler should switch to the next thread
+    pub fn count_switch_point(&mut self) -> bool {
+        if std::mem::take(&mut self.current_thread_mut().suspended_at_switch_point) {
+            return false;
+        }
+        self.remaining_switch_points -= 1;
+        if self.remaining_switch_points == 0 {
+            self.remaining_switch_points = self.time_slice;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Marks that the current thread is being suspended at a switch point; see
+    /// [count_switch_point](Self::count_switch_point)
+    pub fn suspend_current_at_switch_point(&mut self) {
+        self.current_thread_mut().suspended_at_switch_point = true;
+    }
+
+    /// Makes the given thread the current one, with a new time slice
+    pub fn switch_to(&mut self, id: ThreadId) {
+        self.current = id;
+        self.remaining_switch_points = self.time_slice;
+    }
+
+    /// The threads other than the current one, in round-robin order starting after it
+    pub fn other_threads(&self) -> Vec<ThreadId> {
+        let position = self
+            .threads
+            .iter()
+            .position(|thread| thread.id == self.current)
+            .expect("the current thread should be alive");
+        let count = self.threads.len();
+        (1..count)
+            .map(|offset| self.threads[(position + offset) % count].id)
+            .collect()
+    }
+
+    /// Whether the given thread is alive, suspended, and either can run or is being stopped
+    pub fn can_resume(&self, id: ThreadId, now: Instant, stopping: bool) -> bool {
+        self.threads
+            .iter()
+            .find(|thread| thread.id == id)
+            .is_some_and(|thread| thread.call_stack.is_some() && (stopping || thread.can_run(now)))
+    }
+
+    pub fn can_current_run(&self, now: Instant) -> bool {
+        self.current_thread().can_run(now)
+    }
+
+    /// The first deadline among the waiting threads that could run after it: the current
+    /// one, and the suspended ones
+    pub fn next_deadline(&self) -> Option<Instant> {
+        self.threads
+            .iter()
+            .filter(|thread| thread.id == self.current || thread.call_stack.is_some())
+            .filter_map(GreenThread::wait_deadline)
+            .min()
+    }
+
+    /// Takes the call stack of the given suspended thread, to resume it
+    pub fn take_call_stack(&mut self, id: ThreadId) -> &'a mut CallStack<'a> {
+        self.thread_mut(id)
+            .call_stack
+            .take()
+            .expect("the thread should be suspended")
+    }
+
+    /// Gives back the call stack of the current thread, which has been suspended
+    pub fn suspend_current(&mut self, call_stack: &'a mut CallStack<'a>) {
+        self.current_thread_mut().call_stack = Some(call_stack);
+    }
+
+    pub fn current_phase(&self) -> ThreadPhase {
+        self.current_thread().phase
+    }
+
+    pub fn set_current_phase(&mut self, phase: ThreadPhase) {
+        self.current_thread_mut().phase = phase;
+    }
+
+    /// Marks that the current thread is executing some code that cannot be suspended; see
+    /// [unpin_current](Self::unpin_current)
+    pub fn pin_current(&mut self) {
+        self.current_thread_mut().pinned += 1;
+    }
+
+    pub fn unpin_current(&mut self) {
+        self.current_thread_mut().pinned -= 1;
+    }
+
+    /// The number of nested [pin_current](Self::pin_current) of the current thread
+    pub fn current_pinned(&self) -> usize {
+        self.current_thread().pinned
+    }
+
+    pub fn set_continuation(&mut self, continuation: NativeContinuation<'a>) {
+        self.current_thread_mut().continuation = Some(continuation);
+    }
+
+    pub fn take_continuation(&mut self) -> Option<NativeContinuation<'a>> {
+        self.current_thread_mut().continuation.take()
+    }
+
+    pub fn set_kept_object(&mut self, object: AbstractObject<'a>) {
+        self.current_thread_mut().kept_object = Some(object);
+    }
+
+    pub fn take_kept_object(&mut self) -> Option<AbstractObject<'a>> {
+        self.current_thread_mut().kept_object.take()
+    }
+
+    /// Forgets what the current thread was blocked in, i.e. its continuation, the object
+    /// that it kept and its wait state, when the vm stops it
+    pub fn clear_blocking(&mut self) {
+        let thread = self.current_thread_mut();
+        thread.continuation = None;
+        thread.kept_object = None;
+        thread.wait = None;
+    }
+
+    /// Whether all the threads other than the current one are waiting without a timeout, so
+    /// that they could only be woken up by the current one
+    pub fn are_others_waiting_forever(&self) -> bool {
+        self.threads.iter().all(|thread| {
+            thread.id == self.current
+                || thread
+                    .wait
+                    .as_ref()
+                    .is_some_and(|wait| !wait.woken_up && wait.deadline.is_none())
+        })
+    }
+
+    /// Puts the current thread in the wait set of the given object, until it is notified,
+    /// interrupted, or the deadline expires. Without an object, the thread sleeps until the
+    /// deadline or an interrupt. The scheduler does not resume the thread until then.
+    pub fn start_waiting(&mut self, object: Option<AbstractObject<'a>>, deadline: Option<Instant>) {
+        let sequence = self.next_wait_sequence;
+        self.next_wait_sequence += 1;
+        self.current_thread_mut().wait = Some(WaitState {
+            object,
+            sequence,
+            deadline,
+            woken_up: false,
+        });
+    }
+
+    /// Removes the current thread from the wait set it was in, and returns the object it
+    /// was waiting on, since the gc may have moved it
+    pub fn finish_waiting(&mut self) -> Option<AbstractObject<'a>> {
+        self.current_thread_mut()
+            .wait
+            .take()
+            .expect("thread should be waiting")
+            .object
+    }
+
+    /// Wakes up the threads waiting on the given object: either all of them, or the one
+    /// that started waiting first
+    pub fn notify(&mut self, object: &AbstractObject<'a>, all: bool) {
+        let mut waiting = self
+            .threads
+            .iter_mut()
+            .filter_map(|thread| thread.wait.as_mut())
+            .filter(|wait| {
+                !wait.woken_up
+                    && wait
+                        .object
+                        .as_ref()
+                        .is_some_and(|waited| waited.is_same_as(object))
+            })
+            .collect::<Vec<_>>();
+        if all {
+            waiting.iter_mut().for_each(|wait| wait.woken_up = true);
+        } else if let Some(first) = waiting.iter_mut().min_by_key(|wait| wait.sequence) {
+            first.woken_up = true;
+        }
+    }
+
+    pub fn gc_roots(&mut self) -> impl Iterator<Item = *mut AbstractObject<'a>> + '_ {
+        self.threads.iter_mut().flat_map(|thread| {
+            thread
+                .thread_object
+                .iter_mut()
+                .chain(thread.kept_object.iter_mut())
+                .chain(
+                    thread
+                        .wait
+                        .iter_mut()
+                        .filter_map(|wait| wait.object.as_mut()),
+                )
+                .map(|object| object as *mut AbstractObject<'a>)
+        })
+    }
+
+    /// Sets the interrupt status of the thread with the given instance of `java.lang.Thread`,
+    /// waking it up if it is waiting or sleeping. Does nothing if the thread is not alive.
+    pub fn interrupt(&mut self, thread_object: &AbstractObject<'a>) {
+        if let Some(thread) = self.thread_with_object(thread_object) {
+            thread.interrupted = true;
+            if let Some(wait) = &mut thread.wait {
+                wait.woken_up = true;
+            }
+        }
+    }
+
+    /// Returns the interrupt status of the thread with the given instance of
+    /// `java.lang.Thread`, optionally clearing it. Threads that are not alive are never
+    /// interrupted.
+    pub fn is_interrupted(&mut self, thread_object: &AbstractObject<'a>, clear: bool) -> bool {
+        match self.thread_with_object(thread_object) {
+            Some(thread) if clear => std::mem::take(&mut thread.interrupted),
+            Some(thread) => thread.interrupted,
+            None => false,
+        }
+    }
+
+    /// Returns and clears the interrupt status of the current thread
+    pub fn take_current_interrupt(&mut self) -> bool {
+        std::mem::take(&mut self.current_thread_mut().interrupted)
+    }
+
+    fn thread_with_object(
+        &mut self,
+        thread_object: &AbstractObject<'a>,
+    ) -> Option<&mut GreenThread<'a>> {
+        self.threads.iter_mut().find(|thread| {
+            thread
+                .thread_object
+                .as_ref()
+                .is_some_and(|object| object.is_same_as(thread_object))
+        })
+    }
+
+    fn current_thread(&self) -> &GreenThread<'a> {
+        self.threads
+            .iter()
+            .find(|thread| thread.id == self.current)
+            .expect("the current thread should be alive")
+    }
+
+    fn current_thread_mut(&mut self) -> &mut GreenThread<'a> {
+        let current = self.current;
+        self.thread_mut(current)
+    }
+
+    fn thread_mut(&mut self, id: ThreadId) -> &mut GreenThread<'a> {
+        self.threads
+            .iter_mut()
+            .find(|thread| thread.id == id)
+            .expect("thread should be alive")
+    }
+}
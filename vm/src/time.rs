@@ -9,10 +9,10 @@ fn time_since_epoch() -> Duration {
 
 /// Returns the current epoch as nano seconds
 pub(crate) fn get_nano_time() -> i64 {
-    time_since_epoch().as_nanos() as i64
+    i64::try_from(time_since_epoch().as_nanos()).expect("the time should fit in 64 bits")
 }
 
 /// Returns the current epoch as milliseconds
 pub(crate) fn get_current_time_millis() -> i64 {
-    time_since_epoch().as_millis() as i64
+    i64::try_from(time_since_epoch().as_millis()).expect("the time should fit in 64 bits")
 }
@@ -0,0 +1,374 @@
+use log::Level;
+
+use crate::{
+    abstract_object::AbstractObject,
+    call_frame::MethodCallResult,
+    call_stack::CallStack,
+    class::{ClassRef, DefiningLoader},
+    exceptions::{JavaException, MethodCallFailed},
+    file_io::{bytes_of_byte_array, checked_range},
+    java_objects_creation::{
+        extract_str_from_java_lang_string, get_java_lang_class_object, new_java_lang_string_object,
+        throw_exception,
+    },
+    log_config::{vm_log, LogCategory},
+    native_methods_registry::NativeMethodsRegistry,
+    object::Object,
+    object_fields::{get_object_field, set_object_field},
+    value::{expect_abstract_object_at, expect_int_at, Value},
+    vm::Vm,
+    vm_error::VmError,
+};
+
+const PERF_COUNTER: &str = "sun/misc/PerfCounter";
+const APP_CLASS_LOADER: &str = "sun/misc/Launcher$AppClassLoader";
+
+/// Registers the methods that let the subclasses of `java.lang.ClassLoader` define their own
+/// classes. The classes of the boot class path belong to the bootstrap loader, i.e. `null`,
+/// and the ones of the application class path to the system class loader; the classes given
+/// to `defineClass` are tagged with the loader that defined them, and the classes they
+/// reference that are not in the class paths are requested to that loader, via `loadClass`.
+///
+/// Class names are still global: a class can be defined only once, by a single loader.
+pub(crate) fn register_class_loader_methods(registry: &mut NativeMethodsRegistry) {
+    // Every loader creates its default protection domain. The initializer of the class only
+    // publishes the accessors of its internals via `SharedSecrets`, which needs
+    // `sun.misc.Unsafe`, and looks up its debug logger, which can stay null.
+    registry.register_intrinsic(
+        "java/security/ProtectionDomain",
+        "<clinit>",
+        "()V",
+        |_, _, _, _| Ok(None),
+    );
+    // Replaced because the JDK implementation checks the protection domain and the signers
+    // of the class, which we do not model, before and after invoking the natives
+    registry.register_intrinsic(
+        "java/lang/ClassLoader",
+        "defineClass",
+        "(Ljava/lang/String;[BIILjava/security/ProtectionDomain;)Ljava/lang/Class;",
+        define_class,
+    );
+    registry.register(
+        "java/lang/ClassLoader",
+        "defineClass0",
+        "(Ljava/lang/String;[BIILjava/security/ProtectionDomain;)Ljava/lang/Class;",
+        define_class,
+    );
+    registry.register(
+        "java/lang/ClassLoader",
+        "defineClass1",
+        "(Ljava/lang/String;[BIILjava/security/ProtectionDomain;Ljava/lang/String;)Ljava/lang/Class;",
+        define_class,
+    );
+    registry.register(
+        "java/lang/ClassLoader",
+        "findLoadedClass0",
+        "(Ljava/lang/String;)Ljava/lang/Class;",
+        find_loaded_class,
+    );
+    registry.register(
+        "java/lang/ClassLoader",
+        "findBootstrapClass",
+        "(Ljava/lang/String;)Ljava/lang/Class;",
+        find_bootstrap_class,
+    );
+    // Classes are linked when they are defined
+    registry.register(
+        "java/lang/ClassLoader",
+        "resolveClass0",
+        "(Ljava/lang/Class;)V",
+        |_, _, _, _| Ok(None),
+    );
+    registry.register(
+        "java/lang/Class",
+        "getClassLoader0",
+        "()Ljava/lang/ClassLoader;",
+        get_class_loader,
+    );
+    register_system_class_loader_methods(registry);
+    register_perf_counter_methods(registry);
+}
+
+/// The JDK creates the system class loader via `sun.misc.Launcher`, which reads the class path
+/// from the system properties and builds a hierarchy of `URLClassLoader`. We replace the
+/// entry points with intrinsics, so that the application classes are defined by the vm, as
+/// the ones of the boot class path.
+fn register_system_class_loader_methods(registry: &mut NativeMethodsRegistry) {
+    registry.register_intrinsic(
+        "java/lang/ClassLoader",
+        "getSystemClassLoader",
+        "()Ljava/lang/ClassLoader;",
+        |vm, _, _, _| Ok(Some(Value::Object(system_class_loader(vm)?))),
+    );
+    registry.register_intrinsic(
+        APP_CLASS_LOADER,
+        "loadClass",
+        "(Ljava/lang/String;Z)Ljava/lang/Class;",
+        load_application_class,
+    );
+}
+
+/// The instance of `sun.misc.Launcher$AppClassLoader` that stands for the application class
+/// loader, created the first time it is needed. Since it is not initialized by its
+/// constructor, it only supports the methods replaced by the intrinsics and the ones of
+/// `java.lang.ClassLoader` that do not need its state, such as `getParent`, which returns
+/// `null`, i.e. the bootstrap loader.
+pub(crate) fn system_class_loader<'a>(vm: &mut Vm<'a>) -> Result<AbstractObject<'a>, VmError> {
+    if let Some(loader) = vm.system_class_loader() {
+        return Ok(loader);
+    }
+    let loader_class = vm.resolve_class_of(APP_CLASS_LOADER, DefiningLoader::Bootstrap)?;
+    let loader = vm.new_object_of_class(loader_class)?;
+    vm_log!(
+        vm.log_config(),
+        LogCategory::ClassLoad,
+        Level::Debug,
+        "created the system class loader"
+    );
+    vm.set_system_class_loader(loader.clone());
+    Ok(loader)
+}
+
+/// Loads a class from the boot or the application class path
+fn load_application_class<'a>(
+    vm: &mut Vm<'a>,
+    call_stack: &mut CallStack<'a>,
+    _: Option<AbstractObject<'a>>,
+    args: Vec<Value<'a>>,
+) -> MethodCallResult<'a> {
+    let name = extract_str_from_java_lang_string(vm, &expect_abstract_object_at(&args, 0)?)?;
+    let class = match vm.resolve_class_of(&name.replace('.', "/"), DefiningLoader::Application) {
+        Err(VmError::ClassNotFoundException(_)) => {
+            return throw_exception(vm, call_stack, "java/lang/ClassNotFoundException", &name)
+        }
+        result => result?,
+    };
+    let class_object = get_java_lang_class_object(vm, call_stack, &class.name)?;
+    Ok(Some(Value::Object(class_object)))
+}
+
+/// `ClassLoader.loadClass` updates some performance counters, which the JDK stores in memory
+/// shared with the monitoring tools. We do not expose them: the counters keep just their name,
+/// and are always zero.
+fn register_perf_counter_methods(registry: &mut NativeMethodsRegistry) {
+    registry.register("sun/misc/Perf", "registerNatives", "()V", |_, _, _, _| {
+        Ok(None)
+    });
+    registry.register_intrinsic(
+        PERF_COUNTER,
+        "<init>",
+        "(Ljava/lang/String;I)V",
+        |vm, _, receiver, args| {
+            let counter = receiver.ok_or(VmError::NullPointerException)?;
+            let name = args.first().cloned().unwrap_or(Value::Null);
+            vm.write_barrier(&name);
+            set_object_field(vm, &counter, "name", name)?;
+            Ok(None)
+        },
+    );
+    registry.register_intrinsic(PERF_COUNTER, "get", "()J", |_, _, _, _| {
+        Ok(Some(Value::Long(0)))
+    });
+    for method_name in ["set", "add"] {
+        registry.register_intrinsic(PERF_COUNTER, method_name, "(J)V", |_, _, _, _| Ok(None));
+    }
+}
+
+/// Loads a class through the given loader, by invoking its `loadClass` method. A
+/// `ClassNotFoundException` thrown by the loader becomes a [VmError::ClassNotFoundException].
+pub(crate) fn load_class<'a>(
+    vm: &mut Vm<'a>,
+    call_stack: &mut CallStack<'a>,
+    loader: AbstractObject<'a>,
+    class_name: &str,
+) -> Result<ClassRef<'a>, MethodCallFailed<'a>> {
+    vm_log!(
+        vm.log_config(),
+        LogCategory::ClassLoad,
+        Level::Debug,
+        "loading class {} through a user class loader",
+        class_name
+    );
+    let scope = vm.handle_scope();
+    let loader = scope.handle(loader);
+    let binary_name = new_java_lang_string_object(vm, call_stack, &class_name.replace('/', "."))?;
+    let not_found = || VmError::ClassNotFoundException(class_name.to_string()).into();
+    match vm.invoke_virtual(
+        call_stack,
+        loader.get(),
+        "loadClass",
+        "(Ljava/lang/String;)Ljava/lang/Class;",
+        vec![Value::Object(binary_name)],
+    ) {
+        Ok(Some(Value::Object(class_object))) => class_of_class_object(vm, &class_object),
+        Ok(_) => Err(not_found()),
+        Err(MethodCallFailed::ExceptionThrown(JavaException(exception)))
+            if is_class_not_found_exception(vm, &exception)? =>
+        {
+            Err(not_found())
+        }
+        Err(err) => Err(err),
+    }
+}
+
+fn is_class_not_found_exception<'a>(
+    vm: &mut Vm<'a>,
+    exception: &AbstractObject<'a>,
+) -> Result<bool, VmError> {
+    let class_not_found_exception = vm.resolve_class("java/lang/ClassNotFoundException")?;
+    let exception_class = vm.get_class_by_id(exception.class_id())?;
+    Ok(exception_class.is_subclass_of(class_not_found_exception))
+}
+
+/// The class represented by an instance of `java.lang.Class`
+fn class_of_class_object<'a>(
+    vm: &Vm<'a>,
+    class_object: &AbstractObject<'a>,
+) -> Result<ClassRef<'a>, MethodCallFailed<'a>> {
+    let class_name = class_name_of_class_object(vm, class_object)?;
+    vm.find_class_by_name(&class_name)
+        .ok_or_else(|| VmError::ClassNotFoundException(class_name).into())
+}
+
+fn class_name_of_class_object<'a>(
+    vm: &Vm<'a>,
+    class_object: &AbstractObject<'a>,
+) -> Result<String, VmError> {
+    match get_object_field(vm, class_object, "name")? {
+        Value::Object(class_name) => extract_str_from_java_lang_string(vm, &class_name),
+        _ => Err(VmError::ValidationException),
+    }
+}
+
+/// Implements all the variants of `defineClass` taking a `byte[]`. The superclass and the
+/// interfaces of the new class are loaded first, through the same loader.
+fn define_class<'a>(
+    vm: &mut Vm<'a>,
+    call_stack: &mut CallStack<'a>,
+    receiver: Option<AbstractObject<'a>>,
+    args: Vec<Value<'a>>,
+) -> MethodCallResult<'a> {
+    let loader = receiver.ok_or(VmError::NullPointerException)?;
+    let expected_name = match args.first() {
+        Some(Value::Object(name)) => Some(extract_str_from_java_lang_string(vm, name)?),
+        _ => None,
+    };
+    let array = expect_abstract_object_at(&args, 1)?;
+    let Some((offset, length)) =
+        checked_range(&array, expect_int_at(&args, 2)?, expect_int_at(&args, 3)?)
+    else {
+        return throw_exception(vm, call_stack, "java/lang/IndexOutOfBoundsException", "");
+    };
+    let bytes = bytes_of_byte_array(&array, offset, length)?;
+
+    let internal_name = expected_name.as_ref().map(|name| name.replace('.', "/"));
+    let (class_file, class_file_bytes) = match vm.parse_class(internal_name.as_deref(), bytes) {
+        Ok(parsed) => parsed,
+        Err(err) => {
+            return throw_exception(
+                vm,
+                call_stack,
+                "java/lang/ClassFormatError",
+                &err.to_string(),
+            )
+        }
+    };
+    let class_name = class_file.name.to_string();
+    if let Some(expected_name) = expected_name {
+        if expected_name.replace('.', "/") != class_name {
+            let message = format!("{expected_name} (wrong name: {class_name})");
+            return throw_exception(vm, call_stack, "java/lang/NoClassDefFoundError", &message);
+        }
+    }
+    if vm.find_class_by_name(&class_name).is_some() {
+        let loader_class = vm.get_class_by_id(loader.class_id())?;
+        let message = format!(
+            "loader (instance of {}): attempted duplicate class definition for name: \"{}\"",
+            loader_class.name, class_name
+        );
+        return throw_exception(vm, call_stack, "java/lang/LinkageError", &message);
+    }
+
+    let scope = vm.handle_scope();
+    let loader = scope.handle(loader);
+    let referenced_classes = class_file.superclass.iter().chain(&class_file.interfaces);
+    for referenced_class in referenced_classes {
+        if vm.find_class_by_name(referenced_class).is_some() {
+            continue;
+        }
+        match vm.resolve_class(referenced_class) {
+            Err(VmError::ClassNotFoundException(_)) => {
+                match load_class(vm, call_stack, loader.get(), referenced_class) {
+                    // The definition will fail, unless the class is a missing interface
+                    // and lazy linkage is enabled
+                    Err(MethodCallFailed::InternalError(VmError::ClassNotFoundException(_))) => {}
+                    result => {
+                        result?;
+                    }
+                }
+            }
+            result => {
+                result?;
+            }
+        }
+    }
+
+    let class = vm.define_class_of_loader(class_file, class_file_bytes, loader.get())?;
+    let class_object = get_java_lang_class_object(vm, call_stack, &class.name)?;
+    Ok(Some(Value::Object(class_object)))
+}
+
+/// Returns the class with the given binary name if it was defined by the receiver
+fn find_loaded_class<'a>(
+    vm: &mut Vm<'a>,
+    call_stack: &mut CallStack<'a>,
+    receiver: Option<AbstractObject<'a>>,
+    args: Vec<Value<'a>>,
+) -> MethodCallResult<'a> {
+    let loader = receiver.ok_or(VmError::NullPointerException)?;
+    let name = extract_str_from_java_lang_string(vm, &expect_abstract_object_at(&args, 0)?)?;
+    match vm.find_class_by_name(&name.replace('.', "/")) {
+        Some(class) if vm.defining_loader(class) == Some(loader) => {
+            let class_object = get_java_lang_class_object(vm, call_stack, &class.name)?;
+            Ok(Some(Value::Object(class_object)))
+        }
+        _ => Ok(Some(Value::Null)),
+    }
+}
+
+/// Returns the class with the given binary name from the boot class path, or `null`
+fn find_bootstrap_class<'a>(
+    vm: &mut Vm<'a>,
+    call_stack: &mut CallStack<'a>,
+    _: Option<AbstractObject<'a>>,
+    args: Vec<Value<'a>>,
+) -> MethodCallResult<'a> {
+    let name = extract_str_from_java_lang_string(vm, &expect_abstract_object_at(&args, 0)?)?;
+    let class = match vm.resolve_class_of(&name.replace('.', "/"), DefiningLoader::Bootstrap) {
+        Err(VmError::ClassNotFoundException(_)) => return Ok(Some(Value::Null)),
+        result => result?,
+    };
+    let class_object = get_java_lang_class_object(vm, call_stack, &class.name)?;
+    Ok(Some(Value::Object(class_object)))
+}
+
+fn get_class_loader<'a>(
+    vm: &mut Vm<'a>,
+    _: &mut CallStack<'a>,
+    receiver: Option<AbstractObject<'a>>,
+    _: Vec<Value<'a>>,
+) -> MethodCallResult<'a> {
+    let class_object = receiver.ok_or(VmError::NullPointerException)?;
+    let class_name = class_name_of_class_object(vm, &class_object)?;
+    // We do not model the classes of arrays and primitive types, which report the bootstrap
+    // loader
+    let loader = match vm.find_class_by_name(&class_name) {
+        Some(class) => match class.defining_loader {
+            DefiningLoader::Bootstrap => None,
+            DefiningLoader::Application => Some(system_class_loader(vm)?),
+            DefiningLoader::User => vm.defining_loader(class),
+        },
+        None => None,
+    };
+    Ok(Some(loader.map_or(Value::Null, Value::Object)))
+}
@@ -1,10 +1,14 @@
 use std::fmt::Debug;
 
-use rjvm_reader::field_type::{BaseType, FieldType};
+use rjvm_reader::{
+    field_type::{BaseType, FieldType},
+    program_counter::ProgramCounter,
+};
 
 use crate::{
     abstract_object::{AbstractObject, ObjectKind},
     array::Array,
+    array_entry_type::ArrayEntryType,
     class::ClassRef,
     class_resolver_by_id::ClassByIdResolver,
     object::Object,
@@ -37,16 +41,20 @@ pub enum Value<'a> {
 
     /// Models a null object
     Null,
-    // TODO: the JVM spec says we need to add return address, which are used to implement `finally`
+
+    /// Models the address of an instruction, pushed by `jsr` and used by `ret` to return from
+    /// a subroutine. Old compilers used these to implement `finally` blocks.
+    ReturnAddress(ProgramCounter),
 }
 
 impl<'a> Value<'a> {
-    /// Used for runtime validations that the value matches the given type.
-    /// Overly complex; these things, according to the JVM spec, should be checked
-    /// at class linkage time, but we have not implemented that phase... :-)
+    /// Checks that the value matches the given type at runtime, e.g. for `instanceof`,
+    /// `checkcast` and `System.arraycopy`. Linking and verification catch the inconsistent
+    /// constant pools and bytecode when the classes are loaded, but the types of the objects
+    /// can only be known when the code runs.
     pub fn matches_type<'b, 'c, ResByName>(
         &self,
-        expected_type: FieldType,
+        expected_type: &FieldType,
         class_resolver_by_id: &impl ClassByIdResolver<'c>,
         class_resolver_by_name: ResByName,
     ) -> bool
@@ -67,32 +75,26 @@ impl<'a> Value<'a> {
                 _ => false,
             },
             Value::Long(_) => match expected_type {
-                FieldType::Base(base_type) => base_type == BaseType::Long,
+                FieldType::Base(base_type) => *base_type == BaseType::Long,
                 _ => false,
             },
             Value::Float(_) => match expected_type {
-                FieldType::Base(base_type) => base_type == BaseType::Float,
+                FieldType::Base(base_type) => *base_type == BaseType::Float,
                 _ => false,
             },
             Value::Double(_) => match expected_type {
-                FieldType::Base(base_type) => base_type == BaseType::Double,
+                FieldType::Base(base_type) => *base_type == BaseType::Double,
                 _ => false,
             },
 
             Value::Object(object) => {
                 if object.kind() == ObjectKind::Array {
-                    match expected_type {
-                        FieldType::Array(expected_field_type) => {
-                            let array_entry_type =
-                                object.elements_type().into_field_type(class_resolver_by_id);
-                            if let Some(array_entry_type) = array_entry_type {
-                                array_entry_type == *expected_field_type
-                            } else {
-                                false
-                            }
-                        }
-                        _ => false,
-                    }
+                    array_matches_type(
+                        object.elements_type(),
+                        expected_type,
+                        class_resolver_by_id,
+                        class_resolver_by_name,
+                    )
                 } else {
                     match expected_type {
                         // TODO: with multiple class loaders, we should check the class identity,
@@ -101,8 +103,8 @@ impl<'a> Value<'a> {
                             let value_class =
                                 class_resolver_by_id.find_class_by_id(object.class_id());
                             if let Some(object_class) = value_class {
-                                let expected_class = class_resolver_by_name(&expected_class_name);
-                                expected_class.map_or(false, |expected_class| {
+                                let expected_class = class_resolver_by_name(expected_class_name);
+                                expected_class.is_some_and(|expected_class| {
                                     object_class.is_subclass_of(expected_class)
                                 })
                             } else {
@@ -119,6 +121,85 @@ impl<'a> Value<'a> {
                 FieldType::Object(_) => true,
                 FieldType::Array(_) => true,
             },
+
+            // Return addresses cannot be stored in fields or passed around
+            Value::ReturnAddress(_) => false,
+        }
+    }
+    /// The name of the runtime type of the value, for diagnostics
+    pub fn runtime_type_name<'c>(&self, class_resolver: &impl ClassByIdResolver<'c>) -> String {
+        match self {
+            Value::Uninitialized => "uninitialized".to_string(),
+            Value::Int(_) => BaseType::Int.to_string(),
+            Value::Long(_) => BaseType::Long.to_string(),
+            Value::Float(_) => BaseType::Float.to_string(),
+            Value::Double(_) => BaseType::Double.to_string(),
+            Value::Object(object) => match object.kind() {
+                ObjectKind::Array => {
+                    format!("{}[]", object.elements_type().type_name(class_resolver))
+                }
+                ObjectKind::Object => match class_resolver.find_class_by_id(object.class_id()) {
+                    Some(class) => class.name.clone(),
+                    None => format!("<unknown class {}>", object.class_id()),
+                },
+            },
+            Value::Null => "null".to_string(),
+            Value::ReturnAddress(_) => "returnAddress".to_string(),
+        }
+    }
+}
+
+/// The classes and interfaces that all arrays extend or implement
+const ARRAY_SUPERTYPES: [&str; 3] = [
+    "java/lang/Object",
+    "java/lang/Cloneable",
+    "java/io/Serializable",
+];
+
+/// Whether an array with the given entries type can be assigned to the expected type.
+/// Arrays are covariant, so i.e. a `String[][]` is also an `Object[][]` and an `Object[]`.
+fn array_matches_type<'b, 'c, ResByName>(
+    elements_type: ArrayEntryType,
+    expected_type: &FieldType,
+    class_resolver_by_id: &impl ClassByIdResolver<'c>,
+    class_resolver_by_name: ResByName,
+) -> bool
+where
+    ResByName: FnOnce(&str) -> Option<ClassRef<'b>>,
+{
+    match expected_type {
+        FieldType::Base(_) => false,
+        FieldType::Object(class_name) => ARRAY_SUPERTYPES.contains(&class_name.as_str()),
+        FieldType::Array(expected_elements_type) => {
+            match (elements_type, expected_elements_type.as_ref()) {
+                (ArrayEntryType::Base(base_type), FieldType::Base(expected_base_type)) => {
+                    base_type == *expected_base_type
+                }
+                (ArrayEntryType::Object(class_id), FieldType::Object(expected_class_name)) => {
+                    let elements_class = class_resolver_by_id.find_class_by_id(class_id);
+                    let expected_class = class_resolver_by_name(expected_class_name);
+                    match (elements_class, expected_class) {
+                        (Some(elements_class), Some(expected_class)) => {
+                            elements_class.is_subclass_of(expected_class)
+                        }
+                        _ => false,
+                    }
+                }
+                (elements_type @ ArrayEntryType::Array(_), expected_elements_type) => {
+                    // The entries are arrays: check them against the expected entries type
+                    elements_type
+                        .component_type()
+                        .is_some_and(|component_type| {
+                            array_matches_type(
+                                component_type,
+                                expected_elements_type,
+                                class_resolver_by_id,
+                                class_resolver_by_name,
+                            )
+                        })
+                }
+                _ => false,
+            }
         }
     }
 }
@@ -128,11 +209,10 @@ pub fn expect_abstract_object_at<'a>(
     vec: &[Value<'a>],
     index: usize,
 ) -> Result<AbstractObject<'a>, VmError> {
-    let value = vec.get(index);
-    if let Some(Value::Object(object)) = value {
-        Ok(object.clone())
-    } else {
-        Err(VmError::ValidationException)
+    match vec.get(index) {
+        Some(Value::Object(object)) => Ok(object.clone()),
+        Some(Value::Null) => Err(VmError::NullPointerException),
+        _ => Err(VmError::ValidationException),
     }
 }
 
@@ -169,6 +249,16 @@ pub fn expect_int_at(vec: &[Value], index: usize) -> Result<i32, VmError> {
     }
 }
 
+/// Checks that the element at the given index is a Long and returns it, or an error.
+pub fn expect_long_at(vec: &[Value], index: usize) -> Result<i64, VmError> {
+    let value = vec.get(index);
+    if let Some(Value::Long(long)) = value {
+        Ok(*long)
+    } else {
+        Err(VmError::ValidationException)
+    }
+}
+
 /// Checks that the element at the given index is a Float and returns it, or an error.
 pub fn expect_float_at(vec: &[Value], index: usize) -> Result<f32, VmError> {
     let value = vec.get(index);
@@ -74,11 +74,11 @@ impl<'a> ValueStack<'a> {
         self.stack.get(index)
     }
 
-    pub fn iter(&self) -> Iter<Value<'a>> {
+    pub fn iter(&self) -> Iter<'_, Value<'a>> {
         self.stack.iter()
     }
 
-    pub fn iter_mut(&mut self) -> IterMut<Value<'a>> {
+    pub fn iter_mut(&mut self) -> IterMut<'_, Value<'a>> {
         self.stack.iter_mut()
     }
 
@@ -99,45 +99,47 @@ impl<'a> ValueStack<'a> {
 
     pub fn dup_x2(&mut self) -> Result<(), ValueStackError> {
         let value1 = self.pop()?;
-        let value2 = self.pop()?;
-        let value3 = self.pop()?;
+        let values2 = self.pop_two_slots()?;
         self.push(value1.clone())?;
-        self.push(value3)?;
-        self.push(value2)?;
+        self.push_all(values2)?;
         self.push(value1)
     }
 
     pub fn dup2(&mut self) -> Result<(), ValueStackError> {
-        let value1 = self.pop()?;
-        let value2 = self.pop()?;
-        self.push(value2.clone())?;
-        self.push(value1.clone())?;
-        self.push(value2)?;
-        self.push(value1)
+        let values1 = self.pop_two_slots()?;
+        self.push_all(values1.clone())?;
+        self.push_all(values1)
     }
 
     pub fn dup2_x1(&mut self) -> Result<(), ValueStackError> {
-        let value1 = self.pop()?;
+        let values1 = self.pop_two_slots()?;
         let value2 = self.pop()?;
-        let value3 = self.pop()?;
-        self.push(value2.clone())?;
-        self.push(value1.clone())?;
-        self.push(value3)?;
+        self.push_all(values1.clone())?;
         self.push(value2)?;
-        self.push(value1)
+        self.push_all(values1)
     }
 
     pub fn dup2_x2(&mut self) -> Result<(), ValueStackError> {
-        let value1 = self.pop()?;
-        let value2 = self.pop()?;
-        let value3 = self.pop()?;
-        let value4 = self.pop()?;
-        self.push(value2.clone())?;
-        self.push(value1.clone())?;
-        self.push(value4)?;
-        self.push(value3)?;
-        self.push(value2)?;
-        self.push(value1)
+        let values1 = self.pop_two_slots()?;
+        let values2 = self.pop_two_slots()?;
+        self.push_all(values1.clone())?;
+        self.push_all(values2)?;
+        self.push_all(values1)
+    }
+
+    /// The JVM specification counts longs and doubles as two stack slots, while we store them
+    /// as a single value: this pops either one of them, or two values of the other types.
+    /// Returns the values in the order in which they were pushed.
+    fn pop_two_slots(&mut self) -> Result<Vec<Value<'a>>, ValueStackError> {
+        let value = self.pop()?;
+        match value {
+            Value::Long(_) | Value::Double(_) => Ok(vec![value]),
+            _ => Ok(vec![self.pop()?, value]),
+        }
+    }
+
+    fn push_all(&mut self, values: Vec<Value<'a>>) -> Result<(), ValueStackError> {
+        values.into_iter().try_for_each(|value| self.push(value))
     }
 
     pub fn swap(&mut self) -> Result<(), ValueStackError> {
@@ -269,6 +271,58 @@ mod tests {
         assert_eq!(Ok(Value::Int(2)), stack.pop());
     }
 
+    #[test]
+    fn can_invoke_dup_x2_with_long() {
+        let mut stack = ValueStack::with_max_size(3);
+        stack.push(Value::Long(2)).expect("should be able to push");
+        stack.push(Value::Int(1)).expect("should be able to push");
+        stack.dup_x2().expect("should be able to dup_x2");
+        assert_eq!(3, stack.len());
+        assert_eq!(Ok(Value::Int(1)), stack.pop());
+        assert_eq!(Ok(Value::Long(2)), stack.pop());
+        assert_eq!(Ok(Value::Int(1)), stack.pop());
+    }
+
+    #[test]
+    fn can_invoke_dup2_with_long() {
+        let mut stack = ValueStack::with_max_size(3);
+        stack.push(Value::Int(2)).expect("should be able to push");
+        stack.push(Value::Long(1)).expect("should be able to push");
+        stack.dup2().expect("should be able to dup2");
+        assert_eq!(3, stack.len());
+        assert_eq!(Ok(Value::Long(1)), stack.pop());
+        assert_eq!(Ok(Value::Long(1)), stack.pop());
+        assert_eq!(Ok(Value::Int(2)), stack.pop());
+    }
+
+    #[test]
+    fn can_invoke_dup2_x1_with_double() {
+        let mut stack = ValueStack::with_max_size(3);
+        stack.push(Value::Int(2)).expect("should be able to push");
+        stack
+            .push(Value::Double(1f64))
+            .expect("should be able to push");
+        stack.dup2_x1().expect("should be able to dup2_x1");
+        assert_eq!(3, stack.len());
+        assert_eq!(Ok(Value::Double(1f64)), stack.pop());
+        assert_eq!(Ok(Value::Int(2)), stack.pop());
+        assert_eq!(Ok(Value::Double(1f64)), stack.pop());
+    }
+
+    #[test]
+    fn can_invoke_dup2_x2_with_longs() {
+        let mut stack = ValueStack::with_max_size(4);
+        stack.push(Value::Int(3)).expect("should be able to push");
+        stack.push(Value::Int(2)).expect("should be able to push");
+        stack.push(Value::Long(1)).expect("should be able to push");
+        stack.dup2_x2().expect("should be able to dup2_x2");
+        assert_eq!(4, stack.len());
+        assert_eq!(Ok(Value::Long(1)), stack.pop());
+        assert_eq!(Ok(Value::Int(2)), stack.pop());
+        assert_eq!(Ok(Value::Int(3)), stack.pop());
+        assert_eq!(Ok(Value::Long(1)), stack.pop());
+    }
+
     #[test]
     fn can_invoke_pop2() {
         let mut stack = ValueStack::with_max_size(4);
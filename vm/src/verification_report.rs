@@ -0,0 +1,79 @@
+use std::{fmt, fmt::Formatter};
+
+use crate::vm_error::VmError;
+
+/// The outcome of [Vm::verify](crate::vm::Vm::verify)
+#[derive(Debug, Default, PartialEq)]
+pub struct VerificationReport {
+    /// The number of classes of the class path that were checked
+    pub classes: usize,
+    /// In class path order; the problems of the same class follow the order in which they were
+    /// found
+    pub problems: Vec<VerificationProblem>,
+}
+
+impl VerificationReport {
+    /// Whether all the classes were loaded and verified successfully
+    pub fn is_ok(&self) -> bool {
+        self.problems.is_empty()
+    }
+}
+
+impl fmt::Display for VerificationReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "verified {} classes, found {} problems",
+            self.classes,
+            self.problems.len()
+        )?;
+        for problem in self.problems.iter() {
+            write!(f, "\n  {}: {}", problem.class_name, problem.error)?;
+        }
+        Ok(())
+    }
+}
+
+/// A problem of a class, or of a class path entry, found by [Vm::verify](crate::vm::Vm::verify)
+#[derive(Debug, PartialEq)]
+pub struct VerificationProblem {
+    /// The class with the problem or, for [VerificationProblemKind::UnreadableEntry], the
+    /// description of the class path entry
+    pub class_name: String,
+    pub kind: VerificationProblemKind,
+    pub error: VmError,
+}
+
+/// What kind of problem a [VerificationProblem] is
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VerificationProblemKind {
+    /// The class file was compiled for a version of java that the vm does not support
+    UnsupportedVersion,
+    /// The class file is malformed, e.g. it is truncated or its constant pool is inconsistent
+    InvalidClassFile,
+    /// The bytecode of a method was rejected by the verifier
+    VerifyError,
+    /// A class that the class needs cannot be found: either its superclass or one of its
+    /// interfaces, in which case the class cannot be loaded at all, or a class referenced by
+    /// its code, whose execution would throw a `NoClassDefFoundError`
+    MissingDependency(String),
+    /// The classes of the class path entry could not be listed
+    UnreadableEntry,
+    /// Any other problem preventing the class from being loaded
+    Other,
+}
+
+impl VerificationProblemKind {
+    /// Classifies an error loading the given class
+    pub(crate) fn of_load_error(class_name: &str, error: &VmError) -> Self {
+        match error {
+            VmError::UnsupportedClassVersionError(..) => Self::UnsupportedVersion,
+            VmError::ClassLoadingError(_) | VmError::ClassFormatError(_) => Self::InvalidClassFile,
+            VmError::VerifyError(_) => Self::VerifyError,
+            VmError::ClassNotFoundException(missing_class) if missing_class != class_name => {
+                Self::MissingDependency(missing_class.clone())
+            }
+            _ => Self::Other,
+        }
+    }
+}
@@ -0,0 +1,1301 @@
+use std::{borrow::Cow, collections::BTreeMap};
+
+use rjvm_reader::{
+    class_file::ClassFile,
+    class_file_method::{ClassFileMethod, ClassFileMethodCode},
+    class_file_version::ClassFileVersion,
+    constant_pool::ConstantPoolEntry,
+    field_type::{BaseType, FieldType},
+    instruction::{Instruction, NewArrayType},
+    method_descriptor::MethodDescriptor,
+    program_counter::ProgramCounter,
+    stack_map_table::{StackMapFrameKind, VerificationType},
+    type_conversion::ToUsizeSafe,
+};
+
+use crate::vm_error::{VerifyError, VmError};
+
+/// Verifies the code of all the methods of a class by type checking it against the frames
+/// declared in their `StackMapTable`, as described in section 4.10.1 of the JVM specs.
+/// Class files older than version 50 have no stack maps and would require the verifier
+/// by type inference, which is not implemented, so they are accepted as they are.
+pub(crate) fn verify_class(class_file: &ClassFile) -> Result<(), VmError> {
+    if class_file.version < ClassFileVersion::Jdk6 {
+        return Ok(());
+    }
+    for method in class_file.methods.iter() {
+        if let Some(code) = &method.code {
+            MethodVerifier::new(class_file, method, code)
+                .and_then(|verifier| verifier.verify())
+                .map_err(|(pc, reason)| {
+                    VmError::VerifyError(Box::new(VerifyError {
+                        class_name: class_file.name.to_string(),
+                        method_name: method.name.to_string(),
+                        method_descriptor: method.type_descriptor.to_string(),
+                        pc: to_program_counter(pc),
+                        reason,
+                    }))
+                })?;
+        }
+    }
+    Ok(())
+}
+
+/// The reason why some code does not verify
+type VerifyResult<T> = Result<T, String>;
+
+/// The types of the locals and of the stack entries at some point of a method. Longs and
+/// doubles take two slots, the second of which is [VerificationType::Top].
+#[derive(Debug, Clone, PartialEq)]
+struct Frame<'c> {
+    locals: Vec<VerificationType<'c>>,
+    stack: Vec<VerificationType<'c>>,
+}
+
+/// Where the execution can continue after an instruction
+struct Flow {
+    falls_through: bool,
+    targets: Vec<usize>,
+}
+
+impl Flow {
+    fn next() -> Self {
+        Self {
+            falls_through: true,
+            targets: Vec::new(),
+        }
+    }
+
+    fn branch(target: u32) -> Self {
+        Self {
+            falls_through: true,
+            targets: vec![target.into_usize_safe()],
+        }
+    }
+
+    fn jump(targets: Vec<u32>) -> Self {
+        Self {
+            falls_through: false,
+            targets: targets.into_iter().map(u32::into_usize_safe).collect(),
+        }
+    }
+
+    fn end() -> Self {
+        Self::jump(Vec::new())
+    }
+}
+
+/// How a method is invoked
+#[derive(Clone, Copy, PartialEq)]
+enum InvokeKind {
+    Virtual,
+    Special,
+    Static,
+    Interface,
+    Dynamic,
+}
+
+struct MethodVerifier<'c, 'a> {
+    class_file: &'c ClassFile<'a>,
+    method: &'c ClassFileMethod<'a>,
+    code: &'c ClassFileMethodCode<'a>,
+    instructions: Vec<(usize, Instruction)>,
+    max_stack: usize,
+    max_locals: usize,
+    /// The frame at the start of the method, derived from its descriptor
+    initial_frame: Frame<'c>,
+    /// The frames declared in the stack map table, by address
+    frames: BTreeMap<usize, Frame<'c>>,
+}
+
+impl<'c, 'a> MethodVerifier<'c, 'a> {
+    fn new(
+        class_file: &'c ClassFile<'a>,
+        method: &'c ClassFileMethod<'a>,
+        code: &'c ClassFileMethodCode<'a>,
+    ) -> Result<Self, (usize, String)> {
+        let instructions = Instruction::parse_instructions(&code.code)
+            .map_err(|err| (0, format!("Invalid bytecode: {err}")))?;
+        let mut verifier = Self {
+            class_file,
+            method,
+            code,
+            instructions,
+            max_stack: code.max_stack.into_usize_safe(),
+            max_locals: code.max_locals.into_usize_safe(),
+            initial_frame: Frame {
+                locals: Vec::new(),
+                stack: Vec::new(),
+            },
+            frames: BTreeMap::new(),
+        };
+        verifier.expand_frames()?;
+        Ok(verifier)
+    }
+
+    /// Computes the full frames from the ones of the stack map table, which are
+    /// expressed as differences with respect to the previous one
+    fn expand_frames(&mut self) -> Result<(), (usize, String)> {
+        let (class_file, method, code) = (self.class_file, self.method, self.code);
+        let mut declared_locals: Vec<VerificationType<'c>> = Vec::new();
+        if !method.is_static() {
+            if method.name == "<init>" && class_file.name != "java/lang/Object" {
+                declared_locals.push(VerificationType::UninitializedThis);
+            } else {
+                declared_locals.push(VerificationType::Object(Cow::Borrowed(&class_file.name)));
+            }
+        }
+        declared_locals.extend(
+            method
+                .parsed_type_descriptor
+                .parameters
+                .iter()
+                .map(verification_type_of),
+        );
+        self.initial_frame = Frame {
+            locals: self
+                .expand_locals(&declared_locals)
+                .map_err(|reason| (0, reason))?,
+            stack: Vec::new(),
+        };
+
+        let Some(stack_map_table) = &code.stack_map_table else {
+            return Ok(());
+        };
+        for frame in stack_map_table.frames() {
+            let pc = frame.pc.0.into_usize_safe();
+            let stack = match &frame.kind {
+                StackMapFrameKind::Same => Vec::new(),
+                StackMapFrameKind::SameLocalsOneStackItem(item) => vec![item.clone()],
+                StackMapFrameKind::Chop(count) => {
+                    let remaining = declared_locals
+                        .len()
+                        .checked_sub(count.into_usize_safe())
+                        .ok_or_else(|| (pc, "Chop frame removes too many locals".to_string()))?;
+                    declared_locals.truncate(remaining);
+                    Vec::new()
+                }
+                StackMapFrameKind::Append(locals) => {
+                    declared_locals.extend(locals.iter().cloned());
+                    Vec::new()
+                }
+                StackMapFrameKind::Full { locals, stack } => {
+                    declared_locals = locals.clone();
+                    stack.clone()
+                }
+            };
+            if self.instruction_at(pc).is_none() {
+                return Err((
+                    pc,
+                    "Stack map frame is not at the start of an instruction".to_string(),
+                ));
+            }
+            let expanded = Frame {
+                locals: self
+                    .expand_locals(&declared_locals)
+                    .map_err(|reason| (pc, reason))?,
+                stack: self.expand_stack(&stack).map_err(|reason| (pc, reason))?,
+            };
+            self.frames.insert(pc, expanded);
+        }
+        Ok(())
+    }
+
+    fn expand_locals(
+        &self,
+        declared: &[VerificationType<'c>],
+    ) -> VerifyResult<Vec<VerificationType<'c>>> {
+        let mut locals = expand_wide_types(declared);
+        if locals.len() > self.max_locals {
+            return Err(format!(
+                "Frame has {} locals, more than max_locals {}",
+                locals.len(),
+                self.max_locals
+            ));
+        }
+        locals.resize(self.max_locals, VerificationType::Top);
+        Ok(locals)
+    }
+
+    fn expand_stack(
+        &self,
+        declared: &[VerificationType<'c>],
+    ) -> VerifyResult<Vec<VerificationType<'c>>> {
+        let stack = expand_wide_types(declared);
+        if stack.len() > self.max_stack {
+            return Err(format!(
+                "Frame has a stack of {} entries, more than max_stack {}",
+                stack.len(),
+                self.max_stack
+            ));
+        }
+        Ok(stack)
+    }
+
+    fn instruction_at(&self, pc: usize) -> Option<&Instruction> {
+        self.instructions
+            .binary_search_by_key(&pc, |(address, _)| *address)
+            .ok()
+            .map(|index| &self.instructions[index].1)
+    }
+
+    /// Walks the instructions in order, checking that each one is applicable to the types
+    /// computed so far, and that the state is compatible with the declared frame wherever
+    /// the code can jump to
+    fn verify(&self) -> Result<(), (usize, String)> {
+        let first_subroutine_instruction = self.instructions.iter().find(|(_, instruction)| {
+            matches!(
+                instruction,
+                Instruction::Jsr(_) | Instruction::Jsr_w(_) | Instruction::Ret(_)
+            )
+        });
+        if let Some((pc, _)) = first_subroutine_instruction {
+            // Version 50 classes using subroutines are verified by type inference in the
+            // reference implementation, so we cannot check them
+            return if self.class_file.version == ClassFileVersion::Jdk6 {
+                Ok(())
+            } else {
+                Err((
+                    *pc,
+                    "jsr and ret are not allowed in class files version 51 or later".to_string(),
+                ))
+            };
+        }
+
+        let mut current = Some(self.initial_frame.clone());
+        for (pc, instruction) in self.instructions.iter() {
+            current = self
+                .verify_instruction(*pc, instruction, current)
+                .map_err(|reason| (*pc, reason))?;
+        }
+        match (current, self.instructions.last()) {
+            (Some(_), Some((last_pc, _))) => {
+                Err((*last_pc, "Falling off the end of the code".to_string()))
+            }
+            (Some(_), None) => Err((0, "Empty code".to_string())),
+            (None, _) => Ok(()),
+        }
+    }
+
+    /// Checks one instruction, given the state after the previous one (if it can fall
+    /// through), and returns the state for the next instruction
+    fn verify_instruction(
+        &self,
+        pc: usize,
+        instruction: &Instruction,
+        current: Option<Frame<'c>>,
+    ) -> VerifyResult<Option<Frame<'c>>> {
+        let mut frame = match (current, self.frames.get(&pc)) {
+            (Some(current), Some(declared)) => {
+                self.check_assignable_frame(&current, declared)?;
+                declared.clone()
+            }
+            (None, Some(declared)) => declared.clone(),
+            (Some(current), None) => current,
+            (None, None) => {
+                return Err("Expecting a stackmap frame after an unconditional branch".to_string());
+            }
+        };
+        self.check_exception_handlers(pc, &frame)?;
+
+        let flow = self.execute(pc, instruction, &mut frame)?;
+        for target in flow.targets {
+            let declared = self
+                .frames
+                .get(&target)
+                .ok_or_else(|| format!("Expecting a stackmap frame at branch target {target}"))?;
+            self.check_assignable_frame(&frame, declared)?;
+        }
+        Ok(flow.falls_through.then_some(frame))
+    }
+
+    /// Every handler covering an instruction must accept its locals, with only the
+    /// exception on the stack
+    fn check_exception_handlers(&self, pc: usize, frame: &Frame<'c>) -> VerifyResult<()> {
+        for entry in self.code.exception_table.lookup(to_program_counter(pc)) {
+            let handler_pc = entry.handler_pc.0.into_usize_safe();
+            let declared = self.frames.get(&handler_pc).ok_or_else(|| {
+                format!("Expecting a stackmap frame at exception handler {handler_pc}")
+            })?;
+            let catch_class = entry
+                .catch_class
+                .as_deref()
+                .unwrap_or("java/lang/Throwable");
+            let handler_frame = Frame {
+                locals: frame.locals.clone(),
+                stack: vec![VerificationType::Object(Cow::Borrowed(catch_class))],
+            };
+            self.check_assignable_frame(&handler_frame, declared)?;
+        }
+        Ok(())
+    }
+
+    fn check_assignable_frame(
+        &self,
+        current: &Frame<'c>,
+        declared: &Frame<'c>,
+    ) -> VerifyResult<()> {
+        if current.stack.len() != declared.stack.len() {
+            return Err(format!(
+                "Inconsistent stack height {} != {}",
+                current.stack.len(),
+                declared.stack.len()
+            ));
+        }
+        for (kind, current_types, declared_types) in [
+            ("locals", &current.locals, &declared.locals),
+            ("stack", &current.stack, &declared.stack),
+        ] {
+            for (index, (from, to)) in current_types.iter().zip(declared_types).enumerate() {
+                if !is_assignable(from, to) {
+                    return Err(format!(
+                        "Type {} (current frame, {kind}[{index}]) is not assignable to {} (stack map, {kind}[{index}])",
+                        describe(from),
+                        describe(to)
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn execute(
+        &self,
+        pc: usize,
+        instruction: &Instruction,
+        frame: &mut Frame<'c>,
+    ) -> VerifyResult<Flow> {
+        use VerificationType::{Double, Float, Integer, Long, Null};
+
+        match instruction {
+            Instruction::Nop => {}
+
+            Instruction::Aconst_null => self.push(frame, Null)?,
+            Instruction::Iconst_m1
+            | Instruction::Iconst_0
+            | Instruction::Iconst_1
+            | Instruction::Iconst_2
+            | Instruction::Iconst_3
+            | Instruction::Iconst_4
+            | Instruction::Iconst_5
+            | Instruction::Bipush(_)
+            | Instruction::Sipush(_) => self.push(frame, Integer)?,
+            Instruction::Lconst_0 | Instruction::Lconst_1 => self.push(frame, Long)?,
+            Instruction::Fconst_0 | Instruction::Fconst_1 | Instruction::Fconst_2 => {
+                self.push(frame, Float)?
+            }
+            Instruction::Dconst_0 | Instruction::Dconst_1 => self.push(frame, Double)?,
+            Instruction::Ldc(index) => self.ldc(frame, u16::from(*index))?,
+            Instruction::Ldc_w(index) => self.ldc(frame, *index)?,
+            Instruction::Ldc2_w(index) => {
+                let value_type = match self.constant(*index)? {
+                    ConstantPoolEntry::Long(_) => Long,
+                    ConstantPoolEntry::Double(_) => Double,
+                    _ => return Err(format!("Invalid constant pool index {index} for ldc2_w")),
+                };
+                self.push(frame, value_type)?
+            }
+
+            Instruction::Iload(index) => self.load(frame, *index, Integer)?,
+            Instruction::Iload_0 => self.load(frame, 0, Integer)?,
+            Instruction::Iload_1 => self.load(frame, 1, Integer)?,
+            Instruction::Iload_2 => self.load(frame, 2, Integer)?,
+            Instruction::Iload_3 => self.load(frame, 3, Integer)?,
+            Instruction::Lload(index) => self.load(frame, *index, Long)?,
+            Instruction::Lload_0 => self.load(frame, 0, Long)?,
+            Instruction::Lload_1 => self.load(frame, 1, Long)?,
+            Instruction::Lload_2 => self.load(frame, 2, Long)?,
+            Instruction::Lload_3 => self.load(frame, 3, Long)?,
+            Instruction::Fload(index) => self.load(frame, *index, Float)?,
+            Instruction::Fload_0 => self.load(frame, 0, Float)?,
+            Instruction::Fload_1 => self.load(frame, 1, Float)?,
+            Instruction::Fload_2 => self.load(frame, 2, Float)?,
+            Instruction::Fload_3 => self.load(frame, 3, Float)?,
+            Instruction::Dload(index) => self.load(frame, *index, Double)?,
+            Instruction::Dload_0 => self.load(frame, 0, Double)?,
+            Instruction::Dload_1 => self.load(frame, 1, Double)?,
+            Instruction::Dload_2 => self.load(frame, 2, Double)?,
+            Instruction::Dload_3 => self.load(frame, 3, Double)?,
+            Instruction::Aload(index) => self.load_reference(frame, *index)?,
+            Instruction::Aload_0 => self.load_reference(frame, 0)?,
+            Instruction::Aload_1 => self.load_reference(frame, 1)?,
+            Instruction::Aload_2 => self.load_reference(frame, 2)?,
+            Instruction::Aload_3 => self.load_reference(frame, 3)?,
+
+            Instruction::Istore(index) => self.store(frame, *index, Integer)?,
+            Instruction::Istore_0 => self.store(frame, 0, Integer)?,
+            Instruction::Istore_1 => self.store(frame, 1, Integer)?,
+            Instruction::Istore_2 => self.store(frame, 2, Integer)?,
+            Instruction::Istore_3 => self.store(frame, 3, Integer)?,
+            Instruction::Lstore(index) => self.store(frame, *index, Long)?,
+            Instruction::Lstore_0 => self.store(frame, 0, Long)?,
+            Instruction::Lstore_1 => self.store(frame, 1, Long)?,
+            Instruction::Lstore_2 => self.store(frame, 2, Long)?,
+            Instruction::Lstore_3 => self.store(frame, 3, Long)?,
+            Instruction::Fstore(index) => self.store(frame, *index, Float)?,
+            Instruction::Fstore_0 => self.store(frame, 0, Float)?,
+            Instruction::Fstore_1 => self.store(frame, 1, Float)?,
+            Instruction::Fstore_2 => self.store(frame, 2, Float)?,
+            Instruction::Fstore_3 => self.store(frame, 3, Float)?,
+            Instruction::Dstore(index) => self.store(frame, *index, Double)?,
+            Instruction::Dstore_0 => self.store(frame, 0, Double)?,
+            Instruction::Dstore_1 => self.store(frame, 1, Double)?,
+            Instruction::Dstore_2 => self.store(frame, 2, Double)?,
+            Instruction::Dstore_3 => self.store(frame, 3, Double)?,
+            Instruction::Astore(index) => self.store_reference(frame, *index)?,
+            Instruction::Astore_0 => self.store_reference(frame, 0)?,
+            Instruction::Astore_1 => self.store_reference(frame, 1)?,
+            Instruction::Astore_2 => self.store_reference(frame, 2)?,
+            Instruction::Astore_3 => self.store_reference(frame, 3)?,
+            Instruction::Iinc(index, _) => self.check_local(frame, *index, &Integer)?,
+
+            Instruction::Iaload => self.array_load(frame, &["[I"], Integer)?,
+            Instruction::Baload => self.array_load(frame, &["[B", "[Z"], Integer)?,
+            Instruction::Caload => self.array_load(frame, &["[C"], Integer)?,
+            Instruction::Saload => self.array_load(frame, &["[S"], Integer)?,
+            Instruction::Laload => self.array_load(frame, &["[J"], Long)?,
+            Instruction::Faload => self.array_load(frame, &["[F"], Float)?,
+            Instruction::Daload => self.array_load(frame, &["[D"], Double)?,
+            Instruction::Aaload => {
+                self.pop(frame, &Integer)?;
+                let element_type = match self.pop_slot(frame)? {
+                    Null => Null,
+                    VerificationType::Object(array_class) => {
+                        match array_class.strip_prefix('[').and_then(component_class) {
+                            Some(component) => {
+                                VerificationType::Object(Cow::Owned(component.to_string()))
+                            }
+                            None => return Err(bad_array_type("aaload", &array_class)),
+                        }
+                    }
+                    other => return Err(bad_array_type("aaload", &describe(&other))),
+                };
+                self.push(frame, element_type)?
+            }
+            Instruction::Iastore => self.array_store(frame, &["[I"], Integer)?,
+            Instruction::Bastore => self.array_store(frame, &["[B", "[Z"], Integer)?,
+            Instruction::Castore => self.array_store(frame, &["[C"], Integer)?,
+            Instruction::Sastore => self.array_store(frame, &["[S"], Integer)?,
+            Instruction::Lastore => self.array_store(frame, &["[J"], Long)?,
+            Instruction::Fastore => self.array_store(frame, &["[F"], Float)?,
+            Instruction::Dastore => self.array_store(frame, &["[D"], Double)?,
+            Instruction::Aastore => {
+                // The type of the value is checked against the array's at runtime
+                self.pop_object(frame)?;
+                self.pop(frame, &Integer)?;
+                match self.pop_slot(frame)? {
+                    Null => {}
+                    VerificationType::Object(array_class)
+                        if array_class
+                            .strip_prefix('[')
+                            .and_then(component_class)
+                            .is_some() => {}
+                    other => return Err(bad_array_type("aastore", &describe(&other))),
+                }
+            }
+
+            Instruction::Pop => {
+                self.check_category_boundary(frame, 1)?;
+                let [_] = self.pop_slots(frame)?;
+            }
+            Instruction::Pop2 => {
+                self.check_category_boundary(frame, 2)?;
+                let [_, _] = self.pop_slots(frame)?;
+            }
+            Instruction::Dup => {
+                self.check_category_boundary(frame, 1)?;
+                let [v1] = self.pop_slots(frame)?;
+                self.push_slots(frame, [v1.clone(), v1])?;
+            }
+            Instruction::Dup_x1 => {
+                self.check_category_boundary(frame, 1)?;
+                self.check_category_boundary(frame, 2)?;
+                let [v1, v2] = self.pop_slots(frame)?;
+                self.push_slots(frame, [v1.clone(), v2, v1])?;
+            }
+            Instruction::Dup_x2 => {
+                self.check_category_boundary(frame, 1)?;
+                self.check_category_boundary(frame, 3)?;
+                let [v1, v2, v3] = self.pop_slots(frame)?;
+                self.push_slots(frame, [v1.clone(), v3, v2, v1])?;
+            }
+            Instruction::Dup2 => {
+                self.check_category_boundary(frame, 2)?;
+                let [v1, v2] = self.pop_slots(frame)?;
+                self.push_slots(frame, [v2.clone(), v1.clone(), v2, v1])?;
+            }
+            Instruction::Dup2_x1 => {
+                self.check_category_boundary(frame, 2)?;
+                self.check_category_boundary(frame, 3)?;
+                let [v1, v2, v3] = self.pop_slots(frame)?;
+                self.push_slots(frame, [v2.clone(), v1.clone(), v3, v2, v1])?;
+            }
+            Instruction::Dup2_x2 => {
+                self.check_category_boundary(frame, 2)?;
+                self.check_category_boundary(frame, 4)?;
+                let [v1, v2, v3, v4] = self.pop_slots(frame)?;
+                self.push_slots(frame, [v2.clone(), v1.clone(), v4, v3, v2, v1])?;
+            }
+            Instruction::Swap => {
+                self.check_category_boundary(frame, 1)?;
+                self.check_category_boundary(frame, 2)?;
+                let [v1, v2] = self.pop_slots(frame)?;
+                self.push_slots(frame, [v1, v2])?;
+            }
+
+            Instruction::Iadd
+            | Instruction::Isub
+            | Instruction::Imul
+            | Instruction::Idiv
+            | Instruction::Irem
+            | Instruction::Iand
+            | Instruction::Ior
+            | Instruction::Ixor
+            | Instruction::Ishl
+            | Instruction::Ishr
+            | Instruction::Iushr => self.transform(frame, &[Integer, Integer], Integer)?,
+            Instruction::Ladd
+            | Instruction::Lsub
+            | Instruction::Lmul
+            | Instruction::Ldiv
+            | Instruction::Lrem
+            | Instruction::Land
+            | Instruction::Lor
+            | Instruction::Lxor => self.transform(frame, &[Long, Long], Long)?,
+            Instruction::Lshl | Instruction::Lshr | Instruction::Lushr => {
+                self.transform(frame, &[Long, Integer], Long)?
+            }
+            Instruction::Fadd
+            | Instruction::Fsub
+            | Instruction::Fmul
+            | Instruction::Fdiv
+            | Instruction::Frem => self.transform(frame, &[Float, Float], Float)?,
+            Instruction::Dadd
+            | Instruction::Dsub
+            | Instruction::Dmul
+            | Instruction::Ddiv
+            | Instruction::Drem => self.transform(frame, &[Double, Double], Double)?,
+            Instruction::Ineg => self.transform(frame, &[Integer], Integer)?,
+            Instruction::Lneg => self.transform(frame, &[Long], Long)?,
+            Instruction::Fneg => self.transform(frame, &[Float], Float)?,
+            Instruction::Dneg => self.transform(frame, &[Double], Double)?,
+
+            Instruction::I2l => self.transform(frame, &[Integer], Long)?,
+            Instruction::I2f => self.transform(frame, &[Integer], Float)?,
+            Instruction::I2d => self.transform(frame, &[Integer], Double)?,
+            Instruction::I2b | Instruction::I2c | Instruction::I2s => {
+                self.transform(frame, &[Integer], Integer)?
+            }
+            Instruction::L2i => self.transform(frame, &[Long], Integer)?,
+            Instruction::L2f => self.transform(frame, &[Long], Float)?,
+            Instruction::L2d => self.transform(frame, &[Long], Double)?,
+            Instruction::F2i => self.transform(frame, &[Float], Integer)?,
+            Instruction::F2l => self.transform(frame, &[Float], Long)?,
+            Instruction::F2d => self.transform(frame, &[Float], Double)?,
+            Instruction::D2i => self.transform(frame, &[Double], Integer)?,
+            Instruction::D2l => self.transform(frame, &[Double], Long)?,
+            Instruction::D2f => self.transform(frame, &[Double], Float)?,
+
+            Instruction::Lcmp => self.transform(frame, &[Long, Long], Integer)?,
+            Instruction::Fcmpl | Instruction::Fcmpg => {
+                self.transform(frame, &[Float, Float], Integer)?
+            }
+            Instruction::Dcmpl | Instruction::Dcmpg => {
+                self.transform(frame, &[Double, Double], Integer)?
+            }
+
+            Instruction::Ifeq(target)
+            | Instruction::Ifne(target)
+            | Instruction::Iflt(target)
+            | Instruction::Ifge(target)
+            | Instruction::Ifgt(target)
+            | Instruction::Ifle(target) => {
+                self.pop(frame, &Integer)?;
+                return Ok(Flow::branch(*target));
+            }
+            Instruction::If_icmpeq(target)
+            | Instruction::If_icmpne(target)
+            | Instruction::If_icmplt(target)
+            | Instruction::If_icmpge(target)
+            | Instruction::If_icmpgt(target)
+            | Instruction::If_icmple(target) => {
+                self.pop(frame, &Integer)?;
+                self.pop(frame, &Integer)?;
+                return Ok(Flow::branch(*target));
+            }
+            Instruction::If_acmpeq(target) | Instruction::If_acmpne(target) => {
+                self.pop_reference(frame)?;
+                self.pop_reference(frame)?;
+                return Ok(Flow::branch(*target));
+            }
+            Instruction::Ifnull(target) | Instruction::Ifnonnull(target) => {
+                self.pop_reference(frame)?;
+                return Ok(Flow::branch(*target));
+            }
+            Instruction::Goto(target) | Instruction::Goto_w(target) => {
+                return Ok(Flow::jump(vec![*target]));
+            }
+            Instruction::Tableswitch(table) | Instruction::Lookupswitch(table) => {
+                self.pop(frame, &Integer)?;
+                let targets = table
+                    .jump_addresses(&self.code.code)
+                    .map_err(|err| format!("Invalid switch: {err}"))?;
+                return Ok(Flow::jump(targets));
+            }
+            Instruction::Jsr(_) | Instruction::Jsr_w(_) | Instruction::Ret(_) => {
+                return Err("Subroutines are not supported".to_string());
+            }
+
+            Instruction::Ireturn => {
+                self.check_return_type(Some(&Integer))?;
+                self.pop(frame, &Integer)?;
+                return Ok(Flow::end());
+            }
+            Instruction::Lreturn => {
+                self.check_return_type(Some(&Long))?;
+                self.pop(frame, &Long)?;
+                return Ok(Flow::end());
+            }
+            Instruction::Freturn => {
+                self.check_return_type(Some(&Float))?;
+                self.pop(frame, &Float)?;
+                return Ok(Flow::end());
+            }
+            Instruction::Dreturn => {
+                self.check_return_type(Some(&Double))?;
+                self.pop(frame, &Double)?;
+                return Ok(Flow::end());
+            }
+            Instruction::Areturn => {
+                match self.return_type() {
+                    Some(return_type @ VerificationType::Object(_)) => {
+                        self.pop(frame, &return_type)?
+                    }
+                    _ => return Err("Method does not return a reference".to_string()),
+                };
+                return Ok(Flow::end());
+            }
+            Instruction::Return => {
+                self.check_return_type(None)?;
+                if self.method.name == "<init>"
+                    && frame.locals.contains(&VerificationType::UninitializedThis)
+                {
+                    return Err("Constructor must call super() or this() before return".to_string());
+                }
+                return Ok(Flow::end());
+            }
+            Instruction::Athrow => {
+                self.pop(frame, &object_type("java/lang/Throwable"))?;
+                return Ok(Flow::end());
+            }
+
+            Instruction::Getstatic(index) => {
+                let (_, field_type) = self.field_reference(*index)?;
+                self.push(frame, field_type)?
+            }
+            Instruction::Putstatic(index) => {
+                let (_, field_type) = self.field_reference(*index)?;
+                self.pop(frame, &field_type)?;
+            }
+            Instruction::Getfield(index) => {
+                let (class_name, field_type) = self.field_reference(*index)?;
+                self.pop(frame, &object_type(class_name))?;
+                self.push(frame, field_type)?
+            }
+            Instruction::Putfield(index) => {
+                let (class_name, field_type) = self.field_reference(*index)?;
+                self.pop(frame, &field_type)?;
+                // Constructors can assign the fields of their class before calling super()
+                let receiver = self.pop_slot(frame)?;
+                let is_own_field_of_uninitialized_this = receiver
+                    == VerificationType::UninitializedThis
+                    && class_name == self.class_file.name;
+                if !is_own_field_of_uninitialized_this
+                    && !is_assignable(&receiver, &object_type(class_name))
+                {
+                    return Err(format!(
+                        "Bad type on operand stack: type {} is not assignable to {class_name}",
+                        describe(&receiver)
+                    ));
+                }
+            }
+
+            Instruction::Invokevirtual(index) => self.invoke(frame, *index, InvokeKind::Virtual)?,
+            Instruction::Invokespecial(index) => self.invoke(frame, *index, InvokeKind::Special)?,
+            Instruction::Invokestatic(index) => self.invoke(frame, *index, InvokeKind::Static)?,
+            Instruction::Invokeinterface(index, _) => {
+                self.invoke(frame, *index, InvokeKind::Interface)?
+            }
+            Instruction::Invokedynamic(index) => self.invoke(frame, *index, InvokeKind::Dynamic)?,
+
+            Instruction::New(index) => {
+                let class_name = self.class_name(*index)?;
+                if class_name.starts_with('[') {
+                    return Err(format!("Illegal use of new with array class {class_name}"));
+                }
+                self.push(
+                    frame,
+                    VerificationType::Uninitialized(to_program_counter(pc)),
+                )?
+            }
+            Instruction::Newarray(array_type) => {
+                self.pop(frame, &Integer)?;
+                let descriptor = match array_type {
+                    NewArrayType::Boolean => "[Z",
+                    NewArrayType::Char => "[C",
+                    NewArrayType::Float => "[F",
+                    NewArrayType::Double => "[D",
+                    NewArrayType::Byte => "[B",
+                    NewArrayType::Short => "[S",
+                    NewArrayType::Int => "[I",
+                    NewArrayType::Long => "[J",
+                };
+                self.push(frame, object_type(descriptor))?
+            }
+            Instruction::Anewarray(index) => {
+                self.pop(frame, &Integer)?;
+                let class_name = self.class_name(*index)?;
+                let array_class = if class_name.starts_with('[') {
+                    format!("[{class_name}")
+                } else {
+                    format!("[L{class_name};")
+                };
+                self.push(frame, VerificationType::Object(Cow::Owned(array_class)))?
+            }
+            Instruction::Multianewarray(index, dimensions) => {
+                let class_name = self.class_name(*index)?;
+                let array_dimensions = class_name.chars().take_while(|c| *c == '[').count();
+                if *dimensions == 0 || array_dimensions < dimensions.into_usize_safe() {
+                    return Err(format!(
+                        "Illegal dimension {dimensions} for array class {class_name}"
+                    ));
+                }
+                for _ in 0..*dimensions {
+                    self.pop(frame, &Integer)?;
+                }
+                self.push(frame, object_type(class_name))?
+            }
+            Instruction::Arraylength => match self.pop_slot(frame)? {
+                Null => self.push(frame, Integer)?,
+                VerificationType::Object(class_name) if class_name.starts_with('[') => {
+                    self.push(frame, Integer)?
+                }
+                other => return Err(bad_array_type("arraylength", &describe(&other))),
+            },
+
+            Instruction::Checkcast(index) => {
+                let class_name = self.class_name(*index)?;
+                self.pop_object(frame)?;
+                self.push(frame, object_type(class_name))?
+            }
+            Instruction::Instanceof(index) => {
+                self.class_name(*index)?;
+                self.pop_object(frame)?;
+                self.push(frame, Integer)?
+            }
+            Instruction::Monitorenter | Instruction::Monitorexit => {
+                self.pop_object(frame)?;
+            }
+        }
+        Ok(Flow::next())
+    }
+
+    fn push(&self, frame: &mut Frame<'c>, value_type: VerificationType<'c>) -> VerifyResult<()> {
+        if value_type.is_wide() {
+            self.push_slots(frame, [value_type, VerificationType::Top])
+        } else {
+            self.push_slots(frame, [value_type])
+        }
+    }
+
+    fn push_slots<const N: usize>(
+        &self,
+        frame: &mut Frame<'c>,
+        slots: [VerificationType<'c>; N],
+    ) -> VerifyResult<()> {
+        frame.stack.extend(slots);
+        if frame.stack.len() > self.max_stack {
+            return Err("Operand stack overflow".to_string());
+        }
+        Ok(())
+    }
+
+    fn pop_slot(&self, frame: &mut Frame<'c>) -> VerifyResult<VerificationType<'c>> {
+        frame
+            .stack
+            .pop()
+            .ok_or_else(|| "Operand stack underflow".to_string())
+    }
+
+    /// Pops the given number of slots, returning the topmost first
+    fn pop_slots<const N: usize>(
+        &self,
+        frame: &mut Frame<'c>,
+    ) -> VerifyResult<[VerificationType<'c>; N]> {
+        let mut slots: [VerificationType<'c>; N] = std::array::from_fn(|_| VerificationType::Top);
+        for slot in slots.iter_mut() {
+            *slot = self.pop_slot(frame)?;
+        }
+        Ok(slots)
+    }
+
+    /// Pops a value of the given type, or of one assignable to it
+    fn pop(
+        &self,
+        frame: &mut Frame<'c>,
+        expected: &VerificationType<'c>,
+    ) -> VerifyResult<VerificationType<'c>> {
+        if expected.is_wide() {
+            let second_half = self.pop_slot(frame)?;
+            if second_half != VerificationType::Top {
+                return Err(bad_stack_type(&second_half, expected));
+            }
+        }
+        let value = self.pop_slot(frame)?;
+        if !is_assignable(&value, expected) {
+            return Err(bad_stack_type(&value, expected));
+        }
+        Ok(value)
+    }
+
+    /// Pops a reference to an object, initialized or not
+    fn pop_reference(&self, frame: &mut Frame<'c>) -> VerifyResult<VerificationType<'c>> {
+        let value = self.pop_slot(frame)?;
+        if !is_reference(&value) {
+            return Err(format!(
+                "Bad type on operand stack: type {} is not a reference",
+                describe(&value)
+            ));
+        }
+        Ok(value)
+    }
+
+    /// Pops a reference to an initialized object
+    fn pop_object(&self, frame: &mut Frame<'c>) -> VerifyResult<VerificationType<'c>> {
+        self.pop(frame, &object_type("java/lang/Object"))
+    }
+
+    /// Pops the given types, in reverse order, and pushes the result
+    fn transform(
+        &self,
+        frame: &mut Frame<'c>,
+        operands: &[VerificationType<'c>],
+        result: VerificationType<'c>,
+    ) -> VerifyResult<()> {
+        for operand in operands.iter().rev() {
+            self.pop(frame, operand)?;
+        }
+        self.push(frame, result)
+    }
+
+    /// Instructions that manipulate the stack without knowing the types of the values must
+    /// not split a long or a double: the given depth must be the start of a value
+    fn check_category_boundary(&self, frame: &Frame<'c>, depth: usize) -> VerifyResult<()> {
+        let index = frame
+            .stack
+            .len()
+            .checked_sub(depth)
+            .ok_or_else(|| "Operand stack underflow".to_string())?;
+        if frame.stack[index] == VerificationType::Top {
+            return Err(
+                "Bad type on operand stack: the instruction would split a long or a double"
+                    .to_string(),
+            );
+        }
+        Ok(())
+    }
+
+    fn check_local_index(&self, index: usize, value_type: &VerificationType) -> VerifyResult<()> {
+        let slots = if value_type.is_wide() { 2 } else { 1 };
+        if index + slots > self.max_locals {
+            return Err(format!("Illegal local variable number {index}"));
+        }
+        Ok(())
+    }
+
+    fn check_local(
+        &self,
+        frame: &Frame<'c>,
+        index: u16,
+        expected: &VerificationType<'c>,
+    ) -> VerifyResult<()> {
+        let index = index.into_usize_safe();
+        self.check_local_index(index, expected)?;
+        let value = &frame.locals[index];
+        let is_valid = is_assignable(value, expected)
+            && (!expected.is_wide() || frame.locals[index + 1] == VerificationType::Top);
+        if !is_valid {
+            return Err(format!(
+                "Bad local variable type: type {} (current frame, locals[{index}]) is not assignable to {}",
+                describe(value),
+                describe(expected)
+            ));
+        }
+        Ok(())
+    }
+
+    fn load(
+        &self,
+        frame: &mut Frame<'c>,
+        index: u16,
+        expected: VerificationType<'c>,
+    ) -> VerifyResult<()> {
+        self.check_local(frame, index, &expected)?;
+        self.push(frame, expected)
+    }
+
+    fn load_reference(&self, frame: &mut Frame<'c>, index: u16) -> VerifyResult<()> {
+        let index = index.into_usize_safe();
+        self.check_local_index(index, &VerificationType::Null)?;
+        let value = frame.locals[index].clone();
+        if !is_reference(&value) {
+            return Err(format!(
+                "Bad local variable type: type {} (current frame, locals[{index}]) is not a reference",
+                describe(&value)
+            ));
+        }
+        self.push(frame, value)
+    }
+
+    fn store(
+        &self,
+        frame: &mut Frame<'c>,
+        index: u16,
+        expected: VerificationType<'c>,
+    ) -> VerifyResult<()> {
+        let value = self.pop(frame, &expected)?;
+        self.set_local(frame, index.into_usize_safe(), value)
+    }
+
+    fn store_reference(&self, frame: &mut Frame<'c>, index: u16) -> VerifyResult<()> {
+        let value = self.pop_reference(frame)?;
+        self.set_local(frame, index.into_usize_safe(), value)
+    }
+
+    fn set_local(
+        &self,
+        frame: &mut Frame<'c>,
+        index: usize,
+        value: VerificationType<'c>,
+    ) -> VerifyResult<()> {
+        self.check_local_index(index, &value)?;
+        // Overwriting the second half of a long or a double invalidates it
+        if index > 0 && frame.locals[index - 1].is_wide() {
+            frame.locals[index - 1] = VerificationType::Top;
+        }
+        if value.is_wide() {
+            frame.locals[index + 1] = VerificationType::Top;
+        }
+        frame.locals[index] = value;
+        Ok(())
+    }
+
+    fn array_load(
+        &self,
+        frame: &mut Frame<'c>,
+        array_classes: &[&str],
+        element_type: VerificationType<'c>,
+    ) -> VerifyResult<()> {
+        self.pop(frame, &VerificationType::Integer)?;
+        self.pop_array(frame, array_classes)?;
+        self.push(frame, element_type)
+    }
+
+    fn array_store(
+        &self,
+        frame: &mut Frame<'c>,
+        array_classes: &[&str],
+        element_type: VerificationType<'c>,
+    ) -> VerifyResult<()> {
+        self.pop(frame, &element_type)?;
+        self.pop(frame, &VerificationType::Integer)?;
+        self.pop_array(frame, array_classes)
+    }
+
+    fn pop_array(&self, frame: &mut Frame<'c>, array_classes: &[&str]) -> VerifyResult<()> {
+        match self.pop_slot(frame)? {
+            VerificationType::Null => Ok(()),
+            VerificationType::Object(class_name) if array_classes.contains(&&*class_name) => Ok(()),
+            other => Err(format!(
+                "Bad type on operand stack in array access: type {} is not an array of {}",
+                describe(&other),
+                array_classes.join(" or ")
+            )),
+        }
+    }
+
+    fn return_type(&self) -> Option<VerificationType<'c>> {
+        self.method
+            .parsed_type_descriptor
+            .return_type
+            .as_ref()
+            .map(verification_type_of)
+    }
+
+    fn check_return_type(&self, expected: Option<&VerificationType<'c>>) -> VerifyResult<()> {
+        if self.return_type().as_ref() != expected {
+            return Err(format!(
+                "Method expects a return value of type {}",
+                self.return_type()
+                    .as_ref()
+                    .map(describe)
+                    .unwrap_or_else(|| "void".to_string())
+            ));
+        }
+        Ok(())
+    }
+
+    fn invoke(&self, frame: &mut Frame<'c>, index: u16, kind: InvokeKind) -> VerifyResult<()> {
+        let (class_name, method_name, descriptor) = self.method_reference(index, kind)?;
+        let is_constructor_call = kind == InvokeKind::Special && method_name == "<init>";
+        if method_name.starts_with('<') && !is_constructor_call {
+            return Err(format!("Illegal call to internal method {method_name}"));
+        }
+        let descriptor = MethodDescriptor::parse(descriptor)
+            .map_err(|err| format!("Invalid method descriptor {descriptor}: {err}"))?;
+        for parameter in descriptor.parameters.iter().rev() {
+            self.pop(frame, &verification_type_of(parameter))?;
+        }
+
+        if is_constructor_call {
+            let receiver = self.pop_slot(frame)?;
+            let initialized = match &receiver {
+                VerificationType::UninitializedThis => {
+                    VerificationType::Object(Cow::Borrowed(&self.class_file.name))
+                }
+                VerificationType::Uninitialized(new_pc) => {
+                    let new_pc = new_pc.0.into_usize_safe();
+                    match self.instruction_at(new_pc) {
+                        Some(Instruction::New(class_index)) => {
+                            object_type(self.class_name(*class_index)?)
+                        }
+                        _ => {
+                            return Err(format!(
+                                "Uninitialized object refers to pc {new_pc}, which is not a new instruction"
+                            ));
+                        }
+                    }
+                }
+                other => {
+                    return Err(format!(
+                        "Bad operand type when invoking <init>: type {} is already initialized",
+                        describe(other)
+                    ));
+                }
+            };
+            for slot in frame.locals.iter_mut().chain(frame.stack.iter_mut()) {
+                if *slot == receiver {
+                    *slot = initialized.clone();
+                }
+            }
+        } else if kind != InvokeKind::Static && kind != InvokeKind::Dynamic {
+            self.pop(frame, &object_type(class_name))?;
+        }
+
+        if let Some(return_type) = &descriptor.return_type {
+            self.push(frame, verification_type_of(return_type))?;
+        }
+        Ok(())
+    }
+
+    fn constant(&self, index: u16) -> VerifyResult<&'c ConstantPoolEntry<'a>> {
+        self.class_file
+            .constants
+            .get(index)
+            .map_err(|_| format!("Invalid constant pool index {index}"))
+    }
+
+    fn utf8(&self, index: u16) -> VerifyResult<&'c str> {
+        match self.constant(index)? {
+            ConstantPoolEntry::Utf8(string) => Ok(string),
+            _ => Err(format!("Constant pool index {index} is not a string")),
+        }
+    }
+
+    fn class_name(&self, index: u16) -> VerifyResult<&'c str> {
+        match self.constant(index)? {
+            ConstantPoolEntry::ClassReference(name_index) => self.utf8(*name_index),
+            _ => Err(format!("Constant pool index {index} is not a class")),
+        }
+    }
+
+    fn name_and_type(&self, index: u16) -> VerifyResult<(&'c str, &'c str)> {
+        match self.constant(index)? {
+            ConstantPoolEntry::NameAndTypeDescriptor(name_index, type_index) => {
+                Ok((self.utf8(*name_index)?, self.utf8(*type_index)?))
+            }
+            _ => Err(format!(
+                "Constant pool index {index} is not a name and type"
+            )),
+        }
+    }
+
+    /// Returns the class declaring the field and the type of the field
+    fn field_reference(&self, index: u16) -> VerifyResult<(&'c str, VerificationType<'c>)> {
+        match self.constant(index)? {
+            ConstantPoolEntry::FieldReference(class_index, name_and_type_index) => {
+                let (_, descriptor) = self.name_and_type(*name_and_type_index)?;
+                let field_type = FieldType::parse(descriptor)
+                    .map_err(|err| format!("Invalid field descriptor {descriptor}: {err}"))?;
+                Ok((
+                    self.class_name(*class_index)?,
+                    verification_type_of(&field_type),
+                ))
+            }
+            _ => Err(format!("Constant pool index {index} is not a field")),
+        }
+    }
+
+    /// Returns the class, the name and the descriptor of the invoked method
+    fn method_reference(
+        &self,
+        index: u16,
+        kind: InvokeKind,
+    ) -> VerifyResult<(&'c str, &'c str, &'c str)> {
+        match (kind, self.constant(index)?) {
+            (InvokeKind::Dynamic, ConstantPoolEntry::InvokeDynamic(_, name_and_type_index)) => {
+                let (name, descriptor) = self.name_and_type(*name_and_type_index)?;
+                Ok(("java/lang/Object", name, descriptor))
+            }
+            (InvokeKind::Interface, ConstantPoolEntry::InterfaceMethodReference(class, nat))
+            | (InvokeKind::Virtual, ConstantPoolEntry::MethodReference(class, nat))
+            | (
+                InvokeKind::Special | InvokeKind::Static,
+                ConstantPoolEntry::MethodReference(class, nat)
+                | ConstantPoolEntry::InterfaceMethodReference(class, nat),
+            ) => {
+                let (name, descriptor) = self.name_and_type(*nat)?;
+                Ok((self.class_name(*class)?, name, descriptor))
+            }
+            _ => Err(format!(
+                "Constant pool index {index} is not a method applicable to the invocation"
+            )),
+        }
+    }
+
+    fn ldc(&self, frame: &mut Frame<'c>, index: u16) -> VerifyResult<()> {
+        let value_type = match self.constant(index)? {
+            ConstantPoolEntry::Integer(_) => VerificationType::Integer,
+            ConstantPoolEntry::Float(_) => VerificationType::Float,
+            ConstantPoolEntry::StringReference(_) => object_type("java/lang/String"),
+            ConstantPoolEntry::ClassReference(_) => object_type("java/lang/Class"),
+            ConstantPoolEntry::MethodType(_) => object_type("java/lang/invoke/MethodType"),
+            ConstantPoolEntry::MethodHandle(_, _) => object_type("java/lang/invoke/MethodHandle"),
+            _ => return Err(format!("Invalid constant pool index {index} for ldc")),
+        };
+        self.push(frame, value_type)
+    }
+}
+
+fn to_program_counter(pc: usize) -> ProgramCounter {
+    // Code is at most 65535 bytes long
+    ProgramCounter(u32::try_from(pc).unwrap_or(u32::MAX))
+}
+
+fn object_type(class_name: &str) -> VerificationType<'_> {
+    VerificationType::Object(Cow::Borrowed(class_name))
+}
+
+/// Adds a [VerificationType::Top] after each long or double
+fn expand_wide_types<'c>(declared: &[VerificationType<'c>]) -> Vec<VerificationType<'c>> {
+    let mut slots = Vec::with_capacity(declared.len());
+    for value_type in declared {
+        slots.push(value_type.clone());
+        if value_type.is_wide() {
+            slots.push(VerificationType::Top);
+        }
+    }
+    slots
+}
+
+fn verification_type_of<'c>(field_type: &FieldType) -> VerificationType<'c> {
+    match field_type {
+        FieldType::Base(BaseType::Long) => VerificationType::Long,
+        FieldType::Base(BaseType::Float) => VerificationType::Float,
+        FieldType::Base(BaseType::Double) => VerificationType::Double,
+        FieldType::Base(_) => VerificationType::Integer,
+        FieldType::Object(class_name) => VerificationType::Object(Cow::Owned(class_name.clone())),
+        FieldType::Array(_) => VerificationType::Object(Cow::Owned(field_type.to_descriptor())),
+    }
+}
+
+fn is_reference(value_type: &VerificationType) -> bool {
+    matches!(
+        value_type,
+        VerificationType::Null
+            | VerificationType::Object(_)
+            | VerificationType::UninitializedThis
+            | VerificationType::Uninitialized(_)
+    )
+}
+
+fn is_assignable(from: &VerificationType, to: &VerificationType) -> bool {
+    match (from, to) {
+        (_, VerificationType::Top) => true,
+        (VerificationType::Null, VerificationType::Object(_)) => true,
+        (VerificationType::Object(from), VerificationType::Object(to)) => {
+            is_class_assignable(from, to)
+        }
+        _ => from == to,
+    }
+}
+
+/// Checks the subtyping of classes and arrays. Checking it between two classes would
+/// require loading them, so it is left to the runtime.
+fn is_class_assignable(from: &str, to: &str) -> bool {
+    if from == to || to == "java/lang/Object" {
+        return true;
+    }
+    match (from.strip_prefix('['), to.strip_prefix('[')) {
+        (Some(from_component), Some(to_component)) => {
+            match (
+                component_class(from_component),
+                component_class(to_component),
+            ) {
+                (Some(from_class), Some(to_class)) => is_class_assignable(from_class, to_class),
+                _ => from_component == to_component,
+            }
+        }
+        (Some(_), None) => to == "java/lang/Cloneable" || to == "java/io/Serializable",
+        (None, Some(_)) => false,
+        (None, None) => true,
+    }
+}
+
+/// Returns the class of an array's components, given their descriptor, or `None` if they
+/// are primitive
+fn component_class(component_descriptor: &str) -> Option<&str> {
+    if component_descriptor.starts_with('[') {
+        Some(component_descriptor)
+    } else {
+        component_descriptor
+            .strip_prefix('L')
+            .and_then(|name| name.strip_suffix(';'))
+    }
+}
+
+fn describe(value_type: &VerificationType) -> String {
+    match value_type {
+        VerificationType::Top => "top".to_string(),
+        VerificationType::Integer => "integer".to_string(),
+        VerificationType::Float => "float".to_string(),
+        VerificationType::Long => "long".to_string(),
+        VerificationType::Double => "double".to_string(),
+        VerificationType::Null => "null".to_string(),
+        VerificationType::UninitializedThis => "uninitializedThis".to_string(),
+        VerificationType::Object(class_name) => format!("'{class_name}'"),
+        VerificationType::Uninitialized(pc) => format!("uninitialized({pc})"),
+    }
+}
+
+fn bad_stack_type(found: &VerificationType, expected: &VerificationType) -> String {
+    format!(
+        "Bad type on operand stack: type {} is not assignable to {}",
+        describe(found),
+        describe(expected)
+    )
+}
+
+fn bad_array_type(instruction: &str, found: &str) -> String {
+    format!("Bad type on operand stack in {instruction}: type {found} is not a valid array")
+}
@@ -1,31 +1,88 @@
-use std::{collections::HashMap, string::ToString};
+use std::{
+    cell::RefCell,
+    collections::{BTreeMap, HashMap, VecDeque},
+    rc::Rc,
+    string::ToString,
+    time::{Duration, Instant},
+};
+
+#[cfg(feature = "std")]
+use std::num::NonZeroUsize;
 
-use log::{debug, error, info};
+use log::{debug, error, info, warn, Level};
 use typed_arena::Arena;
 
-use rjvm_reader::type_conversion::ToUsizeSafe;
+use rjvm_reader::{
+    class_file::ClassFile, class_file_field::FieldConstantValue, field_flags::FieldFlags,
+    field_type::BaseType, type_conversion::ToUsizeSafe,
+};
 
 use crate::{
-    abstract_object::{AbstractObject, ObjectKind},
+    abstract_object::{AbstractObject, ObjectId, ObjectKind},
+    allocation::{AllocationCounters, AllocationEvent, AllocationHook, AllocationKind},
     array::Array,
     array_entry_type::ArrayEntryType,
-    call_frame::MethodCallResult,
-    call_stack::CallStack,
-    class::{ClassId, ClassRef},
+    boxed_caches::BoxedCaches,
+    call_frame::{CallFrame, MethodCallResult},
+    call_stack::{CallStack, DEFAULT_MAX_CALL_STACK_DEPTH},
+    class::{ClassFileBytes, ClassId, ClassRef, DefiningLoader, InitializationState},
     class_and_method::ClassAndMethod,
-    class_manager::{ClassManager, ResolvedClass},
-    class_path::ClassPathParseError,
+    class_file_transformer::ClassFileTransformer,
+    class_load_observer::{ClassLoadObserver, ClassSource},
+    class_manager::{ClassManager, LoadedClasses, ResolvedClass},
+    class_path::{ClassPathParseError, DuplicateClass},
+    class_path_entry::ClassPathEntry,
+    class_path_scan::ScanProgressListener,
     class_resolver_by_id::ClassByIdResolver,
-    exceptions::MethodCallFailed,
-    gc::ObjectAllocator,
+    collection_intrinsics::CollectionIntrinsics,
+    exceptions::{JavaException, MethodCallFailed, RunMainError, UncaughtException},
+    file_io::DescriptorTable,
+    gc::{CollectedObjects, Ephemeron, ObjectAllocator},
+    gc_algorithm::GcAlgorithm,
+    gc_stats::GcStats,
+    handles::{HandleScope, HandleTable, WeakHandle},
+    heap_growth::HeapGrowthPolicy,
+    heap_object::HeapObject,
+    identity_hash::IdentityHashGenerator,
+    incremental_marking::IncrementalMarkingConfig,
+    inline_cache::InlineCaches,
+    io::{
+        FileHandle, JvmIo, JvmNet, NoJvmIo, NoJvmNet, OutputSink, SocketHandle, StderrSink,
+        StdoutSink,
+    },
+    jar_manifest::JarManifest,
+    java_objects_creation::{
+        extract_str_from_java_lang_string, get_interned_java_lang_string,
+        new_java_lang_exception_in_initializer_error, new_java_lang_string_array,
+        new_main_thread_object,
+    },
+    lambda_metafactory::LambdaCallSites,
+    log_config::{vm_log, InstructionsLogThrottler, LogCategory, LogConfig},
     native_methods_impl::array_copy,
-    native_methods_registry::NativeMethodsRegistry,
+    native_methods_registry::{NativeCallback, NativeMethodsRegistry},
+    object::Object,
+    object_fields::{get_object_field, set_object_field},
+    preload_summary::PreloadSummary,
+    quickened_fields::QuickenedFields,
+    reference_objects::try_enqueue_reference,
+    run_config::RunConfig,
+    safepoint::{SafepointRequester, SafepointView},
+    shutdown_hooks::ShutdownHooks,
     stack_trace_element::StackTraceElement,
+    standard_streams::{initialize_standard_streams, StandardStream, StandardStreams},
+    system_properties::default_system_properties,
+    thread_scheduler::{
+        NativeContinuation, ThreadId, ThreadPhase, ThreadScheduler, THREAD_STATUS_RUNNABLE,
+        THREAD_STATUS_TERMINATED,
+    },
+    user_class_loaders,
     value::Value,
-    vm_error::VmError,
+    verification_report::VerificationReport,
+    vm_error::{ClassInitializationFailed, VmError},
 };
 
-/// An instance of the virtual machine. Single-threaded, can execute one method (generally `main`).
+/// An instance of the virtual machine. It can execute one method (generally `main`), which can
+/// start other java threads; they are green threads, multiplexed by a [ThreadScheduler].
 pub struct Vm<'a> {
     /// Responsible for allocating and storing classes
     class_manager: ClassManager<'a>,
@@ -33,13 +90,15 @@ pub struct Vm<'a> {
     /// Responsible for allocating objects
     object_allocator: ObjectAllocator<'a>,
 
+    /// Counters of the allocations, and the hooks notified of each one
+    allocation_counters: AllocationCounters,
+    allocation_hooks: Vec<AllocationHook>,
+
     /// Allocated call stacks
     call_stacks: Arena<CallStack<'a>>,
 
-    /// To model static fields, we will create one special instance of each class
-    /// and we will store it in this map. This is a bit hacky, and wastes memory
-    /// because we will allocate space for non-static fields, but it works easily!
-    statics: HashMap<ClassId, AbstractObject<'a>>,
+    /// The maximum number of frames of each call stack
+    max_call_stack_depth: usize,
 
     /// Stores native methods
     pub native_methods_registry: NativeMethodsRegistry<'a>,
@@ -51,11 +110,95 @@ pub struct Vm<'a> {
     /// keep it in this weird map.
     /// See the implementation of Throwable::getStackTrace() in our rt.jar for
     /// clarity.
-    throwable_call_stacks: HashMap<i32, Vec<StackTraceElement<'a>>>,
+    throwable_call_stacks: HashMap<ObjectId, Vec<StackTraceElement<'a>>>,
 
-    /// Since we do not have I/O, we have a fake native method that does a println.
-    /// To check in the tests what the java bytecode printed, we store it here.
+    /// The unique `java.lang.Class` instance of each class, created the first time that it is
+    /// requested. They are keyed by name rather than by [ClassId], since primitive types and
+    /// arrays have a `Class` instance too.
+    class_objects: HashMap<String, AbstractObject<'a>>,
+
+    /// The canonical instance of each string literal, or string returned by `String.intern`,
+    /// keyed by its content
+    interned_strings: HashMap<String, AbstractObject<'a>>,
+
+    /// Besides `System.out`, we have a fake native method that does a println, `tempPrint`.
+    /// To check in the tests what the java bytecode printed with it, we store it here.
     pub printed: Vec<Value<'a>>,
+
+    /// The instances returned by the boxing methods such as `Integer.valueOf` for small values
+    pub(crate) boxed_caches: BoxedCaches<'a>,
+
+    /// The hooks registered via `Runtime.addShutdownHook`; see [Vm::shutdown]
+    pub(crate) shutdown_hooks: ShutdownHooks<'a>,
+
+    /// Native storage for the intrinsified ArrayList and HashMap
+    pub(crate) collection_intrinsics: CollectionIntrinsics<'a>,
+
+    /// The linked `invokedynamic` call sites of the lambdas
+    pub(crate) lambda_call_sites: LambdaCallSites<'a>,
+
+    /// The inline caches of the virtual call sites
+    pub(crate) inline_caches: InlineCaches<'a>,
+
+    /// The field accesses already resolved
+    pub(crate) quickened_fields: QuickenedFields<'a>,
+
+    /// Which categories of messages should be logged
+    log_config: LogConfig,
+    instructions_log_throttler: InstructionsLogThrottler,
+
+    /// The system properties, i.e. what `System.getProperty` returns
+    system_properties: HashMap<String, String>,
+
+    /// The environment variables set by the embedder. They are visible to the java code
+    /// together with, and taking precedence over, those of the host exposed by `io`.
+    environment: HashMap<String, String>,
+
+    /// The services of the host that the natives can use
+    io: Rc<dyn JvmIo>,
+
+    /// The network used by the sockets
+    net: Rc<dyn JvmNet>,
+
+    /// Where `System.out` writes
+    stdout: Rc<dyn OutputSink>,
+
+    /// Where `System.err` writes
+    stderr: Rc<dyn OutputSink>,
+
+    /// The instances of `System.out` and `System.err`
+    pub(crate) standard_streams: StandardStreams<'a>,
+
+    /// The files opened by the java code
+    pub(crate) open_files: DescriptorTable<FileHandle>,
+
+    /// The sockets opened by the java code
+    pub(crate) open_sockets: DescriptorTable<SocketHandle>,
+
+    /// The callbacks to invoke at the next safepoint
+    safepoint_requests: SafepointRequester,
+
+    /// Whether the bytecode is prevented from accessing the classes and members it cannot see
+    access_checks: bool,
+
+    /// The java threads, and which one is running
+    scheduler: ThreadScheduler<'a>,
+
+    /// The objects rooted by the [HandleScope]s
+    handles: Rc<RefCell<HandleTable<'a>>>,
+
+    identity_hashes: IdentityHashGenerator,
+
+    /// The references cleared by the gc that have yet to be appended to their queues
+    pending_references: Vec<AbstractObject<'a>>,
+
+    /// The unreachable objects whose finalizer has yet to run; see [Vm::run_finalization]
+    finalization_queue: VecDeque<AbstractObject<'a>>,
+    finalizer_call_stack: Option<&'a mut CallStack<'a>>,
+    running_finalizers: bool,
+
+    /// Set when the program has halted the vm, until the main thread returns to the embedder
+    exit_status: Option<i32>,
 }
 
 pub const ONE_MEGABYTE: usize = 1024 * 1024;
@@ -75,49 +218,654 @@ impl<'a> Vm<'a> {
         let mut result = Self {
             class_manager: Default::default(),
             object_allocator: ObjectAllocator::with_maximum_memory(max_memory),
+            allocation_counters: Default::default(),
+            allocation_hooks: Vec::new(),
             call_stacks: Arena::new(),
-            statics: Default::default(),
+            max_call_stack_depth: DEFAULT_MAX_CALL_STACK_DEPTH,
             native_methods_registry: Default::default(),
             throwable_call_stacks: Default::default(),
+            class_objects: Default::default(),
+            interned_strings: Default::default(),
             printed: Vec::new(),
+            boxed_caches: Default::default(),
+            shutdown_hooks: Default::default(),
+            collection_intrinsics: Default::default(),
+            lambda_call_sites: Default::default(),
+            inline_caches: Default::default(),
+            quickened_fields: Default::default(),
+            log_config: Default::default(),
+            instructions_log_throttler: Default::default(),
+            system_properties: default_system_properties(),
+            environment: Default::default(),
+            io: Rc::new(NoJvmIo),
+            net: Rc::new(NoJvmNet),
+            stdout: Rc::new(StdoutSink),
+            stderr: Rc::new(StderrSink),
+            standard_streams: Default::default(),
+            open_files: Default::default(),
+            open_sockets: Default::default(),
+            safepoint_requests: Default::default(),
+            access_checks: true,
+            scheduler: Default::default(),
+            handles: Default::default(),
+            identity_hashes: Default::default(),
+            pending_references: Vec::new(),
+            finalization_queue: VecDeque::new(),
+            finalizer_call_stack: None,
+            running_finalizers: false,
+            exit_status: None,
         };
         crate::native_methods_impl::register_natives(&mut result.native_methods_registry);
         result
     }
 
     pub(crate) fn get_static_instance(&self, class_id: ClassId) -> Option<AbstractObject<'a>> {
-        self.statics.get(&class_id).cloned()
+        self.class_manager.statics_of(class_id)
+    }
+
+    /// Returns the value of a static field of the given class, which must have been initialized
+    pub(crate) fn get_static_field(
+        &self,
+        class_name: &str,
+        field_name: &str,
+    ) -> Result<Value<'a>, VmError> {
+        let class = self
+            .find_class_by_name(class_name)
+            .ok_or_else(|| VmError::ClassNotFoundException(class_name.to_string()))?;
+        let (declaring_class, index, _) = class.find_static_field(field_name).ok_or_else(|| {
+            VmError::FieldNotFoundException(class_name.to_string(), field_name.to_string())
+        })?;
+        let statics = self
+            .get_static_instance(declaring_class.id)
+            .ok_or(VmError::ValidationException)?;
+        Ok(statics.get_field(declaring_class, index))
+    }
+
+    /// Sets a static field of the given class, which must have been initialized
+    pub(crate) fn set_static_field(
+        &self,
+        class_name: &str,
+        field_name: &str,
+        value: Value<'a>,
+    ) -> Result<(), VmError> {
+        let class = self
+            .find_class_by_name(class_name)
+            .ok_or_else(|| VmError::ClassNotFoundException(class_name.to_string()))?;
+        let (declaring_class, index, _) = class.find_static_field(field_name).ok_or_else(|| {
+            VmError::FieldNotFoundException(class_name.to_string(), field_name.to_string())
+        })?;
+        let statics = self
+            .get_static_instance(declaring_class.id)
+            .ok_or(VmError::ValidationException)?;
+        self.write_barrier(&value);
+        statics.set_field(index, value);
+        Ok(())
+    }
+
+    pub fn log_config(&self) -> LogConfig {
+        self.log_config
+    }
+
+    /// Changes which categories of messages are logged. Can be invoked at any time,
+    /// even while executing some code (i.e. from a native method).
+    pub fn set_log_config(&mut self, log_config: LogConfig) {
+        self.log_config = log_config;
+        self.class_manager.set_log_config(log_config);
+        self.object_allocator.log_config = log_config;
+    }
+
+    /// Returns whether the instruction about to be executed should be logged
+    pub(crate) fn should_log_instruction(&mut self, call_depth: usize) -> bool {
+        log::log_enabled!(target: LogCategory::Instructions.target(), Level::Debug)
+            && self
+                .instructions_log_throttler
+                .should_log(&self.log_config, call_depth)
+    }
+
+    pub fn system_property(&self, name: &str) -> Option<&str> {
+        self.system_properties.get(name).map(String::as_str)
+    }
+
+    pub fn set_system_property(&mut self, name: &str, value: &str) {
+        self.system_properties
+            .insert(name.to_string(), value.to_string());
+    }
+
+    /// Removes the given system property, returning its previous value
+    pub fn remove_system_property(&mut self, name: &str) -> Option<String> {
+        self.system_properties.remove(name)
+    }
+
+    /// All the system properties, including the defaults that describe the host
+    pub fn system_properties(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.system_properties
+            .iter()
+            .map(|(name, value)| (name.as_str(), value.as_str()))
+    }
+
+    pub fn environment_variable(&self, name: &str) -> Option<&str> {
+        self.environment.get(name).map(String::as_str)
+    }
+
+    /// Replaces the whole environment set by the embedder
+    pub fn set_environment<I: IntoIterator<Item = (String, String)>>(&mut self, environment: I) {
+        self.environment = environment.into_iter().collect();
+    }
+
+    /// Sets the services of the host that the natives can use. By default the vm uses
+    /// [NoJvmIo], which exposes nothing; [StdJvmIo] exposes the process running the vm.
+    ///
+    /// [StdJvmIo]: crate::io::StdJvmIo
+    pub fn set_io(&mut self, io: Rc<dyn JvmIo>) {
+        self.io = io;
+    }
+
+    pub fn io(&self) -> &dyn JvmIo {
+        self.io.as_ref()
+    }
+
+    /// Sets the network that the sockets use. By default the vm uses [NoJvmNet], which
+    /// fails to connect anywhere; with the `std` feature, [StdJvmNet] uses the network of
+    /// the host.
+    ///
+    /// [StdJvmNet]: crate::io::StdJvmNet
+    pub fn set_net(&mut self, net: Rc<dyn JvmNet>) {
+        self.net = net;
+    }
+
+    pub fn net(&self) -> &dyn JvmNet {
+        self.net.as_ref()
+    }
+
+    /// Sets where `System.out` writes. By default, it is the standard output of the process.
+    pub fn set_stdout(&mut self, sink: Rc<dyn OutputSink>) {
+        self.stdout = sink;
+    }
+
+    /// Sets where `System.err` writes. By default, it is the standard error of the process.
+    pub fn set_stderr(&mut self, sink: Rc<dyn OutputSink>) {
+        self.stderr = sink;
+    }
+
+    pub(crate) fn output_sink(&self, stream: StandardStream) -> &dyn OutputSink {
+        match stream {
+            StandardStream::Out => self.stdout.as_ref(),
+            StandardStream::Err => self.stderr.as_ref(),
+        }
     }
 
+    /// Returns the value of the environment variable as seen by the java code, i.e. the one
+    /// set by the embedder or, if missing, the one of the host exposed by the [JvmIo]
+    pub fn visible_environment_variable(&self, name: &str) -> Option<String> {
+        self.environment
+            .get(name)
+            .cloned()
+            .or_else(|| self.io.env(name))
+    }
+
+    /// Returns all the environment variables seen by the java code, sorted by name
+    pub fn visible_environment(&self) -> Vec<(String, String)> {
+        let mut environment: BTreeMap<String, String> = self.io.env_vars().into_iter().collect();
+        environment.extend(
+            self.environment
+                .iter()
+                .map(|(name, value)| (name.clone(), value.clone())),
+        );
+        environment.into_iter().collect()
+    }
+
+    /// Appends entries to the application class path, whose classes are defined by the
+    /// application class loader, i.e. the one returned by `ClassLoader.getSystemClassLoader`.
+    /// Until a boot class path is appended, the application class path holds the JDK classes
+    /// too, and all of its classes are defined by the bootstrap loader.
     pub fn append_class_path(&mut self, class_path: &str) -> Result<(), ClassPathParseError> {
         self.class_manager.append_class_path(class_path)
     }
 
+    /// Appends entries to the boot class path, e.g. `rt.jar`, whose classes are defined by the
+    /// bootstrap loader. Classes are searched in the boot class path before the application
+    /// one, and the classes of the boot class path cannot see the application ones.
+    pub fn append_boot_class_path(&mut self, class_path: &str) -> Result<(), ClassPathParseError> {
+        self.class_manager.append_boot_class_path(class_path)
+    }
+
+    /// Adds a custom [ClassPathEntry] to the end of the class path, for classes and resources
+    /// that do not come from a jar or a directory
+    pub fn add_class_path_entry(
+        &mut self,
+        entry: Box<dyn ClassPathEntry>,
+    ) -> Result<(), ClassPathParseError> {
+        self.class_manager.add_class_path_entry(entry)
+    }
+
+    /// Reads a resource, e.g. `rjvm/data.properties`, from the boot or the application
+    /// class path
+    pub fn resolve_resource(&self, path: &str) -> Result<Option<Vec<u8>>, VmError> {
+        self.class_manager.resolve_resource(path)
+    }
+
+    /// In strict mode, the class path is scanned for duplicate classes whenever entries are
+    /// appended, and a warning is logged whenever a class that shadows others is resolved.
+    /// Should be enabled before appending the class path entries.
+    pub fn set_strict_class_path(&mut self, strict: bool) {
+        self.class_manager.set_strict_class_path(strict)
+    }
+
+    /// In lazy linkage mode, the references to classes that cannot be found only fail when they
+    /// are actually needed, as for library code with optional dependencies. The missing
+    /// interfaces of a class are ignored, the `catch` clauses of missing classes never match
+    /// and `instanceof` returns false for them, since no object can be an instance of them.
+    pub fn set_lazy_linkage(&mut self, lazy_linkage: bool) {
+        self.class_manager.set_lazy_linkage(lazy_linkage)
+    }
+
+    pub fn lazy_linkage(&self) -> bool {
+        self.class_manager.lazy_linkage()
+    }
+
+    /// When enabled, which is the default, the bytecode of each class is type checked against
+    /// its stack map frames when the class is loaded, and classes that fail the check are
+    /// rejected with a `java.lang.VerifyError`. Class files older than Java 6 are not checked.
+    pub fn set_verify_classes(&mut self, verify_classes: bool) {
+        self.class_manager.set_verify_classes(verify_classes)
+    }
+
+    pub fn verify_classes(&self) -> bool {
+        self.class_manager.verify_classes()
+    }
+
+    /// With more than one thread, which requires the `std` feature, the class files are parsed
+    /// in parallel: whenever a class is resolved, the missing classes of its hierarchy are read
+    /// and then parsed on the given number of threads. The classes are still defined and
+    /// initialized on the vm thread, in the same order as with a single thread, the default.
+    #[cfg(feature = "std")]
+    pub fn set_class_loading_threads(&mut self, threads: NonZeroUsize) {
+        self.class_manager.set_class_loading_threads(threads)
+    }
+
+    #[cfg(feature = "std")]
+    pub fn class_loading_threads(&self) -> NonZeroUsize {
+        self.class_manager.class_loading_threads()
+    }
+
+    /// Reads and parses ahead the given classes, e.g. the ones that an application is known to
+    /// need at startup, together with their hierarchies, on the class loading threads; see
+    /// [Vm::set_class_loading_threads]. The classes are defined only when they are first used.
+    #[cfg(feature = "std")]
+    pub fn prefetch_classes(&mut self, class_names: &[&str]) {
+        self.class_manager
+            .prefetch_classes(class_names.iter().map(|name| name.to_string()).collect())
+    }
+
+    /// Defines, links and verifies all the classes of the boot and of the application class
+    /// paths upfront, e.g. so that latency-sensitive applications pay the whole cost of loading
+    /// them at startup. The classes are not initialized. With more than one class loading
+    /// thread, they are all parsed in parallel first. The classes that cannot be loaded do not
+    /// stop the preload; they are listed in the returned summary. Note that this can take a
+    /// while, and a lot of memory, with a large class path such as the whole JDK.
+    pub fn preload_all(&mut self) -> PreloadSummary {
+        let (class_names, unlisted_entries) = self.class_manager.class_path_class_names();
+        let mut summary = PreloadSummary {
+            loaded: 0,
+            failures: unlisted_entries
+                .into_iter()
+                .map(|(entry, err)| (entry, VmError::ClassLoadingError(err.to_string())))
+                .collect(),
+        };
+
+        #[cfg(feature = "std")]
+        if self.class_loading_threads().get() > 1 {
+            self.class_manager.prefetch_classes(class_names.clone());
+        }
+        for class_name in class_names {
+            match self.resolve_class(&class_name) {
+                Ok(_) => summary.loaded += 1,
+                Err(err) => summary.failures.push((class_name, err)),
+            }
+        }
+        vm_log!(
+            self.log_config,
+            LogCategory::ClassLoad,
+            Level::Info,
+            "preloaded {} classes, {} failed",
+            summary.loaded,
+            summary.failures.len()
+        );
+        summary
+    }
+
+    /// Checks, without running any code, that all the classes of the given class path can be
+    /// loaded, e.g. to validate the jars of an application in a CI pipeline. Each of their
+    /// classes is defined, linked and verified, even if [Vm::set_verify_classes] disabled the
+    /// verification, but not initialized. Besides the classes that cannot be loaded, the report
+    /// lists the classes referenced by their code that cannot be found, neither in the given
+    /// class path nor in the ones of the vm, i.e. the JDK classes. Fails only if an entry is not
+    /// valid.
+    ///
+    /// The classes are loaded by a scratch class manager, dropped afterwards: the vm does not
+    /// keep the given class path, nor any of the classes loaded to check it.
+    pub fn verify(&mut self, class_path: &str) -> Result<VerificationReport, ClassPathParseError> {
+        self.class_manager.verify(class_path)
+    }
+
+    /// When enabled, which is the default, the bytecode cannot use the private, protected and
+    /// package-private members, or the non-public classes, that it is not allowed to access:
+    /// an `IllegalAccessError` is thrown instead. Tools that need to reach into any class
+    /// can disable the checks.
+    pub fn set_access_checks(&mut self, enabled: bool) {
+        self.access_checks = enabled;
+    }
+
+    pub fn access_checks(&self) -> bool {
+        self.access_checks
+    }
+
+    /// Sets a listener that will be notified with the progress of the scan of the class path
+    /// entries appended afterwards, which can take a while for large jars
+    pub fn set_class_path_scan_listener(&mut self, listener: Option<ScanProgressListener>) {
+        self.class_manager.set_class_path_scan_listener(listener)
+    }
+
+    /// Sets how the jars of the class path appended afterwards are read, and how the
+    /// directories of its wildcards are listed. By default they are
+    /// read from the file system of the operating system, independently of [Vm::set_io];
+    /// a [JvmIo] lets embedders without one provide them, e.g. from a flash storage. Only the
+    /// central directory of each jar is kept in memory, and classes are read one at a time
+    /// via [JvmIo::read_file_at].
+    pub fn set_class_path_io(&mut self, io: Option<Rc<dyn JvmIo>>) {
+        self.class_manager.set_class_path_io(io)
+    }
+
+    /// Adds a transformer that can rewrite the bytes of the classes loaded afterwards, before
+    /// they are defined. The transformers are applied in the order in which they were added.
+    pub fn add_class_file_transformer(&mut self, transformer: Rc<dyn ClassFileTransformer>) {
+        self.class_manager.add_class_file_transformer(transformer)
+    }
+
+    /// Adds an observer notified of the classes looked up, defined, initialized, or failing
+    /// to load from then on, e.g. to trace where each class comes from
+    pub fn add_class_load_observer(&mut self, observer: Rc<dyn ClassLoadObserver>) {
+        self.class_manager.add_class_load_observer(observer)
+    }
+
+    /// The duplicate classes found in the class path; always empty unless in strict mode
+    pub fn duplicate_classes(&self) -> &[DuplicateClass] {
+        self.class_manager.duplicate_classes()
+    }
+
+    /// Enables backing `java.util.ArrayList` and `java.util.HashMap` with native Rust
+    /// collections. This is meant for minimal class libraries: the classes that are not
+    /// compatible, like the ones of rt.jar, are not intrinsified. The setting only affects the
+    /// classes loaded afterwards, since the decision is taken before any instance exists.
+    pub fn set_collection_intrinsics_enabled(&mut self, enabled: bool) {
+        self.collection_intrinsics.set_enabled(enabled);
+    }
+
+    pub fn are_collection_intrinsics_enabled(&self) -> bool {
+        self.collection_intrinsics.is_enabled()
+    }
+
+    /// Whether the methods of the given collection class, if loaded, are implemented by the
+    /// intrinsics; see [Self::set_collection_intrinsics_enabled]
+    pub fn is_collection_class_intrinsified(&self, class_name: &str) -> bool {
+        self.find_class_by_name(class_name)
+            .is_some_and(|class| self.collection_intrinsics.is_intrinsified(class))
+    }
+
+    /// Returns the class with the given name, loading it if needed, and initializes it,
+    /// as required before creating its instances or using its static members
     pub fn get_or_resolve_class(
         &mut self,
         stack: &mut CallStack<'a>,
         class_name: &str,
     ) -> Result<ClassRef<'a>, MethodCallFailed<'a>> {
-        let class = self.class_manager.get_or_resolve_class(class_name)?;
-        if let ResolvedClass::NewClass(classes_to_init) = &class {
-            for class_to_init in classes_to_init.to_initialize.iter() {
-                self.init_class(stack, class_to_init)?;
-            }
+        let class = self.resolve_class(class_name)?;
+        self.initialize_class(stack, class)?;
+        Ok(class)
+    }
+
+    /// Returns the class with the given name, loading it if needed, without initializing it.
+    /// The classes defined by any loader are returned, and the missing ones are loaded by the
+    /// application class loader.
+    pub(crate) fn resolve_class(&mut self, class_name: &str) -> Result<ClassRef<'a>, VmError> {
+        self.resolve_class_of(class_name, DefiningLoader::User)
+    }
+
+    /// Returns the class with the given name as seen by the given loader, loading it if needed,
+    /// without initializing it
+    pub(crate) fn resolve_class_of(
+        &mut self,
+        class_name: &str,
+        initiating_loader: DefiningLoader,
+    ) -> Result<ClassRef<'a>, VmError> {
+        let class = self
+            .class_manager
+            .get_or_resolve_class_of(class_name, initiating_loader)?;
+        if let ResolvedClass::NewClass(loaded_classes) = &class {
+            self.on_classes_loaded(loaded_classes);
         }
         Ok(class.get_class())
     }
 
+    /// Defines and initializes a class synthesized by the vm, rather than loaded from the
+    /// class path, on behalf of the given host class: the new class belongs to the same loader
+    pub(crate) fn define_class(
+        &mut self,
+        stack: &mut CallStack<'a>,
+        class_file: ClassFile<'a>,
+        host: ClassRef<'a>,
+    ) -> Result<ClassRef<'a>, MethodCallFailed<'a>> {
+        let loaded_classes = self.class_manager.define_class(
+            class_file,
+            ClassFileBytes::default(),
+            host.defining_loader,
+            ClassSource::Synthesized,
+        )?;
+        self.on_classes_loaded(&loaded_classes);
+        let class = ResolvedClass::NewClass(loaded_classes).get_class();
+        if let Some(loader) = self.class_manager.defining_loader(host.id) {
+            self.class_manager.set_defining_loader(class.id, loader);
+        }
+        self.initialize_class(stack, class)?;
+        Ok(class)
+    }
+
+    /// Returns a class referenced by the code of another one, loading it if needed, without
+    /// initializing it. The class is requested to the loader that defined the referencing
+    /// class: the classes of the boot class path cannot see the application ones, and the
+    /// classes that are not in the class paths are requested to the user-defined loader of the
+    /// referencing class, if any, by invoking its `loadClass`.
+    pub(crate) fn resolve_class_referenced_by(
+        &mut self,
+        stack: &mut CallStack<'a>,
+        referencing_class: ClassRef<'a>,
+        class_name: &str,
+    ) -> Result<ClassRef<'a>, MethodCallFailed<'a>> {
+        let not_found = match self.resolve_class_of(class_name, referencing_class.defining_loader) {
+            Err(err @ VmError::ClassNotFoundException(_)) => err,
+            result => return Ok(result?),
+        };
+        match self.class_manager.defining_loader(referencing_class.id) {
+            Some(loader) => user_class_loaders::load_class(self, stack, loader, class_name),
+            None => Err(not_found.into()),
+        }
+    }
+
+    /// Replaces the code of the methods of an already loaded class, e.g. to let an interactive
+    /// environment change a method at runtime. The class file must define the same class,
+    /// with the same superclass, interfaces, fields and methods, including their modifiers:
+    /// like in the redefinition of the JVM tools interface, only the code of the methods, and
+    /// the constants it uses, can change. The methods invoked afterwards, including the ones
+    /// inherited by the subclasses, run the new code, while the invocations in progress
+    /// complete with the old one. The static fields keep their values, and the static
+    /// initializer does not run again.
+    pub fn redefine_class(
+        &mut self,
+        class_name: &str,
+        class_file_bytes: Vec<u8>,
+    ) -> Result<(), VmError> {
+        let (class_file, class_file_bytes) = self
+            .class_manager
+            .parse_class(Some(class_name), class_file_bytes)?;
+        if class_file.name != class_name {
+            return Err(VmError::UnsupportedClassRedefinition(
+                class_name.to_string(),
+                format!("the class file defines {}", class_file.name),
+            ));
+        }
+        // The class keeps its id, and thus whether it is intrinsified: since its fields cannot
+        // change, the new version is as compatible with the intrinsics as the old one
+        let class = self
+            .class_manager
+            .redefine_class(class_file, class_file_bytes)?;
+        // Only the invocations in progress still execute the old version, and the methods
+        // cached by the call sites could belong to it
+        self.quickened_fields.remove_class(class.id);
+        self.inline_caches.clear();
+        Ok(())
+    }
+
+    /// Parses the bytes of a class given to `ClassLoader.defineClass`, with the name it was
+    /// given, if any. The class file borrows from the returned bytes, which must be given to
+    /// [Vm::define_class_of_loader] with it.
+    pub(crate) fn parse_class(
+        &mut self,
+        class_name: Option<&str>,
+        class_file_bytes: Vec<u8>,
+    ) -> Result<(ClassFile<'a>, ClassFileBytes), VmError> {
+        self.class_manager.parse_class(class_name, class_file_bytes)
+    }
+
+    /// Defines a class given to `ClassLoader.defineClass`, without initializing it, recording
+    /// the loader that defined it. Its superclass and interfaces must be already loaded, or
+    /// be in the class path.
+    pub(crate) fn define_class_of_loader(
+        &mut self,
+        class_file: ClassFile<'a>,
+        class_file_bytes: ClassFileBytes,
+        loader: AbstractObject<'a>,
+    ) -> Result<ClassRef<'a>, VmError> {
+        let loaded_classes = self.class_manager.define_class(
+            class_file,
+            class_file_bytes,
+            DefiningLoader::User,
+            ClassSource::DefineClass,
+        )?;
+        self.on_classes_loaded(&loaded_classes);
+        let class = ResolvedClass::NewClass(loaded_classes).get_class();
+        self.class_manager.set_defining_loader(class.id, loader);
+        Ok(class)
+    }
+
+    /// The instance of a user-defined subclass of `java.lang.ClassLoader` that defined the
+    /// given class, or `None` for the classes of the boot and of the application class paths
+    pub(crate) fn defining_loader(&self, class: ClassRef<'a>) -> Option<AbstractObject<'a>> {
+        self.class_manager.defining_loader(class.id)
+    }
+
+    /// The instance of `java.lang.ClassLoader` standing for the application class loader,
+    /// if it was already created; see [user_class_loaders::system_class_loader]
+    pub(crate) fn system_class_loader(&self) -> Option<AbstractObject<'a>> {
+        self.class_manager.system_class_loader()
+    }
+
+    pub(crate) fn set_system_class_loader(&mut self, loader: AbstractObject<'a>) {
+        self.class_manager.set_system_class_loader(loader)
+    }
+
+    fn on_classes_loaded(&mut self, loaded_classes: &LoadedClasses<'a>) {
+        for loaded_class in loaded_classes.loaded.iter() {
+            self.collection_intrinsics.on_class_loaded(loaded_class);
+        }
+    }
+
+    /// Initializes the given class, after its superclass, unless it is already initialized.
+    /// The static initializer runs exactly once: if another thread is running it, we wait for
+    /// it to complete, while a recursive request from the same thread returns immediately.
+    /// A class whose initialization failed cannot be used anymore.
+    pub(crate) fn initialize_class(
+        &mut self,
+        stack: &mut CallStack<'a>,
+        class: ClassRef<'a>,
+    ) -> Result<(), MethodCallFailed<'a>> {
+        loop {
+            match class.initialization_state() {
+                InitializationState::Initialized => return Ok(()),
+                InitializationState::BeingInitialized(thread)
+                    if thread == self.current_thread_id() =>
+                {
+                    self.check_recursive_initialization(class);
+                    return Ok(());
+                }
+                InitializationState::BeingInitialized(_) => self.run_other_threads_nested()?,
+                InitializationState::Erroneous => {
+                    return Err(MethodCallFailed::InternalError(VmError::ErroneousClass(
+                        class.name.replace('/', "."),
+                    )));
+                }
+                InitializationState::NotInitialized => {
+                    class.set_initialization_state(InitializationState::BeingInitialized(
+                        self.current_thread_id(),
+                    ));
+                    let result = self
+                        .init_class(stack, class)
+                        .map_err(|err| self.exception_in_initializer(stack, err));
+                    if result.is_ok() {
+                        class.set_initialization_state(InitializationState::Initialized);
+                        self.class_manager.notify_class_initialized(class);
+                    } else {
+                        class.set_initialization_state(InitializationState::Erroneous);
+                    }
+                    return result;
+                }
+            }
+        }
+    }
+
+    /// Whether initializing the given class would wait for another thread, which is running
+    /// the static initializer of the class or of one of its superclasses
+    pub(crate) fn is_class_initialization_blocked(&self, class: ClassRef<'a>) -> bool {
+        let current = self.current_thread_id();
+        let mut class = Some(class);
+        while let Some(current_class) = class {
+            match current_class.initialization_state() {
+                InitializationState::Initialized => return false,
+                InitializationState::BeingInitialized(thread) if thread != current => return true,
+                _ => class = current_class.superclass,
+            }
+        }
+        false
+    }
+
     fn init_class(
         &mut self,
         stack: &mut CallStack<'a>,
-        class_to_init: &ClassRef<'a>,
+        class_to_init: ClassRef<'a>,
     ) -> Result<(), MethodCallFailed<'a>> {
-        debug!("creating static instance of {}", class_to_init.name);
-        let static_instance = self.new_object_of_class(class_to_init);
-        self.statics.insert(class_to_init.id, static_instance);
+        if let Some(superclass) = class_to_init.superclass {
+            self.initialize_class(stack, superclass)?;
+        }
+        vm_log!(
+            self.log_config,
+            LogCategory::ClassLoad,
+            Level::Debug,
+            "creating static instance of {}",
+            class_to_init.name
+        );
+        let static_instance = self.new_object_of_class(class_to_init)?;
+        self.class_manager
+            .set_statics(class_to_init.id, static_instance);
+        self.set_constant_values(stack, class_to_init)?;
         if let Some(clinit_method) = class_to_init.find_method("<clinit>", "()V") {
-            debug!("invoking {}::<clinit>()", class_to_init.name);
-            self.invoke(
+            vm_log!(
+                self.log_config,
+                LogCategory::ClassLoad,
+                Level::Debug,
+                "invoking {}::<clinit>()",
+                class_to_init.name
+            );
+            self.class_manager.begin_initialization(class_to_init);
+            let result = self.invoke(
                 stack,
                 ClassAndMethod {
                     class: class_to_init,
@@ -125,11 +873,122 @@ impl<'a> Vm<'a> {
                 },
                 None,
                 Vec::new(),
-            )?;
+            );
+            let result = result.map_err(|err| self.class_initialization_failed(err));
+            self.class_manager.end_initialization();
+            result?;
+        }
+        // Completes the setup that the JVM does in `System.initializeSystemClass`
+        if class_to_init.name == "java/lang/System" {
+            initialize_standard_streams(self, stack)?;
+        }
+        Ok(())
+    }
+
+    /// Sets the static fields initialized with a compile-time constant, i.e. the
+    /// `static final int MAX = 100;` ones, whose value is stored in their `ConstantValue`
+    /// attribute rather than set by the static initializer
+    fn set_constant_values(
+        &mut self,
+        stack: &mut CallStack<'a>,
+        class: ClassRef<'a>,
+    ) -> Result<(), MethodCallFailed<'a>> {
+        for (index, field) in class.fields.iter().enumerate() {
+            if !field.flags.contains(FieldFlags::STATIC) {
+                continue;
+            }
+            let value = match &field.constant_value {
+                None => continue,
+                Some(FieldConstantValue::Int(value)) => Value::Int(*value),
+                Some(FieldConstantValue::Float(value)) => Value::Float(*value),
+                Some(FieldConstantValue::Long(value)) => Value::Long(*value),
+                Some(FieldConstantValue::Double(value)) => Value::Double(*value),
+                Some(FieldConstantValue::String(value)) => {
+                    Value::Object(get_interned_java_lang_string(self, stack, value)?)
+                }
+            };
+            if !value.matches_type(&field.type_descriptor, self, |name| {
+                self.find_class_by_name(name)
+            }) {
+                return Err(MethodCallFailed::InternalError(
+                    VmError::ValidationException,
+                ));
+            }
+            // Allocating the string may have moved the object holding the static fields
+            let statics = self
+                .get_static_instance(class.id)
+                .ok_or(VmError::ValidationException)?;
+            self.write_barrier(&value);
+            statics.set_field(class.first_field_index + index, value);
         }
         Ok(())
     }
 
+    /// Wraps an exception thrown by a static initializer, unless it is an `Error`, in an
+    /// `ExceptionInInitializerError`, as the JVM spec mandates
+    fn exception_in_initializer(
+        &mut self,
+        stack: &mut CallStack<'a>,
+        err: MethodCallFailed<'a>,
+    ) -> MethodCallFailed<'a> {
+        let MethodCallFailed::ExceptionThrown(exception) = err else {
+            return err;
+        };
+        let is_error = self
+            .find_class_by_id(exception.0.class_id())
+            .zip(self.find_class_by_name("java/lang/Error"))
+            .is_some_and(|(class, error_class)| class.is_subclass_of(error_class));
+        if is_error {
+            return MethodCallFailed::ExceptionThrown(exception);
+        }
+        match new_java_lang_exception_in_initializer_error(self, stack, exception.0) {
+            Ok(error) => MethodCallFailed::ExceptionThrown(JavaException(error)),
+            Err(err) => err,
+        }
+    }
+
+    /// Adds the chain of classes being initialized to an internal error raised by a static
+    /// initializer. Errors raised by nested initializers already carry their own chain, and
+    /// java exceptions are left for the caller to handle.
+    fn class_initialization_failed(&self, err: MethodCallFailed<'a>) -> MethodCallFailed<'a> {
+        match err {
+            MethodCallFailed::InternalError(VmError::ClassInitializationFailed(_))
+            | MethodCallFailed::ExceptionThrown(_)
+            | MethodCallFailed::VmExit(_) => err,
+            MethodCallFailed::InternalError(cause) => {
+                let initialization_chain = self.class_manager.initialization_chain();
+                MethodCallFailed::InternalError(VmError::ClassInitializationFailed(Box::new(
+                    ClassInitializationFailed {
+                        initialization_chain,
+                        cause,
+                    },
+                )))
+            }
+        }
+    }
+
+    /// A class that is still being initialized by an outer initializer is being used by a
+    /// nested one, which will see its static fields before they are set. This is legal, but
+    /// is a common source of surprising behavior, so we log the chain that caused it.
+    fn check_recursive_initialization(&self, class: ClassRef<'a>) {
+        if self.class_manager.is_being_initialized(class)
+            && self
+                .class_manager
+                .initialization_chain()
+                .last()
+                .is_some_and(|innermost| *innermost != class.name)
+        {
+            vm_log!(
+                self.log_config,
+                LogCategory::ClassLoad,
+                Level::Warn,
+                "class {} used while still being initialized, initialization chain: {}",
+                class.name,
+                self.class_manager.initialization_chain().join(" -> ")
+            );
+        }
+    }
+
     pub fn get_class_by_id(&self, class_id: ClassId) -> Result<ClassRef<'a>, VmError> {
         self.find_class_by_id(class_id)
             .ok_or(VmError::ValidationException)
@@ -161,6 +1020,10 @@ impl<'a> Vm<'a> {
             })
     }
 
+    /// Invokes the given method on the running thread. When the embedder invokes it, on the
+    /// main thread, the other threads run whenever the main one is suspended, and after the
+    /// outermost method completes, until they terminate; see
+    /// [finish_other_threads](Self::finish_other_threads).
     pub fn invoke(
         &mut self,
         call_stack: &mut CallStack<'a>,
@@ -168,126 +1031,732 @@ impl<'a> Vm<'a> {
         object: Option<AbstractObject<'a>>,
         args: Vec<Value<'a>>,
     ) -> MethodCallResult<'a> {
-        if class_and_method.method.is_native() {
-            return self.invoke_native(call_stack, class_and_method, object, args);
+        if !self.scheduler.is_running() {
+            return self.invoke_from_embedder(call_stack, class_and_method, object, args);
         }
-
-        // Generic bytecode method
-        let mut frame = call_stack.add_frame(class_and_method, object, args)?;
-        let result = frame.as_mut().execute(self, call_stack);
-        call_stack
-            .pop_frame()
-            .expect("should be able to pop the frame we just pushed");
+        // The Rust code invoking the method cannot be unwound, so neither can the method
+        self.scheduler.pin_current();
+        let result = self.invoke_suspendable(call_stack, class_and_method, object, args);
+        self.scheduler.unpin_current();
         result
     }
 
-    fn invoke_native(
+    fn invoke_from_embedder(
         &mut self,
         call_stack: &mut CallStack<'a>,
         class_and_method: ClassAndMethod<'a>,
         object: Option<AbstractObject<'a>>,
         args: Vec<Value<'a>>,
     ) -> MethodCallResult<'a> {
-        let native_callback = self.native_methods_registry.get_method(&class_and_method);
-        if let Some(native_callback) = native_callback {
-            debug!(
-                "executing native method {}::{} {}",
-                class_and_method.class.name,
-                class_and_method.method.name,
-                class_and_method.method.type_descriptor
-            );
-            native_callback(self, call_stack, object, args)
-        } else {
-            error!(
-                "cannot resolve native method {}::{} {}",
-                class_and_method.class.name,
-                class_and_method.method.name,
-                class_and_method.method.type_descriptor
-            );
-            Err(MethodCallFailed::InternalError(VmError::NotImplemented))
+        self.scheduler.set_running(true);
+        let base_depth = call_stack.depth();
+        let mut result = self.invoke_suspendable(call_stack, class_and_method, object, args);
+        while is_suspended(&result) {
+            self.run_other_threads();
+            self.run_other_threads_until_current_can_run();
+            result = self.resume_frames(call_stack, base_depth);
         }
-    }
-
-    /// Allocates a new call stack. We need to store it to be able to refer it later, for
-    /// extracting the gc roots.
-    pub fn allocate_call_stack(&mut self) -> &'a mut CallStack<'a> {
-        let stack = self.call_stacks.alloc(CallStack::new());
-        unsafe {
-            let stack_ptr: *mut CallStack<'a> = stack;
-            &mut *stack_ptr
+        if base_depth == 0 {
+            self.finish_other_threads();
+            if let Some(status) = self.exit_status.take() {
+                result = Err(MethodCallFailed::VmExit(status));
+            }
         }
+        self.scheduler.set_running(false);
+        result
     }
 
-    pub fn new_object(
+    /// Like [invoke](Self::invoke), but the running thread can be suspended, if it is not
+    /// pinned: the method then fails with [VmError::ThreadSuspended], leaving its frames on the
+    /// call stack, and its result is given to the invoking frame when the thread is resumed
+    pub(crate) fn invoke_suspendable(
         &mut self,
         call_stack: &mut CallStack<'a>,
-        class_name: &str,
-    ) -> Result<AbstractObject<'a>, MethodCallFailed<'a>> {
-        let class = self.get_or_resolve_class(call_stack, class_name)?;
-        Ok(self.new_object_of_class(class))
-    }
-
-    pub fn new_object_of_class(&mut self, class: ClassRef<'a>) -> AbstractObject<'a> {
-        debug!("allocating new instance of {}", class.name);
-        match self.object_allocator.allocate_object(class) {
-            Some(object) => object,
-            None => {
-                self.run_garbage_collection()
-                    .expect("could run garbage collection");
-                self.object_allocator
-                    .allocate_object(class)
-                    .expect("cannot allocate object even after full garbage collection!")
-            }
-        }
-    }
-
+        class_and_method: ClassAndMethod<'a>,
+        object: Option<AbstractObject<'a>>,
+        args: Vec<Value<'a>>,
+    ) -> MethodCallResult<'a> {
+        let class_and_method = self.newest_version_of(class_and_method);
+        if class_and_method.method.is_native() {
+            self.scheduler.pin_current();
+            let result = self.invoke_native(call_stack, class_and_method, object, args);
+            self.scheduler.unpin_current();
+            return result;
+        }
+        if let Some(intrinsic) = self.find_intrinsic(&class_and_method, object.as_ref()) {
+            self.scheduler.pin_current();
+            let result = intrinsic(self, call_stack, object, args);
+            self.scheduler.unpin_current();
+            return result;
+        }
+
+        // Generic bytecode method
+        let mut frame = call_stack.add_frame(class_and_method, object, args)?;
+        let result = frame.as_mut().execute(self, call_stack);
+        if is_suspended(&result) {
+            return result;
+        }
+        self.complete_frame(call_stack, result)
+    }
+
+    /// The method to run when the given one is invoked: the one with the same name and
+    /// descriptor in the newest version of its class, if the class was redefined
+    fn newest_version_of(&self, class_and_method: ClassAndMethod<'a>) -> ClassAndMethod<'a> {
+        if !class_and_method.class.is_redefined() {
+            return class_and_method;
+        }
+        let newest_method = self
+            .find_class_by_id(class_and_method.class.id)
+            .and_then(|class| {
+                let method = class.find_method(
+                    &class_and_method.method.name,
+                    &class_and_method.method.type_descriptor,
+                )?;
+                Some(ClassAndMethod { class, method })
+            });
+        newest_method.unwrap_or(class_and_method)
+    }
+
+    fn find_intrinsic(
+        &self,
+        class_and_method: &ClassAndMethod<'a>,
+        receiver: Option<&AbstractObject<'a>>,
+    ) -> Option<NativeCallback<'a>> {
+        let registry = &self.native_methods_registry;
+        if !class_and_method
+            .class
+            .has_intrinsic(class_and_method.method, || {
+                registry.get_intrinsic(class_and_method).is_some()
+            })
+        {
+            return None;
+        }
+        let intrinsic = registry.get_intrinsic(class_and_method)?;
+        let accepts_receiver = match intrinsic.receiver_filter {
+            Some(filter) => receiver.is_some_and(|receiver| filter(self, receiver)),
+            None => true,
+        };
+        (accepts_receiver
+            && (!intrinsic.is_collection_intrinsic
+                || self
+                    .collection_intrinsics
+                    .is_intrinsified(class_and_method.class)))
+        .then_some(intrinsic.callback)
+    }
+
+    /// Pops the frame of a completed method
+    fn complete_frame(
+        &mut self,
+        call_stack: &mut CallStack<'a>,
+        result: MethodCallResult<'a>,
+    ) -> MethodCallResult<'a> {
+        call_stack.pop_completed_frame(result, self.scheduler.current_id())
+    }
+
+    /// Halts the vm with the given status, as done by `Runtime.halt` and, after the shutdown
+    /// hooks have run, by `System.exit`. The returned [MethodCallFailed::VmExit] unwinds the
+    /// running thread; the other ones are stopped at their next switch point, and the main
+    /// thread then returns the status to the embedder.
+    pub(crate) fn halt(&mut self, status: i32) -> MethodCallFailed<'a> {
+        self.exit_status.get_or_insert(status);
+        MethodCallFailed::VmExit(status)
+    }
+
+    /// Runs the `public static void main(String[])` method of the given class, after having
+    /// applied the given configuration to the vm; see [RunConfig] for the details. When the
+    /// program terminates, the vm is shut down; see [Vm::shutdown].
+    pub fn invoke_main(
+        &mut self,
+        class_name: &str,
+        config: RunConfig,
+    ) -> Result<(), MethodCallFailed<'a>> {
+        if let Some(working_directory) = &config.working_directory {
+            self.set_system_property("user.dir", working_directory);
+        }
+        for (name, value) in config.properties.iter() {
+            self.set_system_property(name, value);
+        }
+        self.environment.extend(config.environment);
+        match self.invoke_static_main(class_name, &config.args) {
+            Ok(()) => self.shutdown(),
+            Err(MethodCallFailed::ExceptionThrown(exception)) => {
+                // The exception must survive the shutdown hooks
+                let scope = self.handle_scope();
+                let exception = scope.handle(exception.0);
+                self.shutdown()?;
+                Err(MethodCallFailed::ExceptionThrown(JavaException(
+                    exception.get(),
+                )))
+            }
+            // The program has halted the vm, or the vm has failed: nothing else can run
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Runs the `public static void main(String[])` method of the given class, whose name can
+    /// use either dots or slashes as package separator, with the given arguments. Returns the
+    /// exit status of the program, i.e. zero if `main` completes or the one passed to
+    /// `System.exit`, or the exception that escaped from `main`, rendered as text. Unlike
+    /// [Vm::invoke_main], the configuration of the vm is left as it is.
+    pub fn run_main(&mut self, class_name: &str, args: &[&str]) -> Result<i32, RunMainError> {
+        let args: Vec<String> = args.iter().map(|arg| arg.to_string()).collect();
+        let uncaught_exception = match self.invoke_static_main(&class_name.replace('.', "/"), &args)
+        {
+            Ok(()) => None,
+            // Like the JVM, the exception is printed before the shutdown hooks run
+            Err(MethodCallFailed::ExceptionThrown(exception)) => {
+                Some(self.render_exception(&exception))
+            }
+            Err(err) => return self.run_main_failure(err),
+        };
+        if let Err(err) = self.shutdown() {
+            return self.run_main_failure(err);
+        }
+        match uncaught_exception {
+            Some(exception) => Err(RunMainError::UncaughtException(exception)),
+            None => Ok(0),
+        }
+    }
+
+    fn run_main_failure(&mut self, err: MethodCallFailed<'a>) -> Result<i32, RunMainError> {
+        match err {
+            MethodCallFailed::VmExit(status) => Ok(status),
+            MethodCallFailed::InternalError(err) => Err(RunMainError::VmError(err)),
+            MethodCallFailed::ExceptionThrown(exception) => Err(RunMainError::UncaughtException(
+                self.render_exception(&exception),
+            )),
+        }
+    }
+
+    /// Runs the shutdown sequence of the JDK, like the JVM does when the program terminates:
+    /// the shutdown hooks registered via `Runtime.addShutdownHook` are started, and the vm
+    /// waits for them to complete. It is done by [Vm::invoke_main] and [Vm::run_main]; an
+    /// embedder that invokes the java code in other ways should call it when it is done.
+    /// Only the first invocation has any effect, and the hooks do not run again if the
+    /// program has invoked `System.exit`, which runs them before halting the vm.
+    pub fn shutdown(&mut self) -> Result<(), MethodCallFailed<'a>> {
+        let call_stack = self.allocate_call_stack();
+        let shutdown =
+            self.resolve_class_method(call_stack, "java/lang/Shutdown", "shutdown", "()V")?;
+        self.invoke(call_stack, shutdown, None, Vec::new())?;
+        Ok(())
+    }
+
+    fn invoke_static_main(
+        &mut self,
+        class_name: &str,
+        args: &[String],
+    ) -> Result<(), MethodCallFailed<'a>> {
+        let call_stack = self.allocate_call_stack();
+        let main_method =
+            self.resolve_class_method(call_stack, class_name, "main", "([Ljava/lang/String;)V")?;
+        if !main_method.method.is_static() {
+            return Err(MethodCallFailed::InternalError(
+                VmError::MethodNotFoundException(
+                    class_name.to_string(),
+                    "main".to_string(),
+                    "([Ljava/lang/String;)V".to_string(),
+                ),
+            ));
+        }
+        let args = new_java_lang_string_array(self, call_stack, args)?;
+        self.invoke(call_stack, main_method, None, vec![Value::Object(args)])?;
+        Ok(())
+    }
+
+    /// Renders a java exception as text, by invoking its `toString` and `printStackTrace`.
+    /// If they fail, the rendering falls back to the name of the class of the exception.
+    pub fn render_exception(&mut self, exception: &JavaException<'a>) -> UncaughtException {
+        let class_name = self
+            .get_class_by_id(exception.0.class_id())
+            .map(|class| class.name.replace('/', "."))
+            .unwrap_or_default();
+        let scope = self.handle_scope();
+        let exception = scope.handle(exception.0.clone());
+        let call_stack = self.allocate_call_stack();
+        let description = self
+            .invoke_to_string(call_stack, exception.get())
+            .unwrap_or_else(|_| class_name.clone());
+        let stack_trace = self
+            .print_stack_trace_to_string(call_stack, exception.get())
+            .unwrap_or_else(|_| format!("{description}\n"));
+        UncaughtException {
+            class_name,
+            description,
+            stack_trace,
+        }
+    }
+
+    fn invoke_to_string(
+        &mut self,
+        call_stack: &mut CallStack<'a>,
+        object: AbstractObject<'a>,
+    ) -> Result<String, MethodCallFailed<'a>> {
+        match self.invoke_virtual(
+            call_stack,
+            object,
+            "toString",
+            "()Ljava/lang/String;",
+            vec![],
+        )? {
+            Some(Value::Object(string)) => Ok(extract_str_from_java_lang_string(self, &string)?),
+            _ => Err(VmError::NullPointerException.into()),
+        }
+    }
+
+    fn print_stack_trace_to_string(
+        &mut self,
+        call_stack: &mut CallStack<'a>,
+        exception: AbstractObject<'a>,
+    ) -> Result<String, MethodCallFailed<'a>> {
+        let scope = self.handle_scope();
+        let exception = scope.handle(exception);
+        let string_writer = scope.handle(self.new_object(call_stack, "java/io/StringWriter")?);
+        let constructor =
+            self.resolve_class_method(call_stack, "java/io/StringWriter", "<init>", "()V")?;
+        self.invoke(call_stack, constructor, Some(string_writer.get()), vec![])?;
+        let print_writer = scope.handle(self.new_object(call_stack, "java/io/PrintWriter")?);
+        let constructor = self.resolve_class_method(
+            call_stack,
+            "java/io/PrintWriter",
+            "<init>",
+            "(Ljava/io/Writer;)V",
+        )?;
+        self.invoke(
+            call_stack,
+            constructor,
+            Some(print_writer.get()),
+            vec![string_writer.value()],
+        )?;
+        self.invoke_virtual(
+            call_stack,
+            exception.get(),
+            "printStackTrace",
+            "(Ljava/io/PrintWriter;)V",
+            vec![print_writer.value()],
+        )?;
+        self.invoke_to_string(call_stack, string_writer.get())
+    }
+
+    /// Runs the application packaged in the given jar, like `java -jar` does: the jar is
+    /// appended to the class path, together with the entries listed in the `Class-Path` of its
+    /// manifest, and the class named by the `Main-Class` attribute is run via
+    /// [Vm::invoke_main]. The jar is read with the io of the class path; see
+    /// [Vm::set_class_path_io]. Fails with [VmError::MissingMainClass] if there is no such attribute.
+    pub fn invoke_jar(
+        &mut self,
+        jar_path: &str,
+        config: RunConfig,
+    ) -> Result<(), MethodCallFailed<'a>> {
+        let manifest = self
+            .class_manager
+            .append_jar(jar_path)
+            .map_err(|err| VmError::ClassLoadingError(err.to_string()))?;
+        let main_class = manifest
+            .as_ref()
+            .and_then(JarManifest::main_class)
+            .ok_or_else(|| VmError::MissingMainClass(jar_path.to_string()))?
+            .replace('.', "/");
+        self.invoke_main(&main_class, config)
+    }
+
+    /// Invokes the method with the given name and descriptor, looking it up in the
+    /// class of the receiver, then in its superclasses and finally among the default methods
+    /// of its superinterfaces, like `invokevirtual` does
+    pub fn invoke_virtual(
+        &mut self,
+        call_stack: &mut CallStack<'a>,
+        receiver: AbstractObject<'a>,
+        method_name: &str,
+        method_type_descriptor: &str,
+        args: Vec<Value<'a>>,
+    ) -> MethodCallResult<'a> {
+        let receiver_class = match receiver.kind() {
+            ObjectKind::Object => self.get_class_by_id(receiver.class_id())?,
+            ObjectKind::Array => self.get_or_resolve_class(call_stack, "java/lang/Object")?,
+        };
+
+        let class_and_method =
+            CallFrame::find_virtual_method(receiver_class, method_name, method_type_descriptor)?;
+        self.invoke(call_stack, class_and_method, Some(receiver), args)
+    }
+
+    fn invoke_native(
+        &mut self,
+        call_stack: &mut CallStack<'a>,
+        class_and_method: ClassAndMethod<'a>,
+        object: Option<AbstractObject<'a>>,
+        args: Vec<Value<'a>>,
+    ) -> MethodCallResult<'a> {
+        let native_callback = self.native_methods_registry.get_method(&class_and_method);
+        if let Some(native_callback) = native_callback {
+            vm_log!(
+                self.log_config,
+                LogCategory::Natives,
+                Level::Debug,
+                "executing native method {}::{} {}",
+                class_and_method.class.name,
+                class_and_method.method.name,
+                class_and_method.method.type_descriptor
+            );
+            native_callback(self, call_stack, object, args)
+        } else {
+            error!(
+                "cannot resolve native method {}::{} {}",
+                class_and_method.class.name,
+                class_and_method.method.name,
+                class_and_method.method.type_descriptor
+            );
+            Err(MethodCallFailed::InternalError(VmError::NotImplemented))
+        }
+    }
+
+    /// Changes the maximum number of frames of the call stacks, including the ones already
+    /// allocated. Java code exceeding it gets a `java.lang.StackOverflowError`.
+    pub fn set_max_call_stack_depth(&mut self, max_depth: usize) {
+        self.max_call_stack_depth = max_depth;
+        for call_stack in self.call_stacks.iter_mut() {
+            call_stack.set_max_depth(max_depth);
+        }
+    }
+
+    /// Allocates a new call stack. We need to store it to be able to refer it later, for
+    /// extracting the gc roots.
+    pub fn allocate_call_stack(&mut self) -> &'a mut CallStack<'a> {
+        let mut stack = CallStack::new();
+        stack.set_max_depth(self.max_call_stack_depth);
+        let stack = self.call_stacks.alloc(stack);
+        unsafe {
+            let stack_ptr: *mut CallStack<'a> = stack;
+            &mut *stack_ptr
+        }
+    }
+
+    pub fn new_object(
+        &mut self,
+        call_stack: &mut CallStack<'a>,
+        class_name: &str,
+    ) -> Result<AbstractObject<'a>, MethodCallFailed<'a>> {
+        let class = self.get_or_resolve_class(call_stack, class_name)?;
+        Ok(self.new_object_of_class(class)?)
+    }
+
+    /// Allocates a new instance of the given class, with all its fields set to their
+    /// default value
+    pub fn new_object_of_class(
+        &mut self,
+        class: ClassRef<'a>,
+    ) -> Result<AbstractObject<'a>, VmError> {
+        vm_log!(
+            self.log_config,
+            LogCategory::Gc,
+            Level::Debug,
+            "allocating new instance of {}",
+            class.name
+        );
+        self.allocate(
+            AllocationKind::Object {
+                class_name: &class.name,
+            },
+            AbstractObject::size_of_object(class),
+            |allocator| allocator.allocate_object(class),
+        )
+    }
+
+    /// Allocates a new array, with all its entries set to their default value
     pub fn new_array(
         &mut self,
         elements_type: ArrayEntryType,
         length: usize,
-    ) -> AbstractObject<'a> {
-        match self
-            .object_allocator
-            .allocate_array(elements_type.clone(), length)
-        {
-            Some(array) => array,
-            None => {
-                self.run_garbage_collection()
-                    .expect("could run garbage collection");
-                self.object_allocator
-                    .allocate_array(elements_type, length)
-                    .expect("cannot allocate array even after full garbage collection!")
+    ) -> Result<AbstractObject<'a>, VmError> {
+        self.allocate(
+            AllocationKind::Array {
+                elements_type: &elements_type,
+                length,
+            },
+            AbstractObject::size_of_array(length),
+            |allocator| allocator.allocate_array(elements_type.clone(), length),
+        )
+    }
+
+    /// Allocates a new `boolean[]` with the given content
+    pub fn new_boolean_array(&mut self, values: &[bool]) -> Result<AbstractObject<'a>, VmError> {
+        let array = self.new_array(ArrayEntryType::Base(BaseType::Boolean), values.len())?;
+        unsafe { array.write_primitive_elements(values.iter().map(|&value| i32::from(value))) };
+        Ok(array)
+    }
+
+    /// Allocates a new `byte[]` with the given content. Since java's bytes are signed,
+    /// values greater than 127 will be read as negative numbers by the java code.
+    pub fn new_byte_array(&mut self, values: &[u8]) -> Result<AbstractObject<'a>, VmError> {
+        let array = self.new_array(ArrayEntryType::Base(BaseType::Byte), values.len())?;
+        unsafe {
+            array.write_primitive_elements(
+                values.iter().map(|&value| i32::from(value.cast_signed())),
+            )
+        };
+        Ok(array)
+    }
+
+    /// Allocates a new `char[]` with the given utf-16 code units
+    pub fn new_char_array(&mut self, values: &[u16]) -> Result<AbstractObject<'a>, VmError> {
+        let array = self.new_array(ArrayEntryType::Base(BaseType::Char), values.len())?;
+        unsafe { array.write_primitive_elements(values.iter().map(|&value| i32::from(value))) };
+        Ok(array)
+    }
+
+    /// Allocates a new `short[]` with the given content
+    pub fn new_short_array(&mut self, values: &[i16]) -> Result<AbstractObject<'a>, VmError> {
+        let array = self.new_array(ArrayEntryType::Base(BaseType::Short), values.len())?;
+        unsafe { array.write_primitive_elements(values.iter().map(|&value| i32::from(value))) };
+        Ok(array)
+    }
+
+    /// Allocates a new `int[]` with the given content
+    pub fn new_int_array(&mut self, values: &[i32]) -> Result<AbstractObject<'a>, VmError> {
+        let array = self.new_array(ArrayEntryType::Base(BaseType::Int), values.len())?;
+        unsafe { array.write_primitive_elements(values.iter().copied()) };
+        Ok(array)
+    }
+
+    /// Allocates a new `long[]` with the given content
+    pub fn new_long_array(&mut self, values: &[i64]) -> Result<AbstractObject<'a>, VmError> {
+        let array = self.new_array(ArrayEntryType::Base(BaseType::Long), values.len())?;
+        unsafe { array.copy_primitive_elements(values) };
+        Ok(array)
+    }
+
+    /// Allocates a new `float[]` with the given content
+    pub fn new_float_array(&mut self, values: &[f32]) -> Result<AbstractObject<'a>, VmError> {
+        let array = self.new_array(ArrayEntryType::Base(BaseType::Float), values.len())?;
+        unsafe { array.write_primitive_elements(values.iter().copied()) };
+        Ok(array)
+    }
+
+    /// Allocates a new `double[]` with the given content
+    pub fn new_double_array(&mut self, values: &[f64]) -> Result<AbstractObject<'a>, VmError> {
+        let array = self.new_array(ArrayEntryType::Base(BaseType::Double), values.len())?;
+        unsafe { array.copy_primitive_elements(values) };
+        Ok(array)
+    }
+
+    /// Allocates a new array of the given class, with all its entries set to `null`
+    pub fn new_object_array(
+        &mut self,
+        call_stack: &mut CallStack<'a>,
+        class_name: &str,
+        length: usize,
+    ) -> Result<AbstractObject<'a>, MethodCallFailed<'a>> {
+        let class = self.get_or_resolve_class(call_stack, class_name)?;
+        Ok(self.new_array(ArrayEntryType::Object(class.id), length)?)
+    }
+
+    /// The entry point of all the allocations on the heap: allocates the object, running the gc
+    /// if the memory is full, then updates the counters and notifies the allocation hooks.
+    /// Fails with [VmError::OutOfMemoryError] if there is not enough memory even after the gc.
+    fn allocate(
+        &mut self,
+        kind: AllocationKind,
+        size: usize,
+        allocate: impl Fn(&mut ObjectAllocator<'a>) -> Option<AbstractObject<'a>>,
+    ) -> Result<AbstractObject<'a>, VmError> {
+        let Some(object) = self.allocate_or_collect(size, allocate) else {
+            vm_log!(
+                self.log_config,
+                LogCategory::Gc,
+                Level::Warn,
+                "cannot allocate {kind:?} even after full garbage collection"
+            );
+            return Err(VmError::OutOfMemoryError);
+        };
+
+        let event = AllocationEvent { kind, size };
+        self.allocation_counters.record(&event);
+        for hook in self.allocation_hooks.iter() {
+            hook.notify(&event);
+        }
+        Ok(object)
+    }
+
+    /// Invokes the given allocation function and, if the memory is full, runs the gc and
+    /// retries. If the gc only finished an incremental marking cycle, the objects that became
+    /// garbage during the cycle were kept alive, so we retry again after a full collection.
+    /// If the memory is still full, the heap grows, until it reaches its maximum size.
+    fn allocate_or_collect(
+        &mut self,
+        size: usize,
+        allocate: impl Fn(&mut ObjectAllocator<'a>) -> Option<AbstractObject<'a>>,
+    ) -> Option<AbstractObject<'a>> {
+        if let Some(object) = allocate(&mut self.object_allocator) {
+            return Some(object);
+        }
+
+        let was_marking = self.object_allocator.is_marking();
+        self.collect_garbage(false)
+            .expect("could run garbage collection");
+        if let Some(object) = allocate(&mut self.object_allocator) {
+            return Some(object);
+        }
+        if was_marking {
+            self.collect_garbage(false)
+                .expect("could run garbage collection");
+            if let Some(object) = allocate(&mut self.object_allocator) {
+                return Some(object);
             }
         }
+
+        while self.object_allocator.can_grow() {
+            self.grow_heap(size).expect("could grow the heap");
+            if let Some(object) = allocate(&mut self.object_allocator) {
+                return Some(object);
+            }
+        }
+
+        // As a last resort, before failing, the soft references are cleared too
+        self.collect_garbage(true)
+            .expect("could run garbage collection");
+        allocate(&mut self.object_allocator)
     }
 
     pub fn clone_array(&mut self, value: Value<'a>) -> Result<Value<'a>, VmError> {
         match &value {
             Value::Object(array) if array.kind() == ObjectKind::Array => {
                 let new_array =
-                    self.new_array(array.elements_type(), array.len().into_usize_safe());
-                array_copy(array, 0, &new_array, 0, array.len().into_usize_safe())?;
+                    self.new_array(array.elements_type(), array.len().into_usize_safe())?;
+                array_copy(self, array, 0, &new_array, 0, array.len().into_usize_safe())?;
                 Ok(Value::Object(new_array))
             }
             _ => Err(VmError::ValidationException),
         }
     }
 
+    /// Allocates a shallow copy of the given instance, as `Object.clone` does: the fields are
+    /// copied, but not the objects they refer to. The copy is a new object, with its own
+    /// identity hash code and monitor.
+    pub fn clone_object(
+        &mut self,
+        object: &AbstractObject<'a>,
+    ) -> Result<AbstractObject<'a>, VmError> {
+        let class = self.get_class_by_id(object.class_id())?;
+        let scope = self.handle_scope();
+        let object = scope.handle(object.clone());
+        let copy = self.new_object_of_class(class)?;
+        let object = object.get();
+        for index in 0..class.num_total_fields {
+            let value = object.get_field(class, index);
+            self.write_barrier(&value);
+            copy.set_field(index, value);
+        }
+        Ok(copy)
+    }
+
+    /// Returns the `java.lang.Class` instance of the given class, if it has already been
+    /// created; see [get_java_lang_class_object]
+    ///
+    /// [get_java_lang_class_object]: crate::java_objects_creation::get_java_lang_class_object
+    pub fn find_class_object(&self, class_name: &str) -> Option<AbstractObject<'a>> {
+        self.class_objects.get(class_name).cloned()
+    }
+
+    pub(crate) fn register_class_object(&mut self, class_name: &str, object: AbstractObject<'a>) {
+        self.class_objects.insert(class_name.to_string(), object);
+    }
+
+    /// Returns the canonical instance of the string with the given content, if any; see
+    /// [get_interned_java_lang_string]
+    ///
+    /// [get_interned_java_lang_string]: crate::java_objects_creation::get_interned_java_lang_string
+    pub fn find_interned_string(&self, content: &str) -> Option<AbstractObject<'a>> {
+        self.interned_strings.get(content).cloned()
+    }
+
+    pub(crate) fn register_interned_string(&mut self, content: String, object: AbstractObject<'a>) {
+        self.interned_strings.insert(content, object);
+    }
+
     pub(crate) fn associate_stack_trace_with_throwable(
         &mut self,
         throwable: AbstractObject<'a>,
         call_stack: Vec<StackTraceElement<'a>>,
     ) {
         self.throwable_call_stacks
-            .insert(throwable.identity_hash_code(), call_stack);
+            .insert(throwable.id(), call_stack);
     }
 
     pub(crate) fn get_stack_trace_associated_with_throwable(
         &self,
         throwable: AbstractObject<'a>,
     ) -> Option<&Vec<StackTraceElement<'a>>> {
-        self.throwable_call_stacks
-            .get(&throwable.identity_hash_code())
+        self.throwable_call_stacks.get(&throwable.id())
+    }
+
+    /// Returns the identity hash code of the object, as `System.identityHashCode` does,
+    /// assigning one the first time it is requested
+    pub fn identity_hash_code(&mut self, object: &AbstractObject<'a>) -> i32 {
+        match object.identity_hash_code() {
+            Some(hash_code) => hash_code,
+            None => {
+                let hash_code = self.identity_hashes.next_hash_code();
+                object.set_identity_hash_code(hash_code);
+                hash_code
+            }
+        }
+    }
+
+    /// The number of objects allocated so far, including the ones already garbage collected.
+    /// Since object ids are assigned progressively, this is also the id of the last object.
+    pub fn allocated_objects_count(&self) -> u64 {
+        self.object_allocator.allocated_objects_count()
+    }
+
+    /// The counters of all the allocations performed so far
+    pub fn allocation_counters(&self) -> AllocationCounters {
+        self.allocation_counters
+    }
+
+    /// The statistics of the garbage collector so far; they can be queried at any time,
+    /// i.e. also by a native method while the java code is running
+    pub fn gc_stats(&self) -> GcStats {
+        self.object_allocator.stats()
+    }
+
+    /// Invokes the visitor with a snapshot of each live object of the heap, in no particular
+    /// order. A full collection runs first, so that only the reachable objects are visited.
+    pub fn for_each_live_object(
+        &mut self,
+        mut visitor: impl FnMut(&HeapObject<'a>),
+    ) -> Result<(), VmError> {
+        self.run_garbage_collection()?;
+        let classes_by_statics_address: HashMap<usize, ClassId> = self
+            .class_manager
+            .all_statics()
+            .map(|(class_id, statics)| (statics.address(), class_id))
+            .collect();
+        for object in self.object_allocator.allocated_objects() {
+            let statics_of = match classes_by_statics_address.get(&object.address()) {
+                Some(class_id) => Some(self.get_class_by_id(*class_id)?),
+                None => None,
+            };
+            visitor(&HeapObject::of(self, &object, statics_of)?);
+        }
+        Ok(())
+    }
+
+    /// Creates a scope to root the objects held by Rust code, which would otherwise be
+    /// collected, or moved, by the gc; see [HandleScope]
+    pub fn handle_scope(&self) -> HandleScope<'a> {
+        HandleScope::new(self.handles.clone())
+    }
+
+    /// Creates a handle that refers to the given object without keeping it alive;
+    /// see [WeakHandle]
+    pub fn new_weak_handle(&self, object: &AbstractObject<'a>) -> WeakHandle<'a> {
+        WeakHandle::new(self.handles.clone(), Some(object.clone()))
+    }
+
+    /// Adds a hook that will be invoked after every allocation on the heap
+    pub fn add_allocation_hook(&mut self, hook: AllocationHook) {
+        self.allocation_hooks.push(hook);
     }
 
     pub fn debug_stats(&self) {
@@ -297,19 +1766,728 @@ impl<'a> Vm<'a> {
         )
     }
 
-    pub fn run_garbage_collection(&mut self) -> Result<(), VmError> {
+    /// Selects the algorithm of the garbage collector. Since it determines how the memory is
+    /// split, it must be chosen before any object is allocated, i.e. before invoking any method.
+    pub fn set_gc_algorithm(&mut self, algorithm: GcAlgorithm) {
+        self.object_allocator.set_algorithm(algorithm);
+    }
+
+    pub fn gc_algorithm(&self) -> GcAlgorithm {
+        self.object_allocator.algorithm()
+    }
+
+    /// Makes the heap start from the given size and grow, when it is full even after a
+    /// collection, following the given policy, up to the maximum memory of the vm.
+    /// Like the gc algorithm, it must be chosen before any object is allocated.
+    pub fn set_heap_growth(&mut self, initial_memory: usize, policy: HeapGrowthPolicy) {
+        self.object_allocator
+            .set_heap_growth(initial_memory, policy);
+    }
+
+    pub fn heap_growth_policy(&self) -> HeapGrowthPolicy {
+        self.object_allocator.heap_growth_policy()
+    }
+
+    /// The memory currently reserved for the heap, which is at most the maximum memory
+    pub fn heap_size(&self) -> usize {
+        self.object_allocator.heap_size()
+    }
+
+    pub fn max_heap_size(&self) -> usize {
+        self.object_allocator.max_heap_size()
+    }
+
+    /// The memory that can still be allocated before the next garbage collection
+    pub fn free_heap_size(&self) -> usize {
+        self.object_allocator.free_heap_size()
+    }
+
+    /// Enables, or disables with `None`, the incremental marking mode of the garbage collector.
+    /// If a marking cycle is in progress, it will still be completed by the next collection.
+    pub fn set_incremental_marking(&mut self, config: Option<IncrementalMarkingConfig>) {
+        self.object_allocator.incremental_marking = config;
+    }
+
+    /// Invoked between two instructions, when all the references in use are in the gc roots.
+    /// Starts a new incremental marking cycle, or executes one slice of the current one,
+    /// and then invokes the callbacks requested via the [SafepointRequester]. Finally, if
+    /// the next instruction is a switch point, lets the other java threads run when the
+    /// time slice of the current one is over.
+    pub(crate) fn safepoint(&mut self, is_switch_point: bool) -> Result<(), VmError> {
+        if let Some(config) = self.object_allocator.incremental_marking {
+            if self.object_allocator.should_start_marking() {
+                let roots = self.gc_roots();
+                unsafe { self.object_allocator.start_marking(roots) };
+            } else if self.object_allocator.is_marking() {
+                let deadline = config
+                    .slice_time_budget
+                    .map(|budget| Instant::now() + budget);
+                unsafe {
+                    self.object_allocator.do_marking_slice_until(
+                        config.objects_per_slice,
+                        deadline,
+                        &self.class_manager,
+                    )?;
+                }
+            }
+        }
+        if self.safepoint_requests.has_pending_requests() {
+            self.run_safepoint_requests();
+        }
+        if !self.pending_references.is_empty() {
+            self.enqueue_pending_references()?;
+        }
+        if !self.finalization_queue.is_empty() {
+            self.run_finalization()?;
+        }
+        if is_switch_point {
+            self.thread_switch_point()?;
+        }
+        Ok(())
+    }
+
+    /// The number of switch points, i.e. method invocations and backward branches, that a
+    /// java thread executes before the vm lets the next one run. Lower values interleave
+    /// the threads more finely, at the cost of more switches.
+    pub fn set_thread_time_slice(&mut self, switch_points: usize) {
+        self.scheduler.set_time_slice(switch_points);
+    }
+
+    pub fn thread_time_slice(&self) -> usize {
+        self.scheduler.time_slice()
+    }
+
+    /// The java thread that is running
+    pub fn current_thread_id(&self) -> ThreadId {
+        self.scheduler.current_id()
+    }
+
+    /// Returns the instance of `java.lang.Thread` of the running thread, creating the one of
+    /// the main thread, together with its thread groups, the first time it is needed
+    pub(crate) fn current_thread_object(
+        &mut self,
+        call_stack: &mut CallStack<'a>,
+    ) -> Result<AbstractObject<'a>, MethodCallFailed<'a>> {
+        match self.scheduler.current_thread_object() {
+            Some(thread_object) => Ok(thread_object),
+            None => new_main_thread_object(self, call_stack),
+        }
+    }
+
+    pub(crate) fn set_current_thread_object(&mut self, thread_object: AbstractObject<'a>) {
+        self.scheduler.set_current_thread_object(thread_object);
+    }
+
+    /// Whether the given instance of `java.lang.Thread` has been started and has not
+    /// terminated yet
+    pub(crate) fn is_thread_alive(&self, thread_object: &AbstractObject<'a>) -> bool {
+        self.scheduler.is_alive(thread_object)
+    }
+
+    /// Starts a new java thread for the given instance of `java.lang.Thread`, which will
+    /// execute its `run` method once the scheduler switches to it
+    pub(crate) fn start_thread(
+        &mut self,
+        thread_object: AbstractObject<'a>,
+    ) -> Result<(), VmError> {
+        let daemon = get_object_field(self, &thread_object, "daemon")? == Value::Int(1);
+        set_object_field(
+            self,
+            &thread_object,
+            "threadStatus",
+            Value::Int(THREAD_STATUS_RUNNABLE),
+        )?;
+        let call_stack = self.allocate_call_stack();
+        self.scheduler.add_thread(thread_object, daemon, call_stack);
+        Ok(())
+    }
+
+    /// Runs the given suspended thread for one time slice, or until it blocks. The thread
+    /// executes its `run` method, then `Thread.exit`, and is removed once they complete.
+    fn run_thread(&mut self, id: ThreadId) {
+        let current = self.scheduler.current_id();
+        self.scheduler.switch_to(id);
+        let call_stack = self.scheduler.take_call_stack(id);
+        loop {
+            let phase = self.scheduler.current_phase();
+            let result = if call_stack.depth() > 0 {
+                self.resume_frames(call_stack, 0)
+            } else {
+                self.start_thread_phase(call_stack, phase)
+            };
+            if is_suspended(&result) {
+                self.scheduler.suspend_current(call_stack);
+                break;
+            }
+            match (phase, result) {
+                (_, Ok(_))
+                | (_, Err(MethodCallFailed::InternalError(VmError::ThreadTerminated)))
+                | (_, Err(MethodCallFailed::VmExit(_))) => {}
+                (ThreadPhase::Run, Err(MethodCallFailed::ExceptionThrown(exception))) => {
+                    let class_name = self
+                        .get_class_by_id(exception.0.class_id())
+                        .map(|class| class.name.as_str())
+                        .unwrap_or("?");
+                    warn!("exception in thread {id}: {class_name}");
+                }
+                (ThreadPhase::Run, Err(err)) => error!("thread {id} failed: {err:?}"),
+                (ThreadPhase::Exit, Err(err)) => error!("thread {id} failed to exit: {err:?}"),
+            }
+            if phase == ThreadPhase::Run
+                && !self.scheduler.is_terminating()
+                && self.exit_status.is_none()
+            {
+                // Like the JVM, let Thread.exit() remove the thread from its group
+                self.scheduler.set_current_phase(ThreadPhase::Exit);
+                continue;
+            }
+            self.terminate_current_thread();
+            break;
+        }
+        self.scheduler.switch_to(current);
+    }
+
+    /// Invokes the method of the given phase of the running thread, i.e. `run` or
+    /// `Thread.exit`, on its empty call stack
+    fn start_thread_phase(
+        &mut self,
+        call_stack: &mut CallStack<'a>,
+        phase: ThreadPhase,
+    ) -> MethodCallResult<'a> {
+        if self.is_current_thread_terminating() {
+            return Err(MethodCallFailed::InternalError(VmError::ThreadTerminated));
+        }
+        let thread_object = self
+            .scheduler
+            .current_thread_object()
+            .expect("started threads should have an object");
+        let method = match phase {
+            ThreadPhase::Run => {
+                let class = self.get_class_by_id(thread_object.class_id())?;
+                CallFrame::find_virtual_method(class, "run", "()V")?
+            }
+            ThreadPhase::Exit => {
+                self.resolve_class_method(call_stack, "java/lang/Thread", "exit", "()V")?
+            }
+        };
+        self.invoke_suspendable(call_stack, method, Some(thread_object), Vec::new())
+    }
+
+    /// Marks the running thread as terminated and removes it
+    fn terminate_current_thread(&mut self) {
+        let id = self.current_thread_id();
+        let thread_object = self
+            .scheduler
+            .current_thread_object()
+            .expect("started threads should have an object");
+        if let Err(err) = set_object_field(
+            self,
+            &thread_object,
+            "threadStatus",
+            Value::Int(THREAD_STATUS_TERMINATED),
+        ) {
+            error!("cannot update the status of thread {id}: {err:?}");
+        }
+        // Wakes up the threads in Thread.join, which waits on the thread object
+        self.scheduler.notify(&thread_object, true);
+        self.scheduler.remove_current();
+    }
+
+    /// Resumes the running thread, which was suspended: completes its pending native method,
+    /// if any, then the frames above the given depth, innermost first, each receiving the
+    /// result of the method it invoked. Returns the result of the outermost one, or fails with
+    /// [VmError::ThreadSuspended] if the thread is suspended again. If the vm is stopping the
+    /// thread, its frames are just popped.
+    fn resume_frames(
+        &mut self,
+        call_stack: &mut CallStack<'a>,
+        base_depth: usize,
+    ) -> MethodCallResult<'a> {
+        if self.is_current_thread_terminating() {
+            self.scheduler.clear_blocking();
+            let mut result = Err(MethodCallFailed::InternalError(VmError::ThreadTerminated));
+            while call_stack.depth() > base_depth {
+                result = self.complete_frame(call_stack, result);
+            }
+            return result;
+        }
+        let mut callee_result = self.scheduler.take_continuation().map(|continuation| {
+            // The continuation is the rest of a native method, which cannot be suspended
+            self.scheduler.pin_current();
+            let result = continuation(self, call_stack);
+            self.scheduler.unpin_current();
+            result
+        });
+        loop {
+            if callee_result.as_ref().is_some_and(is_suspended) {
+                return Err(MethodCallFailed::InternalError(VmError::ThreadSuspended));
+            }
+            let Some(mut frame) = call_stack
+                .top_frame()
+                .filter(|_| call_stack.depth() > base_depth)
+            else {
+                return callee_result
+                    .expect("a suspended thread should have frames or a pending native method");
+            };
+            let result = frame
+                .as_mut()
+                .resume(self, call_stack, callee_result.take());
+            if is_suspended(&result) {
+                return result;
+            }
+            callee_result = Some(self.complete_frame(call_stack, result));
+        }
+    }
+
+    /// Invoked at the switch points, i.e. method invocations and backward branches:
+    /// if the running thread has exhausted its time slice, lets the next one run
+    fn thread_switch_point(&mut self) -> Result<(), VmError> {
+        if self.is_current_thread_terminating() {
+            return Err(VmError::ThreadTerminated);
+        }
+        if !self.scheduler.has_other_threads() {
+            return Ok(());
+        }
+        if self.scheduler.count_switch_point() {
+            let result = self.yield_thread();
+            if result == Err(VmError::ThreadSuspended) {
+                self.scheduler.suspend_current_at_switch_point();
+            }
+            result?;
+        }
+        Ok(())
+    }
+
+    /// Whether the running thread can be suspended, i.e. it is only executing bytecode, and
+    /// no Rust code that invoked a java method is waiting for its result
+    pub(crate) fn can_suspend_current_thread(&self) -> bool {
+        self.scheduler.current_pinned() == 0
+    }
+
+    /// Lets the other threads run. If the running thread can be suspended, fails with
+    /// [VmError::ThreadSuspended], so that the caller must be able to restart what it was
+    /// doing when the thread is resumed; otherwise, the other threads run nested, until they
+    /// get suspended. Fails with [VmError::ThreadTerminated] if the vm is stopping this thread.
+    pub(crate) fn yield_thread(&mut self) -> Result<(), VmError> {
+        if self.can_suspend_current_thread() {
+            return Err(VmError::ThreadSuspended);
+        }
+        self.run_other_threads_nested()
+    }
+
+    /// Lets each of the other threads that can run execute a time slice, nested in the
+    /// execution of the running one. Fails with [VmError::ThreadTerminated] if the vm is
+    /// stopping this thread.
+    pub(crate) fn run_other_threads_nested(&mut self) -> Result<(), VmError> {
+        self.run_other_threads();
+        if self.is_current_thread_terminating() {
+            return Err(VmError::ThreadTerminated);
+        }
+        Ok(())
+    }
+
+    /// Like [run_other_threads_nested](Self::run_other_threads_nested), but keeps the given
+    /// object among the gc roots while the other threads run, and returns it, since the gc may
+    /// have moved it
+    pub(crate) fn yield_thread_keeping(
+        &mut self,
+        object: AbstractObject<'a>,
+    ) -> Result<AbstractObject<'a>, VmError> {
+        let scope = self.handle_scope();
+        let object = scope.handle(object);
+        self.run_other_threads_nested()?;
+        Ok(object.get())
+    }
+
+    /// Blocks the running thread, which is executing a native method, until the scheduler can
+    /// run it again, e.g. because it was notified, then completes the native method with the
+    /// given continuation. If the native method was invoked by bytecode, the thread is
+    /// suspended and the continuation runs when it is resumed; otherwise, the other threads
+    /// run nested until the running one can continue.
+    pub(crate) fn block_current_thread(
+        &mut self,
+        call_stack: &mut CallStack<'a>,
+        continuation: NativeContinuation<'a>,
+    ) -> MethodCallResult<'a> {
+        if self.scheduler.current_pinned() == 1 {
+            self.scheduler.set_continuation(continuation);
+            return Err(MethodCallFailed::InternalError(VmError::ThreadSuspended));
+        }
+        self.run_other_threads();
+        self.run_other_threads_until_current_can_run();
+        if self.is_current_thread_terminating() {
+            self.scheduler.clear_blocking();
+            return Err(MethodCallFailed::InternalError(VmError::ThreadTerminated));
+        }
+        continuation(self, call_stack)
+    }
+
+    /// Like [block_current_thread](Self::block_current_thread), but keeps the given object
+    /// among the gc roots while the thread is blocked, and gives it to the continuation
+    pub(crate) fn block_current_thread_keeping(
+        &mut self,
+        call_stack: &mut CallStack<'a>,
+        object: AbstractObject<'a>,
+        continuation: impl FnOnce(&mut Vm<'a>, &mut CallStack<'a>, AbstractObject<'a>) -> MethodCallResult<'a>
+            + 'a,
+    ) -> MethodCallResult<'a> {
+        self.scheduler.set_kept_object(object);
+        self.block_current_thread(
+            call_stack,
+            Box::new(move |vm, call_stack| {
+                let object = vm
+                    .scheduler
+                    .take_kept_object()
+                    .expect("the object should have been kept");
+                continuation(vm, call_stack, object)
+            }),
+        )
+    }
+
+    /// Makes the running thread wait until another one notifies the given object, or until
+    /// the timeout, if any, expires, as done by `Object.wait`, then runs the given
+    /// continuation with the object, since the gc may have moved it. The caller must have
+    /// released the monitor of the object.
+    pub(crate) fn wait_for_notification(
+        &mut self,
+        call_stack: &mut CallStack<'a>,
+        object: AbstractObject<'a>,
+        timeout: Option<Duration>,
+        continuation: impl FnOnce(&mut Vm<'a>, &mut CallStack<'a>, AbstractObject<'a>) -> MethodCallResult<'a>
+            + 'a,
+    ) -> MethodCallResult<'a> {
+        let deadline = timeout.map(|timeout| self.io.monotonic_now() + timeout);
+        self.scheduler.start_waiting(Some(object), deadline);
+        // The scheduler does not resume this thread until it has been woken up
+        self.block_current_thread(
+            call_stack,
+            Box::new(move |vm, call_stack| {
+                let object = vm
+                    .scheduler
+                    .finish_waiting()
+                    .expect("should have waited on an object");
+                continuation(vm, call_stack, object)
+            }),
+        )
+    }
+
+    /// Suspends the running thread for the given duration, or until it is interrupted,
+    /// letting the other threads run, as done by `Thread.sleep`, then runs the given
+    /// continuation
+    pub(crate) fn sleep_thread(
+        &mut self,
+        call_stack: &mut CallStack<'a>,
+        duration: Duration,
+        continuation: impl FnOnce(&mut Vm<'a>, &mut CallStack<'a>) -> MethodCallResult<'a> + 'a,
+    ) -> MethodCallResult<'a> {
+        self.scheduler
+            .start_waiting(None, Some(self.io.monotonic_now() + duration));
+        self.block_current_thread(
+            call_stack,
+            Box::new(move |vm, call_stack| {
+                vm.scheduler.finish_waiting();
+                continuation(vm, call_stack)
+            }),
+        )
+    }
+
+    /// Sets the interrupt status of the given thread, waking it up if it is waiting or
+    /// sleeping, as done by `Thread.interrupt`
+    pub(crate) fn interrupt_thread(&mut self, thread_object: &AbstractObject<'a>) {
+        self.scheduler.interrupt(thread_object);
+    }
+
+    /// Returns the interrupt status of the given thread, optionally clearing it
+    pub(crate) fn is_thread_interrupted(
+        &mut self,
+        thread_object: &AbstractObject<'a>,
+        clear: bool,
+    ) -> bool {
+        self.scheduler.is_interrupted(thread_object, clear)
+    }
+
+    /// Returns and clears the interrupt status of the running thread, which the blocking
+    /// methods check to throw `InterruptedException`
+    pub(crate) fn take_current_thread_interrupt(&mut self) -> bool {
+        self.scheduler.take_current_interrupt()
+    }
+
+    /// Wakes up the threads waiting on the given object, either all of them or just one,
+    /// as done by `Object.notifyAll` and `Object.notify`
+    pub(crate) fn notify_waiting_threads(&mut self, object: &AbstractObject<'a>, all: bool) {
+        self.scheduler.notify(object, all);
+    }
+
+    /// Whether the vm is stopping the running thread: either the program has halted it, in
+    /// which case the main thread is unwound too, or the main thread is stopping the others
+    fn is_current_thread_terminating(&self) -> bool {
+        self.exit_status.is_some()
+            || (self.scheduler.is_terminating() && self.scheduler.current_id() != ThreadId::MAIN)
+    }
+
+    /// Resumes, for one time slice each, the suspended threads that can run, in round-robin
+    /// order starting after the running one, or all of them if the vm is stopping them.
+    /// Returns whether any thread ran.
+    fn run_other_threads(&mut self) -> bool {
+        let mut any_ran = false;
+        for id in self.scheduler.other_threads() {
+            let stopping = self.scheduler.is_terminating() || self.exit_status.is_some();
+            if self
+                .scheduler
+                .can_resume(id, self.io.monotonic_now(), stopping)
+            {
+                self.run_thread(id);
+                any_ran = true;
+            }
+        }
+        any_ran
+    }
+
+    /// Lets the other threads run until the running one, which is waiting or sleeping, can
+    /// continue, sleeping until the first timeout expires when they are all waiting too
+    fn run_other_threads_until_current_can_run(&mut self) {
+        loop {
+            let now = self.io.monotonic_now();
+            if self.scheduler.can_current_run(now) || self.is_current_thread_terminating() {
+                return;
+            }
+            if self.run_other_threads() {
+                continue;
+            }
+            match self.scheduler.next_deadline() {
+                Some(deadline) => self.io.sleep(deadline.saturating_duration_since(now)),
+                // Nobody can wake up the running thread: rather than hanging, we wake it up,
+                // which the JVM spec allows as a spurious wakeup
+                None => return,
+            }
+        }
+    }
+
+    /// Invoked when the main thread returns to the embedder: like the JVM does when `main`
+    /// completes, runs the other threads until they have all terminated, except the daemon
+    /// ones, which are stopped. Thus, no thread is left suspended when the embedder gets the
+    /// control back. If the program has halted the vm, all the other threads are stopped.
+    fn finish_other_threads(&mut self) {
+        if !self.scheduler.has_other_threads() || self.current_thread_id() != ThreadId::MAIN {
+            return;
+        }
+        while self.exit_status.is_none() && self.scheduler.has_other_non_daemon_threads() {
+            if self.scheduler.are_others_waiting_forever() {
+                // The JVM would hang; we rather stop them, like the daemon threads
+                warn!("all the remaining threads are waiting forever, stopping them");
+                break;
+            }
+            if !self.run_other_threads() {
+                // All of them are waiting, some with a timeout
+                if let Some(deadline) = self.scheduler.next_deadline() {
+                    let now = self.io.monotonic_now();
+                    self.io.sleep(deadline.saturating_duration_since(now));
+                }
+            }
+        }
+        self.scheduler.set_terminating(true);
+        while self.scheduler.has_other_threads() {
+            self.run_other_threads();
+        }
+        self.scheduler.set_terminating(false);
+    }
+
+    /// Returns a handle that can be used to stop the vm at its next safepoint
+    /// and inspect its state
+    pub fn safepoint_requester(&self) -> SafepointRequester {
+        self.safepoint_requests.clone()
+    }
+
+    /// Invokes the pending safepoint callbacks now. The vm reaches a safepoint before each
+    /// instruction, so this is only needed for the requests made while it is idle.
+    pub fn run_safepoint_requests(&mut self) {
+        let requests = self.safepoint_requests.take_requests();
+        if requests.is_empty() {
+            return;
+        }
+
+        let call_stacks = self
+            .call_stacks
+            .iter_mut()
+            .map(|call_stack| call_stack.get_stack_trace_elements())
+            .filter(|stack_trace| !stack_trace.is_empty())
+            .collect();
+        let roots = self
+            .gc_roots()
+            .into_iter()
+            .map(|root| unsafe { (*root).clone() })
+            .collect();
+        let view = SafepointView {
+            vm: self,
+            call_stacks,
+            roots,
+        };
+        for request in requests {
+            request(&view);
+        }
+    }
+
+    /// Must be invoked whenever a reference is stored into a field of an object, or into an
+    /// element of an array, to keep the incremental marking correct
+    pub(crate) fn write_barrier(&self, value: &Value<'a>) {
+        self.object_allocator.write_barrier(value)
+    }
+
+    /// The objects that the gc must update if they survive, but must not keep alive
+    fn gc_weak_roots(&mut self) -> Vec<*mut Option<AbstractObject<'a>>> {
+        let mut weak_roots: Vec<_> = self.collection_intrinsics.weak_roots().collect();
+        weak_roots.extend(self.handles.borrow_mut().weak_roots());
+        weak_roots
+    }
+
+    /// The objects that the gc must keep alive only as long as some other object is alive
+    fn gc_ephemerons(&mut self) -> Vec<Ephemeron<'a>> {
+        self.collection_intrinsics.ephemerons().collect()
+    }
+
+    fn gc_roots(&mut self) -> Vec<*mut AbstractObject<'a>> {
         let mut roots = vec![];
+        roots.extend(self.class_manager.statics_gc_roots());
+        roots.extend(self.class_manager.defining_loaders_gc_roots());
         roots.extend(
-            self.statics
-                .iter_mut()
-                .map(|(_, object)| object as *mut AbstractObject<'a>),
+            self.class_objects
+                .values_mut()
+                .chain(self.interned_strings.values_mut())
+                .map(|object| object as *mut AbstractObject<'a>),
         );
         roots.extend(self.call_stacks.iter_mut().flat_map(|s| s.gc_roots()));
+        roots.extend(self.boxed_caches.gc_roots());
+        roots.extend(self.shutdown_hooks.gc_roots());
+        roots.extend(self.standard_streams.gc_roots());
+        roots.extend(self.collection_intrinsics.gc_roots());
+        roots.extend(self.scheduler.gc_roots());
+        roots.extend(self.handles.borrow_mut().gc_roots());
+        roots.extend(
+            self.pending_references
+                .iter_mut()
+                .chain(self.finalization_queue.iter_mut())
+                .map(|object| object as *mut AbstractObject<'a>),
+        );
+        // The printed objects must survive until the tests inspect them
+        roots.extend(self.printed.iter_mut().filter_map(|value| match value {
+            Value::Object(object) => Some(object as *mut AbstractObject<'a>),
+            _ => None,
+        }));
+        roots
+    }
 
-        unsafe {
-            self.object_allocator
-                .do_garbage_collection(roots, &self.class_manager)?;
+    /// Runs a full collection. If an incremental marking cycle was in progress, the objects
+    /// that became garbage after being marked survive when it completes, so that a second
+    /// collection follows.
+    pub fn run_garbage_collection(&mut self) -> Result<(), VmError> {
+        let was_marking = self.object_allocator.is_marking();
+        self.collect_garbage(false)?;
+        if was_marking {
+            self.collect_garbage(false)?;
         }
         Ok(())
     }
+
+    /// Runs the gc, clearing the soft references only if requested. The references whose
+    /// referent was cleared are enqueued now, unless their queue is locked by some thread,
+    /// in which case they will be enqueued at one of the next safepoints.
+    fn collect_garbage(&mut self, clear_soft_references: bool) -> Result<(), VmError> {
+        let roots = self.gc_roots();
+        let weak_roots = self.gc_weak_roots();
+        let ephemerons = self.gc_ephemerons();
+
+        let collected = unsafe {
+            self.object_allocator.do_garbage_collection(
+                roots,
+                weak_roots,
+                ephemerons,
+                clear_soft_references,
+                &self.class_manager,
+            )?
+        };
+        self.after_garbage_collection(collected)
+    }
+
+    /// Runs the gc while growing the heap, so that an allocation of the given size will fit
+    fn grow_heap(&mut self, required_size: usize) -> Result<(), VmError> {
+        let roots = self.gc_roots();
+        let weak_roots = self.gc_weak_roots();
+        let ephemerons = self.gc_ephemerons();
+
+        let collected = unsafe {
+            self.object_allocator.do_garbage_collection_growing_heap(
+                roots,
+                weak_roots,
+                ephemerons,
+                required_size,
+                &self.class_manager,
+            )?
+        };
+        self.after_garbage_collection(collected)
+    }
+
+    fn after_garbage_collection(&mut self, collected: CollectedObjects<'a>) -> Result<(), VmError> {
+        self.collection_intrinsics.after_garbage_collection();
+        self.pending_references.extend(collected.cleared_references);
+        self.finalization_queue
+            .extend(collected.objects_to_finalize);
+        self.enqueue_pending_references()
+    }
+
+    /// Invokes the finalizers of the unreachable objects found by the gc, like
+    /// `System.runFinalization` does. The vm also invokes them at the first safepoint after
+    /// the collection: they run on a call stack reserved to them, but in the java thread that
+    /// has reached the safepoint. Like in the JVM, the exceptions they throw are ignored.
+    pub fn run_finalization(&mut self) -> Result<(), VmError> {
+        // A finalizer might trigger the finalization, or be suspended by a thread switch
+        if self.running_finalizers {
+            return Ok(());
+        }
+        self.running_finalizers = true;
+        let call_stack = match self.finalizer_call_stack.take() {
+            Some(call_stack) => call_stack,
+            None => self.allocate_call_stack(),
+        };
+        let result = self.run_finalizers(call_stack);
+        self.finalizer_call_stack = Some(call_stack);
+        self.running_finalizers = false;
+        result
+    }
+
+    fn run_finalizers(&mut self, call_stack: &mut CallStack<'a>) -> Result<(), VmError> {
+        // The object stays in the queue, which is a gc root, until its finalizer has completed
+        while let Some(object) = self.finalization_queue.front().cloned() {
+            let result = self.invoke_virtual(call_stack, object, "finalize", "()V", Vec::new());
+            self.finalization_queue.pop_front();
+            match result {
+                Ok(_) => {}
+                Err(MethodCallFailed::ExceptionThrown(exception)) => {
+                    debug!("ignoring exception thrown by a finalizer: {exception:?}");
+                }
+                Err(MethodCallFailed::InternalError(err)) => return Err(err),
+                // The running thread will be unwound at its next switch point
+                Err(MethodCallFailed::VmExit(_)) => return Ok(()),
+            }
+        }
+        Ok(())
+    }
+
+    fn enqueue_pending_references(&mut self) -> Result<(), VmError> {
+        let mut still_pending = Vec::new();
+        for reference in std::mem::take(&mut self.pending_references) {
+            if !try_enqueue_reference(self, &reference)? {
+                still_pending.push(reference);
+            }
+        }
+        self.pending_references = still_pending;
+        Ok(())
+    }
+}
+
+/// Whether the thread executing the method was suspended; see [VmError::ThreadSuspended]
+fn is_suspended(result: &MethodCallResult) -> bool {
+    matches!(
+        result,
+        Err(MethodCallFailed::InternalError(VmError::ThreadSuspended))
+    )
 }
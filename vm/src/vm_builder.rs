@@ -0,0 +1,389 @@
+use std::rc::Rc;
+
+#[cfg(feature = "std")]
+use std::num::NonZeroUsize;
+
+use crate::{
+    allocation::{AllocationEvent, AllocationHook},
+    call_stack::DEFAULT_MAX_CALL_STACK_DEPTH,
+    class_file_transformer::ClassFileTransformer,
+    class_load_observer::ClassLoadObserver,
+    class_path::ClassPathParseError,
+    class_path_scan::{ClassPathScanProgress, ScanProgressListener},
+    gc_algorithm::GcAlgorithm,
+    heap_growth::HeapGrowthPolicy,
+    incremental_marking::IncrementalMarkingConfig,
+    io::{JvmIo, JvmNet, OutputSink},
+    log_config::{LogCategory, LogConfig},
+    native_methods_registry::AnyVmNativeCallback,
+    thread_scheduler::DEFAULT_THREAD_TIME_SLICE,
+    vm::{Vm, DEFAULT_MAX_MEMORY},
+};
+
+/// Builder to configure and create a [Vm]
+#[derive(Debug, Clone)]
+pub struct VmBuilder {
+    max_memory: usize,
+    initial_memory: Option<usize>,
+    heap_growth_policy: HeapGrowthPolicy,
+    log_config: LogConfig,
+    strict_class_path: bool,
+    lazy_linkage: bool,
+    verify_classes: bool,
+    #[cfg(feature = "std")]
+    class_loading_threads: NonZeroUsize,
+    access_checks: bool,
+    gc_algorithm: GcAlgorithm,
+    incremental_marking: Option<IncrementalMarkingConfig>,
+    class_path_scan_listener: Option<ScanProgressListener>,
+    allocation_hooks: Vec<AllocationHook>,
+    class_file_transformers: Vec<Rc<dyn ClassFileTransformer>>,
+    class_load_observers: Vec<Rc<dyn ClassLoadObserver>>,
+    max_call_stack_depth: usize,
+    thread_time_slice: usize,
+    system_properties: Vec<(String, String)>,
+    io: Option<Rc<dyn JvmIo>>,
+    net: Option<Rc<dyn JvmNet>>,
+    stdout: Option<Rc<dyn OutputSink>>,
+    stderr: Option<Rc<dyn OutputSink>>,
+    boot_class_path: Vec<String>,
+    class_path: Vec<String>,
+    class_path_io: Option<Rc<dyn JvmIo>>,
+    natives: Vec<NativeRegistration>,
+}
+
+/// A native method or an intrinsic to register in the vm
+#[derive(Debug, Clone)]
+struct NativeRegistration {
+    class_name: String,
+    method_name: String,
+    type_descriptor: String,
+    callback: AnyVmNativeCallback,
+    is_intrinsic: bool,
+}
+
+impl Default for VmBuilder {
+    fn default() -> Self {
+        Self {
+            max_memory: DEFAULT_MAX_MEMORY,
+            initial_memory: None,
+            heap_growth_policy: HeapGrowthPolicy::default(),
+            log_config: Default::default(),
+            strict_class_path: false,
+            lazy_linkage: false,
+            verify_classes: true,
+            #[cfg(feature = "std")]
+            class_loading_threads: NonZeroUsize::MIN,
+            access_checks: true,
+            gc_algorithm: GcAlgorithm::default(),
+            incremental_marking: None,
+            class_path_scan_listener: None,
+            allocation_hooks: Vec::new(),
+            class_file_transformers: Vec::new(),
+            class_load_observers: Vec::new(),
+            max_call_stack_depth: DEFAULT_MAX_CALL_STACK_DEPTH,
+            thread_time_slice: DEFAULT_THREAD_TIME_SLICE,
+            system_properties: Vec::new(),
+            io: None,
+            net: None,
+            stdout: None,
+            stderr: None,
+            boot_class_path: Vec::new(),
+            class_path: Vec::new(),
+            class_path_io: None,
+            natives: Vec::new(),
+        }
+    }
+}
+
+impl VmBuilder {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// The maximum memory, in bytes, that the vm will use for the heap
+    pub fn with_max_memory(mut self, max_memory: usize) -> Self {
+        self.max_memory = max_memory;
+        self
+    }
+
+    /// The memory, in bytes, that the heap starts from; by default the maximum one.
+    /// It matters only with a growth policy other than [HeapGrowthPolicy::Fixed].
+    pub fn with_initial_memory(mut self, initial_memory: usize) -> Self {
+        self.initial_memory = Some(initial_memory);
+        self
+    }
+
+    /// How the heap grows from the initial memory to the maximum one; see [Vm::set_heap_growth]
+    pub fn with_heap_growth_policy(mut self, policy: HeapGrowthPolicy) -> Self {
+        self.heap_growth_policy = policy;
+        self
+    }
+
+    /// Replaces the whole log configuration
+    pub fn with_log_config(mut self, log_config: LogConfig) -> Self {
+        self.log_config = log_config;
+        self
+    }
+
+    /// Enables or disables a single category of log messages
+    pub fn with_log_category(mut self, category: LogCategory, enabled: bool) -> Self {
+        self.log_config = self.log_config.with_category(category, enabled);
+        self
+    }
+
+    /// Enables the detection of duplicate classes in the class path;
+    /// see [Vm::set_strict_class_path]
+    pub fn with_strict_class_path(mut self, strict: bool) -> Self {
+        self.strict_class_path = strict;
+        self
+    }
+
+    /// Tolerates references to missing classes until they are needed;
+    /// see [Vm::set_lazy_linkage]
+    pub fn with_lazy_linkage(mut self, lazy_linkage: bool) -> Self {
+        self.lazy_linkage = lazy_linkage;
+        self
+    }
+
+    /// Enables or disables the bytecode verifier; see [Vm::set_verify_classes]
+    pub fn with_verify_classes(mut self, verify_classes: bool) -> Self {
+        self.verify_classes = verify_classes;
+        self
+    }
+
+    /// Parses the class files on the given number of threads;
+    /// see [Vm::set_class_loading_threads]
+    #[cfg(feature = "std")]
+    pub fn with_class_loading_threads(mut self, threads: NonZeroUsize) -> Self {
+        self.class_loading_threads = threads;
+        self
+    }
+
+    /// Enables or disables the access control checks; see [Vm::set_access_checks]
+    pub fn with_access_checks(mut self, enabled: bool) -> Self {
+        self.access_checks = enabled;
+        self
+    }
+
+    /// Sets how many switch points a java thread executes before the next one runs;
+    /// see [Vm::set_thread_time_slice]
+    pub fn with_thread_time_slice(mut self, switch_points: usize) -> Self {
+        self.thread_time_slice = switch_points;
+        self
+    }
+
+    /// Selects the algorithm of the garbage collector; see [Vm::set_gc_algorithm]
+    pub fn with_gc_algorithm(mut self, algorithm: GcAlgorithm) -> Self {
+        self.gc_algorithm = algorithm;
+        self
+    }
+
+    /// Enables the incremental marking mode of the garbage collector;
+    /// see [Vm::set_incremental_marking]
+    pub fn with_incremental_marking(mut self, config: IncrementalMarkingConfig) -> Self {
+        self.incremental_marking = Some(config);
+        self
+    }
+
+    /// Reports the progress of the scan of the class path entries;
+    /// see [Vm::set_class_path_scan_listener]
+    pub fn with_class_path_scan_listener(
+        mut self,
+        listener: impl Fn(&ClassPathScanProgress) + 'static,
+    ) -> Self {
+        self.class_path_scan_listener = Some(ScanProgressListener::new(listener));
+        self
+    }
+
+    /// Adds a hook invoked after every allocation; see [Vm::add_allocation_hook]
+    pub fn with_allocation_hook(mut self, hook: impl Fn(&AllocationEvent) + 'static) -> Self {
+        self.allocation_hooks.push(AllocationHook::new(hook));
+        self
+    }
+
+    /// Adds a transformer of the bytes of the classes; see [Vm::add_class_file_transformer]
+    pub fn with_class_file_transformer(
+        mut self,
+        transformer: impl ClassFileTransformer + 'static,
+    ) -> Self {
+        self.class_file_transformers.push(Rc::new(transformer));
+        self
+    }
+
+    /// Adds an observer of the loading of the classes; see [Vm::add_class_load_observer]
+    pub fn with_class_load_observer(mut self, observer: impl ClassLoadObserver + 'static) -> Self {
+        self.class_load_observers.push(Rc::new(observer));
+        self
+    }
+
+    /// The maximum number of frames of each call stack; see [Vm::set_max_call_stack_depth]
+    pub fn with_max_call_stack_depth(mut self, max_depth: usize) -> Self {
+        self.max_call_stack_depth = max_depth;
+        self
+    }
+
+    /// Sets a system property, overriding the default one with the same name, if any;
+    /// see [Vm::set_system_property]
+    pub fn with_system_property(
+        mut self,
+        name: impl Into<String>,
+        value: impl Into<String>,
+    ) -> Self {
+        self.system_properties.push((name.into(), value.into()));
+        self
+    }
+
+    /// The services of the host that the natives can use; see [Vm::set_io]
+    pub fn with_io(mut self, io: impl JvmIo + 'static) -> Self {
+        self.io = Some(Rc::new(io));
+        self
+    }
+
+    /// The network that the sockets use; see [Vm::set_net]
+    pub fn with_net(mut self, net: impl JvmNet + 'static) -> Self {
+        self.net = Some(Rc::new(net));
+        self
+    }
+
+    /// Where `System.out` writes; see [Vm::set_stdout]
+    pub fn with_stdout(mut self, sink: impl OutputSink + 'static) -> Self {
+        self.stdout = Some(Rc::new(sink));
+        self
+    }
+
+    /// Where `System.err` writes; see [Vm::set_stderr]
+    pub fn with_stderr(mut self, sink: impl OutputSink + 'static) -> Self {
+        self.stderr = Some(Rc::new(sink));
+        self
+    }
+
+    /// Appends entries to the class path, separated by a colon (:) like in a real JVM;
+    /// see [Vm::append_class_path]. Invalid entries make [VmBuilder::build] fail.
+    pub fn with_class_path(mut self, class_path: impl Into<String>) -> Self {
+        self.class_path.push(class_path.into());
+        self
+    }
+
+    /// Appends entries to the boot class path, separated by a colon (:) like the class path;
+    /// see [Vm::append_boot_class_path]. Invalid entries make [VmBuilder::build] fail.
+    pub fn with_boot_class_path(mut self, class_path: impl Into<String>) -> Self {
+        self.boot_class_path.push(class_path.into());
+        self
+    }
+
+    /// How the jars of the class path are read; see [Vm::set_class_path_io]
+    pub fn with_class_path_io(mut self, io: impl JvmIo + 'static) -> Self {
+        self.class_path_io = Some(Rc::new(io));
+        self
+    }
+
+    /// Registers the implementation of a method marked as `native`, replacing the built-in
+    /// one, if any
+    pub fn with_native_method(
+        mut self,
+        class_name: &str,
+        method_name: &str,
+        type_descriptor: &str,
+        callback: AnyVmNativeCallback,
+    ) -> Self {
+        self.natives.push(NativeRegistration {
+            class_name: class_name.to_string(),
+            method_name: method_name.to_string(),
+            type_descriptor: type_descriptor.to_string(),
+            callback,
+            is_intrinsic: false,
+        });
+        self
+    }
+
+    /// Registers a native implementation of a method that is not marked as `native`, which
+    /// the vm runs instead of its bytecode
+    pub fn with_intrinsic(
+        mut self,
+        class_name: &str,
+        method_name: &str,
+        type_descriptor: &str,
+        callback: AnyVmNativeCallback,
+    ) -> Self {
+        self.natives.push(NativeRegistration {
+            class_name: class_name.to_string(),
+            method_name: method_name.to_string(),
+            type_descriptor: type_descriptor.to_string(),
+            callback,
+            is_intrinsic: true,
+        });
+        self
+    }
+
+    /// Creates the vm, failing only if an entry of the class path is not valid
+    pub fn build<'a>(self) -> Result<Vm<'a>, ClassPathParseError> {
+        let mut vm = Vm::new(self.max_memory);
+        vm.set_gc_algorithm(self.gc_algorithm);
+        vm.set_heap_growth(
+            self.initial_memory.unwrap_or(self.max_memory),
+            self.heap_growth_policy,
+        );
+        vm.set_log_config(self.log_config);
+        vm.set_strict_class_path(self.strict_class_path);
+        vm.set_lazy_linkage(self.lazy_linkage);
+        vm.set_verify_classes(self.verify_classes);
+        #[cfg(feature = "std")]
+        vm.set_class_loading_threads(self.class_loading_threads);
+        vm.set_access_checks(self.access_checks);
+        vm.set_thread_time_slice(self.thread_time_slice);
+        vm.set_incremental_marking(self.incremental_marking);
+        vm.set_class_path_scan_listener(self.class_path_scan_listener);
+        vm.set_max_call_stack_depth(self.max_call_stack_depth);
+        for hook in self.allocation_hooks {
+            vm.add_allocation_hook(hook);
+        }
+        for transformer in self.class_file_transformers {
+            vm.add_class_file_transformer(transformer);
+        }
+        for observer in self.class_load_observers {
+            vm.add_class_load_observer(observer);
+        }
+        for (name, value) in self.system_properties {
+            vm.set_system_property(&name, &value);
+        }
+        if let Some(io) = self.io {
+            vm.set_io(io);
+        }
+        if let Some(net) = self.net {
+            vm.set_net(net);
+        }
+        if let Some(stdout) = self.stdout {
+            vm.set_stdout(stdout);
+        }
+        if let Some(stderr) = self.stderr {
+            vm.set_stderr(stderr);
+        }
+        for native in self.natives {
+            let registry = &mut vm.native_methods_registry;
+            if native.is_intrinsic {
+                registry.register_intrinsic(
+                    &native.class_name,
+                    &native.method_name,
+                    &native.type_descriptor,
+                    native.callback,
+                );
+            } else {
+                registry.register(
+                    &native.class_name,
+                    &native.method_name,
+                    &native.type_descriptor,
+                    native.callback,
+                );
+            }
+        }
+        vm.set_class_path_io(self.class_path_io);
+        for class_path in self.boot_class_path {
+            vm.append_boot_class_path(&class_path)?;
+        }
+        for class_path in self.class_path {
+            vm.append_class_path(&class_path)?;
+        }
+        Ok(vm)
+    }
+}
@@ -1,3 +1,6 @@
+use std::{fmt, fmt::Formatter};
+
+use rjvm_reader::{program_counter::ProgramCounter, type_conversion::NarrowingError};
 use thiserror::Error;
 
 use crate::value_stack::ValueStackError;
@@ -10,40 +13,250 @@ pub enum VmError {
     #[error("unexpected error loading class: {0}")]
     ClassLoadingError(String),
 
-    /// TODO: this should become throwing a real `java.lang.NullPointerException`
+    /// A null dereference. The interpreter replaces it with a real
+    /// `java.lang.NullPointerException`, so that it can be caught by java code.
     #[error("null pointer exception")]
     NullPointerException,
 
-    /// TODO: this should become throwing a real `java.lang.ClassNotFoundException`
+    /// A `monitorexit`, or the return of a synchronized method, on a monitor that is not held.
+    /// The interpreter replaces it with a real `java.lang.IllegalMonitorStateException`.
+    #[error("illegal monitor state")]
+    IllegalMonitorStateException,
+
+    /// A class that cannot be found. When raised while executing bytecode, the interpreter
+    /// replaces it with a real `java.lang.NoClassDefFoundError`.
     #[error("class not found: {0}")]
     ClassNotFoundException(String),
 
+    /// A class whose static initializer has failed, thus cannot be used. When raised while
+    /// executing bytecode, the interpreter replaces it with a real
+    /// `java.lang.NoClassDefFoundError`.
+    #[error("Could not initialize class {0}")]
+    ErroneousClass(String),
+
+    /// A method that cannot be found. When raised while executing bytecode, the interpreter
+    /// replaces it with a real `java.lang.NoSuchMethodError`.
     #[error("method not found: {0}.{1}#{2}")]
     MethodNotFoundException(String, String, String),
 
+    /// A field that cannot be found. When raised while executing bytecode, the interpreter
+    /// replaces it with a real `java.lang.NoSuchFieldError`.
     #[error("field not found: {0}.{1}")]
     FieldNotFoundException(String, String),
 
+    #[error("value does not match the type of field {0}.{1}")]
+    FieldTypeMismatch(String, String),
+
     /// This is an overly generic error, abused to mean "something unexpected happened".
     /// It includes mostly errors that should be checked during the linking phase of the class file
     /// (which we have not implemented).
     #[error("validation exception - invalid class file")]
     ValidationException,
 
-    /// TODO: this should become throwing a real `java.lang.ArithmeticException`
-    #[error("arithmetic exception")]
+    /// A call stack has exceeded its maximum depth. The interpreter replaces it with a real
+    /// `java.lang.StackOverflowError`.
+    #[error("stack overflow")]
+    StackOverflowError,
+
+    /// The vm is stopping the thread, i.e. a daemon thread that is still running when the
+    /// program ends. It is not visible to the java code: it just unwinds the thread's stack.
+    #[error("thread terminated by the vm")]
+    ThreadTerminated,
+
+    /// The scheduler is switching from the running thread to another one. It is not visible
+    /// to the java code: it unwinds the interpreter up to the scheduler, leaving the frames of
+    /// the thread on its call stack, from which they are resumed later.
+    #[error("thread suspended by the vm")]
+    ThreadSuspended,
+
+    /// An integer division by zero. The interpreter replaces it with a real
+    /// `java.lang.ArithmeticException`.
+    #[error("/ by zero")]
     ArithmeticException,
 
+    #[error("unsupported bootstrap method: {0}")]
+    UnsupportedBootstrapMethod(String),
+
     #[error("not yet implemented")]
     NotImplemented,
 
-    /// TODO: this should become throwing a real `java.lang.ArrayIndexOutOfBoundsException`
-    #[error("array index out of bounds")]
-    ArrayIndexOutOfBoundsException,
+    /// An access to an array with an invalid index, which is stored here. The interpreter
+    /// replaces it with a real `java.lang.ArrayIndexOutOfBoundsException`.
+    #[error("{0}")]
+    ArrayIndexOutOfBoundsException(i32),
+
+    /// A failed `checkcast`. The interpreter replaces it with a real
+    /// `java.lang.ClassCastException`. Class names use the java binary format,
+    /// i.e. `java.lang.String`.
+    #[error("{class_name} cannot be cast to {target_class_name}")]
+    ClassCastException {
+        class_name: String,
+        target_class_name: String,
+    },
+
+    /// A store of a value into an array of an incompatible type, with the given message.
+    /// The interpreter replaces it with a real `java.lang.ArrayStoreException`.
+    #[error("{0}")]
+    ArrayStoreException(String),
+
+    /// The heap is full, even after a garbage collection. The interpreter replaces it with a
+    /// real `java.lang.OutOfMemoryError`.
+    #[error("Java heap space")]
+    OutOfMemoryError,
+
+    #[error("{0}")]
+    ArrayTypeMismatch(Box<ArrayTypeMismatch>),
+
+    #[error("{0}")]
+    ClassInitializationFailed(Box<ClassInitializationFailed>),
+
+    /// An access to a class, field or method that is not visible from the accessing class,
+    /// with the given message. The interpreter replaces it with a real
+    /// `java.lang.IllegalAccessError`.
+    #[error("{0}")]
+    IllegalAccessError(String),
+
+    /// A class whose bytecode was rejected by the verifier. When raised while executing
+    /// bytecode, the interpreter replaces it with a real `java.lang.VerifyError`.
+    #[error("{0}")]
+    VerifyError(Box<VerifyError>),
+
+    /// A class file of a version that the vm does not support, with the name of the class and
+    /// the major and minor versions. When raised while executing bytecode, the interpreter
+    /// replaces it with a real `java.lang.UnsupportedClassVersionError`.
+    #[error(
+        "{0} has been compiled by an unsupported version of java (class file version {1}.{2})"
+    )]
+    UnsupportedClassVersionError(String, u16, u16),
+
+    /// A class whose constant pool is inconsistent, detected when the class is linked. When
+    /// raised while executing bytecode, the interpreter replaces it with a real
+    /// `java.lang.ClassFormatError`.
+    #[error("{0}")]
+    ClassFormatError(Box<ClassFormatError>),
+
+    /// An invocation that resolved to an abstract method, i.e. of a class compiled against an
+    /// older version of an interface, with the given message. When raised while executing
+    /// bytecode, the interpreter replaces it with a real `java.lang.AbstractMethodError`.
+    #[error("{0}")]
+    AbstractMethodError(String),
+
+    /// A `new` of an abstract class or of an interface, with the given message. When raised
+    /// while executing bytecode, the interpreter replaces it with a real
+    /// `java.lang.InstantiationError`.
+    #[error("{0}")]
+    InstantiationError(String),
+
+    /// A redefinition of a class via [Vm::redefine_class](crate::vm::Vm::redefine_class)
+    /// that changes more than the code of its methods, with the given reason
+    #[error("unsupported redefinition of class {0}: {1}")]
+    UnsupportedClassRedefinition(String, String),
+
+    /// A jar launched via [Vm::invoke_jar](crate::vm::Vm::invoke_jar) whose manifest does
+    /// not name the class to run, with the path of the jar
+    #[error("no main manifest attribute, in {0}")]
+    MissingMainClass(String),
+}
+
+/// An array load or store whose array's elements do not have the type expected by the
+/// instruction, or the type of the value being stored
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArrayTypeMismatch {
+    pub class_name: String,
+    pub method_name: String,
+    pub pc: ProgramCounter,
+    /// The mnemonic of the instruction, i.e. `iastore`
+    pub instruction: &'static str,
+    pub array_element_type: String,
+    /// The runtime type of the value being stored; `None` for loads
+    pub value_type: Option<String>,
+}
+
+impl fmt::Display for ArrayTypeMismatch {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} at {}.{} (pc {}): array of {} ",
+            self.instruction, self.class_name, self.method_name, self.pc, self.array_element_type
+        )?;
+        match &self.value_type {
+            Some(value_type) => write!(f, "cannot store a value of type {value_type}"),
+            None => f.write_str("does not match the instruction"),
+        }
+    }
+}
+
+/// An error raised while running the static initializer of a class, together with the chain of
+/// class initializations that led to it
+#[derive(Debug, PartialEq, Eq)]
+pub struct ClassInitializationFailed {
+    /// The classes whose initialization was in progress, outermost first. The last one is the
+    /// class whose initializer failed.
+    pub initialization_chain: Vec<String>,
+    pub cause: VmError,
+}
+
+impl fmt::Display for ClassInitializationFailed {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "error initializing class {} (initialization chain: {}): {}",
+            self.initialization_chain
+                .last()
+                .map(String::as_str)
+                .unwrap_or_default(),
+            self.initialization_chain.join(" -> "),
+            self.cause
+        )
+    }
+}
+
+/// A method whose bytecode does not type check against its stack map frames
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VerifyError {
+    pub class_name: String,
+    pub method_name: String,
+    pub method_descriptor: String,
+    /// The address of the offending instruction
+    pub pc: ProgramCounter,
+    pub reason: String,
+}
+
+impl fmt::Display for VerifyError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}.{}{} @{}: {}",
+            self.class_name, self.method_name, self.method_descriptor, self.pc, self.reason
+        )
+    }
+}
+
+/// An entry of a class' constant pool that references entries of the wrong kind, or that
+/// contains a malformed name or descriptor
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClassFormatError {
+    pub class_name: String,
+    /// The index of the offending constant
+    pub index: u16,
+    pub reason: String,
+}
 
-    /// TODO: this should become throwing a real `java.lang.ClassCastException`
-    #[error("class cast exception")]
-    ClassCastException,
+impl fmt::Display for ClassFormatError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "invalid constant pool entry #{} in class {}: {}",
+            self.index, self.class_name, self.reason
+        )
+    }
+}
+
+/// Values that overflow their type can only come from invalid bytecode
+impl From<NarrowingError> for VmError {
+    fn from(_: NarrowingError) -> Self {
+        Self::ValidationException
+    }
 }
 
 // TODO: remove once we implement exceptions
@@ -0,0 +1,137 @@
+use std::{fmt, fmt::Formatter};
+
+use rjvm_reader::{
+    class_access_flags::ClassAccessFlags, class_file_method::ClassFileMethod,
+    method_flags::MethodFlags,
+};
+
+use crate::{
+    class::{package_name_of, Class, ClassRef},
+    class_and_method::ClassAndMethod,
+};
+
+/// The virtual method table of a class, built when the class is defined, so that virtual
+/// dispatch is an index lookup rather than a search of the superclasses by name.
+///
+/// A class starts with the entries of its superclass, in the same order, replaces the ones of
+/// the methods it overrides, and appends its new methods. Thus, a method has the same index in
+/// the tables of all the subclasses of its declaring class. Static and private methods, as
+/// well as constructors, are never dispatched virtually, so they have no entry; neither have
+/// interface methods, which are still looked up by name.
+#[derive(Debug, Default)]
+pub(crate) struct Vtable<'a> {
+    entries: Vec<VtableEntry<'a>>,
+    /// The index in `entries` of each method declared by the class, if it has one
+    method_indexes: Vec<Option<usize>>,
+}
+
+#[derive(Clone, Copy)]
+struct VtableEntry<'a> {
+    /// The class declaring the method, or `None` for the class owning the table, which does
+    /// not exist yet when the table is built
+    declaring_class: Option<ClassRef<'a>>,
+    method_index: usize,
+}
+
+impl<'a> fmt::Debug for VtableEntry<'a> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self.declaring_class {
+            Some(class) => write!(f, "{}#{}", class.name, self.method_index),
+            None => write!(f, "#{}", self.method_index),
+        }
+    }
+}
+
+impl<'a> Vtable<'a> {
+    pub fn new(
+        class_name: &str,
+        flags: ClassAccessFlags,
+        superclass: Option<ClassRef<'a>>,
+        methods: &[ClassFileMethod<'a>],
+    ) -> Self {
+        let mut method_indexes = vec![None; methods.len()];
+        if flags.contains(ClassAccessFlags::INTERFACE) {
+            return Self {
+                entries: Vec::new(),
+                method_indexes,
+            };
+        }
+
+        let mut entries: Vec<VtableEntry<'a>> = match superclass {
+            Some(superclass) => superclass
+                .vtable
+                .entries
+                .iter()
+                .map(|entry| VtableEntry {
+                    declaring_class: Some(entry.declaring_class.unwrap_or(superclass)),
+                    method_index: entry.method_index,
+                })
+                .collect(),
+            None => Vec::new(),
+        };
+        let num_inherited_entries = entries.len();
+
+        for (method_index, method) in methods.iter().enumerate() {
+            if !is_virtual(method) {
+                continue;
+            }
+            let new_entry = VtableEntry {
+                declaring_class: None,
+                method_index,
+            };
+            for (index, entry) in entries.iter_mut().take(num_inherited_entries).enumerate() {
+                let Some(declaring_class) = entry.declaring_class else {
+                    continue;
+                };
+                let inherited = &declaring_class.methods[entry.method_index];
+                if inherited.name == method.name
+                    && inherited.type_descriptor == method.type_descriptor
+                    && can_override(class_name, declaring_class, inherited)
+                {
+                    *entry = new_entry;
+                    method_indexes[method_index].get_or_insert(index);
+                }
+            }
+            if method_indexes[method_index].is_none() {
+                method_indexes[method_index] = Some(entries.len());
+                entries.push(new_entry);
+            }
+        }
+
+        Self {
+            entries,
+            method_indexes,
+        }
+    }
+
+    /// The index of the entry of the given method, declared by the class owning the table
+    pub fn index_of(&self, method_index: usize) -> Option<usize> {
+        self.method_indexes.get(method_index).copied().flatten()
+    }
+
+    /// The method invoked on the instances of `class`, which must own the table, for the
+    /// given entry
+    pub fn get<'b>(&self, class: &'b Class<'a>, index: usize) -> Option<ClassAndMethod<'b>> {
+        let entry = self.entries.get(index)?;
+        let declaring_class: &'b Class<'a> = entry.declaring_class.unwrap_or(class);
+        Some(ClassAndMethod {
+            class: declaring_class,
+            method: &declaring_class.methods[entry.method_index],
+        })
+    }
+}
+
+fn is_virtual(method: &ClassFileMethod) -> bool {
+    !method
+        .flags
+        .intersects(MethodFlags::STATIC | MethodFlags::PRIVATE)
+        && method.name != "<init>"
+}
+
+/// Package-private methods can only be overridden by classes of the same package
+fn can_override(class_name: &str, declaring_class: &Class, inherited: &ClassFileMethod) -> bool {
+    inherited
+        .flags
+        .intersects(MethodFlags::PUBLIC | MethodFlags::PROTECTED)
+        || package_name_of(class_name) == declaring_class.package_name()
+}
@@ -0,0 +1,300 @@
+use std::{
+    io,
+    io::{Read, Seek, SeekFrom},
+};
+
+use flate2::read::DeflateDecoder;
+use indexmap::IndexMap;
+
+const END_OF_CENTRAL_DIRECTORY_SIGNATURE: u32 = 0x06054b50;
+const END_OF_CENTRAL_DIRECTORY_SIZE: usize = 22;
+/// The end of central directory record can be followed by a comment up to this long
+const MAX_COMMENT_LENGTH: usize = 0xFFFF;
+const ZIP64_LOCATOR_SIGNATURE: u32 = 0x07064b50;
+const ZIP64_LOCATOR_SIZE: usize = 20;
+const ZIP64_END_OF_CENTRAL_DIRECTORY_SIGNATURE: u32 = 0x06064b50;
+const ZIP64_END_OF_CENTRAL_DIRECTORY_SIZE: usize = 56;
+const ZIP64_EXTRA_FIELD_ID: u16 = 0x0001;
+const CENTRAL_DIRECTORY_HEADER_SIGNATURE: u32 = 0x02014b50;
+const CENTRAL_DIRECTORY_HEADER_SIZE: usize = 46;
+const LOCAL_HEADER_SIGNATURE: u32 = 0x04034b50;
+const LOCAL_HEADER_SIZE: usize = 30;
+
+const METHOD_STORED: u16 = 0;
+const METHOD_DEFLATED: u16 = 8;
+
+/// The sizes in the central directory are not trusted when allocating the buffer of an entry:
+/// a larger one grows as it is read
+const MAX_PREALLOCATED_SIZE: u64 = 1 << 20;
+
+/// The index of a zip archive, built by reading only its central directory. For each entry
+/// it keeps just where it is stored and how it is compressed, so that the entries can be read
+/// one at a time, without ever loading the whole archive in memory. Zip64 archives, i.e. the
+/// ones larger than 4 GB or with more than 65535 entries, are supported.
+#[derive(Debug, Default)]
+pub(crate) struct ZipCentralDirectory {
+    /// The number of bytes preceding the archive, e.g. in self-extracting ones
+    archive_offset: u64,
+    /// In the order of the central directory
+    entries: IndexMap<String, ZipEntryLocation>,
+}
+
+/// Where an entry of a zip archive is stored
+#[derive(Debug, Clone, Copy)]
+struct ZipEntryLocation {
+    /// The offset of the local header of the entry, which precedes its content
+    header_offset: u64,
+    compressed_size: u64,
+    uncompressed_size: u64,
+    method: u16,
+}
+
+impl ZipCentralDirectory {
+    /// Reads the central directory of the archive, which is stored at its end
+    pub fn read<R: Read + Seek>(reader: &mut R) -> io::Result<Self> {
+        let length = reader.seek(SeekFrom::End(0))?;
+        let tail_length = length.min(to_u64(END_OF_CENTRAL_DIRECTORY_SIZE + MAX_COMMENT_LENGTH)?);
+        reader.seek(SeekFrom::Start(length - tail_length))?;
+        let mut tail = vec![0u8; to_usize(tail_length)?];
+        reader.read_exact(&mut tail)?;
+
+        let not_found = || invalid_data("end of central directory not found");
+        let last_position = tail
+            .len()
+            .checked_sub(END_OF_CENTRAL_DIRECTORY_SIZE)
+            .ok_or_else(not_found)?;
+        let end_position = (0..=last_position)
+            .rev()
+            .find(|&position| u32_at(&tail, position) == END_OF_CENTRAL_DIRECTORY_SIGNATURE)
+            .ok_or_else(not_found)?;
+        let end = &tail[end_position..];
+        let entry_count = u64::from(u16_at(end, 10));
+        let directory_size = u64::from(u32_at(end, 12));
+        let directory_offset = u64::from(u32_at(end, 16));
+        let end_position = length - tail_length + to_u64(end_position)?;
+
+        let (entry_count, directory_offset, archive_offset) = if entry_count == 0xFFFF
+            || directory_size == 0xFFFFFFFF
+            || directory_offset == 0xFFFFFFFF
+        {
+            // Archives with leading bytes are only supported when they are not zip64
+            let zip64_end = Self::read_zip64_end(reader, end_position)?;
+            (u64_at(&zip64_end, 32), u64_at(&zip64_end, 48), 0)
+        } else {
+            let archive_offset = end_position
+                .checked_sub(directory_size + directory_offset)
+                .ok_or_else(|| invalid_data("invalid central directory offset"))?;
+            (entry_count, directory_offset, archive_offset)
+        };
+
+        reader.seek(SeekFrom::Start(archive_offset + directory_offset))?;
+        let mut directory = Self {
+            archive_offset,
+            entries: IndexMap::new(),
+        };
+        for _ in 0..entry_count {
+            let (name, location) = Self::read_directory_header(reader)?;
+            directory.entries.entry(name).or_insert(location);
+        }
+        Ok(directory)
+    }
+
+    fn read_zip64_end<R: Read + Seek>(
+        reader: &mut R,
+        end_position: u64,
+    ) -> io::Result<[u8; ZIP64_END_OF_CENTRAL_DIRECTORY_SIZE]> {
+        let locator_position = end_position
+            .checked_sub(to_u64(ZIP64_LOCATOR_SIZE)?)
+            .ok_or_else(|| invalid_data("zip64 locator not found"))?;
+        reader.seek(SeekFrom::Start(locator_position))?;
+        let mut locator = [0u8; ZIP64_LOCATOR_SIZE];
+        reader.read_exact(&mut locator)?;
+        if u32_at(&locator, 0) != ZIP64_LOCATOR_SIGNATURE {
+            return Err(invalid_data("zip64 locator not found"));
+        }
+
+        reader.seek(SeekFrom::Start(u64_at(&locator, 8)))?;
+        let mut zip64_end = [0u8; ZIP64_END_OF_CENTRAL_DIRECTORY_SIZE];
+        reader.read_exact(&mut zip64_end)?;
+        if u32_at(&zip64_end, 0) != ZIP64_END_OF_CENTRAL_DIRECTORY_SIGNATURE {
+            return Err(invalid_data("zip64 end of central directory not found"));
+        }
+        Ok(zip64_end)
+    }
+
+    fn read_directory_header<R: Read>(reader: &mut R) -> io::Result<(String, ZipEntryLocation)> {
+        let mut header = [0u8; CENTRAL_DIRECTORY_HEADER_SIZE];
+        reader.read_exact(&mut header)?;
+        if u32_at(&header, 0) != CENTRAL_DIRECTORY_HEADER_SIGNATURE {
+            return Err(invalid_data("invalid central directory header"));
+        }
+        let mut location = ZipEntryLocation {
+            header_offset: u64::from(u32_at(&header, 42)),
+            compressed_size: u64::from(u32_at(&header, 20)),
+            uncompressed_size: u64::from(u32_at(&header, 24)),
+            method: u16_at(&header, 10),
+        };
+
+        let mut name = vec![0u8; usize::from(u16_at(&header, 28))];
+        reader.read_exact(&mut name)?;
+        let mut extra_field = vec![0u8; usize::from(u16_at(&header, 30))];
+        reader.read_exact(&mut extra_field)?;
+        let comment_length = u64::from(u16_at(&header, 32));
+        io::copy(&mut reader.by_ref().take(comment_length), &mut io::sink())?;
+
+        location.read_zip64_extra_field(&extra_field)?;
+        Ok((String::from_utf8_lossy(&name).to_string(), location))
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// The names of all the files and directories of the archive, in the order in which they
+    /// are stored
+    pub fn file_names(&self) -> impl Iterator<Item = &str> {
+        self.entries.keys().map(String::as_str)
+    }
+
+    /// Reads and decompresses the given file, returning `None` if the archive does not
+    /// contain it or if it is a directory
+    pub fn read_file<R: Read + Seek>(
+        &self,
+        reader: &mut R,
+        name: &str,
+    ) -> io::Result<Option<Vec<u8>>> {
+        let Some(location) = self.entries.get(name) else {
+            return Ok(None);
+        };
+        if name.ends_with('/') {
+            return Ok(None);
+        }
+
+        reader.seek(SeekFrom::Start(
+            self.archive_offset + location.header_offset,
+        ))?;
+        let mut header = [0u8; LOCAL_HEADER_SIZE];
+        reader.read_exact(&mut header)?;
+        if u32_at(&header, 0) != LOCAL_HEADER_SIGNATURE {
+            return Err(invalid_data("invalid local header"));
+        }
+        // The lengths of the name and of the extra field can differ from the central directory
+        let skipped = i64::from(u16_at(&header, 26)) + i64::from(u16_at(&header, 28));
+        reader.seek(SeekFrom::Current(skipped))?;
+
+        let mut content = Vec::with_capacity(to_usize(
+            location.uncompressed_size.min(MAX_PREALLOCATED_SIZE),
+        )?);
+        let compressed = reader.by_ref().take(location.compressed_size);
+        match location.method {
+            METHOD_STORED => compressed
+                .take(location.uncompressed_size)
+                .read_to_end(&mut content)?,
+            METHOD_DEFLATED => DeflateDecoder::new(compressed).read_to_end(&mut content)?,
+            method => {
+                return Err(io::Error::new(
+                    io::ErrorKind::Unsupported,
+                    format!("unsupported compression method {method}"),
+                ))
+            }
+        };
+        if to_u64(content.len())? != location.uncompressed_size {
+            return Err(invalid_data("truncated entry"));
+        }
+        Ok(Some(content))
+    }
+}
+
+impl ZipEntryLocation {
+    /// In zip64 archives, the sizes and offset that do not fit in 32 bits are set to
+    /// `0xFFFFFFFF` and stored, in this order, in an extra field
+    fn read_zip64_extra_field(&mut self, extra_field: &[u8]) -> io::Result<()> {
+        let mut position = 0;
+        while position + 4 <= extra_field.len() {
+            let id = u16_at(extra_field, position);
+            let size = usize::from(u16_at(extra_field, position + 2));
+            let data = extra_field
+                .get(position + 4..position + 4 + size)
+                .ok_or_else(|| invalid_data("invalid extra field"))?;
+            if id == ZIP64_EXTRA_FIELD_ID {
+                let mut values = data.chunks_exact(8).map(|value| u64_at(value, 0));
+                for field in [
+                    &mut self.uncompressed_size,
+                    &mut self.compressed_size,
+                    &mut self.header_offset,
+                ] {
+                    if *field == 0xFFFFFFFF {
+                        *field = values
+                            .next()
+                            .ok_or_else(|| invalid_data("invalid zip64 extra field"))?;
+                    }
+                }
+            }
+            position += 4 + size;
+        }
+        Ok(())
+    }
+}
+
+fn invalid_data(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+fn to_usize(value: u64) -> io::Result<usize> {
+    usize::try_from(value).map_err(|_| invalid_data("size does not fit in memory"))
+}
+
+fn to_u64(value: usize) -> io::Result<u64> {
+    u64::try_from(value).map_err(|_| invalid_data("size too large"))
+}
+
+// Zip archives are little endian. The callers make sure that the offsets are in bounds.
+
+fn u16_at(bytes: &[u8], offset: usize) -> u16 {
+    u16::from_le_bytes(bytes[offset..offset + 2].try_into().unwrap())
+}
+
+fn u32_at(bytes: &[u8], offset: usize) -> u32 {
+    u32::from_le_bytes(bytes[offset..offset + 4].try_into().unwrap())
+}
+
+fn u64_at(bytes: &[u8], offset: usize) -> u64 {
+    u64::from_le_bytes(bytes[offset..offset + 8].try_into().unwrap())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{Cursor, ErrorKind, Write};
+
+    use zip::{write::FileOptions, CompressionMethod, ZipWriter};
+
+    use crate::zip_central_directory::ZipCentralDirectory;
+
+    #[test]
+    fn sizes_of_the_central_directory_are_not_trusted() {
+        let mut writer = ZipWriter::new(Cursor::new(Vec::new()));
+        writer
+            .start_file(
+                "rjvm/Huge.class",
+                FileOptions::default().compression_method(CompressionMethod::Stored),
+            )
+            .unwrap();
+        writer.write_all(&[0xCA, 0xFE, 0xBA, 0xBE]).unwrap();
+        let mut archive = writer.finish().unwrap().into_inner();
+
+        // Claim that the entry is almost 4 GB large
+        let header_position = archive
+            .windows(4)
+            .position(|bytes| bytes == [0x50, 0x4b, 0x01, 0x02])
+            .unwrap();
+        archive[header_position + 24..header_position + 28]
+            .copy_from_slice(&0xFFFFFFF0u32.to_le_bytes());
+
+        let mut reader = Cursor::new(archive);
+        let directory = ZipCentralDirectory::read(&mut reader).unwrap();
+        let error = directory
+            .read_file(&mut reader, "rjvm/Huge.class")
+            .expect_err("should not be able to read the entry");
+        assert_eq!(ErrorKind::InvalidData, error.kind());
+    }
+}
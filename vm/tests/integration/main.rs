@@ -1 +1,2 @@
+mod native_methods_tests;
 mod real_code_tests;
@@ -0,0 +1,138 @@
+use rjvm_vm::{
+    exceptions::{JavaException, MethodCallFailed},
+    java_objects_creation::{extract_str_from_java_lang_string, new_java_lang_string_object},
+    object::Object,
+    value::Value,
+    vm::{Vm, DEFAULT_MAX_MEMORY},
+};
+
+// This file tests the native methods directly, without going through any java code
+
+fn create_base_vm() -> Vm<'static> {
+    let mut vm = Vm::new(DEFAULT_MAX_MEMORY);
+
+    let src_dir = env!("CARGO_MANIFEST_DIR");
+    vm.append_class_path(&format!("{src_dir}/rt.jar"))
+        .expect("should be able to add entries to the classpath");
+    vm
+}
+
+fn call_native<'a>(
+    vm: &mut Vm<'a>,
+    class_name: &str,
+    method_name: &str,
+    descriptor: &str,
+    args: Vec<Value<'a>>,
+) -> Result<Option<Value<'a>>, MethodCallFailed<'a>> {
+    let call_stack = vm.allocate_call_stack();
+    let callback = vm
+        .native_methods_registry
+        .get(class_name, method_name, descriptor)
+        .expect("native method should be registered");
+    callback(vm, call_stack, None, args)
+}
+
+fn new_string<'a>(vm: &mut Vm<'a>, content: &str) -> Value<'a> {
+    let call_stack = vm.allocate_call_stack();
+    Value::Object(new_java_lang_string_object(vm, call_stack, content).expect("can create string"))
+}
+
+fn expect_string<'a>(vm: &Vm<'a>, value: Option<Value<'a>>) -> String {
+    match value {
+        Some(Value::Object(object)) => {
+            extract_str_from_java_lang_string(vm, &object).expect("should have a valid string")
+        }
+        _ => panic!("expected a string but got {value:?}"),
+    }
+}
+
+fn expect_exception_of_class(vm: &Vm, result: Result<Option<Value>, MethodCallFailed>, name: &str) {
+    match result {
+        Err(MethodCallFailed::ExceptionThrown(JavaException(exception))) => {
+            let class = vm
+                .get_class_by_id(exception.class_id())
+                .expect("exception should have a valid class");
+            assert_eq!(name, class.name);
+        }
+        _ => panic!("expected an exception of class {name} but got {result:?}"),
+    }
+}
+
+#[test_log::test]
+fn integer_to_string_with_radix() {
+    let mut vm = create_base_vm();
+    let result = call_native(
+        &mut vm,
+        "java/lang/Integer",
+        "toString",
+        "(II)Ljava/lang/String;",
+        vec![Value::Int(-255), Value::Int(16)],
+    )
+    .expect("should not fail");
+    assert_eq!("-ff", expect_string(&vm, result));
+}
+
+#[test_log::test]
+fn long_to_string_with_radix() {
+    let mut vm = create_base_vm();
+    let result = call_native(
+        &mut vm,
+        "java/lang/Long",
+        "toString",
+        "(JI)Ljava/lang/String;",
+        vec![Value::Long(1 << 40), Value::Uninitialized, Value::Int(2)],
+    )
+    .expect("should not fail");
+    assert_eq!(format!("1{}", "0".repeat(40)), expect_string(&vm, result));
+}
+
+#[test_log::test]
+fn parse_int_with_radix() {
+    let mut vm = create_base_vm();
+    let string = new_string(&mut vm, "-7fffffff");
+    let result = call_native(
+        &mut vm,
+        "java/lang/Integer",
+        "parseInt",
+        "(Ljava/lang/String;I)I",
+        vec![string, Value::Int(16)],
+    );
+    assert_eq!(Ok(Some(Value::Int(-0x7fffffff))), result);
+}
+
+#[test_log::test]
+fn parse_int_throws_number_format_exception() {
+    let mut vm = create_base_vm();
+    let string = new_string(&mut vm, "2147483648");
+    let result = call_native(
+        &mut vm,
+        "java/lang/Integer",
+        "parseInt",
+        "(Ljava/lang/String;)I",
+        vec![string],
+    );
+    expect_exception_of_class(&vm, result, "java/lang/NumberFormatException");
+
+    let result = call_native(
+        &mut vm,
+        "java/lang/Integer",
+        "parseInt",
+        "(Ljava/lang/String;)I",
+        vec![Value::Null],
+    );
+    expect_exception_of_class(&vm, result, "java/lang/NumberFormatException");
+}
+
+#[test_log::test]
+fn parse_long_with_radix() {
+    let mut vm = create_base_vm();
+    let string = new_string(&mut vm, "+zz");
+    let result = call_native(
+        &mut vm,
+        "java/lang/Long",
+        "parseLong",
+        "(Ljava/lang/String;I)J",
+        vec![string, Value::Int(36)],
+    );
+    assert_eq!(Ok(Some(Value::Long(1295))), result);
+}
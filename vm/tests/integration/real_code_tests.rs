@@ -1,8 +1,40 @@
+use std::{
+    cell::{Cell, RefCell},
+    collections::{HashSet, VecDeque},
+    net::IpAddr,
+    rc::Rc,
+    time::{Duration, Instant},
+};
+
+use rjvm_reader::program_counter::ProgramCounter;
 use rjvm_vm::{
-    exceptions::MethodCallFailed,
-    java_objects_creation::extract_str_from_java_lang_string,
+    abstract_object::ObjectId,
+    allocation::{AllocationHook, AllocationKind},
+    array::Array,
+    call_stack::DEFAULT_MAX_CALL_STACK_DEPTH,
+    class::{Class, DefiningLoader, InitializationState},
+    class_load_observer::{ClassLoadObserver, ClassSource},
+    exceptions::{MethodCallFailed, RunMainError},
+    gc_algorithm::GcAlgorithm,
+    gc_stats::GcStats,
+    heap_growth::HeapGrowthPolicy,
+    heap_object::HeapObjectContents,
+    incremental_marking::IncrementalMarkingConfig,
+    io::{
+        CapturedOutput, FileHandle, FileMetadata, JvmIo, JvmNet, NetError, NoJvmIo, OpenOptions,
+        StdJvmIo,
+    },
+    java_objects_creation::{extract_str_from_java_lang_string, new_java_lang_string_object},
+    log_config::{LogCategory, LogConfig},
+    memory_class_path_entry::MemoryClassPathEntry,
+    object::Object,
+    object_fields::{get_object_fields, set_object_field, set_object_field_of_class},
+    run_config::RunConfig,
     value::{expect_concrete_object_at, Value},
+    verification_report::VerificationProblemKind,
     vm::{Vm, DEFAULT_MAX_MEMORY},
+    vm_builder::VmBuilder,
+    vm_error::{ArrayTypeMismatch, ClassInitializationFailed, VmError},
 };
 
 // This file tests the real classes in ../resources/rjvm
@@ -11,7 +43,9 @@ fn create_base_vm(max_memory: usize) -> Vm<'static> {
     let mut vm = Vm::new(max_memory);
 
     let src_dir = env!("CARGO_MANIFEST_DIR");
-    vm.append_class_path(&format!("{src_dir}/rt.jar:{src_dir}/tests/resources",))
+    vm.append_boot_class_path(&format!("{src_dir}/rt.jar"))
+        .expect("should be able to add entries to the boot classpath");
+    vm.append_class_path(&format!("{src_dir}/tests/resources"))
         .expect("should be able to add entries to the classpath");
     vm
 }
@@ -21,13 +55,23 @@ fn invoke<'a>(
     class_name: &str,
     method_name: &str,
     descriptor: &str,
+) -> Result<Option<Value<'a>>, MethodCallFailed<'a>> {
+    invoke_with_args(vm, class_name, method_name, descriptor, vec![])
+}
+
+fn invoke_with_args<'a>(
+    vm: &mut Vm<'a>,
+    class_name: &str,
+    method_name: &str,
+    descriptor: &str,
+    args: Vec<Value<'a>>,
 ) -> Result<Option<Value<'a>>, MethodCallFailed<'a>> {
     let call_stack = vm.allocate_call_stack();
     let main_method = vm
         .resolve_class_method(call_stack, class_name, method_name, descriptor)
         .expect("should find main method");
 
-    let main_result = vm.invoke(call_stack, main_method, None, vec![]);
+    let main_result = vm.invoke(call_stack, main_method, None, args);
     vm.debug_stats();
     println!("result of {class_name}::{method_name}: {main_result:?}");
 
@@ -40,6 +84,30 @@ fn extract_printed_string(vm: &Vm, index: usize) -> String {
     extract_str_from_java_lang_string(vm, &string).expect("should have a valid string")
 }
 
+/// Checks that the result is an exception of the given class, and returns its message
+fn extract_thrown_exception_message<'a>(
+    vm: &Vm<'a>,
+    result: Result<Option<Value<'a>>, MethodCallFailed<'a>>,
+    expected_class_name: &str,
+) -> String {
+    let Err(MethodCallFailed::ExceptionThrown(exception)) = result else {
+        panic!("expected an exception to be thrown, got {result:?}");
+    };
+    let class = vm
+        .get_class_by_id(exception.0.class_id())
+        .expect("exception should have a class");
+    assert_eq!(expected_class_name, class.name);
+    let fields = get_object_fields(vm, &exception.0).expect("should be able to read the fields");
+    let message = fields
+        .iter()
+        .find(|field| field.name == "detailMessage")
+        .expect("throwable should have a message field");
+    let Value::Object(message) = &message.value else {
+        panic!("expected a message, got {:?}", message.value)
+    };
+    extract_str_from_java_lang_string(vm, message).expect("should have a valid string")
+}
+
 #[test_log::test]
 fn simple_main() {
     let mut vm = create_base_vm(DEFAULT_MAX_MEMORY);
@@ -49,6 +117,466 @@ fn simple_main() {
     assert_eq!(vec![Value::Int(3), Value::Int(6)], vm.printed);
 }
 
+#[test_log::test]
+fn invoke_main_with_run_config() {
+    let mut vm = create_base_vm(DEFAULT_MAX_MEMORY);
+    vm.set_environment([
+        ("LANG".to_string(), "C".to_string()),
+        ("HOME".to_string(), "/root".to_string()),
+    ]);
+    let run_config = RunConfig::new()
+        .with_args(["first", "second"])
+        .with_working_directory("/home/rjvm")
+        .with_property("custom.property", "42")
+        .with_environment_variable("HOME", "/home/rjvm");
+    let main_result = vm.invoke_main("rjvm/MainArguments", run_config);
+    assert_eq!(Ok(()), main_result);
+
+    assert_eq!(3, vm.printed.len());
+    assert_eq!(Value::Int(2), vm.printed[0]);
+    assert_eq!("first", extract_printed_string(&vm, 1));
+    assert_eq!("second", extract_printed_string(&vm, 2));
+
+    assert_eq!(Some("/home/rjvm"), vm.system_property("user.dir"));
+    assert_eq!(Some("42"), vm.system_property("custom.property"));
+    assert_eq!(Some("/home/rjvm"), vm.environment_variable("HOME"));
+    assert_eq!(Some("C"), vm.environment_variable("LANG"));
+    assert_eq!(None, vm.environment_variable("PATH"));
+}
+
+#[test_log::test]
+fn invoke_main_requires_a_main_method() {
+    let mut vm = create_base_vm(DEFAULT_MAX_MEMORY);
+    let main_result = vm.invoke_main("rjvm/ObjectFields", RunConfig::new());
+    assert!(matches!(
+        main_result,
+        Err(MethodCallFailed::InternalError(
+            VmError::MethodNotFoundException(..)
+        ))
+    ));
+}
+
+#[test_log::test]
+fn run_main_returns_the_exit_status() {
+    let mut vm = create_base_vm(DEFAULT_MAX_MEMORY);
+    assert_eq!(
+        Ok(0),
+        vm.run_main("rjvm.MainArguments", &["first", "second"])
+    );
+
+    assert_eq!(3, vm.printed.len());
+    assert_eq!(Value::Int(2), vm.printed[0]);
+    assert_eq!("first", extract_printed_string(&vm, 1));
+    assert_eq!("second", extract_printed_string(&vm, 2));
+}
+
+#[test_log::test]
+fn run_main_renders_uncaught_exceptions() {
+    let mut vm = create_base_vm(DEFAULT_MAX_MEMORY);
+    assert_eq!(Ok(0), vm.run_main("rjvm/FailingMain", &[]));
+
+    let Err(RunMainError::UncaughtException(exception)) = vm.run_main("rjvm/FailingMain", &["abc"])
+    else {
+        panic!("main should have thrown an exception");
+    };
+    assert_eq!("java.lang.IllegalStateException", exception.class_name);
+    assert!(exception.description.ends_with(": invalid argument abc"));
+    assert!(exception.stack_trace.starts_with(&exception.description));
+    assert!(exception
+        .stack_trace
+        .contains("FailingMain.main(FailingMain.java:11)"));
+    assert!(exception
+        .stack_trace
+        .contains("NumberFormatException: For input string: \"abc\""));
+
+    let error = RunMainError::UncaughtException(exception.clone());
+    assert_eq!(
+        format!(
+            "Exception in thread \"main\" {}",
+            exception.stack_trace.trim_end()
+        ),
+        error.to_string()
+    );
+}
+
+#[test_log::test]
+fn run_main_requires_a_main_method() {
+    let mut vm = create_base_vm(DEFAULT_MAX_MEMORY);
+    assert_eq!(
+        Err(RunMainError::VmError(VmError::ClassNotFoundException(
+            "rjvm/Missing".to_string()
+        ))),
+        vm.run_main("rjvm.Missing", &[])
+    );
+    assert!(matches!(
+        vm.run_main("rjvm/ObjectFields", &[]),
+        Err(RunMainError::VmError(VmError::MethodNotFoundException(..)))
+    ));
+}
+
+#[test_log::test]
+fn system_exit_returns_the_status_after_running_the_shutdown_hooks() {
+    let mut vm = create_base_vm(DEFAULT_MAX_MEMORY);
+    let main_result = vm.invoke_main("rjvm/SystemExit", RunConfig::new().with_args(["exit"]));
+    assert_eq!(Err(MethodCallFailed::VmExit(3)), main_result);
+
+    assert_eq!(2, vm.printed.len());
+    assert_eq!("exiting", extract_printed_string(&vm, 0));
+    assert_eq!("hook", extract_printed_string(&vm, 1));
+}
+
+#[test_log::test]
+fn shutdown_hooks_run_when_main_completes() {
+    let mut vm = create_base_vm(DEFAULT_MAX_MEMORY);
+    assert_eq!(
+        Ok(()),
+        vm.invoke_main("rjvm/ShutdownHooks", RunConfig::new())
+    );
+
+    assert_eq!(6, vm.printed.len());
+    assert_eq!("Hook previously registered", extract_printed_string(&vm, 0));
+    assert_eq!(Value::Int(1), vm.printed[1]);
+    assert_eq!(Value::Int(0), vm.printed[2]);
+    assert_eq!("main done", extract_printed_string(&vm, 3));
+    assert_eq!("hook", extract_printed_string(&vm, 4));
+    assert_eq!("Shutdown in progress", extract_printed_string(&vm, 5));
+
+    // The hooks run only once
+    assert_eq!(Ok(()), vm.shutdown());
+    assert_eq!(6, vm.printed.len());
+}
+
+#[test_log::test]
+fn shutdown_hooks_run_after_an_uncaught_exception() {
+    let mut vm = create_base_vm(DEFAULT_MAX_MEMORY);
+    let Err(RunMainError::UncaughtException(exception)) =
+        vm.run_main("rjvm.ShutdownHooks", &["failed"])
+    else {
+        panic!("main should have thrown an exception");
+    };
+    assert_eq!("java.lang.IllegalStateException", exception.class_name);
+
+    assert_eq!(6, vm.printed.len());
+    assert_eq!("main done", extract_printed_string(&vm, 3));
+    assert_eq!("hook", extract_printed_string(&vm, 4));
+}
+
+#[test_log::test]
+fn system_exit_stops_all_the_threads() {
+    let mut vm = create_base_vm(DEFAULT_MAX_MEMORY);
+    assert_eq!(Ok(7), vm.run_main("rjvm.SystemExit", &["thread"]));
+
+    assert_eq!(1, vm.printed.len());
+    assert_eq!("hook", extract_printed_string(&vm, 0));
+}
+
+#[test_log::test]
+fn runtime_halt_skips_the_shutdown_hooks() {
+    let mut vm = create_base_vm(DEFAULT_MAX_MEMORY);
+    assert_eq!(Ok(5), vm.run_main("rjvm.SystemExit", &["halt"]));
+
+    assert_eq!(1, vm.printed.len());
+    assert_eq!("exiting", extract_printed_string(&vm, 0));
+}
+
+#[test_log::test]
+fn invoke_jar_runs_the_main_class_of_the_manifest() {
+    let mut vm = Vm::new(DEFAULT_MAX_MEMORY);
+    let src_dir = env!("CARGO_MANIFEST_DIR");
+    vm.append_class_path(&format!("{src_dir}/rt.jar"))
+        .expect("should be able to add entries to the classpath");
+
+    let main_result = vm.invoke_jar(
+        &format!("{src_dir}/tests/resources/jars/app.jar"),
+        RunConfig::new(),
+    );
+    assert_eq!(Ok(()), main_result);
+    assert_eq!("hello, rjvm", extract_printed_string(&vm, 0));
+}
+
+#[test_log::test]
+fn classes_can_be_loaded_from_memory() {
+    let mut vm = Vm::new(DEFAULT_MAX_MEMORY);
+    let src_dir = env!("CARGO_MANIFEST_DIR");
+    vm.append_class_path(&format!("{src_dir}/rt.jar"))
+        .expect("should be able to add entries to the classpath");
+    let entry: MemoryClassPathEntry = [
+        (
+            "rjvm/SimpleMain",
+            include_bytes!("../resources/rjvm/SimpleMain.class").as_slice(),
+        ),
+        (
+            "rjvm/SimpleMain$Generator",
+            include_bytes!("../resources/rjvm/SimpleMain$Generator.class").as_slice(),
+        ),
+    ]
+    .into_iter()
+    .collect();
+    vm.add_class_path_entry(Box::new(entry))
+        .expect("should be able to add the entry");
+
+    let main_result = invoke(&mut vm, "rjvm/SimpleMain", "main", "([Ljava/lang/String;)V");
+    assert_eq!(Ok(None), main_result);
+    assert_eq!(vec![Value::Int(3), Value::Int(6)], vm.printed);
+}
+
+#[test_log::test]
+fn invoke_jar_runs_fat_jars() {
+    let mut vm = Vm::new(DEFAULT_MAX_MEMORY);
+    let src_dir = env!("CARGO_MANIFEST_DIR");
+    vm.append_class_path(&format!("{src_dir}/rt.jar"))
+        .expect("should be able to add entries to the classpath");
+
+    let main_result = vm.invoke_jar(
+        &format!("{src_dir}/tests/resources/jars/fat.jar"),
+        RunConfig::new(),
+    );
+    assert_eq!(Ok(()), main_result);
+    assert_eq!("hello, rjvm", extract_printed_string(&vm, 0));
+}
+
+#[test_log::test]
+fn classes_can_be_loaded_from_a_jimage() {
+    let mut vm = Vm::new(DEFAULT_MAX_MEMORY);
+    let src_dir = env!("CARGO_MANIFEST_DIR");
+    vm.append_class_path(&format!(
+        "{src_dir}/rt.jar:{src_dir}/tests/resources/modules/modules"
+    ))
+    .expect("should be able to add entries to the classpath");
+
+    let main_result = invoke(&mut vm, "rjvm/SimpleMain", "main", "([Ljava/lang/String;)V");
+    assert_eq!(Ok(None), main_result);
+    assert_eq!(vec![Value::Int(3), Value::Int(6)], vm.printed);
+}
+
+/// Serves the files of a directory of the host at a path that does not exist
+#[derive(Debug)]
+struct VirtualDirectoryIo {
+    inner: StdJvmIo,
+    virtual_directory: String,
+    host_directory: String,
+}
+
+impl VirtualDirectoryIo {
+    fn host_path(&self, path: &str) -> String {
+        match path.strip_prefix(&self.virtual_directory) {
+            Some(relative_path) => format!("{}{relative_path}", self.host_directory),
+            None => path.to_string(),
+        }
+    }
+}
+
+impl JvmIo for VirtualDirectoryIo {
+    fn open_file(&self, path: &str, options: OpenOptions) -> Result<FileHandle, String> {
+        self.inner.open_file(&self.host_path(path), options)
+    }
+
+    fn read_file_at(
+        &self,
+        handle: FileHandle,
+        position: u64,
+        buffer: &mut [u8],
+    ) -> Result<usize, String> {
+        self.inner.read_file_at(handle, position, buffer)
+    }
+
+    fn file_length(&self, handle: FileHandle) -> Result<u64, String> {
+        self.inner.file_length(handle)
+    }
+
+    fn close_file(&self, handle: FileHandle) -> Result<(), String> {
+        self.inner.close_file(handle)
+    }
+
+    fn file_metadata(&self, path: &str) -> Result<FileMetadata, String> {
+        self.inner.file_metadata(&self.host_path(path))
+    }
+}
+
+#[test_log::test]
+fn invoke_jar_reads_the_manifest_through_the_class_path_io() {
+    let mut vm = Vm::new(DEFAULT_MAX_MEMORY);
+    let src_dir = env!("CARGO_MANIFEST_DIR");
+    vm.append_class_path(&format!("{src_dir}/rt.jar"))
+        .expect("should be able to add entries to the classpath");
+    vm.set_class_path_io(Some(Rc::new(VirtualDirectoryIo {
+        inner: StdJvmIo::new(),
+        virtual_directory: "/virtual".to_string(),
+        host_directory: format!("{src_dir}/tests/resources/jars"),
+    })));
+
+    let main_result = vm.invoke_jar("/virtual/app.jar", RunConfig::new());
+    assert_eq!(Ok(()), main_result);
+    assert_eq!("hello, rjvm", extract_printed_string(&vm, 0));
+}
+
+#[test_log::test]
+fn invoke_jar_requires_a_main_class() {
+    let mut vm = create_base_vm(DEFAULT_MAX_MEMORY);
+    let src_dir = env!("CARGO_MANIFEST_DIR");
+    for jar in ["sample.jar", "jars/lib/greeter.jar"] {
+        let jar_path = format!("{src_dir}/tests/resources/{jar}");
+        let main_result = vm.invoke_jar(&jar_path, RunConfig::new());
+        assert_eq!(
+            Err(MethodCallFailed::InternalError(VmError::MissingMainClass(
+                jar_path
+            ))),
+            main_result
+        );
+    }
+    assert_eq!(
+        format!("no main manifest attribute, in {src_dir}/tests/resources/sample.jar"),
+        VmError::MissingMainClass(format!("{src_dir}/tests/resources/sample.jar")).to_string()
+    );
+}
+
+#[test_log::test]
+fn vm_builder_configures_class_path_and_natives() {
+    let src_dir = env!("CARGO_MANIFEST_DIR");
+    let mut vm = VmBuilder::new()
+        .with_class_path(format!("{src_dir}/rt.jar"))
+        .with_class_path(format!("{src_dir}/tests/resources"))
+        .with_native_method(
+            "rjvm/ClassInitializationChain$Third",
+            "missing",
+            "()I",
+            |_, _, _, _| Ok(Some(Value::Int(41))),
+        )
+        .with_intrinsic("rjvm/SimpleMain$Generator", "next", "()I", |_, _, _, _| {
+            Ok(Some(Value::Int(7)))
+        })
+        .build()
+        .expect("should be able to build the vm");
+
+    let main_result = invoke(
+        &mut vm,
+        "rjvm/ClassInitializationChain",
+        "main",
+        "([Ljava/lang/String;)V",
+    );
+    assert_eq!(Ok(None), main_result);
+    let main_result = invoke(&mut vm, "rjvm/SimpleMain", "main", "([Ljava/lang/String;)V");
+    assert_eq!(Ok(None), main_result);
+    assert_eq!(
+        vec![Value::Int(42), Value::Int(7), Value::Int(7)],
+        vm.printed
+    );
+}
+
+/// Reads the files of the host, counting the bytes read
+#[derive(Debug)]
+struct CountingIo {
+    inner: StdJvmIo,
+    bytes_read: Rc<Cell<u64>>,
+}
+
+impl JvmIo for CountingIo {
+    fn open_file(&self, path: &str, options: OpenOptions) -> Result<FileHandle, String> {
+        self.inner.open_file(path, options)
+    }
+
+    fn read_file_at(
+        &self,
+        handle: FileHandle,
+        position: u64,
+        buffer: &mut [u8],
+    ) -> Result<usize, String> {
+        let bytes = self.inner.read_file_at(handle, position, buffer)?;
+        self.bytes_read.set(self.bytes_read.get() + bytes as u64);
+        Ok(bytes)
+    }
+
+    fn file_length(&self, handle: FileHandle) -> Result<u64, String> {
+        self.inner.file_length(handle)
+    }
+
+    fn close_file(&self, handle: FileHandle) -> Result<(), String> {
+        self.inner.close_file(handle)
+    }
+
+    fn file_metadata(&self, path: &str) -> Result<FileMetadata, String> {
+        self.inner.file_metadata(path)
+    }
+}
+
+#[test_log::test]
+fn vm_builder_reads_jars_lazily_through_the_class_path_io() {
+    let src_dir = env!("CARGO_MANIFEST_DIR");
+    let bytes_read = Rc::new(Cell::new(0));
+    let mut vm = VmBuilder::new()
+        .with_class_path_io(CountingIo {
+            inner: StdJvmIo::new(),
+            bytes_read: bytes_read.clone(),
+        })
+        .with_class_path(format!("{src_dir}/rt.jar:{src_dir}/tests/resources"))
+        .build()
+        .expect("should be able to build the vm");
+
+    let main_result = invoke(&mut vm, "rjvm/SimpleMain", "main", "([Ljava/lang/String;)V");
+    assert_eq!(Ok(None), main_result);
+    assert_eq!(vec![Value::Int(3), Value::Int(6)], vm.printed);
+
+    // Only the central directory and the classes that were loaded have been read
+    let rt_jar_length = std::fs::metadata(format!("{src_dir}/rt.jar"))
+        .unwrap()
+        .len();
+    assert!(bytes_read.get() > 0);
+    assert!(bytes_read.get() < rt_jar_length / 10);
+}
+
+#[cfg(feature = "std")]
+#[test_log::test]
+fn parallel_class_loading_defines_the_same_classes_in_the_same_order() {
+    let run = |threads: usize, prefetched: &[&str]| {
+        let src_dir = env!("CARGO_MANIFEST_DIR");
+        let mut vm = VmBuilder::new()
+            .with_class_loading_threads(std::num::NonZeroUsize::new(threads).unwrap())
+            .with_class_path(format!("{src_dir}/rt.jar:{src_dir}/tests/resources"))
+            .build()
+            .expect("should be able to build the vm");
+        vm.prefetch_classes(prefetched);
+
+        let main_result = invoke(
+            &mut vm,
+            "rjvm/SuperClasses",
+            "main",
+            "([Ljava/lang/String;)V",
+        );
+        assert_eq!(Ok(None), main_result);
+        assert_eq!(vec![Value::Int(4)], vm.printed);
+
+        let class_ids: Vec<_> = [
+            "java/lang/Object",
+            "rjvm/SuperClasses",
+            "rjvm/SuperClasses$BaseClass",
+            "rjvm/SuperClasses$DerivedClass",
+        ]
+        .into_iter()
+        .map(|class_name| vm.find_class_by_name(class_name).unwrap().id)
+        .collect();
+        class_ids
+    };
+
+    let serial = run(1, &[]);
+    assert_eq!(serial, run(4, &[]));
+    assert_eq!(serial, run(4, &["rjvm/SuperClasses", "rjvm/DoesNotExist"]));
+    assert_eq!(serial, run(1, &["rjvm/SuperClasses"]));
+}
+
+#[test_log::test]
+fn vm_builder_rejects_invalid_class_path() {
+    match VmBuilder::new()
+        .with_class_path("/not/a/class/path")
+        .build()
+    {
+        Ok(_) => panic!("should not be able to build the vm"),
+        Err(err) => assert_eq!(
+            "invalid classpath entry: /not/a/class/path",
+            err.to_string()
+        ),
+    }
+}
+
 #[test_log::test]
 fn superclasses() {
     let mut vm = create_base_vm(DEFAULT_MAX_MEMORY);
@@ -359,20 +887,2595 @@ fn exceptions_throwing_and_catching() {
 }
 
 #[test_log::test]
-fn gabarge_collector() {
-    let mut vm = create_base_vm(10_000_000);
+fn negative_array_size() {
+    let mut vm = create_base_vm(DEFAULT_MAX_MEMORY);
     let main_result = invoke(
         &mut vm,
-        "rjvm/GarbageCollection",
+        "rjvm/NegativeArraySize",
         "main",
         "([Ljava/lang/String;)V",
     );
     assert_eq!(Ok(None), main_result);
+
+    assert_eq!(3, vm.printed.len());
+    assert_eq!("-3", extract_printed_string(&vm, 0));
+    assert_eq!("-7", extract_printed_string(&vm, 1));
+    assert_eq!("allocated 0", extract_printed_string(&vm, 2));
 }
 
 #[test_log::test]
-fn generic() {
-    let mut vm = create_base_vm(10_000_000);
-    let main_result = invoke(&mut vm, "rjvm/Generic", "main", "([Ljava/lang/String;)V");
+fn array_type_mismatch_reports_the_types_and_location() {
+    let mut vm = create_base_vm(DEFAULT_MAX_MEMORY);
+    let main_result = invoke(
+        &mut vm,
+        "rjvm/ArrayTypeMismatch",
+        "main",
+        "([Ljava/lang/String;)V",
+    );
+
+    let expected_mismatch = ArrayTypeMismatch {
+        class_name: "rjvm/ArrayTypeMismatch".to_string(),
+        method_name: "store".to_string(),
+        pc: ProgramCounter(3),
+        instruction: "aastore",
+        array_element_type: "java/lang/String".to_string(),
+        value_type: Some("java/lang/Object".to_string()),
+    };
+    assert_eq!(
+        "aastore at rjvm/ArrayTypeMismatch.store (pc 3): array of java/lang/String cannot store \
+         a value of type java/lang/Object",
+        expected_mismatch.to_string()
+    );
+
+    // Storing into a covariant array is checked at runtime, and throws a java exception
+    match main_result {
+        Err(MethodCallFailed::ExceptionThrown(exception)) => {
+            let class = vm
+                .get_class_by_id(exception.0.class_id())
+                .expect("exception should have a class");
+            assert_eq!("java/lang/ArrayStoreException", class.name);
+        }
+        result => panic!("expected an ArrayStoreException, got {result:?}"),
+    }
+}
+
+#[test_log::test]
+fn array_store_exceptions() {
+    let mut vm = create_base_vm(DEFAULT_MAX_MEMORY);
+    let main_result = invoke(&mut vm, "rjvm/ArrayStore", "main", "([Ljava/lang/String;)V");
+    assert_eq!(Ok(None), main_result);
+
+    assert_eq!(6, vm.printed.len());
+    assert_eq!("java.lang.Integer", extract_printed_string(&vm, 0));
+    assert_eq!("a string", extract_printed_string(&vm, 1));
+    assert_eq!("arraycopy: type mismatch", extract_printed_string(&vm, 2));
+    assert_eq!(
+        "arraycopy: element type mismatch",
+        extract_printed_string(&vm, 3)
+    );
+    assert_eq!("first null", extract_printed_string(&vm, 4));
+    assert_eq!("y", extract_printed_string(&vm, 5));
+}
+
+#[test_log::test]
+fn many_locals() {
+    let mut vm = create_base_vm(DEFAULT_MAX_MEMORY);
+    let main_result = invoke(&mut vm, "rjvm/ManyLocals", "main", "([Ljava/lang/String;)V");
+    assert_eq!(Ok(None), main_result);
+
+    assert_eq!(5, vm.printed.len());
+    assert_eq!(Value::Int(1 + 259 + 1000 + 1), vm.printed[0]);
+    assert_eq!(Value::Long((1 << 40) + 1), vm.printed[1]);
+    assert_eq!(Value::Float(3.0), vm.printed[2]);
+    assert_eq!(Value::Double(4.5), vm.printed[3]);
+    assert_eq!("wide", extract_printed_string(&vm, 4));
+}
+
+#[test_log::test]
+fn class_initialization_errors_report_the_initialization_chain() {
+    let mut vm = create_base_vm(DEFAULT_MAX_MEMORY);
+    let main_result = invoke(
+        &mut vm,
+        "rjvm/ClassInitializationChain",
+        "main",
+        "([Ljava/lang/String;)V",
+    );
+
+    let error = ClassInitializationFailed {
+        initialization_chain: vec![
+            "rjvm/ClassInitializationChain$First".to_string(),
+            "rjvm/ClassInitializationChain$Second".to_string(),
+            "rjvm/ClassInitializationChain$Third".to_string(),
+        ],
+        cause: VmError::NotImplemented,
+    };
+    assert_eq!(
+        "error initializing class rjvm/ClassInitializationChain$Third (initialization chain: \
+         rjvm/ClassInitializationChain$First -> rjvm/ClassInitializationChain$Second -> \
+         rjvm/ClassInitializationChain$Third): not yet implemented",
+        error.to_string()
+    );
+    assert_eq!(
+        Err(MethodCallFailed::InternalError(
+            VmError::ClassInitializationFailed(Box::new(error))
+        )),
+        main_result
+    );
+    assert!(vm.printed.is_empty());
+}
+
+#[test_log::test]
+fn large_method() {
+    let mut vm = create_base_vm(DEFAULT_MAX_MEMORY);
+    let main_result = invoke(
+        &mut vm,
+        "rjvm/LargeMethod",
+        "main",
+        "([Ljava/lang/String;)V",
+    );
+    assert_eq!(Ok(None), main_result);
+    assert_eq!(vec![Value::Int(-1160210048)], vm.printed);
+}
+
+#[test_log::test]
+fn invoke_special() {
+    let mut vm = create_base_vm(DEFAULT_MAX_MEMORY);
+    let main_result = invoke(
+        &mut vm,
+        "rjvm/InvokeSpecial",
+        "main",
+        "([Ljava/lang/String;)V",
+    );
     assert_eq!(Ok(None), main_result);
+
+    assert_eq!(
+        vec![
+            Value::Int(101),
+            Value::Int(222),
+            Value::Int(3),
+            Value::Int(7),
+            Value::Int(42),
+            Value::Int(42),
+            Value::Int(1042),
+        ],
+        vm.printed
+    );
+}
+
+#[test_log::test]
+fn invoke_virtual_selects_default_methods() {
+    let mut vm = create_base_vm(DEFAULT_MAX_MEMORY);
+    let call_stack = vm.allocate_call_stack();
+    let receiver = vm
+        .new_object(call_stack, "rjvm/InvokeSpecial$Impl")
+        .expect("should be able to create the object");
+
+    let result = vm.invoke_virtual(call_stack, receiver, "greet", "()I", vec![]);
+    assert_eq!(Ok(Some(Value::Int(42))), result);
+}
+
+#[test_log::test]
+fn subroutines() {
+    let mut vm = create_base_vm(DEFAULT_MAX_MEMORY);
+    let main_result = invoke(
+        &mut vm,
+        "rjvm/Subroutines",
+        "main",
+        "([Ljava/lang/String;)V",
+    );
+    assert_eq!(Ok(None), main_result);
+    assert_eq!(vec![Value::Int(30), Value::Int(42)], vm.printed);
+}
+
+#[test_log::test]
+fn null_pointer_exceptions() {
+    let mut vm = create_base_vm(DEFAULT_MAX_MEMORY);
+    let main_result = invoke(
+        &mut vm,
+        "rjvm/NullPointerExceptions",
+        "main",
+        "([Ljava/lang/String;)V",
+    );
+    assert_eq!(Ok(None), main_result);
+    assert_eq!((1..=8).map(Value::Int).collect::<Vec<_>>(), vm.printed);
+}
+
+#[test_log::test]
+fn runtime_exceptions() {
+    let mut vm = create_base_vm(DEFAULT_MAX_MEMORY);
+    let main_result = invoke(
+        &mut vm,
+        "rjvm/RuntimeExceptions",
+        "main",
+        "([Ljava/lang/String;)V",
+    );
+    assert_eq!(Ok(None), main_result);
+    assert_eq!(6, vm.printed.len());
+    assert_eq!("/ by zero", extract_printed_string(&vm, 0));
+    assert_eq!("/ by zero", extract_printed_string(&vm, 1));
+    assert_eq!(
+        "java.lang.String cannot be cast to java.lang.Integer",
+        extract_printed_string(&vm, 2)
+    );
+    assert_eq!("3", extract_printed_string(&vm, 3));
+    assert_eq!("-1", extract_printed_string(&vm, 4));
+    assert_eq!("caught", extract_printed_string(&vm, 5));
+}
+
+#[test_log::test]
+fn stack_overflow() {
+    let mut vm = create_base_vm(DEFAULT_MAX_MEMORY);
+    vm.set_max_call_stack_depth(100);
+    let main_result = invoke(
+        &mut vm,
+        "rjvm/StackOverflow",
+        "main",
+        "([Ljava/lang/String;)V",
+    );
+    assert_eq!(Ok(None), main_result);
+
+    // The frames of main and of the method that started the recursion are part of the stack,
+    // and the constructors also invoke the one of java.lang.Object
+    assert_eq!(
+        vec![Value::Int(99), Value::Int(1), Value::Int(97)],
+        vm.printed
+    );
+}
+
+#[test]
+fn stack_overflow_with_default_max_depth() {
+    // The default maximum depth must not overflow the native stack of a thread with a
+    // typical size, even in debug builds
+    let thread = std::thread::Builder::new()
+        .stack_size(8 * 1024 * 1024)
+        .spawn(|| {
+            let mut vm = create_base_vm(DEFAULT_MAX_MEMORY);
+            let main_result = invoke(
+                &mut vm,
+                "rjvm/StackOverflow",
+                "main",
+                "([Ljava/lang/String;)V",
+            );
+            assert_eq!(Ok(None), main_result);
+            let max_depth = i32::try_from(DEFAULT_MAX_CALL_STACK_DEPTH).unwrap();
+            assert_eq!(
+                vec![
+                    Value::Int(max_depth - 1),
+                    Value::Int(1),
+                    Value::Int(max_depth - 3)
+                ],
+                vm.printed
+            );
+        })
+        .expect("should be able to spawn a thread");
+    thread.join().expect("test thread should succeed");
+}
+
+#[test_log::test]
+fn lazy_linkage_tolerates_missing_classes_until_needed() {
+    let mut vm = create_base_vm(DEFAULT_MAX_MEMORY);
+    vm.set_lazy_linkage(true);
+    let main_result = invoke(
+        &mut vm,
+        "rjvm/LazyLinkage",
+        "main",
+        "([Ljava/lang/String;)V",
+    );
+    assert_eq!(
+        "rjvm/LazyLinkage$MissingClass",
+        extract_thrown_exception_message(&vm, main_result, "java/lang/NoClassDefFoundError")
+    );
+    assert_eq!(
+        vec![
+            Value::Int(42),
+            Value::Int(1),
+            Value::Int(0),
+            Value::Int(0),
+            Value::Int(1),
+            Value::Int(2),
+        ],
+        vm.printed
+    );
+}
+
+#[test_log::test]
+fn missing_interfaces_fail_without_lazy_linkage() {
+    let mut vm = create_base_vm(DEFAULT_MAX_MEMORY);
+    let main_result = invoke(
+        &mut vm,
+        "rjvm/LazyLinkage",
+        "main",
+        "([Ljava/lang/String;)V",
+    );
+    assert_eq!(
+        "rjvm/LazyLinkage$MissingInterface",
+        extract_thrown_exception_message(&vm, main_result, "java/lang/NoClassDefFoundError")
+    );
+    assert!(vm.printed.is_empty());
+}
+
+#[test_log::test]
+fn linkage_errors() {
+    let mut vm = create_base_vm(DEFAULT_MAX_MEMORY);
+    let main_result = invoke(
+        &mut vm,
+        "rjvm/LinkageErrors",
+        "main",
+        "([Ljava/lang/String;)V",
+    );
+    assert_eq!(
+        "rjvm.EvolvingLibrary.removedInstanceMethod(I)V",
+        extract_thrown_exception_message(&vm, main_result, "java/lang/NoSuchMethodError")
+    );
+
+    assert_eq!(6, vm.printed.len());
+    assert_eq!(
+        "rjvm.EvolvingLibrary.removedMethod()V",
+        extract_printed_string(&vm, 0)
+    );
+    assert_eq!("removedField", extract_printed_string(&vm, 1));
+    assert_eq!("removedStaticField", extract_printed_string(&vm, 2));
+    assert_eq!(
+        "rjvm/LinkageErrors$MissingClass",
+        extract_printed_string(&vm, 3)
+    );
+    assert_eq!(
+        "rjvm/LinkageErrors$MissingClass",
+        extract_printed_string(&vm, 4)
+    );
+    assert_eq!(Value::Int(3), vm.printed[5]);
+}
+
+#[test_log::test]
+fn abstract_errors() {
+    let mut vm = create_base_vm(DEFAULT_MAX_MEMORY);
+    let main_result = invoke(
+        &mut vm,
+        "rjvm/AbstractErrors",
+        "main",
+        "([Ljava/lang/String;)V",
+    );
+    assert_eq!(Ok(None), main_result);
+
+    assert_eq!(3, vm.printed.len());
+    assert_eq!("rjvm.EvolvingBase", extract_printed_string(&vm, 0));
+    assert_eq!(
+        "rjvm.EvolvingBase.compute()I",
+        extract_printed_string(&vm, 1)
+    );
+    assert_eq!(Value::Int(2), vm.printed[2]);
+}
+
+#[test_log::test]
+fn verify_errors() {
+    let mut vm = create_base_vm(DEFAULT_MAX_MEMORY);
+    let main_result = invoke(
+        &mut vm,
+        "rjvm/VerifyErrors",
+        "main",
+        "([Ljava/lang/String;)V",
+    );
+    assert_eq!(Ok(None), main_result);
+
+    assert_eq!(6, vm.printed.len());
+    assert_eq!(
+        "rjvm/VerifyErrors$BadStackType.run()I @2: Bad type on operand stack: type null is not assignable to integer",
+        extract_printed_string(&vm, 0)
+    );
+    assert_eq!(
+        "rjvm/VerifyErrors$StackUnderflow.run()I @1: Operand stack underflow",
+        extract_printed_string(&vm, 1)
+    );
+    assert_eq!(
+        "rjvm/VerifyErrors$MissingBranchFrame.run()I @1: Expecting a stackmap frame at branch target 6",
+        extract_printed_string(&vm, 2)
+    );
+    assert_eq!(
+        "rjvm/VerifyErrors$InconsistentLocals.run()I @8: Type float (current frame, locals[0]) is not assignable to integer (stack map, locals[0])",
+        extract_printed_string(&vm, 3)
+    );
+    assert_eq!(
+        "rjvm/VerifyErrors$UninitializedReturn.<init>()V @0: Constructor must call super() or this() before return",
+        extract_printed_string(&vm, 4)
+    );
+    assert_eq!(
+        extract_printed_string(&vm, 0),
+        extract_printed_string(&vm, 5)
+    );
+}
+
+#[test_log::test]
+fn invalid_bytecode_runs_without_verification() {
+    let mut vm = create_base_vm(DEFAULT_MAX_MEMORY);
+    vm.set_verify_classes(false);
+    let result = invoke(
+        &mut vm,
+        "rjvm/VerifyErrors$MissingBranchFrame",
+        "run",
+        "()I",
+    );
+    assert_eq!(Ok(Some(Value::Int(2))), result);
+}
+
+#[test_log::test]
+fn access_control() {
+    let mut vm = create_base_vm(DEFAULT_MAX_MEMORY);
+    let main_result = invoke(
+        &mut vm,
+        "rjvm/AccessControl",
+        "main",
+        "([Ljava/lang/String;)V",
+    );
+    assert_eq!(Ok(None), main_result);
+
+    assert_eq!(9, vm.printed.len());
+    assert_eq!(Value::Int(7), vm.printed[0]);
+    assert_eq!(Value::Int(8), vm.printed[1]);
+    assert_eq!(Value::Int(2), vm.printed[2]);
+    assert_eq!(
+        "tried to access method rjvm.access.Library.restrictedMethod()I from class rjvm.AccessControl",
+        extract_printed_string(&vm, 3)
+    );
+    assert_eq!(
+        "tried to access field rjvm.access.Library.restrictedField from class rjvm.AccessControl",
+        extract_printed_string(&vm, 4)
+    );
+    assert_eq!(
+        "tried to access method rjvm.access.Library.restrictedInstanceMethod()I from class rjvm.AccessControl",
+        extract_printed_string(&vm, 5)
+    );
+    assert_eq!(
+        "tried to access method rjvm.access.Library.protectedMethod()I from class rjvm.AccessControl",
+        extract_printed_string(&vm, 6)
+    );
+    assert_eq!(Value::Int(5), vm.printed[7]);
+    assert_eq!(
+        "tried to access class rjvm.access.Hidden from class rjvm.AccessControl",
+        extract_printed_string(&vm, 8)
+    );
+}
+
+#[test_log::test]
+fn access_control_can_be_disabled() {
+    let mut vm = create_base_vm(DEFAULT_MAX_MEMORY);
+    vm.set_access_checks(false);
+    let main_result = invoke(
+        &mut vm,
+        "rjvm/AccessControl",
+        "main",
+        "([Ljava/lang/String;)V",
+    );
+    assert_eq!(Ok(None), main_result);
+    assert_eq!(
+        [7, 8, 2, 3, 1, 4, 5, 5, 6]
+            .into_iter()
+            .map(Value::Int)
+            .collect::<Vec<_>>(),
+        vm.printed
+    );
+}
+
+#[test_log::test]
+fn synchronization() {
+    let mut vm = create_base_vm(DEFAULT_MAX_MEMORY);
+    let main_result = invoke(
+        &mut vm,
+        "rjvm/Synchronization",
+        "main",
+        "([Ljava/lang/String;)V",
+    );
+    assert_eq!(Ok(None), main_result);
+
+    assert_eq!(12, vm.printed.len());
+    assert_eq!(
+        [0, 1, 0, 1, 1, 0, 0, 0]
+            .into_iter()
+            .map(Value::Int)
+            .collect::<Vec<_>>(),
+        vm.printed[..8]
+    );
+    assert_eq!("exit without enter", extract_printed_string(&vm, 8));
+    assert_eq!("released twice", extract_printed_string(&vm, 9));
+    assert_eq!(Value::Int(0), vm.printed[10]);
+    assert_eq!("npe", extract_printed_string(&vm, 11));
+}
+
+#[test_log::test]
+fn threads() {
+    let mut vm = create_base_vm(DEFAULT_MAX_MEMORY);
+    let main_result = invoke(&mut vm, "rjvm/Threads", "main", "([Ljava/lang/String;)V");
+    assert_eq!(Ok(None), main_result);
+    assert_threads_printed(&vm);
+}
+
+#[test_log::test]
+fn threads_with_short_time_slice() {
+    let mut vm = create_base_vm(DEFAULT_MAX_MEMORY);
+    vm.set_thread_time_slice(1);
+    let main_result = invoke(&mut vm, "rjvm/Threads", "main", "([Ljava/lang/String;)V");
+    assert_eq!(Ok(None), main_result);
+    assert_threads_printed(&vm);
+}
+
+fn assert_threads_printed(vm: &Vm) {
+    assert_eq!(12, vm.printed.len());
+    assert_eq!("main", extract_printed_string(vm, 0));
+    assert_eq!(Value::Int(0), vm.printed[1]);
+    // The threads print their names when they start, in the order they were started
+    assert_eq!("first", extract_printed_string(vm, 2));
+    assert_eq!("second", extract_printed_string(vm, 3));
+    assert_eq!(Value::Int(1), vm.printed[4]);
+    assert_eq!(Value::Int(0), vm.printed[5]);
+    assert_eq!(Value::Int(200), vm.printed[6]);
+    assert_eq!("spinner stopped", extract_printed_string(vm, 7));
+    assert_eq!(Value::Int(1), vm.printed[8]);
+    assert_eq!(Value::Int(0), vm.printed[9]);
+    assert_eq!("started twice", extract_printed_string(vm, 10));
+    assert_eq!("main done", extract_printed_string(vm, 11));
+}
+
+#[test_log::test]
+fn wait_and_notify() {
+    let mut vm = create_base_vm(DEFAULT_MAX_MEMORY);
+    let main_result = invoke(&mut vm, "rjvm/WaitNotify", "main", "([Ljava/lang/String;)V");
+    assert_eq!(Ok(None), main_result);
+
+    assert_eq!(11, vm.printed.len());
+    assert_eq!(Value::Int(55), vm.printed[0]);
+    assert_eq!("wait", extract_printed_string(&vm, 1));
+    assert_eq!("notify", extract_printed_string(&vm, 2));
+    assert_eq!("notifyAll", extract_printed_string(&vm, 3));
+    assert_eq!(Value::Int(1), vm.printed[4]);
+    assert_eq!("timeout value is negative", extract_printed_string(&vm, 5));
+    assert_eq!(Value::Int(1), vm.printed[6]);
+    assert_eq!(Value::Int(3), vm.printed[7]);
+    assert_eq!(
+        [1, 1, 0].into_iter().map(Value::Int).collect::<Vec<_>>(),
+        vm.printed[8..]
+    );
+}
+
+#[test_log::test]
+fn sleep_and_interrupt() {
+    let mut vm = create_base_vm(DEFAULT_MAX_MEMORY);
+    let main_result = invoke(
+        &mut vm,
+        "rjvm/SleepInterrupt",
+        "main",
+        "([Ljava/lang/String;)V",
+    );
+    assert_eq!(Ok(None), main_result);
+
+    assert_eq!(13, vm.printed.len());
+    assert_eq!(Value::Int(1), vm.printed[0]);
+    assert_eq!("timeout value is negative", extract_printed_string(&vm, 1));
+    assert_eq!(Value::Int(1), vm.printed[2]);
+    assert_eq!(Value::Int(0), vm.printed[3]);
+    assert_eq!("sleep interrupted", extract_printed_string(&vm, 4));
+    assert_eq!(
+        [0, 1, 1, 1, 1, 1, 0, 0]
+            .into_iter()
+            .map(Value::Int)
+            .collect::<Vec<_>>(),
+        vm.printed[5..]
+    );
+}
+
+/// A clock that only advances when the vm sleeps, without blocking
+#[derive(Debug)]
+struct VirtualClockIo {
+    start: Instant,
+    elapsed: Cell<Duration>,
+}
+
+impl JvmIo for VirtualClockIo {
+    fn monotonic_now(&self) -> Instant {
+        self.start + self.elapsed.get()
+    }
+
+    fn sleep(&self, duration: Duration) {
+        self.elapsed.set(self.elapsed.get() + duration);
+    }
+}
+
+#[test_log::test]
+fn sleep_uses_the_clock_of_the_jvm_io() {
+    let mut vm = create_base_vm(DEFAULT_MAX_MEMORY);
+    let io = Rc::new(VirtualClockIo {
+        start: Instant::now(),
+        elapsed: Cell::new(Duration::ZERO),
+    });
+    vm.set_io(io.clone());
+    let result = invoke_with_args(
+        &mut vm,
+        "java/lang/Thread",
+        "sleep",
+        "(J)V",
+        vec![Value::Long(60_000)],
+    );
+    assert_eq!(Ok(None), result);
+    assert_eq!(Duration::from_secs(60), io.elapsed.get());
+}
+
+#[test_log::test]
+fn references() {
+    // Small enough that the soft reference needs to be cleared
+    let mut vm = create_base_vm(10_000_000);
+    assert_references_work(&mut vm);
+}
+
+#[test_log::test]
+fn references_with_incremental_marking() {
+    let mut vm = create_base_vm(10_000_000);
+    vm.set_incremental_marking(Some(IncrementalMarkingConfig {
+        start_threshold_percent: 10,
+        objects_per_slice: 4,
+        slice_time_budget: None,
+    }));
+    assert_references_work(&mut vm);
+}
+
+fn assert_references_work(vm: &mut Vm) {
+    let main_result = invoke(vm, "rjvm/References", "main", "([Ljava/lang/String;)V");
+    assert_eq!(Ok(None), main_result);
+
+    assert_eq!(
+        [1, 1, 1, 1, 1, 1, 1, 1, 300000, 1, 1]
+            .into_iter()
+            .map(Value::Int)
+            .collect::<Vec<_>>(),
+        vm.printed
+    );
+}
+
+#[test_log::test]
+fn finalization() {
+    let mut vm = create_base_vm(DEFAULT_MAX_MEMORY);
+    assert_finalizers_run(&mut vm);
+}
+
+fn assert_finalizers_run(vm: &mut Vm) {
+    let main_result = invoke(vm, "rjvm/Finalization", "main", "([Ljava/lang/String;)V");
+    assert_eq!(Ok(None), main_result);
+
+    assert_eq!(
+        [3, 4, 1, 1, 42, 1, 1, 4]
+            .into_iter()
+            .map(Value::Int)
+            .collect::<Vec<_>>(),
+        vm.printed
+    );
+}
+
+#[test_log::test]
+fn identity_hash_codes() {
+    let mut vm = create_base_vm(DEFAULT_MAX_MEMORY);
+    let main_result = invoke(
+        &mut vm,
+        "rjvm/IdentityHash",
+        "main",
+        "([Ljava/lang/String;)V",
+    );
+    assert_eq!(Ok(None), main_result);
+
+    assert_eq!(
+        [1, 1, 1, 1, 1, 1, 42, 1, 0]
+            .into_iter()
+            .map(Value::Int)
+            .collect::<Vec<_>>(),
+        vm.printed
+    );
+}
+
+#[test_log::test]
+fn cloning() {
+    let mut vm = create_base_vm(DEFAULT_MAX_MEMORY);
+    let main_result = invoke(&mut vm, "rjvm/Cloning", "main", "([Ljava/lang/String;)V");
+    assert_eq!(Ok(None), main_result);
+
+    assert_eq!(
+        [1, 1, 2, 1, 1, 1, 1, 1, 5]
+            .into_iter()
+            .map(Value::Int)
+            .collect::<Vec<_>>(),
+        vm.printed
+    );
+}
+
+#[test_log::test]
+fn class_objects_are_unique() {
+    let mut vm = create_base_vm(DEFAULT_MAX_MEMORY);
+    let main_result = invoke(
+        &mut vm,
+        "rjvm/ClassObjects",
+        "main",
+        "([Ljava/lang/String;)V",
+    );
+    assert_eq!(Ok(None), main_result);
+
+    assert_eq!(
+        [1, 1, 1, 1, 1, 1, 1]
+            .into_iter()
+            .map(Value::Int)
+            .collect::<Vec<_>>(),
+        vm.printed
+    );
+}
+
+#[test_log::test]
+fn user_defined_class_loaders() {
+    let mut vm = create_base_vm(DEFAULT_MAX_MEMORY);
+    let main_result = invoke(
+        &mut vm,
+        "rjvm/ClassLoaders",
+        "main",
+        "([Ljava/lang/String;)V",
+    );
+    assert_eq!(Ok(None), main_result);
+
+    assert_eq!(
+        vec![
+            Value::Int(1),
+            Value::Int(1),
+            Value::Int(1),
+            Value::Int(1),
+            Value::Int(1),
+        ],
+        vm.printed[..5]
+    );
+    assert_eq!("[hello rjvm]", extract_printed_string(&vm, 5));
+    assert_eq!(
+        vec![Value::Int(2), Value::Int(1), Value::Int(1), Value::Int(1)],
+        vm.printed[6..10]
+    );
+    assert_eq!("rjvm.plugins.Missing", extract_printed_string(&vm, 10));
+    assert_eq!(
+        "loader (instance of rjvm/ClassLoaders$ResourceClassLoader): attempted duplicate class \
+         definition for name: \"rjvm/plugins/Plugin\"",
+        extract_printed_string(&vm, 11)
+    );
+}
+
+#[test_log::test]
+fn boot_and_application_class_loaders() {
+    let mut vm = create_base_vm(DEFAULT_MAX_MEMORY);
+    let main_result = invoke(
+        &mut vm,
+        "rjvm/ClassLoaderDelegation",
+        "main",
+        "([Ljava/lang/String;)V",
+    );
+    assert_eq!(Ok(None), main_result);
+
+    assert_eq!(vec![Value::Int(1); 7], vm.printed[..7]);
+    assert_eq!("rjvm.ClassLoaderDelegation", extract_printed_string(&vm, 7));
+    assert_eq!("rjvm.Missing", extract_printed_string(&vm, 8));
+    let defining_loader = |class_name| vm.find_class_by_name(class_name).unwrap().defining_loader;
+    assert_eq!(
+        DefiningLoader::Bootstrap,
+        defining_loader("java/lang/String")
+    );
+    assert_eq!(
+        DefiningLoader::Application,
+        defining_loader("rjvm/ClassLoaderDelegation")
+    );
+}
+
+#[test_log::test]
+fn without_a_boot_class_path_all_classes_belong_to_the_bootstrap_loader() {
+    let src_dir = env!("CARGO_MANIFEST_DIR");
+    let mut vm = VmBuilder::new()
+        .with_class_path(format!("{src_dir}/rt.jar:{src_dir}/tests/resources"))
+        .build()
+        .expect("should be able to create the vm");
+    let call_stack = vm.allocate_call_stack();
+    let class = vm
+        .get_or_resolve_class(call_stack, "rjvm/SimpleMain")
+        .expect("should be able to load the class");
+    assert_eq!(DefiningLoader::Bootstrap, class.defining_loader);
+}
+
+#[test_log::test]
+fn array_type_checks() {
+    let mut vm = create_base_vm(DEFAULT_MAX_MEMORY);
+    let main_result = invoke(
+        &mut vm,
+        "rjvm/ArrayTypeChecks",
+        "main",
+        "([Ljava/lang/String;)V",
+    );
+    assert_eq!(Ok(None), main_result);
+
+    assert_eq!(
+        [1, 0, 0, 1, 1, 0, 0, 1, 1, 0, 1, 1, 1, 1, 0, 0, 0, 2, -1]
+            .into_iter()
+            .map(Value::Int)
+            .collect::<Vec<_>>(),
+        vm.printed
+    );
+}
+
+#[test_log::test]
+fn interface_type_checks() {
+    let mut vm = create_base_vm(DEFAULT_MAX_MEMORY);
+    let main_result = invoke(
+        &mut vm,
+        "rjvm/InterfaceTypeChecks",
+        "main",
+        "([Ljava/lang/String;)V",
+    );
+    assert_eq!(Ok(None), main_result);
+
+    assert_eq!(
+        [1, 1, 0, 1, 1, 1, 0, 1, 1, 1, 0, 1, 0]
+            .into_iter()
+            .map(Value::Int)
+            .collect::<Vec<_>>(),
+        vm.printed
+    );
+}
+
+#[test_log::test]
+fn super_calls_skipping_the_parent() {
+    let mut vm = create_base_vm(DEFAULT_MAX_MEMORY);
+    let main_result = invoke(&mut vm, "rjvm/SuperCalls", "main", "([Ljava/lang/String;)V");
+    assert_eq!(Ok(None), main_result);
+    assert_eq!(vec![Value::Int(102), Value::Int(101)], vm.printed);
+}
+
+#[test_log::test]
+fn virtual_dispatch() {
+    let mut vm = create_base_vm(DEFAULT_MAX_MEMORY);
+    let main_result = invoke(
+        &mut vm,
+        "rjvm/VirtualDispatch",
+        "main",
+        "([Ljava/lang/String;)V",
+    );
+    assert_eq!(Ok(None), main_result);
+
+    assert_eq!(
+        [3, 20, 1, 20, 200, 100, 1]
+            .into_iter()
+            .map(Value::Int)
+            .collect::<Vec<_>>(),
+        vm.printed
+    );
+}
+
+#[test_log::test]
+fn inline_caches() {
+    let mut vm = create_base_vm(DEFAULT_MAX_MEMORY);
+    let main_result = invoke(
+        &mut vm,
+        "rjvm/InlineCaches",
+        "main",
+        "([Ljava/lang/String;)V",
+    );
+    assert_eq!(Ok(None), main_result);
+
+    assert_eq!(
+        [3, 22, 24, 24, 3]
+            .into_iter()
+            .map(Value::Int)
+            .collect::<Vec<_>>(),
+        vm.printed
+    );
+}
+
+#[test_log::test]
+fn quickened_fields() {
+    let mut vm = create_base_vm(DEFAULT_MAX_MEMORY);
+    let main_result = invoke(
+        &mut vm,
+        "rjvm/QuickenedFields",
+        "main",
+        "([Ljava/lang/String;)V",
+    );
+    assert_eq!(Ok(None), main_result);
+
+    assert_eq!(
+        vec![
+            Value::Int(15),
+            Value::Long(150),
+            Value::Int(4),
+            Value::Int(30),
+            Value::Int(7),
+            Value::Int(1),
+        ],
+        vm.printed
+    );
+}
+
+#[test_log::test]
+fn inherited_statics() {
+    let mut vm = create_base_vm(DEFAULT_MAX_MEMORY);
+    let main_result = invoke(
+        &mut vm,
+        "rjvm/InheritedStatics",
+        "main",
+        "([Ljava/lang/String;)V",
+    );
+    assert_eq!(Ok(None), main_result);
+
+    assert_eq!(
+        [5, 6, 6, 7, 100, 7, 200, 3, 1]
+            .into_iter()
+            .map(Value::Int)
+            .collect::<Vec<_>>(),
+        vm.printed
+    );
+}
+
+#[test_log::test]
+fn lazy_initialization() {
+    let mut vm = create_base_vm(DEFAULT_MAX_MEMORY);
+    let main_result = invoke(
+        &mut vm,
+        "rjvm/LazyInitialization",
+        "main",
+        "([Ljava/lang/String;)V",
+    );
+    assert_eq!(Ok(None), main_result);
+
+    assert_eq!(
+        [1, 1, 0, 5, 5, 7, 1, 2, 3, 4, 5, 11, 10, 1, 2, 3]
+            .into_iter()
+            .map(Value::Int)
+            .collect::<Vec<_>>(),
+        vm.printed
+    );
+}
+
+#[test_log::test]
+fn redefined_classes_run_their_new_methods() {
+    let mut vm = create_base_vm(DEFAULT_MAX_MEMORY);
+    assert_eq!(
+        Ok(Some(Value::Int(2))),
+        invoke(&mut vm, "rjvm/Redefinition", "callCompute", "()I")
+    );
+    assert_eq!(
+        Ok(Some(Value::Int(1))),
+        invoke(&mut vm, "rjvm/Redefinition", "describeChild", "()I")
+    );
+
+    let src_dir = env!("CARGO_MANIFEST_DIR");
+    let redefined = std::fs::read(format!(
+        "{src_dir}/tests/resources/redefined/rjvm/Redefinition.class"
+    ))
+    .unwrap();
+    vm.redefine_class("rjvm/Redefinition", redefined)
+        .expect("should be able to redefine the class");
+    assert!(vm.find_class_by_name("rjvm/Redefinition").unwrap().version > 0);
+
+    // The call sites run the new code, and the static fields and the initialization are kept
+    assert_eq!(
+        Ok(Some(Value::Int(10))),
+        invoke(&mut vm, "rjvm/Redefinition", "callCompute", "()I")
+    );
+    assert_eq!(
+        Ok(Some(Value::Int(9))),
+        invoke(&mut vm, "rjvm/Redefinition", "describeChild", "()I")
+    );
+    assert_eq!(
+        Ok(Some(Value::Int(2))),
+        invoke(&mut vm, "rjvm/Redefinition", "calls", "()I")
+    );
+    assert_eq!(vec![Value::Int(-1)], vm.printed);
+}
+
+#[test_log::test]
+fn class_redefinition_cannot_change_the_fields() {
+    let mut vm = create_base_vm(DEFAULT_MAX_MEMORY);
+    let result = invoke(&mut vm, "rjvm/Redefinition", "callCompute", "()I");
+    assert_eq!(Ok(Some(Value::Int(2))), result);
+
+    let src_dir = env!("CARGO_MANIFEST_DIR");
+    let incompatible = std::fs::read(format!(
+        "{src_dir}/tests/resources/redefined/incompatible/rjvm/Redefinition.class"
+    ))
+    .unwrap();
+    assert!(matches!(
+        vm.redefine_class("rjvm/Redefinition", incompatible.clone()),
+        Err(VmError::UnsupportedClassRedefinition(..))
+    ));
+    assert!(matches!(
+        vm.redefine_class("rjvm/SimpleMain", incompatible),
+        Err(VmError::UnsupportedClassRedefinition(..))
+    ));
+
+    let result = invoke(&mut vm, "rjvm/Redefinition", "callCompute", "()I");
+    assert_eq!(Ok(Some(Value::Int(2))), result);
+}
+
+#[test_log::test]
+fn class_file_transformers_rewrite_the_classes_before_they_are_defined() {
+    let src_dir = env!("CARGO_MANIFEST_DIR");
+    let redefined = std::fs::read(format!(
+        "{src_dir}/tests/resources/redefined/rjvm/Redefinition.class"
+    ))
+    .unwrap();
+    let transformed_classes = Rc::new(RefCell::new(Vec::new()));
+    let transformed_classes_clone = transformed_classes.clone();
+    let mut vm = VmBuilder::new()
+        .with_boot_class_path(format!("{src_dir}/rt.jar"))
+        .with_class_path(format!("{src_dir}/tests/resources"))
+        .with_class_file_transformer(move |class_name: &str, _: &[u8]| {
+            transformed_classes_clone
+                .borrow_mut()
+                .push(class_name.to_string());
+            (class_name == "rjvm/Redefinition").then(|| redefined.clone())
+        })
+        .build()
+        .expect("should be able to create the vm");
+
+    let result = invoke(&mut vm, "rjvm/Redefinition", "callCompute", "()I");
+    assert_eq!(Ok(Some(Value::Int(10))), result);
+    let transformed_classes = transformed_classes.borrow();
+    assert!(transformed_classes.contains(&"rjvm/Redefinition".to_string()));
+    assert!(transformed_classes.contains(&"java/lang/Object".to_string()));
+}
+
+/// Records the events of the loading of the classes, in order
+#[derive(Default, Clone)]
+struct ClassLoadEvents(Rc<RefCell<Vec<String>>>);
+
+impl ClassLoadObserver for ClassLoadEvents {
+    fn class_looked_up(&self, class_name: &str, initiating_loader: DefiningLoader) {
+        self.push(format!("looked up {class_name} by {initiating_loader:?}"));
+    }
+
+    fn class_defined(&self, class: &Class, source: &ClassSource) {
+        self.push(format!("defined {} from {source}", class.name));
+    }
+
+    fn class_initialized(&self, class: &Class) {
+        self.push(format!("initialized {}", class.name));
+    }
+
+    fn class_load_failed(&self, class_name: &str, error: &VmError) {
+        self.push(format!("failed to load {class_name}: {error}"));
+    }
+}
+
+impl ClassLoadEvents {
+    fn push(&self, event: String) {
+        self.0.borrow_mut().push(event);
+    }
+
+    fn position(&self, event: &str) -> usize {
+        self.0
+            .borrow()
+            .iter()
+            .position(|recorded| recorded == event)
+            .unwrap_or_else(|| panic!("event \"{event}\" should have been recorded"))
+    }
+}
+
+#[test_log::test]
+fn class_load_observers_are_notified_of_the_loading_of_the_classes() {
+    let src_dir = env!("CARGO_MANIFEST_DIR");
+    let events = ClassLoadEvents::default();
+    let mut vm = VmBuilder::new()
+        .with_boot_class_path(format!("{src_dir}/rt.jar"))
+        .with_class_path(format!("{src_dir}/tests/resources"))
+        .with_class_load_observer(events.clone())
+        .build()
+        .expect("should be able to create the vm");
+
+    let result = invoke(&mut vm, "rjvm/Redefinition", "describeChild", "()I");
+    assert_eq!(Ok(Some(Value::Int(1))), result);
+    let call_stack = vm.allocate_call_stack();
+    assert!(vm.get_or_resolve_class(call_stack, "rjvm/Missing").is_err());
+
+    let looked_up = events.position("looked up rjvm/Redefinition by User");
+    let object_defined =
+        events.position(&format!("defined java/lang/Object from {src_dir}/rt.jar"));
+    let defined = events.position(&format!(
+        "defined rjvm/Redefinition from {src_dir}/tests/resources"
+    ));
+    let initialized = events.position("initialized rjvm/Redefinition");
+    let child_defined = events.position(&format!(
+        "defined rjvm/Redefinition$Child from {src_dir}/tests/resources"
+    ));
+    // The superclass is defined before the class
+    assert!(looked_up < object_defined && object_defined < defined);
+    assert!(defined < initialized && initialized < child_defined);
+    events.position("failed to load rjvm/Missing: class not found: rjvm/Missing");
+}
+
+#[test_log::test]
+fn preload_all_loads_every_class_of_the_class_paths() {
+    let object = create_base_vm(DEFAULT_MAX_MEMORY)
+        .resolve_resource("java/lang/Object.class")
+        .expect("should be able to read rt.jar")
+        .expect("rt.jar should contain java.lang.Object");
+    let entry = MemoryClassPathEntry::new("preloaded")
+        .with_class("java/lang/Object", object)
+        .with_class(
+            "rjvm/Redefinition",
+            include_bytes!("../resources/rjvm/Redefinition.class").as_slice(),
+        )
+        .with_class(
+            "rjvm/Redefinition$Child",
+            include_bytes!("../resources/rjvm/Redefinition$Child.class").as_slice(),
+        )
+        .with_class(
+            "rjvm/LazyLinkage$Implementation",
+            include_bytes!("../resources/rjvm/LazyLinkage$Implementation.class").as_slice(),
+        )
+        .with_class("rjvm/Truncated", [0xCA, 0xFE, 0xBA, 0xBE].as_slice());
+    let mut vm = Vm::new(DEFAULT_MAX_MEMORY);
+    vm.add_class_path_entry(Box::new(entry))
+        .expect("should be able to add the entry");
+
+    let summary = vm.preload_all();
+    assert_eq!(3, summary.loaded);
+    let mut failed_classes: Vec<&str> = summary
+        .failures
+        .iter()
+        .map(|(class_name, _)| class_name.as_str())
+        .collect();
+    failed_classes.sort();
+    assert_eq!(
+        vec!["rjvm/LazyLinkage$Implementation", "rjvm/Truncated"],
+        failed_classes
+    );
+    for class_name in ["rjvm/Redefinition", "rjvm/Redefinition$Child"] {
+        let class = vm.find_class_by_name(class_name).unwrap();
+        assert_eq!(
+            InitializationState::NotInitialized,
+            class.initialization_state()
+        );
+    }
+}
+
+#[test_log::test]
+fn verify_reports_the_problems_of_the_classes_without_running_them() {
+    let src_dir = env!("CARGO_MANIFEST_DIR");
+    let mut vm = Vm::new(DEFAULT_MAX_MEMORY);
+    vm.append_boot_class_path(&format!("{src_dir}/rt.jar"))
+        .expect("should be able to add entries to the boot classpath");
+    vm.set_verify_classes(false);
+
+    let report = vm
+        .verify(&format!("{src_dir}/tests/resources/verification"))
+        .expect("should be able to verify the class path");
+    assert_eq!(5, report.classes);
+    assert!(!report.is_ok());
+    let mut problems: Vec<(&str, &VerificationProblemKind)> = report
+        .problems
+        .iter()
+        .map(|problem| (problem.class_name.as_str(), &problem.kind))
+        .collect();
+    problems.sort_by_key(|(class_name, _)| *class_name);
+    let missing_dependency =
+        VerificationProblemKind::MissingDependency("rjvm/verification/Dependency".to_string());
+    assert_eq!(
+        vec![
+            ("rjvm/verification/ExtendsMissingClass", &missing_dependency),
+            (
+                "rjvm/verification/NewerVersion",
+                &VerificationProblemKind::UnsupportedVersion
+            ),
+            (
+                "rjvm/verification/Truncated",
+                &VerificationProblemKind::InvalidClassFile
+            ),
+            ("rjvm/verification/UsesMissingClass", &missing_dependency),
+        ],
+        problems
+    );
+    assert!(!vm.verify_classes());
+
+    // The classes were loaded by a scratch class manager, and the class path was not kept
+    assert!(vm.find_class_by_name("rjvm/verification/Valid").is_none());
+    assert!(vm.find_class_by_name("java/lang/Object").is_none());
+    let call_stack = vm.allocate_call_stack();
+    assert!(vm
+        .get_or_resolve_class(call_stack, "rjvm/verification/Valid")
+        .is_err());
+}
+
+#[test_log::test]
+fn constant_values() {
+    let mut vm = create_base_vm(DEFAULT_MAX_MEMORY);
+    let main_result = invoke(
+        &mut vm,
+        "rjvm/ConstantValues",
+        "main",
+        "([Ljava/lang/String;)V",
+    );
+    assert_eq!(Ok(None), main_result);
+
+    assert_eq!(
+        vec![
+            Value::Int(100),
+            Value::Long(1 << 40),
+            Value::Double(2.5),
+            Value::Int(1),
+            Value::Int('x' as i32),
+            Value::Int(8),
+            Value::Int(1),
+            Value::Int(101),
+        ],
+        vm.printed
+    );
+}
+
+#[test_log::test]
+fn string_interning() {
+    let mut vm = create_base_vm(DEFAULT_MAX_MEMORY);
+    let main_result = invoke(
+        &mut vm,
+        "rjvm/StringInterning",
+        "main",
+        "([Ljava/lang/String;)V",
+    );
+    assert_eq!(Ok(None), main_result);
+
+    assert_eq!(
+        [1, 1, 1, 1, 0, 1, 0, 1, 1]
+            .into_iter()
+            .map(Value::Int)
+            .collect::<Vec<_>>(),
+        vm.printed
+    );
+}
+
+#[test_log::test]
+fn boxed_caches() {
+    let mut vm = create_base_vm(DEFAULT_MAX_MEMORY);
+    let main_result = invoke(
+        &mut vm,
+        "rjvm/BoxedCaches",
+        "main",
+        "([Ljava/lang/String;)V",
+    );
+    assert_eq!(Ok(None), main_result);
+
+    assert_eq!(
+        [1, 1, 0, 1000, 1, 1, 1, 1, 0, 1, 1, 1, 0, 1]
+            .into_iter()
+            .map(Value::Int)
+            .collect::<Vec<_>>(),
+        vm.printed
+    );
+}
+
+#[test_log::test]
+fn array_copy() {
+    let mut vm = create_base_vm(DEFAULT_MAX_MEMORY);
+    let main_result = invoke(&mut vm, "rjvm/ArrayCopy", "main", "([Ljava/lang/String;)V");
+    assert_eq!(Ok(None), main_result);
+
+    assert_eq!(
+        [
+            1, 1, 2, 3, 4, 2, 3, 4, 3, 4, 1, 1, 1, 1, 20, 20, 20, 20, 20, 2, 3, 4, 3, 4, 1, 1, 30,
+            31, 32, 1, 1
+        ]
+        .into_iter()
+        .map(Value::Int)
+        .collect::<Vec<_>>(),
+        vm.printed
+    );
+}
+
+#[test_log::test]
+fn math_functions() {
+    let mut vm = create_base_vm(DEFAULT_MAX_MEMORY);
+    let main_result = invoke(
+        &mut vm,
+        "rjvm/MathFunctions",
+        "main",
+        "([Ljava/lang/String;)V",
+    );
+    assert_eq!(Ok(None), main_result);
+
+    assert_eq!(
+        vec![
+            Value::Double(4.0),
+            Value::Double(1.5),
+            Value::Double(1024.0),
+            Value::Double(0.0),
+            Value::Double(1.0),
+            Value::Int(1),
+            Value::Int(1),
+            Value::Double(1.0),
+            Value::Double(1.0),
+            Value::Double(3.0),
+            Value::Double(-3.0),
+            Value::Double(5.0),
+            Value::Double(1.0),
+            Value::Double(-1.0),
+            Value::Double(0.0),
+            Value::Double(0.0),
+            Value::Double(1.0),
+            Value::Int(1),
+            Value::Double(0.0),
+            Value::Int(1),
+            Value::Int(1),
+            Value::Int(1),
+            Value::Double(180.0),
+            Value::Double(-2.0),
+            Value::Double(2.0),
+            Value::Long(3),
+            Value::Int(0),
+            Value::Int(0),
+            Value::Long(1 << 40),
+            Value::Long(15),
+            Value::Long(-4),
+        ],
+        vm.printed
+    );
+}
+
+#[test_log::test]
+fn floating_point_strings() {
+    let mut vm = create_base_vm(DEFAULT_MAX_MEMORY);
+    let main_result = invoke(
+        &mut vm,
+        "rjvm/FloatingPointStrings",
+        "main",
+        "([Ljava/lang/String;)V",
+    );
+    assert_eq!(Ok(None), main_result);
+
+    let expected = [
+        "0.1",
+        "100.0",
+        "x=0.30000000000000004",
+        "1.0E-5, 1.5E10",
+        "4.9E-324",
+        "-0.0",
+        "NaN-Infinity",
+        "0.1",
+        "f=1.1",
+        "3.4028235E38",
+        "2.5,3.25",
+        "1234567.0",
+    ];
+    assert_eq!(expected.len(), vm.printed.len());
+    for (index, expected) in expected.into_iter().enumerate() {
+        assert_eq!(expected, extract_printed_string(&vm, index));
+    }
+}
+
+#[test_log::test]
+fn system_properties() {
+    let mut vm = VmBuilder::new()
+        .with_system_property("embedder.property", "from the embedder")
+        .with_system_property("java.version", "1.7.0_99")
+        .with_system_property("number.property", "42")
+        .with_system_property("boolean.property", "true")
+        .build()
+        .expect("should be able to build the vm");
+    let src_dir = env!("CARGO_MANIFEST_DIR");
+    vm.append_class_path(&format!("{src_dir}/rt.jar:{src_dir}/tests/resources",))
+        .expect("should be able to add entries to the classpath");
+    assert_eq!(Some("UTF-8"), vm.system_property("file.encoding"));
+
+    let main_result = invoke(
+        &mut vm,
+        "rjvm/SystemProperties",
+        "main",
+        "([Ljava/lang/String;)V",
+    );
+    assert_eq!(Ok(None), main_result);
+
+    assert_eq!(15, vm.printed.len());
+    assert_eq!("from the embedder", extract_printed_string(&vm, 0));
+    assert_eq!("1.7.0_99", extract_printed_string(&vm, 1));
+    assert_eq!(
+        std::path::MAIN_SEPARATOR_STR,
+        extract_printed_string(&vm, 2)
+    );
+    assert_eq!(Value::Int(1), vm.printed[3]);
+    assert_eq!(Value::Int(1), vm.printed[4]);
+    assert_eq!("fallback", extract_printed_string(&vm, 5));
+    assert_eq!(Value::Int(1), vm.printed[6]);
+    assert_eq!("first", extract_printed_string(&vm, 7));
+    assert_eq!("second", extract_printed_string(&vm, 8));
+    assert_eq!("second", extract_printed_string(&vm, 9));
+    assert_eq!(Value::Int(1), vm.printed[10]);
+    assert_eq!(Value::Int(42), vm.printed[11]);
+    assert_eq!(Value::Int(1), vm.printed[12]);
+    assert_eq!("key can't be null", extract_printed_string(&vm, 13));
+    assert_eq!("key can't be empty", extract_printed_string(&vm, 14));
+    assert_eq!(None, vm.system_property("java.property"));
+}
+
+/// A host environment with two variables, one of which is overridden by the embedder
+#[derive(Debug)]
+struct TestEnvironmentIo;
+
+impl JvmIo for TestEnvironmentIo {
+    fn env(&self, name: &str) -> Option<String> {
+        self.env_vars()
+            .into_iter()
+            .find(|(variable, _)| variable == name)
+            .map(|(_, value)| value)
+    }
+
+    fn env_vars(&self) -> Vec<(String, String)> {
+        vec![
+            (
+                "RJVM_HOST_VARIABLE".to_string(),
+                "from the host".to_string(),
+            ),
+            (
+                "RJVM_OVERRIDDEN_VARIABLE".to_string(),
+                "from the host".to_string(),
+            ),
+        ]
+    }
+}
+
+#[test_log::test]
+fn environment_variables() {
+    let mut vm = VmBuilder::new()
+        .with_io(TestEnvironmentIo)
+        .build()
+        .expect("should be able to build the vm");
+    let src_dir = env!("CARGO_MANIFEST_DIR");
+    vm.append_class_path(&format!("{src_dir}/rt.jar:{src_dir}/tests/resources",))
+        .expect("should be able to add entries to the classpath");
+    vm.set_environment([
+        (
+            "RJVM_EMBEDDER_VARIABLE".to_string(),
+            "from the embedder".to_string(),
+        ),
+        (
+            "RJVM_OVERRIDDEN_VARIABLE".to_string(),
+            "overridden".to_string(),
+        ),
+    ]);
+
+    let main_result = invoke(
+        &mut vm,
+        "rjvm/Environment",
+        "main",
+        "([Ljava/lang/String;)V",
+    );
+    assert_eq!(Ok(None), main_result);
+
+    assert_eq!(5, vm.printed.len());
+    assert_eq!("from the host", extract_printed_string(&vm, 0));
+    assert_eq!("from the embedder", extract_printed_string(&vm, 1));
+    assert_eq!("overridden", extract_printed_string(&vm, 2));
+    assert_eq!(Value::Int(1), vm.printed[3]);
+    assert_eq!(Value::Int(1), vm.printed[4]);
+    assert_eq!(
+        vec![
+            (
+                "RJVM_EMBEDDER_VARIABLE".to_string(),
+                "from the embedder".to_string()
+            ),
+            (
+                "RJVM_HOST_VARIABLE".to_string(),
+                "from the host".to_string()
+            ),
+            (
+                "RJVM_OVERRIDDEN_VARIABLE".to_string(),
+                "overridden".to_string()
+            ),
+        ],
+        vm.visible_environment()
+    );
+}
+
+#[test_log::test]
+fn host_environment_is_not_visible_by_default() {
+    std::env::set_var("RJVM_DEFAULT_IO_VARIABLE", "value");
+    let mut vm = create_base_vm(DEFAULT_MAX_MEMORY);
+    assert_eq!(
+        None,
+        vm.visible_environment_variable("RJVM_DEFAULT_IO_VARIABLE")
+    );
+    assert!(vm.visible_environment().is_empty());
+
+    vm.set_io(Rc::new(NoJvmIo));
+    vm.set_environment([("NAME".to_string(), "value".to_string())]);
+    assert_eq!(
+        vec![("NAME".to_string(), "value".to_string())],
+        vm.visible_environment()
+    );
+}
+
+#[test_log::test]
+fn standard_output() {
+    let stdout = CapturedOutput::new();
+    let stderr = CapturedOutput::new();
+    let mut vm = VmBuilder::new()
+        .with_stdout(stdout.clone())
+        .with_stderr(stderr.clone())
+        .with_system_property("line.separator", "\n")
+        .build()
+        .expect("should be able to build the vm");
+    let src_dir = env!("CARGO_MANIFEST_DIR");
+    vm.append_class_path(&format!("{src_dir}/rt.jar:{src_dir}/tests/resources",))
+        .expect("should be able to add entries to the classpath");
+
+    let main_result = invoke(
+        &mut vm,
+        "rjvm/StandardOutput",
+        "main",
+        "([Ljava/lang/String;)V",
+    );
+    assert_eq!(Ok(None), main_result);
+
+    assert_eq!(
+        "Hello, world!\n\
+         42 -7 true 1.5 0.25\n\
+         chars\n\
+         null\n\
+         null\n\
+         (1, 2)\n\
+         café ☃\n\
+         abc\n\
+         !yz\n\
+         false\n",
+        stdout.contents()
+    );
+    assert_eq!("to stderr\nout of bounds\nredirected\n", stderr.contents());
+}
+
+/// A standard input that returns at most three bytes at a time
+#[derive(Debug)]
+struct TestInputIo {
+    input: RefCell<VecDeque<u8>>,
+}
+
+impl JvmIo for TestInputIo {
+    fn read_stdin(&self, buffer: &mut [u8]) -> Result<usize, String> {
+        let mut input = self.input.borrow_mut();
+        let read = buffer.len().min(input.len()).min(3);
+        for (byte, value) in buffer.iter_mut().zip(input.drain(..read)) {
+            *byte = value;
+        }
+        Ok(read)
+    }
+}
+
+#[test_log::test]
+fn standard_input() {
+    let mut vm = VmBuilder::new()
+        .with_io(TestInputIo {
+            input: RefCell::new(b"hello world!".iter().copied().collect()),
+        })
+        .build()
+        .expect("should be able to build the vm");
+    let src_dir = env!("CARGO_MANIFEST_DIR");
+    vm.append_class_path(&format!("{src_dir}/rt.jar:{src_dir}/tests/resources",))
+        .expect("should be able to add entries to the classpath");
+
+    let main_result = invoke(
+        &mut vm,
+        "rjvm/StandardInput",
+        "main",
+        "([Ljava/lang/String;)V",
+    );
+    assert_eq!(Ok(None), main_result);
+
+    assert_eq!(
+        vec![
+            Value::Int(i32::from(b'h')),
+            Value::Int(3),
+            Value::Int(i32::from(b'e')),
+            Value::Int(i32::from(b'l')),
+            Value::Int(i32::from(b'l')),
+            Value::Long(2),
+            Value::Int(0),
+            Value::Int(1),
+            Value::Int(i32::from(b'w')),
+            Value::Int(i32::from(b'o')),
+            Value::Int(4),
+            Value::Int(-1),
+        ],
+        vm.printed[..12]
+    );
+    assert_eq!("mark/reset not supported", extract_printed_string(&vm, 12));
+}
+
+#[test_log::test]
+fn file_io() {
+    let directory = std::env::temp_dir().join(format!("rjvm-file-io-{}", std::process::id()));
+    std::fs::create_dir_all(&directory).expect("should be able to create the directory");
+    let stdout = CapturedOutput::new();
+    let mut vm = VmBuilder::new()
+        .with_io(StdJvmIo::new())
+        .with_stdout(stdout.clone())
+        .with_system_property("rjvm.test.directory", directory.to_str().unwrap())
+        .build()
+        .expect("should be able to build the vm");
+    let src_dir = env!("CARGO_MANIFEST_DIR");
+    vm.append_class_path(&format!("{src_dir}/rt.jar:{src_dir}/tests/resources",))
+        .expect("should be able to add entries to the classpath");
+
+    let main_result = invoke(&mut vm, "rjvm/FileIo", "main", "([Ljava/lang/String;)V");
+    let written = std::fs::read(directory.join("rjvm-file-io.txt"));
+    std::fs::remove_dir_all(&directory).expect("should be able to remove the directory");
+    assert_eq!(Ok(None), main_result);
+
+    assert_eq!(
+        vec![
+            Value::Int(11),
+            Value::Int(i32::from(b'h')),
+            Value::Int(4),
+            Value::Long(1)
+        ],
+        vm.printed[..4]
+    );
+    assert_eq!("world", extract_printed_string(&vm, 4));
+    assert_eq!(Value::Int(-1), vm.printed[5]);
+    assert_eq!("Stream Closed", extract_printed_string(&vm, 6));
+    assert_eq!(
+        vec![
+            Value::Long(11),
+            Value::Long(7),
+            Value::Long(5),
+            Value::Long(5),
+            Value::Int(i32::from(b'h')),
+            Value::Int(0x01020304),
+            Value::Int(-1),
+        ],
+        vm.printed[7..14]
+    );
+    assert_eq!("Negative seek offset", extract_printed_string(&vm, 14));
+    let missing_file = directory.join("rjvm-missing-file.txt");
+    assert_eq!(
+        format!("{} (No such file or directory)", missing_file.display()),
+        extract_printed_string(&vm, 15)
+    );
+    assert_eq!("written to the descriptor\n", stdout.contents());
+    assert_eq!(
+        b"hello\x01\x02\x03\x04".to_vec(),
+        written.expect("the file should have been written")
+    );
+}
+
+#[test_log::test]
+fn file_io_needs_a_file_system() {
+    let mut vm = VmBuilder::new()
+        .with_system_property("rjvm.test.directory", "/tmp")
+        .build()
+        .expect("should be able to build the vm");
+    let src_dir = env!("CARGO_MANIFEST_DIR");
+    vm.append_class_path(&format!("{src_dir}/rt.jar:{src_dir}/tests/resources",))
+        .expect("should be able to add entries to the classpath");
+
+    let main_result = invoke(&mut vm, "rjvm/FileIo", "main", "([Ljava/lang/String;)V");
+    assert_eq!(
+        "/tmp/rjvm-file-io.txt (no file system available)",
+        extract_thrown_exception_message(&vm, main_result, "java/io/FileNotFoundException")
+    );
+}
+
+#[test_log::test]
+fn file_operations() {
+    let directory =
+        std::env::temp_dir().join(format!("rjvm-file-operations-{}", std::process::id()));
+    std::fs::create_dir_all(&directory).expect("should be able to create the directory");
+    let mut vm = VmBuilder::new()
+        .with_io(StdJvmIo::new())
+        .with_system_property("rjvm.test.directory", directory.to_str().unwrap())
+        .build()
+        .expect("should be able to build the vm");
+    let src_dir = env!("CARGO_MANIFEST_DIR");
+    vm.append_class_path(&format!("{src_dir}/rt.jar:{src_dir}/tests/resources",))
+        .expect("should be able to add entries to the classpath");
+
+    let main_result = invoke(
+        &mut vm,
+        "rjvm/FileOperations",
+        "main",
+        "([Ljava/lang/String;)V",
+    );
+    let canonical_directory = std::fs::canonicalize(&directory);
+    std::fs::remove_dir_all(&directory).expect("should be able to remove the directory");
+    assert_eq!(Ok(None), main_result);
+
+    assert_eq!(
+        vec![
+            Value::Int(0),
+            Value::Int(1),
+            Value::Int(0),
+            Value::Int(1),
+            Value::Int(0),
+            Value::Int(1),
+            Value::Int(0),
+            Value::Long(42),
+            Value::Int(1),
+            Value::Int(1),
+            Value::Int(1),
+            Value::Int(1),
+            Value::Long(1234567000),
+            Value::Int(1),
+            Value::Int(1),
+            Value::Int(2),
+        ],
+        vm.printed[..16]
+    );
+    assert_eq!(".hidden", extract_printed_string(&vm, 16));
+    assert_eq!("data.bin", extract_printed_string(&vm, 17));
+    assert_eq!(
+        vec![Value::Int(1), Value::Int(0), Value::Long(42)],
+        vm.printed[18..21]
+    );
+    let canonical_file = canonical_directory
+        .expect("should be able to canonicalize the directory")
+        .join("nested/renamed.bin");
+    assert_eq!(
+        canonical_file.to_str().unwrap(),
+        extract_printed_string(&vm, 21)
+    );
+    assert_eq!(
+        vec![
+            Value::Long(0),
+            Value::Long(0),
+            Value::Int(1),
+            Value::Int(0),
+            Value::Int(1),
+            Value::Int(1),
+            Value::Int(1),
+            Value::Int(0),
+        ],
+        vm.printed[22..]
+    );
+}
+
+#[cfg(feature = "std")]
+#[test_log::test]
+fn sockets() {
+    use rjvm_vm::io::StdJvmNet;
+
+    let mut vm = VmBuilder::new()
+        .with_net(StdJvmNet::new())
+        .build()
+        .expect("should be able to build the vm");
+    let src_dir = env!("CARGO_MANIFEST_DIR");
+    vm.append_class_path(&format!("{src_dir}/rt.jar:{src_dir}/tests/resources",))
+        .expect("should be able to add entries to the classpath");
+
+    let main_result = invoke(&mut vm, "rjvm/Sockets", "main", "([Ljava/lang/String;)V");
+    assert_eq!(Ok(None), main_result);
+
+    assert_eq!(Value::Int(1), vm.printed[0]);
+    assert_eq!("127.0.0.1", extract_printed_string(&vm, 1));
+    assert_eq!(
+        vec![
+            Value::Int(1),
+            Value::Int(1),
+            Value::Int(1234),
+            Value::Int(3),
+            Value::Int(6),
+            Value::Int(42),
+        ],
+        vm.printed[2..8]
+    );
+    assert_eq!("Read timed out", extract_printed_string(&vm, 8));
+    assert_eq!(vec![Value::Int(-1), Value::Int(1)], vm.printed[9..11]);
+    assert_eq!("Socket is closed", extract_printed_string(&vm, 11));
+    assert_eq!("Accept timed out", extract_printed_string(&vm, 12));
+    assert_eq!("Connection refused", extract_printed_string(&vm, 13));
+}
+
+/// A network that only knows the host "rjvm.test"
+#[derive(Debug)]
+struct TestHostsNet;
+
+impl JvmNet for TestHostsNet {
+    fn resolve_host(&self, host: &str) -> Result<Vec<IpAddr>, NetError> {
+        match host {
+            "rjvm.test" => Ok(vec![IpAddr::from([10, 1, 2, 3])]),
+            _ => Err(NetError::other("Name or service not known")),
+        }
+    }
+}
+
+#[test_log::test]
+fn sockets_without_network() {
+    let mut vm = VmBuilder::new()
+        .with_net(TestHostsNet)
+        .build()
+        .expect("should be able to build the vm");
+    let src_dir = env!("CARGO_MANIFEST_DIR");
+    vm.append_class_path(&format!("{src_dir}/rt.jar:{src_dir}/tests/resources",))
+        .expect("should be able to add entries to the classpath");
+
+    let result = invoke(&mut vm, "rjvm/Sockets", "withoutNetwork", "()V");
+    assert_eq!(Ok(None), result);
+
+    assert_eq!("rjvm.test", extract_printed_string(&vm, 0));
+    assert_eq!("10.1.2.3", extract_printed_string(&vm, 1));
+    assert_eq!(
+        "unknown.test: Name or service not known",
+        extract_printed_string(&vm, 2)
+    );
+    assert_eq!("no network available", extract_printed_string(&vm, 3));
+}
+
+#[test_log::test]
+fn resources() {
+    let mut vm = create_base_vm(DEFAULT_MAX_MEMORY);
+
+    let main_result = invoke(&mut vm, "rjvm/Resources", "main", "([Ljava/lang/String;)V");
+    assert_eq!(Ok(None), main_result);
+
+    assert_eq!("hello from a resource", extract_printed_string(&vm, 0));
+    assert_eq!(vec![Value::Int(42), Value::Int(8)], vm.printed[1..3]);
+    assert_eq!("Manifest", extract_printed_string(&vm, 3));
+    assert_eq!(
+        vec![Value::Int(66), Value::Int(1), Value::Int(1)],
+        vm.printed[4..]
+    );
+}
+
+#[test_log::test]
+fn jar_manifest_class_path() {
+    let mut vm = Vm::new(DEFAULT_MAX_MEMORY);
+    let src_dir = env!("CARGO_MANIFEST_DIR");
+    vm.append_class_path(&format!(
+        "{src_dir}/rt.jar:{src_dir}/tests/resources/jars/app.jar",
+    ))
+    .expect("should be able to add entries to the classpath");
+
+    let main_result = invoke(&mut vm, "rjvm/jars/App", "main", "([Ljava/lang/String;)V");
+    assert_eq!(Ok(None), main_result);
+    assert_eq!("hello, rjvm", extract_printed_string(&vm, 0));
+}
+
+#[test_log::test]
+fn switches() {
+    let mut vm = create_base_vm(DEFAULT_MAX_MEMORY);
+    let main_result = invoke(&mut vm, "rjvm/Switches", "main", "([Ljava/lang/String;)V");
+    assert_eq!(Ok(None), main_result);
+    assert_eq!(
+        [99, 20, 10, 0, -10, 99, -30, 99, 1, 2, 0, 3, 4, 5, 6, 0]
+            .into_iter()
+            .map(Value::Int)
+            .collect::<Vec<_>>(),
+        vm.printed
+    );
+}
+
+#[test_log::test]
+fn multi_dimensional_arrays() {
+    let mut vm = create_base_vm(10_000_000);
+    let main_result = invoke(
+        &mut vm,
+        "rjvm/MultiDimensionalArrays",
+        "main",
+        "([Ljava/lang/String;)V",
+    );
+    assert_eq!(Ok(None), main_result);
+
+    assert_eq!(
+        vec![
+            Value::Int(3),
+            Value::Int(4),
+            Value::Int(23),
+            Value::Int(3),
+            Value::Int(1),
+            Value::Int(7),
+            Value::Int(1),
+            Value::Int(1),
+            Value::Int(0),
+            Value::Int(2),
+            Value::Int(11),
+            Value::Int(42),
+            Value::Int(0),
+            Value::Int(23),
+        ],
+        vm.printed[0..14]
+    );
+    assert_eq!("a", extract_printed_string(&vm, 14));
+    assert_eq!("c", extract_printed_string(&vm, 15));
+    assert_eq!("-1", extract_printed_string(&vm, 16));
+}
+
+#[test_log::test]
+fn arrays_created_by_the_host() {
+    let mut vm = create_base_vm(DEFAULT_MAX_MEMORY);
+    let sum = |vm: &mut Vm<'static>, method_name: &str, descriptor: &str, array| {
+        invoke_with_args(
+            vm,
+            "rjvm/HostArrays",
+            method_name,
+            descriptor,
+            vec![Value::Object(array)],
+        )
+    };
+
+    let array = vm
+        .new_boolean_array(&[true, false, true])
+        .expect("should allocate the array");
+    assert_eq!(
+        Ok(Some(Value::Int(2))),
+        sum(&mut vm, "sumBooleans", "([Z)I", array)
+    );
+    let array = vm
+        .new_byte_array(&[1, 2, 0xff])
+        .expect("should allocate the array");
+    assert_eq!(
+        Ok(Some(Value::Int(2))),
+        sum(&mut vm, "sumBytes", "([B)I", array)
+    );
+    let array = vm
+        .new_char_array(&[0x41, 0xffff])
+        .expect("should allocate the array");
+    assert_eq!(
+        Ok(Some(Value::Int(0x41 + 0xffff))),
+        sum(&mut vm, "sumChars", "([C)I", array)
+    );
+    let array = vm
+        .new_short_array(&[-300, 5])
+        .expect("should allocate the array");
+    assert_eq!(
+        Ok(Some(Value::Int(-295))),
+        sum(&mut vm, "sumShorts", "([S)I", array)
+    );
+    let array = vm
+        .new_int_array(&[1, -2, 40])
+        .expect("should allocate the array");
+    assert_eq!(
+        Ok(Some(Value::Int(39))),
+        sum(&mut vm, "sumInts", "([I)I", array)
+    );
+    let array = vm
+        .new_long_array(&[1 << 40, -1])
+        .expect("should allocate the array");
+    assert_eq!(
+        Ok(Some(Value::Long((1 << 40) - 1))),
+        sum(&mut vm, "sumLongs", "([J)J", array)
+    );
+    let array = vm
+        .new_float_array(&[0.5, 1.25])
+        .expect("should allocate the array");
+    assert_eq!(
+        Ok(Some(Value::Float(1.75))),
+        sum(&mut vm, "sumFloats", "([F)F", array)
+    );
+    let array = vm
+        .new_double_array(&[0.5, -2.0])
+        .expect("should allocate the array");
+    assert_eq!(
+        Ok(Some(Value::Double(-1.5))),
+        sum(&mut vm, "sumDoubles", "([D)D", array)
+    );
+
+    let call_stack = vm.allocate_call_stack();
+    let array = vm
+        .new_object_array(call_stack, "java/lang/String", 3)
+        .expect("should allocate the array");
+    assert_eq!(3, array.len());
+    assert_eq!(Ok(Value::Null), array.get_element(2));
+    assert_eq!(
+        Ok(Some(Value::Int(3))),
+        sum(&mut vm, "countNulls", "([Ljava/lang/Object;)I", array)
+    );
+}
+
+#[test_log::test]
+fn lambdas() {
+    let mut vm = create_base_vm(DEFAULT_MAX_MEMORY);
+    let main_result = invoke(&mut vm, "rjvm/Lambdas", "main", "([Ljava/lang/String;)V");
+    assert_eq!(Ok(None), main_result);
+
+    assert_eq!(8, vm.printed.len());
+    assert_eq!(Value::Int(7), vm.printed[0]);
+    assert_eq!(Value::Int(15), vm.printed[1]);
+    assert_eq!(Value::Int(49), vm.printed[2]);
+    assert_eq!("counter=42", extract_printed_string(&vm, 3));
+    assert_eq!("trimmed", extract_printed_string(&vm, 4));
+    assert_eq!("built!", extract_printed_string(&vm, 5));
+    assert_eq!(Value::Int(123), vm.printed[6]);
+    assert_eq!(Value::Int(10), vm.printed[7]);
+}
+
+#[test_log::test]
+fn invokedynamic_with_unsupported_bootstrap_method() {
+    let mut vm = create_base_vm(DEFAULT_MAX_MEMORY);
+    let main_result = invoke(
+        &mut vm,
+        "rjvm/DynamicStringConcat",
+        "main",
+        "([Ljava/lang/String;)V",
+    );
+    assert!(matches!(
+        main_result,
+        Err(MethodCallFailed::InternalError(VmError::UnsupportedBootstrapMethod(method)))
+            if method.starts_with("InvokeStatic java/lang/invoke/StringConcatFactory.makeConcatWithConstants")
+    ));
+}
+
+#[test_log::test]
+fn gabarge_collector() {
+    let mut vm = create_base_vm(10_000_000);
+    let main_result = invoke(
+        &mut vm,
+        "rjvm/GarbageCollection",
+        "main",
+        "([Ljava/lang/String;)V",
+    );
+    assert_eq!(Ok(None), main_result);
+}
+
+#[test_log::test]
+fn out_of_memory_error() {
+    let mut vm = create_base_vm(10_000_000);
+    let main_result = invoke(
+        &mut vm,
+        "rjvm/OutOfMemory",
+        "main",
+        "([Ljava/lang/String;)V",
+    );
+    assert_eq!(Ok(None), main_result);
+
+    assert_eq!(3, vm.printed.len());
+    assert_eq!("Java heap space", extract_printed_string(&vm, 0));
+    assert_eq!("heap exhausted", extract_printed_string(&vm, 1));
+    assert_eq!(Value::Int(100_000), vm.printed[2]);
+
+    // Allocations requested by the embedder fail without throwing
+    assert_eq!(
+        Err(VmError::OutOfMemoryError),
+        vm.new_long_array(&vec![0; 10_000_000]).map(|_| ())
+    );
+}
+
+#[test_log::test]
+fn allocation_hooks_observe_every_allocation() {
+    let events = Rc::new(RefCell::new(Vec::new()));
+    let events_in_hook = events.clone();
+    let mut vm = VmBuilder::new()
+        .with_max_memory(10_000_000)
+        .with_allocation_hook(move |event| {
+            let description = match &event.kind {
+                AllocationKind::Object { class_name } => class_name.to_string(),
+                AllocationKind::Array { length, .. } => format!("array[{length}]"),
+            };
+            events_in_hook.borrow_mut().push((description, event.size));
+        })
+        .build()
+        .expect("should be able to build the vm");
+    let src_dir = env!("CARGO_MANIFEST_DIR");
+    vm.append_class_path(&format!("{src_dir}/rt.jar:{src_dir}/tests/resources",))
+        .expect("should be able to add entries to the classpath");
+
+    let main_result = invoke(
+        &mut vm,
+        "rjvm/GarbageCollection",
+        "main",
+        "([Ljava/lang/String;)V",
+    );
+    assert_eq!(Ok(None), main_result);
+
+    // The large objects do not all fit in memory, so the gc must have run in between.
+    // Each class also has one more instance, used to store its static fields.
+    let events = events.borrow();
+    let count_of = |name: &str| events.iter().filter(|(event, _)| event == name).count();
+    assert_eq!(13, count_of("rjvm/GarbageCollection$AWrapperObject"));
+    assert_eq!(13, count_of("rjvm/GarbageCollection$ALargeObject"));
+    assert_eq!(23, count_of("rjvm/GarbageCollection$ASmallObject"));
+
+    let counters = vm.allocation_counters();
+    let arrays_count = events
+        .iter()
+        .filter(|(event, _)| event.starts_with("array"))
+        .count();
+    assert_eq!(u64::try_from(arrays_count).unwrap(), counters.arrays);
+    assert_eq!(
+        u64::try_from(events.len() - arrays_count).unwrap(),
+        counters.objects
+    );
+    let total_size: usize = events.iter().map(|(_, size)| size).sum();
+    assert_eq!(u64::try_from(total_size).unwrap(), counters.bytes);
+    assert!(counters.bytes > 10_000_000);
+}
+
+#[test_log::test]
+fn safepoint_requests_inspect_the_stopped_vm() {
+    let mut vm = create_base_vm(10_000_000);
+    let requester = vm.safepoint_requester();
+    let snapshots = Rc::new(RefCell::new(Vec::new()));
+
+    // Stops the vm right after the first allocation of one of the large objects, which is
+    // executed by the `new` instruction in the constructor of the wrapper
+    let snapshots_in_hook = snapshots.clone();
+    let requested = Rc::new(RefCell::new(false));
+    vm.add_allocation_hook(AllocationHook::new(move |event| {
+        let is_large_object = event.kind
+            == AllocationKind::Object {
+                class_name: "rjvm/GarbageCollection$ALargeObject",
+            };
+        if is_large_object && !requested.replace(true) {
+            let snapshots = snapshots_in_hook.clone();
+            requester.request(move |view| {
+                let methods: Vec<String> = view.call_stacks[0]
+                    .iter()
+                    .map(|element| format!("{}::{}", element.class_name, element.method_name))
+                    .collect();
+                let large_object_class = view
+                    .vm
+                    .find_class_by_name("rjvm/GarbageCollection$ALargeObject");
+                snapshots.borrow_mut().push((
+                    methods,
+                    large_object_class.is_some(),
+                    view.roots.is_empty(),
+                ));
+            });
+        }
+    }));
+
+    let main_result = invoke(
+        &mut vm,
+        "rjvm/GarbageCollection",
+        "main",
+        "([Ljava/lang/String;)V",
+    );
+    assert_eq!(Ok(None), main_result);
+    assert!(!vm.safepoint_requester().has_pending_requests());
+
+    let snapshots = snapshots.borrow();
+    assert_eq!(1, snapshots.len());
+    let (methods, has_loaded_class, roots_empty) = &snapshots[0];
+    assert_eq!(
+        &vec![
+            "rjvm/GarbageCollection$AWrapperObject::<init>".to_string(),
+            "rjvm/GarbageCollection::main".to_string(),
+        ],
+        methods
+    );
+    assert!(has_loaded_class);
+    assert!(!roots_empty);
+}
+
+#[test_log::test]
+fn garbage_collection_with_incremental_marking() {
+    let mut vm = create_base_vm(10_000_000);
+    vm.set_incremental_marking(Some(IncrementalMarkingConfig {
+        start_threshold_percent: 30,
+        objects_per_slice: 4,
+        slice_time_budget: None,
+    }));
+    let main_result = invoke(
+        &mut vm,
+        "rjvm/GarbageCollection",
+        "main",
+        "([Ljava/lang/String;)V",
+    );
+    assert_eq!(Ok(None), main_result);
+
+    let printed_count = vm.printed.len();
+    assert_eq!(
+        "checking references are still alive...",
+        extract_printed_string(&vm, printed_count - 4)
+    );
+    assert_eq!(
+        vec![Value::Long(0), Value::Long(-3), Value::Long(1)],
+        vm.printed[printed_count - 3..]
+    );
+}
+
+#[test_log::test]
+fn garbage_collection_with_mark_compact() {
+    // The same usable memory as the other tests, since no semi-space is reserved
+    let mut vm = create_base_vm(5_000_000);
+    vm.set_gc_algorithm(GcAlgorithm::MarkCompact);
+    assert_garbage_collection_works(&mut vm);
+}
+
+#[test_log::test]
+fn garbage_collection_with_mark_compact_and_incremental_marking() {
+    let mut vm = create_base_vm(5_000_000);
+    vm.set_gc_algorithm(GcAlgorithm::MarkCompact);
+    vm.set_incremental_marking(Some(IncrementalMarkingConfig {
+        start_threshold_percent: 30,
+        objects_per_slice: 4,
+        slice_time_budget: None,
+    }));
+    assert_garbage_collection_works(&mut vm);
+}
+
+#[test_log::test]
+fn garbage_collection_with_growing_heap() {
+    // The large objects of the test do not even fit in the initial semi-spaces
+    let mut vm = create_base_vm(10_000_000);
+    vm.set_heap_growth(1_000_000, HeapGrowthPolicy::Doubling);
+    assert_eq!(1_000_000, vm.heap_size());
+    assert_garbage_collection_works(&mut vm);
+    assert!(vm.heap_size() > 1_000_000);
+    assert!(vm.heap_size() <= 10_000_000);
+}
+
+#[test_log::test]
+fn garbage_collection_with_mark_compact_and_growing_heap() {
+    let mut vm = create_base_vm(5_000_000);
+    vm.set_gc_algorithm(GcAlgorithm::MarkCompact);
+    vm.set_heap_growth(500_000, HeapGrowthPolicy::Percentage(50));
+    assert_garbage_collection_works(&mut vm);
+    assert!(vm.heap_size() > 500_000);
+    assert!(vm.heap_size() <= 5_000_000);
+}
+
+#[test_log::test]
+fn garbage_collection_stats() {
+    let mut vm = create_base_vm(10_000_000);
+    assert_eq!(GcStats::default(), vm.gc_stats());
+    assert_garbage_collection_works(&mut vm);
+
+    let stats = vm.gc_stats();
+    assert!(stats.collections > 0);
+    assert_eq!(0, stats.marking_slices);
+    assert_eq!(vm.allocation_counters().bytes, stats.bytes_allocated);
+    assert!(stats.bytes_reclaimed > 0);
+    assert!(stats.live_bytes > 0);
+    assert!(stats.live_bytes <= stats.bytes_allocated - stats.bytes_reclaimed);
+    assert!(stats.max_pause > Duration::ZERO);
+    assert!(stats.max_pause <= stats.total_pause);
+}
+
+#[test_log::test]
+fn heap_walk_reports_only_live_objects() {
+    let mut vm = create_base_vm(10_000_000);
+    assert_garbage_collection_works(&mut vm);
+
+    let mut objects = Vec::new();
+    vm.for_each_live_object(|object| objects.push(object.clone()))
+        .expect("should be able to walk the heap");
+
+    let ids: HashSet<ObjectId> = objects.iter().map(|object| object.id).collect();
+    assert_eq!(objects.len(), ids.len());
+    let total_size: usize = objects.iter().map(|object| object.size).sum();
+    assert_eq!(vm.gc_stats().live_bytes, total_size as u64);
+
+    let instances_of = |class_name: &str| {
+        objects
+            .iter()
+            .filter(|object| {
+                matches!(&object.contents, HeapObjectContents::Instance { class, .. } if class.name == class_name)
+            })
+            .count()
+    };
+    // The program does not keep any of its objects after returning
+    assert_eq!(0, instances_of("rjvm/GarbageCollection$AWrapperObject"));
+    assert!(instances_of("java/lang/String") > 0);
+    assert!(objects.iter().any(|object| matches!(
+        &object.contents,
+        HeapObjectContents::Statics { class, fields }
+            if class.name == "rjvm/GarbageCollection$AWrapperObject" && fields.is_empty()
+    )));
+
+    for object in objects.iter() {
+        match &object.contents {
+            HeapObjectContents::Instance { class, fields } if class.name == "java/lang/String" => {
+                let value = fields
+                    .iter()
+                    .find(|field| field.name == "value")
+                    .expect("strings should have a value field");
+                assert!(matches!(value.value, Value::Object(_)));
+            }
+            HeapObjectContents::Array { elements, .. } => {
+                assert!(object.size >= elements.len() * 8);
+            }
+            _ => {}
+        }
+    }
+}
+
+#[test_log::test]
+fn handles_keep_objects_alive_across_collections() {
+    let mut vm = create_base_vm(DEFAULT_MAX_MEMORY);
+    let call_stack = vm.allocate_call_stack();
+    let live_ids = |vm: &mut Vm| {
+        let mut ids = HashSet::new();
+        vm.for_each_live_object(|object| {
+            ids.insert(object.id);
+        })
+        .expect("should be able to walk the heap");
+        ids
+    };
+
+    let string_id = {
+        let scope = vm.handle_scope();
+        let string = scope.handle(
+            new_java_lang_string_object(&mut vm, call_stack, "rooted")
+                .expect("should be able to create a string"),
+        );
+        for _ in 0..3 {
+            vm.run_garbage_collection()
+                .expect("should be able to run the gc");
+        }
+        assert!(live_ids(&mut vm).contains(&string.get().id()));
+        assert_eq!(
+            "rooted",
+            extract_str_from_java_lang_string(&vm, &string.get())
+                .expect("should still be a valid string")
+        );
+        string.get().id()
+    };
+
+    // Once the scope is dropped, nothing keeps the string alive
+    assert!(!live_ids(&mut vm).contains(&string_id));
+}
+
+#[test_log::test]
+fn weak_handles_are_cleared_once_the_object_is_collected() {
+    let mut vm = create_base_vm(DEFAULT_MAX_MEMORY);
+    let call_stack = vm.allocate_call_stack();
+
+    let scope = vm.handle_scope();
+    let string = scope.handle(
+        new_java_lang_string_object(&mut vm, call_stack, "cached")
+            .expect("should be able to create a string"),
+    );
+    let weak = string.downgrade();
+    let garbage = new_java_lang_string_object(&mut vm, call_stack, "garbage")
+        .expect("should be able to create a string");
+    let garbage = vm.new_weak_handle(&garbage);
+    vm.run_garbage_collection()
+        .expect("should be able to run the gc");
+
+    assert!(garbage.is_collected());
+    let cached = weak.get().expect("should be alive while rooted");
+    assert!(cached.is_same_as(&string.get()));
+    assert_eq!(
+        "cached",
+        extract_str_from_java_lang_string(&vm, &cached).expect("should be a valid string")
+    );
+
+    drop(scope);
+    vm.run_garbage_collection()
+        .expect("should be able to run the gc");
+    assert!(weak.get().is_none());
+}
+
+fn assert_garbage_collection_works(vm: &mut Vm) {
+    let main_result = invoke(
+        vm,
+        "rjvm/GarbageCollection",
+        "main",
+        "([Ljava/lang/String;)V",
+    );
+    assert_eq!(Ok(None), main_result);
+
+    let printed_count = vm.printed.len();
+    assert_eq!(
+        "checking references are still alive...",
+        extract_printed_string(vm, printed_count - 4)
+    );
+    assert_eq!(
+        vec![Value::Long(0), Value::Long(-3), Value::Long(1)],
+        vm.printed[printed_count - 3..]
+    );
+}
+
+#[test_log::test]
+fn finalization_with_mark_compact() {
+    let mut vm = create_base_vm(DEFAULT_MAX_MEMORY);
+    vm.set_gc_algorithm(GcAlgorithm::MarkCompact);
+    assert_finalizers_run(&mut vm);
+}
+
+#[test_log::test]
+fn generic() {
+    let mut vm = create_base_vm(10_000_000);
+    let main_result = invoke(&mut vm, "rjvm/Generic", "main", "([Ljava/lang/String;)V");
+    assert_eq!(Ok(None), main_result);
+}
+
+/// Runs the unmodified ASM 9.7.1 library, compiled for java 5, through a harness compiled for
+/// java 8: it generates a class, reads it back and copies it
+#[test_log::test]
+fn asm_library_end_to_end() {
+    let mut vm = Vm::new(DEFAULT_MAX_MEMORY);
+    let src_dir = env!("CARGO_MANIFEST_DIR");
+    vm.append_class_path(&format!(
+        "{src_dir}/rt.jar:{src_dir}/tests/resources/asm-9.7.1.jar:{src_dir}/tests/resources",
+    ))
+    .expect("should be able to add entries to the classpath");
+
+    let main_result = invoke(&mut vm, "rjvm/AsmLibrary", "main", "([Ljava/lang/String;)V");
+    assert_eq!(Ok(None), main_result);
+
+    assert_eq!(Value::Int(466), vm.printed[0]);
+    let printed: Vec<String> = (1..vm.printed.len())
+        .map(|i| extract_printed_string(&vm, i))
+        .collect();
+    assert_eq!(
+        vec![
+            "class rjvm/Shapes extends java/lang/Object implements java/lang/Runnable, version 52",
+            "source Shapes.java",
+            "field sides I",
+            "field NAME Ljava/lang/String; = shapes",
+            "method <init> 25:0 183:java/lang/Object.<init> 25:0 21:1 181:sides 177 maxs:2/2",
+            "method name 25:0 180:sides tableswitch:3-4 ldc:triangle 176 ldc:square 176 \
+             ldc:polygon 176 maxs:2/1",
+            "method run 177 maxs:0/1",
+            "copy ok",
+            "Unsupported class file major version 99",
+        ],
+        printed
+    );
+}
+
+#[test_log::test]
+fn collection_intrinsics_with_minimal_class_library() {
+    let mut vm = Vm::new(DEFAULT_MAX_MEMORY);
+    let src_dir = env!("CARGO_MANIFEST_DIR");
+    vm.append_class_path(&format!(
+        "{src_dir}/tests/resources/minimal_runtime:{src_dir}/rt.jar:{src_dir}/tests/resources",
+    ))
+    .expect("should be able to add entries to the classpath");
+    vm.set_collection_intrinsics_enabled(true);
+
+    let main_result = invoke(
+        &mut vm,
+        "rjvm/CollectionIntrinsics",
+        "main",
+        "([Ljava/lang/String;)V",
+    );
+    assert_eq!(Ok(None), main_result);
+    assert!(vm.is_collection_class_intrinsified("java/util/ArrayList"));
+    assert!(vm.is_collection_class_intrinsified("java/util/HashMap"));
+
+    assert_eq!(19, vm.printed.len());
+    assert_eq!(Value::Int(3), vm.printed[0]);
+    let printed_strings: Vec<String> = (1..8).map(|i| extract_printed_string(&vm, i)).collect();
+    assert_eq!(
+        vec!["a", "c", "b", "a", "b", "d", "Index: 5, Size: 2"],
+        printed_strings
+    );
+    assert_eq!(Value::Int(1), vm.printed[8]);
+
+    assert_eq!("1", extract_printed_string(&vm, 9));
+    assert_eq!(Value::Int(3), vm.printed[10]);
+    assert_eq!("11", extract_printed_string(&vm, 11));
+    assert_eq!("0", extract_printed_string(&vm, 12));
+    assert_eq!(Value::Int(0), vm.printed[13]);
+    assert_eq!("2", extract_printed_string(&vm, 14));
+    assert_eq!(vec![Value::Int(2), Value::Int(1)], vm.printed[15..17]);
+
+    // The elements are kept alive by the lists, but do not keep them alive
+    assert_eq!(vec![Value::Int(42), Value::Int(1)], vm.printed[17..]);
+}
+
+#[test_log::test]
+fn collection_intrinsics_survive_gc_in_hash_code_and_equals() {
+    let mut vm = Vm::new(2 * 1024 * 1024);
+    let src_dir = env!("CARGO_MANIFEST_DIR");
+    vm.append_class_path(&format!(
+        "{src_dir}/tests/resources/minimal_runtime:{src_dir}/rt.jar:{src_dir}/tests/resources",
+    ))
+    .expect("should be able to add entries to the classpath");
+    vm.set_collection_intrinsics_enabled(true);
+
+    let main_result = invoke(
+        &mut vm,
+        "rjvm/CollectionIntrinsicsAllocatingKeys",
+        "main",
+        "([Ljava/lang/String;)V",
+    );
+    assert_eq!(Ok(None), main_result);
+    assert!(vm.is_collection_class_intrinsified("java/util/HashMap"));
+    assert_eq!(vec![Value::Int(200), Value::Int(200)], vm.printed);
+}
+
+#[test_log::test]
+fn collection_intrinsics_are_not_used_with_real_class_library() {
+    let mut vm = create_base_vm(DEFAULT_MAX_MEMORY);
+    vm.set_collection_intrinsics_enabled(true);
+
+    let call_stack = vm.allocate_call_stack();
+    vm.get_or_resolve_class(call_stack, "java/util/ArrayList")
+        .expect("should be able to load ArrayList");
+    assert!(!vm.is_collection_class_intrinsified("java/util/ArrayList"));
+    assert!(vm.are_collection_intrinsics_enabled());
+}
+
+#[test_log::test]
+fn simple_main_with_throttled_logging() {
+    let log_config = LogConfig::none()
+        .with_category(LogCategory::Instructions, true)
+        .with_instructions_sampling(10)
+        .with_instructions_max_call_depth(Some(1));
+    let mut vm = VmBuilder::new()
+        .with_log_config(log_config)
+        .with_log_category(LogCategory::Gc, true)
+        .build()
+        .expect("should be able to build the vm");
+    assert_eq!(
+        log_config.with_category(LogCategory::Gc, true),
+        vm.log_config()
+    );
+
+    let src_dir = env!("CARGO_MANIFEST_DIR");
+    vm.append_class_path(&format!("{src_dir}/rt.jar:{src_dir}/tests/resources",))
+        .expect("should be able to add entries to the classpath");
+    let main_result = invoke(&mut vm, "rjvm/SimpleMain", "main", "([Ljava/lang/String;)V");
+    assert_eq!(Ok(None), main_result);
+    assert_eq!(vec![Value::Int(3), Value::Int(6)], vm.printed);
+}
+
+#[test_log::test]
+fn object_fields_iteration_and_mutation() {
+    let mut vm = create_base_vm(DEFAULT_MAX_MEMORY);
+    let call_stack = vm.allocate_call_stack();
+    let derived = vm
+        .new_object(call_stack, "rjvm/ObjectFields$Derived")
+        .expect("should be able to allocate object");
+    let base = vm
+        .new_object(call_stack, "rjvm/ObjectFields$Base")
+        .expect("should be able to allocate object");
+    let name = new_java_lang_string_object(&mut vm, call_stack, "derived")
+        .expect("should be able to allocate string");
+
+    set_object_field(&vm, &derived, "id", Value::Long(42)).unwrap();
+    set_object_field_of_class(&vm, &derived, "rjvm/ObjectFields$Base", "id", Value::Int(7))
+        .unwrap();
+    set_object_field(&vm, &derived, "name", Value::Object(name)).unwrap();
+    set_object_field(&vm, &derived, "parent", Value::Object(base)).unwrap();
+
+    let fields = get_object_fields(&vm, &derived).expect("should be able to get fields");
+    let summary: Vec<(&str, &str, String)> = fields
+        .iter()
+        .map(|field| {
+            (
+                field.declaring_class.name.as_str(),
+                field.name,
+                field.field_type.to_string(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        vec![
+            ("rjvm/ObjectFields$Base", "id", "Int".to_string()),
+            (
+                "rjvm/ObjectFields$Base",
+                "name",
+                "java/lang/String".to_string()
+            ),
+            ("rjvm/ObjectFields$Derived", "id", "Long".to_string()),
+            ("rjvm/ObjectFields$Derived", "values", "Int[]".to_string()),
+            (
+                "rjvm/ObjectFields$Derived",
+                "parent",
+                "rjvm/ObjectFields$Base".to_string()
+            ),
+        ],
+        summary
+    );
+    assert_eq!(Value::Int(7), fields[0].value);
+    assert_eq!(Value::Long(42), fields[2].value);
+    assert_eq!(Value::Null, fields[3].value);
+    let Value::Object(name) = &fields[1].value else {
+        panic!("expected an object but got {:?}", fields[1].value)
+    };
+    assert_eq!(
+        "derived",
+        extract_str_from_java_lang_string(&vm, name).unwrap()
+    );
+
+    assert_eq!(
+        Err(VmError::FieldTypeMismatch(
+            "rjvm/ObjectFields$Derived".to_string(),
+            "id".to_string()
+        )),
+        set_object_field(&vm, &derived, "id", Value::Int(1))
+    );
+    assert_eq!(
+        Err(VmError::FieldTypeMismatch(
+            "rjvm/ObjectFields$Derived".to_string(),
+            "parent".to_string()
+        )),
+        set_object_field(&vm, &derived, "parent", fields[1].value.clone())
+    );
+    assert_eq!(
+        Err(VmError::FieldNotFoundException(
+            "rjvm/ObjectFields$Derived".to_string(),
+            "counter".to_string()
+        )),
+        set_object_field(&vm, &derived, "counter", Value::Int(1))
+    );
 }
@@ -1,2 +1,63 @@
 #!/usr/bin/env sh
-javac -source 6 -target 6 rjvm/*.java
+javac -source 6 -target 6 $(ls rjvm/*.java | grep -v -e Lambdas.java -e DynamicStringConcat.java -e InvokeSpecial.java -e AccessControl.java -e AsmLibrary.java)
+# Lambdas require at least java 8, and string concatenation uses invokedynamic since java 9
+javac -source 8 -target 8 rjvm/Lambdas.java
+# Drives the unmodified ASM library, vendored as a jar with its license in asm-LICENSE.txt
+javac -source 8 -target 8 -cp asm-9.7.1.jar rjvm/AsmLibrary.java
+javac -source 11 -target 11 rjvm/DynamicStringConcat.java
+# Private interface methods require at least java 9
+javac -source 11 -target 11 rjvm/InvokeSpecial.java
+# Nestmates can access each other's private members since java 11
+javac -source 11 -target 11 rjvm/AccessControl.java
+javac -source 8 -target 8 minimal_runtime/java/util/*.java
+# These model optional dependencies, which are not available at runtime
+rm rjvm/LazyLinkage\$Missing*.class rjvm/LinkageErrors\$Missing*.class
+# Replaces the classes LinkageErrors and AbstractErrors were compiled against with newer,
+# incompatible versions
+javac -source 6 -target 6 -d . evolved/rjvm/EvolvingLibrary.java evolved/rjvm/EvolvingBase.java
+# New versions of Redefinition, which the tests swap in while it is loaded
+javac -source 6 -target 6 -d redefined redefined/rjvm/Redefinition.java
+javac -source 6 -target 6 -d redefined/incompatible redefined/incompatible/rjvm/Redefinition.java
+# Makes the fields ConstantValues reads via getstatic compile-time constants
+javac -source 6 -target 6 -d . evolved/rjvm/ConstantHolder.java
+# Likewise, replaces the classes AccessControl was compiled against with more restrictive versions
+javac -source 11 -target 11 -d . evolved/rjvm/access/*.java
+# javac no longer emits jsr and ret, so the classes using them are generated with ASM
+java --add-exports java.base/jdk.internal.org.objectweb.asm=ALL-UNNAMED generators/GenerateSubroutines.java
+# Replaces the nested classes of VerifyErrors with invalid versions, which javac cannot produce
+java --add-exports java.base/jdk.internal.org.objectweb.asm=ALL-UNNAMED generators/GenerateInvalidBytecode.java
+# Replaces the nested class of Synchronization with one that exits monitors it does not hold
+java --add-exports java.base/jdk.internal.org.objectweb.asm=ALL-UNNAMED generators/GenerateUnbalancedMonitors.java
+# Replaces the nested classes of SuperCalls with ones whose super calls skip their parent
+java --add-exports java.base/jdk.internal.org.objectweb.asm=ALL-UNNAMED generators/GenerateSuperCalls.java
+# Jars whose manifests reference each other through Class-Path, the first one also being
+# runnable through its Main-Class
+javac -source 7 -target 7 -d jars/classes jars/src/rjvm/jars/*.java jars/src/rjvm/jars/lib/*.java
+jar cfm jars/app.jar jars/app.mf -C jars/classes rjvm/jars/App.class
+jar cfm jars/lib/greeter.jar jars/greeter.mf -C jars/classes rjvm/jars/lib
+# A Spring Boot style fat jar, with the application classes in BOOT-INF/classes and the
+# dependencies in nested jars, themselves nested inside another jar
+mkdir -p jars/boot/BOOT-INF/classes/rjvm/jars jars/boot/BOOT-INF/lib
+cp jars/classes/rjvm/jars/App.class jars/boot/BOOT-INF/classes/rjvm/jars/
+jar cf jars/boot/BOOT-INF/lib/bundle.jar -C jars/lib greeter.jar
+jar cfm jars/fat.jar jars/fat.mf -C jars/boot BOOT-INF
+rm -r jars/boot
+rm -r jars/classes
+# A jimage and a jmod containing the module rjvm.sample, like the ones of JDK 9+
+java --add-modules jdk.jlink --add-exports jdk.jlink/jdk.tools.jlink.internal=ALL-UNNAMED generators/GenerateJImage.java
+javac -d modules/classes modules/src/module-info.java
+mkdir -p modules/classes/rjvm
+cp rjvm/SimpleMain.class rjvm/SimpleMain\$Generator.class modules/classes/rjvm/
+rm -f modules/rjvm.sample.jmod
+jmod create --class-path modules/classes modules/rjvm.sample.jmod
+rm -r modules/classes
+# Classes outside of the class path, stored as resources with the .bytes extension, that
+# ClassLoaders defines through its own class loader
+javac -source 7 -target 7 -cp . -d plugins plugins/src/rjvm/plugins/*.java
+for class_file in plugins/rjvm/plugins/*.class; do mv "$class_file" "${class_file%.class}.bytes"; done
+# Classes that Vm::verify checks: some of them miss a dependency, one has a class file version
+# from the future, and one is truncated
+javac -source 6 -target 6 -d verification verification/src/rjvm/verification/*.java
+rm verification/rjvm/verification/Dependency.class
+printf '\000\143' | dd of=verification/rjvm/verification/NewerVersion.class bs=1 seek=6 conv=notrunc 2>/dev/null
+head -c 32 verification/rjvm/verification/Valid.class > verification/rjvm/verification/Truncated.class
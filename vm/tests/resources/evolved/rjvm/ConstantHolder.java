@@ -0,0 +1,12 @@
+package rjvm;
+
+public class ConstantHolder {
+    public static final int INT = 100;
+    public static final long LONG = 1L << 40;
+    public static final double DOUBLE = 2.5;
+    public static final boolean BOOLEAN = true;
+    public static final char CHAR = 'x';
+    public static final String STRING = "constant";
+    // Not a compile-time constant, thus set by the static initializer
+    public static final int COMPUTED = INT + Integer.parseInt("1");
+}
@@ -0,0 +1,5 @@
+package rjvm;
+
+public abstract class EvolvingBase {
+    public abstract int compute();
+}
@@ -0,0 +1,8 @@
+package rjvm;
+
+// A newer version of rjvm/EvolvingLibrary.java, which has dropped some of its members
+public class EvolvingLibrary {
+    public static int keptMethod() {
+        return 3;
+    }
+}
@@ -0,0 +1,8 @@
+package rjvm.access;
+
+// A newer version of rjvm/access/Hidden.java, which is no longer public
+class Hidden {
+    public static int value() {
+        return 6;
+    }
+}
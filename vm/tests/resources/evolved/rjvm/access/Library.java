@@ -0,0 +1,22 @@
+package rjvm.access;
+
+// A newer version of rjvm/access/Library.java, which has restricted the access to some of its members
+public class Library {
+    private int restrictedField = 1;
+
+    public static int keptMethod() {
+        return 2;
+    }
+
+    private static int restrictedMethod() {
+        return 3;
+    }
+
+    int restrictedInstanceMethod() {
+        return 4;
+    }
+
+    protected int protectedMethod() {
+        return 5;
+    }
+}
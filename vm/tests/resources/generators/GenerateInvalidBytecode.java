@@ -0,0 +1,106 @@
+import java.nio.file.Files;
+import java.nio.file.Paths;
+
+import jdk.internal.org.objectweb.asm.ClassWriter;
+import jdk.internal.org.objectweb.asm.Label;
+import jdk.internal.org.objectweb.asm.MethodVisitor;
+import jdk.internal.org.objectweb.asm.Opcodes;
+
+/**
+ * Replaces the nested classes of rjvm/VerifyErrors with versions whose bytecode is invalid,
+ * which javac would never produce. The stack map frames are written by hand, since ASM
+ * cannot compute them for invalid code.
+ */
+public class GenerateInvalidBytecode implements Opcodes {
+    public static void main(String[] args) throws Exception {
+        // Adds an int and a null
+        ClassWriter cw = newClass("BadStackType");
+        MethodVisitor mv = newRunMethod(cw);
+        mv.visitInsn(ICONST_1);
+        mv.visitInsn(ACONST_NULL);
+        mv.visitInsn(IADD);
+        mv.visitInsn(IRETURN);
+        mv.visitMaxs(2, 0);
+        mv.visitEnd();
+        write(cw, "BadStackType");
+
+        // Adds with a single value on the stack
+        cw = newClass("StackUnderflow");
+        mv = newRunMethod(cw);
+        mv.visitInsn(ICONST_1);
+        mv.visitInsn(IADD);
+        mv.visitInsn(IRETURN);
+        mv.visitMaxs(2, 0);
+        mv.visitEnd();
+        write(cw, "StackUnderflow");
+
+        // Jumps to an instruction without a stack map frame
+        cw = newClass("MissingBranchFrame");
+        mv = newRunMethod(cw);
+        Label target = new Label();
+        mv.visitInsn(ICONST_0);
+        mv.visitJumpInsn(IFEQ, target);
+        mv.visitInsn(ICONST_1);
+        mv.visitInsn(IRETURN);
+        mv.visitLabel(target);
+        mv.visitInsn(ICONST_2);
+        mv.visitInsn(IRETURN);
+        mv.visitMaxs(1, 0);
+        mv.visitEnd();
+        write(cw, "MissingBranchFrame");
+
+        // Reaches a frame declaring an int local with a float in it
+        cw = newClass("InconsistentLocals");
+        mv = newRunMethod(cw);
+        Label join = new Label();
+        mv.visitInsn(ICONST_0);
+        mv.visitVarInsn(ISTORE, 0);
+        mv.visitVarInsn(ILOAD, 0);
+        mv.visitJumpInsn(IFEQ, join);
+        mv.visitInsn(FCONST_0);
+        mv.visitVarInsn(FSTORE, 0);
+        mv.visitLabel(join);
+        mv.visitFrame(F_FULL, 1, new Object[] {INTEGER}, 0, new Object[0]);
+        mv.visitVarInsn(ILOAD, 0);
+        mv.visitInsn(IRETURN);
+        mv.visitMaxs(1, 1);
+        mv.visitEnd();
+        write(cw, "InconsistentLocals");
+
+        // The constructor does not invoke the one of the superclass
+        cw = newClass("UninitializedReturn");
+        mv = cw.visitMethod(0, "<init>", "()V", null, null);
+        mv.visitCode();
+        mv.visitInsn(RETURN);
+        mv.visitMaxs(0, 1);
+        mv.visitEnd();
+        mv = newRunMethod(cw);
+        mv.visitTypeInsn(NEW, "rjvm/VerifyErrors$UninitializedReturn");
+        mv.visitInsn(DUP);
+        mv.visitMethodInsn(INVOKESPECIAL, "rjvm/VerifyErrors$UninitializedReturn", "<init>", "()V", false);
+        mv.visitInsn(POP);
+        mv.visitInsn(ICONST_0);
+        mv.visitInsn(IRETURN);
+        mv.visitMaxs(2, 0);
+        mv.visitEnd();
+        write(cw, "UninitializedReturn");
+    }
+
+    // Class files before version 50 have no stack maps, so they are not checked
+    private static ClassWriter newClass(String name) {
+        ClassWriter cw = new ClassWriter(0);
+        cw.visit(V1_7, ACC_SUPER, "rjvm/VerifyErrors$" + name, null, "java/lang/Object", null);
+        return cw;
+    }
+
+    private static MethodVisitor newRunMethod(ClassWriter cw) {
+        MethodVisitor mv = cw.visitMethod(ACC_STATIC, "run", "()I", null, null);
+        mv.visitCode();
+        return mv;
+    }
+
+    private static void write(ClassWriter cw, String name) throws Exception {
+        cw.visitEnd();
+        Files.write(Paths.get("rjvm/VerifyErrors$" + name + ".class"), cw.toByteArray());
+    }
+}
@@ -0,0 +1,67 @@
+import java.io.ByteArrayOutputStream;
+import java.nio.ByteBuffer;
+import java.nio.file.Files;
+import java.nio.file.Paths;
+import java.util.zip.Deflater;
+
+import jdk.tools.jlink.internal.BasicImageWriter;
+
+/**
+ * Writes modules/modules, a jimage containing the module rjvm.sample with rjvm/SimpleMain and
+ * its nested class, the latter compressed like jlink --compress=2 does.
+ */
+public class GenerateJImage {
+    private static final int COMPRESSED_RESOURCE_MAGIC = 0xCAFEFAFA;
+
+    public static void main(String[] args) throws Exception {
+        BasicImageWriter writer = new BasicImageWriter();
+        ByteArrayOutputStream content = new ByteArrayOutputStream();
+
+        byte[] simpleMain = Files.readAllBytes(Paths.get("rjvm/SimpleMain.class"));
+        writer.addLocation(
+                "/rjvm.sample/rjvm/SimpleMain.class", content.size(), 0, simpleMain.length);
+        content.write(simpleMain);
+
+        byte[] generator = Files.readAllBytes(Paths.get("rjvm/SimpleMain$Generator.class"));
+        byte[] compressed = compress(writer, generator);
+        writer.addLocation(
+                "/rjvm.sample/rjvm/SimpleMain$Generator.class",
+                content.size(),
+                compressed.length,
+                generator.length);
+        content.write(compressed);
+
+        // Real images also list the modules of each package, which the vm does not need
+        writer.addLocation("/packages/rjvm/rjvm.sample", content.size(), 0, 0);
+
+        ByteArrayOutputStream image = new ByteArrayOutputStream();
+        image.write(writer.getBytes());
+        image.write(content.toByteArray());
+        Files.write(Paths.get("modules/modules"), image.toByteArray());
+    }
+
+    private static byte[] compress(BasicImageWriter writer, byte[] bytes) {
+        Deflater deflater = new Deflater();
+        deflater.setInput(bytes);
+        deflater.finish();
+        ByteArrayOutputStream deflated = new ByteArrayOutputStream();
+        byte[] buffer = new byte[1024];
+        while (!deflater.finished()) {
+            deflated.write(buffer, 0, deflater.deflate(buffer));
+        }
+        byte[] payload = deflated.toByteArray();
+
+        ByteBuffer header = ByteBuffer.allocate(29).order(writer.getByteOrder());
+        header.putInt(COMPRESSED_RESOURCE_MAGIC);
+        header.putLong(payload.length);
+        header.putLong(bytes.length);
+        header.putInt(writer.addString("zip"));
+        header.putInt(0);
+        header.put((byte) 1);
+
+        ByteBuffer resource = ByteBuffer.allocate(29 + payload.length);
+        resource.put(header.array());
+        resource.put(payload);
+        return resource.array();
+    }
+}
@@ -0,0 +1,71 @@
+import java.nio.file.Files;
+import java.nio.file.Paths;
+
+import jdk.internal.org.objectweb.asm.ClassWriter;
+import jdk.internal.org.objectweb.asm.Label;
+import jdk.internal.org.objectweb.asm.MethodVisitor;
+import jdk.internal.org.objectweb.asm.Opcodes;
+
+/**
+ * Generates rjvm/Subroutines.class, which uses jsr and ret. Since javac has not emitted them in
+ * a long time, we need to assemble the class ourselves, with the copy of ASM included in the JDK.
+ */
+public class GenerateSubroutines implements Opcodes {
+    public static void main(String[] args) throws Exception {
+        // jsr and ret are not allowed in class files with version 51 or later
+        ClassWriter cw = new ClassWriter(ClassWriter.COMPUTE_MAXS);
+        cw.visit(V1_5, ACC_PUBLIC | ACC_SUPER, "rjvm/Subroutines", null, "java/lang/Object", null);
+
+        MethodVisitor mv = cw.visitMethod(ACC_PUBLIC | ACC_STATIC, "main", "([Ljava/lang/String;)V", null, null);
+        mv.visitCode();
+        mv.visitInsn(ICONST_5);
+        mv.visitMethodInsn(INVOKESTATIC, "rjvm/Subroutines", "twiceThenAddTwenty", "(I)I", false);
+        mv.visitMethodInsn(INVOKESTATIC, "rjvm/Subroutines", "tempPrint", "(I)V", false);
+        mv.visitIntInsn(BIPUSH, 41);
+        mv.visitMethodInsn(INVOKESTATIC, "rjvm/Subroutines", "incrementWithWideLocals", "(I)I", false);
+        mv.visitMethodInsn(INVOKESTATIC, "rjvm/Subroutines", "tempPrint", "(I)V", false);
+        mv.visitInsn(RETURN);
+        mv.visitMaxs(0, 0);
+        mv.visitEnd();
+
+        // int result = n * 2; then invokes twice a subroutine that adds 10 to result
+        mv = cw.visitMethod(ACC_STATIC, "twiceThenAddTwenty", "(I)I", null, null);
+        mv.visitCode();
+        Label addTen = new Label();
+        mv.visitVarInsn(ILOAD, 0);
+        mv.visitInsn(ICONST_2);
+        mv.visitInsn(IMUL);
+        mv.visitVarInsn(ISTORE, 1);
+        mv.visitJumpInsn(JSR, addTen);
+        mv.visitJumpInsn(JSR, addTen);
+        mv.visitVarInsn(ILOAD, 1);
+        mv.visitInsn(IRETURN);
+        mv.visitLabel(addTen);
+        mv.visitVarInsn(ASTORE, 2);
+        mv.visitIincInsn(1, 10);
+        mv.visitVarInsn(RET, 2);
+        mv.visitMaxs(0, 0);
+        mv.visitEnd();
+
+        // Stores the return address in a local with an index larger than 255, which requires
+        // the wide forms of astore and ret
+        mv = cw.visitMethod(ACC_STATIC, "incrementWithWideLocals", "(I)I", null, null);
+        mv.visitCode();
+        Label increment = new Label();
+        mv.visitJumpInsn(JSR, increment);
+        mv.visitVarInsn(ILOAD, 0);
+        mv.visitInsn(IRETURN);
+        mv.visitLabel(increment);
+        mv.visitVarInsn(ASTORE, 300);
+        mv.visitIincInsn(0, 1);
+        mv.visitVarInsn(RET, 300);
+        mv.visitMaxs(0, 0);
+        mv.visitEnd();
+
+        mv = cw.visitMethod(ACC_PRIVATE | ACC_STATIC | ACC_NATIVE, "tempPrint", "(I)V", null, null);
+        mv.visitEnd();
+
+        cw.visitEnd();
+        Files.write(Paths.get("rjvm/Subroutines.class"), cw.toByteArray());
+    }
+}
@@ -0,0 +1,44 @@
+import java.nio.file.Files;
+import java.nio.file.Paths;
+
+import jdk.internal.org.objectweb.asm.ClassWriter;
+import jdk.internal.org.objectweb.asm.MethodVisitor;
+import jdk.internal.org.objectweb.asm.Opcodes;
+
+/**
+ * Replaces rjvm/SuperCalls$Modern and rjvm/SuperCalls$Legacy with versions whose super call
+ * refers to the method of their grandparent, with and without ACC_SUPER respectively.
+ */
+public class GenerateSuperCalls implements Opcodes {
+    public static void main(String[] args) throws Exception {
+        generate("rjvm/SuperCalls$Modern", ACC_SUPER);
+        generate("rjvm/SuperCalls$Legacy", 0);
+    }
+
+    private static void generate(String name, int access) throws Exception {
+        String parent = "rjvm/SuperCalls$Parent";
+        ClassWriter cw = new ClassWriter(0);
+        cw.visit(V1_7, access, name, null, parent, null);
+
+        MethodVisitor mv = cw.visitMethod(0, "<init>", "()V", null, null);
+        mv.visitCode();
+        mv.visitVarInsn(ALOAD, 0);
+        mv.visitMethodInsn(INVOKESPECIAL, parent, "<init>", "()V", false);
+        mv.visitInsn(RETURN);
+        mv.visitMaxs(1, 1);
+        mv.visitEnd();
+
+        mv = cw.visitMethod(0, "value", "()I", null, null);
+        mv.visitCode();
+        mv.visitIntInsn(BIPUSH, 100);
+        mv.visitVarInsn(ALOAD, 0);
+        mv.visitMethodInsn(INVOKESPECIAL, "rjvm/SuperCalls$GrandParent", "value", "()I", false);
+        mv.visitInsn(IADD);
+        mv.visitInsn(IRETURN);
+        mv.visitMaxs(2, 1);
+        mv.visitEnd();
+
+        cw.visitEnd();
+        Files.write(Paths.get(name + ".class"), cw.toByteArray());
+    }
+}
@@ -0,0 +1,45 @@
+import java.nio.file.Files;
+import java.nio.file.Paths;
+
+import jdk.internal.org.objectweb.asm.ClassWriter;
+import jdk.internal.org.objectweb.asm.MethodVisitor;
+import jdk.internal.org.objectweb.asm.Opcodes;
+
+/**
+ * Replaces rjvm/Synchronization$Unbalanced with a version whose methods exit monitors
+ * they do not hold, which javac would never produce.
+ */
+public class GenerateUnbalancedMonitors implements Opcodes {
+    public static void main(String[] args) throws Exception {
+        String name = "rjvm/Synchronization$Unbalanced";
+        ClassWriter cw = new ClassWriter(0);
+        cw.visit(V1_7, ACC_SUPER, name, null, "java/lang/Object", null);
+
+        MethodVisitor mv = cw.visitMethod(0, "<init>", "()V", null, null);
+        mv.visitCode();
+        mv.visitVarInsn(ALOAD, 0);
+        mv.visitMethodInsn(INVOKESPECIAL, "java/lang/Object", "<init>", "()V", false);
+        mv.visitInsn(RETURN);
+        mv.visitMaxs(1, 1);
+        mv.visitEnd();
+
+        mv = cw.visitMethod(ACC_STATIC, "exitWithoutEnter", "(Ljava/lang/Object;)V", null, null);
+        mv.visitCode();
+        mv.visitVarInsn(ALOAD, 0);
+        mv.visitInsn(MONITOREXIT);
+        mv.visitInsn(RETURN);
+        mv.visitMaxs(1, 1);
+        mv.visitEnd();
+
+        mv = cw.visitMethod(ACC_SYNCHRONIZED, "exitOwnMonitor", "()V", null, null);
+        mv.visitCode();
+        mv.visitVarInsn(ALOAD, 0);
+        mv.visitInsn(MONITOREXIT);
+        mv.visitInsn(RETURN);
+        mv.visitMaxs(1, 1);
+        mv.visitEnd();
+
+        cw.visitEnd();
+        Files.write(Paths.get(name + ".class"), cw.toByteArray());
+    }
+}
@@ -0,0 +1,11 @@
+package rjvm.jars;
+
+import rjvm.jars.lib.Greeter;
+
+public class App {
+    public static void main(String[] args) {
+        tempPrint(Greeter.greet("rjvm"));
+    }
+
+    private static native void tempPrint(String value);
+}
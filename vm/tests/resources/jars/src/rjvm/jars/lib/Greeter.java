@@ -0,0 +1,7 @@
+package rjvm.jars.lib;
+
+public class Greeter {
+    public static String greet(String name) {
+        return "hello, " + name;
+    }
+}
@@ -0,0 +1,47 @@
+package java.util;
+
+/**
+ * An ArrayList as it could be implemented by a minimal class library: it has no fields, and its
+ * methods are meant to be replaced by the vm's intrinsics.
+ */
+public class ArrayList<E> extends AbstractList<E> {
+    public ArrayList() {
+        throw new UnsupportedOperationException("should be intrinsified");
+    }
+
+    public ArrayList(int initialCapacity) {
+        throw new UnsupportedOperationException("should be intrinsified");
+    }
+
+    public int size() {
+        throw new UnsupportedOperationException("should be intrinsified");
+    }
+
+    public boolean isEmpty() {
+        throw new UnsupportedOperationException("should be intrinsified");
+    }
+
+    public E get(int index) {
+        throw new UnsupportedOperationException("should be intrinsified");
+    }
+
+    public E set(int index, E element) {
+        throw new UnsupportedOperationException("should be intrinsified");
+    }
+
+    public boolean add(E element) {
+        throw new UnsupportedOperationException("should be intrinsified");
+    }
+
+    public void add(int index, E element) {
+        throw new UnsupportedOperationException("should be intrinsified");
+    }
+
+    public E remove(int index) {
+        throw new UnsupportedOperationException("should be intrinsified");
+    }
+
+    public void clear() {
+        throw new UnsupportedOperationException("should be intrinsified");
+    }
+}
@@ -0,0 +1,47 @@
+package java.util;
+
+/**
+ * A HashMap as it could be implemented by a minimal class library: it has no fields, and its
+ * methods are meant to be replaced by the vm's intrinsics.
+ */
+public class HashMap<K, V> extends AbstractMap<K, V> {
+    public HashMap() {
+        throw new UnsupportedOperationException("should be intrinsified");
+    }
+
+    public HashMap(int initialCapacity) {
+        throw new UnsupportedOperationException("should be intrinsified");
+    }
+
+    public int size() {
+        throw new UnsupportedOperationException("should be intrinsified");
+    }
+
+    public boolean isEmpty() {
+        throw new UnsupportedOperationException("should be intrinsified");
+    }
+
+    public V get(Object key) {
+        throw new UnsupportedOperationException("should be intrinsified");
+    }
+
+    public boolean containsKey(Object key) {
+        throw new UnsupportedOperationException("should be intrinsified");
+    }
+
+    public V put(K key, V value) {
+        throw new UnsupportedOperationException("should be intrinsified");
+    }
+
+    public V remove(Object key) {
+        throw new UnsupportedOperationException("should be intrinsified");
+    }
+
+    public void clear() {
+        throw new UnsupportedOperationException("should be intrinsified");
+    }
+
+    public Set<Map.Entry<K, V>> entrySet() {
+        throw new UnsupportedOperationException("not supported by the intrinsics");
+    }
+}
@@ -0,0 +1,14 @@
+package rjvm.plugins;
+
+import rjvm.ClassLoaders;
+
+// Not in the class path: defined by ClassLoaders.ResourceClassLoader
+public class Plugin implements ClassLoaders.Greeter {
+    static {
+        ClassLoaders.register(new Plugin());
+    }
+
+    public String greet(String name) {
+        return PluginHelper.decorate("hello " + name);
+    }
+}
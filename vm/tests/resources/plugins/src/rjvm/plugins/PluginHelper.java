@@ -0,0 +1,8 @@
+package rjvm.plugins;
+
+// Loaded through the loader of Plugin, when Plugin first uses it
+public class PluginHelper {
+    public static String decorate(String text) {
+        return "[" + text + "]";
+    }
+}
@@ -0,0 +1,32 @@
+package rjvm;
+
+// A new version of rjvm/Redefinition that cannot replace the loaded one, since it adds a field
+public class Redefinition {
+    private static int calls;
+    private int added;
+
+    public static int compute(int x) {
+        calls++;
+        return x + 1;
+    }
+
+    public int describe() {
+        return 1;
+    }
+
+    static class Child extends Redefinition {}
+
+    public static int callCompute() {
+        return compute(1);
+    }
+
+    public static int describeChild() {
+        return new Child().describe();
+    }
+
+    public static int calls() {
+        return calls;
+    }
+
+    private static native void tempPrint(int value);
+}
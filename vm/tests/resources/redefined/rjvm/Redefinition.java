@@ -0,0 +1,35 @@
+package rjvm;
+
+// A new version of rjvm/Redefinition, which differs only in the code of its methods
+public class Redefinition {
+    private static int calls;
+
+    static {
+        tempPrint(-2);
+    }
+
+    public static int compute(int x) {
+        calls++;
+        return x * 10;
+    }
+
+    public int describe() {
+        return "redefined".length();
+    }
+
+    static class Child extends Redefinition {}
+
+    public static int callCompute() {
+        return compute(1);
+    }
+
+    public static int describeChild() {
+        return new Child().describe();
+    }
+
+    public static int calls() {
+        return calls;
+    }
+
+    private static native void tempPrint(int value);
+}
@@ -0,0 +1,29 @@
+package rjvm;
+
+// EvolvingBase is replaced after compilation by an abstract version, whose compute method
+// is abstract too
+public class AbstractErrors {
+    public static void main(String[] args) {
+        try {
+            new EvolvingBase();
+        } catch (InstantiationError e) {
+            tempPrint(e.getMessage());
+        }
+        try {
+            new Subclass().compute();
+        } catch (AbstractMethodError e) {
+            tempPrint(e.getMessage());
+        }
+        tempPrint(new Subclass().other());
+    }
+
+    static class Subclass extends EvolvingBase {
+        int other() {
+            return 2;
+        }
+    }
+
+    private static native void tempPrint(int value);
+
+    private static native void tempPrint(String value);
+}
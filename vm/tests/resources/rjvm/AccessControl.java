@@ -0,0 +1,63 @@
+package rjvm;
+
+import rjvm.access.Hidden;
+import rjvm.access.Library;
+
+// The classes of the rjvm.access package are replaced after compilation by versions that
+// restrict the access to some of their members, like a library that has evolved
+public class AccessControl {
+    public static void main(String[] args) {
+        // Nestmates can access each other's private members
+        Nested nested = new Nested();
+        tempPrint(nested.secret);
+        tempPrint(Nested.hidden());
+
+        tempPrint(Library.keptMethod());
+        try {
+            tempPrint(Library.restrictedMethod());
+        } catch (IllegalAccessError e) {
+            tempPrint(e.getMessage());
+        }
+        Library library = new Library();
+        try {
+            tempPrint(library.restrictedField);
+        } catch (IllegalAccessError e) {
+            tempPrint(e.getMessage());
+        }
+        try {
+            tempPrint(library.restrictedInstanceMethod());
+        } catch (IllegalAccessError e) {
+            tempPrint(e.getMessage());
+        }
+        try {
+            tempPrint(library.protectedMethod());
+        } catch (IllegalAccessError e) {
+            tempPrint(e.getMessage());
+        }
+        // Protected members are accessible from the subclasses
+        tempPrint(new Subclass().callProtectedMethod());
+        try {
+            tempPrint(Hidden.value());
+        } catch (IllegalAccessError e) {
+            tempPrint(e.getMessage());
+        }
+    }
+
+    static class Nested {
+        private int secret = 7;
+
+        private static int hidden() {
+            return 8;
+        }
+    }
+
+    static class Subclass extends Library {
+        int callProtectedMethod() {
+            return protectedMethod();
+        }
+    }
+
+    private static native void tempPrint(int value);
+
+    private static native void tempPrint(String value);
+}
@@ -0,0 +1,92 @@
+package rjvm;
+
+public class ArrayCopy {
+    public static void main(String[] args) {
+        // Overlapping ranges inside the same array, in both directions
+        int[] ints = new int[]{1, 2, 3, 4, 5};
+        System.arraycopy(ints, 0, ints, 1, 4);
+        printAll(ints);
+        System.arraycopy(ints, 2, ints, 0, 3);
+        printAll(ints);
+
+        String[] strings = new String[]{"a", "b", "c"};
+        System.arraycopy(strings, 0, strings, 1, 2);
+        tempPrint(strings[0] == strings[1]);
+        tempPrint(strings[2] == "b");
+
+        char[] chars = "hello".toCharArray();
+        System.arraycopy(chars, 1, chars, 0, 4);
+        tempPrint(new String(chars).equals("elloo"));
+
+        long[] longs = new long[]{1L, 2L};
+        System.arraycopy(new long[]{7L}, 0, longs, 1, 1);
+        tempPrint(longs[1] == 7L);
+
+        // Copying zero elements at the end of the array is allowed
+        System.arraycopy(ints, 5, ints, 5, 0);
+
+        checkOutOfBounds(ints, -1, ints, 0, 1);
+        checkOutOfBounds(ints, 0, ints, -1, 1);
+        checkOutOfBounds(ints, 0, ints, 0, -1);
+        checkOutOfBounds(ints, 3, ints, 0, 3);
+        checkOutOfBounds(ints, 0, new int[2], 0, 3);
+        // Nothing was copied by the failed calls
+        printAll(ints);
+
+        try {
+            System.arraycopy("not an array", 0, ints, 0, 1);
+        } catch (ArrayStoreException e) {
+            tempPrint(e.getMessage().equals("arraycopy: source type java.lang.String is not an array"));
+        }
+        try {
+            System.arraycopy(ints, 0, new Object(), 0, 1);
+        } catch (ArrayStoreException e) {
+            tempPrint(e.getMessage().equals("arraycopy: destination type java.lang.Object is not an array"));
+        }
+        try {
+            System.arraycopy(ints, 0, new Object[5], 0, 1);
+        } catch (ArrayStoreException e) {
+            tempPrint(30);
+        }
+        try {
+            // Type errors are reported before the bounds errors
+            System.arraycopy(ints, 0, new long[1], 0, 100);
+        } catch (ArrayStoreException e) {
+            tempPrint(31);
+        }
+        try {
+            System.arraycopy(ints, 0, null, 0, 1);
+        } catch (NullPointerException e) {
+            tempPrint(32);
+        }
+
+        // The elements before the first incompatible one are copied
+        Object[] mixed = new Object[]{"x", "y", new Object(), "z"};
+        String[] destination = new String[4];
+        try {
+            System.arraycopy(mixed, 0, destination, 0, 4);
+        } catch (ArrayStoreException e) {
+            tempPrint(destination[1] == "y");
+            tempPrint(destination[2] == null);
+        }
+    }
+
+    private static void checkOutOfBounds(int[] src, int srcPos, int[] dest, int destPos, int length) {
+        try {
+            System.arraycopy(src, srcPos, dest, destPos, length);
+            tempPrint(0);
+        } catch (ArrayIndexOutOfBoundsException e) {
+            tempPrint(20);
+        }
+    }
+
+    private static void printAll(int[] array) {
+        for (int value : array) {
+            tempPrint(value);
+        }
+    }
+
+    private static native void tempPrint(int value);
+
+    private static native void tempPrint(boolean value);
+}
@@ -0,0 +1,39 @@
+package rjvm;
+
+public class ArrayStore {
+    public static void main(String[] args) {
+        Object[] strings = new String[2];
+        try {
+            strings[0] = new Integer(1);
+        } catch (ArrayStoreException e) {
+            tempPrint(e.getMessage());
+        }
+
+        // Storing a compatible value, or null, is fine
+        strings[0] = "a string";
+        strings[1] = null;
+        tempPrint((String) strings[0]);
+
+        try {
+            System.arraycopy(new int[1], 0, new long[1], 0, 1);
+        } catch (ArrayStoreException e) {
+            tempPrint(e.getMessage());
+        }
+
+        Object[] mixed = new Object[]{"first", new Integer(2), "third"};
+        String[] destination = new String[3];
+        try {
+            System.arraycopy(mixed, 0, destination, 0, 3);
+        } catch (ArrayStoreException e) {
+            tempPrint(e.getMessage());
+        }
+        // The elements before the mismatched one have been copied
+        tempPrint(destination[0] + " " + destination[2]);
+
+        Object[] objects = new Object[2];
+        System.arraycopy(new String[]{"x", "y"}, 0, objects, 0, 2);
+        tempPrint((String) objects[1]);
+    }
+
+    private static native void tempPrint(String value);
+}
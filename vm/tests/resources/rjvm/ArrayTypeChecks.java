@@ -0,0 +1,45 @@
+package rjvm;
+
+import java.io.Serializable;
+
+public class ArrayTypeChecks {
+    public static void main(String[] args) {
+        Object ints = new int[1];
+        tempPrint(ints instanceof int[]);
+        tempPrint(ints instanceof long[]);
+        tempPrint(ints instanceof Object[]);
+        tempPrint(ints instanceof Cloneable);
+
+        Object matrix = new int[2][2];
+        tempPrint(matrix instanceof int[][]);
+        tempPrint(matrix instanceof int[]);
+        tempPrint(matrix instanceof int[][][]);
+        tempPrint(matrix instanceof Object[]);
+        tempPrint(matrix instanceof Serializable[]);
+        tempPrint(matrix instanceof long[][]);
+
+        Object strings = new String[1][1][1];
+        tempPrint(strings instanceof String[][][]);
+        tempPrint(strings instanceof Object[][][]);
+        tempPrint(strings instanceof Comparable[][][]);
+        tempPrint(strings instanceof Object[][]);
+        tempPrint(strings instanceof Integer[][][]);
+        tempPrint(strings instanceof String[][]);
+
+        Object objects = new Object[] {new int[0]};
+        tempPrint(objects instanceof int[][]);
+
+        try {
+            int[][] cast = (int[][]) matrix;
+            tempPrint(cast.length);
+            long[][] wrong = (long[][]) matrix;
+            tempPrint(wrong.length);
+        } catch (ClassCastException e) {
+            tempPrint(-1);
+        }
+    }
+
+    private static native void tempPrint(int value);
+
+    private static native void tempPrint(boolean value);
+}
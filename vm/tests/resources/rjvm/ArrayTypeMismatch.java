@@ -0,0 +1,13 @@
+package rjvm;
+
+public class ArrayTypeMismatch {
+    public static void main(String[] args) {
+        // Arrays are covariant, so this compiles but the store must fail at runtime
+        Object[] objects = new String[1];
+        store(objects, new Object());
+    }
+
+    private static void store(Object[] array, Object value) {
+        array[0] = value;
+    }
+}
@@ -0,0 +1,163 @@
+package rjvm;
+
+import org.objectweb.asm.ClassReader;
+import org.objectweb.asm.ClassVisitor;
+import org.objectweb.asm.ClassWriter;
+import org.objectweb.asm.FieldVisitor;
+import org.objectweb.asm.Label;
+import org.objectweb.asm.MethodVisitor;
+import org.objectweb.asm.Opcodes;
+
+/** Generates a class with the ASM library, then reads it back and copies it */
+public class AsmLibrary {
+    public static void main(String[] args) {
+        byte[] bytes = generate();
+        tempPrint(bytes.length);
+
+        ClassReader reader = new ClassReader(bytes);
+        reader.accept(new Printer(), 0);
+
+        ClassWriter copier = new ClassWriter(reader, 0);
+        reader.accept(copier, 0);
+        tempPrint(java.util.Arrays.equals(bytes, copier.toByteArray()) ? "copy ok" : "copy differs");
+
+        bytes[7] = 99;
+        try {
+            new ClassReader(bytes);
+            tempPrint("read an unsupported version");
+        } catch (IllegalArgumentException e) {
+            tempPrint(e.getMessage());
+        }
+    }
+
+    private static byte[] generate() {
+        ClassWriter writer = new ClassWriter(ClassWriter.COMPUTE_MAXS);
+        writer.visit(Opcodes.V1_8, Opcodes.ACC_PUBLIC | Opcodes.ACC_SUPER, "rjvm/Shapes", null,
+                "java/lang/Object", new String[] {"java/lang/Runnable"});
+        writer.visitSource("Shapes.java", null);
+
+        FieldVisitor field = writer.visitField(Opcodes.ACC_PRIVATE | Opcodes.ACC_FINAL, "sides", "I", null, null);
+        field.visitEnd();
+        writer.visitField(Opcodes.ACC_PUBLIC | Opcodes.ACC_STATIC | Opcodes.ACC_FINAL, "NAME", "Ljava/lang/String;",
+                null, "shapes").visitEnd();
+
+        MethodVisitor constructor = writer.visitMethod(Opcodes.ACC_PUBLIC, "<init>", "(I)V", null, null);
+        constructor.visitCode();
+        constructor.visitVarInsn(Opcodes.ALOAD, 0);
+        constructor.visitMethodInsn(Opcodes.INVOKESPECIAL, "java/lang/Object", "<init>", "()V", false);
+        constructor.visitVarInsn(Opcodes.ALOAD, 0);
+        constructor.visitVarInsn(Opcodes.ILOAD, 1);
+        constructor.visitFieldInsn(Opcodes.PUTFIELD, "rjvm/Shapes", "sides", "I");
+        constructor.visitInsn(Opcodes.RETURN);
+        constructor.visitMaxs(0, 0);
+        constructor.visitEnd();
+
+        MethodVisitor name = writer.visitMethod(Opcodes.ACC_PUBLIC, "name", "()Ljava/lang/String;", null, null);
+        name.visitCode();
+        Label triangle = new Label();
+        Label square = new Label();
+        Label other = new Label();
+        name.visitVarInsn(Opcodes.ALOAD, 0);
+        name.visitFieldInsn(Opcodes.GETFIELD, "rjvm/Shapes", "sides", "I");
+        name.visitTableSwitchInsn(3, 4, other, triangle, square);
+        name.visitLabel(triangle);
+        name.visitLdcInsn("triangle");
+        name.visitInsn(Opcodes.ARETURN);
+        name.visitLabel(square);
+        name.visitLdcInsn("square");
+        name.visitInsn(Opcodes.ARETURN);
+        name.visitLabel(other);
+        name.visitLdcInsn("polygon");
+        name.visitInsn(Opcodes.ARETURN);
+        name.visitMaxs(0, 0);
+        name.visitEnd();
+
+        MethodVisitor run = writer.visitMethod(Opcodes.ACC_PUBLIC, "run", "()V", null, null);
+        run.visitCode();
+        run.visitInsn(Opcodes.RETURN);
+        run.visitMaxs(0, 0);
+        run.visitEnd();
+
+        writer.visitEnd();
+        return writer.toByteArray();
+    }
+
+    /** Prints the structure of the visited class */
+    private static class Printer extends ClassVisitor {
+        Printer() {
+            super(Opcodes.ASM9);
+        }
+
+        @Override
+        public void visit(int version, int access, String name, String signature, String superName,
+                String[] interfaces) {
+            tempPrint("class " + name + " extends " + superName + " implements " + interfaces[0] + ", version "
+                    + version);
+        }
+
+        @Override
+        public void visitSource(String source, String debug) {
+            tempPrint("source " + source);
+        }
+
+        @Override
+        public FieldVisitor visitField(int access, String name, String descriptor, String signature,
+                Object value) {
+            tempPrint("field " + name + " " + descriptor + (value != null ? " = " + value : ""));
+            return null;
+        }
+
+        @Override
+        public MethodVisitor visitMethod(int access, final String name, String descriptor, String signature,
+                String[] exceptions) {
+            return new MethodVisitor(Opcodes.ASM9) {
+                private final StringBuilder code = new StringBuilder();
+
+                @Override
+                public void visitInsn(int opcode) {
+                    code.append(' ').append(opcode);
+                }
+
+                @Override
+                public void visitVarInsn(int opcode, int index) {
+                    code.append(' ').append(opcode).append(':').append(index);
+                }
+
+                @Override
+                public void visitFieldInsn(int opcode, String owner, String field, String descriptor) {
+                    code.append(' ').append(opcode).append(':').append(field);
+                }
+
+                @Override
+                public void visitMethodInsn(int opcode, String owner, String method, String descriptor,
+                        boolean isInterface) {
+                    code.append(' ').append(opcode).append(':').append(owner).append('.').append(method);
+                }
+
+                @Override
+                public void visitTableSwitchInsn(int min, int max, Label dflt, Label... labels) {
+                    code.append(" tableswitch:").append(min).append('-').append(max);
+                }
+
+                @Override
+                public void visitLdcInsn(Object value) {
+                    code.append(" ldc:").append(value);
+                }
+
+                @Override
+                public void visitMaxs(int maxStack, int maxLocals) {
+                    code.append(" maxs:").append(maxStack).append('/').append(maxLocals);
+                }
+
+                @Override
+                public void visitEnd() {
+                    tempPrint("method " + name + code);
+                }
+            };
+        }
+    }
+
+    private static native void tempPrint(String value);
+
+    private static native void tempPrint(int value);
+}
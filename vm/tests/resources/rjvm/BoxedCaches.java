@@ -0,0 +1,30 @@
+package rjvm;
+
+public class BoxedCaches {
+    public static void main(String[] args) {
+        tempPrint(Integer.valueOf(127) == Integer.valueOf(127));
+        tempPrint(Integer.valueOf(-128) == Integer.valueOf(-128));
+        tempPrint(Integer.valueOf(128) == Integer.valueOf(128));
+        tempPrint(Integer.valueOf(1000).intValue());
+
+        Integer autoboxed = 42;
+        Integer other = 42;
+        tempPrint(autoboxed == other);
+
+        tempPrint(Boolean.valueOf(true) == Boolean.TRUE);
+        tempPrint(Boolean.valueOf(false) == Boolean.FALSE);
+
+        tempPrint(Character.valueOf('a') == Character.valueOf('a'));
+        tempPrint(Character.valueOf((char) 200) == Character.valueOf((char) 200));
+
+        tempPrint(Short.valueOf((short) 5) == Short.valueOf((short) 5));
+        tempPrint(Byte.valueOf((byte) -1) == Byte.valueOf((byte) -1));
+        tempPrint(Long.valueOf(7L) == Long.valueOf(7L));
+        tempPrint(Long.valueOf(500L) == Long.valueOf(500L));
+        tempPrint(Long.valueOf(500L).longValue() == 500L);
+    }
+
+    private static native void tempPrint(int value);
+
+    private static native void tempPrint(boolean value);
+}
@@ -0,0 +1,28 @@
+package rjvm;
+
+public class ClassInitializationChain {
+    public static void main(String[] args) {
+        tempPrint(First.VALUE);
+    }
+
+    static class First {
+        static final int VALUE = Second.VALUE + 1;
+    }
+
+    static class Second {
+        static int VALUE = compute();
+
+        static int compute() {
+            return Third.VALUE;
+        }
+    }
+
+    static class Third {
+        // First is still being initialized here
+        static int VALUE = First.VALUE + missing();
+
+        private static native int missing();
+    }
+
+    private static native void tempPrint(int value);
+}
@@ -0,0 +1,35 @@
+package rjvm;
+
+public class ClassLoaderDelegation {
+    public static void main(String[] args) throws Exception {
+        ClassLoader system = ClassLoader.getSystemClassLoader();
+
+        // The JDK classes are defined by the bootstrap loader, the application ones by the
+        // system class loader, whose parent is the bootstrap loader
+        tempPrint(String.class.getClassLoader() == null);
+        tempPrint(ClassLoaderDelegation.class.getClassLoader() == system);
+        tempPrint(system.getParent() == null);
+
+        // The system class loader delegates to the bootstrap one
+        tempPrint(system.loadClass("java.lang.String") == String.class);
+        tempPrint(system.loadClass("rjvm.ClassLoaderDelegation") == ClassLoaderDelegation.class);
+        tempPrint(Class.forName("rjvm.ClassLoaderDelegation") == ClassLoaderDelegation.class);
+        tempPrint(Class.forName("java.lang.Integer", false, null) == Integer.class);
+
+        // But the bootstrap loader cannot see the application classes
+        try {
+            Class.forName("rjvm.ClassLoaderDelegation", false, null);
+        } catch (ClassNotFoundException e) {
+            tempPrint(e.getMessage());
+        }
+        try {
+            system.loadClass("rjvm.Missing");
+        } catch (ClassNotFoundException e) {
+            tempPrint(e.getMessage());
+        }
+    }
+
+    private static native void tempPrint(boolean value);
+
+    private static native void tempPrint(String value);
+}
@@ -0,0 +1,95 @@
+package rjvm;
+
+import java.io.ByteArrayOutputStream;
+import java.io.IOException;
+import java.io.InputStream;
+
+public class ClassLoaders {
+    public interface Greeter {
+        String greet(String name);
+    }
+
+    private static Greeter registered;
+
+    public static void register(Greeter greeter) {
+        registered = greeter;
+    }
+
+    // Defines the classes stored, outside of the class path, in the plugins directory
+    static class ResourceClassLoader extends ClassLoader {
+        int definedClasses;
+
+        ResourceClassLoader(ClassLoader parent) {
+            super(parent);
+        }
+
+        @Override
+        protected Class<?> findClass(String name) throws ClassNotFoundException {
+            String path = "/plugins/" + name.replace('.', '/') + ".bytes";
+            InputStream input = ClassLoaders.class.getResourceAsStream(path);
+            if (input == null) {
+                throw new ClassNotFoundException(name);
+            }
+            try {
+                byte[] bytes = readAll(input);
+                definedClasses++;
+                return defineClass(name, bytes, 0, bytes.length);
+            } catch (IOException e) {
+                throw new ClassNotFoundException(name, e);
+            }
+        }
+
+        Class<?> defineAgain(String name) throws ClassNotFoundException {
+            return findClass(name);
+        }
+    }
+
+    public static void main(String[] args) throws Exception {
+        ResourceClassLoader loader = new ResourceClassLoader(ClassLoaders.class.getClassLoader());
+        tempPrint(ClassLoaders.class.getClassLoader() == ClassLoader.getSystemClassLoader());
+
+        Class<?> pluginClass = loader.loadClass("rjvm.plugins.Plugin");
+        tempPrint(pluginClass.getClassLoader() == loader);
+        tempPrint(loader.loadClass("rjvm.plugins.Plugin") == pluginClass);
+        tempPrint(registered == null);
+
+        // Initializing the plugin registers it; the helper it uses is loaded by the same loader
+        tempPrint(Class.forName("rjvm.plugins.Plugin", true, loader) == pluginClass);
+        tempPrint(registered.greet("rjvm"));
+        tempPrint(loader.definedClasses);
+        Class<?> helperClass = Class.forName("rjvm.plugins.PluginHelper", false, loader);
+        tempPrint(helperClass.getClassLoader() == loader);
+
+        // The classes of the class path are delegated to the parent
+        tempPrint(loader.loadClass("java.lang.String") == String.class);
+        tempPrint(loader.loadClass("rjvm.ClassLoaders") == ClassLoaders.class);
+
+        try {
+            loader.loadClass("rjvm.plugins.Missing");
+        } catch (ClassNotFoundException e) {
+            tempPrint(e.getMessage());
+        }
+        try {
+            loader.defineAgain("rjvm.plugins.Plugin");
+        } catch (LinkageError e) {
+            tempPrint(e.getMessage());
+        }
+    }
+
+    private static byte[] readAll(InputStream input) throws IOException {
+        ByteArrayOutputStream output = new ByteArrayOutputStream();
+        byte[] buffer = new byte[1024];
+        int read;
+        while ((read = input.read(buffer)) != -1) {
+            output.write(buffer, 0, read);
+        }
+        input.close();
+        return output.toByteArray();
+    }
+
+    private static native void tempPrint(boolean value);
+
+    private static native void tempPrint(int value);
+
+    private static native void tempPrint(String value);
+}
@@ -0,0 +1,22 @@
+package rjvm;
+
+public class ClassObjects {
+    public static void main(String[] args) {
+        Class<?> first = ClassObjects.class;
+        Class<?> second = ClassObjects.class;
+        tempPrint(first == second);
+        tempPrint(new ClassObjects().getClass() == first);
+        tempPrint(first != Object.class);
+        tempPrint(new int[1].getClass() == int[].class);
+        Object longArray = new long[1];
+        tempPrint(new int[1].getClass() != longArray.getClass());
+        tempPrint(new String[0].getClass() == String[].class);
+
+        // The same instance survives the garbage collection
+        int hashCode = first.hashCode();
+        System.gc();
+        tempPrint(ClassObjects.class.hashCode() == hashCode);
+    }
+
+    private static native void tempPrint(boolean value);
+}
@@ -0,0 +1,54 @@
+package rjvm;
+
+public class Cloning {
+    public static void main(String[] args) throws Exception {
+        Point point = new Point(1, 2, new int[] {3});
+        Point copy = point.copy();
+        tempPrint(copy != point);
+        tempPrint(copy.x);
+        tempPrint(copy.y);
+        // The copy is shallow
+        tempPrint(copy.data == point.data);
+        copy.x = 10;
+        tempPrint(point.x);
+        tempPrint(copy.hashCode() != point.hashCode());
+
+        try {
+            new NotCloneable().copy();
+            tempPrint(0);
+        } catch (CloneNotSupportedException e) {
+            tempPrint(1);
+        }
+
+        int[] array = new int[] {4, 5};
+        int[] arrayCopy = array.clone();
+        tempPrint(arrayCopy != array);
+        tempPrint(arrayCopy[1]);
+    }
+
+    private static class Point implements Cloneable {
+        int x;
+        final int y;
+        final int[] data;
+
+        Point(int x, int y, int[] data) {
+            this.x = x;
+            this.y = y;
+            this.data = data;
+        }
+
+        Point copy() throws CloneNotSupportedException {
+            return (Point) clone();
+        }
+    }
+
+    private static class NotCloneable {
+        Object copy() throws CloneNotSupportedException {
+            return clone();
+        }
+    }
+
+    private static native void tempPrint(int value);
+
+    private static native void tempPrint(boolean value);
+}
@@ -0,0 +1,75 @@
+package rjvm;
+
+import java.lang.ref.WeakReference;
+import java.util.ArrayList;
+import java.util.HashMap;
+import java.util.List;
+import java.util.Map;
+
+public class CollectionIntrinsics {
+    public static void main(String[] args) {
+        lists();
+        maps();
+        garbageCollection();
+    }
+
+    private static void lists() {
+        List<String> list = new ArrayList<String>();
+        list.add("a");
+        list.add("b");
+        list.add(1, "c");
+        tempPrint(list.size());
+        for (String s : list) {
+            tempPrint(s);
+        }
+
+        tempPrint(list.remove(0));
+        tempPrint(list.set(1, "d"));
+        tempPrint(list.get(1));
+        try {
+            list.get(5);
+        } catch (IndexOutOfBoundsException e) {
+            tempPrint(e.getMessage());
+        }
+
+        list.clear();
+        tempPrint(list.isEmpty());
+    }
+
+    private static void maps() {
+        Map<String, String> map = new HashMap<String, String>();
+        map.put("one", "1");
+        map.put("two", "2");
+        tempPrint(map.put("one", "11"));
+        map.put(null, "0");
+        tempPrint(map.size());
+
+        tempPrint(map.get(new String("one")));
+        tempPrint(map.get(null));
+        tempPrint(map.containsKey("three"));
+        tempPrint(map.remove("two"));
+        tempPrint(map.size());
+        tempPrint(map.get("two") == null);
+    }
+
+    private static void garbageCollection() {
+        List<int[]> kept = new ArrayList<int[]>();
+        kept.add(new int[]{42});
+
+        // The list is only reachable from itself
+        List<Object> cycle = new ArrayList<Object>();
+        cycle.add(cycle);
+        WeakReference<Object> reference = new WeakReference<Object>(cycle);
+        cycle = null;
+
+        System.gc();
+        tempPrint(kept.get(0)[0]);
+        tempPrint(reference.get() == null);
+    }
+
+    private static native void tempPrint(int value);
+
+    private static native void tempPrint(boolean value);
+
+    private static native void tempPrint(String value);
+}
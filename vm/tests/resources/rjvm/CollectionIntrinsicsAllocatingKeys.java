@@ -0,0 +1,49 @@
+package rjvm;
+
+import java.util.HashMap;
+import java.util.Map;
+
+public class CollectionIntrinsicsAllocatingKeys {
+    public static void main(String[] args) {
+        Map<Key, Key> map = new HashMap<Key, Key>();
+        for (int i = 0; i < 200; ++i) {
+            map.put(new Key(i), new Key(-i));
+        }
+
+        int found = 0;
+        for (int i = 0; i < 200; ++i) {
+            Key value = map.get(new Key(i));
+            if (value != null && value.id == -i) {
+                ++found;
+            }
+        }
+        tempPrint(found);
+        tempPrint(map.size());
+    }
+
+    // Allocates in hashCode and equals, so that the map intrinsics run the gc while they look
+    // up a key
+    private static class Key {
+        private final int id;
+
+        Key(int id) {
+            this.id = id;
+        }
+
+        @Override
+        public int hashCode() {
+            int[] garbage = new int[1000];
+            garbage[0] = id % 10;
+            return garbage[0];
+        }
+
+        @Override
+        public boolean equals(Object other) {
+            int[] garbage = new int[1000];
+            garbage[0] = id;
+            return other instanceof Key && ((Key) other).id == garbage[0];
+        }
+    }
+
+    private static native void tempPrint(int value);
+}
@@ -0,0 +1,15 @@
+package rjvm;
+
+/**
+ * Replaced by evolved/rjvm/ConstantHolder.java, whose fields are compile-time constants, so
+ * that ConstantValues reads them with getstatic rather than having them inlined by javac
+ */
+public class ConstantHolder {
+    public static int INT;
+    public static long LONG;
+    public static double DOUBLE;
+    public static boolean BOOLEAN;
+    public static char CHAR;
+    public static String STRING;
+    public static int COMPUTED;
+}
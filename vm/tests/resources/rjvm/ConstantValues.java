@@ -0,0 +1,24 @@
+package rjvm;
+
+public class ConstantValues {
+    public static void main(String[] args) {
+        tempPrint(ConstantHolder.INT);
+        tempPrint(ConstantHolder.LONG);
+        tempPrint(ConstantHolder.DOUBLE);
+        tempPrint(ConstantHolder.BOOLEAN);
+        tempPrint(ConstantHolder.CHAR);
+        tempPrint(ConstantHolder.STRING.length());
+        tempPrint(ConstantHolder.STRING.equals("constant"));
+        tempPrint(ConstantHolder.COMPUTED);
+    }
+
+    private static native void tempPrint(int value);
+
+    private static native void tempPrint(long value);
+
+    private static native void tempPrint(double value);
+
+    private static native void tempPrint(boolean value);
+
+    private static native void tempPrint(char value);
+}
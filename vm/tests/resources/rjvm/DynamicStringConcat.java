@@ -0,0 +1,14 @@
+package rjvm;
+
+public class DynamicStringConcat {
+    public static void main(String[] args) {
+        int value = twice(21);
+        tempPrint("value=" + value);
+    }
+
+    private static int twice(int value) {
+        return value * 2;
+    }
+
+    private static native void tempPrint(String value);
+}
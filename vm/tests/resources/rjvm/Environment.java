@@ -0,0 +1,22 @@
+package rjvm;
+
+public class Environment {
+    public static void main(String[] args) {
+        tempPrint(System.getenv("RJVM_HOST_VARIABLE"));
+        tempPrint(System.getenv("RJVM_EMBEDDER_VARIABLE"));
+        tempPrint(System.getenv("RJVM_OVERRIDDEN_VARIABLE"));
+        tempPrint(System.getenv("RJVM_MISSING_VARIABLE") == null);
+
+        try {
+            System.getenv(null);
+        } catch (NullPointerException e) {
+            tempPrint(true);
+        }
+    }
+
+    private static native void tempPrint(String value);
+
+    private static native void tempPrint(int value);
+
+    private static native void tempPrint(boolean value);
+}
@@ -0,0 +1,9 @@
+package rjvm;
+
+// The version of the class that AbstractErrors is compiled against. After compilation, it is
+// replaced by the one in evolved/rjvm, which has become abstract.
+public class EvolvingBase {
+    public int compute() {
+        return 1;
+    }
+}
@@ -0,0 +1,18 @@
+package rjvm;
+
+// The version of the library that LinkageErrors is compiled against. After compilation, it is
+// replaced by the one in evolved/rjvm, which no longer has the members marked as removed.
+public class EvolvingLibrary {
+    public int removedField = 1;
+    public static int removedStaticField = 2;
+
+    public static int keptMethod() {
+        return 3;
+    }
+
+    public static void removedMethod() {
+    }
+
+    public void removedInstanceMethod(int value) {
+    }
+}
@@ -0,0 +1,14 @@
+package rjvm;
+
+public class FailingMain {
+    public static void main(String[] args) {
+        if (args.length == 0) {
+            return;
+        }
+        try {
+            Integer.parseInt(args[0]);
+        } catch (NumberFormatException e) {
+            throw new IllegalStateException("invalid argument " + args[0], e);
+        }
+    }
+}
@@ -0,0 +1,84 @@
+package rjvm;
+
+import java.io.File;
+import java.io.FileDescriptor;
+import java.io.FileInputStream;
+import java.io.FileNotFoundException;
+import java.io.FileOutputStream;
+import java.io.IOException;
+import java.io.RandomAccessFile;
+
+public class FileIo {
+    public static void main(String[] args) throws IOException {
+        String directory = System.getProperty("rjvm.test.directory");
+        File file = new File(directory, "rjvm-file-io.txt");
+
+        FileOutputStream out = new FileOutputStream(file);
+        out.write(bytes("hello"));
+        out.write(' ');
+        out.close();
+        out = new FileOutputStream(file, true);
+        out.write(bytes("--world--"), 2, 5);
+        out.close();
+
+        FileInputStream in = new FileInputStream(file);
+        tempPrint(in.available());
+        tempPrint(in.read());
+        byte[] buffer = new byte[16];
+        tempPrint(in.read(buffer, 0, 4));
+        tempPrint(in.skip(1));
+        int read = in.read(buffer);
+        tempPrint(new String(buffer, 0, 0, read));
+        tempPrint(in.read());
+        in.close();
+        try {
+            in.read();
+        } catch (IOException e) {
+            tempPrint(e.getMessage());
+        }
+
+        RandomAccessFile randomAccess = new RandomAccessFile(file, "rw");
+        tempPrint(randomAccess.length());
+        randomAccess.seek(6);
+        randomAccess.write('W');
+        tempPrint(randomAccess.getFilePointer());
+        randomAccess.setLength(5);
+        tempPrint(randomAccess.length());
+        tempPrint(randomAccess.getFilePointer());
+        randomAccess.writeInt(0x01020304);
+        randomAccess.seek(0);
+        tempPrint(randomAccess.read());
+        randomAccess.seek(5);
+        tempPrint(randomAccess.readInt());
+        tempPrint(randomAccess.read());
+        try {
+            randomAccess.seek(-1);
+        } catch (IOException e) {
+            tempPrint(e.getMessage());
+        }
+        randomAccess.close();
+
+        try {
+            new FileInputStream(new File(directory, "rjvm-missing-file.txt"));
+        } catch (FileNotFoundException e) {
+            tempPrint(e.getMessage());
+        }
+
+        FileOutputStream stdout = new FileOutputStream(FileDescriptor.out);
+        stdout.write(bytes("written to the descriptor\n"));
+    }
+
+    private static byte[] bytes(String text) {
+        byte[] bytes = new byte[text.length()];
+        for (int i = 0; i < bytes.length; ++i) {
+            bytes[i] = (byte) text.charAt(i);
+        }
+        return bytes;
+    }
+
+    private static native void tempPrint(String value);
+
+    private static native void tempPrint(int value);
+
+    private static native void tempPrint(long value);
+}
@@ -0,0 +1,75 @@
+package rjvm;
+
+import java.io.File;
+import java.io.FileOutputStream;
+import java.io.IOException;
+
+public class FileOperations {
+    public static void main(String[] args) throws IOException {
+        File directory = new File(System.getProperty("rjvm.test.directory"));
+        File nested = new File(directory, "nested");
+        tempPrint(nested.exists());
+        tempPrint(nested.mkdir());
+        tempPrint(nested.mkdir());
+        tempPrint(nested.isDirectory());
+        tempPrint(nested.isFile());
+
+        File file = new File(nested, "data.bin");
+        tempPrint(file.createNewFile());
+        tempPrint(file.createNewFile());
+        FileOutputStream out = new FileOutputStream(file);
+        out.write(new byte[42]);
+        out.close();
+        tempPrint(file.length());
+        tempPrint(file.isFile());
+        tempPrint(file.canRead());
+        tempPrint(file.canWrite());
+        tempPrint(file.setLastModified(1234567000L));
+        tempPrint(file.lastModified());
+
+        File hidden = new File(nested, ".hidden");
+        tempPrint(hidden.createNewFile());
+        tempPrint(hidden.isHidden());
+        String[] names = nested.list();
+        sort(names);
+        tempPrint(names.length);
+        for (String name : names) {
+            tempPrint(name);
+        }
+
+        File renamed = new File(nested, "renamed.bin");
+        tempPrint(file.renameTo(renamed));
+        tempPrint(file.exists());
+        tempPrint(renamed.length());
+        tempPrint(new File(nested, "./renamed.bin").getCanonicalPath());
+
+        File missing = new File(nested, "missing");
+        tempPrint(missing.length());
+        tempPrint(missing.lastModified());
+        tempPrint(missing.list() == null);
+
+        tempPrint(nested.delete());
+        tempPrint(renamed.delete());
+        tempPrint(hidden.delete());
+        tempPrint(nested.delete());
+        tempPrint(nested.exists());
+    }
+
+    private static void sort(String[] strings) {
+        for (int i = 1; i < strings.length; ++i) {
+            for (int j = i; j > 0 && strings[j - 1].compareTo(strings[j]) > 0; --j) {
+                String swapped = strings[j];
+                strings[j] = strings[j - 1];
+                strings[j - 1] = swapped;
+            }
+        }
+    }
+
+    private static native void tempPrint(String value);
+
+    private static native void tempPrint(int value);
+
+    private static native void tempPrint(long value);
+
+    private static native void tempPrint(boolean value);
+}
@@ -0,0 +1,104 @@
+package rjvm;
+
+public class Finalization {
+    static int finalized;
+    static Resurrecting resurrected;
+
+    public static void main(String[] args) {
+        // Only the unreachable objects are finalized
+        Finalizable kept = new Finalizable();
+        for (int i = 0; i < 3; i++) {
+            new Finalizable();
+        }
+        System.gc();
+        System.runFinalization();
+        tempPrint(finalized);
+
+        // The finalizers also run without being requested, after the gc
+        new Finalizable();
+        System.gc();
+        tempPrint(finalized);
+
+        // The finalizer runs only once, even if it makes the object reachable again
+        new Resurrecting();
+        System.gc();
+        System.runFinalization();
+        tempPrint(resurrected != null);
+        resurrected = null;
+        System.gc();
+        System.runFinalization();
+        tempPrint(Resurrecting.count);
+
+        // The objects referred by an unreachable object survive until its finalizer has run
+        new Holder(new int[] {42});
+        System.gc();
+        System.runFinalization();
+        tempPrint(Holder.seen);
+
+        // Exceptions thrown by the finalizers are ignored
+        new Throwing();
+        System.gc();
+        System.runFinalization();
+        tempPrint(Throwing.count);
+
+        // Objects with an empty finalizer do not need to be finalized, but must still be collected
+        for (int i = 0; i < 3; i++) {
+            new EmptyFinalizer();
+        }
+        System.gc();
+
+        tempPrint(kept != null);
+        tempPrint(finalized);
+    }
+
+    private static class Finalizable {
+        @Override
+        protected void finalize() {
+            finalized++;
+        }
+    }
+
+    private static class Resurrecting {
+        static int count;
+
+        @Override
+        protected void finalize() {
+            count++;
+            resurrected = this;
+        }
+    }
+
+    private static class Holder {
+        static int seen;
+        private final int[] values;
+
+        Holder(int[] values) {
+            this.values = values;
+        }
+
+        @Override
+        protected void finalize() {
+            seen = values[0];
+        }
+    }
+
+    private static class Throwing {
+        static int count;
+
+        @Override
+        protected void finalize() {
+            count++;
+            throw new IllegalStateException("finalizer failed");
+        }
+    }
+
+    private static class EmptyFinalizer {
+        @Override
+        protected void finalize() {
+        }
+    }
+
+    private static native void tempPrint(int value);
+
+    private static native void tempPrint(boolean value);
+}
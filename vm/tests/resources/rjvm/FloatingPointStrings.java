@@ -0,0 +1,24 @@
+package rjvm;
+
+public class FloatingPointStrings {
+    public static void main(String[] args) {
+        tempPrint(Double.toString(0.1));
+        tempPrint(String.valueOf(100.0));
+        tempPrint("x=" + (0.1 + 0.2));
+        tempPrint("" + 1e-5 + ", " + 1.5e10);
+        tempPrint(Double.toString(Double.MIN_VALUE));
+        tempPrint(Double.toString(-0.0));
+        tempPrint(Double.toString(Double.NaN) + Double.NEGATIVE_INFINITY);
+
+        tempPrint(Float.toString(0.1f));
+        tempPrint("f=" + 1.1f);
+        tempPrint(String.valueOf(Float.MAX_VALUE));
+
+        StringBuilder buffer = new StringBuilder();
+        buffer.append(2.5).append(',').append(3.25f);
+        tempPrint(buffer.toString());
+        tempPrint(Double.valueOf(1234567.0).toString());
+    }
+
+    private static native void tempPrint(String value);
+}
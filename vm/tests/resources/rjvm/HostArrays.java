@@ -0,0 +1,79 @@
+package rjvm;
+
+public class HostArrays {
+    public static int sumBooleans(boolean[] values) {
+        int count = 0;
+        for (boolean value : values) {
+            if (value) {
+                ++count;
+            }
+        }
+        return count;
+    }
+
+    public static int sumBytes(byte[] values) {
+        int sum = 0;
+        for (byte value : values) {
+            sum += value;
+        }
+        return sum;
+    }
+
+    public static int sumChars(char[] values) {
+        int sum = 0;
+        for (char value : values) {
+            sum += value;
+        }
+        return sum;
+    }
+
+    public static int sumShorts(short[] values) {
+        int sum = 0;
+        for (short value : values) {
+            sum += value;
+        }
+        return sum;
+    }
+
+    public static int sumInts(int[] values) {
+        int sum = 0;
+        for (int value : values) {
+            sum += value;
+        }
+        return sum;
+    }
+
+    public static long sumLongs(long[] values) {
+        long sum = 0;
+        for (long value : values) {
+            sum += value;
+        }
+        return sum;
+    }
+
+    public static float sumFloats(float[] values) {
+        float sum = 0;
+        for (float value : values) {
+            sum += value;
+        }
+        return sum;
+    }
+
+    public static double sumDoubles(double[] values) {
+        double sum = 0;
+        for (double value : values) {
+            sum += value;
+        }
+        return sum;
+    }
+
+    public static int countNulls(Object[] values) {
+        int count = 0;
+        for (Object value : values) {
+            if (value == null) {
+                ++count;
+            }
+        }
+        return count;
+    }
+}
@@ -0,0 +1,40 @@
+package rjvm;
+
+public class IdentityHash {
+    public static void main(String[] args) {
+        Object first = new Object();
+        Object second = new Object();
+        int firstHash = first.hashCode();
+        tempPrint(firstHash != 0);
+        tempPrint(firstHash == System.identityHashCode(first));
+        tempPrint(firstHash != second.hashCode());
+
+        // The hash code does not change when the gc moves the object
+        Object[] objects = new Object[] {first, second};
+        int secondHash = second.hashCode();
+        for (int i = 0; i < 100; i++) {
+            new Object().hashCode();
+        }
+        System.gc();
+        tempPrint(firstHash == first.hashCode());
+        tempPrint(secondHash == objects[1].hashCode());
+        tempPrint(objects[0] == first);
+
+        // Classes that override hashCode still have an identity one
+        Key key = new Key();
+        tempPrint(key.hashCode());
+        tempPrint(System.identityHashCode(key) == System.identityHashCode(key));
+        tempPrint(System.identityHashCode(null));
+    }
+
+    private static class Key {
+        @Override
+        public int hashCode() {
+            return 42;
+        }
+    }
+
+    private static native void tempPrint(int value);
+
+    private static native void tempPrint(boolean value);
+}
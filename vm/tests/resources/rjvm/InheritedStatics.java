@@ -0,0 +1,52 @@
+package rjvm;
+
+public class InheritedStatics {
+    static class Base {
+        static int counter;
+
+        static int next() {
+            return ++counter;
+        }
+    }
+
+    static class Derived extends Base {
+    }
+
+    static class Hiding extends Base {
+        static int counter = 100;
+    }
+
+    interface Constants {
+        int[] VALUES = {1, 2, 3};
+    }
+
+    static class WithConstants implements Constants {
+    }
+
+    static class DerivedWithConstants extends WithConstants {
+    }
+
+    public static void main(String[] args) {
+        // Written via the subclass, read via the declaring class, and vice versa
+        Derived.counter = 5;
+        tempPrint(Base.counter);
+        tempPrint(Base.next());
+        tempPrint(Derived.counter);
+        Derived.counter++;
+        tempPrint(Base.counter);
+
+        // A field hiding the one of the superclass is a different field
+        tempPrint(Hiding.counter);
+        Hiding.counter = 200;
+        tempPrint(Base.counter);
+        tempPrint(Hiding.counter);
+
+        // Fields declared by the implemented interfaces
+        tempPrint(WithConstants.VALUES.length);
+        tempPrint(DerivedWithConstants.VALUES == Constants.VALUES);
+    }
+
+    private static native void tempPrint(int value);
+
+    private static native void tempPrint(boolean value);
+}
@@ -0,0 +1,92 @@
+package rjvm;
+
+public class InlineCaches {
+    interface Shape {
+        int area();
+    }
+
+    static class Square implements Shape {
+        private final int side;
+
+        Square(int side) {
+            this.side = side;
+        }
+
+        @Override
+        public int area() {
+            return side * side;
+        }
+    }
+
+    static class Rectangle implements Shape {
+        private final int width;
+        private final int height;
+
+        Rectangle(int width, int height) {
+            this.width = width;
+            this.height = height;
+        }
+
+        @Override
+        public int area() {
+            return width * height;
+        }
+    }
+
+    static class Base {
+        int value() {
+            return 1;
+        }
+    }
+
+    static class Derived extends Base {
+        @Override
+        int value() {
+            return 10;
+        }
+    }
+
+    public static void main(String[] args) {
+        // The same call site, executed with receivers of the same class
+        tempPrint(sumValues(new Base[] {new Base(), new Base(), new Base()}));
+        // The call site is already cached, but the receivers change class at each iteration
+        tempPrint(sumValues(new Base[] {new Derived(), new Base(), new Derived(), new Base()}));
+
+        // Same for an interface call site
+        Shape[] shapes = {new Square(2), new Square(3), new Rectangle(2, 5), new Square(1)};
+        tempPrint(sumAreas(shapes));
+        tempPrint(sumAreas(shapes));
+
+        // Arrays at a call site of a method of Object
+        Object[] objects = {new Base(), new int[1], new Derived()};
+        tempPrint(countClasses(objects));
+    }
+
+    private static int sumValues(Base[] values) {
+        int sum = 0;
+        for (Base value : values) {
+            sum += value.value();
+        }
+        return sum;
+    }
+
+    private static int sumAreas(Shape[] shapes) {
+        int sum = 0;
+        for (Shape shape : shapes) {
+            sum += shape.area();
+        }
+        return sum;
+    }
+
+    private static int countClasses(Object[] objects) {
+        int count = 0;
+        for (Object object : objects) {
+            if (object.getClass() != null) {
+                count++;
+            }
+        }
+        return count;
+    }
+
+    private static native void tempPrint(int value);
+}
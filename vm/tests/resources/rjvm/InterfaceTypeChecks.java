@@ -0,0 +1,43 @@
+package rjvm;
+
+public class InterfaceTypeChecks {
+    interface Named {}
+    interface Labeled extends Named {}
+    static class Base implements Labeled {}
+    static class Derived extends Base {}
+    static class Unrelated {}
+
+    public static void main(String[] args) {
+        // Interfaces implemented by a superclass, and their superinterfaces
+        Object derived = new Derived();
+        tempPrint(derived instanceof Labeled);
+        tempPrint(derived instanceof Named);
+        tempPrint(new Unrelated() instanceof Named);
+
+        // Interfaces of the class library
+        Object string = "hello";
+        tempPrint(string instanceof Comparable);
+        tempPrint(string instanceof CharSequence);
+        tempPrint(string instanceof java.io.Serializable);
+        tempPrint(string instanceof Runnable);
+
+        // Arrays of classes implementing the interfaces
+        Object deriveds = new Derived[1];
+        tempPrint(deriveds instanceof Named[]);
+        tempPrint(deriveds instanceof Labeled[]);
+        Object strings = new String[1][1];
+        tempPrint(strings instanceof Comparable[][]);
+        tempPrint(strings instanceof Runnable[][]);
+
+        Named named = (Named) derived;
+        tempPrint(named == derived);
+        try {
+            Object unrelated = new Unrelated();
+            tempPrint(((Named) unrelated) != null);
+        } catch (ClassCastException e) {
+            tempPrint(false);
+        }
+    }
+
+    private static native void tempPrint(boolean value);
+}
@@ -0,0 +1,85 @@
+package rjvm;
+
+public class InvokeSpecial {
+    public static void main(String[] args) {
+        Leaf leaf = new Leaf();
+        tempPrint(leaf.a());
+        tempPrint(leaf.b());
+        tempPrint(leaf.callSecret());
+        tempPrint(Leaf.staticValue());
+
+        Greeter greeter = new Impl();
+        tempPrint(greeter.greet());
+        tempPrint(new Impl().greet());
+        tempPrint(new LoudImpl().greet());
+    }
+
+    static class Base {
+        static int staticValue() {
+            return 7;
+        }
+
+        int a() {
+            return 1;
+        }
+
+        int b() {
+            return 2;
+        }
+
+        int callSecret() {
+            return secret();
+        }
+
+        private int secret() {
+            return 3;
+        }
+    }
+
+    static class Middle extends Base {
+        // Does not override a(), so super.a() in Leaf must find the version of Base
+        @Override
+        int b() {
+            return 20 + super.b();
+        }
+    }
+
+    static class Leaf extends Middle {
+        @Override
+        int a() {
+            return 100 + super.a();
+        }
+
+        @Override
+        int b() {
+            return 200 + super.b();
+        }
+
+        // Must not be invoked by Base.callSecret
+        private int secret() {
+            return 300;
+        }
+    }
+
+    interface Greeter {
+        default int greet() {
+            return helper() * 2;
+        }
+
+        private int helper() {
+            return 21;
+        }
+    }
+
+    static class Impl implements Greeter {
+    }
+
+    static class LoudImpl implements Greeter {
+        @Override
+        public int greet() {
+            return Greeter.super.greet() + 1000;
+        }
+    }
+
+    private static native void tempPrint(int value);
+}
@@ -0,0 +1,99 @@
+package rjvm;
+
+public class Lambdas {
+    interface IntOperation {
+        int apply(int value);
+    }
+
+    interface Transformer<T, R> {
+        R transform(T value);
+    }
+
+    interface Action {
+        void run();
+    }
+
+    interface LongCombiner {
+        long combine(long a, double b);
+    }
+
+    static class Counter {
+        private int count;
+
+        Counter(int start) {
+            count = start;
+        }
+
+        int increment() {
+            return ++count;
+        }
+
+        int get() {
+            return count;
+        }
+    }
+
+    private final int base;
+
+    private Lambdas(int base) {
+        this.base = base;
+    }
+
+    public static void main(String[] args) {
+        // Capturing a local variable
+        int delta = 3;
+        IntOperation addDelta = value -> value + delta;
+        tempPrint(addDelta.apply(addDelta.apply(1)));
+
+        // Capturing this
+        new Lambdas(10).captureThis();
+
+        // References to static methods
+        IntOperation square = Lambdas::square;
+        tempPrint(square.apply(7));
+
+        // References to methods of a given object, and of the first argument
+        Counter counter = new Counter(41);
+        Action increment = counter::increment;
+        increment.run();
+        Transformer<Counter, String> describe = Lambdas::describe;
+        tempPrint(describe.transform(counter));
+        Transformer<String, String> trim = String::trim;
+        tempPrint(trim.transform("  trimmed  "));
+
+        // References to constructors
+        Transformer<String, StringBuilder> newBuilder = StringBuilder::new;
+        tempPrint(newBuilder.transform("built").append("!").toString());
+
+        // Long and double arguments
+        long captured = 100;
+        LongCombiner combiner = (a, b) -> a + (long) b + captured;
+        tempPrint((int) combiner.combine(20, 3.5));
+
+        // The same call site is linked only once
+        int sum = 0;
+        for (int i = 0; i < 5; ++i) {
+            final int value = i;
+            IntOperation addValue = x -> x + value;
+            sum = addValue.apply(sum);
+        }
+        tempPrint(sum);
+    }
+
+    private void captureThis() {
+        IntOperation addBase = value -> value + base;
+        tempPrint(addBase.apply(5));
+    }
+
+    private static int square(int value) {
+        return value * value;
+    }
+
+    private static String describe(Counter counter) {
+        return "counter=" + counter.get();
+    }
+
+    private static native void tempPrint(int value);
+
+    private static native void tempPrint(String value);
+}
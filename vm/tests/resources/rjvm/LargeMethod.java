@@ -0,0 +1,513 @@
+package rjvm;
+
+public class LargeMethod {
+    public static void main(String[] args) {
+        int sum = 0;
+        // The loop body is larger than 32KB, so javac needs a goto_w to jump back to its start
+        for (int i = 0; i < 3; i++) {
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+            sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i; sum = sum * 31 + i;
+        }
+        tempPrint(sum);
+    }
+
+    private static native void tempPrint(int value);
+}
@@ -0,0 +1,123 @@
+package rjvm;
+
+public class LazyInitialization {
+    static int[] initialized = new int[20];
+    static int count;
+
+    static void initializing(int id) {
+        initialized[count++] = id;
+    }
+
+    static class Parent {
+        static {
+            initializing(1);
+        }
+    }
+
+    static class Child extends Parent {
+        static int value = 5;
+
+        static {
+            initializing(2);
+        }
+    }
+
+    static class OnlyReferenced {
+        static {
+            initializing(99);
+        }
+    }
+
+    static class ViaNew {
+        static {
+            initializing(3);
+        }
+    }
+
+    static class ViaStaticMethod {
+        static {
+            initializing(4);
+        }
+
+        static int get() {
+            return 7;
+        }
+    }
+
+    static class ViaForName {
+        static {
+            initializing(5);
+        }
+    }
+
+    static class NotViaForName {
+        static {
+            initializing(98);
+        }
+    }
+
+    static class CircularA {
+        static int a = CircularB.b + 1;
+    }
+
+    static class CircularB {
+        static int b = CircularA.a + 10;
+    }
+
+    static class Failing {
+        static int value = fail();
+
+        static int fail() {
+            throw new IllegalStateException("boom");
+        }
+    }
+
+    public static void main(String[] args) throws Exception {
+        // Passive uses do not initialize the class
+        OnlyReferenced[] array = new OnlyReferenced[2];
+        Object object = array;
+        tempPrint(object instanceof OnlyReferenced[]);
+        tempPrint(OnlyReferenced.class != null);
+        tempPrint(count);
+
+        // The superclass is initialized first, and only once
+        tempPrint(Child.value);
+        tempPrint(Child.value);
+        new ViaNew();
+        new ViaNew();
+        tempPrint(ViaStaticMethod.get());
+        ClassLoader loader = LazyInitialization.class.getClassLoader();
+        Class.forName("rjvm.LazyInitialization$ViaForName", true, loader);
+        Class.forName("rjvm.LazyInitialization$ViaForName", true, loader);
+        Class.forName("rjvm.LazyInitialization$NotViaForName", false, loader);
+        for (int i = 0; i < count; i++) {
+            tempPrint(initialized[i]);
+        }
+
+        // An initializer using a class being initialized sees its fields before they are set
+        tempPrint(CircularA.a);
+        tempPrint(CircularB.b);
+
+        // A failed initialization makes the class unusable
+        try {
+            tempPrint(Failing.value);
+        } catch (ExceptionInInitializerError e) {
+            tempPrint(e.getCause() instanceof IllegalStateException);
+        }
+        try {
+            tempPrint(Failing.value);
+        } catch (NoClassDefFoundError e) {
+            tempPrint(2);
+        }
+
+        try {
+            Class.forName("rjvm.DoesNotExist", true, null);
+        } catch (ClassNotFoundException e) {
+            tempPrint(3);
+        }
+    }
+
+    private static native void tempPrint(int value);
+
+    private static native void tempPrint(boolean value);
+}
@@ -0,0 +1,51 @@
+package rjvm;
+
+// The classes named Missing* are deleted after compilation, like optional dependencies
+// that are not available at runtime
+public class LazyLinkage {
+    public static void main(String[] args) {
+        Implementation implementation = new Implementation();
+        tempPrint(implementation.value());
+        tempPrint(implementation instanceof Present ? 1 : 0);
+
+        Object object = implementation;
+        tempPrint(object instanceof MissingInterface ? 1 : 0);
+        tempPrint(object instanceof MissingClass ? 1 : 0);
+        MissingClass nothing = (MissingClass) null;
+        tempPrint(nothing == null ? 1 : 0);
+
+        try {
+            throw new IllegalStateException();
+        } catch (MissingException e) {
+            tempPrint(-1);
+        } catch (IllegalStateException e) {
+            tempPrint(2);
+        }
+
+        // Actually needing a missing class still fails
+        MissingClass.touch();
+    }
+
+    interface Present {
+        int value();
+    }
+
+    interface MissingInterface {
+    }
+
+    static class Implementation implements Present, MissingInterface {
+        public int value() {
+            return 42;
+        }
+    }
+
+    static class MissingClass {
+        static void touch() {
+        }
+    }
+
+    static class MissingException extends RuntimeException {
+    }
+
+    private static native void tempPrint(int value);
+}
@@ -0,0 +1,47 @@
+package rjvm;
+
+// EvolvingLibrary is replaced after compilation by a version without some of its members,
+// and MissingClass is deleted, like a dependency that is not available at runtime
+public class LinkageErrors {
+    public static void main(String[] args) {
+        try {
+            EvolvingLibrary.removedMethod();
+        } catch (NoSuchMethodError e) {
+            tempPrint(e.getMessage());
+        }
+        EvolvingLibrary library = new EvolvingLibrary();
+        try {
+            tempPrint(library.removedField);
+        } catch (NoSuchFieldError e) {
+            tempPrint(e.getMessage());
+        }
+        try {
+            EvolvingLibrary.removedStaticField = 4;
+        } catch (NoSuchFieldError e) {
+            tempPrint(e.getMessage());
+        }
+        try {
+            new MissingClass();
+        } catch (NoClassDefFoundError e) {
+            tempPrint(e.getMessage());
+        }
+        try {
+            MissingClass.touch();
+        } catch (LinkageError e) {
+            tempPrint(e.getMessage());
+        }
+        tempPrint(EvolvingLibrary.keptMethod());
+
+        // Nothing catches this one
+        library.removedInstanceMethod(5);
+    }
+
+    static class MissingClass {
+        static void touch() {
+        }
+    }
+
+    private static native void tempPrint(int value);
+
+    private static native void tempPrint(String value);
+}
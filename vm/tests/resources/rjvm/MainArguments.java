@@ -0,0 +1,14 @@
+package rjvm;
+
+public class MainArguments {
+    public static void main(String[] args) {
+        tempPrint(args.length);
+        for (String arg : args) {
+            tempPrint(arg);
+        }
+    }
+
+    private static native void tempPrint(int value);
+
+    private static native void tempPrint(String value);
+}
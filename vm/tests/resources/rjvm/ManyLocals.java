@@ -0,0 +1,43 @@
+package rjvm;
+
+public class ManyLocals {
+    public static void main(String[] args) {
+        // Enough locals to push the following ones past index 255, which requires the
+        // wide form of the load, store and iinc instructions
+        int i0 = 0, i1 = 1, i2 = 2, i3 = 3, i4 = 4, i5 = 5, i6 = 6, i7 = 7, i8 = 8, i9 = 9, i10 = 10, i11 = 11, i12 = 12, i13 = 13, i14 = 14, i15 = 15, i16 = 16, i17 = 17, i18 = 18, i19 = 19;
+        int i20 = 20, i21 = 21, i22 = 22, i23 = 23, i24 = 24, i25 = 25, i26 = 26, i27 = 27, i28 = 28, i29 = 29, i30 = 30, i31 = 31, i32 = 32, i33 = 33, i34 = 34, i35 = 35, i36 = 36, i37 = 37, i38 = 38, i39 = 39;
+        int i40 = 40, i41 = 41, i42 = 42, i43 = 43, i44 = 44, i45 = 45, i46 = 46, i47 = 47, i48 = 48, i49 = 49, i50 = 50, i51 = 51, i52 = 52, i53 = 53, i54 = 54, i55 = 55, i56 = 56, i57 = 57, i58 = 58, i59 = 59;
+        int i60 = 60, i61 = 61, i62 = 62, i63 = 63, i64 = 64, i65 = 65, i66 = 66, i67 = 67, i68 = 68, i69 = 69, i70 = 70, i71 = 71, i72 = 72, i73 = 73, i74 = 74, i75 = 75, i76 = 76, i77 = 77, i78 = 78, i79 = 79;
+        int i80 = 80, i81 = 81, i82 = 82, i83 = 83, i84 = 84, i85 = 85, i86 = 86, i87 = 87, i88 = 88, i89 = 89, i90 = 90, i91 = 91, i92 = 92, i93 = 93, i94 = 94, i95 = 95, i96 = 96, i97 = 97, i98 = 98, i99 = 99;
+        int i100 = 100, i101 = 101, i102 = 102, i103 = 103, i104 = 104, i105 = 105, i106 = 106, i107 = 107, i108 = 108, i109 = 109, i110 = 110, i111 = 111, i112 = 112, i113 = 113, i114 = 114, i115 = 115, i116 = 116, i117 = 117, i118 = 118, i119 = 119;
+        int i120 = 120, i121 = 121, i122 = 122, i123 = 123, i124 = 124, i125 = 125, i126 = 126, i127 = 127, i128 = 128, i129 = 129, i130 = 130, i131 = 131, i132 = 132, i133 = 133, i134 = 134, i135 = 135, i136 = 136, i137 = 137, i138 = 138, i139 = 139;
+        int i140 = 140, i141 = 141, i142 = 142, i143 = 143, i144 = 144, i145 = 145, i146 = 146, i147 = 147, i148 = 148, i149 = 149, i150 = 150, i151 = 151, i152 = 152, i153 = 153, i154 = 154, i155 = 155, i156 = 156, i157 = 157, i158 = 158, i159 = 159;
+        int i160 = 160, i161 = 161, i162 = 162, i163 = 163, i164 = 164, i165 = 165, i166 = 166, i167 = 167, i168 = 168, i169 = 169, i170 = 170, i171 = 171, i172 = 172, i173 = 173, i174 = 174, i175 = 175, i176 = 176, i177 = 177, i178 = 178, i179 = 179;
+        int i180 = 180, i181 = 181, i182 = 182, i183 = 183, i184 = 184, i185 = 185, i186 = 186, i187 = 187, i188 = 188, i189 = 189, i190 = 190, i191 = 191, i192 = 192, i193 = 193, i194 = 194, i195 = 195, i196 = 196, i197 = 197, i198 = 198, i199 = 199;
+        int i200 = 200, i201 = 201, i202 = 202, i203 = 203, i204 = 204, i205 = 205, i206 = 206, i207 = 207, i208 = 208, i209 = 209, i210 = 210, i211 = 211, i212 = 212, i213 = 213, i214 = 214, i215 = 215, i216 = 216, i217 = 217, i218 = 218, i219 = 219;
+        int i220 = 220, i221 = 221, i222 = 222, i223 = 223, i224 = 224, i225 = 225, i226 = 226, i227 = 227, i228 = 228, i229 = 229, i230 = 230, i231 = 231, i232 = 232, i233 = 233, i234 = 234, i235 = 235, i236 = 236, i237 = 237, i238 = 238, i239 = 239;
+        int i240 = 240, i241 = 241, i242 = 242, i243 = 243, i244 = 244, i245 = 245, i246 = 246, i247 = 247, i248 = 248, i249 = 249, i250 = 250, i251 = 251, i252 = 252, i253 = 253, i254 = 254, i255 = 255, i256 = 256, i257 = 257, i258 = 258, i259 = 259;
+        int wideInt = i1 + i259;
+        wideInt += 1000;
+        wideInt++;
+        long wideLong = 1L << 40;
+        float wideFloat = 1.5f;
+        double wideDouble = 2.25;
+        String wideString = "wide";
+        tempPrint(wideInt);
+        tempPrint(wideLong + 1);
+        tempPrint(wideFloat * 2);
+        tempPrint(wideDouble * 2);
+        tempPrint(wideString);
+    }
+
+    private static native void tempPrint(int value);
+
+    private static native void tempPrint(long value);
+
+    private static native void tempPrint(float value);
+
+    private static native void tempPrint(double value);
+
+    private static native void tempPrint(String value);
+}
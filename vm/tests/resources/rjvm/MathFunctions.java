@@ -0,0 +1,49 @@
+package rjvm;
+
+public class MathFunctions {
+    public static void main(String[] args) {
+        tempPrint(Math.sqrt(16.0));
+        tempPrint(StrictMath.sqrt(2.25));
+        tempPrint(Math.pow(2.0, 10.0));
+        tempPrint(Math.sin(0.0));
+        tempPrint(Math.cos(0.0));
+        tempPrint(Math.abs(Math.tan(Math.PI / 4) - 1.0) < 1e-15);
+        tempPrint(Math.abs(Math.atan2(1.0, 1.0) - Math.PI / 4) < 1e-15);
+        tempPrint(Math.exp(0.0));
+        tempPrint(Math.log(Math.E));
+        tempPrint(Math.log10(1000.0));
+        tempPrint(Math.cbrt(-27.0));
+        tempPrint(Math.hypot(3.0, 4.0));
+        tempPrint(Math.IEEEremainder(5.0, 2.0));
+        tempPrint(StrictMath.IEEEremainder(7.0, 2.0));
+        tempPrint(Math.expm1(0.0));
+        tempPrint(Math.log1p(0.0));
+        tempPrint(Math.sinh(0.0) + Math.cosh(0.0) + Math.tanh(0.0));
+        tempPrint(Math.asin(1.0) * 2 == Math.PI);
+        tempPrint(Math.acos(1.0) + Math.atan(0.0));
+
+        tempPrint(Double.isNaN(Math.sqrt(-1.0)));
+        tempPrint(Double.isNaN(Math.pow(1.0, Double.NaN)));
+        tempPrint(Math.log(0.0) == Double.NEGATIVE_INFINITY);
+
+        // Methods implemented in bytecode on top of the natives
+        tempPrint(Math.toDegrees(Math.PI));
+        tempPrint(Math.floor(-1.5));
+        tempPrint(Math.ceil(1.2));
+        tempPrint(Math.round(2.5));
+
+        // Comparisons with NaN are always false, and long shifts use six bits of the distance
+        double nan = Double.NaN;
+        tempPrint(nan < 1.0 || nan > 1.0 || nan == nan);
+        tempPrint(1.0 < 2.0 && 2.0 > 1.0 && 1.0f <= 1.0f && 2.0f >= 3.0f);
+        tempPrint(1L << 40);
+        tempPrint(-1L >>> 60);
+        tempPrint(-(1L << 50) >> 48);
+    }
+
+    private static native void tempPrint(double value);
+
+    private static native void tempPrint(long value);
+
+    private static native void tempPrint(boolean value);
+}
@@ -0,0 +1,61 @@
+package rjvm;
+
+public class MultiDimensionalArrays {
+    public static void main(String[] args) {
+        int[][] matrix = new int[3][4];
+        for (int i = 0; i < 3; ++i) {
+            for (int j = 0; j < 4; ++j) {
+                matrix[i][j] = i * 10 + j;
+            }
+        }
+        tempPrint(matrix.length);
+        tempPrint(matrix[2].length);
+        tempPrint(matrix[2][3]);
+
+        // Only the first two dimensions are allocated
+        long[][][] cube = new long[2][3][];
+        tempPrint(cube[1].length);
+        tempPrint(cube[1][2] == null ? 1 : 0);
+        cube[1][2] = new long[]{7L};
+        tempPrint((int) cube[1][2][0]);
+
+        String[][] jagged = new String[2][];
+        jagged[0] = new String[]{"a"};
+        jagged[1] = new String[]{"b", "c"};
+
+        // Arrays are covariant, and arrays of arrays are arrays of objects
+        Object[] objects = matrix;
+        tempPrint(objects[1] instanceof int[] ? 1 : 0);
+        tempPrint(objects instanceof int[][] ? 1 : 0);
+        tempPrint(objects instanceof long[][] ? 1 : 0);
+        Object[][] objectMatrix = jagged;
+        tempPrint(objectMatrix[1].length);
+        int[] row = (int[]) objects[1];
+        tempPrint(row[1]);
+        objects[0] = new int[]{42};
+        tempPrint(matrix[0][0]);
+
+        int[][] empty = new int[0][5];
+        tempPrint(empty.length);
+
+        // Inner arrays must survive the garbage collection
+        for (int i = 0; i < 2000; ++i) {
+            int[][] garbage = new int[10][100];
+            garbage[9][99] = i;
+        }
+        tempPrint(matrix[2][3]);
+        tempPrint(jagged[0][0]);
+        tempPrint(jagged[1][1]);
+
+        try {
+            int[][] invalid = new int[2][-1];
+            tempPrint(invalid.length);
+        } catch (NegativeArraySizeException e) {
+            tempPrint(e.getMessage());
+        }
+    }
+
+    private static native void tempPrint(int value);
+
+    private static native void tempPrint(String value);
+}
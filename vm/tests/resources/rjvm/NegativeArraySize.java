@@ -0,0 +1,28 @@
+package rjvm;
+
+public class NegativeArraySize {
+    public static void main(String[] args) {
+        try {
+            int[] array = new int[-3];
+            tempPrint("allocated " + array.length);
+        } catch (NegativeArraySizeException e) {
+            tempPrint(e.getMessage());
+        }
+
+        try {
+            Object[] array = new Object[length(-7)];
+            tempPrint("allocated " + array.length);
+        } catch (NegativeArraySizeException e) {
+            tempPrint(e.getMessage());
+        }
+
+        int[] empty = new int[0];
+        tempPrint("allocated " + empty.length);
+    }
+
+    private static int length(int value) {
+        return value;
+    }
+
+    private static native void tempPrint(String value);
+}
@@ -0,0 +1,71 @@
+package rjvm;
+
+public class NullPointerExceptions {
+    private int field;
+
+    public static void main(String[] args) {
+        NullPointerExceptions nothing = null;
+        try {
+            tempPrint(nothing.field);
+        } catch (NullPointerException e) {
+            tempPrint(1);
+        }
+
+        try {
+            nothing.field = 2;
+        } catch (NullPointerException e) {
+            tempPrint(2);
+        }
+
+        try {
+            nothing.method();
+        } catch (NullPointerException e) {
+            tempPrint(3);
+        }
+
+        int[] array = null;
+        try {
+            tempPrint(array[0]);
+        } catch (NullPointerException e) {
+            tempPrint(4);
+        }
+
+        try {
+            tempPrint(array.length);
+        } catch (NullPointerException e) {
+            tempPrint(5);
+        }
+
+        RuntimeException exception = null;
+        try {
+            throw exception;
+        } catch (NullPointerException e) {
+            tempPrint(6);
+        }
+
+        try {
+            System.arraycopy(null, 0, new int[1], 0, 1);
+        } catch (NullPointerException e) {
+            tempPrint(7);
+        }
+
+        try {
+            tempPrint(deepDereference(nothing, 3));
+        } catch (NullPointerException e) {
+            tempPrint(e.getMessage() == null ? 8 : -8);
+        }
+    }
+
+    private int method() {
+        return field;
+    }
+
+    private static int deepDereference(NullPointerExceptions object, int depth) {
+        if (depth == 0) {
+            return object.method();
+        }
+        return deepDereference(object, depth - 1) + 1;
+    }
+
+    private static native void tempPrint(int value);
+}
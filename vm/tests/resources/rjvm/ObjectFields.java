@@ -0,0 +1,15 @@
+package rjvm;
+
+public class ObjectFields {
+    static class Base {
+        static int counter;
+        int id;
+        String name;
+    }
+
+    static class Derived extends Base {
+        long id;
+        int[] values;
+        Base parent;
+    }
+}
@@ -0,0 +1,35 @@
+package rjvm;
+
+public class OutOfMemory {
+    public static void main(String[] args) {
+        try {
+            long[] tooLarge = new long[10000000];
+            tempPrint(tooLarge.length);
+        } catch (OutOfMemoryError e) {
+            tempPrint(e.getMessage());
+        }
+
+        try {
+            fillHeap();
+        } catch (OutOfMemoryError e) {
+            tempPrint("heap exhausted");
+        }
+
+        // The objects retained by fillHeap are now garbage, so their memory can be reused
+        int[] afterwards = new int[100000];
+        tempPrint(afterwards.length);
+    }
+
+    private static void fillHeap() {
+        Object[] head = null;
+        while (true) {
+            Object[] node = new Object[10000];
+            node[0] = head;
+            head = node;
+        }
+    }
+
+    private static native void tempPrint(int value);
+
+    private static native void tempPrint(String value);
+}
@@ -0,0 +1,90 @@
+package rjvm;
+
+public class QuickenedFields {
+    static class Point {
+        int x;
+        long y;
+        String label;
+
+        Point(int x, long y, String label) {
+            this.x = x;
+            this.y = y;
+            this.label = label;
+        }
+    }
+
+    static class ColoredPoint extends Point {
+        int color;
+
+        ColoredPoint(int x, long y, int color) {
+            super(x, y, "colored");
+            this.color = color;
+        }
+    }
+
+    public static void main(String[] args) {
+        Point[] points = {
+            new Point(1, 10, "a"),
+            new Point(2, 20, "b"),
+            new ColoredPoint(3, 30, 7),
+            new Point(4, 40, null),
+            new ColoredPoint(5, 50, 8),
+        };
+
+        // The same instructions, executed on receivers of different classes
+        tempPrint(sumX(points));
+        tempPrint(sumY(points));
+        tempPrint(countLabels(points));
+
+        // Writes through the quickened instructions
+        for (int i = 0; i < 3; i++) {
+            moveRight(points);
+        }
+        tempPrint(sumX(points));
+        tempPrint(((ColoredPoint) points[2]).color);
+
+        // A null receiver, after the instruction has been quickened
+        try {
+            sumX(new Point[] {new Point(1, 1, "c"), null});
+            tempPrint(0);
+        } catch (NullPointerException e) {
+            tempPrint(1);
+        }
+    }
+
+    private static int sumX(Point[] points) {
+        int sum = 0;
+        for (Point point : points) {
+            sum += point.x;
+        }
+        return sum;
+    }
+
+    private static long sumY(Point[] points) {
+        long sum = 0;
+        for (Point point : points) {
+            sum += point.y;
+        }
+        return sum;
+    }
+
+    private static int countLabels(Point[] points) {
+        int count = 0;
+        for (Point point : points) {
+            if (point.label != null) {
+                count++;
+            }
+        }
+        return count;
+    }
+
+    private static void moveRight(Point[] points) {
+        for (Point point : points) {
+            point.x = point.x + 1;
+        }
+    }
+
+    private static native void tempPrint(int value);
+
+    private static native void tempPrint(long value);
+}
@@ -0,0 +1,34 @@
+package rjvm;
+
+public class Redefinition {
+    private static int calls;
+
+    static {
+        tempPrint(-1);
+    }
+
+    public static int compute(int x) {
+        calls++;
+        return x + 1;
+    }
+
+    public int describe() {
+        return 1;
+    }
+
+    static class Child extends Redefinition {}
+
+    public static int callCompute() {
+        return compute(1);
+    }
+
+    public static int describeChild() {
+        return new Child().describe();
+    }
+
+    public static int calls() {
+        return calls;
+    }
+
+    private static native void tempPrint(int value);
+}
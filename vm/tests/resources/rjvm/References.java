@@ -0,0 +1,88 @@
+package rjvm;
+
+import java.lang.ref.PhantomReference;
+import java.lang.ref.Reference;
+import java.lang.ref.ReferenceQueue;
+import java.lang.ref.SoftReference;
+import java.lang.ref.WeakReference;
+
+public class References {
+    public static void main(String[] args) throws InterruptedException {
+        weakReferences();
+        removeWaitsForTheGc();
+        softReferences();
+        phantomReferences();
+    }
+
+    private static void weakReferences() {
+        ReferenceQueue<Object> queue = new ReferenceQueue<>();
+        Object strong = new Object();
+        WeakReference<Object> kept = new WeakReference<>(strong, queue);
+        WeakReference<Object> cleared = new WeakReference<>(new Object(), queue);
+        WeakReference<Object> withoutQueue = new WeakReference<>(new Object());
+        System.gc();
+        tempPrint(kept.get() == strong);
+        tempPrint(cleared.get() == null);
+        tempPrint(withoutQueue.get() == null);
+        tempPrint(queue.poll() == cleared);
+        tempPrint(queue.poll() == null);
+    }
+
+    private static void removeWaitsForTheGc() throws InterruptedException {
+        ReferenceQueue<Object> queue = new ReferenceQueue<>();
+        WeakReference<Object> reference = new WeakReference<>(new Object(), queue);
+        Remover remover = new Remover(queue);
+        Thread thread = new Thread(remover);
+        thread.start();
+        while (!remover.started) {
+            Thread.yield();
+        }
+        System.gc();
+        thread.join();
+        tempPrint(remover.removed == reference);
+    }
+
+    private static void softReferences() {
+        // Soft references survive the gc, as long as there is enough memory
+        SoftReference<int[]> soft = new SoftReference<>(new int[400000]);
+        System.gc();
+        tempPrint(soft.get() != null);
+
+        // This does not fit in memory together with the softly reachable array
+        int[] strong = new int[300000];
+        tempPrint(soft.get() == null);
+        tempPrint(strong.length);
+    }
+
+    private static void phantomReferences() throws InterruptedException {
+        ReferenceQueue<Object> queue = new ReferenceQueue<>();
+        PhantomReference<Object> phantom = new PhantomReference<>(new Object(), queue);
+        tempPrint(phantom.get() == null);
+        System.gc();
+        tempPrint(queue.remove(1000) == phantom);
+    }
+
+    private static class Remover implements Runnable {
+        private final ReferenceQueue<Object> queue;
+        volatile boolean started;
+        volatile Reference<?> removed;
+
+        Remover(ReferenceQueue<Object> queue) {
+            this.queue = queue;
+        }
+
+        @Override
+        public void run() {
+            started = true;
+            try {
+                removed = queue.remove();
+            } catch (InterruptedException e) {
+                removed = null;
+            }
+        }
+    }
+
+    private static native void tempPrint(int value);
+
+    private static native void tempPrint(boolean value);
+}
@@ -0,0 +1,39 @@
+package rjvm;
+
+import java.io.IOException;
+import java.io.InputStream;
+import java.util.Properties;
+
+public class Resources {
+    public static void main(String[] args) throws IOException {
+        // Relative to the package of the class
+        Properties properties = new Properties();
+        InputStream in = Resources.class.getResourceAsStream("resources.properties");
+        properties.load(in);
+        in.close();
+        tempPrint(properties.getProperty("greeting"));
+        tempPrint(Integer.parseInt(properties.getProperty("answer")));
+
+        // Absolute, and from a jar
+        InputStream manifest = Resources.class.getResourceAsStream("/META-INF/MANIFEST.MF");
+        byte[] header = new byte[8];
+        tempPrint(manifest.read(header));
+        char[] chars = new char[header.length];
+        for (int i = 0; i < header.length; ++i) {
+            chars[i] = (char) header[i];
+        }
+        tempPrint(new String(chars));
+
+        InputStream viaLoader = ClassLoader.getSystemResourceAsStream("rjvm/resources.properties");
+        tempPrint(viaLoader.available());
+
+        tempPrint(Resources.class.getResourceAsStream("missing.properties") == null);
+        tempPrint(ClassLoader.getSystemResourceAsStream("rjvm") == null);
+    }
+
+    private static native void tempPrint(String value);
+
+    private static native void tempPrint(int value);
+
+    private static native void tempPrint(boolean value);
+}
@@ -0,0 +1,53 @@
+package rjvm;
+
+public class RuntimeExceptions {
+    public static void main(String[] args) {
+        int zero = 0;
+        try {
+            tempPrint("" + 1 / zero);
+        } catch (ArithmeticException e) {
+            tempPrint(e.getMessage());
+        }
+
+        try {
+            tempPrint("" + 1L % zero);
+        } catch (ArithmeticException e) {
+            tempPrint(e.getMessage());
+        }
+
+        Object object = "a string";
+        try {
+            tempPrint("" + ((Integer) object).intValue());
+        } catch (ClassCastException e) {
+            tempPrint(e.getMessage());
+        }
+
+        int[] array = new int[3];
+        try {
+            tempPrint("" + array[3]);
+        } catch (ArrayIndexOutOfBoundsException e) {
+            tempPrint(e.getMessage());
+        }
+
+        try {
+            array[-1] = 1;
+        } catch (ArrayIndexOutOfBoundsException e) {
+            tempPrint(e.getMessage());
+        }
+
+        try {
+            throwingDeeply(3);
+        } catch (RuntimeException e) {
+            tempPrint(e instanceof ArithmeticException ? "caught" : "wrong exception");
+        }
+    }
+
+    private static int throwingDeeply(int depth) {
+        if (depth == 0) {
+            return depth / depth;
+        }
+        return throwingDeeply(depth - 1);
+    }
+
+    private static native void tempPrint(String value);
+}
@@ -0,0 +1,43 @@
+package rjvm;
+
+public class ShutdownHooks {
+    public static void main(String[] args) {
+        Runtime runtime = Runtime.getRuntime();
+        Thread hook = new Thread() {
+            @Override
+            public void run() {
+                tempPrint("hook");
+                try {
+                    Runtime.getRuntime().addShutdownHook(new Thread());
+                } catch (IllegalStateException e) {
+                    tempPrint(e.getMessage());
+                }
+            }
+        };
+        runtime.addShutdownHook(hook);
+        try {
+            runtime.addShutdownHook(hook);
+        } catch (IllegalArgumentException e) {
+            tempPrint(e.getMessage());
+        }
+
+        Thread removedHook = new Thread() {
+            @Override
+            public void run() {
+                tempPrint("removed hook");
+            }
+        };
+        runtime.addShutdownHook(removedHook);
+        tempPrint(runtime.removeShutdownHook(removedHook));
+        tempPrint(runtime.removeShutdownHook(removedHook));
+
+        tempPrint("main done");
+        if (args.length > 0) {
+            throw new IllegalStateException(args[0]);
+        }
+    }
+
+    private static native void tempPrint(String value);
+
+    private static native void tempPrint(boolean value);
+}
@@ -0,0 +1,128 @@
+package rjvm;
+
+public class SleepInterrupt {
+    public static void main(String[] args) throws InterruptedException {
+        sleep();
+        interruptStatus();
+        interruptSleepingThread();
+        interruptWaitingThread();
+        timedJoin();
+
+        // Interrupting a thread that has not been started has no effect
+        Thread unstarted = new Thread(new Sleeper());
+        unstarted.interrupt();
+        tempPrint(unstarted.isInterrupted());
+    }
+
+    private static void sleep() throws InterruptedException {
+        long start = System.currentTimeMillis();
+        Thread.sleep(20);
+        tempPrint(System.currentTimeMillis() - start >= 20);
+        try {
+            Thread.sleep(-1);
+        } catch (IllegalArgumentException e) {
+            tempPrint(e.getMessage());
+        }
+    }
+
+    private static void interruptStatus() {
+        Thread.currentThread().interrupt();
+        tempPrint(Thread.interrupted());
+        tempPrint(Thread.interrupted());
+
+        // Blocking methods throw if the thread has been interrupted before, clearing the status
+        Thread.currentThread().interrupt();
+        try {
+            Thread.sleep(1000);
+        } catch (InterruptedException e) {
+            tempPrint(e.getMessage());
+        }
+        tempPrint(Thread.currentThread().isInterrupted());
+
+        Object lock = new Object();
+        Thread.currentThread().interrupt();
+        synchronized (lock) {
+            try {
+                lock.wait();
+            } catch (InterruptedException e) {
+                tempPrint(e.getMessage() == null);
+            }
+        }
+    }
+
+    private static void interruptSleepingThread() throws InterruptedException {
+        Sleeper sleeper = new Sleeper();
+        Thread thread = new Thread(sleeper);
+        long start = System.currentTimeMillis();
+        thread.start();
+        while (!sleeper.started) {
+            Thread.yield();
+        }
+        thread.interrupt();
+        thread.join();
+        tempPrint(sleeper.interrupted);
+        tempPrint(System.currentTimeMillis() - start < 5000);
+    }
+
+    private static void interruptWaitingThread() throws InterruptedException {
+        Waiter waiter = new Waiter();
+        Thread thread = new Thread(waiter);
+        thread.start();
+        while (true) {
+            synchronized (waiter) {
+                if (waiter.waiting) {
+                    break;
+                }
+            }
+        }
+        thread.interrupt();
+        thread.join();
+        tempPrint(waiter.heldLockWhenInterrupted);
+    }
+
+    private static void timedJoin() throws InterruptedException {
+        Thread thread = new Thread(new Sleeper());
+        thread.start();
+        thread.join(20);
+        tempPrint(thread.isAlive());
+        thread.interrupt();
+        thread.join();
+        tempPrint(thread.isAlive());
+    }
+
+    private static class Sleeper implements Runnable {
+        volatile boolean started;
+        volatile boolean interrupted;
+
+        @Override
+        public void run() {
+            started = true;
+            try {
+                Thread.sleep(10000);
+            } catch (InterruptedException e) {
+                interrupted = true;
+            }
+        }
+    }
+
+    private static class Waiter implements Runnable {
+        boolean waiting;
+        volatile boolean heldLockWhenInterrupted;
+
+        @Override
+        public void run() {
+            synchronized (this) {
+                waiting = true;
+                try {
+                    wait();
+                } catch (InterruptedException e) {
+                    heldLockWhenInterrupted = Thread.holdsLock(this);
+                }
+            }
+        }
+    }
+
+    private static native void tempPrint(boolean value);
+
+    private static native void tempPrint(String value);
+}
@@ -0,0 +1,95 @@
+package rjvm;
+
+import java.io.DataInputStream;
+import java.io.DataOutputStream;
+import java.io.IOException;
+import java.io.InputStream;
+import java.net.ConnectException;
+import java.net.InetAddress;
+import java.net.ServerSocket;
+import java.net.Socket;
+import java.net.SocketException;
+import java.net.SocketTimeoutException;
+import java.net.UnknownHostException;
+
+public class Sockets {
+    public static void main(String[] args) throws IOException {
+        InetAddress loopback = InetAddress.getByName("127.0.0.1");
+        ServerSocket server = new ServerSocket(0, 1, loopback);
+        int port = server.getLocalPort();
+        tempPrint(port > 0);
+
+        // The connection is queued until it gets accepted
+        Socket client = new Socket(loopback, port);
+        Socket accepted = server.accept();
+        tempPrint(accepted.getInetAddress().getHostAddress());
+        tempPrint(accepted.getLocalPort() == port);
+        tempPrint(accepted.getPort() == client.getLocalPort());
+
+        DataOutputStream out = new DataOutputStream(client.getOutputStream());
+        out.writeInt(1234);
+        out.write(new byte[] {1, 2, 3});
+        out.flush();
+        DataInputStream in = new DataInputStream(accepted.getInputStream());
+        tempPrint(in.readInt());
+        byte[] buffer = new byte[8];
+        tempPrint(in.read(buffer, 1, 7));
+        tempPrint(buffer[1] + buffer[2] + buffer[3]);
+
+        accepted.getOutputStream().write(42);
+        tempPrint(client.getInputStream().read());
+
+        accepted.setSoTimeout(10);
+        try {
+            in.read();
+        } catch (SocketTimeoutException e) {
+            tempPrint(e.getMessage());
+        }
+        client.shutdownOutput();
+        tempPrint(in.read());
+
+        client.close();
+        accepted.close();
+        tempPrint(client.isClosed());
+        try {
+            accepted.getInputStream();
+        } catch (SocketException e) {
+            tempPrint(e.getMessage());
+        }
+
+        server.setSoTimeout(10);
+        try {
+            server.accept();
+        } catch (SocketTimeoutException e) {
+            tempPrint(e.getMessage());
+        }
+        server.close();
+        try {
+            new Socket(loopback, port);
+        } catch (ConnectException e) {
+            tempPrint(e.getMessage());
+        }
+    }
+
+    public static void withoutNetwork() throws IOException {
+        InetAddress address = InetAddress.getByName("rjvm.test");
+        tempPrint(address.getHostName());
+        tempPrint(address.getHostAddress());
+        try {
+            InetAddress.getByName("unknown.test");
+        } catch (UnknownHostException e) {
+            tempPrint(e.getMessage());
+        }
+        try {
+            new Socket(address, 80);
+        } catch (SocketException e) {
+            tempPrint(e.getMessage());
+        }
+    }
+
+    private static native void tempPrint(String value);
+
+    private static native void tempPrint(int value);
+
+    private static native void tempPrint(boolean value);
+}
@@ -0,0 +1,40 @@
+package rjvm;
+
+public class StackOverflow {
+    private static int depth;
+
+    public static void main(String[] args) {
+        try {
+            recurse();
+        } catch (StackOverflowError e) {
+            tempPrint(depth);
+            tempPrint(e.getMessage() == null ? 1 : 0);
+        }
+
+        // Once the error has been caught, the stack can be used again
+        depth = 0;
+        try {
+            recurseThroughConstructors();
+        } catch (StackOverflowError e) {
+            tempPrint(depth);
+        }
+    }
+
+    private static void recurse() {
+        depth++;
+        recurse();
+    }
+
+    private static void recurseThroughConstructors() {
+        new Recursive();
+    }
+
+    static class Recursive {
+        Recursive() {
+            depth++;
+            new Recursive();
+        }
+    }
+
+    private static native void tempPrint(int value);
+}
@@ -0,0 +1,52 @@
+package rjvm;
+
+import java.io.DataInputStream;
+import java.io.IOException;
+import java.io.InputStream;
+
+public class StandardInput {
+    public static void main(String[] args) throws IOException {
+        InputStream in = System.in;
+        tempPrint(in.read());
+
+        byte[] buffer = new byte[8];
+        int read = in.read(buffer, 2, 4);
+        tempPrint(read);
+        for (int i = 2; i < 2 + read; ++i) {
+            tempPrint(buffer[i]);
+        }
+
+        tempPrint(in.skip(2));
+        tempPrint(in.markSupported());
+        try {
+            in.read(buffer, 7, 2);
+        } catch (IndexOutOfBoundsException e) {
+            tempPrint(true);
+        }
+
+        DataInputStream data = new DataInputStream(in);
+        tempPrint(data.readByte());
+        tempPrint(data.read());
+
+        int remaining = 0;
+        while (in.read() != -1) {
+            ++remaining;
+        }
+        tempPrint(remaining);
+        tempPrint(in.read(buffer));
+
+        try {
+            in.reset();
+        } catch (IOException e) {
+            tempPrint(e.getMessage());
+        }
+    }
+
+    private static native void tempPrint(String value);
+
+    private static native void tempPrint(int value);
+
+    private static native void tempPrint(long value);
+
+    private static native void tempPrint(boolean value);
+}
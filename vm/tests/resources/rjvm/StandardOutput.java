@@ -0,0 +1,57 @@
+package rjvm;
+
+import java.io.PrintStream;
+
+public class StandardOutput {
+    public static void main(String[] args) {
+        System.out.println("Hello, world!");
+        System.out.print(42);
+        System.out.print(' ');
+        System.out.print(-7L);
+        System.out.print(' ');
+        System.out.print(true);
+        System.out.print(' ');
+        System.out.print(1.5);
+        System.out.print(' ');
+        System.out.println(0.25f);
+        System.out.println(new char[] {'c', 'h', 'a', 'r', 's'});
+        System.out.println((String) null);
+        System.out.println((Object) null);
+        System.out.println(new Point(1, 2));
+        System.out.println("café ☃");
+        System.out.append('a').append("bc").println();
+        System.out.write('!');
+        System.out.write(new byte[] {'x', 'y', 'z'}, 1, 2);
+        System.out.println();
+        System.out.flush();
+
+        System.err.println("to stderr");
+
+        try {
+            System.out.write(new byte[2], 1, 2);
+        } catch (IndexOutOfBoundsException e) {
+            System.err.println("out of bounds");
+        }
+
+        PrintStream out = System.out;
+        System.setOut(System.err);
+        System.out.println("redirected");
+        System.setOut(out);
+        System.out.println(System.out.checkError());
+    }
+
+    private static class Point {
+        private final int x;
+        private final int y;
+
+        Point(int x, int y) {
+            this.x = x;
+            this.y = y;
+        }
+
+        @Override
+        public String toString() {
+            return "(" + x + ", " + y + ")";
+        }
+    }
+}
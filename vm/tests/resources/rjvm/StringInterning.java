@@ -0,0 +1,37 @@
+package rjvm;
+
+public class StringInterning {
+    static class Other {
+        static String hello() {
+            return "hello";
+        }
+    }
+
+    public static void main(String[] args) {
+        // The same literal, in different classes or evaluated more than once
+        tempPrint("hello" == Other.hello());
+        Object first = null;
+        for (int i = 0; i < 3; i++) {
+            Object current = "loop";
+            tempPrint(first == null || first == current);
+            first = current;
+        }
+
+        // Strings created at runtime are distinct objects, unless interned
+        String created = new String("hello");
+        tempPrint(created == "hello");
+        tempPrint(created.intern() == "hello");
+        tempPrint(created.intern() == created);
+
+        // Interning a string with no canonical instance yet makes it the canonical one
+        String runtime = new String(new char[] {'x', 'y', 'z'});
+        tempPrint(runtime.intern() == runtime);
+        tempPrint(xyzLiteral() == runtime);
+    }
+
+    private static String xyzLiteral() {
+        return "xyz";
+    }
+
+    private static native void tempPrint(boolean value);
+}
@@ -0,0 +1,39 @@
+package rjvm;
+
+// Modern and Legacy are replaced after compilation by versions whose super.value() refers to
+// GrandParent rather than Parent, like old compilers could emit. Only Modern has ACC_SUPER.
+public class SuperCalls {
+    public static void main(String[] args) {
+        tempPrint(new Modern().value());
+        tempPrint(new Legacy().value());
+    }
+
+    static class GrandParent {
+        int value() {
+            return 1;
+        }
+    }
+
+    static class Parent extends GrandParent {
+        @Override
+        int value() {
+            return 2;
+        }
+    }
+
+    static class Modern extends Parent {
+        @Override
+        int value() {
+            return 100 + super.value();
+        }
+    }
+
+    static class Legacy extends Parent {
+        @Override
+        int value() {
+            return 100 + super.value();
+        }
+    }
+
+    private static native void tempPrint(int value);
+}
@@ -0,0 +1,53 @@
+package rjvm;
+
+public class Switches {
+    public static void main(String[] args) {
+        for (int i = -3; i <= 4; i++) {
+            tempPrint(dense(i));
+        }
+        int[] sparseKeys = {Integer.MIN_VALUE, -1000, 0, 7, 1000, 123456, Integer.MAX_VALUE, 8};
+        for (int key : sparseKeys) {
+            tempPrint(sparse(key));
+        }
+    }
+
+    // Compiled to a tableswitch
+    private static int dense(int value) {
+        switch (value) {
+            case -2:
+                return 20;
+            case -1:
+                return 10;
+            case 0:
+                return 0;
+            case 1:
+                return -10;
+            case 3:
+                return -30;
+            default:
+                return 99;
+        }
+    }
+
+    // Compiled to a lookupswitch
+    private static int sparse(int value) {
+        switch (value) {
+            case Integer.MIN_VALUE:
+                return 1;
+            case -1000:
+                return 2;
+            case 7:
+                return 3;
+            case 1000:
+                return 4;
+            case 123456:
+                return 5;
+            case Integer.MAX_VALUE:
+                return 6;
+            default:
+                return 0;
+        }
+    }
+
+    private static native void tempPrint(int value);
+}
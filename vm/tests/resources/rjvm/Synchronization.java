@@ -0,0 +1,76 @@
+package rjvm;
+
+// The nested class Unbalanced is replaced after compilation by a version generated by
+// generators/GenerateUnbalancedMonitors.java, since javac always balances the monitors
+public class Synchronization {
+    public static void main(String[] args) {
+        Synchronization object = new Synchronization();
+        tempPrint(Thread.holdsLock(object));
+        object.synchronizedMethod();
+        tempPrint(Thread.holdsLock(object));
+
+        // Monitors are reentrant
+        synchronized (object) {
+            object.synchronizedMethod();
+            tempPrint(Thread.holdsLock(object));
+        }
+        tempPrint(Thread.holdsLock(object));
+
+        // The monitor is released when a synchronized method throws
+        try {
+            object.synchronizedMethodThatThrows();
+        } catch (IllegalStateException e) {
+            tempPrint(Thread.holdsLock(object));
+        }
+
+        // Likewise for synchronized blocks, thanks to the handlers generated by javac
+        try {
+            synchronized (object) {
+                throw new IllegalStateException();
+            }
+        } catch (IllegalStateException e) {
+            tempPrint(Thread.holdsLock(object));
+        }
+
+        try {
+            Unbalanced.exitWithoutEnter(object);
+        } catch (IllegalMonitorStateException e) {
+            tempPrint("exit without enter");
+        }
+        Unbalanced unbalanced = new Unbalanced();
+        try {
+            unbalanced.exitOwnMonitor();
+        } catch (IllegalMonitorStateException e) {
+            tempPrint("released twice");
+        }
+        tempPrint(Thread.holdsLock(unbalanced));
+
+        try {
+            Thread.holdsLock(null);
+        } catch (NullPointerException e) {
+            tempPrint("npe");
+        }
+    }
+
+    private synchronized void synchronizedMethod() {
+        tempPrint(Thread.holdsLock(this));
+    }
+
+    private synchronized void synchronizedMethodThatThrows() {
+        throw new IllegalStateException();
+    }
+
+    static class Unbalanced {
+        // Exits the monitor of the object without having entered it
+        static void exitWithoutEnter(Object object) {
+        }
+
+        // Exits the monitor of this, so that the return cannot release it
+        synchronized void exitOwnMonitor() {
+        }
+    }
+
+    private static native void tempPrint(boolean value);
+
+    private static native void tempPrint(String value);
+}
@@ -0,0 +1,42 @@
+package rjvm;
+
+public class SystemExit {
+    public static void main(final String[] args) throws InterruptedException {
+        Runtime.getRuntime().addShutdownHook(new Thread() {
+            @Override
+            public void run() {
+                tempPrint("hook");
+            }
+        });
+
+        String mode = args[0];
+        if (mode.equals("thread")) {
+            Thread thread = new Thread() {
+                @Override
+                public void run() {
+                    System.exit(7);
+                }
+            };
+            thread.start();
+            thread.join();
+            // The main thread is stopped before it gets here
+            tempPrint("still running");
+        }
+
+        try {
+            tempPrint("exiting");
+            if (mode.equals("halt")) {
+                Runtime.getRuntime().halt(5);
+            } else {
+                System.exit(3);
+            }
+        } catch (Throwable t) {
+            tempPrint("caught");
+        } finally {
+            tempPrint("finally");
+        }
+        tempPrint("after exit");
+    }
+
+    private static native void tempPrint(String value);
+}
@@ -0,0 +1,38 @@
+package rjvm;
+
+public class SystemProperties {
+    public static void main(String[] args) {
+        tempPrint(System.getProperty("embedder.property"));
+        tempPrint(System.getProperty("java.version"));
+        tempPrint(System.getProperty("file.separator"));
+        tempPrint(System.getProperty("line.separator").equals(System.lineSeparator()));
+        tempPrint(System.getProperty("missing.property") == null);
+        tempPrint(System.getProperty("missing.property", "fallback"));
+
+        tempPrint(System.setProperty("java.property", "first") == null);
+        tempPrint(System.setProperty("java.property", "second"));
+        tempPrint(System.getProperty("java.property"));
+        tempPrint(System.clearProperty("java.property"));
+        tempPrint(System.getProperty("java.property") == null);
+
+        tempPrint(Integer.getInteger("number.property", 0));
+        tempPrint(Boolean.getBoolean("boolean.property"));
+
+        try {
+            System.getProperty(null);
+        } catch (NullPointerException e) {
+            tempPrint(e.getMessage());
+        }
+        try {
+            System.getProperty("");
+        } catch (IllegalArgumentException e) {
+            tempPrint(e.getMessage());
+        }
+    }
+
+    private static native void tempPrint(String value);
+
+    private static native void tempPrint(int value);
+
+    private static native void tempPrint(boolean value);
+}
@@ -0,0 +1,133 @@
+package rjvm;
+
+public class Threads {
+    public static void main(String[] args) throws InterruptedException {
+        tempPrint(Thread.currentThread().getName());
+
+        // Both threads update the counter, whose methods are synchronized
+        Counter counter = new Counter();
+        Thread first = new Thread(new Incrementer(counter), "first");
+        Thread second = new Thread(new Incrementer(counter), "second");
+        tempPrint(first.isAlive());
+        first.start();
+        second.start();
+        // Printed later, since the started threads may print before main gets to run again
+        boolean aliveAfterStart = first.isAlive();
+        first.join();
+        second.join();
+        tempPrint(aliveAfterStart);
+        tempPrint(first.isAlive());
+        tempPrint(counter.get());
+
+        // The main thread busy-waits for the spinner, so it must be preempted
+        Spinner spinner = new Spinner();
+        Thread spinnerThread = new Thread(spinner);
+        spinnerThread.start();
+        while (!spinner.started) {
+        }
+        spinner.stop = true;
+        spinnerThread.join();
+        tempPrint("spinner stopped");
+
+        // The main thread must wait for the holder to release the lock
+        LockHolder holder = new LockHolder();
+        Thread holderThread = new Thread(holder);
+        holderThread.start();
+        while (!holder.locked) {
+        }
+        synchronized (holder) {
+            tempPrint(holder.value);
+        }
+        holderThread.join();
+
+        // An uncaught exception terminates only its thread
+        Thread failing = new Thread(new Failing());
+        failing.start();
+        failing.join();
+        tempPrint(failing.isAlive());
+
+        try {
+            failing.start();
+        } catch (IllegalThreadStateException e) {
+            tempPrint("started twice");
+        }
+
+        // The vm does not wait for daemon threads, which are stopped when main completes
+        Thread daemon = new Thread(new Spinner());
+        daemon.setDaemon(true);
+        daemon.start();
+        tempPrint("main done");
+    }
+
+    private static class Counter {
+        private int value;
+
+        public synchronized void increment() {
+            // Reads and writes in separate steps, so that an unsynchronized version
+            // would lose updates when the threads are switched in between
+            int current = value;
+            Thread.yield();
+            value = current + 1;
+        }
+
+        public synchronized int get() {
+            return value;
+        }
+    }
+
+    private static class Incrementer implements Runnable {
+        private final Counter counter;
+
+        Incrementer(Counter counter) {
+            this.counter = counter;
+        }
+
+        @Override
+        public void run() {
+            tempPrint(Thread.currentThread().getName());
+            for (int i = 0; i < 100; i++) {
+                counter.increment();
+            }
+        }
+    }
+
+    private static class Spinner implements Runnable {
+        volatile boolean started;
+        volatile boolean stop;
+
+        @Override
+        public void run() {
+            started = true;
+            while (!stop) {
+            }
+        }
+    }
+
+    private static class LockHolder implements Runnable {
+        volatile boolean locked;
+        int value;
+
+        @Override
+        public void run() {
+            synchronized (this) {
+                locked = true;
+                for (int i = 0; i < 20000; i++) {
+                }
+                value = 1;
+            }
+        }
+    }
+
+    private static class Failing implements Runnable {
+        @Override
+        public void run() {
+            throw new IllegalStateException("failing");
+        }
+    }
+
+    private static native void tempPrint(int value);
+
+    private static native void tempPrint(boolean value);
+
+    private static native void tempPrint(String value);
+}
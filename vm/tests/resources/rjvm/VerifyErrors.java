@@ -0,0 +1,73 @@
+package rjvm;
+
+// The nested classes are replaced after compilation by invalid versions generated
+// by generators/GenerateInvalidBytecode.java, which the verifier must reject
+public class VerifyErrors {
+    public static void main(String[] args) {
+        try {
+            tempPrint(BadStackType.run());
+        } catch (VerifyError e) {
+            tempPrint(e.getMessage());
+        }
+        try {
+            tempPrint(StackUnderflow.run());
+        } catch (VerifyError e) {
+            tempPrint(e.getMessage());
+        }
+        try {
+            tempPrint(MissingBranchFrame.run());
+        } catch (VerifyError e) {
+            tempPrint(e.getMessage());
+        }
+        try {
+            tempPrint(InconsistentLocals.run());
+        } catch (VerifyError e) {
+            tempPrint(e.getMessage());
+        }
+        try {
+            tempPrint(UninitializedReturn.run());
+        } catch (VerifyError e) {
+            tempPrint(e.getMessage());
+        }
+        // A class that failed verification stays unusable
+        try {
+            tempPrint(BadStackType.run());
+        } catch (VerifyError e) {
+            tempPrint(e.getMessage());
+        }
+    }
+
+    static class BadStackType {
+        static int run() {
+            return 1;
+        }
+    }
+
+    static class StackUnderflow {
+        static int run() {
+            return 2;
+        }
+    }
+
+    static class MissingBranchFrame {
+        static int run() {
+            return 3;
+        }
+    }
+
+    static class InconsistentLocals {
+        static int run() {
+            return 4;
+        }
+    }
+
+    static class UninitializedReturn {
+        static int run() {
+            return 5;
+        }
+    }
+
+    private static native void tempPrint(int value);
+
+    private static native void tempPrint(String value);
+}
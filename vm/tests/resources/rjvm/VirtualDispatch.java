@@ -0,0 +1,63 @@
+package rjvm;
+
+import rjvm.dispatch.PackageBase;
+
+public class VirtualDispatch {
+    public static void main(String[] args) {
+        Sub sub = new SubSub();
+        tempPrint(sub.packageValue());
+        tempPrint(sub.publicValue());
+        tempPrint(sub.callPackageValue());
+        PackageBase base = sub;
+        tempPrint(base.publicValue());
+
+        // Methods inherited from interfaces are found even without a table entry
+        Impl impl = new OverridingImpl();
+        tempPrint(impl.greet());
+        tempPrint(new Impl().greet());
+        tempPrint(impl.toString() != null);
+    }
+
+    static class Sub extends PackageBase {
+        int packageValue() {
+            return 2;
+        }
+
+        @Override
+        public int publicValue() {
+            return 20;
+        }
+    }
+
+    static class SubSub extends Sub {
+        @Override
+        int packageValue() {
+            return 3;
+        }
+    }
+
+    interface Greeter {
+        int greet();
+    }
+
+    static abstract class AbstractGreeter implements Greeter {
+    }
+
+    static class Impl extends AbstractGreeter {
+        @Override
+        public int greet() {
+            return 100;
+        }
+    }
+
+    static class OverridingImpl extends Impl {
+        @Override
+        public int greet() {
+            return 200;
+        }
+    }
+
+    private static native void tempPrint(int value);
+
+    private static native void tempPrint(boolean value);
+}
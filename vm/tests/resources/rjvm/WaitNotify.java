@@ -0,0 +1,194 @@
+package rjvm;
+
+public class WaitNotify {
+    public static void main(String[] args) throws InterruptedException {
+        producerAndConsumer();
+        waitAndNotifyRequireTheMonitor();
+        timedWait();
+        notifyWakesOneThread();
+        waitRestoresReentrantMonitor();
+    }
+
+    private static void producerAndConsumer() throws InterruptedException {
+        Buffer buffer = new Buffer();
+        Consumer consumer = new Consumer(buffer);
+        Thread producerThread = new Thread(new Producer(buffer));
+        Thread consumerThread = new Thread(consumer);
+        consumerThread.start();
+        producerThread.start();
+        producerThread.join();
+        consumerThread.join();
+        tempPrint(consumer.sum);
+    }
+
+    private static void waitAndNotifyRequireTheMonitor() throws InterruptedException {
+        Object lock = new Object();
+        try {
+            lock.wait();
+        } catch (IllegalMonitorStateException e) {
+            tempPrint("wait");
+        }
+        try {
+            lock.notify();
+        } catch (IllegalMonitorStateException e) {
+            tempPrint("notify");
+        }
+        try {
+            lock.notifyAll();
+        } catch (IllegalMonitorStateException e) {
+            tempPrint("notifyAll");
+        }
+    }
+
+    private static void timedWait() throws InterruptedException {
+        // Nobody notifies the lock, so the wait ends when the timeout expires
+        Object lock = new Object();
+        synchronized (lock) {
+            long start = System.currentTimeMillis();
+            lock.wait(20);
+            tempPrint(System.currentTimeMillis() - start >= 20);
+        }
+        try {
+            synchronized (lock) {
+                lock.wait(-1);
+            }
+        } catch (IllegalArgumentException e) {
+            tempPrint(e.getMessage());
+        }
+    }
+
+    private static void notifyWakesOneThread() throws InterruptedException {
+        Waiters waiters = new Waiters();
+        Thread[] threads = new Thread[3];
+        for (int i = 0; i < threads.length; i++) {
+            threads[i] = new Thread(waiters);
+            threads[i].start();
+        }
+        while (true) {
+            synchronized (waiters) {
+                if (waiters.waiting == threads.length) {
+                    waiters.notify();
+                    break;
+                }
+            }
+        }
+        while (waiters.woken == 0) {
+            Thread.yield();
+        }
+        // Give the other threads a chance to (wrongly) wake up
+        for (int i = 0; i < 100; i++) {
+            Thread.yield();
+        }
+        tempPrint(waiters.woken);
+
+        synchronized (waiters) {
+            waiters.notifyAll();
+        }
+        for (Thread thread : threads) {
+            thread.join();
+        }
+        tempPrint(waiters.woken);
+    }
+
+    private static void waitRestoresReentrantMonitor() throws InterruptedException {
+        Object lock = new Object();
+        synchronized (lock) {
+            synchronized (lock) {
+                lock.wait(1);
+                tempPrint(Thread.holdsLock(lock));
+            }
+            tempPrint(Thread.holdsLock(lock));
+        }
+        tempPrint(Thread.holdsLock(lock));
+    }
+
+    /** Holds at most two values */
+    private static class Buffer {
+        private final int[] values = new int[2];
+        private int size;
+
+        public synchronized void put(int value) throws InterruptedException {
+            while (size == values.length) {
+                wait();
+            }
+            values[size++] = value;
+            notifyAll();
+        }
+
+        public synchronized int take() throws InterruptedException {
+            while (size == 0) {
+                wait();
+            }
+            int value = values[0];
+            values[0] = values[1];
+            size--;
+            notifyAll();
+            return value;
+        }
+    }
+
+    private static class Producer implements Runnable {
+        private final Buffer buffer;
+
+        Producer(Buffer buffer) {
+            this.buffer = buffer;
+        }
+
+        @Override
+        public void run() {
+            try {
+                for (int i = 1; i <= 10; i++) {
+                    buffer.put(i);
+                }
+                buffer.put(-1);
+            } catch (InterruptedException e) {
+                tempPrint("interrupted");
+            }
+        }
+    }
+
+    private static class Consumer implements Runnable {
+        private final Buffer buffer;
+        int sum;
+
+        Consumer(Buffer buffer) {
+            this.buffer = buffer;
+        }
+
+        @Override
+        public void run() {
+            try {
+                int value;
+                while ((value = buffer.take()) >= 0) {
+                    sum += value;
+                }
+            } catch (InterruptedException e) {
+                tempPrint("interrupted");
+            }
+        }
+    }
+
+    private static class Waiters implements Runnable {
+        int waiting;
+        volatile int woken;
+
+        @Override
+        public void run() {
+            synchronized (this) {
+                waiting++;
+                try {
+                    wait();
+                } catch (InterruptedException e) {
+                    tempPrint("interrupted");
+                }
+                woken++;
+            }
+        }
+    }
+
+    private static native void tempPrint(int value);
+
+    private static native void tempPrint(boolean value);
+
+    private static native void tempPrint(String value);
+}
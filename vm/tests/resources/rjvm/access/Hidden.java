@@ -0,0 +1,8 @@
+package rjvm.access;
+
+// Replaced after compilation by a package-private version, in evolved/rjvm/access
+public class Hidden {
+    public static int value() {
+        return 6;
+    }
+}
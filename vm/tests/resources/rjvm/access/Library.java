@@ -0,0 +1,23 @@
+package rjvm.access;
+
+// The version of the library that AccessControl is compiled against. After compilation, it is
+// replaced by the one in evolved/rjvm/access, which restricts the access to some of its members.
+public class Library {
+    public int restrictedField = 1;
+
+    public static int keptMethod() {
+        return 2;
+    }
+
+    public static int restrictedMethod() {
+        return 3;
+    }
+
+    public int restrictedInstanceMethod() {
+        return 4;
+    }
+
+    public int protectedMethod() {
+        return 5;
+    }
+}
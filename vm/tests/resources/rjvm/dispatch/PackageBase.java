@@ -0,0 +1,17 @@
+package rjvm.dispatch;
+
+// Its package-private method cannot be overridden by the subclasses in VirtualDispatch,
+// which belong to another package
+public class PackageBase {
+    int packageValue() {
+        return 1;
+    }
+
+    public int publicValue() {
+        return 10;
+    }
+
+    public int callPackageValue() {
+        return packageValue();
+    }
+}
@@ -0,0 +1,4 @@
+package rjvm.verification;
+
+// Deleted after compilation, to model a dependency missing at runtime
+public class Dependency {}
@@ -0,0 +1,3 @@
+package rjvm.verification;
+
+public class ExtendsMissingClass extends Dependency {}
@@ -0,0 +1,4 @@
+package rjvm.verification;
+
+// Its class file version is set to one that the vm does not support
+public class NewerVersion {}
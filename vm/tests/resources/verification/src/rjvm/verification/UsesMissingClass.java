@@ -0,0 +1,7 @@
+package rjvm.verification;
+
+public class UsesMissingClass {
+    public static Object create() {
+        return new Dependency[] {new Dependency()};
+    }
+}
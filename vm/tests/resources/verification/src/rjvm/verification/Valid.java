@@ -0,0 +1,12 @@
+package rjvm.verification;
+
+import java.util.ArrayList;
+import java.util.List;
+
+public class Valid {
+    public static int size() {
+        List<String> strings = new ArrayList<String>();
+        strings.add("valid");
+        return strings.size();
+    }
+}
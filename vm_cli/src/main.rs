@@ -1,17 +1,18 @@
-use clap::Parser;
+use clap::{ArgAction, Parser};
 
 use rjvm_vm::{
-    array::Array,
-    array_entry_type::ArrayEntryType,
-    call_stack::CallStack,
-    class_and_method::ClassAndMethod,
-    exceptions::MethodCallFailed,
-    java_objects_creation::new_java_lang_string_object,
-    value::Value,
-    vm::{Vm, DEFAULT_MAX_MEMORY_MB_STR, ONE_MEGABYTE},
+    class::Class,
+    class_load_observer::{ClassLoadObserver, ClassSource},
+    exceptions::{MethodCallFailed, RunMainError},
+    io::{StdJvmIo, StdJvmNet},
+    run_config::RunConfig,
+    vm::{DEFAULT_MAX_MEMORY_MB_STR, ONE_MEGABYTE},
+    vm_builder::VmBuilder,
     vm_error::VmError,
 };
 
+/// Runs a java program, in the spirit of the `java` executable. The options of `java`
+/// `-cp`, `-classpath`, `-Xbootclasspath:`, `-verbose:class` and `-jar` are accepted too.
 #[derive(Parser, Debug)]
 #[command(author, version, about, long_about = None)]
 struct Args {
@@ -19,21 +20,52 @@ struct Args {
     #[arg(short, long)]
     classpath: Option<String>,
 
-    /// Class name to execute
-    class_name: String,
+    /// Boot class path, holding the JDK classes, e.g. `rt.jar`. Use colon (:) as separator for
+    /// entries. If missing, the JDK classes are searched in the class path
+    #[arg(long)]
+    boot_classpath: Option<String>,
+
+    /// Runs the program packaged in the given jar, whose manifest names the main class.
+    /// All the positional arguments are then passed to the program
+    #[arg(long)]
+    jar: Option<String>,
+
+    /// Sets a system property, e.g. `-Dname=value`
+    #[arg(short = 'D', value_name = "NAME=VALUE")]
+    properties: Vec<String>,
 
     /// Maximum memory to use in MB
     #[arg(short, long, default_value = DEFAULT_MAX_MEMORY_MB_STR)]
     maximum_mb_of_memory: usize,
 
+    /// Logs more details; can be repeated up to three times. The `RUST_LOG` environment
+    /// variable, if set, takes precedence
+    #[arg(short, long, action = ArgAction::Count, conflicts_with = "quiet")]
+    verbose: u8,
+
+    /// Logs only errors
+    #[arg(short, long)]
+    quiet: bool,
+
+    /// Prints each class when it is defined, along with where it was loaded from
+    #[arg(long)]
+    verbose_class: bool,
+
+    /// Class name to execute, e.g. `com.example.Main`
+    #[arg(required_unless_present = "jar")]
+    class_name: Option<String>,
+
     /// Java program arguments
     java_program_arguments: Vec<String>,
 }
 
+/// Exit code used when the program cannot be launched or fails, like the `java` launcher does
+const FAILURE_EXIT_CODE: i32 = 1;
+
 fn main() {
-    let args = Args::parse();
+    let args = Args::parse_from(translate_java_style_options(std::env::args()));
     env_logger::init_from_env(
-        env_logger::Env::default().filter_or(env_logger::DEFAULT_FILTER_ENV, "info"),
+        env_logger::Env::default().filter_or(env_logger::DEFAULT_FILTER_ENV, log_level(&args)),
     );
 
     let result = run(args);
@@ -41,83 +73,200 @@ fn main() {
         Ok(exit_code) => std::process::exit(exit_code),
         Err(err) => {
             eprintln!("{err}");
-            std::process::exit(-1);
+            std::process::exit(FAILURE_EXIT_CODE);
         }
     }
 }
 
-fn append_classpath(vm: &mut Vm, args: &Args) -> Result<(), String> {
-    if let Some(classpath) = &args.classpath {
-        vm.append_class_path(classpath)
-            .map_err(|err| err.to_string())?;
+/// Translates the options written in the style of the `java` launcher, which clap does not
+/// support since they are made of a single dash followed by a word, into their long form.
+/// Everything after the main class, or after the jar, is passed to the program untouched.
+fn translate_java_style_options(args: impl IntoIterator<Item = String>) -> Vec<String> {
+    let mut args = args.into_iter();
+    let mut translated: Vec<String> = args.next().into_iter().collect();
+    let mut main_class = None;
+    while let Some(arg) = args.next() {
+        if let Some(boot_class_path) = arg.strip_prefix("-Xbootclasspath:") {
+            translated.push("--boot-classpath".to_string());
+            translated.push(boot_class_path.to_string());
+            continue;
+        }
+        let (option, takes_value) = match arg.as_str() {
+            "-verbose:class" => ("--verbose-class", false),
+            "-cp" | "-classpath" | "--class-path" => ("--classpath", true),
+            "-jar" | "--jar" => ("--jar", true),
+            "-c" | "--classpath" | "--boot-classpath" | "-D" | "-m" | "--maximum-mb-of-memory" => {
+                (arg.as_str(), true)
+            }
+            "--" => break,
+            _ if arg.starts_with('-') => (arg.as_str(), false),
+            _ => {
+                main_class = Some(arg);
+                break;
+            }
+        };
+        let is_jar = option == "--jar";
+        translated.push(option.to_string());
+        if takes_value {
+            translated.extend(args.next());
+        }
+        if is_jar {
+            break;
+        }
     }
-    Ok(())
+    translated.push("--".to_string());
+    translated.extend(main_class);
+    translated.extend(args);
+    translated
 }
 
-fn resolve_class_and_main_method<'a>(
-    vm: &mut Vm<'a>,
-    args: &Args,
-) -> Result<(&'a mut CallStack<'a>, ClassAndMethod<'a>), String> {
-    let call_stack = vm.allocate_call_stack();
-    let main_method = vm
-        .resolve_class_method(
-            call_stack,
-            &args.class_name,
-            "main",
-            "([Ljava/lang/String;)V",
-        )
-        .map_err(|v| match v {
-            MethodCallFailed::InternalError(VmError::ClassNotFoundException(name)) => {
-                format!("class not found: {name}")
-            }
-            MethodCallFailed::InternalError(VmError::MethodNotFoundException(..)) => {
-                "class does not contain a valid <main> method".to_string()
-            }
-            _ => format!("unexpected error: {:?}", v),
-        })?;
-    Ok((call_stack, main_method))
+fn log_level(args: &Args) -> &'static str {
+    if args.quiet {
+        return "error";
+    }
+    match args.verbose {
+        0 => "warn",
+        1 => "info",
+        2 => "debug",
+        _ => "trace",
+    }
+}
+
+/// Prints the classes as they are defined, in the format of `java -verbose:class`
+struct VerboseClass;
+
+impl ClassLoadObserver for VerboseClass {
+    fn class_defined(&self, class: &Class, source: &ClassSource) {
+        println!("[Loaded {} from {source}]", class.name.replace('/', "."));
+    }
+}
+
+fn run_config(args: &Args) -> Result<RunConfig, String> {
+    let mut program_arguments = args.java_program_arguments.clone();
+    if args.jar.is_some() {
+        // There is no main class, so the first positional argument is the program's
+        program_arguments.splice(0..0, args.class_name.iter().cloned());
+    }
+    let mut run_config = RunConfig::new().with_args(program_arguments);
+    if let Ok(current_dir) = std::env::current_dir() {
+        run_config = run_config.with_working_directory(current_dir.to_string_lossy());
+    }
+    for property in args.properties.iter() {
+        let (name, value) = property.split_once('=').unwrap_or((property, ""));
+        if name.is_empty() {
+            return Err(format!("invalid system property definition: -D{property}"));
+        }
+        run_config = run_config.with_property(name, value);
+    }
+    Ok(run_config)
 }
 
 fn run(args: Args) -> Result<i32, String> {
-    let mut vm = Vm::new(args.maximum_mb_of_memory * ONE_MEGABYTE);
-    append_classpath(&mut vm, &args)?;
-
-    let (call_stack, main_method) = resolve_class_and_main_method(&mut vm, &args)?;
-
-    let main_args = allocate_java_args(&mut vm, call_stack, &args.java_program_arguments)
-        .map_err(|err| format!("{err:?}"))?;
-    let main_result = vm
-        .invoke(call_stack, main_method, None, vec![main_args])
-        .map_err(|v| format!("execution error: {:?}", v))?;
-
-    match main_result {
-        None => Ok(0),
-        Some(v) => Err(format!(
-            "<main> method should be void, but returned the value: {v:?}",
-        )),
+    let mut builder = VmBuilder::new()
+        .with_max_memory(args.maximum_mb_of_memory * ONE_MEGABYTE)
+        .with_io(StdJvmIo::new())
+        .with_net(StdJvmNet::new());
+    if let Some(boot_classpath) = &args.boot_classpath {
+        builder = builder.with_boot_class_path(boot_classpath);
+    }
+    if let Some(classpath) = &args.classpath {
+        builder = builder.with_class_path(classpath);
+    }
+    if args.verbose_class {
+        builder = builder.with_class_load_observer(VerboseClass);
+    }
+    let mut vm = builder.build().map_err(|err| err.to_string())?;
+    let run_config = run_config(&args)?;
+
+    let result = match (&args.jar, &args.class_name) {
+        (Some(jar), _) => vm.invoke_jar(jar, run_config),
+        (None, Some(class_name)) => vm.invoke_main(&class_name.replace('.', "/"), run_config),
+        (None, None) => unreachable!("clap requires the class name when there is no jar"),
+    };
+    match result {
+        Ok(()) => Ok(0),
+        Err(MethodCallFailed::VmExit(status)) => Ok(status),
+        Err(MethodCallFailed::ExceptionThrown(exception)) => {
+            // Printed like the default uncaught exception handler of the main thread does
+            let exception = vm.render_exception(&exception);
+            eprintln!("{}", RunMainError::UncaughtException(exception));
+            Ok(FAILURE_EXIT_CODE)
+        }
+        Err(MethodCallFailed::InternalError(err)) => Err(match err {
+            VmError::ClassNotFoundException(name) => format!("class not found: {name}"),
+            VmError::MethodNotFoundException(..) => {
+                "class does not contain a valid <main> method".to_string()
+            }
+            VmError::MissingMainClass(..) => err.to_string(),
+            _ => format!("execution error: {err:?}"),
+        }),
     }
 }
 
-fn allocate_java_args<'a>(
-    vm: &mut Vm<'a>,
-    call_stack: &mut CallStack<'a>,
-    command_line_args: &[String],
-) -> Result<Value<'a>, MethodCallFailed<'a>> {
-    let class_id_java_lang_string = vm.get_or_resolve_class(call_stack, "java/lang/String")?.id;
-
-    let strings: Result<Vec<Value<'a>>, MethodCallFailed<'a>> = command_line_args
-        .iter()
-        .map(|s| new_java_lang_string_object(vm, call_stack, s).map(Value::Object))
-        .collect();
-
-    let strings = strings?;
-    let array = vm.new_array(
-        ArrayEntryType::Object(class_id_java_lang_string),
-        strings.len(),
-    );
+#[cfg(test)]
+mod tests {
+    use crate::translate_java_style_options;
+
+    fn translate(args: &[&str]) -> Vec<String> {
+        translate_java_style_options(args.iter().map(|arg| arg.to_string()))
+    }
+
+    #[test]
+    fn java_style_options_are_translated_until_the_main_class() {
+        assert_eq!(
+            vec![
+                "rjvm",
+                "--classpath",
+                "a.jar:b",
+                "-Dname=value",
+                "-v",
+                "--",
+                "rjvm.Main",
+                "-cp",
+                "-v"
+            ],
+            translate(&[
+                "rjvm",
+                "-cp",
+                "a.jar:b",
+                "-Dname=value",
+                "-v",
+                "rjvm.Main",
+                "-cp",
+                "-v"
+            ])
+        );
+        assert_eq!(
+            vec!["rjvm", "--classpath", "a", "-m", "64", "--", "Main"],
+            translate(&["rjvm", "-classpath", "a", "-m", "64", "Main"])
+        );
+        assert_eq!(
+            vec![
+                "rjvm",
+                "--boot-classpath",
+                "rt.jar",
+                "--classpath",
+                "a",
+                "--",
+                "Main"
+            ],
+            translate(&["rjvm", "-Xbootclasspath:rt.jar", "-cp", "a", "Main"])
+        );
+        assert_eq!(
+            vec!["rjvm", "--verbose-class", "--", "Main", "-verbose:class"],
+            translate(&["rjvm", "-verbose:class", "Main", "-verbose:class"])
+        );
+    }
 
-    for (index, string) in strings.into_iter().enumerate() {
-        array.set_element(index, string)?;
+    #[test]
+    fn arguments_after_a_jar_are_passed_to_the_program() {
+        assert_eq!(
+            vec!["rjvm", "-q", "--jar", "app.jar", "--", "-v", "first"],
+            translate(&["rjvm", "-q", "-jar", "app.jar", "-v", "first"])
+        );
+        assert_eq!(
+            vec!["rjvm", "--jar", "app.jar", "--"],
+            translate(&["rjvm", "-jar", "app.jar"])
+        );
     }
-    Ok(Value::Object(array))
 }